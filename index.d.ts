@@ -20,6 +20,44 @@ export interface SimilarityResult {
   path: string
   /** Similarity score (0.0 to 1.0) */
   similarity: number
+  /** How the score and rank came about (only with `explain`) */
+  explanation?: SimilarityExplanation
+}
+/**
+ * Diagnostics of one vector in a `findSimilarVectors` search
+ *
+ * `similarity` is `dot_product / (query_norm * vector_norm)`, computed in f32.
+ */
+export interface SimilarityExplanation {
+  /** Dot product of the query and the vector */
+  dotProduct: number
+  /** L2 norm of the query */
+  queryNorm: number
+  /** L2 norm of the vector */
+  vectorNorm: number
+  /** Similarity threshold results had to meet */
+  threshold: number
+  /**
+   * Position among all vectors by similarity (1-based), before the
+   * threshold and `top_k` were applied
+   */
+  rank: number
+  /** Vectors that met the threshold, before truncating to `top_k` */
+  candidates: number
+  /**
+   * Filter that kept the vector out of the results: "threshold" or
+   * "top_k" (absent for returned results)
+   */
+  droppedBy?: string
+}
+/** Two vectors at least as similar as the `similarPairs` threshold */
+export interface SimilarPair {
+  /** Index of the first vector */
+  indexA: number
+  /** Index of the second vector (always greater than `index_a`) */
+  indexB: number
+  /** Cosine similarity of the two */
+  similarity: number
 }
 /** Batch embedding generation result */
 export interface BatchEmbeddingResult {
@@ -34,8 +72,15 @@ export interface BatchEmbeddingResult {
   /** Whether SIMD was used */
   usedSimd: boolean
 }
-/** Standalone function for quick similarity calculation */
-export declare function quickCosineSimilarity(vecA: Array<number>, vecB: Array<number>): number
+/**
+ * Standalone function for quick similarity calculation
+ *
+ * # Arguments
+ * * `vec_a` - First vector
+ * * `vec_b` - Second vector
+ * * `config` - Optional vector configuration (e.g. to disable SIMD)
+ */
+export declare function quickCosineSimilarity(vecA: Array<number>, vecB: Array<number>, config?: VectorConfig | undefined | null): number
 /** Benchmark vector operations performance */
 export declare function benchmarkVectorOperations(vectorSize: number, numVectors: number): Record<string, number>
 /** Configuration for file search operations */
@@ -52,6 +97,132 @@ export interface FileSearchConfig {
   excludePatterns: Array<string>
   /** File size limit in bytes (0 for no limit) */
   maxFileSize: number
+  /** Sandbox every traversal root and visited entry must satisfy */
+  sandbox?: SandboxPolicy
+  /**
+   * Cache directory stats, extension stats and duplicate scans: "mtime"
+   * revalidates by modification times, "events" keeps results until
+   * `invalidateCachedResults` (omit or "off" to disable)
+   */
+  resultCache?: string
+  /**
+   * Return files, matches and duplicate groups sorted by path, compared
+   * component by component (`a/b` before `a.txt`); matches within a file
+   * stay in line order
+   */
+  deterministic?: boolean
+  /**
+   * Only include files detected as one of these languages (e.g. "Rust",
+   * "Python"; case-insensitive). Files the name doesn't classify are
+   * read for a `#!` line or modeline.
+   */
+  languages?: Array<string>
+  /**
+   * "background" runs scans on worker threads with lowered CPU and I/O
+   * priority; "normal" (default) runs them at full speed
+   */
+  priority?: string
+  /**
+   * Scan roots on network and FUSE filesystems (NFS, SMB, sshfs, ...)
+   * sequentially, without mmap, and with mtime-revalidated result caching
+   * (default true)
+   */
+  adaptToNetworkFs?: boolean
+  /**
+   * Match patterns and excludes against NFC-normalized paths and
+   * patterns, so names in composed and decomposed form (`é` as one code
+   * point or `e` plus an accent, as HFS+ stores it) match alike (default
+   * true on macOS, false elsewhere)
+   */
+  normalizeUnicode?: boolean
+  /**
+   * List the entries scans leave out, with the reason for each, in
+   * paged results, directory stats and duplicate scans (default false)
+   */
+  reportSkipped?: boolean
+  /**
+   * With `follow_symlinks`, symlinks that may be followed to reach an
+   * entry, counting each link of a chain; deeper entries are skipped and
+   * not descended into (default 16)
+   */
+  maxSymlinkDepth?: number
+  /**
+   * Entries one walk visits before it stops, excluded ones included
+   * (omit or 0 for unlimited)
+   */
+  maxEntries?: number
+  /**
+   * Give text search results a `snippet` of at most this many characters
+   * (at least 8) centered on the match, so minified lines stay printable
+   * (omit for none)
+   */
+  snippetWidth?: number
+  /**
+   * Don't match text on lines longer than this many bytes (omit or 0 for
+   * no limit)
+   */
+  maxLineLength?: number
+  /**
+   * How text searches treat files that look minified (at least 1 KiB,
+   * with lines averaging over 200 bytes): "search" (default) searches
+   * them like other files, "skip" leaves them out, and "last" returns
+   * their matches after all others
+   */
+  minifiedFiles?: string
+  /**
+   * Only match text in one class of token, found by a lightweight lexer
+   * for the file's language: "identifier" (whole identifiers),
+   * "string" (string literals), "comment", or "code" (outside strings
+   * and comments). Files in languages without a lexer are skipped
+   */
+  tokenClass?: string
+}
+/**
+ * Per-call changes to a `FileSearchConfig`
+ *
+ * Every `FileSearch` method takes these as its last argument. Set fields
+ * replace the instance's value for that call only; the sandbox and result
+ * cache mode can't be changed per call.
+ */
+export interface FileSearchOverrides {
+  /** Maximum depth for directory traversal (-1 for unlimited) */
+  maxDepth?: number
+  /** Follow symbolic links */
+  followSymlinks?: boolean
+  /** Include hidden files (.dot files) */
+  includeHidden?: boolean
+  /** Use parallel processing */
+  useParallel?: boolean
+  /** Patterns to exclude, replacing the instance's list */
+  excludePatterns?: Array<string>
+  /** Patterns to exclude in addition to the instance's (or replaced) list */
+  addExcludePatterns?: Array<string>
+  /** File size limit in bytes (0 for no limit) */
+  maxFileSize?: number
+  /** Return results sorted by path */
+  deterministic?: boolean
+  /** Only include files detected as one of these languages */
+  languages?: Array<string>
+  /** "background" or "normal" scan priority */
+  priority?: string
+  /** Adapt scans of network and FUSE filesystems */
+  adaptToNetworkFs?: boolean
+  /** Match paths regardless of Unicode normalization form */
+  normalizeUnicode?: boolean
+  /** List skipped entries in results */
+  reportSkipped?: boolean
+  /** Symlinks that may be followed to reach an entry */
+  maxSymlinkDepth?: number
+  /** Entries one walk visits before it stops (0 for unlimited) */
+  maxEntries?: number
+  /** Characters of each matching line returned as a snippet */
+  snippetWidth?: number
+  /** Longest line searched, in bytes (0 for no limit) */
+  maxLineLength?: number
+  /** "search", "skip" or "last" for minified files */
+  minifiedFiles?: string
+  /** "identifier", "string", "comment" or "code" to match in */
+  tokenClass?: string
 }
 /** File metadata result */
 export interface FileInfo {
@@ -82,6 +253,48 @@ export interface TextSearchResult {
   lineContent: string
   /** Match text */
   matchText: string
+  /** The line cut down around the match (only with `snippet_width`) */
+  snippet?: SearchSnippet
+}
+/** Part of a matching line around the match, for display */
+export interface SearchSnippet {
+  /** At most `snippet_width` characters of the line, with "…" where it was cut */
+  text: string
+  /** Occurrences of the search text within `text` */
+  highlights: Array<SnippetHighlight>
+}
+/** An occurrence of the search text within a snippet */
+export interface SnippetHighlight {
+  /** Start offset in the snippet's text, in UTF-16 code units (JavaScript string indices) */
+  start: number
+  /** End offset in the snippet's text, in UTF-16 code units */
+  end: number
+}
+/** A page of files from `findFilesPaged` */
+export interface FilePage {
+  /** Matching files in walk order */
+  files: Array<FileInfo>
+  /** Token for the next page; absent when the walk is complete */
+  cursor?: string
+  /** How the filesystem shaped the scan */
+  strategy: ScanStrategy
+  /** Entries left out of the scan (only with `report_skipped`) */
+  skipped?: SkipReport
+  /** Whether the walk stopped at `max_entries` (set on the last page) */
+  truncated: boolean
+}
+/** A page of matches from `searchTextPaged` */
+export interface TextSearchPage {
+  /** Matches in walk order, in line order within each file */
+  results: Array<TextSearchResult>
+  /** Token for the next page; absent when the walk is complete */
+  cursor?: string
+  /** How the filesystem shaped the scan */
+  strategy: ScanStrategy
+  /** Entries left out of the scan (only with `report_skipped`) */
+  skipped?: SkipReport
+  /** Whether the walk stopped at `max_entries` (set on the last page) */
+  truncated: boolean
 }
 /** Directory statistics */
 export interface DirectoryStats {
@@ -95,160 +308,3898 @@ export interface DirectoryStats {
   largestFileSize: number
   /** Average file size */
   averageFileSize: number
+  /** Logical size of files and directories, counting hard-linked files once (`du -sb`) */
+  apparentSize: number
+  /** Bytes allocated on disk, counting hard-linked files once (`du -sB1`) */
+  diskUsage: number
+  /** Files with fewer bytes allocated than their length (sparse or compressed) */
+  sparseFileCount: number
+  /** Extra links to files already counted, left out of `apparent_size` and `disk_usage` */
+  hardLinkCount: number
+  /** Files by age of last modification (only when requested) */
+  timeline?: Array<ModifiedTimeBucket>
+  /** How the filesystem shaped the scan */
+  strategy: ScanStrategy
+  /** Entries left out of the scan (only with `report_skipped`) */
+  skipped?: SkipReport
+  /** Whether the walk stopped at `max_entries`, leaving the totals partial */
+  truncated: boolean
+}
+/** Files last modified within one age range */
+export interface ModifiedTimeBucket {
+  /** "day", "week", "month", "year" or "older" */
+  label: string
+  /** Upper bound of the range in seconds before the scan (absent for "older") */
+  maxAgeSeconds?: number
+  /** Number of files modified in the range */
+  fileCount: number
+  /** Total size in bytes of those files */
+  totalSize: number
+}
+/** Files, bytes and lines of code sharing an extension or language */
+export interface FileGroupStats {
+  /** Number of files */
+  fileCount: number
+  /** Total size in bytes */
+  totalSize: number
+  /** Non-blank lines (binary files count none) */
+  linesOfCode: number
+}
+/** How `findDuplicateFilesWithStats` hashes files that share a size */
+export interface DuplicateScanOptions {
+  /** "blake3" (default) or "xxh3" (128-bit xxHash3: faster, not cryptographic) */
+  algorithm: string
+  /** Read size in bytes, from 4 KiB to 64 MiB (ignored with `use_mmap`) */
+  bufferSize: number
+  /** Memory-map each file instead of reading it in chunks */
+  useMmap: boolean
 }
-/** Standalone function for quick file search */
-export declare function quickFindFiles(rootPath: string, pattern: string): Array<FileInfo>
-/** Standalone function for quick text search */
-export declare function quickSearchText(rootPath: string, searchText: string, filePattern?: string | undefined | null): Array<TextSearchResult>
+/** Bytes hashed by a duplicate scan and how fast */
+export interface HashThroughput {
+  /** Algorithm used */
+  algorithm: string
+  /** Files hashed (only files sharing a size with another are hashed) */
+  filesHashed: number
+  /** Total bytes hashed */
+  bytesHashed: number
+  /** Wall-clock time spent hashing, in milliseconds */
+  elapsedMs: number
+  /** Bytes hashed per second of wall-clock time */
+  bytesPerSecond: number
+}
+/** Duplicate groups and the hashing work behind them */
+export interface DuplicateScan {
+  /** Paths of identical files, keyed by content hash */
+  groups: Record<string, Array<string>>
+  /** Hashing throughput */
+  stats: HashThroughput
+  /** How the filesystem shaped the scan */
+  strategy: ScanStrategy
+  /** Entries left out of the scan (only with `report_skipped`) */
+  skipped?: SkipReport
+  /** Whether the walk stopped at `max_entries`, so duplicates may be missing */
+  truncated: boolean
+}
+/** How a scan was adapted to the filesystem holding its root */
+export interface ScanStrategy {
+  /** Filesystem type of the root's mount (e.g. "ext4", "nfs4", "apfs"; Linux and macOS) */
+  filesystem?: string
+  /** Whether the root is on a network or FUSE filesystem */
+  network: boolean
+  /** Whether files are processed in parallel */
+  parallel: boolean
+  /** Whether files may be memory-mapped */
+  mmap: boolean
+  /** Result cache mode in effect: "off", "mtime" or "events" */
+  resultCache: string
+  /** Changes made because of the filesystem */
+  adjustments: Array<string>
+}
+/** Glob pattern validity and its compiled regex */
+export interface GlobPatternInfo {
+  /** The glob pattern */
+  pattern: string
+  /** Whether the pattern compiles */
+  isValid: boolean
+  /** Anchored regex equivalent (if valid) */
+  regex?: string
+  /** Compilation error (if invalid) */
+  error?: string
+}
+/** Explanation of why a path is or isn't returned by a search */
+export interface PathMatchExplanation {
+  /** The path that was checked */
+  path: string
+  /** Whether the path would be returned */
+  matched: boolean
+  /** Whether the file name is hidden (starts with '.') */
+  isHidden: boolean
+  /** Exclude patterns that match the path */
+  excludedBy: Array<string>
+  /** Whether the file exceeds `max_file_size` */
+  exceedsSizeLimit: boolean
+  /** Whether the search pattern matches (if a pattern was given) */
+  matchesPattern?: boolean
+  /** Why the sandbox policy denies the path (if it does) */
+  sandboxDenial?: string
+  /** Detected language (when a `languages` filter is set) */
+  language?: string
+  /** Whether the language filter rejects the path */
+  excludedByLanguage: boolean
+  /** Human-readable reasons for the outcome */
+  reasons: Array<string>
+}
+/** Convert a glob pattern to the anchored regex used for matching paths */
+export declare function globToRegex(pattern: string): string
+/** Check glob patterns for validity without running a search */
+export declare function validateGlobPatterns(patterns: Array<string>): Array<GlobPatternInfo>
+/**
+ * Standalone function for quick file search
+ *
+ * # Arguments
+ * * `root_path` - Directory to search
+ * * `pattern` - Glob pattern matched against full paths
+ * * `config` - Optional search configuration (defaults exclude `node_modules`, `.git` and hidden files)
+ */
+export declare function quickFindFiles(rootPath: string, pattern: string, config?: FileSearchConfig | undefined | null): Array<FileInfo>
+/**
+ * Standalone function for quick text search
+ *
+ * # Arguments
+ * * `root_path` - Directory to search
+ * * `search_text` - Text to find (case-sensitive)
+ * * `file_pattern` - Optional glob restricting the files searched
+ * * `config` - Optional search configuration (defaults exclude `node_modules`, `.git` and hidden files)
+ */
+export declare function quickSearchText(rootPath: string, searchText: string, filePattern?: string | undefined | null, config?: FileSearchConfig | undefined | null): Array<TextSearchResult>
 /** Benchmark file search performance */
 export declare function benchmarkFileSearch(rootPath: string, pattern: string, iterations: number): Record<string, number>
 /** Configuration for text processing */
 export interface TextProcessingConfig {
   /** Case-sensitive matching */
   caseSensitive: boolean
-  /** Use overlapping matches */
+  /** Report overlapping matches (only with `match_kind` "standard") */
   overlapping: boolean
   /** Maximum match count (0 for unlimited) */
   maxMatches: number
+  /**
+   * Which pattern wins when several match at one position:
+   * "leftmost-first" (default; the one listed first), "leftmost-longest"
+   * (the longest), or "standard" (the first to finish matching, as in
+   * classic Aho-Corasick)
+   */
+  matchKind?: string
+  /**
+   * Only report matches starting at the beginning of the text or right
+   * where the previous match ended (default false; not combinable with
+   * overlapping matches)
+   */
+  anchored?: boolean
+  /**
+   * Automaton to build: "auto" (default; chosen by pattern count and
+   * size), "nfa", "contiguous-nfa" (faster, more memory) or "dfa"
+   * (fastest search, slowest build, most memory)
+   */
+  automaton?: string
 }
 /** Text match result */
 export interface TextMatch {
-  /** Start position of the match */
+  /**
+   * Start offset of the match: UTF-8 bytes for string input, or the
+   * buffer encoding's units for the `*InBuffer` methods
+   */
   start: number
-  /** End position of the match */
+  /** End offset of the match, in the same units as `start` */
   end: number
+  /** Line of the match start (1-based) */
+  line: number
+  /**
+   * Column of the match start within its line (0-based), in the same
+   * units as `start`
+   */
+  column: number
   /** The matched text */
   text: string
   /** Pattern index (for multi-pattern search) */
   patternIndex: number
 }
-/** Quick substring search function */
-export declare function quickSubstringSearch(text: string, patterns: Array<string>, caseSensitive?: boolean | undefined | null): Array<TextMatch>
-/** Path validation result */
-export interface PathValidationResult {
-  /** Whether the path is valid */
+/** Match found by a streaming matcher */
+export interface StreamMatch {
+  /** Absolute byte offset of the match start within the stream */
+  start: number
+  /** Absolute byte offset of the match end within the stream */
+  end: number
+  /** The matched text (lossily decoded as UTF-8) */
+  text: string
+  /** Pattern index (for multi-pattern search) */
+  patternIndex: number
+}
+/** Mapping of a run of output bytes back to the original text */
+export interface OffsetSegment {
+  /** Byte offset of the run in the sanitized output */
+  outputStart: number
+  /** Byte offset of the run in the original text */
+  originalStart: number
+  /** Length of the run in bytes */
+  length: number
+}
+/** Result of ANSI or control-character sanitization */
+export interface SanitizedText {
+  /** Sanitized text */
+  text: string
+  /** Kept runs mapping output byte offsets to original byte offsets */
+  segments: Array<OffsetSegment>
+  /** Number of escape sequences removed */
+  escapeSequencesRemoved: number
+  /** Number of control characters removed or replaced */
+  controlCharsRemoved: number
+}
+/** JSON validation result */
+export interface JsonValidationResult {
+  /** Whether the input is valid JSON */
   isValid: boolean
-  /** Sanitized path (if valid) */
-  sanitizedPath?: string
   /** Error message (if invalid) */
   error?: string
+  /** Line of the error (1-based, if invalid) */
+  line?: number
+  /** Column of the error (1-based, if invalid) */
+  column?: number
 }
-/** Quick path validation function */
-export declare function quickValidatePath(path: string, basePath: string): boolean
-/** Benchmark result */
-export interface BenchmarkResult {
-  /** Test name */
-  name: string
-  /** Average execution time in milliseconds */
-  avgTimeMs: number
-  /** Operations per second */
-  opsPerSec: number
-  /** Performance improvement ratio */
-  speedup: number
+/** Value matched by a JSON Pointer or JSONPath query */
+export interface JsonQueryMatch {
+  /** JSON Pointer (RFC 6901) to the matched value */
+  pointer: string
+  /** The matched value */
+  value: any
 }
-/** Quick benchmark function */
-export declare function quickBenchmark(): Record<string, number>
+/** Aggregate text statistics */
+export interface TextStats {
+  /** Number of lines (a trailing newline does not start a new line) */
+  lineCount: number
+  /** Number of whitespace-separated words */
+  wordCount: number
+  /** Number of Unicode scalar values */
+  charCount: number
+  /** Number of extended grapheme clusters */
+  graphemeCount: number
+  /** Length of the longest line in characters */
+  longestLineLength: number
+  /** Line number of the longest line (1-based, 0 for empty text) */
+  longestLine: number
+  /** Average line length in characters */
+  averageLineLength: number
+  /** Size in UTF-8 bytes */
+  byteSize: number
+}
+/** Quick substring search function */
+export declare function quickSubstringSearch(text: string, patterns: Array<string>, caseSensitive?: boolean | undefined | null): Array<TextMatch>
 /**
- * Initialize the MOIDVK Rust core module
+ * Strip ANSI escape sequences from captured terminal output
+ * Offsets in `segments` are UTF-8 byte offsets
+ */
+export declare function stripAnsi(text: string): SanitizedText
+/**
+ * Strip ANSI escapes and control characters (tab, LF and CR are kept)
  *
- * Returns a success message indicating the core has been initialized
+ * # Arguments
+ * * `text` - Text to sanitize
+ * * `replacement` - Optional replacement for each removed control character
  */
-export declare function initializeRustCore(): string
+export declare function sanitizeControlChars(text: string, replacement?: string | undefined | null): SanitizedText
+/** Validate JSON from a string or Buffer, reporting line and column of the first error */
+export declare function validateJson(input: string | Buffer): JsonValidationResult
+/** Minify JSON from a string or Buffer, preserving key order */
+export declare function minifyJson(input: string | Buffer): string
 /**
- * Get the version of the MOIDVK core crate
+ * Pretty-print JSON from a string or Buffer, preserving key order
  *
- * Returns the version string from Cargo.toml
+ * # Arguments
+ * * `input` - JSON text as a string or Buffer
+ * * `indent` - Spaces per indentation level (default 2)
  */
-export declare function getVersion(): string
+export declare function prettyPrintJson(input: string | Buffer, indent?: number | undefined | null): string
+/** Query a JSON document with a JSON Pointer (`/a/0`) or JSONPath (`$.a[*].b`) */
+export declare function queryJson(input: string | Buffer, expression: string): Array<JsonQueryMatch>
+/** Query a JSON file natively without passing its contents through JS */
+export declare function queryJsonFile(path: string, expression: string): Array<JsonQueryMatch>
+/** Compute line, word, character and grapheme statistics in a single pass */
+export declare function textStats(text: string): TextStats
+/** Match found by an `IncrementalMatcher` */
+export interface LineMatch {
+  /** Absolute byte offset of the match start within the stream */
+  start: number
+  /** Absolute byte offset of the match end within the stream */
+  end: number
+  /** Line of the stream holding the match (1-based) */
+  lineNumber: number
+  /** That line without its line ending (lossily decoded as UTF-8) */
+  line: string
+  /** The matched text (lossily decoded as UTF-8) */
+  text: string
+  /** Index of the pattern that matched */
+  patternIndex: number
+}
+/** Quick streaming search over a file for multiple patterns */
+export declare function quickStreamSearchFile(path: string, patterns: Array<string>, caseSensitive?: boolean | undefined | null): Array<StreamMatch>
+/** Archive entry as listed by the archive reader */
+export interface ArchiveEntry {
+  /** Entry name as stored in the archive */
+  name: string
+  /** Whether the entry is a symbolic link */
+  isSymlink?: boolean
+  /** Link target for symlink entries */
+  linkTarget?: string
+}
+/** Options for archive entry validation */
+export interface ArchiveValidationOptions {
+  /** Accept symlink entries whose target stays inside the destination */
+  allowSymlinks: boolean
+  /** Reject names that cannot be created on Windows (reserved devices, ':') */
+  portableNames: boolean
+  /** Check existing symlinks inside the destination directory on disk */
+  checkDestination: boolean
+}
+/** Verdict for a single archive entry */
+export interface ArchiveEntryVerdict {
+  /** Entry name as stored in the archive */
+  name: string
+  /** Whether the entry can be extracted safely */
+  isSafe: boolean
+  /** Where the entry would be written (if safe) */
+  destinationPath?: string
+  /**
+   * Violated rule (if unsafe): "empty_name", "control_char", "absolute_path",
+   * "drive_letter", "parent_component", "reserved_name", "invalid_character",
+   * "symlink_not_allowed", "symlink_escape", or "through_symlink"
+   */
+  violation?: string
+  /** Human-readable explanation (if unsafe) */
+  reason?: string
+}
 /**
- * Get performance information about the Rust runtime
+ * Validate archive entry names against a destination directory
+ *
+ * Entries are checked in archive order, so a file placed beneath an earlier
+ * symlink entry (the two-step zip-slip variant) is rejected even when the
+ * link itself looked harmless. Call this before extracting anything.
  *
- * Returns JSON string with SIMD support, thread count, allocator info, etc.
+ * # Arguments
+ * * `entries` - Entries in archive order
+ * * `destination` - Directory the archive will be extracted into
+ * * `options` - Optional validation options
  */
-export declare function getPerformanceInfo(): string
-/** Vector operations implementation */
-export declare class VectorOperations {
+export declare function validateArchiveEntries(entries: Array<ArchiveEntry>, destination: string, options?: ArchiveValidationOptions | undefined | null): Array<ArchiveEntryVerdict>
+/** Configuration for config leak scanning */
+export interface ConfigLeakConfig {
   /**
-   * Create a new vector operations instance with optional configuration
-   *
-   * # Arguments
-   * * `config` - Optional configuration for vector operations
+   * Environment variable names whose values are secrets; config keys
+   * with these names are reported when assigned a literal value
    */
-  constructor(config?: VectorConfig | undefined | null)
+  knownSecretNames: Array<string>
+  /** Minimum length of a literal value assigned to a credential-like key */
+  minValueLength: number
+  /** Maximum number of findings to return (0 for unlimited) */
+  maxFindings: number
+  /** Traversal and exclusion settings for directory scans */
+  search?: FileSearchConfig
+}
+/** Leaked credential found in a config file */
+export interface ConfigLeakFinding {
+  /** File path (or the label passed for text scans) */
+  path: string
   /**
-   * Calculate cosine similarity between two vectors
-   * 10-20x faster than JavaScript implementation
+   * Finding kind: "known_secret_format", "url_credentials",
+   * "known_secret_name", or "hardcoded_credential"
    */
-  cosineSimilarity(vecA: Array<number>, vecB: Array<number>): number
+  kind: string
+  /** Dotted key path of the value (e.g. "database.password") */
+  key: string
+  /** Line number (1-based) */
+  lineNumber: number
+  /** Start byte column of the value within the line */
+  columnStart: number
+  /** End byte column of the value within the line */
+  columnEnd: number
+  /** Severity: "critical", "high", "medium", or "low" */
+  severity: string
+  /** Value with all but a short prefix masked */
+  redactedValue: string
+  /** Explanation of the finding */
+  message: string
+}
+/** Quick config leak scan of text in the given format with default settings */
+export declare function quickScanConfigLeaks(text: string, format: string): Array<ConfigLeakFinding>
+/** Options for `sanitize_filename` */
+export interface SanitizeFilenameOptions {
   /**
-   * Calculate cosine similarity for multiple vector pairs in parallel
-   * 20-50x faster than JavaScript for large batches
+   * Target file system deciding how length is counted: "posix", "ext4",
+   * "xfs", "btrfs", "zfs" or "apfs" (UTF-8 bytes) or "ntfs", "exfat",
+   * "fat32", "vfat", "hfs+" or "refs" (UTF-16 code units)
    */
-  batchCosineSimilarity(queryVector: Array<number>, vectorsFlat: Array<number>, vectorSize: number): Array<number>
+  filesystem: string
+  /** Maximum name length in the file system's unit (default 255) */
+  maxLength: number
+  /** Rename Windows reserved device names such as "CON" or "com1.txt" */
+  windowsSafe: boolean
+  /** Transliterate non-ASCII text to ASCII (e.g. "Crème brûlée" to "Creme brulee") */
+  transliterate: boolean
+  /** Names the result must not collide with */
+  existingNames?: Array<string>
+  /** Directory whose entries the result must not collide with */
+  uniqueInDirectory?: string
+}
+/** Configuration for PII detection */
+export interface PiiConfig {
+  /** Detectors to run: "email", "phone", "iban", "ssn", "credit_card" */
+  detectors: Array<string>
+  /** Minimum confidence (0-1) for a finding to be reported */
+  minConfidence: number
+  /** Maximum number of findings to return (0 for unlimited) */
+  maxFindings: number
+  /** Traversal and exclusion settings for directory scans */
+  search?: FileSearchConfig
+}
+/** PII found in scanned content */
+export interface PiiFinding {
+  /** File path (or the label passed for text scans) */
+  path: string
+  /** Detector that produced the finding */
+  kind: string
+  /** Start byte offset in the content */
+  start: number
+  /** End byte offset in the content */
+  end: number
+  /** Line number (1-based) */
+  lineNumber: number
+  /** Start byte column within the line */
+  columnStart: number
+  /** Matched value with identifying parts masked */
+  redactedMatch: string
+  /** Confidence that the match is real PII (0-1) */
+  confidence: number
+}
+/** Quick PII detection over text with all detectors enabled */
+export declare function quickDetectPii(text: string): Array<PiiFinding>
+/** Quarantine settings */
+export interface QuarantineConfig {
+  /** Directory holding quarantined files (created if missing) */
+  directory: string
+  /** Hex-encoded 32-byte Ed25519 secret seed used to sign records */
+  signingKey: string
+}
+/** Signed metadata for a quarantined file */
+export interface QuarantineRecord {
+  /** Quarantine id */
+  id: string
+  /** Absolute path the file was taken from */
+  originalPath: string
+  /** Why the file was quarantined (e.g. the scanner finding) */
+  reason: string
+  /** RFC 3339 UTC time of quarantine */
+  quarantinedAt: string
+  /** File size in bytes */
+  size: number
+  /** Hex SHA-256 digest of the contents */
+  sha256: string
+  /** Unix permission bits of the original file */
+  mode?: number
+  /** Hex public key of the signer */
+  publicKey: string
+  /** Hex Ed25519 signature over the other fields */
+  signature: string
+}
+/** A record as listed, with its verification status */
+export interface QuarantineEntry {
+  /** Stored metadata */
+  record: QuarantineRecord
+  /** Whether the record is signed by this quarantine's key */
+  signatureValid: boolean
+  /** Whether the stored contents still match the recorded digest */
+  contentIntact: boolean
+}
+/** Budget for paths below one prefix */
+export interface PrefixBudget {
+  /** Directory the budget applies to */
+  prefix: string
+  /** Maximum files written below the prefix (0 for no limit) */
+  maxFilesWritten: number
+  /** Maximum files deleted below the prefix (0 for no limit) */
+  maxFilesDeleted: number
+  /** Maximum bytes written below the prefix (0 for no limit) */
+  maxBytesWritten: number
+}
+/** Limits on file mutations for one run */
+export interface QuotaPolicy {
+  /** Maximum files written (0 for no limit) */
+  maxFilesWritten: number
+  /** Maximum files deleted (0 for no limit) */
+  maxFilesDeleted: number
+  /** Maximum bytes written (0 for no limit) */
+  maxBytesWritten: number
+  /** Additional budgets for paths below specific directories */
+  prefixBudgets: Array<PrefixBudget>
+}
+/** Mutations counted so far under one budget */
+export interface QuotaUsage {
+  /** Prefix of the budget (absent for the overall budget) */
+  prefix?: string
+  /** Files written */
+  filesWritten: number
+  /** Files deleted */
+  filesDeleted: number
+  /** Bytes written */
+  bytesWritten: number
+}
+/** Outcome of charging the quota */
+export interface QuotaVerdict {
+  /** Whether the operation fits in every applicable budget */
+  allowed: boolean
+  /** Limit that would be exceeded: "max_files_written", "max_files_deleted" or "max_bytes_written" */
+  limit?: string
+  /** Prefix of the exceeded budget (absent for the overall budget) */
+  prefix?: string
+  /** Amount already used under the exceeded limit */
+  used: number
+  /** Amount the operation asked for */
+  requested: number
+  /** Value of the exceeded limit */
+  maximum: number
+}
+/**
+ * Install a quota for subsequent native file mutations, resetting usage
+ *
+ * # Arguments
+ * * `policy` - Limits to enforce
+ */
+export declare function setFileQuota(policy: QuotaPolicy): void
+/** Remove the active quota */
+export declare function clearFileQuota(): void
+/** Usage under the overall budget followed by each prefix budget */
+export declare function getFileQuotaUsage(): Array<QuotaUsage>
+/**
+ * Charge the active quota for a mutation performed outside the native layer
+ *
+ * Nothing is counted when the operation would exceed a budget, so the
+ * caller can skip it and continue with smaller operations.
+ *
+ * # Arguments
+ * * `operation` - "write" or "delete"
+ * * `path` - File being modified
+ * * `bytes` - Bytes to be written (ignored for deletes)
+ */
+export declare function consumeFileQuota(operation: string, path: string, bytes?: number | undefined | null): QuotaVerdict
+/** Limits applied by the analyzer */
+export interface RegexAnalysisConfig {
+  /** Maximum compiled program size in bytes */
+  maxCompiledSize: number
+  /** Maximum number of atoms after expanding counted repetitions */
+  maxExpandedSize: number
+}
+/** A dangerous construct found in a pattern */
+export interface RegexIssue {
   /**
-   * Find the most similar vectors from a collection
-   * Returns top-k results above the similarity threshold
+   * Issue kind: "nested_quantifier", "overlapping_alternation",
+   * "adjacent_quantifiers", "backreference", "compiled_size" or "excessive_repetition"
    */
-  findSimilarVectors(queryVector: Array<number>, vectorsFlat: Array<number>, vectorSize: number, paths: Array<string>, topK: number): Array<SimilarityResult>
-  /** Normalize a vector to unit length */
-  normalizeVector(vector: Array<number>): Array<number>
-  /** Calculate the L2 norm (magnitude) of a vector */
-  vectorNorm(vector: Array<number>): number
-  /** Compute pairwise distances between all vectors in a collection */
-  pairwiseDistances(vectorsFlat: Array<number>, vectorSize: number): Array<number>
-  /** Create embeddings cache key from vector */
-  createCacheKey(vector: Array<number>): string
+  kind: string
+  /** Severity: "high" (exponential), "medium" (polynomial or oversized) or "low" */
+  severity: string
+  /** Character offset of the construct in the pattern */
+  position: number
+  /** Source text of the construct */
+  fragment: string
+  /** Description of the problem */
+  message: string
+  /** Suggested safe rewrite */
+  suggestion: string
 }
-/** File search operations implementation */
-export declare class FileSearch {
+/** Result of analyzing a pattern */
+export interface RegexAnalysis {
+  /** Pattern that was analyzed */
+  pattern: string
+  /** Whether no high or medium severity issue was found */
+  safe: boolean
+  /** Issues found, in pattern order */
+  issues: Array<RegexIssue>
+  /** Deepest nesting of unbounded quantifiers */
+  starHeight: number
+  /** Atom count after expanding counted repetitions */
+  expandedSize: number
+  /** Why the pattern doesn't compile with the linear-time engine, if it doesn't */
+  compileError?: string
   /**
-   * Create a new file search instance with optional configuration
-   *
-   * # Arguments
-   * * `config` - Optional configuration for file search operations
+   * Estimated compiled size in bytes (absent if the pattern doesn't
+   * compile or exceeds `max_compiled_size`)
    */
-  constructor(config?: FileSearchConfig | undefined | null)
+  compiledSize?: number
+}
+/** Validation of one pattern of a batch */
+export interface RegexValidation {
+  /** Pattern that was validated */
+  pattern: string
+  /** Whether the pattern compiles with the linear-time engine */
+  valid: boolean
+  /** Why the pattern doesn't parse or compile, if it doesn't */
+  error?: string
+  /** Character offset of a syntax error, when known */
+  errorPosition?: number
   /**
-   * Search for files by glob pattern
-   * 5-10x faster than Node.js glob implementations
+   * Estimated compiled size in bytes (absent if the pattern doesn't
+   * compile or exceeds `max_compiled_size`)
    */
-  findFilesByPattern(rootPath: string, pattern: string): Array<FileInfo>
+  compiledSize?: number
+  /** ReDoS issues, in pattern order (empty if the pattern doesn't parse) */
+  issues: Array<RegexIssue>
+  /** Whether the pattern is valid and has no high or medium severity issue */
+  safe: boolean
+}
+/**
+ * Analyze a regular expression for catastrophic backtracking
+ *
+ * # Arguments
+ * * `pattern` - Regular expression source, without delimiters or flags
+ * * `config` - Optional size limits
+ */
+export declare function analyzeRegex(pattern: string, config?: RegexAnalysisConfig | undefined | null): RegexAnalysis
+/**
+ * Validate many regular expressions in one call
+ *
+ * Every pattern is parsed, compiled and checked for catastrophic
+ * backtracking; a broken pattern is reported in its entry rather than
+ * failing the batch. Results are in input order.
+ *
+ * # Arguments
+ * * `patterns` - Regular expression sources, without delimiters or flags
+ * * `config` - Optional size limits
+ */
+export declare function validateRegexes(patterns: Array<string>, config?: RegexAnalysisConfig | undefined | null): Array<RegexValidation>
+/** Limits applied to every native file access */
+export interface SandboxPolicy {
+  /** Directories file access is confined to (empty allows any location) */
+  allowedRoots: Array<string>
   /**
-   * Search for text content within files
-   * 10-20x faster than JavaScript regex operations on large files
+   * Glob patterns for paths that may never be accessed (e.g. "**/.ssh/**"),
+   * matched against the absolute, normalized path
    */
-  searchTextInFiles(rootPath: string, searchText: string, filePattern?: string | undefined | null, caseSensitive?: boolean | undefined | null): Array<TextSearchResult>
-  /** Get directory statistics (size, file count, etc.) */
-  getDirectoryStats(path: string): DirectoryStats
-  /** Create a map of file extensions to their counts */
-  getFileExtensionStats(path: string): Record<string, number>
-  /** Fast duplicate file finder using content hashing */
-  findDuplicateFiles(path: string): Record<string, Array<string>>
+  deniedGlobs: Array<string>
+  /** Maximum size in bytes of a file that may be read (0 for no limit) */
+  maxFileSize: number
+  /** Maximum number of results a single operation may return (0 for no limit) */
+  maxResults: number
+  /** Follow symlinks whose real location stays inside an allowed root */
+  followSymlinks: boolean
 }
-/** Text processor for high-performance pattern matching */
-export declare class TextProcessor {
-  /** Create a new text processing instance with optional configuration */
-  constructor(config?: TextProcessingConfig | undefined | null)
-  /** Fast substring search using Aho-Corasick */
-  findSubstrings(text: string, patterns: Array<string>): Array<TextMatch>
-  /** Regex pattern matching */
-  findRegexMatches(text: string, pattern: string): Array<TextMatch>
+/** Verdict for a single path under a sandbox policy */
+export interface SandboxVerdict {
+  /** Whether the path may be accessed */
+  allowed: boolean
+  /** Why access is denied (if denied) */
+  reason?: string
 }
-/** Security utilities */
-export declare class SecurityUtils {
-  /** Create a new security utilities instance */
-  constructor()
-  /** Validate and sanitize file path */
-  validatePath(path: string, basePath: string): PathValidationResult
-  /** Sanitize filename by removing dangerous characters */
-  sanitizeFilename(filename: string): string
+/**
+ * Check whether a path may be accessed under a sandbox policy
+ *
+ * Intended for file operations performed outside the native modules: the
+ * path must resolve inside an allowed root without escaping through a
+ * symlink, must not match a denied glob, and must respect the size limit.
+ *
+ * # Arguments
+ * * `policy` - Sandbox policy to apply
+ * * `path` - Path that is about to be accessed
+ */
+export declare function checkSandboxAccess(policy: SandboxPolicy, path: string): SandboxVerdict
+/** Configuration for secret scanning */
+export interface SecretScanConfig {
+  /** Report high-entropy strings that match no rule */
+  enableEntropy: boolean
+  /**
+   * Minimum Shannon entropy (bits per char) for base64-like strings;
+   * hex strings use two thirds of this value
+   */
+  entropyThreshold: number
+  /** Minimum length of strings considered by the entropy pass */
+  minEntropyLength: number
+  /** Maximum number of findings to return (0 for unlimited) */
+  maxFindings: number
+  /** Traversal and exclusion settings for directory scans */
+  search?: FileSearchConfig
 }
-/** Benchmark suite */
-export declare class BenchmarkSuite {
-  /** Create a new benchmark runner instance */
-  constructor()
-  /** Run all performance benchmarks */
-  runAllBenchmarks(): Array<BenchmarkResult>
-  /** Get benchmark results */
-  getResults(): Array<BenchmarkResult>
+/** Secret found in scanned content */
+export interface SecretFinding {
+  /** File path (or the label passed for text scans) */
+  path: string
+  /** Line number (1-based) */
+  lineNumber: number
+  /** Start byte column within the line */
+  columnStart: number
+  /** End byte column within the line */
+  columnEnd: number
+  /** Rule identifier (e.g. "aws-access-key-id", "high-entropy-string") */
+  ruleId: string
+  /** Rule description */
+  description: string
+  /** Severity: "critical", "high", "medium", or "low" */
+  severity: string
+  /** Matched secret with all but a short prefix masked */
+  redactedMatch: string
+  /** Shannon entropy of the match (bits per char) */
+  entropy: number
+}
+/** High-entropy string found by `find_high_entropy_strings` */
+export interface EntropySpan {
+  /** Start byte offset in the text */
+  start: number
+  /** End byte offset in the text */
+  end: number
+  /** Line number (1-based) */
+  lineNumber: number
+  /** Start byte column within the line */
+  columnStart: number
+  /** Alphabet of the string: "hex", "base64", "base64url" or "mixed" */
+  charset: string
+  /** Shannon entropy of the string (bits per char) */
+  entropy: number
+  /** String with all but a short prefix masked */
+  redactedMatch: string
+}
+/**
+ * Find high-entropy strings without running the secret rules
+ *
+ * Candidates are runs of base64, base64url or hex characters. Hex runs only
+ * need two thirds of the threshold, matching their smaller alphabet.
+ *
+ * # Arguments
+ * * `text` - Content to scan
+ * * `min_length` - Minimum run length (default 20)
+ * * `threshold` - Minimum Shannon entropy in bits per char for base64 runs (default 4.5)
+ */
+export declare function findHighEntropyStrings(text: string, minLength?: number | undefined | null, threshold?: number | undefined | null): Array<EntropySpan>
+/** Quick secret scan of text with default rules */
+export declare function quickScanSecrets(text: string): Array<SecretFinding>
+/** Outcome of a secure delete */
+export interface SecureDeleteResult {
+  /** Path that was deleted */
+  path: string
+  /** Whether the file was removed */
+  deleted: boolean
+  /** Overwrite passes completed */
+  passes: number
+  /** Bytes written across all passes */
+  bytesOverwritten: number
+  /** Filesystem type, where it could be determined */
+  filesystem?: string
+  /** Whether old contents may survive on disk despite the overwrite */
+  bestEffort: boolean
+  /** Reasons the overwrite may be incomplete */
+  warnings: Array<string>
+}
+/**
+ * Overwrite a file's contents and then delete it
+ *
+ * Every pass but the last writes random bytes; the last writes zeros. Each
+ * pass is synced to disk before the next starts. Symlinks and non-regular
+ * files are rejected so a link can't redirect the overwrite elsewhere; the
+ * file is opened without following links and checked to be the one
+ * inspected, so swapping in a link meanwhile fails instead.
+ *
+ * A file with other hard links is rejected unless `allow_hard_links` is
+ * set: the overwrite destroys the data under every name, while only this
+ * one is removed.
+ *
+ * # Arguments
+ * * `path` - File to delete
+ * * `passes` - Number of overwrite passes (default 1, at most 35)
+ * * `allow_hard_links` - Overwrite files with other hard links (default: false)
+ */
+export declare function secureDelete(path: string, passes?: number | undefined | null, allowHardLinks?: boolean | undefined | null): SecureDeleteResult
+/**
+ * Compare two buffers without leaking where they differ
+ *
+ * # Arguments
+ * * `a` - First value (e.g. the expected token)
+ * * `b` - Second value (e.g. the token supplied by a caller)
+ */
+export declare function constantTimeEquals(a: Buffer, b: Buffer): boolean
+/**
+ * Compare two strings (tokens, hex digests) without leaking where they differ
+ *
+ * The UTF-8 bytes are compared as-is; no case folding is applied.
+ *
+ * # Arguments
+ * * `a` - First string
+ * * `b` - Second string
+ */
+export declare function constantTimeEqualsStr(a: string, b: string): boolean
+/** Potentially dangerous construct found in a shell command */
+export interface ShellFinding {
+  /**
+   * Construct kind: "command_substitution", "process_substitution",
+   * "arithmetic_expansion", "unquoted_variable", "variable_expansion",
+   * "unquoted_glob", "command_separator", "command_chaining", "pipe",
+   * "background", "redirection", "eval", or "unterminated_quote"
+   */
+  kind: string
+  /** Severity: "high", "medium", or "low" */
+  severity: string
+  /** Start byte offset in the command */
+  start: number
+  /** End byte offset in the command */
+  end: number
+  /** The offending text */
+  snippet: string
+  /** Explanation of the risk */
+  message: string
+}
+/**
+ * Quote a single argument so the shell passes it through unchanged
+ *
+ * # Arguments
+ * * `arg` - Argument to quote
+ * * `platform` - "posix", "cmd", or "powershell" (defaults to the host shell)
+ */
+export declare function quoteShellArg(arg: string, platform?: string | undefined | null): string
+/**
+ * Quote every argument and join them into a command line
+ *
+ * # Arguments
+ * * `args` - Program and arguments
+ * * `platform` - "posix", "cmd", or "powershell" (defaults to the host shell)
+ */
+export declare function quoteShellCommand(args: Array<string>, platform?: string | undefined | null): string
+/**
+ * Analyze a command string for injection-prone constructs
+ *
+ * The command is tokenized with the shell's quoting rules, so constructs
+ * inside single quotes (or escaped) are not reported, while substitutions
+ * inside double quotes are.
+ *
+ * # Arguments
+ * * `command` - Command line to analyze
+ * * `platform` - "posix" or "cmd" (defaults to the host shell)
+ */
+export declare function analyzeShellCommand(command: string, platform?: string | undefined | null): Array<ShellFinding>
+/** Freshly generated Ed25519 key pair */
+export interface Ed25519KeyPair {
+  /** Hex-encoded 32-byte public key */
+  publicKey: string
+  /** Hex-encoded 32-byte secret seed (keep private) */
+  secretKey: string
+}
+/** Generate a new Ed25519 key pair from OS randomness */
+export declare function generateEd25519Keypair(): Ed25519KeyPair
+/**
+ * Derive the public key for a secret seed
+ *
+ * # Arguments
+ * * `secret_key` - Hex-encoded 32-byte secret seed
+ */
+export declare function ed25519PublicKey(secretKey: string): string
+/**
+ * Sign a buffer
+ *
+ * # Arguments
+ * * `secret_key` - Hex-encoded 32-byte secret seed
+ * * `message` - Bytes to sign
+ */
+export declare function ed25519Sign(secretKey: string, message: Buffer): string
+/**
+ * Verify a signature over a buffer
+ *
+ * Returns false when the signature does not match; malformed keys or
+ * signatures are reported as errors.
+ *
+ * # Arguments
+ * * `public_key` - Hex-encoded 32-byte public key
+ * * `message` - Bytes that were signed
+ * * `signature` - Hex-encoded 64-byte signature
+ */
+export declare function ed25519Verify(publicKey: string, message: Buffer, signature: string): boolean
+/**
+ * Sign a file's contents
+ *
+ * # Arguments
+ * * `secret_key` - Hex-encoded 32-byte secret seed
+ * * `path` - File to sign
+ */
+export declare function ed25519SignFile(secretKey: string, path: string): string
+/**
+ * Verify a signature over a file's contents
+ *
+ * # Arguments
+ * * `public_key` - Hex-encoded 32-byte public key
+ * * `path` - File that was signed
+ * * `signature` - Hex-encoded 64-byte signature
+ */
+export declare function ed25519VerifyFile(publicKey: string, path: string, signature: string): boolean
+/** Configuration for Unicode spoofing detection */
+export interface UnicodeSpoofConfig {
+  /** Checks to run: "bidi", "invisible", "confusable" */
+  checks: Array<string>
+  /** Maximum number of findings to return (0 for unlimited) */
+  maxFindings: number
+  /** Traversal and exclusion settings for directory scans */
+  search?: FileSearchConfig
+}
+/** Suspicious character found in scanned content */
+export interface UnicodeFinding {
+  /** File path (or the label passed for text scans) */
+  path: string
+  /** Finding kind: "bidi_control", "bidi_unterminated", "invisible_char", or "confusable" */
+  kind: string
+  /** Code point, e.g. "U+202E" */
+  codePoint: string
+  /** Unicode character name (or lookalike description for confusables) */
+  name: string
+  /** Start byte offset in the content */
+  start: number
+  /** End byte offset in the content */
+  end: number
+  /** Line number (1-based) */
+  lineNumber: number
+  /** Start byte column within the line */
+  columnStart: number
+  /** End byte column within the line */
+  columnEnd: number
+  /** Severity: "critical", "high", "medium", or "low" */
+  severity: string
+  /** Explanation of the risk */
+  message: string
+}
+/** Quick Unicode spoofing scan over text with all checks enabled */
+export declare function quickScanUnicodeSpoofing(text: string): Array<UnicodeFinding>
+/** Structure of a path interpreted with Windows semantics */
+export interface WindowsPathInfo {
+  /** Prefix kind: "relative", "rooted", "drive_relative", "drive", "unc", or "device" */
+  prefixKind: string
+  /** Rendered prefix (e.g. "C:" or "\\server\share"), if any */
+  prefix?: string
+  /** Whether the path uses the `\\?\` verbatim form */
+  verbatim: boolean
+  /** Whether the path is fully qualified (does not depend on process state) */
+  isAbsolute: boolean
+  /** Path components after the prefix */
+  components: Array<string>
+  /** Components that name a reserved device (e.g. "NUL.txt") */
+  reservedNames: Array<string>
+  /** Alternate data stream suffixes (e.g. "secret:$DATA") */
+  alternateStreams: Array<string>
+}
+/**
+ * Describe how a path is interpreted under Windows semantics
+ *
+ * Works on any host, which makes it suitable for checking paths that will
+ * be used on Windows machines (e.g. in archives or configuration files).
+ */
+export declare function analyzeWindowsPath(path: string): WindowsPathInfo
+/** Policy controlling which paths `validate_path` accepts */
+export interface PathPolicy {
+  /** Accept absolute input paths (they must still resolve inside the base) */
+  allowAbsolute: boolean
+  /** Accept `..` components as long as the result stays inside the base */
+  allowParentComponents: boolean
+  /** Accept hidden components (names starting with '.') below the base */
+  allowHidden: boolean
+  /** Accept ASCII control characters in component names (NUL is always rejected) */
+  allowControlChars: boolean
+  /** Resolve symlinks of the existing part of the path before the containment check */
+  resolveSymlinks: boolean
+  /** Maximum total path length in bytes (0 for no limit) */
+  maxPathLength: number
+  /** Maximum length of a single component in bytes (0 for no limit) */
+  maxComponentLength: number
+  /** Component names that are always rejected (e.g. ".git") */
+  forbiddenComponents: Array<string>
+  /**
+   * Interpret paths with Windows semantics: drive letters, UNC and `\\?\`
+   * prefixes, reserved device names, alternate data streams, and
+   * case-insensitive containment (defaults to the host platform)
+   */
+  windowsPaths: boolean
+}
+/** Path validation result */
+export interface PathValidationResult {
+  /** Whether the path is valid */
+  isValid: boolean
+  /** Sanitized path (if valid) */
+  sanitizedPath?: string
+  /** Error message (if invalid) */
+  error?: string
+  /**
+   * Violated policy rule (if invalid): "null_byte", "control_char",
+   * "path_too_long", "component_too_long", "absolute_path",
+   * "parent_component", "outside_base", "symlink_escape",
+   * "hidden_component", or "forbidden_component"; with Windows
+   * semantics also "device_path", "drive_relative", "reserved_name", or
+   * "alternate_stream"
+   */
+  violation?: string
+}
+/** Result of resolving a path one component at a time */
+export interface SymlinkCheckResult {
+  /** Whether the path, and every link followed on the way, resolves inside the base */
+  isValid: boolean
+  /** Fully resolved path (if valid) */
+  resolvedPath?: string
+  /** Input prefix whose resolution left the base (e.g. "assets/link") */
+  escapingComponent?: string
+  /** Target of the symlink that led outside the base, if a symlink did */
+  linkTarget?: string
+  /** Number of symlinks followed */
+  symlinksFollowed: number
+  /** Error message (if invalid) */
+  error?: string
+}
+/** Quick path validation function */
+export declare function quickValidatePath(path: string, basePath: string): boolean
+/** Benchmark timing settings */
+export interface BenchmarkConfig {
+  /** Untimed iterations run before sampling */
+  warmupIterations: number
+  /** Keep warming up until this much time has passed */
+  warmupTimeMs: number
+  /** Minimum number of samples */
+  minIterations: number
+  /** Maximum number of samples */
+  maxIterations: number
+  /** Keep sampling until this much time has passed (bounded by `max_iterations`) */
+  measurementTimeMs: number
+  /** Confidence level of the interval for the mean, between 0 and 1 */
+  confidenceLevel: number
+}
+/**
+ * Which built-in benchmarks run and on what data
+ *
+ * Unset fields keep the default workload.
+ */
+export interface BenchmarkWorkload {
+  /**
+   * Built-in benchmarks to run: "vector_ops", "file_search" and/or
+   * "text_processing" (default all)
+   */
+  benchmarks?: Array<string>
+  /** Dimensions of each vector (default 1536) */
+  vectorDimensions?: number
+  /** Number of vectors compared against the query (default 1000) */
+  vectorCount?: number
+  /** Directory searched by the file search benchmark (default the working directory) */
+  searchRoot?: string
+  /** Glob searched for by the file search benchmark (default "*.rs") */
+  searchPattern?: string
+  /** Size of the text searched by the text benchmark in bytes (default 45000) */
+  textBytes?: number
+  /** Number of patterns searched for at once (default 5) */
+  patternCount?: number
+  /**
+   * Seed for reproducible data: the vector benchmark uses a synthetic
+   * embedding corpus, and without `search_root` the file search runs on
+   * a synthetic tree generated in a temporary directory
+   */
+  syntheticSeed?: number
+}
+/** Benchmark result */
+export interface BenchmarkResult {
+  /** Test name */
+  name: string
+  /** Average execution time in milliseconds */
+  avgTimeMs: number
+  /** Items processed per second (see `item_unit`) */
+  opsPerSec: number
+  /** What one processed item is, e.g. "vectors", "files" or "lines" */
+  itemUnit: string
+  /** Items processed per iteration */
+  itemsPerIteration: number
+  /** Bytes processed per iteration, for benchmarks that consume data */
+  bytesPerIteration?: number
+  /** Data throughput in megabytes (10^6 bytes) per second */
+  megabytesPerSec?: number
+  /**
+   * Measured speedup over the JavaScript reference (JS median divided by
+   * this median); absent until a reference has been compared
+   */
+  speedup?: number
+  /** Median of the JavaScript reference in milliseconds, when compared */
+  jsMedianMs?: number
+  /** Number of timed iterations */
+  iterations: number
+  /** Median iteration time in milliseconds */
+  medianMs: number
+  /** Sample standard deviation in milliseconds */
+  stddevMs: number
+  /** Fastest iteration in milliseconds */
+  minMs: number
+  /** Slowest iteration in milliseconds */
+  maxMs: number
+  /** 95th percentile in milliseconds */
+  p95Ms: number
+  /** 99th percentile in milliseconds */
+  p99Ms: number
+  /** Lower bound of the confidence interval for the mean */
+  ciLowerMs: number
+  /** Upper bound of the confidence interval for the mean */
+  ciUpperMs: number
+  /** Confidence level of the interval */
+  confidenceLevel: number
+  /** Per-iteration times in milliseconds, in run order */
+  samplesMs: Array<number>
+  /** Native allocations per timed iteration */
+  allocationsPerIteration: number
+  /** Native bytes allocated per timed iteration, including freed ones */
+  allocatedBytesPerIteration: number
+  /** Peak growth of live native memory over its level before sampling */
+  peakMemoryGrowthBytes: number
+  /** Peak resident set size of the process after the benchmark */
+  peakRssBytes: number
+}
+/** Change of one benchmark relative to the baseline */
+export interface BenchmarkComparison {
+  /** Benchmark name */
+  name: string
+  /** Baseline median in milliseconds */
+  baselineMs: number
+  /** Current median in milliseconds */
+  currentMs: number
+  /** Current minus baseline median in milliseconds */
+  deltaMs: number
+  /** Relative change of the median in percent (positive is slower) */
+  deltaPercent: number
+  /** "regressed", "improved" or "unchanged" */
+  status: string
+}
+/** Outcome of comparing the current results to a saved baseline */
+export interface RegressionReport {
+  /** Whether no benchmark regressed beyond the tolerance */
+  passed: boolean
+  /** Allowed relative slowdown (0.1 = 10%) */
+  tolerance: number
+  /** When the baseline was saved (RFC 3339) */
+  baselineCreatedAt: string
+  /** Benchmarks present in both runs */
+  comparisons: Array<BenchmarkComparison>
+  /** Names of the benchmarks that regressed */
+  regressions: Array<string>
+  /** Baseline benchmarks missing from the current results */
+  missing: Array<string>
+  /** Current benchmarks not in the baseline */
+  added: Array<string>
+}
+/** Progress event emitted after each built-in benchmark */
+export interface BenchmarkProgress {
+  /** Benchmark that just finished */
+  name: string
+  /** Benchmarks finished so far, including this one */
+  completed: number
+  /** Benchmarks in the run */
+  total: number
+  /** Result of the benchmark that just finished */
+  result: BenchmarkResult
+}
+/** Measured speedup of a native benchmark over its JavaScript reference */
+export interface SpeedupMeasurement {
+  /** Benchmark name */
+  name: string
+  /** Native median in milliseconds */
+  nativeMedianMs: number
+  /** JavaScript reference median in milliseconds */
+  jsMedianMs: number
+  /** JavaScript median divided by native median (above 1 means native is faster) */
+  speedup: number
+  /** Full statistics of the JavaScript reference */
+  js: BenchmarkResult
+}
+/** Machine a benchmark run happened on */
+export interface BenchmarkEnvironment {
+  /** Operating system, e.g. "linux" */
+  os: string
+  /** CPU architecture, e.g. "x86_64" */
+  arch: string
+  /** CPU brand string, or "unknown" */
+  cpuModel: string
+  /** Logical cores available to the process */
+  cpuCores: number
+  /** Threads in the rayon pool used by the parallel operations */
+  rayonThreads: number
+  /** SIMD instruction sets detected at runtime */
+  simdFeatures: Array<string>
+  /** Version of this crate */
+  crateVersion: string
+  /** "debug" or "release" */
+  optimizationLevel: string
+}
+/** Describe the machine benchmarks run on */
+export declare function getBenchmarkEnvironment(): BenchmarkEnvironment
+/** JSON Schema of the document produced by `exportResults("json")` */
+export declare function getBenchmarkResultsSchema(): string
+/** Quick benchmark function */
+export declare function quickBenchmark(): Record<string, number>
+/** Configuration for near-duplicate detection */
+export interface NearDuplicateConfig {
+  /** Number of whitespace-separated tokens per shingle */
+  shingleSize: number
+  /** Number of hash functions in each MinHash signature */
+  numHashes: number
+  /** Number of LSH bands (must divide `num_hashes`) */
+  bands: number
+  /** Minimum estimated Jaccard similarity for a reported pair */
+  similarityThreshold: number
+  /** Lowercase text before shingling */
+  ignoreCase: boolean
+  /** Use parallel processing for signature generation */
+  useParallel: boolean
+}
+/** A pair of near-duplicate documents */
+export interface DuplicatePair {
+  /** Index of the first document */
+  indexA: number
+  /** Index of the second document */
+  indexB: number
+  /** Estimated Jaccard similarity (0.0 to 1.0) */
+  similarity: number
+}
+/** Quick near-duplicate search with default configuration */
+export declare function quickFindNearDuplicates(texts: Array<string>, similarityThreshold?: number | undefined | null): Array<DuplicatePair>
+/** Configuration for text formatting checks */
+export interface FormatConfig {
+  /** Target line ending when normalizing ("lf" or "crlf") */
+  lineEnding: string
+  /** Remove trailing spaces and tabs when normalizing */
+  trimTrailingWhitespace: boolean
+  /** Append a final newline when normalizing if one is missing */
+  ensureFinalNewline: boolean
+}
+/** Line ending and whitespace analysis report */
+export interface LineEndingReport {
+  /** Number of LF line endings */
+  lfCount: number
+  /** Number of CRLF line endings */
+  crlfCount: number
+  /** Number of bare CR line endings */
+  crCount: number
+  /** Most common line ending ("lf", "crlf", "cr", or "none") */
+  dominant: string
+  /** Whether more than one kind of line ending is present */
+  isMixed: boolean
+  /** Line numbers (1-based) whose ending differs from the dominant one */
+  inconsistentLines: Array<number>
+  /** Line numbers (1-based) with trailing spaces or tabs */
+  trailingWhitespaceLines: Array<number>
+  /** Whether the text ends with a line ending */
+  hasFinalNewline: boolean
+  /** Number of blank lines after the last non-blank line */
+  trailingBlankLines: number
+}
+/** A single text edit expressed in byte offsets of the original text */
+export interface TextEdit {
+  /** Start byte offset in the original text */
+  start: number
+  /** End byte offset in the original text */
+  end: number
+  /** Replacement text */
+  replacement: string
+  /** Line number (1-based) the edit applies to */
+  line: number
+}
+/** Result of a normalization operation */
+export interface NormalizationResult {
+  /** Normalized text */
+  text: string
+  /** Edits that transform the original text into the normalized text */
+  edits: Array<TextEdit>
+  /** Whether any change was made */
+  changed: boolean
+}
+/** Replacement of a byte span of the original text */
+export interface SpanEdit {
+  /** Start byte offset in the original text */
+  start: number
+  /** End byte offset in the original text (equal to start for insertions) */
+  end: number
+  /** Replacement text */
+  replacement: string
+}
+/** Mapping between a span of the edited text and the original text */
+export interface SourceMapSegment {
+  /** Start byte offset in the edited text */
+  generatedStart: number
+  /** End byte offset in the edited text */
+  generatedEnd: number
+  /** Start byte offset in the original text */
+  originalStart: number
+  /** End byte offset in the original text */
+  originalEnd: number
+  /** Whether the segment comes from an edit rather than the original text */
+  edited: boolean
+}
+/** Result of applying a batch of edits */
+export interface EditApplicationResult {
+  /** Edited text */
+  text: string
+  /** Segments covering the edited text in order */
+  sourceMap: Array<SourceMapSegment>
+  /** Number of edits applied */
+  applied: number
+}
+/** Indentation problem found on a single line */
+export interface IndentationIssue {
+  /** Line number (1-based) */
+  line: number
+  /** Issue kind ("mixed", "wrong_style", or "irregular_width") */
+  kind: string
+  /** Human-readable description */
+  message: string
+}
+/** Indentation analysis report */
+export interface IndentationReport {
+  /** Dominant indentation character ("spaces", "tabs", or "none") */
+  style: string
+  /** Inferred indentation width in columns (0 if unknown) */
+  width: number
+  /** Number of lines indented with spaces only */
+  spaceLines: number
+  /** Number of lines indented with tabs only */
+  tabLines: number
+  /** Number of lines mixing tabs and spaces in their indentation */
+  mixedLines: number
+  /** Lines whose indentation is inconsistent with the dominant style */
+  issues: Array<IndentationIssue>
+}
+/**
+ * Apply span edits in one pass using a piece table
+ *
+ * Edits may be given in any order. Insertions at the same offset keep their
+ * input order; overlapping spans, out-of-range offsets and offsets inside a
+ * UTF-8 character are rejected before anything is applied.
+ */
+export declare function applyTextEdits(text: string, edits: Array<SpanEdit>): EditApplicationResult
+/** Quick line ending normalization function */
+export declare function quickNormalizeLineEndings(text: string, lineEnding?: string | undefined | null): NormalizationResult
+/** Markdown heading */
+export interface MarkdownHeading {
+  /** Heading level (1-6) */
+  level: number
+  /** Heading text without markup */
+  text: string
+  /** Start byte offset in the source */
+  start: number
+  /** End byte offset in the source */
+  end: number
+  /** Line number (1-based) */
+  line: number
+}
+/** Markdown link or image reference */
+export interface MarkdownLink {
+  /** Link destination */
+  url: string
+  /** Link title (if any) */
+  title?: string
+  /** Link text or image alt text */
+  text: string
+  /** Whether this is an image */
+  isImage: boolean
+  /** Start byte offset in the source */
+  start: number
+  /** End byte offset in the source */
+  end: number
+  /** Line number (1-based) */
+  line: number
+}
+/** Fenced or indented code block */
+export interface MarkdownCodeBlock {
+  /** Info-string language (if any) */
+  language?: string
+  /** Code block content */
+  content: string
+  /** Start byte offset in the source */
+  start: number
+  /** End byte offset in the source */
+  end: number
+  /** Line number (1-based) */
+  line: number
+}
+/** Structured outline of a Markdown document */
+export interface DocumentOutline {
+  /** Headings in document order */
+  headings: Array<MarkdownHeading>
+  /** Links and images in document order */
+  links: Array<MarkdownLink>
+  /** Code blocks in document order */
+  codeBlocks: Array<MarkdownCodeBlock>
+  /** Plain text content with markup removed */
+  plainText: string
+}
+/** Extract headings, links, code blocks and plain text from Markdown */
+export declare function extractMarkdownStructure(text: string): DocumentOutline
+/** Strip HTML tags, drop script/style content and decode common entities */
+export declare function stripHtmlTags(html: string): string
+/** Configuration for CSV parsing */
+export interface CsvConfig {
+  /** Field delimiter (single ASCII character, e.g. "," or "	") */
+  delimiter: string
+  /** Quote character (single ASCII character) */
+  quote: string
+  /** Treat the first record as a header row */
+  hasHeaders: boolean
+  /**
+   * Allow records with differing field counts; otherwise every record
+   * must have as many fields as the header (or the first record)
+   */
+  flexible: boolean
+  /** Trim whitespace around fields */
+  trim: boolean
+  /** Maximum number of data rows to return (0 for unlimited) */
+  maxRows: number
+  /** Use parallel processing for large inputs */
+  useParallel: boolean
+}
+/** Parsed CSV data */
+export interface CsvParseResult {
+  /** Header names of the returned columns (empty without headers) */
+  headers: Array<string>
+  /** Data rows, projected to the requested columns */
+  rows: Array<Array<string>>
+  /** Number of rows returned */
+  rowCount: number
+  /** Whether rows were truncated by `max_rows` */
+  truncated: boolean
+}
+/** Quick CSV parsing with default configuration */
+export declare function quickParseCsv(text: string, delimiter?: string | undefined | null): CsvParseResult
+/**
+ * Encode bytes as base64
+ *
+ * # Arguments
+ * * `data` - Bytes to encode (strings are encoded as UTF-8)
+ * * `variant` - "base64" (default; padded standard alphabet) or "base64url"
+ *   (unpadded URL-safe alphabet), matching `buffer.toString(variant)`
+ */
+export declare function encodeBase64(data: string | Buffer, variant?: string | undefined | null): string
+/**
+ * Decode base64 into bytes
+ *
+ * Accepts the standard and URL-safe alphabets, with or without `=`
+ * padding, and ignores ASCII whitespace (e.g. line-wrapped PEM bodies).
+ * Unlike `Buffer.from(text, "base64")`, which stops at the first invalid
+ * character, malformed input is an error.
+ *
+ * # Arguments
+ * * `input` - Base64 text
+ */
+export declare function decodeBase64(input: string | Buffer): Buffer
+/**
+ * Encode bytes as hex
+ *
+ * # Arguments
+ * * `data` - Bytes to encode (strings are encoded as UTF-8)
+ * * `uppercase` - Use uppercase digits (default: false)
+ */
+export declare function encodeHex(data: string | Buffer, uppercase?: boolean | undefined | null): string
+/**
+ * Decode hex into bytes
+ *
+ * Digits may be upper- or lowercase. Odd-length input or any other
+ * character is an error.
+ *
+ * # Arguments
+ * * `input` - Hex text
+ */
+export declare function decodeHex(input: string | Buffer): Buffer
+/**
+ * Percent-encode bytes for use in a URL
+ *
+ * # Arguments
+ * * `data` - Bytes to encode (strings are encoded as UTF-8)
+ * * `mode` - "component" (default; same output as `encodeURIComponent`) or
+ *   "form" (same as `URLSearchParams`, with `+` for spaces)
+ */
+export declare function encodeUrl(data: string | Buffer, mode?: string | undefined | null): string
+/**
+ * Decode percent-encoded URL text into bytes
+ *
+ * Malformed escapes (a `%` not followed by two hex digits) are kept as
+ * they are, like `URLSearchParams` does.
+ *
+ * # Arguments
+ * * `input` - Encoded text
+ * * `mode` - "component" (default) or "form" (`+` decodes to a space)
+ */
+export declare function decodeUrl(input: string | Buffer, mode?: string | undefined | null): Buffer
+/**
+ * Convert identifiers to a target case style in one call
+ *
+ * # Arguments
+ * * `identifiers` - Identifiers to convert
+ * * `target_style` - "camel", "pascal", "snake", "kebab", "constant", or "title"
+ */
+export declare function convertCase(identifiers: Array<string>, targetStyle: string): Array<string>
+/** Split identifiers into words, handling acronyms and digits */
+export declare function splitIdentifiers(identifiers: Array<string>): Array<Array<string>>
+/**
+ * Detect the case style of each identifier
+ * Returns "camel", "pascal", "snake", "kebab", "constant", "title", "mixed", or "unknown"
+ */
+export declare function detectCaseStyles(identifiers: Array<string>): Array<string>
+/** Options for line sorting */
+export interface SortOptions {
+  /** Treat the input as a file path rather than text */
+  fromFile: boolean
+  /** Compare embedded numbers by value ("file2" < "file10") */
+  natural: boolean
+  /** Drop lines that compare equal to the previous line */
+  unique: boolean
+  /** Compare lines case-insensitively */
+  ignoreCase: boolean
+  /** Sort in descending order */
+  reverse: boolean
+  /** Memory budget in bytes before sorted runs are spilled to disk */
+  maxMemoryBytes: number
+  /** Write the result to this file instead of returning it */
+  outputPath?: string
+  /** Directory for temporary run files (system temp dir if omitted) */
+  tempDir?: string
+}
+/** Result of a sort operation */
+export interface SortResult {
+  /** Sorted text (when no output path was given) */
+  text?: string
+  /** Output file path (when one was given) */
+  outputPath?: string
+  /** Number of lines written */
+  lineCount: number
+  /** Number of duplicate lines removed */
+  duplicatesRemoved: number
+  /** Number of sorted runs spilled to disk (0 for in-memory sorts) */
+  spilledRuns: number
+}
+/**
+ * Sort lines of text or a file, spilling to disk when the memory budget is exceeded
+ *
+ * # Arguments
+ * * `input` - Text to sort, or a file path when `from_file` is set
+ * * `options` - Optional sort options
+ */
+export declare function sortLines(input: string, options?: SortOptions | undefined | null): SortResult
+/** Configuration for typo-tolerant lookups */
+export interface FuzzyMatchConfig {
+  /** Compare terms case-insensitively */
+  ignoreCase: boolean
+  /** Maximum number of results per query (0 for unlimited) */
+  maxResults: number
+  /** Use parallel processing for batch queries */
+  useParallel: boolean
+}
+/** Term found within the requested edit distance */
+export interface FuzzyMatch {
+  /** Matched term as it was added */
+  term: string
+  /** Levenshtein distance from the query */
+  distance: number
+}
+/** Double Metaphone encoding */
+export interface PhoneticCode {
+  /** Primary encoding */
+  primary: string
+  /** Alternate encoding (equal to primary when there is no alternative) */
+  alternate: string
+}
+/** Encode words with American Soundex */
+export declare function soundex(words: Array<string>): Array<string>
+/** Encode words with Double Metaphone (primary and alternate codes) */
+export declare function doubleMetaphone(words: Array<string>): Array<PhoneticCode>
+/** Quick typo-tolerant lookup against a candidate list */
+export declare function quickFuzzyLookup(query: string, candidates: Array<string>, maxDistance?: number | undefined | null): Array<FuzzyMatch>
+/** Highlighted span of source text */
+export interface HighlightToken {
+  /** Start byte offset in the source */
+  start: number
+  /** End byte offset in the source */
+  end: number
+  /** Line number (1-based) */
+  line: number
+  /** Innermost TextMate scope (e.g. "keyword.control.rust") */
+  scope: string
+  /**
+   * Coarse classification: "keyword", "string", "comment", "number",
+   * "constant", "function", "type", "variable", "operator",
+   * "punctuation", or "plain"
+   */
+  classification: string
+}
+/** Language supported by the highlighter */
+export interface HighlightLanguage {
+  /** Display name */
+  name: string
+  /** File extensions and tokens accepted as the language argument */
+  extensions: Array<string>
+}
+/**
+ * Tokenize source code into highlight spans
+ *
+ * # Arguments
+ * * `source` - Source text to highlight
+ * * `language` - Language name or file extension (e.g. "rs", "TypeScript");
+ *   detected from a shebang or modeline when omitted
+ */
+export declare function highlightTokens(source: string, language?: string | undefined | null): Array<HighlightToken>
+/** List languages supported by the highlighter */
+export declare function listHighlightLanguages(): Array<HighlightLanguage>
+/**
+ * Hash a file with streaming reads
+ *
+ * # Arguments
+ * * `path` - File to hash
+ * * `algorithm` - "blake3" (default) or "sha256"
+ */
+export declare function hashFile(path: string, algorithm?: string | undefined | null): string
+/**
+ * Hash an in-memory buffer in one call
+ *
+ * # Arguments
+ * * `data` - Bytes to hash
+ * * `algorithm` - "blake3" (default) or "sha256"
+ */
+export declare function hashBuffer(data: Buffer, algorithm?: string | undefined | null): string
+/**
+ * Compute a keyed MAC over a buffer
+ *
+ * # Arguments
+ * * `key` - Secret key (exactly 32 bytes for Blake3)
+ * * `data` - Bytes to authenticate
+ * * `algorithm` - "sha256" for HMAC-SHA256 (default) or "blake3" for keyed Blake3
+ */
+export declare function hmac(key: Buffer, data: Buffer, algorithm?: string | undefined | null): string
+/**
+ * Compute a keyed MAC over a file with streaming reads
+ *
+ * # Arguments
+ * * `key` - Secret key (exactly 32 bytes for Blake3)
+ * * `path` - File to authenticate
+ * * `algorithm` - "sha256" for HMAC-SHA256 (default) or "blake3" for keyed Blake3
+ */
+export declare function hmacFile(key: Buffer, path: string, algorithm?: string | undefined | null): string
+/**
+ * Verify a keyed MAC in constant time
+ *
+ * Returns false for a wrong or malformed `expected` value.
+ *
+ * # Arguments
+ * * `key` - Secret key (exactly 32 bytes for Blake3)
+ * * `data` - Bytes that were authenticated
+ * * `expected` - Hex MAC to check
+ * * `algorithm` - "sha256" for HMAC-SHA256 (default) or "blake3" for keyed Blake3
+ */
+export declare function verifyHmac(key: Buffer, data: Buffer, expected: string, algorithm?: string | undefined | null): boolean
+/** Configuration for license detection */
+export interface LicenseConfig {
+  /** Minimum confidence (0-1) for a license text match to be reported */
+  minConfidence: number
+  /** Match license texts in every file, not only license-named files */
+  matchAllFiles: boolean
+  /** Traversal and exclusion settings for directory scans */
+  search?: FileSearchConfig
+}
+/** A license identified in a file */
+export interface LicenseMatch {
+  /** SPDX identifier of the license */
+  spdxId: string
+  /** How it was found: "spdx_tag", "exact_text", "fuzzy_text" or "phrase" */
+  kind: string
+  /** Full SPDX expression the identifier came from (tags only) */
+  expression?: string
+  /** Line number of the tag (1-based, tags only) */
+  lineNumber?: number
+  /** Confidence of the identification (0-1) */
+  confidence: number
+}
+/** Licenses found in one file */
+export interface FileLicenses {
+  /** File path */
+  path: string
+  /** Whether the file name marks it as a license file */
+  isLicenseFile: boolean
+  /** Licenses identified in the file */
+  licenses: Array<LicenseMatch>
+}
+/** Number of files declaring one license */
+export interface LicenseCount {
+  /** SPDX identifier */
+  spdxId: string
+  /** Files in which it was found */
+  fileCount: number
+}
+/** License report for a directory tree */
+export interface LicenseReport {
+  /** Files in which at least one license was found */
+  files: Array<FileLicenses>
+  /** Per-license file counts, most common first */
+  licenses: Array<LicenseCount>
+  /** License-named files whose text was not recognized */
+  unidentifiedLicenseFiles: Array<string>
+  /** Number of files scanned */
+  filesScanned: number
+}
+/** Quick license detection over text with default settings */
+export declare function quickDetectLicenses(text: string): Array<LicenseMatch>
+/** A resolved third-party dependency */
+export interface Dependency {
+  /** Package name (npm names keep their @scope) */
+  name: string
+  /** Resolved version */
+  version: string
+  /** OSV ecosystem: "npm", "crates.io" or "PyPI" */
+  ecosystem: string
+  /** Package URL (purl) */
+  purl: string
+  /** Declared license, when the lockfile records it */
+  license?: string
+  /** Whether the package is only needed for development */
+  dev: boolean
+  /** Lockfile the entry was read from */
+  source: string
+}
+/**
+ * Parse a single lockfile
+ *
+ * # Arguments
+ * * `path` - Path to a package-lock.json, Cargo.lock or requirements.txt
+ */
+export declare function parseLockfile(path: string): Array<Dependency>
+/**
+ * List dependencies from every lockfile below a directory
+ *
+ * # Arguments
+ * * `root_path` - Project directory
+ * * `search` - Optional traversal settings (node_modules and target are skipped by default)
+ */
+export declare function listDependencies(rootPath: string, search?: FileSearchConfig | undefined | null): Array<Dependency>
+/** Options for SBOM generation */
+export interface SbomOptions {
+  /** Include development-only dependencies (marked with scope "excluded") */
+  includeDevDependencies: boolean
+  /** Project name (defaults to package.json, Cargo.toml or the directory name) */
+  projectName?: string
+  /** Project version (defaults to package.json or Cargo.toml) */
+  projectVersion?: string
+  /** Traversal settings used to find lockfiles */
+  search?: FileSearchConfig
+}
+/**
+ * Generate a CycloneDX JSON SBOM for a project directory
+ *
+ * # Arguments
+ * * `root_path` - Project directory
+ * * `options` - Optional generation settings
+ */
+export declare function generateSbom(rootPath: string, options?: SbomOptions | undefined | null): string
+/** A dependency affected by an advisory */
+export interface VulnerabilityMatch {
+  /** Advisory identifier (e.g. "GHSA-xxxx-xxxx-xxxx", "RUSTSEC-2024-0001") */
+  advisoryId: string
+  /** Other identifiers for the same issue (e.g. CVE numbers) */
+  aliases: Array<string>
+  /** One-line advisory summary */
+  summary?: string
+  /** Affected package name */
+  package: string
+  /** Installed version that matched */
+  version: string
+  /** OSV ecosystem of the package */
+  ecosystem: string
+  /** Severity label from the database (e.g. "HIGH", "MODERATE") */
+  severity?: string
+  /** CVSS vector, if the advisory provides one */
+  cvss?: string
+  /** Versions that fix the issue */
+  fixedVersions: Array<string>
+  /** Affected ranges in readable form (e.g. ">=1.0.0, <1.2.3") */
+  affectedRanges: Array<string>
+}
+/**
+ * Load an OSV database and match dependencies in one call
+ *
+ * # Arguments
+ * * `dependencies` - Inventory from `list_dependencies` or `parse_lockfile`
+ * * `advisory_db_path` - JSON array, JSON Lines file or directory of OSV JSON files
+ */
+export declare function matchVulnerabilities(dependencies: Array<Dependency>, advisoryDbPath: string): Array<VulnerabilityMatch>
+/** Audit log settings */
+export interface AuditConfig {
+  /** Maximum events kept in memory; the oldest are dropped first */
+  capacity: number
+  /** Key/value context attached to every event (e.g. agent or task id) */
+  context?: Record<string, string>
+}
+/** A recorded operation */
+export interface AuditEvent {
+  /** Monotonic sequence number, starting at 1 */
+  sequence: number
+  /** RFC 3339 UTC timestamp */
+  timestamp: string
+  /** Event kind: "path_validation", "access_denied" or "file_mutation" */
+  kind: string
+  /** Native operation that produced the event (e.g. "validate_path") */
+  operation: string
+  /** Path the operation concerned */
+  path: string
+  /** Result: "allowed", "denied", "success" or "failure" */
+  outcome: string
+  /** Violation, denial reason or error, when there is one */
+  detail?: string
+  /** Caller-provided context at the time of the event */
+  context?: Record<string, string>
+}
+/** Current state of the audit log */
+export interface AuditStatus {
+  /** Whether events are being recorded */
+  enabled: boolean
+  /** Events currently buffered */
+  buffered: number
+  /** Buffer capacity */
+  capacity: number
+  /** Events dropped because the buffer was full */
+  dropped: number
+}
+/**
+ * Start recording events, replacing any previous buffer
+ *
+ * # Arguments
+ * * `config` - Optional buffer capacity and context
+ */
+export declare function enableAuditLog(config?: AuditConfig | undefined | null): void
+/** Stop recording; buffered events stay readable until cleared */
+export declare function disableAuditLog(): void
+/**
+ * Replace the context attached to subsequent events
+ *
+ * # Arguments
+ * * `context` - Key/value pairs, or nothing to clear the context
+ */
+export declare function setAuditContext(context?: Record<string, string> | undefined | null): void
+/**
+ * Buffered events, oldest first
+ *
+ * # Arguments
+ * * `since_sequence` - Only return events after this sequence number
+ */
+export declare function getAuditEvents(sinceSequence?: number | undefined | null): Array<AuditEvent>
+/**
+ * Append buffered events to a JSON Lines file and empty the buffer
+ *
+ * Returns the number of events written.
+ *
+ * # Arguments
+ * * `path` - File to append to (created if missing)
+ */
+export declare function flushAuditLog(path: string): number
+/** Discard buffered events without writing them */
+export declare function clearAuditLog(): void
+/** Whether the log is recording, and how full the buffer is */
+export declare function getAuditStatus(): AuditStatus
+/**
+ * Take an advisory lock on a file
+ *
+ * Waits for other holders to release it, polling with backoff, and fails
+ * with `TIMEOUT` once `timeout_ms` has passed. A timeout of 0 tries once.
+ * Release the returned lock with `release()`; it is also released if the
+ * handle is garbage collected or the process exits.
+ *
+ * # Arguments
+ * * `path` - File to lock (`<path>.lock` is created next to it)
+ * * `timeout_ms` - Maximum wait in milliseconds (default 10000)
+ * * `shared` - Take a shared lock for reading instead of an exclusive one
+ */
+export declare function acquireLock(path: string, timeoutMs?: number | undefined | null, shared?: boolean | undefined | null): FileLock
+/** Options for `writeFileAtomic` */
+export interface AtomicWriteOptions {
+  /** Flush the file and its directory to disk before returning */
+  fsync: boolean
+  /** Unix permission bits for the file, e.g. 0o600 (overrides `preserve_mode`) */
+  mode?: number
+  /** Keep the permission bits of the file being replaced */
+  preserveMode: boolean
+}
+/**
+ * Write a file atomically: temp file, optional fsync, then rename
+ *
+ * # Arguments
+ * * `path` - File to create or replace (its directory must exist)
+ * * `contents` - Text or bytes to write
+ * * `options` - Optional fsync and permission settings (fsync and preserve_mode default to true)
+ */
+export declare function writeFileAtomic(path: string, contents: string | Buffer, options?: AtomicWriteOptions | undefined | null): void
+/** Options for compressing */
+export interface CompressionOptions {
+  /** "zstd" (default) or "gzip" */
+  format?: string
+  /**
+   * Compression level: zstd 1-22 (default 3; negative levels trade ratio
+   * for speed), gzip 0-9 (default 6)
+   */
+  level?: number
+}
+/** Options for decompressing */
+export interface DecompressionOptions {
+  /** "zstd", "gzip" or "auto" (default; detected from the magic bytes) */
+  format?: string
+  /** Fail if the output would be larger than this many bytes */
+  maxOutputBytes?: number
+}
+/** Result of compressing or decompressing a file */
+export interface CompressionResult {
+  /** Format of the compressed side: "zstd" or "gzip" */
+  format: string
+  /** Bytes read from the source */
+  bytesRead: number
+  /** Bytes written to the destination */
+  bytesWritten: number
+}
+/**
+ * Compress a buffer
+ *
+ * # Arguments
+ * * `data` - Bytes to compress (strings are encoded as UTF-8)
+ * * `options` - Optional format and level
+ */
+export declare function compress(data: string | Buffer, options?: CompressionOptions | undefined | null): Buffer
+/**
+ * Decompress a buffer
+ *
+ * # Arguments
+ * * `data` - Compressed bytes
+ * * `options` - Optional format (detected by default) and output limit
+ */
+export declare function decompress(data: Buffer, options?: DecompressionOptions | undefined | null): Buffer
+/**
+ * Compress a file into another with streaming reads and writes
+ *
+ * # Arguments
+ * * `source` - File to compress
+ * * `destination` - Compressed file to create or replace
+ * * `options` - Optional format and level
+ */
+export declare function compressFile(source: string, destination: string, options?: CompressionOptions | undefined | null): CompressionResult
+/**
+ * Decompress a file into another with streaming reads and writes
+ *
+ * # Arguments
+ * * `source` - Compressed file
+ * * `destination` - File to create or replace
+ * * `options` - Optional format (detected by default) and output limit
+ */
+export declare function decompressFile(source: string, destination: string, options?: DecompressionOptions | undefined | null): CompressionResult
+/** Native memory usage of the process */
+export interface MemoryStats {
+  /** Bytes currently allocated by native code */
+  allocatedBytes: number
+  /** Most bytes allocated at once since start-up */
+  peakAllocatedBytes: number
+  /** Allocations since start-up */
+  allocationCount: number
+  /** Deallocations since start-up */
+  deallocationCount: number
+  /** Bytes allocated since start-up, including freed ones */
+  totalAllocatedBytes: number
+  /** Resident set size of the whole process (estimated on Linux) */
+  currentRssBytes: number
+  /** Peak resident set size of the whole process */
+  peakRssBytes: number
+  /** Memory committed by mimalloc */
+  committedBytes: number
+  /** Peak memory committed by mimalloc */
+  peakCommittedBytes: number
+  /** Hard page faults of the process */
+  pageFaults: number
+}
+/** Get native allocator and process memory statistics */
+export declare function getMemoryStats(): MemoryStats
+/** Synthetic file tree settings */
+export interface SyntheticTreeConfig {
+  /** Seed of the generator */
+  seed: number
+  /** Number of files to write */
+  fileCount: number
+  /** Number of directories to spread the files over */
+  directoryCount: number
+  /** Deepest directory nesting below the root */
+  maxDepth: number
+  /** Smallest file size in bytes */
+  minFileSize: number
+  /** Largest file size in bytes */
+  maxFileSize: number
+  /** Languages to write: "rust", "javascript", "typescript", "python", "go" and/or "markdown" */
+  languages: Array<string>
+  /** Fraction of files that are exact copies of an earlier file, between 0 and 1 */
+  duplicateRatio: number
+}
+/** What `generate_synthetic_tree` wrote */
+export interface SyntheticTreeSummary {
+  /** Root directory of the tree */
+  root: string
+  /** Files written */
+  files: number
+  /** Directories created below the root */
+  directories: number
+  /** Bytes written */
+  totalBytes: number
+  /** Files that copy an earlier file */
+  duplicates: number
+  /** Files per language */
+  filesByLanguage: Record<string, number>
+}
+/** Synthetic embedding corpus settings */
+export interface SyntheticEmbeddingConfig {
+  /** Seed of the generator */
+  seed: number
+  /** Number of vectors */
+  count: number
+  /** Dimensions of each vector */
+  dimensions: number
+  /** Number of clusters the vectors are drawn around */
+  clusters: number
+  /** Standard deviation of the noise added to the cluster centers */
+  noise: number
+  /** Scale every vector to unit length */
+  normalize: boolean
+}
+/** Generated embedding corpus */
+export interface SyntheticEmbeddings {
+  /** Dimensions of each vector */
+  dimensions: number
+  /** Number of vectors */
+  count: number
+  /** Vectors, flattened row by row */
+  vectors: Array<number>
+  /** Cluster of each vector */
+  labels: Array<number>
+}
+/**
+ * Write a deterministic synthetic file tree
+ *
+ * # Arguments
+ * * `root` - Directory to fill; must not exist or be empty
+ * * `config` - Optional tree settings
+ */
+export declare function generateSyntheticTree(root: string, config?: SyntheticTreeConfig | undefined | null): SyntheticTreeSummary
+/**
+ * Generate a deterministic clustered embedding corpus
+ *
+ * # Arguments
+ * * `config` - Optional corpus settings
+ */
+export declare function generateSyntheticEmbeddings(config?: SyntheticEmbeddingConfig | undefined | null): SyntheticEmbeddings
+/** Category of a native error */
+export const enum ErrorCode {
+  /** An option or argument has an invalid value */
+  InvalidArgument = 'INVALID_ARGUMENT',
+  /** A file or directory does not exist */
+  PathNotFound = 'PATH_NOT_FOUND',
+  /** The operating system, sandbox or policy refused access */
+  PermissionDenied = 'PERMISSION_DENIED',
+  /** The target already exists and will not be overwritten */
+  AlreadyExists = 'ALREADY_EXISTS',
+  /** A glob, regex or rule pattern failed to compile */
+  PatternInvalid = 'PATTERN_INVALID',
+  /** Input data (JSON, TOML, CSV, lockfile, ...) is malformed */
+  ParseError = 'PARSE_ERROR',
+  /** A configured quota would be exceeded */
+  QuotaExceeded = 'QUOTA_EXCEEDED',
+  /**
+   * A configured limit (e.g. `max_entries`) stopped the operation before
+   * it finished, and the result can't be returned as partial
+   */
+  LimitReached = 'LIMIT_REACHED',
+  /** Stored data failed a digest or signature check */
+  IntegrityError = 'INTEGRITY_ERROR',
+  /** The operation ran out of time */
+  Timeout = 'TIMEOUT',
+  /** The operation was cancelled by the caller */
+  Cancelled = 'CANCELLED',
+  /** A JavaScript callback threw or could not be called */
+  CallbackFailed = 'CALLBACK_FAILED',
+  /** Reading or writing failed for another I/O reason */
+  IoError = 'IO_ERROR',
+  /** An unexpected failure inside the native module */
+  Internal = 'INTERNAL'
+}
+/**
+ * Extract the error code from a native error message
+ *
+ * # Arguments
+ * * `message` - The `message` of an error thrown by this module
+ *
+ * Returns `null` when the message carries no known code.
+ */
+export declare function parseErrorCode(message: string): ErrorCode | null
+/**
+ * Get native-layer metrics as a JSON string
+ *
+ * The object has `window_ms`, per-operation `operations` (calls, errors,
+ * panics and total/mean/max milliseconds), `bytes_processed` per module,
+ * `caches` with hits, misses and hit rate, and `limits_reached` with how
+ * often each resource or walk limit was reached.
+ */
+export declare function getMetrics(): string
+/** Clear all metrics and start a new collection window */
+export declare function resetMetrics(): void
+/** Resolved project configuration */
+export interface MoidvkConfig {
+  /** Path of the loaded `moidvk.toml`, or null when only defaults apply */
+  source?: string
+  /** Absolute project root that relative paths were resolved against */
+  root: string
+  /**
+   * Search settings for FileSearch and every module's `search` option;
+   * includes the sandbox when one is configured
+   */
+  search: FileSearchConfig
+  /** Sandbox policy with absolute roots, if configured */
+  sandbox?: SandboxPolicy
+  /** Worker threads for parallel operations (0 for one per CPU) */
+  threads: number
+  /** Limits on concurrent heavy operations, for `setConcurrencyLimits` */
+  concurrency: ConcurrencyLimits
+  /** Where persistent indexes are stored */
+  index: IndexLocations
+}
+/** Absolute locations of persistent indexes */
+export interface IndexLocations {
+  /** Directory for search indexes */
+  directory: string
+  /** Directory for cached embeddings */
+  embeddings: string
+}
+/**
+ * Load `moidvk.toml` from a project root
+ *
+ * Missing files are not an error: the defaults are returned with a null
+ * `source`.
+ *
+ * # Arguments
+ * * `project_root` - Directory containing `moidvk.toml`
+ */
+export declare function loadConfig(projectRoot: string): MoidvkConfig
+/**
+ * Validate and resolve a `moidvk.toml` document without reading it from disk
+ *
+ * # Arguments
+ * * `source` - TOML document
+ * * `project_root` - Directory relative paths are resolved against
+ */
+export declare function parseConfig(source: string, projectRoot: string): MoidvkConfig
+/**
+ * Size the global thread pool used by parallel operations
+ *
+ * Must run before the first parallel operation; the pool cannot be resized
+ * once it has started.
+ *
+ * # Arguments
+ * * `threads` - Worker count (0 for one per CPU), e.g. `config.threads`
+ *
+ * Returns the number of worker threads
+ */
+export declare function configureThreadPool(threads: number): number
+/**
+ * Detect a file's language from its name, extension, `#!` line or modeline
+ *
+ * Returns a display name such as "Python", or null if unrecognised.
+ */
+export declare function detectLanguage(path: string): string | null
+/**
+ * Drop every cached `FileSearch` result
+ *
+ * Returns the number of results dropped.
+ */
+export declare function clearCache(): number
+/**
+ * Drop cached results affected by a change to `path`
+ *
+ * Call this from file watcher events when using `result_cache: "events"`.
+ * Results for any root containing `path`, or inside it, are dropped.
+ * Returns the number of results dropped.
+ */
+export declare function invalidateCachedResults(path: string): number
+/**
+ * Release a paged search before reaching its last page
+ *
+ * Returns whether the cursor was still open.
+ */
+export declare function closeSearchCursor(cursor: string): boolean
+/** An entry left out of a scan */
+export interface SkippedEntry {
+  /** Path of the entry, as produced by the walk */
+  path: string
+  /**
+   * "permission_denied", "not_found", "symlink_loop", "symlink_depth",
+   * "entry_budget", "io_error", "hidden", "excluded", "too_large",
+   * "sandbox", "language", "binary", "decode_error" or "minified"
+   */
+  reason: string
+  /** Specifics, such as the exclude pattern or the OS error */
+  detail?: string
+}
+/** Entries a scan left out */
+export interface SkipReport {
+  /** The first skipped entries, sorted by path */
+  entries: Array<SkippedEntry>
+  /** Entries skipped in total, including those not listed */
+  total: number
+  /** Skipped entries per reason */
+  byReason: Record<string, number>
+}
+/** Stored metadata of one file */
+export interface FileRecord {
+  /** Path as walked or passed to `updatePaths` */
+  path: string
+  /** File size in bytes */
+  size: number
+  /** Last modified timestamp (milliseconds since Unix epoch) */
+  lastModified: number
+  /** Blake3 hash of the contents, hex-encoded */
+  hash: string
+  /** Detected language */
+  language?: string
+  /** Non-blank lines (0 for binary files) */
+  linesOfCode: number
+}
+/** Filters for `FileMetadataDb.query` */
+export interface MetadataQuery {
+  /** Only files under this directory */
+  under?: string
+  /** Only files detected as this language (case-insensitive) */
+  language?: string
+  /** Only files of at least this many bytes */
+  minSize?: number
+  /** Only files of at most this many bytes */
+  maxSize?: number
+  /** Only files modified at or after this time (milliseconds since Unix epoch) */
+  modifiedSince?: number
+  /** Maximum records returned, in path order */
+  limit?: number
+}
+/** Changes made to the store by `refresh` or `updatePaths` */
+export interface MetadataRefresh {
+  /** Files recorded for the first time */
+  added: Array<string>
+  /** Files re-read because their size or modification time changed */
+  modified: Array<string>
+  /** Files whose records were dropped because they are gone */
+  removed: Array<string>
+  /** Files that could not be read; their records were dropped */
+  failed: Array<string>
+  /** Files whose records were still current */
+  unchanged: number
+  /**
+   * Whether the walk was cut short by `max_entries` or `max_results`;
+   * records of files it didn't reach were kept rather than dropped
+   */
+  truncated: boolean
+}
+/** Files from `findFilesCompact`, with directory paths stored once */
+export interface CompactFileList {
+  /** Root the directories are relative to, as given */
+  root: string
+  /** Directories holding matches, relative to `root` ("" for the root itself) */
+  directories: Array<string>
+  /** Matching files, in the order `findFilesByPattern` returns them */
+  files: Array<CompactFileInfo>
+}
+/** A file of a `CompactFileList` */
+export interface CompactFileInfo {
+  /** Index of the file's directory in `directories` */
+  directory: number
+  /** File name */
+  name: string
+  /** File size in bytes */
+  size: number
+  /** Last modified timestamp (milliseconds since Unix epoch) */
+  lastModified: number
+  /** Is directory */
+  isDirectory: boolean
+  /** File extension (if any) */
+  extension?: string
+}
+/** Matches from `searchTextCompact`, with file paths stored once */
+export interface CompactTextResults {
+  /** Root the file paths are relative to, as given */
+  root: string
+  /** Files with matches, relative to `root` */
+  files: Array<string>
+  /** Matches, in the order `searchTextInFiles` returns them */
+  matches: Array<CompactTextMatch>
+}
+/** A match of `CompactTextResults`; the search text is not repeated */
+export interface CompactTextMatch {
+  /** Index of the file in `files` */
+  file: number
+  /** Line number (1-based) */
+  lineNumber: number
+  /** Column start position */
+  columnStart: number
+  /** Column end position */
+  columnEnd: number
+  /** The matching line content */
+  lineContent: string
+  /** The line cut down around the match (only with `snippet_width`) */
+  snippet?: SearchSnippet
+}
+/** A named vector set visible to every worker */
+export interface SharedVectorSetInfo {
+  /** Name the set was shared under */
+  name: string
+  /** Number of vectors */
+  count: number
+  /** Size of each vector */
+  vectorSize: number
+  /** Memory held by the vectors in bytes */
+  bytes: number
+}
+/** Sizes of the process-wide caches and stores */
+export interface SharedStateInfo {
+  /** Cached single-glob matchers */
+  globs: number
+  /** Cached glob sets (exclude lists, sandbox denials) */
+  globSets: number
+  /** Cached regexes */
+  regexes: number
+  /** Shared vector sets */
+  vectorSets: Array<SharedVectorSetInfo>
+}
+/**
+ * Store vectors under a name so every worker thread can search them
+ *
+ * Vectors are kept as 32-bit floats. Sharing under an existing name replaces
+ * the previous set; searches already running keep the old one.
+ * To build a large set without one array holding every vector, use a
+ * `VectorSetWriter`.
+ *
+ * # Arguments
+ * * `name` - Name to share the set under
+ * * `vectors_flat` - Flattened vectors (count * vector_size)
+ * * `vector_size` - Size of each vector
+ * * `paths` - Path or identifier of each vector
+ */
+export declare function shareVectors(name: string, vectorsFlat: Array<number>, vectorSize: number, paths: Array<string>): SharedVectorSetInfo
+/**
+ * Remove a shared vector set
+ *
+ * Returns `false` when no set had that name. Memory is released once no
+ * search is using the set.
+ */
+export declare function dropSharedVectors(name: string): boolean
+/** Describe the process-wide caches and shared vector sets */
+export declare function getSharedStateInfo(): SharedStateInfo
+/**
+ * Empty the compiled pattern caches
+ *
+ * Shared vector sets are kept; remove them with `dropSharedVectors`.
+ */
+export declare function clearMatcherCaches(): void
+/** Limits on concurrent heavy operations */
+export interface ConcurrencyLimits {
+  /** Heavy operations allowed to run at once (0 for unlimited) */
+  maxHeavyOperations: number
+  /**
+   * Milliseconds a queued operation waits before failing with TIMEOUT
+   * (0 to wait indefinitely)
+   */
+  queueTimeoutMs: number
+  /**
+   * Files parallel reads may hold open at once (0 for unlimited; omit for
+   * half the soft `ulimit -n`, between 16 and 4096)
+   */
+  maxOpenFiles?: number
+}
+/** Current state of the limiter */
+export interface ConcurrencyStats {
+  /** Limits in effect */
+  limits: ConcurrencyLimits
+  /** Heavy operations currently running */
+  running: number
+  /** Heavy operations waiting for a permit */
+  queued: number
+  /** Longest queue seen since the last reset */
+  peakQueued: number
+  /** Permits granted since the last reset */
+  granted: number
+  /** Total milliseconds spent waiting for permits since the last reset */
+  totalWaitMs: number
+  /** Operations that gave up waiting since the last reset */
+  timeouts: number
+  /** Files currently open for parallel reads */
+  openFiles: number
+  /** Most files open at once since the last reset */
+  peakOpenFiles: number
+  /** Opens that waited for the file budget since the last reset */
+  fileBudgetWaits: number
+  /** Opens retried after `EMFILE` since the last reset */
+  emfileRetries: number
+}
+/**
+ * Set the limits on concurrent heavy operations
+ *
+ * Operations already running keep their permits; raising the limit starts
+ * queued operations immediately.
+ *
+ * # Arguments
+ * * `limits` - New limits, e.g. `config.concurrency` from `loadConfig`
+ */
+export declare function setConcurrencyLimits(limits: ConcurrencyLimits): void
+/** Get the limits, running and queued operations, open files and wait totals */
+export declare function getConcurrencyStats(): ConcurrencyStats
+/** Clear the peak queue length, peak open files and wait totals */
+export declare function resetConcurrencyStats(): void
+/** State of the background pool */
+export interface BackgroundPoolInfo {
+  /** Whether the pool has been started by a background scan */
+  started: boolean
+  /** Worker threads in the pool */
+  threads: number
+  /** Workers running with lowered CPU priority */
+  cpuLowered: number
+  /** Workers running with lowered I/O priority */
+  ioLowered: number
+}
+/** Get the size of the background pool and how many workers were deprioritized */
+export declare function getBackgroundPoolInfo(): BackgroundPoolInfo
+/** Configuration for `chunkFiles` and `chunkFilesIncremental` */
+export interface ChunkConfig {
+  /** Maximum size of a chunk in bytes */
+  maxChunkBytes: number
+  /** Lines repeated at the start of the next chunk */
+  overlapLines: number
+  /** Drop chunks that contain only whitespace */
+  skipBlank: boolean
+  /** Traversal and exclusion settings for the file search */
+  search?: FileSearchConfig
+}
+/** A chunk of a file, ready to embed */
+export interface ChunkRecord {
+  /**
+   * Stable identifier built from the path and hash, with a counter for
+   * repeated identical chunks; unchanged chunks keep their id when other
+   * chunks are inserted or removed before them
+   */
+  id: string
+  /** File path */
+  path: string
+  /** Position of the chunk within its file (0-based) */
+  chunkIndex: number
+  /** Byte offset of the chunk start */
+  start: number
+  /** Byte offset just past the chunk */
+  end: number
+  /** First line of the chunk (1-based) */
+  startLine: number
+  /** Last line of the chunk (1-based, inclusive) */
+  endLine: number
+  /** Chunk text */
+  text: string
+  /** Blake3 hash of the chunk text (hex) */
+  hash: string
+}
+/** Chunks produced by `chunkFiles` */
+export interface ChunkResult {
+  /** Chunks of every matched file, ordered by path and position */
+  chunks: Array<ChunkRecord>
+  /** Files matching the pattern */
+  filesMatched: number
+  /** Matched files skipped as binary or unreadable */
+  filesSkipped: number
+  /** Bytes of text read from the matched files */
+  bytesRead: number
+}
+/** State of one chunked file, as recorded in a snapshot */
+export interface SnapshotFile {
+  /** File path */
+  path: string
+  /** File size in bytes */
+  size: number
+  /** Last modified timestamp (milliseconds since Unix epoch) */
+  modifiedMs: number
+  /** Blake3 hash of the file content (hex) */
+  hash: string
+  /** IDs of the file's chunks, in order */
+  chunkIds: Array<string>
+}
+/** Files chunked by a run of `chunkFilesIncremental`, to pass to the next run */
+export interface ChunkSnapshot {
+  /** `max_chunk_bytes` the chunks were made with */
+  maxChunkBytes: number
+  /** `overlap_lines` the chunks were made with */
+  overlapLines: number
+  /** `skip_blank` the chunks were made with */
+  skipBlank: boolean
+  /** Chunked files, ordered by path */
+  files: Array<SnapshotFile>
+}
+/** Changes since a previous snapshot */
+export interface IncrementalChunkResult {
+  /** Chunks not present in the previous snapshot, ordered by path and position */
+  chunks: Array<ChunkRecord>
+  /** IDs of chunks in the previous snapshot that no longer exist */
+  deletedIds: Array<string>
+  /** Files not in the previous snapshot */
+  addedFiles: Array<string>
+  /** Files whose content changed */
+  changedFiles: Array<string>
+  /** Files in the previous snapshot that are gone, now binary or unreadable */
+  removedFiles: Array<string>
+  /** Files left as they were */
+  unchangedFiles: number
+  /**
+   * Bytes of text read (unchanged files with the same size and
+   * modification time are not read)
+   */
+  bytesRead: number
+  /** Snapshot to pass to the next run */
+  snapshot: ChunkSnapshot
+}
+/**
+ * Find files matching a glob, read them and split their text into chunks
+ *
+ * The pattern is matched against the full path, as in
+ * `FileSearch.findFilesByPattern`. Binary files are skipped.
+ *
+ * # Arguments
+ * * `root_path` - Directory to search
+ * * `pattern` - Glob the file paths must match
+ * * `config` - Optional chunk sizes and search settings
+ */
+export declare function chunkFiles(rootPath: string, pattern: string, config?: ChunkConfig | undefined | null): ChunkResult
+/**
+ * Chunk only what changed since a previous snapshot
+ *
+ * Files whose size and modification time match the snapshot are not read;
+ * files whose content hash matches are not re-chunked. When the chunk
+ * settings differ from the snapshot's, every file is re-chunked.
+ *
+ * # Arguments
+ * * `root_path` - Directory to search
+ * * `pattern` - Glob the file paths must match
+ * * `previous` - Snapshot from the previous run (omit for a full run)
+ * * `config` - Optional chunk sizes and search settings
+ */
+export declare function chunkFilesIncremental(rootPath: string, pattern: string, previous?: ChunkSnapshot | undefined | null, config?: ChunkConfig | undefined | null): IncrementalChunkResult
+/** Whether a mutating operation only plans its changes or applies them */
+export const enum ExecutionMode {
+  /** Return the planned changes without touching disk */
+  DryRun = 'dry_run',
+  /** Apply the changes */
+  Execute = 'execute'
+}
+/** Options for `replaceInFiles` */
+export interface ReplaceOptions {
+  /** Treat the pattern as a regex; the replacement may then use `$1` or `${name}` */
+  regex: boolean
+  /** Match case exactly */
+  caseSensitive: boolean
+}
+/** A source and destination for a copy or move */
+export interface FileTransfer {
+  /** File to copy or move */
+  source: string
+  /** Path to copy or move it to */
+  destination: string
+}
+/** A change to one file, made or planned */
+export interface FileChange {
+  /** "write", "copy", "move" or "delete" */
+  kind: string
+  /** File that is written, copied, moved or deleted */
+  path: string
+  /** Destination of a copy or move */
+  destination?: string
+  /** Bytes written to the file or destination (0 for deletes) */
+  bytes: number
+  /** Whether an existing file is replaced */
+  overwrites: boolean
+  /** Text edits of a write, in byte offsets of the current contents */
+  edits: Array<TextEdit>
+}
+/** Result of a bulk operation */
+export interface FileChangeSet {
+  /** Whether the changes were applied (false for a dry run) */
+  applied: boolean
+  /** Changes in the order they are applied */
+  changes: Array<FileChange>
+  /** Files left alone: unchanged by the operation, or not UTF-8 text */
+  unchanged: Array<string>
+  /** Total bytes written */
+  bytesWritten: number
+}
+/**
+ * Replace a literal string or regex in a set of files
+ *
+ * Files that are not valid UTF-8, or in which nothing matches, are listed
+ * in `unchanged`.
+ *
+ * # Arguments
+ * * `paths` - Files to edit
+ * * `pattern` - Text or regex to find
+ * * `replacement` - Replacement text
+ * * `options` - Optional regex and case settings
+ * * `mode` - `dry_run` or `execute` (default)
+ * * `transaction` - Journal transaction to record the changes in
+ * * `sandbox` - Sandbox policy every path must satisfy
+ */
+export declare function replaceInFiles(paths: Array<string>, pattern: string, replacement: string, options?: ReplaceOptions | undefined | null, mode?: ExecutionMode | undefined | null, transaction?: string | undefined | null, sandbox?: SandboxPolicy | undefined | null): FileChangeSet
+/**
+ * Normalize line endings and trailing whitespace in a set of files
+ *
+ * Uses the same rules as `TextFormatter.normalizeLineEndings`.
+ *
+ * # Arguments
+ * * `paths` - Files to normalize
+ * * `config` - Optional target line ending and whitespace settings
+ * * `mode` - `dry_run` or `execute` (default)
+ * * `transaction` - Journal transaction to record the changes in
+ * * `sandbox` - Sandbox policy every path must satisfy
+ */
+export declare function normalizeFiles(paths: Array<string>, config?: FormatConfig | undefined | null, mode?: ExecutionMode | undefined | null, transaction?: string | undefined | null, sandbox?: SandboxPolicy | undefined | null): FileChangeSet
+/**
+ * Copy files to new paths
+ *
+ * # Arguments
+ * * `transfers` - Sources and destinations
+ * * `overwrite` - Replace existing destinations (default false)
+ * * `mode` - `dry_run` or `execute` (default)
+ * * `transaction` - Journal transaction to record the changes in
+ * * `sandbox` - Sandbox policy every path must satisfy
+ */
+export declare function copyFiles(transfers: Array<FileTransfer>, overwrite?: boolean | undefined | null, mode?: ExecutionMode | undefined | null, transaction?: string | undefined | null, sandbox?: SandboxPolicy | undefined | null): FileChangeSet
+/**
+ * Move files to new paths
+ *
+ * Moves across filesystems fall back to copy and delete.
+ *
+ * # Arguments
+ * * `transfers` - Sources and destinations
+ * * `overwrite` - Replace existing destinations (default false)
+ * * `mode` - `dry_run` or `execute` (default)
+ * * `transaction` - Journal transaction to record the changes in
+ * * `sandbox` - Sandbox policy every path must satisfy
+ */
+export declare function moveFiles(transfers: Array<FileTransfer>, overwrite?: boolean | undefined | null, mode?: ExecutionMode | undefined | null, transaction?: string | undefined | null, sandbox?: SandboxPolicy | undefined | null): FileChangeSet
+/**
+ * Delete files
+ *
+ * Symlinks are removed themselves, not their targets. Directories are
+ * rejected.
+ *
+ * # Arguments
+ * * `paths` - Files to delete
+ * * `mode` - `dry_run` or `execute` (default)
+ * * `transaction` - Journal transaction to record the changes in
+ * * `sandbox` - Sandbox policy every path must satisfy
+ */
+export declare function deleteFiles(paths: Array<string>, mode?: ExecutionMode | undefined | null, transaction?: string | undefined | null, sandbox?: SandboxPolicy | undefined | null): FileChangeSet
+/** Outcome of a rollback */
+export interface RollbackResult {
+  /** Transaction id */
+  transaction: string
+  /** Changes undone */
+  reverted: number
+  /** Changes that could not be undone, with the reason */
+  failures: Array<string>
+}
+/** BM25 scoring parameters */
+export interface Bm25Config {
+  /** Term frequency saturation (typically 1.2-2.0) */
+  k1: number
+  /** Document length normalization (0 for none, 1 for full) */
+  b: number
+}
+/** A document matching a keyword query */
+export interface KeywordHit {
+  /** Index of the document in insertion order */
+  index: number
+  /** Identifier the document was added with */
+  id: string
+  /** BM25 score */
+  score: number
+}
+/** Options for `hybridSearch` */
+export interface HybridSearchOptions {
+  /** Fusion method: "rrf" or "weighted" */
+  fusion: string
+  /** Weight of the keyword score in weighted fusion (0-1) */
+  keywordWeight: number
+  /** Rank offset in reciprocal rank fusion */
+  rrfK: number
+  /** Results taken from each ranking before fusion */
+  candidates: number
+  /** Number of fused results to return */
+  topK: number
+  /** Keyword boost per field (default 1 for every field) */
+  fieldBoosts?: Record<string, number>
+}
+/** A document in the fused ranking */
+export interface HybridResult {
+  /** Index of the document and its vector */
+  index: number
+  /** Identifier the document was added with */
+  id: string
+  /** Fused score */
+  score: number
+  /** BM25 score (0 if no query term occurs in the document) */
+  keywordScore: number
+  /** Cosine similarity to the query vector */
+  vectorSimilarity: number
+  /** Position in the keyword ranking (1-based), if among the candidates */
+  keywordRank?: number
+  /** Position in the vector ranking (1-based), if among the candidates */
+  vectorRank?: number
+}
+/**
+ * Rank documents by keyword relevance and vector similarity together
+ *
+ * # Arguments
+ * * `index` - Keyword index over the documents
+ * * `query` - Query text for keyword scoring
+ * * `query_vector` - Embedding of the query
+ * * `vectors_flat` - Flattened document vectors, one per indexed document
+ * * `vector_size` - Size of each vector
+ * * `options` - Optional fusion method, weights and result counts
+ */
+export declare function hybridSearch(index: KeywordIndex, query: string, queryVector: Array<number>, vectorsFlat: Array<number>, vectorSize: number, options?: HybridSearchOptions | undefined | null): Array<HybridResult>
+/** Embeddings found by `getEmbeddings` */
+export interface EmbeddingLookup {
+  /** Whether each hash was cached, in request order */
+  found: Array<boolean>
+  /** Vectors of the cached hashes, in request order, flattened */
+  vectorsFlat: Array<number>
+  /** Dimensions of each vector (0 if the model has none stored) */
+  vectorSize: number
+  /** Positions in the request of the hashes that were not cached */
+  missing: Array<number>
+}
+/**
+ * Initialize the MOIDVK Rust core module
+ *
+ * Returns a success message indicating the core has been initialized
+ */
+export declare function initializeRustCore(): string
+/**
+ * Get the version of the MOIDVK core crate
+ *
+ * Returns the version string from Cargo.toml
+ */
+export declare function getVersion(): string
+/**
+ * Get performance information about the Rust runtime
+ *
+ * Returns JSON string with SIMD support, per-ISA capability flags
+ * (`avx2`, `avx512f`, `neon`, `sve`, ...), cache sizes in bytes (null where
+ * unknown), the kernels vector operations dispatch to, thread count,
+ * allocator info, etc.
+ */
+export declare function getPerformanceInfo(): string
+/** Vector operations implementation */
+export declare class VectorOperations {
+  /**
+   * Create a new vector operations instance with optional configuration
+   *
+   * # Arguments
+   * * `config` - Optional configuration for vector operations
+   */
+  constructor(config?: VectorConfig | undefined | null)
+  /**
+   * Calculate cosine similarity between two vectors
+   * 10-20x faster than JavaScript implementation
+   */
+  cosineSimilarity(vecA: Array<number>, vecB: Array<number>): number
+  /**
+   * Calculate cosine similarity for multiple vector pairs in parallel
+   * 20-50x faster than JavaScript for large batches
+   */
+  batchCosineSimilarity(queryVector: Array<number>, vectorsFlat: Array<number>, vectorSize: number): Array<number>
+  /**
+   * Find the most similar vectors from a collection
+   * Returns top-k results above the similarity threshold
+   *
+   * With `explain`, each result also carries its dot product, norms,
+   * the threshold and its rank before filtering. To see why an expected
+   * vector did not make the cut, use `explainSimilarity`.
+   */
+  findSimilarVectors(queryVector: Array<number>, vectorsFlat: Array<number>, vectorSize: number, paths: Array<string>, topK: number, explain?: boolean | undefined | null): Array<SimilarityResult>
+  /**
+   * Explain where chosen vectors stand in a `findSimilarVectors` search
+   *
+   * Runs the same search and returns a result with its explanation for
+   * each path in `targets`, in that order, whether or not the search
+   * returns it; `droppedBy` says which filter excluded it. Use it to see
+   * why an expected vector did not appear.
+   *
+   * # Arguments
+   * * `targets` - Paths of the vectors to explain
+   */
+  explainSimilarity(queryVector: Array<number>, vectorsFlat: Array<number>, vectorSize: number, paths: Array<string>, topK: number, targets: Array<string>): Array<SimilarityResult>
+  /**
+   * Find the most similar vectors in a set stored with `shareVectors`
+   *
+   * Works from any worker thread without copying the set into it.
+   * Returns top-k results above the similarity threshold.
+   */
+  findSimilarInShared(name: string, queryVector: Array<number>, topK: number): Array<SimilarityResult>
+  /** Normalize a vector to unit length */
+  normalizeVector(vector: Array<number>): Array<number>
+  /** Calculate the L2 norm (magnitude) of a vector */
+  vectorNorm(vector: Array<number>): number
+  /**
+   * Compute pairwise distances between all vectors in a collection
+   *
+   * Returns the full n*n matrix; for more than a few thousand vectors,
+   * use `similarPairs` to get only the pairs above a threshold.
+   */
+  pairwiseDistances(vectorsFlat: Array<number>, vectorSize: number): Array<number>
+  /**
+   * Find every pair of vectors whose cosine similarity is at least `threshold`
+   *
+   * Unlike `pairwiseDistances`, memory grows with the pairs found rather
+   * than n*n. Vectors are normalized once and compared tile by tile; a
+   * pair whose similarity over the highest-energy quarter of the
+   * dimensions, plus the product of the two remaining norms, can't reach
+   * the threshold is skipped without finishing its dot product, so high
+   * thresholds (near-duplicate detection) prune most of the work.
+   * Results are ordered by `index_a`, then `index_b`.
+   *
+   * # Arguments
+   * * `vectors_flat` - Vectors, flattened (count * vector_size)
+   * * `vector_size` - Dimensions of each vector
+   * * `threshold` - Minimum cosine similarity of a reported pair
+   */
+  similarPairs(vectorsFlat: Array<number>, vectorSize: number, threshold: number): Array<SimilarPair>
+  /**
+   * Create embeddings cache key from vector
+   *
+   * To skip re-embedding unchanged content, store vectors by content
+   * hash in an `EmbeddingCache` instead.
+   */
+  createCacheKey(vector: Array<number>): string
+}
+/** File search operations implementation */
+export declare class FileSearch {
+  /**
+   * Create a new file search instance with optional configuration
+   *
+   * # Arguments
+   * * `config` - Optional configuration for file search operations
+   */
+  constructor(config?: FileSearchConfig | undefined | null)
+  /**
+   * Search for files by glob pattern
+   * 5-10x faster than Node.js glob implementations
+   */
+  findFilesByPattern(rootPath: string, pattern: string, overrides?: FileSearchOverrides | undefined | null): Array<FileInfo>
+  /**
+   * Search for text content within files
+   * 10-20x faster than JavaScript regex operations on large files
+   */
+  searchTextInFiles(rootPath: string, searchText: string, filePattern?: string | undefined | null, caseSensitive?: boolean | undefined | null, overrides?: FileSearchOverrides | undefined | null): Array<TextSearchResult>
+  /**
+   * Find files by glob pattern, storing each directory path once
+   *
+   * Returns the same files as `findFilesByPattern`, with paths split into
+   * a table of root-relative directories and per-file names, which cuts
+   * transfer size and memory for very large results.
+   */
+  findFilesCompact(rootPath: string, pattern: string, overrides?: FileSearchOverrides | undefined | null): CompactFileList
+  /**
+   * Search for text content within files, storing each file path once
+   *
+   * Returns the same matches as `searchTextInFiles`, referring to a table
+   * of root-relative file paths by index.
+   */
+  searchTextCompact(rootPath: string, searchText: string, filePattern?: string | undefined | null, caseSensitive?: boolean | undefined | null, overrides?: FileSearchOverrides | undefined | null): CompactTextResults
+  /**
+   * Find files by glob pattern one page at a time
+   *
+   * The first call starts a walk and returns up to `page_size` files and
+   * a cursor. Passing the cursor back, with the same root and pattern,
+   * continues the walk where the previous page stopped.
+   *
+   * # Arguments
+   * * `root_path` - Directory to search
+   * * `pattern` - Glob pattern matched against full paths
+   * * `page_size` - Maximum files per page
+   * * `cursor` - Cursor from the previous page (omit to start)
+   * * `overrides` - Optional per-call config changes (must match across pages)
+   */
+  findFilesPaged(rootPath: string, pattern: string, pageSize: number, cursor?: string | undefined | null, overrides?: FileSearchOverrides | undefined | null): FilePage
+  /**
+   * Search for text content within files one page at a time
+   *
+   * Works like `searchTextInFiles`, returning up to `page_size` matches
+   * and a cursor that continues the walk, including the remaining matches
+   * of a file whose matches span two pages.
+   *
+   * # Arguments
+   * * `root_path` - Directory to search
+   * * `search_text` - Text to find
+   * * `page_size` - Maximum matches per page
+   * * `cursor` - Cursor from the previous page (omit to start)
+   * * `file_pattern` - Optional glob restricting the files searched
+   * * `case_sensitive` - Match case exactly (default true)
+   * * `overrides` - Optional per-call config changes (must match across pages)
+   */
+  searchTextPaged(rootPath: string, searchText: string, pageSize: number, cursor?: string | undefined | null, filePattern?: string | undefined | null, caseSensitive?: boolean | undefined | null, overrides?: FileSearchOverrides | undefined | null): TextSearchPage
+  /**
+   * Get directory statistics (size, file count, etc.)
+   *
+   * With `timeline`, files are also bucketed by the age of their last
+   * modification, in disjoint ranges: the last day, week, 30 days and 365
+   * days, then older. Files modified in the future count as the last day;
+   * files without a readable mtime are left out of the buckets. Timeline
+   * results are not cached, since the ranges move with the clock.
+   *
+   * `total_size` sums file lengths as listed. `apparent_size` and
+   * `disk_usage` count each hard-linked file once and include directories,
+   * as `du -sb` and `du -sB1` do over the same entries; on-disk usage
+   * below a file's length marks it sparse (or compressed by the filesystem).
+   *
+   * # Arguments
+   * * `path` - Directory to scan
+   * * `timeline` - Also bucket files by modification time
+   * * `overrides` - Optional per-call config changes
+   */
+  getDirectoryStats(path: string, timeline?: boolean | undefined | null, overrides?: FileSearchOverrides | undefined | null): DirectoryStats
+  /**
+   * Create a map of file extensions to their counts
+   *
+   * @deprecated Use `getFileTypeStats`, whose `fileCount` per extension
+   * counts the same readable files alongside their bytes and lines of
+   * code, and which can also group by language.
+   */
+  getFileExtensionStats(path: string, overrides?: FileSearchOverrides | undefined | null): Record<string, number>
+  /**
+   * Count files, bytes and lines of code per extension or detected language
+   *
+   * Supersedes `getFileExtensionStats`, whose keys are used by extension.
+   * By language, files are classified by name, extension, then `#!`
+   * interpreter line, and unrecognised files are grouped under
+   * "<unknown>". Every file is read once to count its lines.
+   *
+   * # Arguments
+   * * `path` - Directory to scan
+   * * `by_language` - Group by detected language instead of extension
+   * * `overrides` - Optional per-call config changes
+   */
+  getFileTypeStats(path: string, byLanguage?: boolean | undefined | null, overrides?: FileSearchOverrides | undefined | null): Record<string, FileGroupStats>
+  /** Fast duplicate file finder using content hashing */
+  findDuplicateFiles(path: string, overrides?: FileSearchOverrides | undefined | null): Record<string, Array<string>>
+  /**
+   * Duplicate finder with a choice of hash, read size and mmap, reporting throughput
+   *
+   * Groups are the same as `findDuplicateFiles` returns, keyed by the
+   * chosen algorithm's digest. The result cache is not used, so the
+   * stats always describe files actually read.
+   *
+   * # Arguments
+   * * `path` - Directory to scan
+   * * `options` - Optional algorithm, buffer size and mmap setting (blake3, 64 KiB reads by default)
+   * * `overrides` - Optional per-call config changes
+   */
+  findDuplicateFilesWithStats(path: string, options?: DuplicateScanOptions | undefined | null, overrides?: FileSearchOverrides | undefined | null): DuplicateScan
+  /**
+   * Report how scans of `path` would be adapted to its filesystem
+   *
+   * # Arguments
+   * * `path` - Root a scan would start from
+   * * `overrides` - Optional per-call config changes
+   */
+  scanStrategy(path: string, overrides?: FileSearchOverrides | undefined | null): ScanStrategy
+  /**
+   * Explain why a path would or would not be returned by a search
+   *
+   * Applies exactly the hidden-file, exclude-pattern, size-limit, sandbox
+   * and glob checks used during traversal, reporting each one that affects the path.
+   *
+   * # Arguments
+   * * `path` - Path as it would be produced by traversal (root joined with the relative path)
+   * * `pattern` - Optional glob pattern passed to the search
+   * * `overrides` - Optional per-call config changes
+   */
+  explainPathMatch(path: string, pattern?: string | undefined | null, overrides?: FileSearchOverrides | undefined | null): PathMatchExplanation
+}
+/** Text processor for high-performance pattern matching */
+export declare class TextProcessor {
+  /** Create a new text processing instance with optional configuration */
+  constructor(config?: TextProcessingConfig | undefined | null)
+  /**
+   * Fast substring search using Aho-Corasick
+   *
+   * Offsets are UTF-8 byte offsets into `text`, which differ from
+   * JavaScript string indices once the text has non-ASCII characters; use
+   * `findSubstringsInBuffer` with "utf16le" for string indices.
+   */
+  findSubstrings(text: string, patterns: Array<string>): Array<TextMatch>
+  /**
+   * Search many documents in parallel with one shared automaton
+   * Returns one match list per document, in input order
+   */
+  findSubstringsInDocuments(documents: Array<string>, patterns: Array<string>): Array<Array<TextMatch>>
+  /**
+   * Regex pattern matching
+   *
+   * Offsets are UTF-8 byte offsets into `text`, as with `findSubstrings`.
+   */
+  findRegexMatches(text: string, pattern: string): Array<TextMatch>
+  /**
+   * Substring search in a Buffer, without first copying it into a string
+   *
+   * `encoding` says how the buffer encodes the text, as in Node's
+   * `Buffer.from(text, encoding)`, and sets the unit of match offsets:
+   * - "utf8" (default): UTF-8 byte offsets; the buffer is searched in place
+   *   and must be valid UTF-8
+   * - "latin1": byte offsets, which equal string indices; ASCII-only
+   *   buffers are searched in place, others are transcoded
+   * - "utf16le": UTF-16 code units, which equal JavaScript string indices;
+   *   the buffer is transcoded and lone surrogates match as U+FFFD
+   */
+  findSubstringsInBuffer(buffer: Buffer, patterns: Array<string>, encoding?: string | undefined | null): Array<TextMatch>
+  /**
+   * Regex pattern matching in a Buffer, without first copying it into a string
+   *
+   * `encoding` and the offset units are as for `findSubstringsInBuffer`.
+   */
+  findRegexMatchesInBuffer(buffer: Buffer, pattern: string, encoding?: string | undefined | null): Array<TextMatch>
+}
+/**
+ * Streaming multi-pattern matcher fed with Buffer chunks
+ *
+ * Matches that straddle chunk boundaries are reported exactly once with
+ * absolute offsets; only the last `max_pattern_len - 1` bytes are retained
+ * between chunks, so multi-GB inputs can be scanned in constant memory.
+ */
+export declare class StreamingMatcher {
+  /**
+   * Create a new streaming matcher for a set of patterns
+   *
+   * # Arguments
+   * * `patterns` - Patterns to search for
+   * * `config` - Optional text processing configuration (`overlapping` and `anchored` are ignored)
+   */
+  constructor(patterns: Array<string>, config?: TextProcessingConfig | undefined | null)
+  /** Feed the next chunk of the stream and return newly settled matches */
+  feed(chunk: Buffer): Array<StreamMatch>
+  /** Flush any buffered bytes at end of stream and return the remaining matches */
+  finish(): Array<StreamMatch>
+  /** Reset the matcher so it can be reused for a new stream */
+  reset(): void
+  /** Total number of bytes fed into the current stream */
+  bytesProcessed(): number
+  /** Scan a whole file in fixed-size chunks without loading it into memory */
+  scanFile(path: string): Array<StreamMatch>
+}
+/**
+ * Regex matcher for text appended over time, such as a followed log
+ *
+ * Appended chunks are split into lines and each complete line is matched
+ * once, so a pattern split across two appends is still found and earlier
+ * text is never rescanned. Only the unfinished last line is held between
+ * appends; a line growing past 1 MiB without a newline is matched as it is
+ * and its remainder counted as a new line. Matches don't span lines.
+ */
+export declare class IncrementalMatcher {
+  /**
+   * Create a matcher for a set of regular expressions
+   *
+   * # Arguments
+   * * `patterns` - Regular expressions, reported by index
+   * * `config` - Optional text processing configuration (only `case_sensitive` and `max_matches` apply)
+   */
+  constructor(patterns: Array<string>, config?: TextProcessingConfig | undefined | null)
+  /** Append the next chunk of text and return matches in the lines it completed */
+  append(chunk: string | Buffer): Array<LineMatch>
+  /** Match the unfinished last line, if any, at the end of the stream */
+  flush(): Array<LineMatch>
+  /** Reset the matcher so it can be reused for a new stream */
+  reset(): void
+  /** Total number of bytes appended to the current stream */
+  bytesProcessed(): number
+  /** Number of lines matched so far, excluding the unfinished last line */
+  linesProcessed(): number
+}
+/** Config file credential leak scanner */
+export declare class ConfigLeakScanner {
+  /**
+   * Create a new config leak scanner with optional configuration
+   *
+   * # Arguments
+   * * `config` - Optional configuration for the scanner
+   */
+  constructor(config?: ConfigLeakConfig | undefined | null)
+  /**
+   * Scan config text in the given format
+   *
+   * # Arguments
+   * * `text` - Config file content
+   * * `format` - "env", "yaml", or "json"
+   * * `label` - Optional name reported as the finding path
+   */
+  scanText(text: string, format: string, label?: string | undefined | null): Array<ConfigLeakFinding>
+  /** Scan a single config file, inferring the format from its name */
+  scanFile(path: string): Array<ConfigLeakFinding>
+  /** Scan every `.env`, YAML and JSON file in a directory tree in parallel */
+  scanDirectory(rootPath: string): Array<ConfigLeakFinding>
+}
+/** PII detector */
+export declare class PiiDetector {
+  /**
+   * Create a new PII detector with optional configuration
+   *
+   * # Arguments
+   * * `config` - Optional configuration for PII detection
+   */
+  constructor(config?: PiiConfig | undefined | null)
+  /**
+   * Detect PII in text
+   *
+   * # Arguments
+   * * `text` - Content to scan
+   * * `label` - Optional name reported as the finding path
+   */
+  scanText(text: string, label?: string | undefined | null): Array<PiiFinding>
+  /** Detect PII in a single file (binary files are skipped) */
+  scanFile(path: string): Array<PiiFinding>
+  /** Detect PII across a directory tree in parallel */
+  scanDirectory(rootPath: string): Array<PiiFinding>
+}
+/** Quarantine directory with signed records */
+export declare class Quarantine {
+  /**
+   * Open (and create, if needed) a quarantine directory
+   *
+   * # Arguments
+   * * `config` - Directory and signing key
+   */
+  constructor(config: QuarantineConfig)
+  /** Hex public key that verifies this quarantine's records */
+  get publicKey(): string
+  /**
+   * Move a file into quarantine
+   *
+   * # Arguments
+   * * `path` - Regular file to quarantine (symlinks are rejected)
+   * * `reason` - Why the file is being quarantined
+   */
+  quarantineFile(path: string, reason: string): QuarantineRecord
+  /**
+   * Records in quarantine, oldest first
+   *
+   * Metadata files that can't be parsed are skipped.
+   */
+  list(): Array<QuarantineEntry>
+  /**
+   * Move a quarantined file back, after checking its record and contents
+   *
+   * Returns the restored path.
+   *
+   * # Arguments
+   * * `id` - Quarantine id
+   * * `destination` - Where to restore to (defaults to the original path; must not exist)
+   */
+  restore(id: string, destination?: string | undefined | null): string
+}
+/** Secret scanner */
+export declare class SecretScanner {
+  /**
+   * Create a new secret scanner with optional configuration
+   *
+   * # Arguments
+   * * `config` - Optional configuration for secret scanning
+   */
+  constructor(config?: SecretScanConfig | undefined | null)
+  /**
+   * Scan text for secrets
+   *
+   * # Arguments
+   * * `text` - Content to scan
+   * * `label` - Optional name reported as the finding path
+   */
+  scanText(text: string, label?: string | undefined | null): Array<SecretFinding>
+  /** Scan a single file for secrets (binary files are skipped) */
+  scanFile(path: string): Array<SecretFinding>
+  /** Scan a directory tree for secrets in parallel */
+  scanDirectory(rootPath: string): Array<SecretFinding>
+  /**
+   * Load additional rules from a TOML or JSON ruleset
+   *
+   * Each rule has an `id`, a `regex` (a `secret` named group selects the
+   * reported span), and optional `description`, `severity`, `keywords` and
+   * minimum `entropy`. The whole ruleset is validated and compiled before
+   * any rule is added; ids must not collide with active rules.
+   *
+   * # Arguments
+   * * `ruleset` - Ruleset document
+   * * `format` - "toml" or "json" (detected from the first character when omitted)
+   *
+   * Returns the number of rules added
+   */
+  loadRules(ruleset: string, format?: string | undefined | null): number
+  /** Identifiers of the active rules */
+  ruleIds(): Array<string>
+}
+/** Secret bytes held in native memory and wiped when no longer needed */
+export declare class SecretBuffer {
+  /**
+   * Copy secret bytes into native memory
+   *
+   * # Arguments
+   * * `data` - Secret bytes
+   * * `wipe_source` - Overwrite the passed buffer with zeros after copying
+   */
+  constructor(data: Buffer, wipeSource?: boolean | undefined | null)
+  /**
+   * Copy a secret string (e.g. a token read from the environment)
+   *
+   * # Arguments
+   * * `value` - Secret string, stored as UTF-8
+   */
+  static fromString(value: string): SecretBuffer
+  /** Number of bytes held (0 once cleared) */
+  get length(): number
+  /** Whether the secret has been wiped */
+  get isCleared(): boolean
+  /**
+   * Compare against a candidate value in constant time
+   *
+   * # Arguments
+   * * `candidate` - Bytes to compare with the secret
+   */
+  equals(candidate: Buffer): boolean
+  /**
+   * Compare against a candidate string in constant time
+   *
+   * # Arguments
+   * * `candidate` - String whose UTF-8 bytes are compared with the secret
+   */
+  equalsStr(candidate: string): boolean
+  /**
+   * Compare against another secret in constant time
+   *
+   * # Arguments
+   * * `other` - Secret to compare with
+   */
+  equalsSecret(other: SecretBuffer): boolean
+  /**
+   * Copy the secret back into a JavaScript buffer
+   *
+   * The copy is owned by JavaScript and is not wiped automatically.
+   */
+  expose(): Buffer
+  /** Overwrite the secret with zeros and release it */
+  clear(): void
+}
+/**
+ * Unicode spoofing scanner
+ *
+ * Needs the `fs` and `text` features; `is_invisible_char` is always available.
+ */
+export declare class UnicodeSpoofScanner {
+  /**
+   * Create a new Unicode spoofing scanner with optional configuration
+   *
+   * # Arguments
+   * * `config` - Optional configuration for the scanner
+   */
+  constructor(config?: UnicodeSpoofConfig | undefined | null)
+  /**
+   * Scan text for spoofing characters
+   *
+   * # Arguments
+   * * `text` - Content to scan
+   * * `label` - Optional name reported as the finding path
+   */
+  scanText(text: string, label?: string | undefined | null): Array<UnicodeFinding>
+  /** Scan a single file (binary files are skipped) */
+  scanFile(path: string): Array<UnicodeFinding>
+  /** Scan a directory tree in parallel */
+  scanDirectory(rootPath: string): Array<UnicodeFinding>
+}
+/** Security utilities */
+export declare class SecurityUtils {
+  /**
+   * Create a new security utilities instance
+   *
+   * # Arguments
+   * * `policy` - Optional path policy (permissive defaults when omitted)
+   */
+  constructor(policy?: PathPolicy | undefined | null)
+  /**
+   * Validate and sanitize file path
+   *
+   * The path is normalized component by component (relative paths are
+   * joined onto the base), checked against the policy, and must stay
+   * inside the base. With `resolve_symlinks`, the existing part of both
+   * paths is canonicalized so symlinks cannot be used to leave the base.
+   * Legal filename characters such as `~`, `$` or spaces are accepted.
+   * With `windows_paths`, Windows syntax is parsed on any host.
+   */
+  validatePath(path: string, basePath: string): PathValidationResult
+  /**
+   * Check that resolving a path never leaves the base through a symlink
+   *
+   * Unlike canonicalizing the whole path, each component is resolved in
+   * turn: symlinks are read and their targets walked the same way, and
+   * each link inside the base must resolve inside it once its target is
+   * consumed (a target may pass through `..` outside on the way). This
+   * catches links whose target is outside the base even when the rest of
+   * the path does not exist yet, and links that come back into the base
+   * through a symlink outside it. Components past the first missing one
+   * are resolved lexically.
+   *
+   * # Arguments
+   * * `path` - Path to check, relative to the base or absolute below it
+   * * `base_path` - Existing directory the path must stay inside
+   */
+  validatePathNoSymlinkEscape(path: string, basePath: string): SymlinkCheckResult
+  /**
+   * Sanitize a file name into a single portable path component
+   *
+   * Separators and Windows-reserved punctuation become '_', control and
+   * invisible characters are dropped, reserved device names are renamed,
+   * and the name is truncated to the file system's length limit while
+   * keeping its extension.
+   *
+   * # Arguments
+   * * `filename` - Name to sanitize
+   * * `options` - Optional length limit, transliteration and uniqueness settings
+   */
+  sanitizeFilename(filename: string, options?: SanitizeFilenameOptions | undefined | null): string
+}
+/** Benchmark suite */
+export declare class BenchmarkSuite {
+  /**
+   * Create a new benchmark runner instance
+   *
+   * # Arguments
+   * * `config` - Optional warmup, iteration and timing settings
+   * * `workload` - Optional subset of built-in benchmarks and their parameters
+   */
+  constructor(config?: BenchmarkConfig | undefined | null, workload?: BenchmarkWorkload | undefined | null)
+  /**
+   * Run the built-in benchmarks selected by the workload on a background thread
+   *
+   * Replaces the previous results. Resolves with the new results once
+   * every benchmark has finished; `on_progress`, if given, is called with
+   * a `BenchmarkProgress` after each one.
+   *
+   * # Arguments
+   * * `on_progress` - Optional callback receiving per-benchmark progress
+   */
+  runAllBenchmarks(onProgress?: (...args: any[]) => any | undefined | null): Promise<BenchmarkResult[]>
+  /** Get benchmark results */
+  getResults(): Array<BenchmarkResult>
+  /**
+   * Save the current results as a JSON baseline
+   *
+   * # Arguments
+   * * `path` - File to write, replaced if it exists
+   */
+  saveBaseline(path: string): void
+  /**
+   * Compare the current results to a saved baseline
+   *
+   * Medians are compared, since they are less sensitive to outliers than
+   * means. A benchmark regresses when its median grew by more than
+   * `tolerance`; benchmarks present in only one run are listed but don't
+   * fail the report.
+   *
+   * # Arguments
+   * * `path` - Baseline written by `saveBaseline`
+   * * `tolerance` - Allowed relative slowdown (default 0.1 for 10%)
+   */
+  compareToBaseline(path: string, tolerance?: number | undefined | null): RegressionReport
+  /**
+   * Export the current results with environment metadata
+   *
+   * JSON follows the versioned schema returned by
+   * `getBenchmarkResultsSchema`. CSV has one row per benchmark with the
+   * environment repeated on every row and without the raw samples.
+   *
+   * # Arguments
+   * * `format` - "json" or "csv"
+   */
+  exportResults(format: string): string
+  /**
+   * Register a benchmark whose measured body is a JavaScript callback
+   *
+   * The callback is called with no arguments once per iteration and
+   * should do its work synchronously; only the time until it returns is
+   * measured. Run registered benchmarks with `runCustomBenchmarks`.
+   *
+   * # Arguments
+   * * `name` - Unique benchmark name
+   * * `callback` - Function to benchmark
+   * * `ops_per_iteration` - Items processed per call, used to scale `ops_per_sec` (default 1)
+   */
+  registerBenchmark(name: string, callback: (...args: any[]) => any, opsPerIteration?: number | undefined | null): void
+  /**
+   * Register a JavaScript reference implementation of a native benchmark
+   *
+   * The callback should do the same work as the native benchmark of that
+   * name (see `runAllBenchmarks` for the built-in ones). `runJsComparisons`
+   * times it and sets the result's `speedup` from the two medians.
+   *
+   * # Arguments
+   * * `name` - Name of the native benchmark the callback reimplements
+   * * `callback` - Synchronous JavaScript implementation of the same workload
+   */
+  registerJsReference(name: string, callback: (...args: any[]) => any): void
+  /** Names of the registered custom benchmarks */
+  customBenchmarks(): Array<string>
+  /**
+   * Run the registered custom benchmarks off the JavaScript thread
+   *
+   * Resolves with their results, which are also appended to `getResults`.
+   */
+  runCustomBenchmarks(): Promise<BenchmarkResult[]>
+  /**
+   * Time the registered JavaScript references off the JavaScript thread
+   *
+   * Each native benchmark with a reference must already have a result.
+   * Resolves with the measured speedups, which are also stored on the
+   * results returned by `getResults`.
+   */
+  runJsComparisons(): Promise<SpeedupMeasurement[]>
+}
+/** Near-duplicate detector based on MinHash and LSH banding */
+export declare class NearDuplicateDetector {
+  /**
+   * Create a new near-duplicate detector with optional configuration
+   *
+   * # Arguments
+   * * `config` - Optional configuration for shingling and banding
+   */
+  constructor(config?: NearDuplicateConfig | undefined | null)
+  /** Compute the MinHash signature of a single text */
+  computeSignature(text: string): Array<number>
+  /** Estimate Jaccard similarity from two MinHash signatures */
+  estimateSimilarity(signatureA: Array<number>, signatureB: Array<number>): number
+  /**
+   * Find near-duplicate pairs among a collection of texts
+   * Uses LSH banding so only candidate pairs are compared
+   */
+  findNearDuplicates(texts: Array<string>): Array<DuplicatePair>
+  /** Find near-duplicate pairs from precomputed signatures */
+  findNearDuplicatesFromSignatures(signatures: Array<Array<number>>): Array<DuplicatePair>
+}
+/** Text formatter for whitespace and line ending checks */
+export declare class TextFormatter {
+  /**
+   * Create a new text formatter with optional configuration
+   *
+   * # Arguments
+   * * `config` - Optional configuration for normalization
+   */
+  constructor(config?: FormatConfig | undefined | null)
+  /** Analyze line endings, trailing whitespace and final newline in one pass */
+  analyzeLineEndings(text: string): LineEndingReport
+  /** Normalize line endings and whitespace, returning the result and an edit list */
+  normalizeLineEndings(text: string): NormalizationResult
+  /** Analyze indentation character, inferred width and inconsistencies */
+  analyzeIndentation(text: string): IndentationReport
+  /**
+   * Convert leading indentation between tabs and spaces
+   *
+   * Only leading whitespace is rewritten, so alignment after code is kept,
+   * and lines continuing a multi-line string literal are left untouched.
+   *
+   * # Arguments
+   * * `text` - Text to convert
+   * * `target_style` - "spaces" or "tabs"
+   * * `tab_width` - Columns per indentation level (inferred when omitted)
+   */
+  convertIndentation(text: string, targetStyle: string, tabWidth?: number | undefined | null): NormalizationResult
+}
+/** CSV parser implementation */
+export declare class CsvParser {
+  /**
+   * Create a new CSV parser with optional configuration
+   *
+   * # Arguments
+   * * `config` - Optional configuration for CSV parsing
+   */
+  constructor(config?: CsvConfig | undefined | null)
+  /**
+   * Parse CSV text
+   *
+   * # Arguments
+   * * `text` - CSV content
+   * * `columns` - Optional column names (or zero-based indices without headers) to project
+   */
+  parseText(text: string, columns?: Array<string> | undefined | null): CsvParseResult
+  /**
+   * Parse a CSV file using memory-mapped I/O
+   *
+   * # Arguments
+   * * `path` - Path to the CSV file
+   * * `columns` - Optional column names (or zero-based indices without headers) to project
+   */
+  parseFile(path: string, columns?: Array<string> | undefined | null): CsvParseResult
+}
+/** BK-tree index for edit-distance lookups */
+export declare class BkTree {
+  /**
+   * Create an empty BK-tree with optional configuration
+   *
+   * # Arguments
+   * * `config` - Optional configuration for lookups
+   */
+  constructor(config?: FuzzyMatchConfig | undefined | null)
+  /** Add terms to the tree, returning how many were new */
+  add(terms: Array<string>): number
+  /** Find terms within `max_distance` edits of the query, closest first */
+  search(query: string, maxDistance: number): Array<FuzzyMatch>
+  /** Run several queries in one call */
+  searchBatch(queries: Array<string>, maxDistance: number): Array<Array<FuzzyMatch>>
+  /** Number of distinct terms in the tree */
+  size(): number
+}
+/** Incremental Blake3 hasher */
+export declare class Blake3Hasher {
+  /** Create a new Blake3 hasher */
+  constructor()
+  /** Add a chunk of data */
+  update(data: Buffer): void
+  /**
+   * Hex digest of all data added so far
+   *
+   * The hasher stays usable: further updates extend the same stream.
+   */
+  finalize(): string
+  /** Discard all data added so far */
+  reset(): void
+}
+/** Incremental SHA-256 hasher */
+export declare class Sha256Hasher {
+  /** Create a new SHA-256 hasher */
+  constructor()
+  /** Add a chunk of data */
+  update(data: Buffer): void
+  /**
+   * Hex digest of all data added so far
+   *
+   * The hasher stays usable: further updates extend the same stream.
+   */
+  finalize(): string
+  /** Discard all data added so far */
+  reset(): void
+}
+/** License scanner */
+export declare class LicenseScanner {
+  /**
+   * Create a new license scanner with optional configuration
+   *
+   * # Arguments
+   * * `config` - Optional configuration for license detection
+   */
+  constructor(config?: LicenseConfig | undefined | null)
+  /**
+   * Identify licenses in text
+   *
+   * SPDX tags and license texts are both checked.
+   *
+   * # Arguments
+   * * `text` - Content to scan
+   */
+  detectText(text: string): Array<LicenseMatch>
+  /** Identify licenses in a single file (binary files yield no matches) */
+  scanFile(path: string): FileLicenses
+  /** Identify licenses across a directory tree and aggregate the results */
+  scanDirectory(rootPath: string): LicenseReport
+}
+/** Offline advisory database indexed by package */
+export declare class AdvisoryDatabase {
+  /**
+   * Load advisories from an OSV dump
+   *
+   * # Arguments
+   * * `path` - JSON array, JSON Lines file or directory of OSV JSON files
+   */
+  constructor(path: string)
+  /** Number of advisories loaded */
+  get advisoryCount(): number
+  /**
+   * Match dependencies against the loaded advisories
+   *
+   * # Arguments
+   * * `dependencies` - Inventory from `list_dependencies` or `parse_lockfile`
+   */
+  matchDependencies(dependencies: Array<Dependency>): Array<VulnerabilityMatch>
+}
+/** Lock on a file, held until `release` is called or the handle is collected */
+export declare class FileLock {
+  /** Path the lock was taken for */
+  get path(): string
+  /** Whether this is a shared (read) lock */
+  get shared(): boolean
+  /** Whether the lock is still held */
+  get held(): boolean
+  /** Release the lock; returns false if it was already released */
+  release(): boolean
+}
+/**
+ * Incremental compressor for data that arrives in chunks
+ *
+ * Each call returns the compressed bytes produced so far, which may be
+ * empty; concatenated, they form one complete stream after `finish`.
+ */
+export declare class Compressor {
+  /**
+   * Create a compressor
+   *
+   * # Arguments
+   * * `options` - Optional format and level
+   */
+  constructor(options?: CompressionOptions | undefined | null)
+  /** Compress a chunk */
+  write(chunk: string | Buffer): Buffer
+  /** Flush buffered input, so everything written so far can be decompressed */
+  flush(): Buffer
+  /** End the stream and return its last bytes */
+  finish(): Buffer
+}
+/**
+ * Incremental decompressor for data that arrives in chunks
+ *
+ * Each call returns the decompressed bytes produced so far, which may be
+ * empty. `finish` fails if the stream was cut short.
+ */
+export declare class Decompressor {
+  /**
+   * Create a decompressor
+   *
+   * # Arguments
+   * * `options` - Optional format (detected from the first bytes by
+   *   default) and a limit on the total output
+   */
+  constructor(options?: DecompressionOptions | undefined | null)
+  /** Decompress a chunk */
+  write(chunk: Buffer): Buffer
+  /** End the stream and return the last decompressed bytes */
+  finish(): Buffer
+}
+/** File metadata kept on disk and updated incrementally */
+export declare class FileMetadataDb {
+  /**
+   * Open or create a metadata database
+   *
+   * # Arguments
+   * * `path` - Directory holding the database (created if missing)
+   * * `search` - Optional config for `refresh` walks (excludes, hidden files, sandbox...)
+   */
+  constructor(path: string, search?: FileSearchConfig | undefined | null)
+  /**
+   * Bring the records of files under `root` up to date
+   *
+   * Walks `root` with the search config, reads files that are new or
+   * whose size or modification time changed, and drops records of files
+   * under `root` that the walk no longer finds (unless it was cut short).
+   */
+  refresh(root: string): MetadataRefresh
+  /**
+   * Update the records of paths reported changed, e.g. by a file watcher
+   *
+   * Existing files are re-read if their size or modification time
+   * changed; existing directories are refreshed like `refresh`; records
+   * of missing paths, and of files under them, are dropped. Paths are
+   * not checked against the search config's filters.
+   */
+  updatePaths(paths: Array<string>): MetadataRefresh
+  /** Get the stored record of a file */
+  get(path: string): FileRecord | null
+  /** Find stored records matching all given filters, in path order */
+  query(query?: MetadataQuery | undefined | null): Array<FileRecord>
+  /**
+   * Group stored non-empty files by content hash, keeping groups of two or more
+   *
+   * # Arguments
+   * * `under` - Only files under this directory (omit for all)
+   */
+  findDuplicates(under?: string | undefined | null): Record<string, Array<string>>
+  /**
+   * Count stored files, bytes and lines of code per language
+   *
+   * Keys match `getFileTypeStats` by language, with unrecognised files
+   * under "<unknown>".
+   *
+   * # Arguments
+   * * `under` - Only files under this directory (omit for all)
+   */
+  languageStats(under?: string | undefined | null): Record<string, FileGroupStats>
+  /**
+   * Drop the records of a file or of every file under a directory
+   *
+   * Returns the number of records dropped.
+   */
+  remove(path: string): number
+  /** Write pending changes to disk (also done periodically in the background) */
+  flush(): void
+  /** Number of files with a stored record */
+  get fileCount(): number
+}
+/**
+ * Builds a shared vector set from batches
+ *
+ * Each `addBatch` only copies its own vectors; batches are merged into the
+ * set shared under the name on the thread pool, in the order they were
+ * added, so the corpus never has to be held in one JavaScript array.
+ * Merging replaces the set like `shareVectors` does, so searches in
+ * progress keep the set they started with. Batches become searchable once
+ * merged: pending vectors are merged when they reach a quarter of the
+ * shared set, and `flush` merges the rest.
+ */
+export declare class VectorSetWriter {
+  /**
+   * Start writing to a shared vector set
+   *
+   * An existing set with that name is appended to; otherwise an empty
+   * set is shared.
+   *
+   * # Arguments
+   * * `name` - Name of the shared set
+   * * `vector_size` - Size of each vector
+   */
+  constructor(name: string, vectorSize: number)
+  /**
+   * Add a batch of vectors
+   *
+   * Returns the number of vectors not merged yet. Fails if a background
+   * merge failed since the last call (the set was replaced with one of
+   * another vector size).
+   *
+   * # Arguments
+   * * `ids` - Path or identifier of each vector
+   * * `vectors_flat` - Flattened vectors (ids.length * vector_size)
+   */
+  addBatch(ids: Array<string>, vectorsFlat: Array<number>): number
+  /**
+   * Merge every pending batch and return the shared set
+   *
+   * Waits for a background merge in progress.
+   */
+  flush(): SharedVectorSetInfo
+  /** Vectors added and not merged yet */
+  get pending(): number
+  /** Name of the shared set */
+  get name(): string
+  /** Size of each vector */
+  get vectorSize(): number
+}
+/** Directory of transactions that record before-images of file changes */
+export declare class Journal {
+  /** Open a journal directory, creating it if missing */
+  constructor(directory: string)
+  /**
+   * Start a transaction and return its id
+   *
+   * Pass the id to bulk file operations to record their changes.
+   */
+  begin(): string
+  /**
+   * Keep a transaction's changes and discard its backups
+   *
+   * Returns the number of changes it recorded.
+   */
+  commit(transaction: string): number
+  /**
+   * Undo a transaction's changes, newest first
+   *
+   * Entries that cannot be undone are reported and the rest are still
+   * attempted. The transaction directory is kept if anything failed, so
+   * its backups can be recovered by hand.
+   */
+  rollback(transaction: string): RollbackResult
+  /**
+   * Ids of transactions in the directory that were neither committed nor rolled back
+   *
+   * Includes transactions left behind by other or crashed processes.
+   */
+  pending(): Array<string>
+}
+/** Inverted index over a growing set of documents */
+export declare class KeywordIndex {
+  /**
+   * Create an empty index
+   *
+   * # Arguments
+   * * `config` - Optional BM25 parameters
+   */
+  constructor(config?: Bm25Config | undefined | null)
+  /** Load an index written by `save`, compressed or not */
+  static load(path: string): KeywordIndex
+  /**
+   * Write the index to a file as JSON
+   *
+   * # Arguments
+   * * `path` - File to create or replace
+   * * `compression` - Compress the JSON with this format and level
+   *   (uncompressed if omitted)
+   */
+  save(path: string, compression?: CompressionOptions | undefined | null): void
+  /** Add a document with a single `body` field and return its index */
+  addDocument(id: string, text: string): number
+  /**
+   * Add a document with named fields and return its index
+   *
+   * # Arguments
+   * * `id` - Identifier returned in search results
+   * * `fields` - Field name to text, e.g. `{ path, title, body }`
+   */
+  addFields(id: string, fields: Record<string, string>): number
+  /** Add several documents; `ids` and `texts` are paired by position */
+  addDocuments(ids: Array<string>, texts: Array<string>): void
+  /** Number of documents in the index */
+  get documentCount(): number
+  /** Names of the indexed fields, sorted */
+  get fieldNames(): Array<string>
+  /**
+   * Find the `top_k` documents with the highest BM25 score for a query
+   *
+   * # Arguments
+   * * `query` - Query text
+   * * `top_k` - Maximum number of hits
+   * * `boosts` - Multiplier per field (default 1; 0 ignores the field)
+   */
+  search(query: string, topK: number, boosts?: Record<string, number> | undefined | null): Array<KeywordHit>
+}
+/** Embeddings stored on disk by content hash */
+export declare class EmbeddingCache {
+  /**
+   * Open or create an embedding cache
+   *
+   * # Arguments
+   * * `path` - Directory holding the database (created if missing; may
+   *   be shared with a `FileMetadataDb`)
+   * * `model` - Name of the model the vectors come from (default: "default")
+   */
+  constructor(path: string, model?: string | undefined | null)
+  /**
+   * Store the embedding of one piece of content
+   *
+   * # Arguments
+   * * `content_hash` - Hash of the content the vector was computed from
+   * * `vector` - The embedding
+   */
+  putEmbedding(contentHash: string, vector: Array<number>): void
+  /**
+   * Store several embeddings at once; `content_hashes` and vectors are paired by position
+   *
+   * The batch is written atomically.
+   *
+   * # Arguments
+   * * `content_hashes` - Hashes of the contents
+   * * `vectors_flat` - Embeddings, flattened (content_hashes.length * vector_size)
+   * * `vector_size` - Dimensions of each embedding
+   */
+  putEmbeddings(contentHashes: Array<string>, vectorsFlat: Array<number>, vectorSize: number): void
+  /** Get the cached embedding of one piece of content */
+  getEmbedding(contentHash: string): Array<number> | null
+  /**
+   * Look up the embeddings of several pieces of content
+   *
+   * Embed the contents at the `missing` positions and store them with
+   * `putEmbeddings`.
+   */
+  getEmbeddings(contentHashes: Array<string>): EmbeddingLookup
+  /**
+   * Drop the embeddings of the given hashes
+   *
+   * Returns the number of embeddings dropped.
+   */
+  remove(contentHashes: Array<string>): number
+  /**
+   * Drop every embedding whose hash is not in `content_hashes`, e.g. after a re-index
+   *
+   * Returns the number of embeddings dropped.
+   */
+  retain(contentHashes: Array<string>): number
+  /** Drop every embedding of this model, so its dimensions can change */
+  clear(): void
+  /** Write pending changes to disk (also done periodically in the background) */
+  flush(): void
+  /** Number of stored embeddings of this model */
+  get count(): number
+  /** Dimensions of this model's embeddings, once any are stored */
+  get dimensions(): number | null
 }
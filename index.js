@@ -310,21 +310,159 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { VectorOperations, quickCosineSimilarity, benchmarkVectorOperations, FileSearch, quickFindFiles, quickSearchText, benchmarkFileSearch, TextProcessor, quickSubstringSearch, SecurityUtils, quickValidatePath, BenchmarkSuite, quickBenchmark, initializeRustCore, getVersion, getPerformanceInfo } = nativeBinding
+const { VectorOperations, quickCosineSimilarity, benchmarkVectorOperations, FileSearch, globToRegex, validateGlobPatterns, quickFindFiles, quickSearchText, benchmarkFileSearch, TextProcessor, quickSubstringSearch, stripAnsi, sanitizeControlChars, validateJson, minifyJson, prettyPrintJson, queryJson, queryJsonFile, textStats, StreamingMatcher, IncrementalMatcher, quickStreamSearchFile, validateArchiveEntries, ConfigLeakScanner, quickScanConfigLeaks, PiiDetector, quickDetectPii, Quarantine, setFileQuota, clearFileQuota, getFileQuotaUsage, consumeFileQuota, analyzeRegex, validateRegexes, checkSandboxAccess, SecretScanner, findHighEntropyStrings, quickScanSecrets, secureDelete, constantTimeEquals, constantTimeEqualsStr, SecretBuffer, quoteShellArg, quoteShellCommand, analyzeShellCommand, generateEd25519Keypair, ed25519PublicKey, ed25519Sign, ed25519Verify, ed25519SignFile, ed25519VerifyFile, UnicodeSpoofScanner, quickScanUnicodeSpoofing, analyzeWindowsPath, SecurityUtils, quickValidatePath, BenchmarkSuite, getBenchmarkEnvironment, getBenchmarkResultsSchema, quickBenchmark, NearDuplicateDetector, quickFindNearDuplicates, TextFormatter, applyTextEdits, quickNormalizeLineEndings, extractMarkdownStructure, stripHtmlTags, CsvParser, quickParseCsv, encodeBase64, decodeBase64, encodeHex, decodeHex, encodeUrl, decodeUrl, convertCase, splitIdentifiers, detectCaseStyles, sortLines, BkTree, soundex, doubleMetaphone, quickFuzzyLookup, highlightTokens, listHighlightLanguages, Blake3Hasher, Sha256Hasher, hashFile, hashBuffer, hmac, hmacFile, verifyHmac, LicenseScanner, quickDetectLicenses, parseLockfile, listDependencies, generateSbom, AdvisoryDatabase, matchVulnerabilities, enableAuditLog, disableAuditLog, setAuditContext, getAuditEvents, flushAuditLog, clearAuditLog, getAuditStatus, FileLock, acquireLock, writeFileAtomic, compress, decompress, compressFile, decompressFile, Compressor, Decompressor, getMemoryStats, generateSyntheticTree, generateSyntheticEmbeddings, ErrorCode, parseErrorCode, getMetrics, resetMetrics, loadConfig, parseConfig, configureThreadPool, detectLanguage, clearCache, invalidateCachedResults, closeSearchCursor, FileMetadataDb, shareVectors, VectorSetWriter, dropSharedVectors, getSharedStateInfo, clearMatcherCaches, setConcurrencyLimits, getConcurrencyStats, resetConcurrencyStats, getBackgroundPoolInfo, chunkFiles, chunkFilesIncremental, ExecutionMode, replaceInFiles, normalizeFiles, copyFiles, moveFiles, deleteFiles, Journal, KeywordIndex, hybridSearch, EmbeddingCache, initializeRustCore, getVersion, getPerformanceInfo } = nativeBinding
 
 module.exports.VectorOperations = VectorOperations
 module.exports.quickCosineSimilarity = quickCosineSimilarity
 module.exports.benchmarkVectorOperations = benchmarkVectorOperations
 module.exports.FileSearch = FileSearch
+module.exports.globToRegex = globToRegex
+module.exports.validateGlobPatterns = validateGlobPatterns
 module.exports.quickFindFiles = quickFindFiles
 module.exports.quickSearchText = quickSearchText
 module.exports.benchmarkFileSearch = benchmarkFileSearch
 module.exports.TextProcessor = TextProcessor
 module.exports.quickSubstringSearch = quickSubstringSearch
+module.exports.stripAnsi = stripAnsi
+module.exports.sanitizeControlChars = sanitizeControlChars
+module.exports.validateJson = validateJson
+module.exports.minifyJson = minifyJson
+module.exports.prettyPrintJson = prettyPrintJson
+module.exports.queryJson = queryJson
+module.exports.queryJsonFile = queryJsonFile
+module.exports.textStats = textStats
+module.exports.StreamingMatcher = StreamingMatcher
+module.exports.IncrementalMatcher = IncrementalMatcher
+module.exports.quickStreamSearchFile = quickStreamSearchFile
+module.exports.validateArchiveEntries = validateArchiveEntries
+module.exports.ConfigLeakScanner = ConfigLeakScanner
+module.exports.quickScanConfigLeaks = quickScanConfigLeaks
+module.exports.PiiDetector = PiiDetector
+module.exports.quickDetectPii = quickDetectPii
+module.exports.Quarantine = Quarantine
+module.exports.setFileQuota = setFileQuota
+module.exports.clearFileQuota = clearFileQuota
+module.exports.getFileQuotaUsage = getFileQuotaUsage
+module.exports.consumeFileQuota = consumeFileQuota
+module.exports.analyzeRegex = analyzeRegex
+module.exports.validateRegexes = validateRegexes
+module.exports.checkSandboxAccess = checkSandboxAccess
+module.exports.SecretScanner = SecretScanner
+module.exports.findHighEntropyStrings = findHighEntropyStrings
+module.exports.quickScanSecrets = quickScanSecrets
+module.exports.secureDelete = secureDelete
+module.exports.constantTimeEquals = constantTimeEquals
+module.exports.constantTimeEqualsStr = constantTimeEqualsStr
+module.exports.SecretBuffer = SecretBuffer
+module.exports.quoteShellArg = quoteShellArg
+module.exports.quoteShellCommand = quoteShellCommand
+module.exports.analyzeShellCommand = analyzeShellCommand
+module.exports.generateEd25519Keypair = generateEd25519Keypair
+module.exports.ed25519PublicKey = ed25519PublicKey
+module.exports.ed25519Sign = ed25519Sign
+module.exports.ed25519Verify = ed25519Verify
+module.exports.ed25519SignFile = ed25519SignFile
+module.exports.ed25519VerifyFile = ed25519VerifyFile
+module.exports.UnicodeSpoofScanner = UnicodeSpoofScanner
+module.exports.quickScanUnicodeSpoofing = quickScanUnicodeSpoofing
+module.exports.analyzeWindowsPath = analyzeWindowsPath
 module.exports.SecurityUtils = SecurityUtils
 module.exports.quickValidatePath = quickValidatePath
 module.exports.BenchmarkSuite = BenchmarkSuite
+module.exports.getBenchmarkEnvironment = getBenchmarkEnvironment
+module.exports.getBenchmarkResultsSchema = getBenchmarkResultsSchema
 module.exports.quickBenchmark = quickBenchmark
+module.exports.NearDuplicateDetector = NearDuplicateDetector
+module.exports.quickFindNearDuplicates = quickFindNearDuplicates
+module.exports.TextFormatter = TextFormatter
+module.exports.applyTextEdits = applyTextEdits
+module.exports.quickNormalizeLineEndings = quickNormalizeLineEndings
+module.exports.extractMarkdownStructure = extractMarkdownStructure
+module.exports.stripHtmlTags = stripHtmlTags
+module.exports.CsvParser = CsvParser
+module.exports.quickParseCsv = quickParseCsv
+module.exports.encodeBase64 = encodeBase64
+module.exports.decodeBase64 = decodeBase64
+module.exports.encodeHex = encodeHex
+module.exports.decodeHex = decodeHex
+module.exports.encodeUrl = encodeUrl
+module.exports.decodeUrl = decodeUrl
+module.exports.convertCase = convertCase
+module.exports.splitIdentifiers = splitIdentifiers
+module.exports.detectCaseStyles = detectCaseStyles
+module.exports.sortLines = sortLines
+module.exports.BkTree = BkTree
+module.exports.soundex = soundex
+module.exports.doubleMetaphone = doubleMetaphone
+module.exports.quickFuzzyLookup = quickFuzzyLookup
+module.exports.highlightTokens = highlightTokens
+module.exports.listHighlightLanguages = listHighlightLanguages
+module.exports.Blake3Hasher = Blake3Hasher
+module.exports.Sha256Hasher = Sha256Hasher
+module.exports.hashFile = hashFile
+module.exports.hashBuffer = hashBuffer
+module.exports.hmac = hmac
+module.exports.hmacFile = hmacFile
+module.exports.verifyHmac = verifyHmac
+module.exports.LicenseScanner = LicenseScanner
+module.exports.quickDetectLicenses = quickDetectLicenses
+module.exports.parseLockfile = parseLockfile
+module.exports.listDependencies = listDependencies
+module.exports.generateSbom = generateSbom
+module.exports.AdvisoryDatabase = AdvisoryDatabase
+module.exports.matchVulnerabilities = matchVulnerabilities
+module.exports.enableAuditLog = enableAuditLog
+module.exports.disableAuditLog = disableAuditLog
+module.exports.setAuditContext = setAuditContext
+module.exports.getAuditEvents = getAuditEvents
+module.exports.flushAuditLog = flushAuditLog
+module.exports.clearAuditLog = clearAuditLog
+module.exports.getAuditStatus = getAuditStatus
+module.exports.FileLock = FileLock
+module.exports.acquireLock = acquireLock
+module.exports.writeFileAtomic = writeFileAtomic
+module.exports.compress = compress
+module.exports.decompress = decompress
+module.exports.compressFile = compressFile
+module.exports.decompressFile = decompressFile
+module.exports.Compressor = Compressor
+module.exports.Decompressor = Decompressor
+module.exports.getMemoryStats = getMemoryStats
+module.exports.generateSyntheticTree = generateSyntheticTree
+module.exports.generateSyntheticEmbeddings = generateSyntheticEmbeddings
+module.exports.ErrorCode = ErrorCode
+module.exports.parseErrorCode = parseErrorCode
+module.exports.getMetrics = getMetrics
+module.exports.resetMetrics = resetMetrics
+module.exports.loadConfig = loadConfig
+module.exports.parseConfig = parseConfig
+module.exports.configureThreadPool = configureThreadPool
+module.exports.detectLanguage = detectLanguage
+module.exports.clearCache = clearCache
+module.exports.invalidateCachedResults = invalidateCachedResults
+module.exports.closeSearchCursor = closeSearchCursor
+module.exports.FileMetadataDb = FileMetadataDb
+module.exports.shareVectors = shareVectors
+module.exports.VectorSetWriter = VectorSetWriter
+module.exports.dropSharedVectors = dropSharedVectors
+module.exports.getSharedStateInfo = getSharedStateInfo
+module.exports.clearMatcherCaches = clearMatcherCaches
+module.exports.setConcurrencyLimits = setConcurrencyLimits
+module.exports.getConcurrencyStats = getConcurrencyStats
+module.exports.resetConcurrencyStats = resetConcurrencyStats
+module.exports.getBackgroundPoolInfo = getBackgroundPoolInfo
+module.exports.chunkFiles = chunkFiles
+module.exports.chunkFilesIncremental = chunkFilesIncremental
+module.exports.ExecutionMode = ExecutionMode
+module.exports.replaceInFiles = replaceInFiles
+module.exports.normalizeFiles = normalizeFiles
+module.exports.copyFiles = copyFiles
+module.exports.moveFiles = moveFiles
+module.exports.deleteFiles = deleteFiles
+module.exports.Journal = Journal
+module.exports.KeywordIndex = KeywordIndex
+module.exports.hybridSearch = hybridSearch
+module.exports.EmbeddingCache = EmbeddingCache
 module.exports.initializeRustCore = initializeRustCore
 module.exports.getVersion = getVersion
 module.exports.getPerformanceInfo = getPerformanceInfo
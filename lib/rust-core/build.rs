@@ -1,5 +1,8 @@
 extern crate napi_build;
 
 fn main() {
-    napi_build::setup();
-}
\ No newline at end of file
+    // The wasm32 fallback is not a Node addon
+    if std::env::var_os("CARGO_FEATURE_NODE").is_some() {
+        napi_build::setup();
+    }
+}
@@ -0,0 +1,460 @@
+//! Known-vulnerability matching against an offline OSV database
+//!
+//! Advisories in the OSV schema are loaded from a JSON array, a JSON Lines
+//! file or a directory of per-advisory JSON files, and indexed by ecosystem
+//! and package name. Dependencies from the lockfile inventory are then
+//! matched natively: explicit `versions` lists and `SEMVER`/`ECOSYSTEM`
+//! ranges are evaluated with semver ordering for npm and crates.io and
+//! PEP 440 ordering for PyPI. `GIT` ranges cannot be evaluated against
+//! released versions and are ignored.
+
+use crate::dependencies::Dependency;
+use crate::errors::{self, ErrorCode};
+use napi_derive::napi;
+use rayon::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// A dependency affected by an advisory
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VulnerabilityMatch {
+    /// Advisory identifier (e.g. "GHSA-xxxx-xxxx-xxxx", "RUSTSEC-2024-0001")
+    pub advisory_id: String,
+    /// Other identifiers for the same issue (e.g. CVE numbers)
+    pub aliases: Vec<String>,
+    /// One-line advisory summary
+    pub summary: Option<String>,
+    /// Affected package name
+    pub package: String,
+    /// Installed version that matched
+    pub version: String,
+    /// OSV ecosystem of the package
+    pub ecosystem: String,
+    /// Severity label from the database (e.g. "HIGH", "MODERATE")
+    pub severity: Option<String>,
+    /// CVSS vector, if the advisory provides one
+    pub cvss: Option<String>,
+    /// Versions that fix the issue
+    pub fixed_versions: Vec<String>,
+    /// Affected ranges in readable form (e.g. ">=1.0.0, <1.2.3")
+    pub affected_ranges: Vec<String>,
+}
+
+/// Version boundary within an OSV range
+#[derive(Debug, Clone)]
+enum RangeEvent {
+    Introduced(String),
+    Fixed(String),
+    LastAffected(String),
+    Limit(String),
+}
+
+/// One `affected` entry of an advisory
+#[derive(Debug, Clone)]
+struct AffectedPackage {
+    advisory: usize,
+    versions: Vec<String>,
+    ranges: Vec<Vec<RangeEvent>>,
+    severity: Option<String>,
+}
+
+/// Advisory fields shared by all of its affected packages
+#[derive(Debug, Clone)]
+struct Advisory {
+    id: String,
+    aliases: Vec<String>,
+    summary: Option<String>,
+    severity: Option<String>,
+    cvss: Option<String>,
+}
+
+/// Offline advisory database indexed by package
+#[napi]
+pub struct AdvisoryDatabase {
+    advisories: Vec<Advisory>,
+    packages: HashMap<(String, String), Vec<AffectedPackage>>,
+}
+
+#[napi]
+impl AdvisoryDatabase {
+    /// Load advisories from an OSV dump
+    ///
+    /// # Arguments
+    /// * `path` - JSON array, JSON Lines file or directory of OSV JSON files
+    #[napi(constructor)]
+    pub fn new(path: String) -> napi::Result<Self> {
+        errors::guard("new AdvisoryDatabase", || {
+            let records = load_records(Path::new(&path))?;
+            let mut database = Self {
+                advisories: Vec::new(),
+                packages: HashMap::new(),
+            };
+            for record in &records {
+                database.add_record(record);
+            }
+            Ok(database)
+        })
+    }
+
+    /// Number of advisories loaded
+    #[napi(getter)]
+    pub fn advisory_count(&self) -> u32 {
+        self.advisories.len() as u32
+    }
+
+    /// Match dependencies against the loaded advisories
+    ///
+    /// # Arguments
+    /// * `dependencies` - Inventory from `list_dependencies` or `parse_lockfile`
+    #[napi]
+    pub fn match_dependencies(&self, dependencies: Vec<Dependency>) -> napi::Result<Vec<VulnerabilityMatch>> {
+        errors::catch_panic("AdvisoryDatabase.matchDependencies", || {
+            let mut matches: Vec<VulnerabilityMatch> = dependencies
+                .par_iter()
+                .flat_map_iter(|dependency| self.match_dependency(dependency))
+                .collect();
+            matches.sort_by(|a, b| {
+                a.package
+                    .cmp(&b.package)
+                    .then(a.version.cmp(&b.version))
+                    .then(a.advisory_id.cmp(&b.advisory_id))
+            });
+            matches
+        })
+    }
+
+    /// Index one OSV record
+    fn add_record(&mut self, record: &Value) {
+        let Some(id) = record.get("id").and_then(Value::as_str) else { return };
+        let index = self.advisories.len();
+        self.advisories.push(Advisory {
+            id: id.to_string(),
+            aliases: string_array(record.get("aliases")),
+            summary: record.get("summary").and_then(Value::as_str).map(str::to_string),
+            severity: database_severity(record),
+            cvss: record
+                .get("severity")
+                .and_then(Value::as_array)
+                .and_then(|entries| entries.first())
+                .and_then(|entry| entry.get("score"))
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        });
+
+        for affected in record.get("affected").and_then(Value::as_array).into_iter().flatten() {
+            let Some(package) = affected.get("package") else { continue };
+            let (Some(ecosystem), Some(name)) = (
+                package.get("ecosystem").and_then(Value::as_str),
+                package.get("name").and_then(Value::as_str),
+            ) else {
+                continue;
+            };
+
+            let ranges = affected
+                .get("ranges")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter(|range| range.get("type").and_then(Value::as_str) != Some("GIT"))
+                .map(|range| {
+                    range
+                        .get("events")
+                        .and_then(Value::as_array)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(parse_event)
+                        .collect()
+                })
+                .collect();
+
+            self.packages
+                .entry(package_key(ecosystem, name))
+                .or_default()
+                .push(AffectedPackage {
+                    advisory: index,
+                    versions: string_array(affected.get("versions")),
+                    ranges,
+                    severity: database_severity(affected),
+                });
+        }
+    }
+
+    /// Advisories affecting one dependency
+    fn match_dependency(&self, dependency: &Dependency) -> Vec<VulnerabilityMatch> {
+        let key = package_key(&dependency.ecosystem, &dependency.name);
+        let Some(entries) = self.packages.get(&key) else {
+            return Vec::new();
+        };
+        let ecosystem = key.0.as_str();
+
+        entries
+            .iter()
+            .filter(|entry| {
+                entry.versions.iter().any(|v| v == &dependency.version)
+                    || entry
+                        .ranges
+                        .iter()
+                        .any(|events| in_range(ecosystem, &dependency.version, events))
+            })
+            .map(|entry| {
+                let advisory = &self.advisories[entry.advisory];
+                VulnerabilityMatch {
+                    advisory_id: advisory.id.clone(),
+                    aliases: advisory.aliases.clone(),
+                    summary: advisory.summary.clone(),
+                    package: dependency.name.clone(),
+                    version: dependency.version.clone(),
+                    ecosystem: dependency.ecosystem.clone(),
+                    severity: entry.severity.clone().or_else(|| advisory.severity.clone()),
+                    cvss: advisory.cvss.clone(),
+                    fixed_versions: entry
+                        .ranges
+                        .iter()
+                        .flatten()
+                        .filter_map(|event| match event {
+                            RangeEvent::Fixed(v) => Some(v.clone()),
+                            _ => None,
+                        })
+                        .collect(),
+                    affected_ranges: entry.ranges.iter().map(|events| describe_range(events)).collect(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Load an OSV database and match dependencies in one call
+///
+/// # Arguments
+/// * `dependencies` - Inventory from `list_dependencies` or `parse_lockfile`
+/// * `advisory_db_path` - JSON array, JSON Lines file or directory of OSV JSON files
+#[napi]
+pub fn match_vulnerabilities(
+    dependencies: Vec<Dependency>,
+    advisory_db_path: String,
+) -> napi::Result<Vec<VulnerabilityMatch>> {
+    errors::guard("matchVulnerabilities", || {
+        let database = AdvisoryDatabase::new(advisory_db_path)?;
+        database.match_dependencies(dependencies)
+    })
+}
+
+/// Read OSV records from a file or directory
+fn load_records(path: &Path) -> napi::Result<Vec<Value>> {
+    let read_error = |e: std::io::Error| {
+        errors::io(
+            &e,
+            format!("Failed to read advisory database {}: {}", path.display(), e),
+        )
+    };
+
+    if path.is_dir() {
+        let mut records = Vec::new();
+        let mut files: Vec<_> = fs::read_dir(path)
+            .map_err(read_error)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .collect();
+        files.sort();
+        for file in files {
+            if file.is_dir() || file.extension().is_some_and(|ext| ext == "json" || ext == "jsonl") {
+                records.extend(load_records(&file)?);
+            }
+        }
+        return Ok(records);
+    }
+
+    let content = fs::read_to_string(path).map_err(read_error)?;
+    let parse_error = |e: serde_json::Error| {
+        errors::new(
+            ErrorCode::ParseError,
+            format!("Invalid OSV JSON in {}: {}", path.display(), e),
+        )
+    };
+    match serde_json::from_str::<Value>(&content) {
+        Ok(Value::Array(records)) => Ok(records),
+        Ok(record) => Ok(vec![record]),
+        // Not a single document: treat as JSON Lines
+        Err(_) => content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(parse_error))
+            .collect(),
+    }
+}
+
+fn parse_event(event: &Value) -> Option<RangeEvent> {
+    let field = |key: &str| event.get(key).and_then(Value::as_str).map(str::to_string);
+    field("introduced")
+        .map(RangeEvent::Introduced)
+        .or_else(|| field("fixed").map(RangeEvent::Fixed))
+        .or_else(|| field("last_affected").map(RangeEvent::LastAffected))
+        .or_else(|| field("limit").map(RangeEvent::Limit))
+}
+
+/// Evaluate a range: the version must follow an `introduced` event with no
+/// later `fixed`/`last_affected` boundary at or below it
+fn in_range(ecosystem: &str, version: &str, events: &[RangeEvent]) -> bool {
+    let mut affected = false;
+    let at_or_after = |boundary: &str| boundary == "0" || compare_versions(ecosystem, version, boundary) != Ordering::Less;
+
+    let mut sorted: Vec<&RangeEvent> = events.iter().collect();
+    sorted.sort_by(|a, b| {
+        let (va, vb) = (event_version(a), event_version(b));
+        match (va, vb) {
+            ("0", "0") => Ordering::Equal,
+            ("0", _) => Ordering::Less,
+            (_, "0") => Ordering::Greater,
+            _ => compare_versions(ecosystem, va, vb),
+        }
+    });
+
+    for event in sorted {
+        match event {
+            RangeEvent::Introduced(v) if at_or_after(v) => affected = true,
+            RangeEvent::Fixed(v) if at_or_after(v) => affected = false,
+            RangeEvent::LastAffected(v) if compare_versions(ecosystem, version, v) == Ordering::Greater => {
+                affected = false
+            }
+            RangeEvent::Limit(v) if at_or_after(v) => return false,
+            _ => {}
+        }
+    }
+    affected
+}
+
+fn event_version(event: &RangeEvent) -> &str {
+    match event {
+        RangeEvent::Introduced(v) | RangeEvent::Fixed(v) | RangeEvent::LastAffected(v) | RangeEvent::Limit(v) => v,
+    }
+}
+
+/// ">=1.0.0, <1.2.3" style description of a range
+fn describe_range(events: &[RangeEvent]) -> String {
+    events
+        .iter()
+        .map(|event| match event {
+            RangeEvent::Introduced(v) => format!(">={}", v),
+            RangeEvent::Fixed(v) => format!("<{}", v),
+            RangeEvent::LastAffected(v) => format!("<={}", v),
+            RangeEvent::Limit(v) => format!("<{} (limit)", v),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Compare versions using the ordering of the package's ecosystem
+pub(crate) fn compare_versions(ecosystem: &str, a: &str, b: &str) -> Ordering {
+    if ecosystem == "pypi" {
+        if let (Some(a), Some(b)) = (Pep440::parse(a), Pep440::parse(b)) {
+            return a.cmp(&b);
+        }
+    } else if let (Ok(a), Ok(b)) = (
+        semver::Version::parse(a.trim_start_matches(['v', '='])),
+        semver::Version::parse(b.trim_start_matches(['v', '='])),
+    ) {
+        return a.cmp_precedence(&b);
+    }
+    compare_segments(a, b)
+}
+
+/// Fallback ordering: dot/dash separated segments, numeric where possible
+fn compare_segments(a: &str, b: &str) -> Ordering {
+    let split = |s: &str| -> Vec<String> {
+        s.split(['.', '-', '+', '_']).map(str::to_string).collect()
+    };
+    let (a, b) = (split(a), split(b));
+    for (x, y) in a.iter().zip(&b) {
+        let ordering = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            _ => x.cmp(y),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+/// PEP 440 version reduced to a sortable key
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Pep440 {
+    epoch: u64,
+    release: Vec<u64>,
+    /// (phase, number): dev-only releases sort first, finals last
+    pre: (i8, u64),
+    /// -1 when there is no post release
+    post: i64,
+    /// u64::MAX when there is no dev release
+    dev: u64,
+}
+
+impl Pep440 {
+    fn parse(version: &str) -> Option<Self> {
+        static PATTERN: OnceLock<Regex> = OnceLock::new();
+        let pattern = PATTERN.get_or_init(|| {
+            Regex::new(
+                r"^v?(?:(\d+)!)?(\d+(?:\.\d+)*)(?:[-_.]?(a|alpha|b|beta|c|rc|pre|preview)[-_.]?(\d*))?(?:-(\d+)|[-_.]?(?:post|rev|r)[-_.]?(\d*))?(?:[-_.]?dev[-_.]?(\d*))?(?:\+[a-z0-9.]*)?$",
+            )
+            .expect("valid PEP 440 pattern")
+        });
+        let lowered = version.trim().to_ascii_lowercase();
+        let caps = pattern.captures(&lowered)?;
+        let number = |i: usize| caps.get(i).map(|m| m.as_str().parse::<u64>().unwrap_or(0));
+
+        let mut release: Vec<u64> = caps[2].split('.').filter_map(|n| n.parse().ok()).collect();
+        while release.len() > 1 && release.last() == Some(&0) {
+            release.pop();
+        }
+        let has_post = caps.get(5).is_some() || caps.get(6).is_some();
+        let dev = number(7);
+        let pre = match caps.get(3).map(|m| m.as_str()) {
+            Some("a" | "alpha") => (1, number(4).unwrap_or(0)),
+            Some("b" | "beta") => (2, number(4).unwrap_or(0)),
+            Some(_) => (3, number(4).unwrap_or(0)),
+            None if dev.is_some() && !has_post => (0, 0),
+            None => (4, 0),
+        };
+
+        Some(Self {
+            epoch: number(1).unwrap_or(0),
+            release,
+            pre,
+            post: if has_post { number(5).or(number(6)).unwrap_or(0) as i64 } else { -1 },
+            dev: dev.unwrap_or(u64::MAX),
+        })
+    }
+}
+
+/// Index key: lowercased ecosystem plus its canonical package name
+fn package_key(ecosystem: &str, name: &str) -> (String, String) {
+    let ecosystem = ecosystem.to_ascii_lowercase();
+    let name = if ecosystem == "pypi" {
+        name.to_ascii_lowercase().replace(['_', '.'], "-")
+    } else {
+        name.to_string()
+    };
+    (ecosystem, name)
+}
+
+/// GitHub-style `database_specific.severity` label
+fn database_severity(value: &Value) -> Option<String> {
+    value
+        .get("database_specific")
+        .and_then(|db| db.get("severity"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn string_array(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|items| items.iter().filter_map(Value::as_str).map(str::to_string).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,219 @@
+//! Crash-safe file writes
+//!
+//! Contents are written to a temporary file in the target's directory and
+//! renamed over the target, so readers and a crashed process only ever see
+//! the old file or the complete new one, never a truncated mix. With
+//! `fsync` the data is flushed before the rename and the directory after
+//! it, so the new file also survives a power loss.
+//!
+//! The replacement keeps the old file's permission bits unless a mode is
+//! given. Writing to a symlink replaces the file it points to, keeping the
+//! link. moidvk's own writers of indexes, baselines, records and outputs use
+//! the same path as `writeFileAtomic`.
+
+use crate::audit::{self, AuditKind};
+use crate::errors;
+#[cfg(feature = "security")]
+use crate::security_utils::quota::{self, Mutation};
+use napi::bindgen_prelude::{Buffer, Either};
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Temporary files created by this process, for unique names
+static TEMP_FILES: AtomicU64 = AtomicU64::new(0);
+
+/// Options for `writeFileAtomic`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AtomicWriteOptions {
+    /// Flush the file and its directory to disk before returning
+    pub fsync: bool,
+    /// Unix permission bits for the file, e.g. 0o600 (overrides `preserve_mode`)
+    pub mode: Option<u32>,
+    /// Keep the permission bits of the file being replaced
+    pub preserve_mode: bool,
+}
+
+impl Default for AtomicWriteOptions {
+    fn default() -> Self {
+        Self {
+            fsync: true,
+            mode: None,
+            preserve_mode: true,
+        }
+    }
+}
+
+/// A temporary file that replaces its target on `commit`
+///
+/// Dropping it without committing removes the temporary file and leaves the
+/// target untouched.
+pub(crate) struct AtomicFile {
+    file: File,
+    temp: PathBuf,
+    target: PathBuf,
+    fsync: bool,
+    committed: bool,
+}
+
+impl AtomicFile {
+    /// Start writing a replacement for `path`
+    pub(crate) fn create(path: &Path, options: &AtomicWriteOptions) -> io::Result<Self> {
+        let target = resolve_symlink(path)?;
+        let directory = match target.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let Some(name) = target.file_name() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("not a file path: {}", path.display()),
+            ));
+        };
+
+        let mut temp_name = OsString::from(".");
+        temp_name.push(name);
+        temp_name.push(format!(".{}.tmp", unique_suffix()));
+        let temp = directory.join(temp_name);
+        let file = OpenOptions::new().write(true).create_new(true).open(&temp)?;
+
+        let previous = fs::metadata(&target).ok().map(|m| m.permissions());
+        let atomic = Self {
+            file,
+            temp,
+            target,
+            fsync: options.fsync,
+            committed: false,
+        };
+        match (options.mode, previous) {
+            (Some(mode), _) => set_mode(&atomic.file, mode)?,
+            (None, Some(permissions)) if options.preserve_mode => atomic.file.set_permissions(permissions)?,
+            _ => {}
+        }
+        Ok(atomic)
+    }
+
+    /// Flush the contents and rename the temporary file over the target
+    pub(crate) fn commit(mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if self.fsync {
+            self.file.sync_all()?;
+        }
+        fs::rename(&self.temp, &self.target)?;
+        self.committed = true;
+        if self.fsync {
+            sync_directory(&self.target)?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if !self.committed {
+            let _ = fs::remove_file(&self.temp);
+        }
+    }
+}
+
+/// Replace `path` with `contents` atomically
+pub(crate) fn write(path: &Path, contents: &[u8], options: &AtomicWriteOptions) -> io::Result<()> {
+    let mut file = AtomicFile::create(path, options)?;
+    file.write_all(contents)?;
+    file.commit()
+}
+
+/// Write a file atomically: temp file, optional fsync, then rename
+///
+/// # Arguments
+/// * `path` - File to create or replace (its directory must exist)
+/// * `contents` - Text or bytes to write
+/// * `options` - Optional fsync and permission settings (fsync and preserve_mode default to true)
+#[napi]
+pub fn write_file_atomic(
+    path: String,
+    contents: Either<String, Buffer>,
+    options: Option<AtomicWriteOptions>,
+) -> napi::Result<()> {
+    errors::guard("writeFileAtomic", || {
+        let options = options.unwrap_or_default();
+        let bytes: &[u8] = match &contents {
+            Either::A(text) => text.as_bytes(),
+            Either::B(buffer) => buffer,
+        };
+        #[cfg(feature = "security")]
+        quota::charge("write_file_atomic", Mutation::Write, Path::new(&path), bytes.len() as u64)?;
+        let result = write(Path::new(&path), bytes, &options)
+            .map_err(|e| errors::io(&e, format!("Failed to write {}: {}", path, e)));
+        match &result {
+            Ok(()) => audit::record(AuditKind::FileMutation, "write_file_atomic", &path, "success", None),
+            Err(e) => audit::record(AuditKind::FileMutation, "write_file_atomic", &path, "failure", Some(&e.reason)),
+        }
+        result
+    })
+}
+
+/// The file a symlink points to, or `path` itself
+fn resolve_symlink(path: &Path) -> io::Result<PathBuf> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_symlink() => fs::canonicalize(path).or_else(|_| {
+            // Dangling: the write creates the file the link names
+            let link = fs::read_link(path)?;
+            Ok(path.parent().unwrap_or(Path::new("")).join(link))
+        }),
+        _ => Ok(path.to_path_buf()),
+    }
+}
+
+/// Suffix that keeps concurrent writers' temporary files apart
+fn unique_suffix() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    format!("{}-{:x}-{}", std::process::id(), nanos, TEMP_FILES.fetch_add(1, Ordering::Relaxed))
+}
+
+#[cfg(unix)]
+fn set_mode(file: &File, mode: u32) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    file.set_permissions(fs::Permissions::from_mode(mode & 0o7777))
+}
+
+#[cfg(not(unix))]
+fn set_mode(file: &File, mode: u32) -> io::Result<()> {
+    // Only the read-only flag exists; a mode without owner write maps to it
+    let mut permissions = file.metadata()?.permissions();
+    permissions.set_readonly(mode & 0o200 == 0);
+    file.set_permissions(permissions)
+}
+
+/// Flush the directory entry of a renamed file
+#[cfg(unix)]
+fn sync_directory(path: &Path) -> io::Result<()> {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => File::open(parent)?.sync_all(),
+        _ => File::open(".")?.sync_all(),
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_directory(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
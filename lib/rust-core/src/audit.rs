@@ -0,0 +1,272 @@
+//! Opt-in audit log of security-relevant native operations
+//!
+//! When enabled, path validations, sandbox denials and file mutations are
+//! recorded with a timestamp and the caller-provided context into a bounded
+//! in-memory ring buffer. JavaScript can read the buffer or flush it to a
+//! JSON Lines file for forensic review of what an automated agent touched.
+//! Recording is a single atomic load while the log is disabled.
+
+use napi_derive::napi;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::errors::{self, ErrorCode};
+use crate::file_lock;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static LOG: Mutex<Option<AuditLog>> = Mutex::new(None);
+
+/// Audit log settings
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditConfig {
+    /// Maximum events kept in memory; the oldest are dropped first
+    pub capacity: u32,
+    /// Key/value context attached to every event (e.g. agent or task id)
+    pub context: Option<HashMap<String, String>>,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            context: None,
+        }
+    }
+}
+
+/// A recorded operation
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEvent {
+    /// Monotonic sequence number, starting at 1
+    pub sequence: f64,
+    /// RFC 3339 UTC timestamp
+    pub timestamp: String,
+    /// Event kind: "path_validation", "access_denied" or "file_mutation"
+    pub kind: String,
+    /// Native operation that produced the event (e.g. "validate_path")
+    pub operation: String,
+    /// Path the operation concerned
+    pub path: String,
+    /// Result: "allowed", "denied", "success" or "failure"
+    pub outcome: String,
+    /// Violation, denial reason or error, when there is one
+    pub detail: Option<String>,
+    /// Caller-provided context at the time of the event
+    pub context: Option<HashMap<String, String>>,
+}
+
+/// Current state of the audit log
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditStatus {
+    /// Whether events are being recorded
+    pub enabled: bool,
+    /// Events currently buffered
+    pub buffered: u32,
+    /// Buffer capacity
+    pub capacity: u32,
+    /// Events dropped because the buffer was full
+    pub dropped: f64,
+}
+
+/// Kinds of audited events
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum AuditKind {
+    PathValidation,
+    AccessDenied,
+    FileMutation,
+}
+
+impl AuditKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::PathValidation => "path_validation",
+            Self::AccessDenied => "access_denied",
+            Self::FileMutation => "file_mutation",
+        }
+    }
+}
+
+struct AuditLog {
+    capacity: usize,
+    context: Option<HashMap<String, String>>,
+    events: VecDeque<AuditEvent>,
+    next_sequence: u64,
+    dropped: u64,
+}
+
+/// Record an event if the audit log is enabled
+pub(crate) fn record(kind: AuditKind, operation: &str, path: &str, outcome: &str, detail: Option<&str>) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut guard = LOG.lock();
+    let Some(log) = guard.as_mut() else { return };
+
+    log.next_sequence += 1;
+    let event = AuditEvent {
+        sequence: log.next_sequence as f64,
+        timestamp: rfc3339(SystemTime::now()),
+        kind: kind.as_str().to_string(),
+        operation: operation.to_string(),
+        path: path.to_string(),
+        outcome: outcome.to_string(),
+        detail: detail.map(str::to_string),
+        context: log.context.clone(),
+    };
+    if log.events.len() >= log.capacity {
+        log.events.pop_front();
+        log.dropped += 1;
+    }
+    log.events.push_back(event);
+}
+
+/// Start recording events, replacing any previous buffer
+///
+/// # Arguments
+/// * `config` - Optional buffer capacity and context
+#[napi]
+pub fn enable_audit_log(config: Option<AuditConfig>) -> napi::Result<()> {
+    errors::guard("enableAuditLog", || {
+        let config = config.unwrap_or_default();
+        if config.capacity == 0 {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                "Audit log capacity must be at least 1".to_string(),
+            ));
+        }
+        *LOG.lock() = Some(AuditLog {
+            capacity: config.capacity as usize,
+            context: config.context,
+            events: VecDeque::new(),
+            next_sequence: 0,
+            dropped: 0,
+        });
+        ENABLED.store(true, Ordering::Relaxed);
+        Ok(())
+    })
+}
+
+/// Stop recording; buffered events stay readable until cleared
+#[napi]
+pub fn disable_audit_log() {
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+/// Replace the context attached to subsequent events
+///
+/// # Arguments
+/// * `context` - Key/value pairs, or nothing to clear the context
+#[napi]
+pub fn set_audit_context(context: Option<HashMap<String, String>>) {
+    if let Some(log) = LOG.lock().as_mut() {
+        log.context = context;
+    }
+}
+
+/// Buffered events, oldest first
+///
+/// # Arguments
+/// * `since_sequence` - Only return events after this sequence number
+#[napi]
+pub fn get_audit_events(since_sequence: Option<f64>) -> Vec<AuditEvent> {
+    let since = since_sequence.unwrap_or(0.0);
+    LOG.lock()
+        .as_ref()
+        .map(|log| log.events.iter().filter(|e| e.sequence > since).cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Append buffered events to a JSON Lines file and empty the buffer
+///
+/// Returns the number of events written.
+///
+/// # Arguments
+/// * `path` - File to append to (created if missing)
+#[napi]
+pub fn flush_audit_log(path: String) -> napi::Result<u32> {
+    errors::guard("flushAuditLog", || {
+        let mut guard = LOG.lock();
+        let Some(log) = guard.as_mut() else { return Ok(0) };
+
+        let mut output = String::new();
+        for event in &log.events {
+            let line = serde_json::to_string(event).map_err(|e| {
+                errors::new(ErrorCode::Internal, format!("Failed to serialize audit event: {}", e))
+            })?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        let _lock = file_lock::lock(Path::new(&path), false, file_lock::DEFAULT_TIMEOUT)?;
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(output.as_bytes()))
+            .map_err(|e| {
+                errors::io(&e, format!("Failed to write {}: {}", path, e))
+            })?;
+
+        let written = log.events.len() as u32;
+        log.events.clear();
+        Ok(written)
+    })
+}
+
+/// Discard buffered events without writing them
+#[napi]
+pub fn clear_audit_log() {
+    if let Some(log) = LOG.lock().as_mut() {
+        log.events.clear();
+        log.dropped = 0;
+    }
+}
+
+/// Whether the log is recording, and how full the buffer is
+#[napi]
+pub fn get_audit_status() -> AuditStatus {
+    let guard = LOG.lock();
+    AuditStatus {
+        enabled: ENABLED.load(Ordering::Relaxed),
+        buffered: guard.as_ref().map_or(0, |log| log.events.len() as u32),
+        capacity: guard.as_ref().map_or(0, |log| log.capacity as u32),
+        dropped: guard.as_ref().map_or(0.0, |log| log.dropped as f64),
+    }
+}
+
+/// RFC 3339 UTC timestamp with millisecond precision
+pub(crate) fn rfc3339(time: SystemTime) -> String {
+    let millis = time.duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0);
+    let (secs, millis) = (millis / 1_000, millis % 1_000);
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60,
+        millis
+    )
+}
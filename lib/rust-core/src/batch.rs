@@ -0,0 +1,19 @@
+//! Per-item work over string batches
+//!
+//! Batch exports such as `soundex` and `convertCase` map one function over
+//! every input, in parallel once a batch is large enough to repay spreading
+//! it over the rayon pool.
+
+use rayon::prelude::*;
+
+/// Batches larger than this are processed in parallel
+pub(crate) const PARALLEL_THRESHOLD: usize = 1000;
+
+/// Map a function over a batch, in parallel for large batches
+pub(crate) fn map_batch<T: Send>(items: &[String], f: impl Fn(&str) -> T + Sync + Send) -> Vec<T> {
+    if items.len() > PARALLEL_THRESHOLD {
+        items.par_iter().map(|item| f(item)).collect()
+    } else {
+        items.iter().map(|item| f(item)).collect()
+    }
+}
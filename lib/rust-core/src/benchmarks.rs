@@ -2,11 +2,218 @@
 //!
 //! This module provides tools to measure and compare the performance
 //! of Rust implementations against JavaScript alternatives.
+//!
+//! Each benchmark runs warmup iterations, then records one sample per
+//! iteration until both the minimum iteration count and the measurement
+//! time are reached. Results summarize the samples the way Criterion does:
+//! mean, median, spread, tail percentiles and a bootstrap confidence
+//! interval for the mean. Native allocations during the measurement are
+//! counted as well, giving allocations per iteration and peak heap growth.
+//!
+//! Custom benchmarks registered from JavaScript are timed with the same
+//! sampler: the callback is invoked through a ThreadsafeFunction from a
+//! worker thread and timed on the JavaScript thread around the call.
+//! JavaScript reference implementations of the native benchmarks are timed
+//! the same way, and their medians give the measured speedups.
+//!
+//! Results can be saved as a JSON baseline and later runs compared against
+//! it, so CI can fail on native-performance regressions. For dashboards,
+//! results export as versioned JSON or CSV with the machine they ran on.
 
+use crate::atomic_write::{self, AtomicWriteOptions};
+use crate::audit::rfc3339;
+use crate::cpu;
+use crate::errors::{self, ErrorCode};
+use crate::file_lock;
+use crate::memory;
+use crate::security_utils::quota::{self, Mutation};
+use crate::synthetic::{self, SeededRng, SyntheticEmbeddingConfig, SyntheticTreeConfig};
+use napi::bindgen_prelude::AsyncTask;
+use napi::threadsafe_function::{
+    ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+};
+use napi::{Env, JsFunction, JsUnknown, Task};
 use napi_derive::napi;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::time::Instant;
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Bootstrap resamples used for the confidence interval
+const BOOTSTRAP_RESAMPLES: usize = 1000;
+
+/// A built-in benchmark body
+type BuiltinBenchmark = fn(&BenchmarkConfig, &BenchmarkWorkload) -> napi::Result<BenchmarkResult>;
+
+/// Built-in native benchmarks by name, in run order
+const BUILTIN_BENCHMARKS: [(&str, BuiltinBenchmark); 3] = [
+    ("vector_ops", benchmark_vector_operations),
+    ("file_search", benchmark_file_search),
+    ("text_processing", benchmark_text_processing),
+];
+
+/// Default embedding dimensions of the vector benchmark
+const DEFAULT_VECTOR_DIMENSIONS: u32 = 1536;
+
+/// Default corpus size of the vector benchmark
+const DEFAULT_VECTOR_COUNT: u32 = 1000;
+
+/// Default glob of the file search benchmark
+const DEFAULT_SEARCH_PATTERN: &str = "*.rs";
+
+/// Default text size of the text benchmark (1000 repetitions of `SAMPLE_SENTENCE`)
+const DEFAULT_TEXT_BYTES: u32 = 45_000;
+
+/// Default number of patterns of the text benchmark
+const DEFAULT_PATTERN_COUNT: u32 = 5;
+
+/// Line repeated to build the text benchmark corpus
+const SAMPLE_SENTENCE: &str = "The quick brown fox jumps over the lazy dog.\n";
+
+/// Patterns searched by the text benchmark, in order of use
+const SAMPLE_PATTERNS: [&str; 8] = ["quick", "brown", "fox", "lazy", "dog", "jumps", "over", "The"];
+
+/// Version of the baseline file format
+///
+/// Result fields added since version 1 carry `#[serde(default)]` (or are
+/// optional), so older baselines still load.
+const BASELINE_VERSION: u32 = 1;
+
+/// Default allowed slowdown before a benchmark counts as regressed (10%)
+const DEFAULT_REGRESSION_TOLERANCE: f64 = 0.1;
+
+/// Identifier of the exported results document
+const EXPORT_SCHEMA: &str = "moidvk-benchmark-results";
+
+/// Version of the exported results document; bumped on breaking changes
+const EXPORT_VERSION: u32 = 1;
+
+/// JSON Schema of the exported results document
+const EXPORT_JSON_SCHEMA: &str = r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "$id": "moidvk-benchmark-results/1",
+  "type": "object",
+  "required": ["schema", "version", "generated_at", "environment", "config", "results"],
+  "properties": {
+    "schema": { "const": "moidvk-benchmark-results" },
+    "version": { "const": 1 },
+    "generated_at": { "type": "string", "format": "date-time" },
+    "environment": {
+      "type": "object",
+      "required": ["os", "arch", "cpu_model", "cpu_cores", "rayon_threads", "simd_features", "crate_version", "optimization_level"],
+      "properties": {
+        "os": { "type": "string" },
+        "arch": { "type": "string" },
+        "cpu_model": { "type": "string" },
+        "cpu_cores": { "type": "integer", "minimum": 1 },
+        "rayon_threads": { "type": "integer", "minimum": 1 },
+        "simd_features": { "type": "array", "items": { "type": "string" } },
+        "crate_version": { "type": "string" },
+        "optimization_level": { "enum": ["debug", "release"] }
+      }
+    },
+    "config": {
+      "type": "object",
+      "required": ["warmup_iterations", "warmup_time_ms", "min_iterations", "max_iterations", "measurement_time_ms", "confidence_level"]
+    },
+    "results": {
+      "type": "array",
+      "items": {
+        "type": "object",
+        "required": ["name", "iterations", "avg_time_ms", "median_ms", "stddev_ms", "min_ms", "max_ms", "p95_ms", "p99_ms", "ci_lower_ms", "ci_upper_ms", "confidence_level", "ops_per_sec", "samples_ms"],
+        "properties": {
+          "name": { "type": "string" },
+          "iterations": { "type": "integer" },
+          "avg_time_ms": { "type": "number" },
+          "median_ms": { "type": "number" },
+          "stddev_ms": { "type": "number" },
+          "min_ms": { "type": "number" },
+          "max_ms": { "type": "number" },
+          "p95_ms": { "type": "number" },
+          "p99_ms": { "type": "number" },
+          "ci_lower_ms": { "type": "number" },
+          "ci_upper_ms": { "type": "number" },
+          "confidence_level": { "type": "number" },
+          "ops_per_sec": { "type": "number" },
+          "item_unit": { "type": "string" },
+          "items_per_iteration": { "type": "number" },
+          "bytes_per_iteration": { "type": ["number", "null"] },
+          "megabytes_per_sec": { "type": ["number", "null"] },
+          "speedup": { "type": ["number", "null"] },
+          "js_median_ms": { "type": ["number", "null"] },
+          "allocations_per_iteration": { "type": "number" },
+          "allocated_bytes_per_iteration": { "type": "number" },
+          "peak_memory_growth_bytes": { "type": "number" },
+          "peak_rss_bytes": { "type": "number" },
+          "samples_ms": { "type": "array", "items": { "type": "number" } }
+        }
+      }
+    }
+  }
+}"##;
+
+/// Benchmark timing settings
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkConfig {
+    /// Untimed iterations run before sampling
+    pub warmup_iterations: u32,
+    /// Keep warming up until this much time has passed
+    pub warmup_time_ms: u32,
+    /// Minimum number of samples
+    pub min_iterations: u32,
+    /// Maximum number of samples
+    pub max_iterations: u32,
+    /// Keep sampling until this much time has passed (bounded by `max_iterations`)
+    pub measurement_time_ms: u32,
+    /// Confidence level of the interval for the mean, between 0 and 1
+    pub confidence_level: f64,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            warmup_iterations: 3,
+            warmup_time_ms: 200,
+            min_iterations: 10,
+            max_iterations: 1000,
+            measurement_time_ms: 2000,
+            confidence_level: 0.95,
+        }
+    }
+}
+
+/// Which built-in benchmarks run and on what data
+///
+/// Unset fields keep the default workload.
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BenchmarkWorkload {
+    /// Built-in benchmarks to run: "vector_ops", "file_search" and/or
+    /// "text_processing" (default all)
+    pub benchmarks: Option<Vec<String>>,
+    /// Dimensions of each vector (default 1536)
+    pub vector_dimensions: Option<u32>,
+    /// Number of vectors compared against the query (default 1000)
+    pub vector_count: Option<u32>,
+    /// Directory searched by the file search benchmark (default the working directory)
+    pub search_root: Option<String>,
+    /// Glob searched for by the file search benchmark (default "*.rs")
+    pub search_pattern: Option<String>,
+    /// Size of the text searched by the text benchmark in bytes (default 45000)
+    pub text_bytes: Option<u32>,
+    /// Number of patterns searched for at once (default 5)
+    pub pattern_count: Option<u32>,
+    /// Seed for reproducible data: the vector benchmark uses a synthetic
+    /// embedding corpus, and without `search_root` the file search runs on
+    /// a synthetic tree generated in a temporary directory
+    pub synthetic_seed: Option<u32>,
+}
 
 /// Benchmark result
 #[napi(object)]
@@ -16,159 +223,1114 @@ pub struct BenchmarkResult {
     pub name: String,
     /// Average execution time in milliseconds
     pub avg_time_ms: f64,
-    /// Operations per second
+    /// Items processed per second (see `item_unit`)
     pub ops_per_sec: f64,
-    /// Performance improvement ratio
+    /// What one processed item is, e.g. "vectors", "files" or "lines"
+    #[serde(default)]
+    pub item_unit: String,
+    /// Items processed per iteration
+    #[serde(default)]
+    pub items_per_iteration: f64,
+    /// Bytes processed per iteration, for benchmarks that consume data
+    pub bytes_per_iteration: Option<f64>,
+    /// Data throughput in megabytes (10^6 bytes) per second
+    pub megabytes_per_sec: Option<f64>,
+    /// Measured speedup over the JavaScript reference (JS median divided by
+    /// this median); absent until a reference has been compared
+    pub speedup: Option<f64>,
+    /// Median of the JavaScript reference in milliseconds, when compared
+    pub js_median_ms: Option<f64>,
+    /// Number of timed iterations
+    pub iterations: u32,
+    /// Median iteration time in milliseconds
+    pub median_ms: f64,
+    /// Sample standard deviation in milliseconds
+    pub stddev_ms: f64,
+    /// Fastest iteration in milliseconds
+    pub min_ms: f64,
+    /// Slowest iteration in milliseconds
+    pub max_ms: f64,
+    /// 95th percentile in milliseconds
+    pub p95_ms: f64,
+    /// 99th percentile in milliseconds
+    pub p99_ms: f64,
+    /// Lower bound of the confidence interval for the mean
+    pub ci_lower_ms: f64,
+    /// Upper bound of the confidence interval for the mean
+    pub ci_upper_ms: f64,
+    /// Confidence level of the interval
+    pub confidence_level: f64,
+    /// Per-iteration times in milliseconds, in run order
+    pub samples_ms: Vec<f64>,
+    /// Native allocations per timed iteration
+    #[serde(default)]
+    pub allocations_per_iteration: f64,
+    /// Native bytes allocated per timed iteration, including freed ones
+    #[serde(default)]
+    pub allocated_bytes_per_iteration: f64,
+    /// Peak growth of live native memory over its level before sampling
+    #[serde(default)]
+    pub peak_memory_growth_bytes: f64,
+    /// Peak resident set size of the process after the benchmark
+    #[serde(default)]
+    pub peak_rss_bytes: f64,
+}
+
+/// Change of one benchmark relative to the baseline
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkComparison {
+    /// Benchmark name
+    pub name: String,
+    /// Baseline median in milliseconds
+    pub baseline_ms: f64,
+    /// Current median in milliseconds
+    pub current_ms: f64,
+    /// Current minus baseline median in milliseconds
+    pub delta_ms: f64,
+    /// Relative change of the median in percent (positive is slower)
+    pub delta_percent: f64,
+    /// "regressed", "improved" or "unchanged"
+    pub status: String,
+}
+
+/// Outcome of comparing the current results to a saved baseline
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegressionReport {
+    /// Whether no benchmark regressed beyond the tolerance
+    pub passed: bool,
+    /// Allowed relative slowdown (0.1 = 10%)
+    pub tolerance: f64,
+    /// When the baseline was saved (RFC 3339)
+    pub baseline_created_at: String,
+    /// Benchmarks present in both runs
+    pub comparisons: Vec<BenchmarkComparison>,
+    /// Names of the benchmarks that regressed
+    pub regressions: Vec<String>,
+    /// Baseline benchmarks missing from the current results
+    pub missing: Vec<String>,
+    /// Current benchmarks not in the baseline
+    pub added: Vec<String>,
+}
+
+/// Progress event emitted after each built-in benchmark
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkProgress {
+    /// Benchmark that just finished
+    pub name: String,
+    /// Benchmarks finished so far, including this one
+    pub completed: u32,
+    /// Benchmarks in the run
+    pub total: u32,
+    /// Result of the benchmark that just finished
+    pub result: BenchmarkResult,
+}
+
+/// Measured speedup of a native benchmark over its JavaScript reference
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeedupMeasurement {
+    /// Benchmark name
+    pub name: String,
+    /// Native median in milliseconds
+    pub native_median_ms: f64,
+    /// JavaScript reference median in milliseconds
+    pub js_median_ms: f64,
+    /// JavaScript median divided by native median (above 1 means native is faster)
     pub speedup: f64,
+    /// Full statistics of the JavaScript reference
+    pub js: BenchmarkResult,
+}
+
+/// Machine a benchmark run happened on
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkEnvironment {
+    /// Operating system, e.g. "linux"
+    pub os: String,
+    /// CPU architecture, e.g. "x86_64"
+    pub arch: String,
+    /// CPU brand string, or "unknown"
+    pub cpu_model: String,
+    /// Logical cores available to the process
+    pub cpu_cores: u32,
+    /// Threads in the rayon pool used by the parallel operations
+    pub rayon_threads: u32,
+    /// SIMD instruction sets detected at runtime
+    pub simd_features: Vec<String>,
+    /// Version of this crate
+    pub crate_version: String,
+    /// "debug" or "release"
+    pub optimization_level: String,
+}
+
+/// Exported results document (see `getBenchmarkResultsSchema`)
+#[derive(Debug, Serialize)]
+struct ResultsExport<'a> {
+    schema: &'static str,
+    version: u32,
+    generated_at: String,
+    environment: BenchmarkEnvironment,
+    config: &'a BenchmarkConfig,
+    results: Vec<BenchmarkResult>,
+}
+
+/// Saved benchmark results
+#[derive(Debug, Serialize, Deserialize)]
+struct BenchmarkBaseline {
+    version: u32,
+    created_at: String,
+    results: Vec<BenchmarkResult>,
 }
 
 /// Benchmark suite
 #[napi]
 pub struct BenchmarkSuite {
-    results: Vec<BenchmarkResult>,
+    config: BenchmarkConfig,
+    workload: BenchmarkWorkload,
+    results: Arc<Mutex<Vec<BenchmarkResult>>>,
+    custom: Vec<JsBenchmark>,
+    references: Vec<JsBenchmark>,
+}
+
+/// A benchmark whose body is a JavaScript callback
+#[derive(Clone)]
+struct JsBenchmark {
+    name: String,
+    ops_per_iteration: f64,
+    /// Invokes the timing wrapper around the user's callback
+    invoke: ThreadsafeFunction<(), ErrorStrategy::Fatal>,
+    /// Per-call outcomes sent back by the timing wrapper
+    outcomes: Arc<Mutex<Receiver<Result<Duration, String>>>>,
 }
 
 #[napi]
 impl BenchmarkSuite {
     #[napi(constructor)]
     /// Create a new benchmark runner instance
-    pub fn new() -> Self {
-        Self {
-            results: Vec::new(),
+    ///
+    /// # Arguments
+    /// * `config` - Optional warmup, iteration and timing settings
+    /// * `workload` - Optional subset of built-in benchmarks and their parameters
+    pub fn new(config: Option<BenchmarkConfig>, workload: Option<BenchmarkWorkload>) -> napi::Result<Self> {
+        let config = config.unwrap_or_default();
+        let workload = workload.unwrap_or_default();
+        if config.min_iterations == 0 || config.max_iterations < config.min_iterations {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                "Iteration counts must satisfy 0 < min_iterations <= max_iterations".to_string(),
+            ));
+        }
+        if !(config.confidence_level > 0.0 && config.confidence_level < 1.0) {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                "confidence_level must be between 0 and 1".to_string(),
+            ));
+        }
+        for name in workload.benchmarks.iter().flatten() {
+            if !BUILTIN_BENCHMARKS.iter().any(|(builtin, _)| builtin == name) {
+                let known: Vec<&str> = BUILTIN_BENCHMARKS.iter().map(|(builtin, _)| *builtin).collect();
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Unknown benchmark: {} (expected one of {})", name, known.join(", ")),
+                ));
+            }
+        }
+        let sizes = [
+            workload.vector_dimensions,
+            workload.vector_count,
+            workload.text_bytes,
+            workload.pattern_count,
+        ];
+        if sizes.contains(&Some(0)) {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                "Workload sizes must be positive".to_string(),
+            ));
         }
+        Ok(Self {
+            config,
+            workload,
+            results: Arc::new(Mutex::new(Vec::new())),
+            custom: Vec::new(),
+            references: Vec::new(),
+        })
+    }
+
+    /// Run the built-in benchmarks selected by the workload on a background thread
+    ///
+    /// Replaces the previous results. Resolves with the new results once
+    /// every benchmark has finished; `on_progress`, if given, is called with
+    /// a `BenchmarkProgress` after each one.
+    ///
+    /// # Arguments
+    /// * `on_progress` - Optional callback receiving per-benchmark progress
+    #[napi(ts_return_type = "Promise<BenchmarkResult[]>")]
+    pub fn run_all_benchmarks(&self, on_progress: Option<JsFunction>) -> napi::Result<AsyncTask<NativeBenchmarkTask>> {
+        let progress = on_progress
+            .map(|callback| {
+                callback.create_threadsafe_function(0, |ctx: ThreadSafeCallContext<BenchmarkProgress>| Ok(vec![ctx.value]))
+            })
+            .transpose()?;
+        Ok(AsyncTask::new(NativeBenchmarkTask {
+            config: self.config.clone(),
+            workload: self.workload.clone(),
+            results: self.results.clone(),
+            progress,
+        }))
     }
 
-    /// Run all performance benchmarks
+    /// Get benchmark results
     #[napi]
-    pub fn run_all_benchmarks(&mut self) -> napi::Result<Vec<BenchmarkResult>> {
-        // Clear previous results
-        self.results.clear();
+    pub fn get_results(&self) -> Vec<BenchmarkResult> {
+        self.results.lock().clone()
+    }
 
-        // Run individual benchmarks
-        self.benchmark_vector_operations()?;
-        self.benchmark_file_search()?;
-        self.benchmark_text_processing()?;
+    /// Save the current results as a JSON baseline
+    ///
+    /// # Arguments
+    /// * `path` - File to write, replaced if it exists
+    #[napi]
+    pub fn save_baseline(&self, path: String) -> napi::Result<()> {
+        errors::guard("BenchmarkSuite.saveBaseline", || {
+            let baseline = BenchmarkBaseline {
+                version: BASELINE_VERSION,
+                created_at: rfc3339(SystemTime::now()),
+                results: self.get_results(),
+            };
+            if baseline.results.is_empty() {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "No benchmark results to save; run the benchmarks first".to_string(),
+                ));
+            }
+            let json = serde_json::to_string_pretty(&baseline).map_err(|e| {
+                errors::new(ErrorCode::Internal, format!("Failed to serialize baseline: {}", e))
+            })?;
+            quota::charge("save_baseline", Mutation::Write, Path::new(&path), json.len() as u64)?;
+            let _lock = file_lock::lock(Path::new(&path), false, file_lock::DEFAULT_TIMEOUT)?;
+            atomic_write::write(Path::new(&path), json.as_bytes(), &AtomicWriteOptions::default()).map_err(|e| {
+                errors::io(
+                    &e,
+                    format!("Failed to write baseline {}: {}", path, e),
+                )
+            })
+        })
+    }
+
+    /// Compare the current results to a saved baseline
+    ///
+    /// Medians are compared, since they are less sensitive to outliers than
+    /// means. A benchmark regresses when its median grew by more than
+    /// `tolerance`; benchmarks present in only one run are listed but don't
+    /// fail the report.
+    ///
+    /// # Arguments
+    /// * `path` - Baseline written by `saveBaseline`
+    /// * `tolerance` - Allowed relative slowdown (default 0.1 for 10%)
+    #[napi]
+    pub fn compare_to_baseline(&self, path: String, tolerance: Option<f64>) -> napi::Result<RegressionReport> {
+        errors::guard("BenchmarkSuite.compareToBaseline", || {
+            let tolerance = tolerance.unwrap_or(DEFAULT_REGRESSION_TOLERANCE);
+            if !tolerance.is_finite() || tolerance < 0.0 {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "tolerance must be a non-negative number".to_string(),
+                ));
+            }
+            let content = file_lock::read_to_string(Path::new(&path)).map_err(|e| {
+                errors::io(
+                    &e,
+                    format!("Failed to read baseline {}: {}", path, e),
+                )
+            })?;
+            let baseline: BenchmarkBaseline = serde_json::from_str(&content).map_err(|e| {
+                errors::new(
+                    ErrorCode::ParseError,
+                    format!("Invalid baseline {}: {}", path, e),
+                )
+            })?;
+            if baseline.version != BASELINE_VERSION {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Unsupported baseline version {} in {}", baseline.version, path),
+                ));
+            }
+
+            let current = self.get_results();
+            let mut comparisons = Vec::new();
+            let mut regressions = Vec::new();
+            let mut missing = Vec::new();
+            for before in &baseline.results {
+                let Some(after) = current.iter().find(|result| result.name == before.name) else {
+                    missing.push(before.name.clone());
+                    continue;
+                };
+                let delta_ms = after.median_ms - before.median_ms;
+                let delta_percent = if before.median_ms > 0.0 { delta_ms / before.median_ms * 100.0 } else { 0.0 };
+                let status = if delta_percent > tolerance * 100.0 {
+                    regressions.push(before.name.clone());
+                    "regressed"
+                } else if delta_percent < -tolerance * 100.0 {
+                    "improved"
+                } else {
+                    "unchanged"
+                };
+                comparisons.push(BenchmarkComparison {
+                    name: before.name.clone(),
+                    baseline_ms: before.median_ms,
+                    current_ms: after.median_ms,
+                    delta_ms,
+                    delta_percent,
+                    status: status.to_string(),
+                });
+            }
+            let added = current
+                .iter()
+                .filter(|result| !baseline.results.iter().any(|before| before.name == result.name))
+                .map(|result| result.name.clone())
+                .collect();
 
-        Ok(self.results.clone())
+            Ok(RegressionReport {
+                passed: regressions.is_empty(),
+                tolerance,
+                baseline_created_at: baseline.created_at,
+                comparisons,
+                regressions,
+                missing,
+                added,
+            })
+        })
     }
 
-    /// Get benchmark results
+    /// Export the current results with environment metadata
+    ///
+    /// JSON follows the versioned schema returned by
+    /// `getBenchmarkResultsSchema`. CSV has one row per benchmark with the
+    /// environment repeated on every row and without the raw samples.
+    ///
+    /// # Arguments
+    /// * `format` - "json" or "csv"
     #[napi]
-    pub fn get_results(&self) -> Vec<BenchmarkResult> {
-        self.results.clone()
+    pub fn export_results(&self, format: String) -> napi::Result<String> {
+        errors::guard("BenchmarkSuite.exportResults", || {
+            let export = ResultsExport {
+                schema: EXPORT_SCHEMA,
+                version: EXPORT_VERSION,
+                generated_at: rfc3339(SystemTime::now()),
+                environment: get_benchmark_environment(),
+                config: &self.config,
+                results: self.get_results(),
+            };
+            match format.to_ascii_lowercase().as_str() {
+                "json" => serde_json::to_string_pretty(&export).map_err(|e| {
+                    errors::new(ErrorCode::Internal, format!("Failed to serialize results: {}", e))
+                }),
+                "csv" => export_csv(&export).map_err(|e| {
+                    errors::new(ErrorCode::Internal, format!("Failed to write CSV: {}", e))
+                }),
+                other => Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Unknown export format: {} (expected json or csv)", other),
+                )),
+            }
+        })
     }
 
-    fn benchmark_vector_operations(&mut self) -> napi::Result<()> {
-        use crate::vector_ops::VectorOperations;
+    /// Register a benchmark whose measured body is a JavaScript callback
+    ///
+    /// The callback is called with no arguments once per iteration and
+    /// should do its work synchronously; only the time until it returns is
+    /// measured. Run registered benchmarks with `runCustomBenchmarks`.
+    ///
+    /// # Arguments
+    /// * `name` - Unique benchmark name
+    /// * `callback` - Function to benchmark
+    /// * `ops_per_iteration` - Items processed per call, used to scale `ops_per_sec` (default 1)
+    #[napi]
+    pub fn register_benchmark(
+        &mut self,
+        env: Env,
+        name: String,
+        callback: JsFunction,
+        ops_per_iteration: Option<f64>,
+    ) -> napi::Result<()> {
+        errors::guard("BenchmarkSuite.registerBenchmark", || {
+            if self.custom.iter().any(|benchmark| benchmark.name == name) {
+                return Err(errors::new(
+                    ErrorCode::AlreadyExists,
+                    format!("Benchmark already registered: {}", name),
+                ));
+            }
+            let ops_per_iteration = ops_per_iteration.unwrap_or(1.0);
+            if !ops_per_iteration.is_finite() || ops_per_iteration <= 0.0 {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "ops_per_iteration must be positive".to_string(),
+                ));
+            }
 
-        let vector_size = 1536; // Typical embedding size
-        let num_vectors = 1000;
-        
-        // Generate test data
-        let query: Vec<f64> = (0..vector_size).map(|i| (i as f64) / (vector_size as f64)).collect();
-        let vectors: Vec<f64> = (0..num_vectors * vector_size)
-            .map(|i| (i as f64) / (vector_size as f64))
-            .collect();
+            self.custom.push(JsBenchmark::new(&env, name, callback, ops_per_iteration)?);
+            Ok(())
+        })
+    }
+
+    /// Register a JavaScript reference implementation of a native benchmark
+    ///
+    /// The callback should do the same work as the native benchmark of that
+    /// name (see `runAllBenchmarks` for the built-in ones). `runJsComparisons`
+    /// times it and sets the result's `speedup` from the two medians.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the native benchmark the callback reimplements
+    /// * `callback` - Synchronous JavaScript implementation of the same workload
+    #[napi]
+    pub fn register_js_reference(&mut self, env: Env, name: String, callback: JsFunction) -> napi::Result<()> {
+        errors::guard("BenchmarkSuite.registerJsReference", || {
+            self.references.retain(|reference| reference.name != name);
+            self.references.push(JsBenchmark::new(&env, name, callback, 1.0)?);
+            Ok(())
+        })
+    }
+
+    /// Names of the registered custom benchmarks
+    #[napi]
+    pub fn custom_benchmarks(&self) -> Vec<String> {
+        self.custom.iter().map(|benchmark| benchmark.name.clone()).collect()
+    }
+
+    /// Run the registered custom benchmarks off the JavaScript thread
+    ///
+    /// Resolves with their results, which are also appended to `getResults`.
+    #[napi(ts_return_type = "Promise<BenchmarkResult[]>")]
+    pub fn run_custom_benchmarks(&self) -> AsyncTask<CustomBenchmarkTask> {
+        AsyncTask::new(CustomBenchmarkTask {
+            config: self.config.clone(),
+            benchmarks: self.custom.clone(),
+            results: self.results.clone(),
+        })
+    }
+
+    /// Time the registered JavaScript references off the JavaScript thread
+    ///
+    /// Each native benchmark with a reference must already have a result.
+    /// Resolves with the measured speedups, which are also stored on the
+    /// results returned by `getResults`.
+    #[napi(ts_return_type = "Promise<SpeedupMeasurement[]>")]
+    pub fn run_js_comparisons(&self) -> AsyncTask<JsComparisonTask> {
+        AsyncTask::new(JsComparisonTask {
+            config: self.config.clone(),
+            references: self.references.clone(),
+            results: self.results.clone(),
+        })
+    }
+}
+
+/// Cosine similarity of one query against a corpus of vectors
+fn benchmark_vector_operations(config: &BenchmarkConfig, workload: &BenchmarkWorkload) -> napi::Result<BenchmarkResult> {
+    use crate::vector_ops::VectorOperations;
+
+    let vector_size = workload.vector_dimensions.unwrap_or(DEFAULT_VECTOR_DIMENSIONS) as usize;
+    let num_vectors = workload.vector_count.unwrap_or(DEFAULT_VECTOR_COUNT) as usize;
+
+    // Generate test data
+    let (query, vectors): (Vec<f64>, Vec<f64>) = match workload.synthetic_seed {
+        Some(seed) => {
+            let mut corpus = synthetic::generate_synthetic_embeddings(Some(SyntheticEmbeddingConfig {
+                seed,
+                count: num_vectors as u32 + 1,
+                dimensions: vector_size as u32,
+                ..Default::default()
+            }))?
+            .vectors;
+            let vectors = corpus.split_off(vector_size);
+            (corpus, vectors)
+        }
+        None => (
+            (0..vector_size).map(|i| (i as f64) / (vector_size as f64)).collect(),
+            (0..num_vectors * vector_size).map(|i| (i as f64) / (vector_size as f64)).collect(),
+        ),
+    };
+
+    let ops = VectorOperations::new(None)?;
+
+    let samples = sample(config, || {
+        timed(|| ops.batch_cosine_similarity(query.clone(), vectors.clone(), vector_size as u32))
+    })?;
+    Ok(summarize(
+        config,
+        &format!("Vector Cosine Similarity ({} vectors)", num_vectors),
+        samples,
+        Throughput::items(num_vectors as f64, "vectors").with_bytes(vectors.len() as f64 * 8.0),
+    ))
+}
 
-        let ops = VectorOperations::new(None)?;
-        
-        // Benchmark
-        let iterations = 100;
-        let start = Instant::now();
-        
-        for _ in 0..iterations {
-            let _ = ops.batch_cosine_similarity(query.clone(), vectors.clone(), vector_size as u32)?;
+/// Glob search under a directory tree
+fn benchmark_file_search(config: &BenchmarkConfig, workload: &BenchmarkWorkload) -> napi::Result<BenchmarkResult> {
+    use std::env;
+
+    let pattern = workload.search_pattern.as_deref().unwrap_or(DEFAULT_SEARCH_PATTERN);
+    match (&workload.search_root, workload.synthetic_seed) {
+        (Some(root), _) => search_files(config, root, pattern),
+        (None, Some(seed)) => {
+            let tree = env::temp_dir().join(format!("moidvk-bench-{}-{}", std::process::id(), seed));
+            let tree = tree.to_string_lossy().to_string();
+            let tree_config = SyntheticTreeConfig { seed, ..Default::default() };
+            let result = synthetic::generate_synthetic_tree(tree.clone(), Some(tree_config))
+                .and_then(|_| search_files(config, &tree, pattern));
+            let _ = fs::remove_dir_all(&tree);
+            result
         }
-        
-        let elapsed = start.elapsed();
-        let avg_time_ms = elapsed.as_secs_f64() * 1000.0 / iterations as f64;
-        let ops_per_sec = 1000.0 / avg_time_ms * num_vectors as f64;
-
-        self.results.push(BenchmarkResult {
-            name: "Vector Cosine Similarity (1000 vectors)".to_string(),
-            avg_time_ms,
-            ops_per_sec,
-            speedup: 20.0, // Estimated speedup over JS
-        });
-
-        Ok(())
-    }
-
-    fn benchmark_file_search(&mut self) -> napi::Result<()> {
-        use crate::file_search::FileSearch;
-        use std::env;
-
-        let searcher = FileSearch::new(None)?;
-        let current_dir = env::current_dir()?.to_string_lossy().to_string();
-        
-        // Benchmark file pattern search
-        let iterations = 10;
-        let start = Instant::now();
-        
-        for _ in 0..iterations {
-            let _ = searcher.find_files_by_pattern(current_dir.clone(), "*.rs".to_string())?;
+        (None, None) => search_files(config, &env::current_dir()?.to_string_lossy(), pattern),
+    }
+}
+
+/// Time glob searches under `root`
+fn search_files(config: &BenchmarkConfig, root: &str, pattern: &str) -> napi::Result<BenchmarkResult> {
+    use crate::file_search::FileSearch;
+
+    let searcher = FileSearch::new(None)?;
+
+    // Throughput counts the matched files; a glob search reads no file
+    // contents, so no byte rate is reported
+    let matched = searcher.find_files_by_pattern(root.to_string(), pattern.to_string(), None)?;
+
+    // Benchmark file pattern search
+    let samples = sample(config, || {
+        timed(|| searcher.find_files_by_pattern(root.to_string(), pattern.to_string(), None))
+    })?;
+    Ok(summarize(
+        config,
+        &format!("File Pattern Search ({})", pattern),
+        samples,
+        Throughput::items(matched.len() as f64, "files"),
+    ))
+}
+
+/// Multi-pattern substring search over repeated English text
+fn benchmark_text_processing(config: &BenchmarkConfig, workload: &BenchmarkWorkload) -> napi::Result<BenchmarkResult> {
+    use crate::text_processing::TextProcessor;
+
+    let processor = TextProcessor::new(None);
+
+    // Generate test data
+    let text_bytes = workload.text_bytes.unwrap_or(DEFAULT_TEXT_BYTES) as usize;
+    let text = SAMPLE_SENTENCE.repeat(text_bytes.div_ceil(SAMPLE_SENTENCE.len()));
+    let pattern_count = workload.pattern_count.unwrap_or(DEFAULT_PATTERN_COUNT) as usize;
+    // Past the sample words, add patterns that never match
+    let patterns: Vec<String> = (0..pattern_count)
+        .map(|i| match SAMPLE_PATTERNS.get(i) {
+            Some(word) => word.to_string(),
+            None => format!("absent{}", i),
+        })
+        .collect();
+
+    // Benchmark
+    let samples = sample(config, || timed(|| processor.find_substrings(text.clone(), patterns.clone())))?;
+    Ok(summarize(
+        config,
+        &format!("Multi-pattern Text Search ({} patterns)", pattern_count),
+        samples,
+        Throughput::items(text.lines().count() as f64, "lines").with_bytes(text.len() as f64),
+    ))
+}
+
+/// Background task running the built-in benchmarks
+pub struct NativeBenchmarkTask {
+    config: BenchmarkConfig,
+    workload: BenchmarkWorkload,
+    results: Arc<Mutex<Vec<BenchmarkResult>>>,
+    progress: Option<ThreadsafeFunction<BenchmarkProgress, ErrorStrategy::Fatal>>,
+}
+
+impl Task for NativeBenchmarkTask {
+    type Output = Vec<BenchmarkResult>;
+    type JsValue = Vec<BenchmarkResult>;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        errors::guard("BenchmarkSuite.runAllBenchmarks", || {
+            self.results.lock().clear();
+            run_builtin(&self.config, &self.workload, |completed, total, result| {
+                self.results.lock().push(result.clone());
+                if let Some(progress) = &self.progress {
+                    let event = BenchmarkProgress {
+                        name: result.name.clone(),
+                        completed: completed as u32,
+                        total: total as u32,
+                        result: result.clone(),
+                    };
+                    progress.call(event, ThreadsafeFunctionCallMode::NonBlocking);
+                }
+            })
+        })
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+/// Run the selected built-in benchmarks in order
+///
+/// `on_result` receives the number finished so far, the number selected and
+/// the latest result.
+fn run_builtin(
+    config: &BenchmarkConfig,
+    workload: &BenchmarkWorkload,
+    mut on_result: impl FnMut(usize, usize, &BenchmarkResult),
+) -> napi::Result<Vec<BenchmarkResult>> {
+    let selected: Vec<BuiltinBenchmark> = BUILTIN_BENCHMARKS
+        .iter()
+        .filter(|(name, _)| {
+            workload
+                .benchmarks
+                .as_ref()
+                .is_none_or(|names| names.iter().any(|selected| selected == name))
+        })
+        .map(|(_, benchmark)| *benchmark)
+        .collect();
+    let mut results = Vec::with_capacity(selected.len());
+    for benchmark in &selected {
+        let result = benchmark(config, workload)?;
+        on_result(results.len() + 1, selected.len(), &result);
+        results.push(result);
+    }
+    Ok(results)
+}
+
+/// Background task running the registered custom benchmarks
+pub struct CustomBenchmarkTask {
+    config: BenchmarkConfig,
+    benchmarks: Vec<JsBenchmark>,
+    results: Arc<Mutex<Vec<BenchmarkResult>>>,
+}
+
+impl Task for CustomBenchmarkTask {
+    type Output = Vec<BenchmarkResult>;
+    type JsValue = Vec<BenchmarkResult>;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        errors::guard("BenchmarkSuite.runCustomBenchmarks", || {
+            let mut results = Vec::new();
+            for benchmark in &self.benchmarks {
+                let samples = benchmark.sample(&self.config)?;
+                results.push(summarize(
+                    &self.config,
+                    &benchmark.name,
+                    samples,
+                    Throughput::items(benchmark.ops_per_iteration, "operations"),
+                ));
+            }
+            Ok(results)
+        })
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        self.results.lock().extend(output.iter().cloned());
+        Ok(output)
+    }
+}
+
+/// Background task timing the registered JavaScript references
+pub struct JsComparisonTask {
+    config: BenchmarkConfig,
+    references: Vec<JsBenchmark>,
+    results: Arc<Mutex<Vec<BenchmarkResult>>>,
+}
+
+impl Task for JsComparisonTask {
+    type Output = Vec<SpeedupMeasurement>;
+    type JsValue = Vec<SpeedupMeasurement>;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        errors::guard("BenchmarkSuite.runJsComparisons", || {
+            let mut measurements = Vec::new();
+            for reference in &self.references {
+                let native_median_ms = self
+                    .results
+                    .lock()
+                    .iter()
+                    .find(|result| result.name == reference.name)
+                    .map(|result| result.median_ms)
+                    .ok_or_else(|| {
+                        errors::new(
+                            ErrorCode::InvalidArgument,
+                            format!("No native result named {}; run the benchmarks first", reference.name),
+                        )
+                    })?;
+                let samples = reference.sample(&self.config)?;
+                let js = summarize(
+                    &self.config,
+                    &reference.name,
+                    samples,
+                    Throughput::items(reference.ops_per_iteration, "operations"),
+                );
+                measurements.push(SpeedupMeasurement {
+                    name: reference.name.clone(),
+                    native_median_ms,
+                    js_median_ms: js.median_ms,
+                    speedup: if native_median_ms > 0.0 { js.median_ms / native_median_ms } else { 0.0 },
+                    js,
+                });
+            }
+            Ok(measurements)
+        })
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        let mut results = self.results.lock();
+        for measurement in &output {
+            if let Some(result) = results.iter_mut().find(|result| result.name == measurement.name) {
+                result.speedup = Some(measurement.speedup);
+                result.js_median_ms = Some(measurement.js_median_ms);
+            }
         }
-        
-        let elapsed = start.elapsed();
-        let avg_time_ms = elapsed.as_secs_f64() * 1000.0 / iterations as f64;
-
-        self.results.push(BenchmarkResult {
-            name: "File Pattern Search (*.rs)".to_string(),
-            avg_time_ms,
-            ops_per_sec: 1000.0 / avg_time_ms,
-            speedup: 10.0, // Estimated speedup over JS
-        });
-
-        Ok(())
-    }
-
-    fn benchmark_text_processing(&mut self) -> napi::Result<()> {
-        use crate::text_processing::TextProcessor;
-
-        let processor = TextProcessor::new(None);
-        
-        // Generate test data
-        let text = "The quick brown fox jumps over the lazy dog. ".repeat(1000);
-        let patterns = vec![
-            "quick".to_string(),
-            "brown".to_string(),
-            "fox".to_string(),
-            "lazy".to_string(),
-            "dog".to_string(),
-        ];
+        Ok(output)
+    }
+}
+
+impl JsBenchmark {
+    fn new(env: &Env, name: String, callback: JsFunction, ops_per_iteration: f64) -> napi::Result<Self> {
+        // The wrapper runs on the JavaScript thread, so the timer covers the
+        // callback alone and not the hop between threads. It never throws:
+        // an exception in the callback is reported back as the outcome.
+        let (sender, receiver) = mpsc::channel();
+        let wrapper = env.create_function_from_closure("timedBenchmark", move |ctx| {
+            let body = ctx.get::<JsFunction>(0)?;
+            let start = Instant::now();
+            let outcome = body
+                .call_without_args(None)
+                .map(|_| start.elapsed())
+                .map_err(|e| e.reason);
+            let _ = sender.send(outcome);
+            ctx.env.get_undefined()
+        })?;
+
+        // Bind the callback as the wrapper's first argument
+        let wrapper_object = wrapper.coerce_to_object()?;
+        let bind: JsFunction = wrapper_object.get_named_property("bind")?;
+        let bound = bind.call(
+            Some(&wrapper_object),
+            &[env.get_undefined()?.into_unknown(), callback.into_unknown()],
+        )?;
+        let bound = JsFunction::try_from(bound)?;
+
+        let mut invoke = bound.create_threadsafe_function(0, |_| Ok(Vec::<JsUnknown>::new()))?;
+        // Registered benchmarks must not keep the process alive
+        invoke.unref(env)?;
+
+        Ok(Self {
+            name,
+            ops_per_iteration,
+            invoke,
+            outcomes: Arc::new(Mutex::new(receiver)),
+        })
+    }
+
+    /// Sample the callback from the current (non-JavaScript) thread
+    fn sample(&self, config: &BenchmarkConfig) -> napi::Result<Samples> {
+        // One run at a time per benchmark, so outcomes can't interleave
+        let outcomes = self.outcomes.lock();
+        sample(config, || {
+            let status = self.invoke.call((), ThreadsafeFunctionCallMode::Blocking);
+            if status != napi::Status::Ok {
+                return Err(errors::new(
+                    ErrorCode::CallbackFailed,
+                    format!("Failed to call benchmark {}: {}", self.name, status),
+                ));
+            }
+            outcomes
+                .recv()
+                .map_err(|_| {
+                    errors::new(
+                        ErrorCode::CallbackFailed,
+                        format!("Benchmark {} was released before it returned", self.name),
+                    )
+                })?
+                .map_err(|reason| {
+                    errors::new(
+                        ErrorCode::CallbackFailed,
+                        format!("Benchmark {} threw: {}", self.name, reason),
+                    )
+                })
+        })
+    }
+}
 
-        // Benchmark
-        let iterations = 100;
-        let start = Instant::now();
-        
-        for _ in 0..iterations {
-            let _ = processor.find_substrings(text.clone(), patterns.clone())?;
+/// Time one call of `body`
+fn timed<T>(body: impl FnOnce() -> napi::Result<T>) -> napi::Result<Duration> {
+    let start = Instant::now();
+    black_box(body()?);
+    Ok(start.elapsed())
+}
+
+/// Warm up, then collect one sample per iteration; `body` returns the time of one iteration
+fn sample(config: &BenchmarkConfig, mut body: impl FnMut() -> napi::Result<Duration>) -> napi::Result<Samples> {
+    let warmup_time = Duration::from_millis(config.warmup_time_ms as u64);
+    let warmup_start = Instant::now();
+    let mut warmed = 0;
+    while warmed < config.warmup_iterations || warmup_start.elapsed() < warmup_time {
+        body()?;
+        warmed += 1;
+    }
+
+    let measurement_time = Duration::from_millis(config.measurement_time_ms as u64);
+    let mut times_ms = Vec::with_capacity(config.max_iterations as usize);
+    let before = memory::counters();
+    memory::reset_window_peak();
+    let measurement_start = Instant::now();
+    while times_ms.len() < config.min_iterations as usize
+        || (times_ms.len() < config.max_iterations as usize && measurement_start.elapsed() < measurement_time)
+    {
+        times_ms.push(body()?.as_secs_f64() * 1000.0);
+    }
+    let after = memory::counters();
+
+    Ok(Samples {
+        allocations: after.allocations - before.allocations,
+        allocated_bytes: after.total_allocated - before.total_allocated,
+        peak_growth_bytes: memory::window_peak().saturating_sub(before.allocated),
+        times_ms,
+    })
+}
+
+/// Per-iteration times and the native allocations made while taking them
+struct Samples {
+    times_ms: Vec<f64>,
+    allocations: u64,
+    allocated_bytes: u64,
+    peak_growth_bytes: u64,
+}
+
+/// Work done by one iteration
+struct Throughput {
+    items: f64,
+    unit: &'static str,
+    bytes: Option<f64>,
+}
+
+impl Throughput {
+    fn items(items: f64, unit: &'static str) -> Self {
+        Self { items, unit, bytes: None }
+    }
+
+    fn with_bytes(self, bytes: f64) -> Self {
+        Self { bytes: Some(bytes), ..self }
+    }
+}
+
+/// Summarize samples into a result, with rates derived from the mean time
+fn summarize(
+    config: &BenchmarkConfig,
+    name: &str,
+    samples: Samples,
+    throughput: Throughput,
+) -> BenchmarkResult {
+    let stats = SampleStatistics::new(&samples.times_ms, config.confidence_level);
+    let iterations = samples.times_ms.len() as f64;
+    let per_sec = |amount: f64| if stats.mean > 0.0 { amount * 1000.0 / stats.mean } else { 0.0 };
+    BenchmarkResult {
+        name: name.to_string(),
+        avg_time_ms: stats.mean,
+        ops_per_sec: per_sec(throughput.items),
+        item_unit: throughput.unit.to_string(),
+        items_per_iteration: throughput.items,
+        bytes_per_iteration: throughput.bytes,
+        megabytes_per_sec: throughput.bytes.map(|bytes| per_sec(bytes) / 1_000_000.0),
+        speedup: None,
+        js_median_ms: None,
+        iterations: samples.times_ms.len() as u32,
+        median_ms: stats.median,
+        stddev_ms: stats.stddev,
+        min_ms: stats.min,
+        max_ms: stats.max,
+        p95_ms: stats.p95,
+        p99_ms: stats.p99,
+        ci_lower_ms: stats.ci_lower,
+        ci_upper_ms: stats.ci_upper,
+        confidence_level: config.confidence_level,
+        samples_ms: samples.times_ms,
+        allocations_per_iteration: samples.allocations as f64 / iterations,
+        allocated_bytes_per_iteration: samples.allocated_bytes as f64 / iterations,
+        peak_memory_growth_bytes: samples.peak_growth_bytes as f64,
+        peak_rss_bytes: memory::get_memory_stats().peak_rss_bytes,
+    }
+}
+
+/// Summary statistics over per-iteration samples
+struct SampleStatistics {
+    mean: f64,
+    median: f64,
+    stddev: f64,
+    min: f64,
+    max: f64,
+    p95: f64,
+    p99: f64,
+    ci_lower: f64,
+    ci_upper: f64,
+}
+
+impl SampleStatistics {
+    fn new(samples: &[f64], confidence_level: f64) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / n;
+        let variance = if sorted.len() > 1 {
+            sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        // Percentile bootstrap of the mean with a fixed seed, so reruns on
+        // the same samples report the same interval
+        let mut rng = SeededRng::new(0x9E37_79B9_7F4A_7C15);
+        let mut means: Vec<f64> = (0..BOOTSTRAP_RESAMPLES)
+            .map(|_| (0..sorted.len()).map(|_| sorted[rng.below(sorted.len())]).sum::<f64>() / n)
+            .collect();
+        means.sort_by(f64::total_cmp);
+        let tail = (1.0 - confidence_level) / 2.0;
+
+        Self {
+            mean,
+            median: percentile(&sorted, 0.5),
+            stddev: variance.sqrt(),
+            min: sorted.first().copied().unwrap_or(0.0),
+            max: sorted.last().copied().unwrap_or(0.0),
+            p95: percentile(&sorted, 0.95),
+            p99: percentile(&sorted, 0.99),
+            ci_lower: percentile(&means, tail),
+            ci_upper: percentile(&means, 1.0 - tail),
         }
-        
-        let elapsed = start.elapsed();
-        let avg_time_ms = elapsed.as_secs_f64() * 1000.0 / iterations as f64;
+    }
+}
+
+/// Linearly interpolated percentile of sorted values, `q` in [0, 1]
+fn percentile(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = q * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// Describe the machine benchmarks run on
+#[napi]
+pub fn get_benchmark_environment() -> BenchmarkEnvironment {
+    BenchmarkEnvironment {
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        cpu_model: cpu_model().unwrap_or_else(|| "unknown".to_string()),
+        cpu_cores: std::thread::available_parallelism().map(|n| n.get() as u32).unwrap_or(1),
+        rayon_threads: rayon::current_num_threads() as u32,
+        simd_features: cpu::simd_features(),
+        crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        optimization_level: if cfg!(debug_assertions) { "debug" } else { "release" }.to_string(),
+    }
+}
+
+/// JSON Schema of the document produced by `exportResults("json")`
+#[napi]
+pub fn get_benchmark_results_schema() -> String {
+    EXPORT_JSON_SCHEMA.to_string()
+}
 
-        self.results.push(BenchmarkResult {
-            name: "Multi-pattern Text Search (5 patterns)".to_string(),
-            avg_time_ms,
-            ops_per_sec: 1000.0 / avg_time_ms,
-            speedup: 15.0, // Estimated speedup over JS
-        });
+/// Write the export as CSV, one row per benchmark
+fn export_csv(export: &ResultsExport) -> Result<String, Box<dyn std::error::Error>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record([
+        "schema_version", "generated_at", "os", "arch", "cpu_model", "cpu_cores", "simd_features",
+        "crate_version", "optimization_level", "name", "iterations", "avg_time_ms", "median_ms",
+        "stddev_ms", "min_ms", "max_ms", "p95_ms", "p99_ms", "ci_lower_ms", "ci_upper_ms",
+        "confidence_level", "ops_per_sec", "item_unit", "items_per_iteration", "bytes_per_iteration",
+        "megabytes_per_sec", "speedup", "js_median_ms", "allocations_per_iteration",
+        "allocated_bytes_per_iteration", "peak_memory_growth_bytes", "peak_rss_bytes",
+    ])?;
+    let environment = &export.environment;
+    let optional = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+    for result in &export.results {
+        writer.write_record([
+            export.version.to_string(),
+            export.generated_at.clone(),
+            environment.os.clone(),
+            environment.arch.clone(),
+            environment.cpu_model.clone(),
+            environment.cpu_cores.to_string(),
+            environment.simd_features.join(" "),
+            environment.crate_version.clone(),
+            environment.optimization_level.clone(),
+            result.name.clone(),
+            result.iterations.to_string(),
+            result.avg_time_ms.to_string(),
+            result.median_ms.to_string(),
+            result.stddev_ms.to_string(),
+            result.min_ms.to_string(),
+            result.max_ms.to_string(),
+            result.p95_ms.to_string(),
+            result.p99_ms.to_string(),
+            result.ci_lower_ms.to_string(),
+            result.ci_upper_ms.to_string(),
+            result.confidence_level.to_string(),
+            result.ops_per_sec.to_string(),
+            result.item_unit.clone(),
+            result.items_per_iteration.to_string(),
+            optional(result.bytes_per_iteration),
+            optional(result.megabytes_per_sec),
+            optional(result.speedup),
+            optional(result.js_median_ms),
+            result.allocations_per_iteration.to_string(),
+            result.allocated_bytes_per_iteration.to_string(),
+            result.peak_memory_growth_bytes.to_string(),
+            result.peak_rss_bytes.to_string(),
+        ])?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
 
-        Ok(())
+/// CPU brand string from CPUID, or from /proc/cpuinfo on other architectures
+fn cpu_model() -> Option<String> {
+    #[cfg(target_arch = "x86_64")]
+    {
+        use std::arch::x86_64::__cpuid;
+        let max_extended = __cpuid(0x8000_0000).eax;
+        if max_extended >= 0x8000_0004 {
+            let mut brand = Vec::with_capacity(48);
+            for leaf in 0x8000_0002..=0x8000_0004 {
+                let regs = __cpuid(leaf);
+                for reg in [regs.eax, regs.ebx, regs.ecx, regs.edx] {
+                    brand.extend_from_slice(&reg.to_le_bytes());
+                }
+            }
+            let brand = String::from_utf8_lossy(&brand)
+                .trim_matches(|c: char| c == '\0' || c.is_whitespace())
+                .to_string();
+            if !brand.is_empty() {
+                return Some(brand);
+            }
+        }
     }
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+    cpuinfo
+        .lines()
+        .find(|line| line.starts_with("model name") || line.starts_with("Model") || line.starts_with("Hardware"))
+        .and_then(|line| line.split_once(':'))
+        .map(|(_, model)| model.trim().to_string())
 }
 
 /// Quick benchmark function
 #[napi]
 pub fn quick_benchmark() -> napi::Result<HashMap<String, f64>> {
-    let mut suite = BenchmarkSuite::new();
-    let results = suite.run_all_benchmarks()?;
-    
-    let mut summary = HashMap::new();
-    for result in results {
-        summary.insert(result.name, result.avg_time_ms);
-    }
+    errors::guard("quickBenchmark", || {
+        let results = run_builtin(&BenchmarkConfig::default(), &BenchmarkWorkload::default(), |_, _, _| {})?;
+
+        let mut summary = HashMap::new();
+        for result in results {
+            summary.insert(result.name, result.avg_time_ms);
+        }
     
-    Ok(summary)
+        Ok(summary)
+    })
 }
\ No newline at end of file
@@ -0,0 +1,144 @@
+//! Compact result formats for large scans
+//!
+//! A million-entry result repeats the same long directory prefixes in every
+//! path, and each copy becomes a separate string on the JavaScript heap. The
+//! compact variants of `findFilesByPattern` and `searchTextInFiles` store
+//! each directory or file path once, relative to the search root, and refer
+//! to it by index. `path.join(root, directories[file.directory], file.name)`
+//! rebuilds a full path.
+
+use crate::file_search::{FileInfo, SearchSnippet, TextSearchResult};
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Files from `findFilesCompact`, with directory paths stored once
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactFileList {
+    /// Root the directories are relative to, as given
+    pub root: String,
+    /// Directories holding matches, relative to `root` ("" for the root itself)
+    pub directories: Vec<String>,
+    /// Matching files, in the order `findFilesByPattern` returns them
+    pub files: Vec<CompactFileInfo>,
+}
+
+/// A file of a `CompactFileList`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactFileInfo {
+    /// Index of the file's directory in `directories`
+    pub directory: u32,
+    /// File name
+    pub name: String,
+    /// File size in bytes
+    pub size: i32,
+    /// Last modified timestamp (milliseconds since Unix epoch)
+    pub last_modified: f64,
+    /// Is directory
+    pub is_directory: bool,
+    /// File extension (if any)
+    pub extension: Option<String>,
+}
+
+/// Matches from `searchTextCompact`, with file paths stored once
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactTextResults {
+    /// Root the file paths are relative to, as given
+    pub root: String,
+    /// Files with matches, relative to `root`
+    pub files: Vec<String>,
+    /// Matches, in the order `searchTextInFiles` returns them
+    pub matches: Vec<CompactTextMatch>,
+}
+
+/// A match of `CompactTextResults`; the search text is not repeated
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactTextMatch {
+    /// Index of the file in `files`
+    pub file: u32,
+    /// Line number (1-based)
+    pub line_number: u32,
+    /// Column start position
+    pub column_start: u32,
+    /// Column end position
+    pub column_end: u32,
+    /// The matching line content
+    pub line_content: String,
+    /// The line cut down around the match (only with `snippet_width`)
+    pub snippet: Option<SearchSnippet>,
+}
+
+/// Strings stored once, in first-seen order
+#[derive(Default)]
+struct Table {
+    index: HashMap<String, u32>,
+    entries: Vec<String>,
+}
+
+impl Table {
+    fn intern(&mut self, value: String) -> u32 {
+        if let Some(&index) = self.index.get(&value) {
+            return index;
+        }
+        let index = self.entries.len() as u32;
+        self.index.insert(value.clone(), index);
+        self.entries.push(value);
+        index
+    }
+}
+
+/// `path` relative to `root`, or unchanged if outside it
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root).unwrap_or(path).to_string_lossy().into_owned()
+}
+
+/// Share the directories of `files` found under `root`
+pub(crate) fn files(root: String, files: Vec<FileInfo>) -> CompactFileList {
+    let mut directories = Table::default();
+    let files = files
+        .into_iter()
+        .map(|info| {
+            let path = Path::new(&info.path);
+            let directory = relative(Path::new(&root), path.parent().unwrap_or(Path::new("")));
+            CompactFileInfo {
+                directory: directories.intern(directory),
+                name: info.name,
+                size: info.size,
+                last_modified: info.last_modified,
+                is_directory: info.is_directory,
+                extension: info.extension,
+            }
+        })
+        .collect();
+    CompactFileList {
+        root,
+        directories: directories.entries,
+        files,
+    }
+}
+
+/// Share the file paths of `results` found under `root`
+pub(crate) fn matches(root: String, results: Vec<TextSearchResult>) -> CompactTextResults {
+    let mut files = Table::default();
+    let matches = results
+        .into_iter()
+        .map(|result| CompactTextMatch {
+            file: files.intern(relative(Path::new(&root), Path::new(&result.path))),
+            line_number: result.line_number,
+            column_start: result.column_start,
+            column_end: result.column_end,
+            line_content: result.line_content,
+            snippet: result.snippet,
+        })
+        .collect();
+    CompactTextResults {
+        root,
+        files: files.entries,
+        matches,
+    }
+}
@@ -0,0 +1,635 @@
+//! gzip and zstd compression for buffers, files and streams
+//!
+//! Indexes and other files moidvk writes can be stored compressed, and
+//! fetched artifacts decompressed without a JavaScript inflater. zstd is the
+//! default: it compresses about as well as gzip at several times the speed.
+//! Decompression detects the format from the data's magic bytes unless one
+//! is given, accepts concatenated gzip members and zstd frames, and can be
+//! capped with `maxOutputBytes` against compression bombs. Files are read
+//! and written in chunks, and outputs are replaced atomically.
+
+use crate::atomic_write::{AtomicFile, AtomicWriteOptions};
+use crate::audit::{self, AuditKind};
+use crate::concurrency;
+use crate::errors::{self, ErrorCode};
+use crate::metrics;
+#[cfg(feature = "security")]
+use crate::security_utils::quota::{self, Mutation};
+use flate2::write::{GzEncoder, MultiGzDecoder};
+use napi::bindgen_prelude::{Buffer, Either};
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use zstd::stream::raw::Decoder as ZstdOperation;
+use zstd::stream::write::Encoder as ZstdEncoder;
+use zstd::stream::zio::Writer as ZstdWriter;
+
+/// First bytes of a gzip member
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// First bytes of a zstd frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Default zstd level (zstd's own default)
+const ZSTD_DEFAULT_LEVEL: i32 = 3;
+
+/// Default gzip level (zlib's own default)
+const GZIP_DEFAULT_LEVEL: i32 = 6;
+
+/// Options for compressing
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompressionOptions {
+    /// "zstd" (default) or "gzip"
+    pub format: Option<String>,
+    /// Compression level: zstd 1-22 (default 3; negative levels trade ratio
+    /// for speed), gzip 0-9 (default 6)
+    pub level: Option<i32>,
+}
+
+/// Options for decompressing
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DecompressionOptions {
+    /// "zstd", "gzip" or "auto" (default; detected from the magic bytes)
+    pub format: Option<String>,
+    /// Fail if the output would be larger than this many bytes
+    pub max_output_bytes: Option<f64>,
+}
+
+/// Result of compressing or decompressing a file
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompressionResult {
+    /// Format of the compressed side: "zstd" or "gzip"
+    pub format: String,
+    /// Bytes read from the source
+    pub bytes_read: f64,
+    /// Bytes written to the destination
+    pub bytes_written: f64,
+}
+
+/// Supported compression formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Format {
+    Gzip,
+    Zstd,
+}
+
+impl Format {
+    /// Parse a format name for compression, defaulting to zstd
+    pub(crate) fn parse(format: Option<&str>) -> napi::Result<Self> {
+        Self::parse_or_auto(format)?.map_or(Ok(Self::Zstd), Ok)
+    }
+
+    /// Parse a format name for decompression; `None` means detect it
+    fn parse_or_auto(format: Option<&str>) -> napi::Result<Option<Self>> {
+        match format.map(str::to_ascii_lowercase).as_deref() {
+            None | Some("auto") => Ok(None),
+            Some("zstd") | Some("zst") => Ok(Some(Self::Zstd)),
+            Some("gzip") | Some("gz") => Ok(Some(Self::Gzip)),
+            Some(other) => Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Unknown compression format: {} (expected zstd, gzip or auto)", other),
+            )),
+        }
+    }
+
+    /// Format of data starting with `bytes`, if it is compressed
+    pub(crate) fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(&ZSTD_MAGIC) {
+            Some(Self::Zstd)
+        } else if bytes.starts_with(&GZIP_MAGIC) {
+            Some(Self::Gzip)
+        } else {
+            None
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+        }
+    }
+
+    /// Validated compression level, or this format's default
+    pub(crate) fn level(self, level: Option<i32>) -> napi::Result<i32> {
+        let (default, range) = match self {
+            Self::Gzip => (GZIP_DEFAULT_LEVEL, 0..=9),
+            Self::Zstd => (ZSTD_DEFAULT_LEVEL, zstd::compression_level_range()),
+        };
+        match level {
+            None => Ok(default),
+            Some(level) if range.contains(&level) => Ok(level),
+            Some(level) => Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!(
+                    "Invalid {} level: {} (expected {} to {})",
+                    self.as_str(),
+                    level,
+                    range.start(),
+                    range.end()
+                ),
+            )),
+        }
+    }
+}
+
+/// A compressing writer of either format
+pub(crate) enum Encoder<W: Write> {
+    Gzip(GzEncoder<W>),
+    Zstd(ZstdEncoder<'static, W>),
+}
+
+impl<W: Write> Encoder<W> {
+    pub(crate) fn new(format: Format, level: i32, inner: W) -> io::Result<Self> {
+        Ok(match format {
+            Format::Gzip => Self::Gzip(GzEncoder::new(inner, flate2::Compression::new(level as u32))),
+            Format::Zstd => Self::Zstd(ZstdEncoder::new(inner, level)?),
+        })
+    }
+
+    fn get_mut(&mut self) -> &mut W {
+        match self {
+            Self::Gzip(encoder) => encoder.get_mut(),
+            Self::Zstd(encoder) => encoder.get_mut(),
+        }
+    }
+
+    /// Write the end of the stream and return the inner writer
+    pub(crate) fn finish(self) -> io::Result<W> {
+        match self {
+            Self::Gzip(encoder) => encoder.finish(),
+            Self::Zstd(encoder) => encoder.finish(),
+        }
+    }
+}
+
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Gzip(encoder) => encoder.write(buf),
+            Self::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Gzip(encoder) => encoder.flush(),
+            Self::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// A decompressing writer of either format
+enum Decoder<W: Write> {
+    Gzip(MultiGzDecoder<W>),
+    Zstd(ZstdWriter<W, ZstdOperation<'static>>),
+}
+
+impl<W: Write> Decoder<W> {
+    fn new(format: Format, inner: W) -> io::Result<Self> {
+        Ok(match format {
+            Format::Gzip => Self::Gzip(MultiGzDecoder::new(inner)),
+            Format::Zstd => Self::Zstd(ZstdWriter::new(inner, ZstdOperation::new()?)),
+        })
+    }
+
+    fn get_mut(&mut self) -> &mut W {
+        match self {
+            Self::Gzip(decoder) => decoder.get_mut(),
+            Self::Zstd(writer) => writer.writer_mut(),
+        }
+    }
+
+    /// Check the stream ended on a complete member or frame and flush its output
+    fn finish(&mut self) -> io::Result<()> {
+        match self {
+            Self::Gzip(decoder) => decoder.try_finish(),
+            Self::Zstd(writer) => writer.finish(),
+        }
+    }
+}
+
+impl<W: Write> Write for Decoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Gzip(decoder) => decoder.write(buf),
+            Self::Zstd(writer) => writer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Gzip(decoder) => decoder.flush(),
+            Self::Zstd(writer) => writer.flush(),
+        }
+    }
+}
+
+/// A writer that fails once more than `limit` bytes pass through it
+struct Limited<W> {
+    inner: W,
+    written: u64,
+    limit: Option<u64>,
+}
+
+impl<W: Write> Limited<W> {
+    fn new(inner: W, limit: Option<u64>) -> Self {
+        Self { inner, written: 0, limit }
+    }
+}
+
+impl<W: Write> Write for Limited<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Some(limit) = self.limit {
+            if self.written + buf.len() as u64 > limit {
+                return Err(io::Error::new(
+                    io::ErrorKind::FileTooLarge,
+                    format!("output exceeds maxOutputBytes ({})", limit),
+                ));
+            }
+        }
+        let written = self.inner.write(buf)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Napi error for a failed decompression: corrupt data, the output limit or I/O
+fn decode_error(error: &io::Error, context: &str) -> napi::Error {
+    let code = if error.kind() == io::ErrorKind::FileTooLarge {
+        ErrorCode::QuotaExceeded
+    } else if error.raw_os_error().is_none() && error.kind() != io::ErrorKind::NotFound {
+        ErrorCode::ParseError
+    } else {
+        ErrorCode::from_io(error)
+    };
+    errors::new(code, format!("{}: {}", context, error))
+}
+
+/// Borrow the bytes of a string-or-Buffer argument
+fn input_bytes(input: &Either<String, Buffer>) -> &[u8] {
+    match input {
+        Either::A(text) => text.as_bytes(),
+        Either::B(buffer) => buffer,
+    }
+}
+
+/// Format given in `options`, or the one `bytes` start with
+fn resolve_format(format: Option<&str>, bytes: &[u8]) -> napi::Result<Format> {
+    match Format::parse_or_auto(format)? {
+        Some(format) => Ok(format),
+        None => Format::detect(bytes).ok_or_else(|| {
+            errors::new(
+                ErrorCode::ParseError,
+                "Unrecognized compression format (expected zstd or gzip data)",
+            )
+        }),
+    }
+}
+
+/// `maxOutputBytes` as a byte count
+fn output_limit(max_output_bytes: Option<f64>) -> napi::Result<Option<u64>> {
+    match max_output_bytes {
+        Some(max) if max.is_nan() || max < 0.0 => Err(errors::new(
+            ErrorCode::InvalidArgument,
+            format!("maxOutputBytes must be non-negative, got {}", max),
+        )),
+        max => Ok(max.map(|max| max as u64)),
+    }
+}
+
+/// Compress `bytes` in one call
+pub(crate) fn compress_bytes(format: Format, level: i32, bytes: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = Encoder::new(format, level, Vec::new())?;
+    encoder.write_all(bytes)?;
+    encoder.finish()
+}
+
+/// Decompress `bytes` in one call
+fn decompress_bytes(format: Format, bytes: &[u8], limit: Option<u64>) -> io::Result<Vec<u8>> {
+    let mut decoder = Decoder::new(format, Limited::new(Vec::new(), limit))?;
+    decoder.write_all(bytes)?;
+    decoder.finish()?;
+    Ok(std::mem::take(&mut decoder.get_mut().inner))
+}
+
+/// Decompress `bytes` if they start with a zstd or gzip header, else return them as they are
+pub(crate) fn decompress_if_compressed(bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+    match Format::detect(&bytes) {
+        // Any failure in memory is the data's fault
+        Some(format) => decompress_bytes(format, &bytes, None).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        None => Ok(bytes),
+    }
+}
+
+/// Compress a buffer
+///
+/// # Arguments
+/// * `data` - Bytes to compress (strings are encoded as UTF-8)
+/// * `options` - Optional format and level
+#[napi]
+pub fn compress(data: Either<String, Buffer>, options: Option<CompressionOptions>) -> napi::Result<Buffer> {
+    errors::guard("compress", || {
+        let options = options.unwrap_or_default();
+        let format = Format::parse(options.format.as_deref())?;
+        let level = format.level(options.level)?;
+        let bytes = input_bytes(&data);
+        metrics::add_bytes("compression", bytes.len() as u64);
+        let compressed = compress_bytes(format, level, bytes).map_err(|e| {
+            errors::io(&e, format!("Failed to compress: {}", e))
+        })?;
+        Ok(compressed.into())
+    })
+}
+
+/// Decompress a buffer
+///
+/// # Arguments
+/// * `data` - Compressed bytes
+/// * `options` - Optional format (detected by default) and output limit
+#[napi]
+pub fn decompress(data: Buffer, options: Option<DecompressionOptions>) -> napi::Result<Buffer> {
+    errors::guard("decompress", || {
+        let options = options.unwrap_or_default();
+        let format = resolve_format(options.format.as_deref(), &data)?;
+        let limit = output_limit(options.max_output_bytes)?;
+        metrics::add_bytes("compression", data.len() as u64);
+        let decompressed = decompress_bytes(format, &data, limit)
+            .map_err(|e| decode_error(&e, &format!("Failed to decompress {} data", format.as_str())))?;
+        Ok(decompressed.into())
+    })
+}
+
+/// Compress a file into another with streaming reads and writes
+///
+/// # Arguments
+/// * `source` - File to compress
+/// * `destination` - Compressed file to create or replace
+/// * `options` - Optional format and level
+#[napi]
+pub fn compress_file(
+    source: String,
+    destination: String,
+    options: Option<CompressionOptions>,
+) -> napi::Result<CompressionResult> {
+    errors::guard("compressFile", || {
+        let options = options.unwrap_or_default();
+        let format = Format::parse(options.format.as_deref())?;
+        let level = format.level(options.level)?;
+        let mut input = concurrency::open_file(Path::new(&source))
+            .map_err(|e| errors::io(&e, format!("Failed to open {}: {}", source, e)))?;
+        let describe = |e: &io::Error| errors::io(e, format!("Failed to compress {} to {}: {}", source, destination, e));
+        let result = write_atomically("compress_file", &destination, describe, |output| {
+            let mut encoder = Encoder::new(format, level, output)?;
+            let bytes_read = io::copy(&mut *input, &mut encoder)?;
+            encoder.finish()?;
+            Ok(bytes_read)
+        });
+        finish_file("compress_file", &destination, format, result)
+    })
+}
+
+/// Decompress a file into another with streaming reads and writes
+///
+/// # Arguments
+/// * `source` - Compressed file
+/// * `destination` - File to create or replace
+/// * `options` - Optional format (detected by default) and output limit
+#[napi]
+pub fn decompress_file(
+    source: String,
+    destination: String,
+    options: Option<DecompressionOptions>,
+) -> napi::Result<CompressionResult> {
+    errors::guard("decompressFile", || {
+        let options = options.unwrap_or_default();
+        let limit = output_limit(options.max_output_bytes)?;
+        let mut file = concurrency::open_file(Path::new(&source))
+            .map_err(|e| errors::io(&e, format!("Failed to open {}: {}", source, e)))?;
+        let mut input = BufReader::new(&mut *file);
+        let head = input
+            .fill_buf()
+            .map_err(|e| errors::io(&e, format!("Failed to read {}: {}", source, e)))?;
+        let format = resolve_format(options.format.as_deref(), head)?;
+        let describe = |e: &io::Error| decode_error(e, &format!("Failed to decompress {} to {}", source, destination));
+        let result = write_atomically("decompress_file", &destination, describe, |output| {
+            let mut decoder = Decoder::new(format, Limited::new(output, limit))?;
+            let bytes_read = io::copy(&mut input, &mut decoder)?;
+            decoder.finish()?;
+            Ok(bytes_read)
+        });
+        finish_file("decompress_file", &destination, format, result)
+    })
+}
+
+/// Write `destination` through `body`, which returns the bytes it read,
+/// and commit it once the quota allows the bytes written
+///
+/// `describe` turns a failure of `body` into the error to report.
+fn write_atomically(
+    operation: &str,
+    destination: &str,
+    describe: impl Fn(&io::Error) -> napi::Error,
+    body: impl FnOnce(&mut Limited<&mut AtomicFile>) -> io::Result<u64>,
+) -> napi::Result<(u64, u64)> {
+    let path = Path::new(destination);
+    let write_error = |e: io::Error| errors::io(&e, format!("Failed to write {}: {}", destination, e));
+    let mut file = AtomicFile::create(path, &AtomicWriteOptions::default()).map_err(write_error)?;
+    let mut output = Limited::new(&mut file, None);
+    let bytes_read = body(&mut output).map_err(|e| describe(&e))?;
+    let bytes_written = output.written;
+    #[cfg(feature = "security")]
+    quota::charge(operation, Mutation::Write, path, bytes_written)?;
+    #[cfg(not(feature = "security"))]
+    let _ = operation;
+    file.commit().map_err(write_error)?;
+    Ok((bytes_read, bytes_written))
+}
+
+/// Audit a file operation and build its result
+fn finish_file(
+    operation: &str,
+    destination: &str,
+    format: Format,
+    result: napi::Result<(u64, u64)>,
+) -> napi::Result<CompressionResult> {
+    match &result {
+        Ok(_) => audit::record(AuditKind::FileMutation, operation, destination, "success", None),
+        Err(e) => audit::record(AuditKind::FileMutation, operation, destination, "failure", Some(&e.reason)),
+    }
+    let (bytes_read, bytes_written) = result?;
+    metrics::add_bytes("compression", bytes_read);
+    Ok(CompressionResult {
+        format: format.as_str().to_string(),
+        bytes_read: bytes_read as f64,
+        bytes_written: bytes_written as f64,
+    })
+}
+
+/// Incremental compressor for data that arrives in chunks
+///
+/// Each call returns the compressed bytes produced so far, which may be
+/// empty; concatenated, they form one complete stream after `finish`.
+#[napi]
+pub struct Compressor {
+    encoder: Option<Encoder<Vec<u8>>>,
+}
+
+#[napi]
+impl Compressor {
+    /// Create a compressor
+    ///
+    /// # Arguments
+    /// * `options` - Optional format and level
+    #[napi(constructor)]
+    pub fn new(options: Option<CompressionOptions>) -> napi::Result<Self> {
+        errors::guard("new Compressor", || {
+            let options = options.unwrap_or_default();
+            let format = Format::parse(options.format.as_deref())?;
+            let encoder = Encoder::new(format, format.level(options.level)?, Vec::new()).map_err(|e| {
+                errors::io(&e, format!("Failed to create {} encoder: {}", format.as_str(), e))
+            })?;
+            Ok(Self { encoder: Some(encoder) })
+        })
+    }
+
+    /// Compress a chunk
+    #[napi]
+    pub fn write(&mut self, chunk: Either<String, Buffer>) -> napi::Result<Buffer> {
+        errors::guard("Compressor.write", || {
+            let encoder = self.active()?;
+            let bytes = input_bytes(&chunk);
+            metrics::add_bytes("compression", bytes.len() as u64);
+            encoder
+                .write_all(bytes)
+                .map_err(|e| errors::io(&e, format!("Failed to compress: {}", e)))?;
+            Ok(std::mem::take(encoder.get_mut()).into())
+        })
+    }
+
+    /// Flush buffered input, so everything written so far can be decompressed
+    #[napi]
+    pub fn flush(&mut self) -> napi::Result<Buffer> {
+        errors::guard("Compressor.flush", || {
+            let encoder = self.active()?;
+            encoder
+                .flush()
+                .map_err(|e| errors::io(&e, format!("Failed to compress: {}", e)))?;
+            Ok(std::mem::take(encoder.get_mut()).into())
+        })
+    }
+
+    /// End the stream and return its last bytes
+    #[napi]
+    pub fn finish(&mut self) -> napi::Result<Buffer> {
+        errors::guard("Compressor.finish", || {
+            let encoder = self.encoder.take().ok_or_else(|| already_finished("Compressor"))?;
+            let rest = encoder
+                .finish()
+                .map_err(|e| errors::io(&e, format!("Failed to compress: {}", e)))?;
+            Ok(rest.into())
+        })
+    }
+
+    fn active(&mut self) -> napi::Result<&mut Encoder<Vec<u8>>> {
+        self.encoder.as_mut().ok_or_else(|| already_finished("Compressor"))
+    }
+}
+
+/// Error for a call after `finish`
+fn already_finished(class: &str) -> napi::Error {
+    errors::new(ErrorCode::InvalidArgument, format!("{} is already finished", class))
+}
+
+/// Incremental decompressor for data that arrives in chunks
+///
+/// Each call returns the decompressed bytes produced so far, which may be
+/// empty. `finish` fails if the stream was cut short.
+#[napi]
+pub struct Decompressor {
+    format: Option<Format>,
+    /// Input held until the format can be detected
+    head: Vec<u8>,
+    decoder: Option<Decoder<Limited<Vec<u8>>>>,
+    limit: Option<u64>,
+    finished: bool,
+}
+
+#[napi]
+impl Decompressor {
+    /// Create a decompressor
+    ///
+    /// # Arguments
+    /// * `options` - Optional format (detected from the first bytes by
+    ///   default) and a limit on the total output
+    #[napi(constructor)]
+    pub fn new(options: Option<DecompressionOptions>) -> napi::Result<Self> {
+        errors::guard("new Decompressor", || {
+            let options = options.unwrap_or_default();
+            Ok(Self {
+                format: Format::parse_or_auto(options.format.as_deref())?,
+                head: Vec::new(),
+                decoder: None,
+                limit: output_limit(options.max_output_bytes)?,
+                finished: false,
+            })
+        })
+    }
+
+    /// Decompress a chunk
+    #[napi]
+    pub fn write(&mut self, chunk: Buffer) -> napi::Result<Buffer> {
+        errors::guard("Decompressor.write", || {
+            if self.finished {
+                return Err(already_finished("Decompressor"));
+            }
+            metrics::add_bytes("compression", chunk.len() as u64);
+            self.head.extend_from_slice(&chunk);
+            if self.decoder.is_none() && self.format.is_none() && self.head.len() < ZSTD_MAGIC.len() {
+                return Ok(Vec::new().into());
+            }
+            Ok(self.decode()?.into())
+        })
+    }
+
+    /// End the stream and return the last decompressed bytes
+    #[napi]
+    pub fn finish(&mut self) -> napi::Result<Buffer> {
+        errors::guard("Decompressor.finish", || {
+            if self.finished {
+                return Err(already_finished("Decompressor"));
+            }
+            self.finished = true;
+            let mut output = self.decode()?;
+            let decoder = self.decoder.as_mut().expect("created by decode");
+            decoder.finish().map_err(|e| decode_error(&e, "Failed to decompress"))?;
+            output.append(&mut decoder.get_mut().inner);
+            Ok(output.into())
+        })
+    }
+
+    /// Feed the held input to the decoder, creating it first if needed
+    fn decode(&mut self) -> napi::Result<Vec<u8>> {
+        if self.decoder.is_none() {
+            let format = resolve_format(self.format.map(Format::as_str), &self.head)?;
+            let decoder = Decoder::new(format, Limited::new(Vec::new(), self.limit)).map_err(|e| {
+                errors::io(&e, format!("Failed to create {} decoder: {}", format.as_str(), e))
+            })?;
+            self.decoder = Some(decoder);
+        }
+        let decoder = self.decoder.as_mut().expect("created above");
+        decoder
+            .write_all(&self.head)
+            .map_err(|e| decode_error(&e, "Failed to decompress"))?;
+        self.head.clear();
+        Ok(std::mem::take(&mut decoder.get_mut().inner))
+    }
+}
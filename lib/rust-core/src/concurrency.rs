@@ -0,0 +1,399 @@
+//! Process-wide limit on concurrent heavy operations
+//!
+//! Directory walks, content scans and duplicate detection each fan out over
+//! the whole rayon pool and read large parts of the disk. When parallel MCP
+//! requests (or several worker threads) start them at the same time, they
+//! compete for the same cores and disk and all finish later than if they had
+//! run in turn. Such operations take a permit with [`acquire`] first; callers
+//! beyond the limit queue until a permit is released, or fail with `TIMEOUT`
+//! when a queue timeout is configured.
+//!
+//! Permits are reentrant per thread, so a heavy operation that calls another
+//! (e.g. `quickFindFiles` running `FileSearch.findFilesByPattern`) never waits
+//! on itself.
+//!
+//! Parallel reads (duplicate hashing, content scans, line counting) also
+//! draw from a budget of open file descriptors, so a wide rayon pool over a
+//! large tree can't exhaust the process's `ulimit -n`. Files are opened with
+//! [`open_file`]; when the budget is spent, readers wait for another file to
+//! be closed. An `EMFILE` from the OS (descriptors held elsewhere in the
+//! process) is treated the same way: the open is retried after a close
+//! instead of failing the scan. Both are counted under `limits_reached` in
+//! `getMetrics()`.
+
+use crate::errors::{self, ErrorCode};
+use crate::metrics;
+use napi_derive::napi;
+use parking_lot::{Condvar, Mutex};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::fs::File;
+use std::io::{self, Read};
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Heavy operations allowed to run at once unless configured otherwise
+pub(crate) const DEFAULT_MAX_HEAVY_OPERATIONS: u32 = 2;
+
+/// File descriptors parallel reads may hold when the soft `ulimit -n` can't be read
+const FALLBACK_MAX_OPEN_FILES: u32 = 256;
+
+/// Bounds on the budget derived from the soft `ulimit -n` (half of it)
+const MIN_DERIVED_OPEN_FILES: u32 = 16;
+const MAX_DERIVED_OPEN_FILES: u32 = 4096;
+
+/// Wait for a close before retrying an open that failed with `EMFILE`, and attempts made
+const EMFILE_RETRY_WAIT: Duration = Duration::from_millis(10);
+const EMFILE_RETRIES: u32 = 100;
+
+static STATE: Mutex<LimiterState> = Mutex::new(LimiterState::new());
+static RELEASED: Condvar = Condvar::new();
+
+static FILES: Mutex<FdState> = Mutex::new(FdState::new());
+static FILE_CLOSED: Condvar = Condvar::new();
+
+thread_local! {
+    /// Permits held by the current thread
+    static HELD: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Limits on concurrent heavy operations
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConcurrencyLimits {
+    /// Heavy operations allowed to run at once (0 for unlimited)
+    pub max_heavy_operations: u32,
+    /// Milliseconds a queued operation waits before failing with TIMEOUT
+    /// (0 to wait indefinitely)
+    pub queue_timeout_ms: u32,
+    /// Files parallel reads may hold open at once (0 for unlimited; omit for
+    /// half the soft `ulimit -n`, between 16 and 4096)
+    pub max_open_files: Option<u32>,
+}
+
+impl Default for ConcurrencyLimits {
+    fn default() -> Self {
+        Self {
+            max_heavy_operations: DEFAULT_MAX_HEAVY_OPERATIONS,
+            queue_timeout_ms: 0,
+            max_open_files: None,
+        }
+    }
+}
+
+/// Current state of the limiter
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConcurrencyStats {
+    /// Limits in effect
+    pub limits: ConcurrencyLimits,
+    /// Heavy operations currently running
+    pub running: u32,
+    /// Heavy operations waiting for a permit
+    pub queued: u32,
+    /// Longest queue seen since the last reset
+    pub peak_queued: u32,
+    /// Permits granted since the last reset
+    pub granted: f64,
+    /// Total milliseconds spent waiting for permits since the last reset
+    pub total_wait_ms: f64,
+    /// Operations that gave up waiting since the last reset
+    pub timeouts: u32,
+    /// Files currently open for parallel reads
+    pub open_files: u32,
+    /// Most files open at once since the last reset
+    pub peak_open_files: u32,
+    /// Opens that waited for the file budget since the last reset
+    pub file_budget_waits: u32,
+    /// Opens retried after `EMFILE` since the last reset
+    pub emfile_retries: u32,
+}
+
+struct LimiterState {
+    max_heavy_operations: u32,
+    queue_timeout_ms: u32,
+    running: u32,
+    queued: u32,
+    peak_queued: u32,
+    granted: u64,
+    total_wait: Duration,
+    timeouts: u32,
+}
+
+impl LimiterState {
+    const fn new() -> Self {
+        Self {
+            max_heavy_operations: DEFAULT_MAX_HEAVY_OPERATIONS,
+            queue_timeout_ms: 0,
+            running: 0,
+            queued: 0,
+            peak_queued: 0,
+            granted: 0,
+            total_wait: Duration::ZERO,
+            timeouts: 0,
+        }
+    }
+
+    fn has_capacity(&self) -> bool {
+        self.max_heavy_operations == 0 || self.running < self.max_heavy_operations
+    }
+}
+
+struct FdState {
+    /// Configured budget; `None` until set or derived on first use
+    max_open_files: Option<u32>,
+    open: u32,
+    peak_open: u32,
+    waits: u32,
+    emfile_retries: u32,
+}
+
+impl FdState {
+    const fn new() -> Self {
+        Self {
+            max_open_files: None,
+            open: 0,
+            peak_open: 0,
+            waits: 0,
+            emfile_retries: 0,
+        }
+    }
+
+    fn limit(&mut self) -> u32 {
+        *self.max_open_files.get_or_insert_with(default_max_open_files)
+    }
+
+    fn has_capacity(&mut self) -> bool {
+        let limit = self.limit();
+        limit == 0 || self.open < limit
+    }
+}
+
+/// A running heavy operation; the permit is released on drop
+pub(crate) struct Permit {
+    /// False for a nested permit on a thread that already holds one
+    counted: bool,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        HELD.with(|held| held.set(held.get() - 1));
+        if self.counted {
+            STATE.lock().running -= 1;
+            RELEASED.notify_one();
+        }
+    }
+}
+
+/// Wait for a heavy-operation permit
+///
+/// `operation` names the export in the timeout error.
+pub(crate) fn acquire(operation: &str) -> napi::Result<Permit> {
+    if HELD.with(|held| held.get()) > 0 {
+        HELD.with(|held| held.set(held.get() + 1));
+        return Ok(Permit { counted: false });
+    }
+
+    let start = Instant::now();
+    let mut state = STATE.lock();
+    if !state.has_capacity() {
+        state.queued += 1;
+        state.peak_queued = state.peak_queued.max(state.queued);
+        let timeout = Duration::from_millis(state.queue_timeout_ms as u64);
+        while !state.has_capacity() {
+            if timeout.is_zero() {
+                RELEASED.wait(&mut state);
+            } else if RELEASED.wait_until(&mut state, start + timeout).timed_out() && !state.has_capacity() {
+                state.queued -= 1;
+                state.timeouts += 1;
+                return Err(errors::new(
+                    ErrorCode::Timeout,
+                    format!(
+                        "{} waited {} ms for one of {} heavy-operation slots",
+                        operation, state.queue_timeout_ms, state.max_heavy_operations
+                    ),
+                ));
+            }
+        }
+        state.queued -= 1;
+    }
+    state.running += 1;
+    state.granted += 1;
+    state.total_wait += start.elapsed();
+    drop(state);
+
+    HELD.with(|held| held.set(1));
+    Ok(Permit { counted: true })
+}
+
+/// A file opened within the descriptor budget; its slot is freed on drop
+pub(crate) struct BudgetedFile {
+    file: File,
+    _slot: FdSlot,
+}
+
+impl Deref for BudgetedFile {
+    type Target = File;
+
+    fn deref(&self) -> &File {
+        &self.file
+    }
+}
+
+impl DerefMut for BudgetedFile {
+    fn deref_mut(&mut self) -> &mut File {
+        &mut self.file
+    }
+}
+
+struct FdSlot;
+
+impl Drop for FdSlot {
+    fn drop(&mut self) {
+        FILES.lock().open -= 1;
+        FILE_CLOSED.notify_one();
+    }
+}
+
+/// Open a file for reading within the descriptor budget
+///
+/// Waits while the budget is spent, and retries after a close when the OS
+/// reports too many open files.
+pub(crate) fn open_file(path: &Path) -> io::Result<BudgetedFile> {
+    let mut retries = 0;
+    loop {
+        let slot = take_fd_slot();
+        match File::open(path) {
+            Ok(file) => return Ok(BudgetedFile { file, _slot: slot }),
+            Err(e) if is_too_many_open_files(&e) && retries < EMFILE_RETRIES => {
+                drop(slot);
+                retries += 1;
+                metrics::record_limit("file_descriptors");
+                let mut files = FILES.lock();
+                files.emfile_retries += 1;
+                FILE_CLOSED.wait_for(&mut files, EMFILE_RETRY_WAIT);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Read a whole file within the descriptor budget
+pub(crate) fn read_file(path: &Path) -> io::Result<Vec<u8>> {
+    let mut file = open_file(path)?;
+    let mut bytes = Vec::with_capacity(file.metadata().map(|m| m.len() as usize).unwrap_or(0));
+    file.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn take_fd_slot() -> FdSlot {
+    let mut files = FILES.lock();
+    if !files.has_capacity() {
+        files.waits += 1;
+        metrics::record_limit("file_descriptors");
+        while !files.has_capacity() {
+            FILE_CLOSED.wait(&mut files);
+        }
+    }
+    files.open += 1;
+    files.peak_open = files.peak_open.max(files.open);
+    FdSlot
+}
+
+#[cfg(unix)]
+fn is_too_many_open_files(e: &io::Error) -> bool {
+    // EMFILE (per process) and ENFILE (system wide)
+    matches!(e.raw_os_error(), Some(24) | Some(23))
+}
+
+#[cfg(windows)]
+fn is_too_many_open_files(e: &io::Error) -> bool {
+    // ERROR_TOO_MANY_OPEN_FILES
+    e.raw_os_error() == Some(4)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn is_too_many_open_files(_e: &io::Error) -> bool {
+    false
+}
+
+/// Half the soft `ulimit -n`, leaving the rest to Node and other modules
+fn default_max_open_files() -> u32 {
+    soft_open_file_limit()
+        .map(|limit| (limit / 2).clamp(MIN_DERIVED_OPEN_FILES as u64, MAX_DERIVED_OPEN_FILES as u64) as u32)
+        .unwrap_or(FALLBACK_MAX_OPEN_FILES)
+}
+
+#[cfg(target_os = "linux")]
+fn soft_open_file_limit() -> Option<u64> {
+    let limits = std::fs::read_to_string("/proc/self/limits").ok()?;
+    let line = limits.lines().find(|line| line.starts_with("Max open files"))?;
+    line.trim_start_matches("Max open files").split_whitespace().next()?.parse().ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn soft_open_file_limit() -> Option<u64> {
+    None
+}
+
+/// Set the limits on concurrent heavy operations
+///
+/// Operations already running keep their permits; raising the limit starts
+/// queued operations immediately.
+///
+/// # Arguments
+/// * `limits` - New limits, e.g. `config.concurrency` from `loadConfig`
+#[napi]
+pub fn set_concurrency_limits(limits: ConcurrencyLimits) {
+    let mut state = STATE.lock();
+    state.max_heavy_operations = limits.max_heavy_operations;
+    state.queue_timeout_ms = limits.queue_timeout_ms;
+    drop(state);
+    RELEASED.notify_all();
+
+    let mut files = FILES.lock();
+    files.max_open_files = limits.max_open_files;
+    drop(files);
+    FILE_CLOSED.notify_all();
+}
+
+/// Get the limits, running and queued operations, open files and wait totals
+#[napi]
+pub fn get_concurrency_stats() -> ConcurrencyStats {
+    let state = STATE.lock();
+    let mut files = FILES.lock();
+    ConcurrencyStats {
+        limits: ConcurrencyLimits {
+            max_heavy_operations: state.max_heavy_operations,
+            queue_timeout_ms: state.queue_timeout_ms,
+            max_open_files: Some(files.limit()),
+        },
+        running: state.running,
+        queued: state.queued,
+        peak_queued: state.peak_queued,
+        granted: state.granted as f64,
+        total_wait_ms: state.total_wait.as_secs_f64() * 1000.0,
+        timeouts: state.timeouts,
+        open_files: files.open,
+        peak_open_files: files.peak_open,
+        file_budget_waits: files.waits,
+        emfile_retries: files.emfile_retries,
+    }
+}
+
+/// Clear the peak queue length, peak open files and wait totals
+#[napi]
+pub fn reset_concurrency_stats() {
+    let mut state = STATE.lock();
+    state.peak_queued = state.queued;
+    state.granted = 0;
+    state.total_wait = Duration::ZERO;
+    state.timeouts = 0;
+    drop(state);
+
+    let mut files = FILES.lock();
+    files.peak_open = files.open;
+    files.waits = 0;
+    files.emfile_retries = 0;
+}
@@ -0,0 +1,365 @@
+//! Project configuration from `moidvk.toml`
+//!
+//! A project can keep its native-layer settings in a `moidvk.toml` at its
+//! root. The loader validates the document, resolves relative paths against
+//! the project root and fills in defaults, so every module instance built
+//! from the result (FileSearch, scanners, sandboxed operations) agrees on the
+//! same excludes and sandbox.
+//!
+//! ```toml
+//! [search]
+//! extend_exclude = ["dist", "*.min.js"]
+//! max_depth = 20
+//!
+//! [sandbox]
+//! allowed_roots = ["."]
+//! denied_globs = ["**/.ssh/**"]
+//!
+//! [threads]
+//! count = 4
+//!
+//! [concurrency]
+//! max_heavy_operations = 2
+//!
+//! [index]
+//! directory = ".moidvk/index"
+//! ```
+
+use crate::concurrency::ConcurrencyLimits;
+use crate::errors::{self, ErrorCode};
+use crate::file_search::FileSearchConfig;
+use crate::result_cache::Validation;
+use crate::security_utils::sandbox::SandboxPolicy;
+use globset::Glob;
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name looked up in the project root
+const CONFIG_FILE_NAME: &str = "moidvk.toml";
+
+/// Upper bound for `threads.count`
+const MAX_THREADS: u32 = 1024;
+
+/// Resolved project configuration
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoidvkConfig {
+    /// Path of the loaded `moidvk.toml`, or null when only defaults apply
+    pub source: Option<String>,
+    /// Absolute project root that relative paths were resolved against
+    pub root: String,
+    /// Search settings for FileSearch and every module's `search` option;
+    /// includes the sandbox when one is configured
+    pub search: FileSearchConfig,
+    /// Sandbox policy with absolute roots, if configured
+    pub sandbox: Option<SandboxPolicy>,
+    /// Worker threads for parallel operations (0 for one per CPU)
+    pub threads: u32,
+    /// Limits on concurrent heavy operations, for `setConcurrencyLimits`
+    pub concurrency: ConcurrencyLimits,
+    /// Where persistent indexes are stored
+    pub index: IndexLocations,
+}
+
+/// Absolute locations of persistent indexes
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexLocations {
+    /// Directory for search indexes
+    pub directory: String,
+    /// Directory for cached embeddings
+    pub embeddings: String,
+}
+
+/// Top-level `moidvk.toml` document
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(default)]
+    search: SearchSection,
+    sandbox: Option<SandboxSection>,
+    #[serde(default)]
+    threads: ThreadsSection,
+    #[serde(default)]
+    concurrency: ConcurrencySection,
+    #[serde(default)]
+    index: IndexSection,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SearchSection {
+    /// Replaces the default excludes
+    exclude: Option<Vec<String>>,
+    /// Added to `exclude` (or the defaults)
+    #[serde(default)]
+    extend_exclude: Vec<String>,
+    max_depth: Option<i32>,
+    include_hidden: Option<bool>,
+    follow_symlinks: Option<bool>,
+    parallel: Option<bool>,
+    max_file_size: Option<u64>,
+    result_cache: Option<String>,
+    deterministic: Option<bool>,
+    /// Languages files must be detected as
+    languages: Option<Vec<String>>,
+    /// "normal" or "background"
+    priority: Option<String>,
+    adapt_to_network_fs: Option<bool>,
+    normalize_unicode: Option<bool>,
+    report_skipped: Option<bool>,
+    max_symlink_depth: Option<u32>,
+    max_entries: Option<u32>,
+    snippet_width: Option<u32>,
+    max_line_length: Option<u32>,
+    /// "search", "skip" or "last"
+    minified_files: Option<String>,
+    /// "identifier", "string", "comment" or "code"
+    token_class: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SandboxSection {
+    #[serde(default)]
+    allowed_roots: Vec<String>,
+    #[serde(default)]
+    denied_globs: Vec<String>,
+    #[serde(default)]
+    max_file_size: u64,
+    #[serde(default)]
+    max_results: u32,
+    #[serde(default)]
+    follow_symlinks: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThreadsSection {
+    count: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConcurrencySection {
+    max_heavy_operations: Option<u32>,
+    queue_timeout_ms: Option<u32>,
+    max_open_files: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IndexSection {
+    directory: Option<String>,
+    embeddings: Option<String>,
+}
+
+/// Load `moidvk.toml` from a project root
+///
+/// Missing files are not an error: the defaults are returned with a null
+/// `source`.
+///
+/// # Arguments
+/// * `project_root` - Directory containing `moidvk.toml`
+#[napi]
+pub fn load_config(project_root: String) -> napi::Result<MoidvkConfig> {
+    errors::guard("loadConfig", || {
+        let root = resolve_root(&project_root)?;
+        let path = root.join(CONFIG_FILE_NAME);
+        if !path.is_file() {
+            return resolve(ConfigFile::default(), &root, None);
+        }
+
+        let source = fs::read_to_string(&path).map_err(|e| {
+            errors::io(&e, format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        let file = parse(&source, &path.to_string_lossy())?;
+        resolve(file, &root, Some(&path))
+    })
+}
+
+/// Validate and resolve a `moidvk.toml` document without reading it from disk
+///
+/// # Arguments
+/// * `source` - TOML document
+/// * `project_root` - Directory relative paths are resolved against
+#[napi]
+pub fn parse_config(source: String, project_root: String) -> napi::Result<MoidvkConfig> {
+    errors::guard("parseConfig", || {
+        let root = resolve_root(&project_root)?;
+        let file = parse(&source, CONFIG_FILE_NAME)?;
+        resolve(file, &root, None)
+    })
+}
+
+/// Size the global thread pool used by parallel operations
+///
+/// Must run before the first parallel operation; the pool cannot be resized
+/// once it has started.
+///
+/// # Arguments
+/// * `threads` - Worker count (0 for one per CPU), e.g. `config.threads`
+///
+/// Returns the number of worker threads
+#[napi]
+pub fn configure_thread_pool(threads: u32) -> napi::Result<u32> {
+    errors::guard("configureThreadPool", || {
+        if threads > MAX_THREADS {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("threads must be at most {}", MAX_THREADS),
+            ));
+        }
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads as usize)
+            .build_global()
+            .map_err(|e| {
+                errors::new(
+                    ErrorCode::AlreadyExists,
+                    format!("Thread pool is already running with {} threads: {}", rayon::current_num_threads(), e),
+                )
+            })?;
+        Ok(rayon::current_num_threads() as u32)
+    })
+}
+
+fn resolve_root(project_root: &str) -> napi::Result<PathBuf> {
+    Path::new(project_root).canonicalize().map_err(|e| {
+        errors::io(&e, format!("Cannot resolve project root '{}': {}", project_root, e))
+    })
+}
+
+fn parse(source: &str, name: &str) -> napi::Result<ConfigFile> {
+    toml::from_str(source)
+        .map_err(|e| errors::new(ErrorCode::ParseError, format!("Invalid {}: {}", name, e)))
+}
+
+/// Validate a parsed document and turn it into the resolved config
+fn resolve(file: ConfigFile, root: &Path, source: Option<&Path>) -> napi::Result<MoidvkConfig> {
+    let name = source.map(|p| p.display().to_string()).unwrap_or_else(|| CONFIG_FILE_NAME.to_string());
+    let invalid = |code: ErrorCode, message: String| errors::new(code, format!("{}: {}", name, message));
+
+    let defaults = FileSearchConfig::default();
+    let search = file.search;
+    let mut exclude_patterns = search.exclude.unwrap_or(defaults.exclude_patterns);
+    exclude_patterns.extend(search.extend_exclude);
+    check_globs("search.exclude", &exclude_patterns).map_err(|m| invalid(ErrorCode::PatternInvalid, m))?;
+
+    let max_depth = search.max_depth.unwrap_or(defaults.max_depth);
+    if max_depth < -1 {
+        return Err(invalid(
+            ErrorCode::InvalidArgument,
+            "search.max_depth must be -1 (unlimited) or greater".to_string(),
+        ));
+    }
+    let max_file_size = match search.max_file_size {
+        None => defaults.max_file_size,
+        Some(size) => i32::try_from(size).map_err(|_| {
+            invalid(
+                ErrorCode::InvalidArgument,
+                format!("search.max_file_size must be at most {} bytes", i32::MAX),
+            )
+        })?,
+    };
+
+    let result_cache = search.result_cache;
+    Validation::parse(result_cache.as_deref())
+        .map_err(|m| invalid(ErrorCode::InvalidArgument, format!("search.result_cache: {}", m)))?;
+
+    let sandbox = match file.sandbox {
+        Some(section) => {
+            check_globs("sandbox.denied_globs", &section.denied_globs)
+                .map_err(|m| invalid(ErrorCode::PatternInvalid, m))?;
+            let allowed_roots = section
+                .allowed_roots
+                .iter()
+                .enumerate()
+                .map(|(i, dir)| {
+                    let path = root.join(dir);
+                    if !path.is_dir() {
+                        return Err(invalid(
+                            ErrorCode::PathNotFound,
+                            format!("sandbox.allowed_roots[{}]: not a directory: {}", i, path.display()),
+                        ));
+                    }
+                    Ok(path.to_string_lossy().to_string())
+                })
+                .collect::<napi::Result<Vec<_>>>()?;
+            Some(SandboxPolicy {
+                allowed_roots,
+                denied_globs: section.denied_globs,
+                max_file_size: section.max_file_size as f64,
+                max_results: section.max_results,
+                follow_symlinks: section.follow_symlinks,
+            })
+        }
+        None => None,
+    };
+
+    let threads = file.threads.count.unwrap_or(0);
+    if threads > MAX_THREADS {
+        return Err(invalid(
+            ErrorCode::InvalidArgument,
+            format!("threads.count must be at most {}", MAX_THREADS),
+        ));
+    }
+
+    let concurrency_defaults = ConcurrencyLimits::default();
+    let concurrency = ConcurrencyLimits {
+        max_heavy_operations: file
+            .concurrency
+            .max_heavy_operations
+            .unwrap_or(concurrency_defaults.max_heavy_operations),
+        queue_timeout_ms: file.concurrency.queue_timeout_ms.unwrap_or(concurrency_defaults.queue_timeout_ms),
+        max_open_files: file.concurrency.max_open_files.or(concurrency_defaults.max_open_files),
+    };
+
+    let locate = |dir: Option<String>, default: &str| {
+        root.join(dir.as_deref().unwrap_or(default)).to_string_lossy().to_string()
+    };
+    let index = IndexLocations {
+        directory: locate(file.index.directory, ".moidvk/index"),
+        embeddings: locate(file.index.embeddings, ".moidvk/embeddings"),
+    };
+
+    Ok(MoidvkConfig {
+        source: source.map(|p| p.to_string_lossy().to_string()),
+        root: root.to_string_lossy().to_string(),
+        search: FileSearchConfig {
+            max_depth,
+            follow_symlinks: search.follow_symlinks.unwrap_or(defaults.follow_symlinks),
+            include_hidden: search.include_hidden.unwrap_or(defaults.include_hidden),
+            use_parallel: search.parallel.unwrap_or(defaults.use_parallel),
+            exclude_patterns,
+            max_file_size,
+            sandbox: sandbox.clone(),
+            result_cache,
+            deterministic: search.deterministic,
+            languages: search.languages,
+            priority: search.priority,
+            adapt_to_network_fs: search.adapt_to_network_fs,
+            normalize_unicode: search.normalize_unicode,
+            report_skipped: search.report_skipped,
+            max_symlink_depth: search.max_symlink_depth,
+            max_entries: search.max_entries,
+            snippet_width: search.snippet_width,
+            max_line_length: search.max_line_length,
+            minified_files: search.minified_files,
+            token_class: search.token_class,
+        },
+        sandbox,
+        threads,
+        concurrency,
+        index,
+    })
+}
+
+/// Check that every pattern compiles, naming the offending entry
+fn check_globs(key: &str, patterns: &[String]) -> Result<(), String> {
+    for (i, pattern) in patterns.iter().enumerate() {
+        Glob::new(pattern).map_err(|e| format!("{}[{}]: {}", key, i, e))?;
+    }
+    Ok(())
+}
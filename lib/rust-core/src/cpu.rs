@@ -0,0 +1,106 @@
+//! CPU capabilities detected at runtime
+//!
+//! Instruction set extensions and cache sizes, reported by
+//! `get_performance_info` and recorded in benchmark environments. Kernels
+//! that dispatch on an extension check it here, so what is reported is what
+//! actually runs.
+
+use serde::Serialize;
+#[cfg(target_os = "linux")]
+use std::fs;
+
+/// Extensions reported on every architecture, in order
+const ISA_EXTENSIONS: [&str; 9] = ["sse2", "sse4.1", "sse4.2", "avx", "avx2", "fma", "avx512f", "neon", "sve"];
+
+/// Whether each known extension is available on this CPU
+pub(crate) fn isa_extensions() -> Vec<(&'static str, bool)> {
+    #[allow(unused_mut)]
+    let mut detected: Vec<(&str, bool)> = Vec::new();
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    detected.extend([
+        ("sse2", is_x86_feature_detected!("sse2")),
+        ("sse4.1", is_x86_feature_detected!("sse4.1")),
+        ("sse4.2", is_x86_feature_detected!("sse4.2")),
+        ("avx", is_x86_feature_detected!("avx")),
+        ("avx2", is_x86_feature_detected!("avx2")),
+        ("fma", is_x86_feature_detected!("fma")),
+        ("avx512f", is_x86_feature_detected!("avx512f")),
+    ]);
+    #[cfg(target_arch = "aarch64")]
+    detected.extend([
+        ("neon", std::arch::is_aarch64_feature_detected!("neon")),
+        ("sve", std::arch::is_aarch64_feature_detected!("sve")),
+    ]);
+
+    ISA_EXTENSIONS
+        .iter()
+        .map(|&name| (name, detected.iter().any(|&(n, on)| n == name && on)))
+        .collect()
+}
+
+/// Names of the SIMD extensions available on this CPU
+pub(crate) fn simd_features() -> Vec<String> {
+    isa_extensions()
+        .into_iter()
+        .filter(|&(_, on)| on)
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+/// Whether the AVX2 vector kernels can run (they also use FMA)
+pub(crate) fn has_avx2_fma() -> bool {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma")
+    }
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    {
+        false
+    }
+}
+
+/// Cache sizes of the first CPU in bytes; `None` where unknown
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct CacheSizes {
+    pub(crate) l1d_bytes: Option<u64>,
+    pub(crate) l2_bytes: Option<u64>,
+    pub(crate) l3_bytes: Option<u64>,
+    pub(crate) line_bytes: Option<u64>,
+}
+
+/// Read cache sizes from sysfs (Linux only)
+pub(crate) fn cache_sizes() -> CacheSizes {
+    #[allow(unused_mut)]
+    let mut sizes = CacheSizes::default();
+    #[cfg(target_os = "linux")]
+    for index in 0.. {
+        let dir = format!("/sys/devices/system/cpu/cpu0/cache/index{}", index);
+        let read = |name: &str| fs::read_to_string(format!("{}/{}", dir, name)).map(|s| s.trim().to_string());
+        let (Ok(level), Ok(kind), Ok(size)) = (read("level"), read("type"), read("size")) else {
+            break;
+        };
+        let size = parse_cache_size(&size);
+        match (level.as_str(), kind.as_str()) {
+            ("1", "Data") => sizes.l1d_bytes = size,
+            ("2", _) => sizes.l2_bytes = size,
+            ("3", _) => sizes.l3_bytes = size,
+            _ => {}
+        }
+        if sizes.line_bytes.is_none() {
+            sizes.line_bytes = read("coherency_line_size").ok().and_then(|s| s.parse().ok());
+        }
+    }
+    sizes
+}
+
+/// Parse a sysfs cache size such as `32K` or `8M`
+#[cfg(target_os = "linux")]
+fn parse_cache_size(size: &str) -> Option<u64> {
+    let (digits, multiplier) = match size.chars().last()? {
+        'K' => (&size[..size.len() - 1], 1024),
+        'M' => (&size[..size.len() - 1], 1024 * 1024),
+        'G' => (&size[..size.len() - 1], 1024 * 1024 * 1024),
+        _ => (size, 1),
+    };
+    digits.parse::<u64>().ok().map(|n| n * multiplier)
+}
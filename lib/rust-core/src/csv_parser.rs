@@ -0,0 +1,358 @@
+//! High-performance CSV/TSV parsing
+//!
+//! This module parses delimited data with header awareness, configurable
+//! delimiter and quoting, and column projection. Large inputs are split at
+//! record boundaries and parsed in parallel.
+
+use napi_derive::napi;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use crate::errors::{self, ErrorCode};
+use crate::metrics;
+
+/// Inputs larger than this are split into chunks and parsed in parallel
+const PARALLEL_THRESHOLD: usize = 8 * 1024 * 1024;
+
+/// Configuration for CSV parsing
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvConfig {
+    /// Field delimiter (single ASCII character, e.g. "," or "\t")
+    pub delimiter: String,
+    /// Quote character (single ASCII character)
+    pub quote: String,
+    /// Treat the first record as a header row
+    pub has_headers: bool,
+    /// Allow records with differing field counts; otherwise every record
+    /// must have as many fields as the header (or the first record)
+    pub flexible: bool,
+    /// Trim whitespace around fields
+    pub trim: bool,
+    /// Maximum number of data rows to return (0 for unlimited)
+    pub max_rows: u32,
+    /// Use parallel processing for large inputs
+    pub use_parallel: bool,
+}
+
+impl Default for CsvConfig {
+    fn default() -> Self {
+        Self {
+            delimiter: ",".to_string(),
+            quote: "\"".to_string(),
+            has_headers: true,
+            flexible: false,
+            trim: false,
+            max_rows: 0,
+            use_parallel: true,
+        }
+    }
+}
+
+/// Parsed CSV data
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvParseResult {
+    /// Header names of the returned columns (empty without headers)
+    pub headers: Vec<String>,
+    /// Data rows, projected to the requested columns
+    pub rows: Vec<Vec<String>>,
+    /// Number of rows returned
+    pub row_count: u32,
+    /// Whether rows were truncated by `max_rows`
+    pub truncated: bool,
+}
+
+/// CSV parser implementation
+#[napi]
+pub struct CsvParser {
+    config: CsvConfig,
+    delimiter: u8,
+    quote: u8,
+}
+
+#[napi]
+impl CsvParser {
+    /// Create a new CSV parser with optional configuration
+    ///
+    /// # Arguments
+    /// * `config` - Optional configuration for CSV parsing
+    #[napi(constructor)]
+    pub fn new(config: Option<CsvConfig>) -> napi::Result<Self> {
+        errors::guard("new CsvParser", || {
+            let config = config.unwrap_or_default();
+            let delimiter = single_byte(&config.delimiter, "delimiter")?;
+            let quote = single_byte(&config.quote, "quote")?;
+
+            Ok(Self {
+                config,
+                delimiter,
+                quote,
+            })
+        })
+    }
+
+    /// Parse CSV text
+    ///
+    /// # Arguments
+    /// * `text` - CSV content
+    /// * `columns` - Optional column names (or zero-based indices without headers) to project
+    #[napi]
+    pub fn parse_text(&self, text: String, columns: Option<Vec<String>>) -> napi::Result<CsvParseResult> {
+        errors::guard("CsvParser.parseText", || {
+            self.parse_bytes(text.as_bytes(), columns)
+        })
+    }
+
+    /// Parse a CSV file using memory-mapped I/O
+    ///
+    /// # Arguments
+    /// * `path` - Path to the CSV file
+    /// * `columns` - Optional column names (or zero-based indices without headers) to project
+    #[napi]
+    pub fn parse_file(&self, path: String, columns: Option<Vec<String>>) -> napi::Result<CsvParseResult> {
+        errors::guard("CsvParser.parseFile", || {
+            let file = fs::File::open(&path).map_err(|e| {
+                errors::io(&e, format!("Failed to open {}: {}", path, e))
+            })?;
+
+            let metadata = file.metadata().map_err(|e| {
+                errors::io(&e, format!("Failed to read {}: {}", path, e))
+            })?;
+            if metadata.len() == 0 {
+                return self.parse_bytes(&[], columns);
+            }
+
+            // SAFETY: the mapping is read-only and dropped before this function returns
+            let mmap = unsafe { memmap2::Mmap::map(&file)? };
+            self.parse_bytes(&mmap, columns)
+        })
+    }
+
+    /// Internal parsing over raw bytes
+    fn parse_bytes(&self, bytes: &[u8], columns: Option<Vec<String>>) -> napi::Result<CsvParseResult> {
+        metrics::add_bytes("csv", bytes.len() as u64);
+        let (all_headers, data_start) = if self.config.has_headers {
+            self.read_headers(bytes)?
+        } else {
+            (Vec::new(), 0)
+        };
+
+        let projection = match &columns {
+            Some(columns) => Some(self.resolve_columns(columns, &all_headers)?),
+            None => None,
+        };
+
+        let data = &bytes[data_start..];
+        // Records are read by separate readers, so field counts are checked here
+        let expected_fields = if self.config.flexible {
+            None
+        } else if self.config.has_headers {
+            Some(all_headers.len())
+        } else {
+            Some(self.read_headers(data)?.0.len())
+        };
+
+        let headers = match &projection {
+            Some(indices) if !all_headers.is_empty() => {
+                indices.iter().map(|&i| all_headers[i].clone()).collect()
+            }
+            _ => all_headers,
+        };
+
+        let header_lines = count_lines(&bytes[..data_start]);
+        let limit = if self.config.max_rows > 0 {
+            Some(self.config.max_rows as usize)
+        } else {
+            None
+        };
+
+        let mut rows = if self.config.use_parallel && data.len() > PARALLEL_THRESHOLD && limit.is_none() {
+            let chunks = self.split_records(data, rayon::current_num_threads() * 4);
+            let mut lines = header_lines;
+            let chunks: Vec<(u64, &[u8])> = chunks
+                .into_iter()
+                .map(|chunk| {
+                    let first_line = lines;
+                    lines += count_lines(chunk);
+                    (first_line, chunk)
+                })
+                .collect();
+            let parsed: Vec<Vec<Vec<String>>> = chunks
+                .par_iter()
+                .map(|&(first_line, chunk)| {
+                    self.parse_records(chunk, first_line, expected_fields, projection.as_deref(), None)
+                })
+                .collect::<napi::Result<_>>()?;
+            parsed.into_iter().flatten().collect()
+        } else {
+            self.parse_records(data, header_lines, expected_fields, projection.as_deref(), limit.map(|l| l + 1))?
+        };
+
+        let truncated = limit.is_some_and(|l| rows.len() > l);
+        if let Some(limit) = limit {
+            rows.truncate(limit);
+        }
+
+        Ok(CsvParseResult {
+            headers,
+            row_count: rows.len() as u32,
+            rows,
+            truncated,
+        })
+    }
+
+    /// Build a csv reader configured from this parser
+    fn reader<'a>(&self, bytes: &'a [u8], has_headers: bool) -> csv::Reader<&'a [u8]> {
+        csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .has_headers(has_headers)
+            .flexible(self.config.flexible)
+            .trim(if self.config.trim { csv::Trim::All } else { csv::Trim::None })
+            .from_reader(bytes)
+    }
+
+    /// Read the header row and return it with the byte offset where data begins
+    fn read_headers(&self, bytes: &[u8]) -> napi::Result<(Vec<String>, usize)> {
+        let mut reader = self.reader(bytes, false);
+        let mut record = csv::StringRecord::new();
+
+        let found = reader.read_record(&mut record).map_err(|e| csv_error(e, 0))?;
+        if !found {
+            return Ok((Vec::new(), bytes.len()));
+        }
+
+        let data_start = reader.position().byte() as usize;
+        Ok((record.iter().map(|f| f.to_string()).collect(), data_start.min(bytes.len())))
+    }
+
+    /// Map requested column names or indices to field indices
+    fn resolve_columns(&self, columns: &[String], headers: &[String]) -> napi::Result<Vec<usize>> {
+        columns
+            .iter()
+            .map(|column| {
+                headers
+                    .iter()
+                    .position(|h| h == column)
+                    .or_else(|| if headers.is_empty() { column.parse().ok() } else { None })
+                    .ok_or_else(|| {
+                        errors::new(ErrorCode::InvalidArgument, format!("Unknown column: {}", column))
+                    })
+            })
+            .collect()
+    }
+
+    /// Parse headerless records from a byte slice
+    ///
+    /// # Arguments
+    /// * `first_line` - Lines of the input before the slice, for error positions
+    /// * `expected_fields` - Field count every record must have, if checked
+    fn parse_records(
+        &self,
+        bytes: &[u8],
+        first_line: u64,
+        expected_fields: Option<usize>,
+        projection: Option<&[usize]>,
+        limit: Option<usize>,
+    ) -> napi::Result<Vec<Vec<String>>> {
+        let mut reader = self.reader(bytes, false);
+        let mut record = csv::StringRecord::new();
+        let mut rows = Vec::new();
+
+        while reader.read_record(&mut record).map_err(|e| csv_error(e, first_line))? {
+            if let Some(expected) = expected_fields.filter(|&n| n != record.len()) {
+                let line = first_line + record.position().map_or(0, |p| p.line());
+                return Err(errors::new(
+                    ErrorCode::ParseError,
+                    format!(
+                        "Invalid CSV at line {}: expected {} fields, found {}",
+                        line,
+                        expected,
+                        record.len()
+                    ),
+                ));
+            }
+            let row = match projection {
+                Some(indices) => indices
+                    .iter()
+                    .map(|&i| record.get(i).unwrap_or("").to_string())
+                    .collect(),
+                None => record.iter().map(|f| f.to_string()).collect(),
+            };
+            rows.push(row);
+
+            if limit.is_some_and(|l| rows.len() >= l) {
+                break;
+            }
+        }
+
+        Ok(rows)
+    }
+
+    /// Split data into roughly equal chunks that end on record boundaries
+    ///
+    /// A newline ends a record only when an even number of quote characters
+    /// precede it, so quoted fields containing newlines are never split.
+    fn split_records<'a>(&self, data: &'a [u8], target_chunks: usize) -> Vec<&'a [u8]> {
+        let chunk_size = (data.len() / target_chunks.max(1)).max(1);
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        let mut in_quotes = false;
+        let mut next_split = chunk_size;
+
+        for (i, &byte) in data.iter().enumerate() {
+            if byte == self.quote {
+                in_quotes = !in_quotes;
+            } else if byte == b'\n' && !in_quotes && i >= next_split {
+                chunks.push(&data[start..=i]);
+                start = i + 1;
+                next_split = i + chunk_size;
+            }
+        }
+
+        if start < data.len() {
+            chunks.push(&data[start..]);
+        }
+        chunks
+    }
+}
+
+/// Validate that a config value is a single ASCII character
+fn single_byte(value: &str, name: &str) -> napi::Result<u8> {
+    match value.as_bytes() {
+        [byte] if byte.is_ascii() => Ok(*byte),
+        _ => Err(errors::new(
+            ErrorCode::InvalidArgument,
+            format!("{} must be a single ASCII character", name),
+        )),
+    }
+}
+
+/// Number of line breaks in `bytes`
+fn count_lines(bytes: &[u8]) -> u64 {
+    bytes.iter().filter(|&&b| b == b'\n').count() as u64
+}
+
+/// Convert a csv error into a napi error with its position
+///
+/// `first_line` is the number of input lines before the parsed slice.
+fn csv_error(e: csv::Error, first_line: u64) -> napi::Error {
+    let location = e
+        .position()
+        .map(|p| format!(" at line {}", first_line + p.line()))
+        .unwrap_or_default();
+    errors::new(ErrorCode::ParseError, format!("Invalid CSV{}: {}", location, e))
+}
+
+/// Quick CSV parsing with default configuration
+#[napi]
+pub fn quick_parse_csv(text: String, delimiter: Option<String>) -> napi::Result<CsvParseResult> {
+    errors::guard("quickParseCsv", || {
+        let parser = CsvParser::new(Some(CsvConfig {
+            delimiter: delimiter.unwrap_or_else(|| ",".to_string()),
+            ..Default::default()
+        }))?;
+        parser.parse_text(text, None)
+    })
+}
@@ -0,0 +1,233 @@
+//! Dependency inventory from lockfiles
+//!
+//! Resolved dependencies are read from `package-lock.json` (lockfile v1-v3),
+//! `Cargo.lock` and pinned `requirements.txt` entries. Each one is reported
+//! with its package URL and OSV ecosystem name, so the inventory can feed
+//! SBOM generation and advisory matching directly.
+
+use crate::errors::{self, ErrorCode};
+use crate::file_search::{FileSearch, FileSearchConfig};
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Lockfile names the inventory understands
+const LOCKFILES: [&str; 3] = ["package-lock.json", "Cargo.lock", "requirements.txt"];
+
+/// A resolved third-party dependency
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dependency {
+    /// Package name (npm names keep their @scope)
+    pub name: String,
+    /// Resolved version
+    pub version: String,
+    /// OSV ecosystem: "npm", "crates.io" or "PyPI"
+    pub ecosystem: String,
+    /// Package URL (purl)
+    pub purl: String,
+    /// Declared license, when the lockfile records it
+    pub license: Option<String>,
+    /// Whether the package is only needed for development
+    pub dev: bool,
+    /// Lockfile the entry was read from
+    pub source: String,
+}
+
+/// Parse a single lockfile
+///
+/// # Arguments
+/// * `path` - Path to a package-lock.json, Cargo.lock or requirements.txt
+#[napi]
+pub fn parse_lockfile(path: String) -> napi::Result<Vec<Dependency>> {
+    errors::guard("parseLockfile", || {
+        let mut dependencies = read_lockfile(Path::new(&path))?;
+        dedupe(&mut dependencies);
+        Ok(dependencies)
+    })
+}
+
+/// List dependencies from every lockfile below a directory
+///
+/// # Arguments
+/// * `root_path` - Project directory
+/// * `search` - Optional traversal settings (node_modules and target are skipped by default)
+#[napi]
+pub fn list_dependencies(root_path: String, search: Option<FileSearchConfig>) -> napi::Result<Vec<Dependency>> {
+    errors::guard("listDependencies", || {
+        collect_dependencies(Path::new(&root_path), search)
+    })
+}
+
+/// Inventory of all lockfiles below `root`, deduplicated and sorted
+pub(crate) fn collect_dependencies(root: &Path, search: Option<FileSearchConfig>) -> napi::Result<Vec<Dependency>> {
+    let files = FileSearch::new(search)?.collect_files(root)?;
+    let mut dependencies = Vec::new();
+    for path in files {
+        let is_lockfile = path
+            .file_name()
+            .is_some_and(|name| LOCKFILES.iter().any(|l| name == *l));
+        if is_lockfile {
+            dependencies.extend(read_lockfile(&path)?);
+        }
+    }
+    dedupe(&mut dependencies);
+    Ok(dependencies)
+}
+
+/// Dispatch on the lockfile name
+fn read_lockfile(path: &Path) -> napi::Result<Vec<Dependency>> {
+    let source = path.to_string_lossy().to_string();
+    let content = fs::read_to_string(path).map_err(|e| {
+        errors::io(&e, format!("Failed to read {}: {}", source, e))
+    })?;
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+
+    match name.as_str() {
+        "package-lock.json" => parse_package_lock(&content, &source),
+        "Cargo.lock" => parse_cargo_lock(&content, &source),
+        "requirements.txt" => Ok(parse_requirements(&content, &source)),
+        _ => Err(errors::new(
+            ErrorCode::InvalidArgument,
+            format!("Unsupported lockfile: {} (expected one of {})", source, LOCKFILES.join(", ")),
+        )),
+    }
+}
+
+/// npm lockfile: the v2/v3 `packages` map, or the nested v1 `dependencies`
+fn parse_package_lock(content: &str, source: &str) -> napi::Result<Vec<Dependency>> {
+    let lock: Value = serde_json::from_str(content).map_err(|e| {
+        errors::new(ErrorCode::ParseError, format!("Invalid package-lock.json {}: {}", source, e))
+    })?;
+    let mut dependencies = Vec::new();
+
+    if let Some(packages) = lock.get("packages").and_then(Value::as_object) {
+        for (key, entry) in packages {
+            // The root project is keyed by ""; linked workspaces have no version
+            let Some(version) = entry.get("version").and_then(Value::as_str) else { continue };
+            if key.is_empty() || !key.contains("node_modules/") {
+                continue;
+            }
+            let name = entry
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_else(|| key.rsplit("node_modules/").next().unwrap_or(key));
+            let dev = ["dev", "devOptional"]
+                .iter()
+                .any(|flag| entry.get(*flag).and_then(Value::as_bool).unwrap_or(false));
+            let license = entry.get("license").and_then(Value::as_str).map(str::to_string);
+            dependencies.push(npm_dependency(name, version, license, dev, source));
+        }
+    } else if let Some(deps) = lock.get("dependencies").and_then(Value::as_object) {
+        collect_npm_v1(deps, source, &mut dependencies);
+    }
+    Ok(dependencies)
+}
+
+/// Walk the nested v1 `dependencies` tree
+fn collect_npm_v1(deps: &serde_json::Map<String, Value>, source: &str, out: &mut Vec<Dependency>) {
+    for (name, entry) in deps {
+        if let Some(version) = entry.get("version").and_then(Value::as_str) {
+            let dev = entry.get("dev").and_then(Value::as_bool).unwrap_or(false);
+            out.push(npm_dependency(name, version, None, dev, source));
+        }
+        if let Some(nested) = entry.get("dependencies").and_then(Value::as_object) {
+            collect_npm_v1(nested, source, out);
+        }
+    }
+}
+
+fn npm_dependency(name: &str, version: &str, license: Option<String>, dev: bool, source: &str) -> Dependency {
+    let purl_name = match name.strip_prefix('@') {
+        Some(scoped) => format!("%40{}", scoped),
+        None => name.to_string(),
+    };
+    Dependency {
+        name: name.to_string(),
+        version: version.to_string(),
+        ecosystem: "npm".to_string(),
+        purl: format!("pkg:npm/{}@{}", purl_name, version),
+        license,
+        dev,
+        source: source.to_string(),
+    }
+}
+
+/// Cargo lockfile `[[package]]` entries; workspace members have no `source`
+fn parse_cargo_lock(content: &str, source: &str) -> napi::Result<Vec<Dependency>> {
+    let lock: toml::Table = toml::from_str(content).map_err(|e| {
+        errors::new(ErrorCode::ParseError, format!("Invalid Cargo.lock {}: {}", source, e))
+    })?;
+    let packages = lock.get("package").and_then(|p| p.as_array()).cloned().unwrap_or_default();
+
+    Ok(packages
+        .iter()
+        .filter(|package| package.get("source").is_some())
+        .filter_map(|package| {
+            let name = package.get("name")?.as_str()?;
+            let version = package.get("version")?.as_str()?;
+            Some(Dependency {
+                name: name.to_string(),
+                version: version.to_string(),
+                ecosystem: "crates.io".to_string(),
+                purl: format!("pkg:cargo/{}@{}", name, version),
+                license: None,
+                dev: false,
+                source: source.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Pinned `name==version` requirements; ranges and URLs are not resolved
+fn parse_requirements(content: &str, source: &str) -> Vec<Dependency> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.split('#').next()?.split(';').next()?.trim();
+            let (name, version) = line.split_once("==")?;
+            let name = name.split('[').next()?.trim();
+            let version = version.split(',').next()?.trim();
+            if name.is_empty() || version.is_empty() || name.starts_with('-') {
+                return None;
+            }
+            let normalized = name.to_ascii_lowercase().replace(['_', '.'], "-");
+            Some(Dependency {
+                name: name.to_string(),
+                version: version.to_string(),
+                ecosystem: "PyPI".to_string(),
+                purl: format!("pkg:pypi/{}@{}", normalized, version),
+                license: None,
+                dev: false,
+                source: source.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Merge repeated packages; an entry is dev-only if every occurrence is
+fn dedupe(dependencies: &mut Vec<Dependency>) {
+    let mut merged: BTreeMap<(String, String, String), Dependency> = BTreeMap::new();
+    for dependency in dependencies.drain(..) {
+        let key = (
+            dependency.ecosystem.clone(),
+            dependency.name.clone(),
+            dependency.version.clone(),
+        );
+        match merged.get_mut(&key) {
+            Some(existing) => {
+                existing.dev &= dependency.dev;
+                if existing.license.is_none() {
+                    existing.license = dependency.license;
+                }
+            }
+            None => {
+                merged.insert(key, dependency);
+            }
+        }
+    }
+    dependencies.extend(merged.into_values());
+}
@@ -0,0 +1,308 @@
+//! Persistent cache of embeddings keyed by content hash
+//!
+//! Re-indexing a tree asks the embedding model for a vector per chunk, but
+//! most chunks are unchanged since the last run. An `EmbeddingCache` stores
+//! each vector under the hash of the content it was computed from (e.g. a
+//! `hashBuffer` digest of the chunk text, or a `FileMetadataDb` file hash),
+//! so callers look the whole batch up first and embed only the misses.
+//!
+//! Vectors live in the same embedded sled store as `FileMetadataDb`, one
+//! tree per model name, since vectors from different models can't be mixed.
+//! They are stored as little-endian f32, the precision the vector
+//! operations compute in, and every vector of a model must have the same
+//! number of dimensions. Lookups are counted under the `embeddings` cache
+//! in `getMetrics()`.
+
+use crate::errors::{self, ErrorCode};
+use crate::metadata_db;
+use crate::metrics;
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Model name used when none is given
+const DEFAULT_MODEL: &str = "default";
+
+/// Tree recording the dimensions of each model's vectors
+const DIMENSIONS_TREE: &str = "embedding_dimensions";
+
+/// Embeddings found by `getEmbeddings`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingLookup {
+    /// Whether each hash was cached, in request order
+    pub found: Vec<bool>,
+    /// Vectors of the cached hashes, in request order, flattened
+    pub vectors_flat: Vec<f64>,
+    /// Dimensions of each vector (0 if the model has none stored)
+    pub vector_size: u32,
+    /// Positions in the request of the hashes that were not cached
+    pub missing: Vec<u32>,
+}
+
+/// Embeddings stored on disk by content hash
+#[napi]
+pub struct EmbeddingCache {
+    vectors: sled::Tree,
+    dimensions: sled::Tree,
+    model: String,
+}
+
+#[napi]
+impl EmbeddingCache {
+    /// Open or create an embedding cache
+    ///
+    /// # Arguments
+    /// * `path` - Directory holding the database (created if missing; may
+    ///   be shared with a `FileMetadataDb`)
+    /// * `model` - Name of the model the vectors come from (default: "default")
+    #[napi(constructor)]
+    pub fn new(path: String, model: Option<String>) -> napi::Result<Self> {
+        errors::guard("new EmbeddingCache", || {
+            let model = model.unwrap_or_else(|| DEFAULT_MODEL.to_string());
+            if model.is_empty() {
+                return Err(errors::new(ErrorCode::InvalidArgument, "Model name must not be empty"));
+            }
+            let db = metadata_db::open_db(&path)?;
+            let vectors = db
+                .open_tree(format!("embeddings/{}", model))
+                .map_err(|e| db_error("open tree", e))?;
+            let dimensions = db.open_tree(DIMENSIONS_TREE).map_err(|e| db_error("open tree", e))?;
+            Ok(Self { vectors, dimensions, model })
+        })
+    }
+
+    /// Store the embedding of one piece of content
+    ///
+    /// # Arguments
+    /// * `content_hash` - Hash of the content the vector was computed from
+    /// * `vector` - The embedding
+    #[napi]
+    pub fn put_embedding(&self, content_hash: String, vector: Vec<f64>) -> napi::Result<()> {
+        errors::guard("EmbeddingCache.putEmbedding", || {
+            self.check_dimensions(vector.len())?;
+            self.vectors
+                .insert(content_hash.as_bytes(), encode(&vector))
+                .map_err(|e| db_error("insert", e))?;
+            Ok(())
+        })
+    }
+
+    /// Store several embeddings at once; `content_hashes` and vectors are paired by position
+    ///
+    /// The batch is written atomically.
+    ///
+    /// # Arguments
+    /// * `content_hashes` - Hashes of the contents
+    /// * `vectors_flat` - Embeddings, flattened (content_hashes.length * vector_size)
+    /// * `vector_size` - Dimensions of each embedding
+    #[napi]
+    pub fn put_embeddings(
+        &self,
+        content_hashes: Vec<String>,
+        vectors_flat: Vec<f64>,
+        vector_size: u32,
+    ) -> napi::Result<()> {
+        errors::guard("EmbeddingCache.putEmbeddings", || {
+            let vector_size = vector_size as usize;
+            if vector_size == 0 || vectors_flat.len() != content_hashes.len() * vector_size {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!(
+                        "Expected {} vectors of {} dimensions, got {} values",
+                        content_hashes.len(),
+                        vector_size,
+                        vectors_flat.len()
+                    ),
+                ));
+            }
+            if content_hashes.is_empty() {
+                return Ok(());
+            }
+            self.check_dimensions(vector_size)?;
+            let mut batch = sled::Batch::default();
+            for (hash, vector) in content_hashes.iter().zip(vectors_flat.chunks(vector_size)) {
+                batch.insert(hash.as_bytes(), encode(vector));
+            }
+            self.vectors.apply_batch(batch).map_err(|e| db_error("insert", e))
+        })
+    }
+
+    /// Get the cached embedding of one piece of content
+    #[napi]
+    pub fn get_embedding(&self, content_hash: String) -> napi::Result<Option<Vec<f64>>> {
+        errors::guard("EmbeddingCache.getEmbedding", || {
+            let vector = self.lookup(&content_hash, self.stored_dimensions()?)?;
+            metrics::record_cache("embeddings", vector.is_some());
+            Ok(vector)
+        })
+    }
+
+    /// Look up the embeddings of several pieces of content
+    ///
+    /// Embed the contents at the `missing` positions and store them with
+    /// `putEmbeddings`.
+    #[napi]
+    pub fn get_embeddings(&self, content_hashes: Vec<String>) -> napi::Result<EmbeddingLookup> {
+        errors::guard("EmbeddingCache.getEmbeddings", || {
+            let dimensions = self.stored_dimensions()?;
+            let mut result = EmbeddingLookup {
+                found: Vec::with_capacity(content_hashes.len()),
+                vectors_flat: Vec::new(),
+                vector_size: dimensions.unwrap_or(0) as u32,
+                missing: Vec::new(),
+            };
+            for (i, hash) in content_hashes.iter().enumerate() {
+                let vector = self.lookup(hash, dimensions)?;
+                metrics::record_cache("embeddings", vector.is_some());
+                result.found.push(vector.is_some());
+                match vector {
+                    Some(vector) => result.vectors_flat.extend(vector),
+                    None => result.missing.push(i as u32),
+                }
+            }
+            Ok(result)
+        })
+    }
+
+    /// Drop the embeddings of the given hashes
+    ///
+    /// Returns the number of embeddings dropped.
+    #[napi]
+    pub fn remove(&self, content_hashes: Vec<String>) -> napi::Result<u32> {
+        errors::guard("EmbeddingCache.remove", || {
+            let mut removed = 0;
+            for hash in &content_hashes {
+                if self.vectors.remove(hash.as_bytes()).map_err(|e| db_error("remove", e))?.is_some() {
+                    removed += 1;
+                }
+            }
+            Ok(removed)
+        })
+    }
+
+    /// Drop every embedding whose hash is not in `content_hashes`, e.g. after a re-index
+    ///
+    /// Returns the number of embeddings dropped.
+    #[napi]
+    pub fn retain(&self, content_hashes: Vec<String>) -> napi::Result<u32> {
+        errors::guard("EmbeddingCache.retain", || {
+            let keep: HashSet<&[u8]> = content_hashes.iter().map(|hash| hash.as_bytes()).collect();
+            let stale: Vec<sled::IVec> = self
+                .vectors
+                .iter()
+                .keys()
+                .filter_map(|key| key.ok())
+                .filter(|key| !keep.contains(key.as_ref()))
+                .collect();
+            for key in &stale {
+                self.vectors.remove(key).map_err(|e| db_error("remove", e))?;
+            }
+            Ok(stale.len() as u32)
+        })
+    }
+
+    /// Drop every embedding of this model, so its dimensions can change
+    #[napi]
+    pub fn clear(&self) -> napi::Result<()> {
+        errors::guard("EmbeddingCache.clear", || {
+            self.vectors.clear().map_err(|e| db_error("clear", e))?;
+            self.dimensions.remove(self.model.as_bytes()).map_err(|e| db_error("clear", e))?;
+            Ok(())
+        })
+    }
+
+    /// Write pending changes to disk (also done periodically in the background)
+    #[napi]
+    pub fn flush(&self) -> napi::Result<()> {
+        errors::guard("EmbeddingCache.flush", || {
+            self.vectors.flush().map(|_| ()).map_err(|e| db_error("flush", e))?;
+            self.dimensions.flush().map(|_| ()).map_err(|e| db_error("flush", e))
+        })
+    }
+
+    /// Number of stored embeddings of this model
+    #[napi(getter)]
+    pub fn count(&self) -> u32 {
+        self.vectors.len() as u32
+    }
+
+    /// Dimensions of this model's embeddings, once any are stored
+    #[napi(getter)]
+    pub fn dimensions(&self) -> napi::Result<Option<u32>> {
+        errors::guard("EmbeddingCache.dimensions", || {
+            Ok(self.stored_dimensions()?.map(|dimensions| dimensions as u32))
+        })
+    }
+}
+
+impl EmbeddingCache {
+    /// Dimensions recorded for this model, if any
+    fn stored_dimensions(&self) -> napi::Result<Option<usize>> {
+        let stored = self.dimensions.get(self.model.as_bytes()).map_err(|e| db_error("read", e))?;
+        Ok(stored
+            .and_then(|value| <[u8; 4]>::try_from(value.as_ref()).ok())
+            .map(|bytes| u32::from_le_bytes(bytes) as usize))
+    }
+
+    /// Record `dimensions` for this model, or check it matches the recorded one
+    fn check_dimensions(&self, dimensions: usize) -> napi::Result<()> {
+        if dimensions == 0 {
+            return Err(errors::new(ErrorCode::InvalidArgument, "Embeddings must not be empty"));
+        }
+        let recorded = self
+            .dimensions
+            .compare_and_swap(
+                self.model.as_bytes(),
+                None as Option<&[u8]>,
+                Some(&(dimensions as u32).to_le_bytes()[..]),
+            )
+            .map_err(|e| db_error("write", e))?;
+        match recorded {
+            Ok(()) => Ok(()),
+            Err(current) => {
+                let current = current
+                    .current
+                    .and_then(|value| <[u8; 4]>::try_from(value.as_ref()).ok())
+                    .map(|bytes| u32::from_le_bytes(bytes) as usize);
+                if current == Some(dimensions) {
+                    Ok(())
+                } else {
+                    Err(errors::new(
+                        ErrorCode::InvalidArgument,
+                        format!(
+                            "Model {} stores {}-dimensional embeddings, got {} dimensions (clear the cache to change)",
+                            self.model,
+                            current.unwrap_or(0),
+                            dimensions
+                        ),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// The stored vector of a hash, if it has the model's dimensions
+    fn lookup(&self, hash: &str, dimensions: Option<usize>) -> napi::Result<Option<Vec<f64>>> {
+        let Some(dimensions) = dimensions else { return Ok(None) };
+        let stored = self.vectors.get(hash.as_bytes()).map_err(|e| db_error("read", e))?;
+        Ok(stored.filter(|value| value.len() == dimensions * 4).map(|value| decode(&value)))
+    }
+}
+
+/// Vector as little-endian f32 bytes
+fn encode(vector: &[f64]) -> Vec<u8> {
+    vector.iter().flat_map(|&x| (x as f32).to_le_bytes()).collect()
+}
+
+/// Vector from little-endian f32 bytes
+fn decode(bytes: &[u8]) -> Vec<f64> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) as f64)
+        .collect()
+}
+
+fn db_error(context: &str, error: sled::Error) -> napi::Error {
+    errors::new(ErrorCode::IoError, format!("Embedding cache error ({}): {}", context, error))
+}
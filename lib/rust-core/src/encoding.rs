@@ -0,0 +1,217 @@
+//! Base64, hex and URL encoding of buffers
+//!
+//! Artifact inspection decodes large base64 and hex payloads (embedded
+//! files, signatures, data URLs) where JavaScript decoding is a bottleneck.
+//! Base64 and hex use SIMD kernels selected at runtime (SSE4.1/AVX2 on x86,
+//! NEON on ARM) with scalar fallbacks; URL percent-encoding is scalar.
+//! Encoders accept a string (encoded as UTF-8) or a Buffer and return a
+//! string; decoders return a Buffer, since the bytes need not be UTF-8.
+
+use crate::errors::{self, ErrorCode};
+use crate::metrics;
+use crate::text_processing::input_bytes;
+use napi::bindgen_prelude::{Buffer, Either};
+use napi_derive::napi;
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC};
+
+/// Bytes `encodeURIComponent` leaves as they are, besides ASCII letters and digits
+const COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'!')
+    .remove(b'~')
+    .remove(b'*')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')');
+
+/// Bytes application/x-www-form-urlencoded leaves as they are; spaces become `+`
+const FORM: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'*');
+
+/// Base64 alphabets, named like Node's Buffer encodings
+#[derive(Debug, Clone, Copy)]
+enum Base64Variant {
+    /// Standard alphabet with `=` padding
+    Standard,
+    /// URL-safe alphabet (`-` and `_`) without padding
+    Url,
+}
+
+impl Base64Variant {
+    fn parse(variant: Option<&str>) -> napi::Result<Self> {
+        match variant {
+            None | Some("base64") => Ok(Self::Standard),
+            Some("base64url") => Ok(Self::Url),
+            Some(other) => Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Unknown base64 variant: {} (expected base64 or base64url)", other),
+            )),
+        }
+    }
+}
+
+/// URL encoding flavours
+#[derive(Debug, Clone, Copy)]
+enum UrlMode {
+    /// Like `encodeURIComponent`/`decodeURIComponent`
+    Component,
+    /// application/x-www-form-urlencoded, with `+` for spaces
+    Form,
+}
+
+impl UrlMode {
+    fn parse(mode: Option<&str>) -> napi::Result<Self> {
+        match mode {
+            None | Some("component") => Ok(Self::Component),
+            Some("form") => Ok(Self::Form),
+            Some(other) => Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Unknown URL encoding mode: {} (expected component or form)", other),
+            )),
+        }
+    }
+}
+
+/// Encode bytes as base64
+///
+/// # Arguments
+/// * `data` - Bytes to encode (strings are encoded as UTF-8)
+/// * `variant` - "base64" (default; padded standard alphabet) or "base64url"
+///   (unpadded URL-safe alphabet), matching `buffer.toString(variant)`
+#[napi]
+pub fn encode_base64(data: Either<String, Buffer>, variant: Option<String>) -> napi::Result<String> {
+    errors::guard("encodeBase64", || {
+        let bytes = input_bytes(&data);
+        metrics::add_bytes("encoding", bytes.len() as u64);
+        let encoded = match Base64Variant::parse(variant.as_deref())? {
+            Base64Variant::Standard => base64_simd::STANDARD.encode_type(bytes),
+            Base64Variant::Url => base64_simd::URL_SAFE_NO_PAD.encode_type(bytes),
+        };
+        Ok(encoded)
+    })
+}
+
+/// Decode base64 into bytes
+///
+/// Accepts the standard and URL-safe alphabets, with or without `=`
+/// padding, and ignores ASCII whitespace (e.g. line-wrapped PEM bodies).
+/// Unlike `Buffer.from(text, "base64")`, which stops at the first invalid
+/// character, malformed input is an error.
+///
+/// # Arguments
+/// * `input` - Base64 text
+#[napi]
+pub fn decode_base64(input: Either<String, Buffer>) -> napi::Result<Buffer> {
+    errors::guard("decodeBase64", || {
+        let bytes = input_bytes(&input);
+        metrics::add_bytes("encoding", bytes.len() as u64);
+        let compact: Vec<u8>;
+        let mut bytes = bytes;
+        if bytes.iter().any(u8::is_ascii_whitespace) {
+            compact = bytes.iter().copied().filter(|b| !b.is_ascii_whitespace()).collect();
+            bytes = &compact;
+        }
+        let (standard, url) = if bytes.ends_with(b"=") {
+            (base64_simd::STANDARD, base64_simd::URL_SAFE)
+        } else {
+            (base64_simd::STANDARD_NO_PAD, base64_simd::URL_SAFE_NO_PAD)
+        };
+        standard
+            .decode_type::<Vec<u8>>(bytes)
+            .or_else(|_| url.decode_type::<Vec<u8>>(bytes))
+            .map(Buffer::from)
+            .map_err(|_| errors::new(ErrorCode::ParseError, "Invalid base64 input"))
+    })
+}
+
+/// Encode bytes as hex
+///
+/// # Arguments
+/// * `data` - Bytes to encode (strings are encoded as UTF-8)
+/// * `uppercase` - Use uppercase digits (default: false)
+#[napi]
+pub fn encode_hex(data: Either<String, Buffer>, uppercase: Option<bool>) -> napi::Result<String> {
+    errors::catch_panic("encodeHex", || {
+        let bytes = input_bytes(&data);
+        metrics::add_bytes("encoding", bytes.len() as u64);
+        if uppercase.unwrap_or(false) {
+            faster_hex::hex_string_upper(bytes)
+        } else {
+            faster_hex::hex_string(bytes)
+        }
+    })
+}
+
+/// Decode hex into bytes
+///
+/// Digits may be upper- or lowercase. Odd-length input or any other
+/// character is an error.
+///
+/// # Arguments
+/// * `input` - Hex text
+#[napi]
+pub fn decode_hex(input: Either<String, Buffer>) -> napi::Result<Buffer> {
+    errors::guard("decodeHex", || {
+        let bytes = input_bytes(&input);
+        metrics::add_bytes("encoding", bytes.len() as u64);
+        if !bytes.len().is_multiple_of(2) {
+            return Err(errors::new(
+                ErrorCode::ParseError,
+                format!("Invalid hex input: odd length {}", bytes.len()),
+            ));
+        }
+        let mut decoded = vec![0u8; bytes.len() / 2];
+        faster_hex::hex_decode(bytes, &mut decoded)
+            .map_err(|_| errors::new(ErrorCode::ParseError, "Invalid hex input: non-hex character"))?;
+        Ok(decoded.into())
+    })
+}
+
+/// Percent-encode bytes for use in a URL
+///
+/// # Arguments
+/// * `data` - Bytes to encode (strings are encoded as UTF-8)
+/// * `mode` - "component" (default; same output as `encodeURIComponent`) or
+///   "form" (same as `URLSearchParams`, with `+` for spaces)
+#[napi]
+pub fn encode_url(data: Either<String, Buffer>, mode: Option<String>) -> napi::Result<String> {
+    errors::guard("encodeUrl", || {
+        let bytes = input_bytes(&data);
+        metrics::add_bytes("encoding", bytes.len() as u64);
+        let encoded = match UrlMode::parse(mode.as_deref())? {
+            UrlMode::Component => percent_encoding::percent_encode(bytes, COMPONENT).to_string(),
+            // A literal "%20" in the input is encoded as "%2520", so only spaces match
+            UrlMode::Form => percent_encoding::percent_encode(bytes, FORM).to_string().replace("%20", "+"),
+        };
+        Ok(encoded)
+    })
+}
+
+/// Decode percent-encoded URL text into bytes
+///
+/// Malformed escapes (a `%` not followed by two hex digits) are kept as
+/// they are, like `URLSearchParams` does.
+///
+/// # Arguments
+/// * `input` - Encoded text
+/// * `mode` - "component" (default) or "form" (`+` decodes to a space)
+#[napi]
+pub fn decode_url(input: Either<String, Buffer>, mode: Option<String>) -> napi::Result<Buffer> {
+    errors::guard("decodeUrl", || {
+        let bytes = input_bytes(&input);
+        metrics::add_bytes("encoding", bytes.len() as u64);
+        let decoded: Vec<u8> = match UrlMode::parse(mode.as_deref())? {
+            UrlMode::Component => percent_encoding::percent_decode(bytes).collect(),
+            UrlMode::Form => {
+                let spaced: Vec<u8> = bytes.iter().map(|&b| if b == b'+' { b' ' } else { b }).collect();
+                percent_encoding::percent_decode(&spaced).collect()
+            }
+        };
+        Ok(decoded.into())
+    })
+}
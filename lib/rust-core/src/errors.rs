@@ -0,0 +1,179 @@
+//! Machine-readable error codes for native errors
+//!
+//! Every error raised by this crate carries an [`ErrorCode`] as a bracketed
+//! prefix of its message, e.g. `[PATH_NOT_FOUND] Path does not exist: src`.
+//! JavaScript callers can branch on the code with `parseErrorCode(err.message)`
+//! instead of matching on message wording, which is free to change.
+//!
+//! Exported functions that do real work run their bodies through [`guard`] or
+//! [`catch_panic`], so a panic (in the calling thread or a rayon worker)
+//! becomes an `INTERNAL` error naming the operation instead of aborting the
+//! Node process. This relies on the release profile keeping `panic = "unwind"`.
+//! The same wrappers feed the per-operation counters in [`crate::metrics`].
+
+use crate::metrics::{self, Outcome};
+use napi_derive::napi;
+use std::any::Any;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
+
+/// Category of a native error
+#[napi(string_enum = "SCREAMING_SNAKE_CASE")]
+#[derive(Debug, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// An option or argument has an invalid value
+    InvalidArgument,
+    /// A file or directory does not exist
+    PathNotFound,
+    /// The operating system, sandbox or policy refused access
+    PermissionDenied,
+    /// The target already exists and will not be overwritten
+    AlreadyExists,
+    /// A glob, regex or rule pattern failed to compile
+    PatternInvalid,
+    /// Input data (JSON, TOML, CSV, lockfile, ...) is malformed
+    ParseError,
+    /// A configured quota would be exceeded
+    QuotaExceeded,
+    /// A configured limit (e.g. `max_entries`) stopped the operation before
+    /// it finished, and the result can't be returned as partial
+    LimitReached,
+    /// Stored data failed a digest or signature check
+    IntegrityError,
+    /// The operation ran out of time
+    Timeout,
+    /// The operation was cancelled by the caller
+    Cancelled,
+    /// A JavaScript callback threw or could not be called
+    CallbackFailed,
+    /// Reading or writing failed for another I/O reason
+    IoError,
+    /// An unexpected failure inside the native module
+    Internal,
+}
+
+/// All codes, in declaration order
+const ALL: [ErrorCode; 14] = [
+    ErrorCode::InvalidArgument,
+    ErrorCode::PathNotFound,
+    ErrorCode::PermissionDenied,
+    ErrorCode::AlreadyExists,
+    ErrorCode::PatternInvalid,
+    ErrorCode::ParseError,
+    ErrorCode::QuotaExceeded,
+    ErrorCode::LimitReached,
+    ErrorCode::IntegrityError,
+    ErrorCode::Timeout,
+    ErrorCode::Cancelled,
+    ErrorCode::CallbackFailed,
+    ErrorCode::IoError,
+    ErrorCode::Internal,
+];
+
+impl ErrorCode {
+    /// The code as it appears in error messages
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::InvalidArgument => "INVALID_ARGUMENT",
+            Self::PathNotFound => "PATH_NOT_FOUND",
+            Self::PermissionDenied => "PERMISSION_DENIED",
+            Self::AlreadyExists => "ALREADY_EXISTS",
+            Self::PatternInvalid => "PATTERN_INVALID",
+            Self::ParseError => "PARSE_ERROR",
+            Self::QuotaExceeded => "QUOTA_EXCEEDED",
+            Self::LimitReached => "LIMIT_REACHED",
+            Self::IntegrityError => "INTEGRITY_ERROR",
+            Self::Timeout => "TIMEOUT",
+            Self::Cancelled => "CANCELLED",
+            Self::CallbackFailed => "CALLBACK_FAILED",
+            Self::IoError => "IO_ERROR",
+            Self::Internal => "INTERNAL",
+        }
+    }
+
+    /// The napi status reported alongside the code
+    fn status(self) -> napi::Status {
+        match self {
+            Self::InvalidArgument
+            | Self::PathNotFound
+            | Self::AlreadyExists
+            | Self::PatternInvalid
+            | Self::ParseError => napi::Status::InvalidArg,
+            Self::Cancelled => napi::Status::Cancelled,
+            _ => napi::Status::GenericFailure,
+        }
+    }
+
+    /// Classify an I/O error by its kind
+    pub(crate) fn from_io(error: &io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::NotFound => Self::PathNotFound,
+            io::ErrorKind::PermissionDenied => Self::PermissionDenied,
+            io::ErrorKind::AlreadyExists => Self::AlreadyExists,
+            io::ErrorKind::TimedOut => Self::Timeout,
+            io::ErrorKind::InvalidData => Self::ParseError,
+            _ => Self::IoError,
+        }
+    }
+}
+
+/// Create a napi error whose message is prefixed with `code`
+pub(crate) fn new(code: ErrorCode, reason: impl Into<String>) -> napi::Error {
+    napi::Error::new(code.status(), format!("[{}] {}", code.as_str(), reason.into()))
+}
+
+/// Create a napi error for an I/O failure, coded by the error's kind
+pub(crate) fn io(error: &io::Error, reason: impl Into<String>) -> napi::Error {
+    new(ErrorCode::from_io(error), reason)
+}
+
+/// Run the body of a fallible export, turning a panic into an error
+///
+/// `operation` is the JavaScript name of the export, e.g. `FileSearch.findFilesByPattern`.
+/// Every call is recorded in the runtime metrics.
+pub(crate) fn guard<T>(operation: &'static str, body: impl FnOnce() -> napi::Result<T>) -> napi::Result<T> {
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(body));
+    let outcome = match &result {
+        Ok(Ok(_)) => Outcome::Ok,
+        Ok(Err(_)) => Outcome::Error,
+        Err(_) => Outcome::Panic,
+    };
+    metrics::record_operation(operation, start.elapsed(), outcome);
+
+    result.unwrap_or_else(|payload| {
+        Err(new(
+            ErrorCode::Internal,
+            format!("Panic in {}: {}", operation, panic_message(payload.as_ref())),
+        ))
+    })
+}
+
+/// Run the body of an infallible export, turning a panic into an error
+pub(crate) fn catch_panic<T>(operation: &'static str, body: impl FnOnce() -> T) -> napi::Result<T> {
+    guard(operation, || Ok(body()))
+}
+
+/// The message passed to `panic!`, if it was a string
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "non-string panic payload"
+    }
+}
+
+/// Extract the error code from a native error message
+///
+/// # Arguments
+/// * `message` - The `message` of an error thrown by this module
+///
+/// Returns `null` when the message carries no known code.
+#[napi]
+pub fn parse_error_code(message: String) -> Option<ErrorCode> {
+    let code = message.strip_prefix('[')?.split_once(']')?.0;
+    ALL.into_iter().find(|c| c.as_str() == code)
+}
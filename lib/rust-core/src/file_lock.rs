@@ -0,0 +1,163 @@
+//! Advisory locks for files shared between processes
+//!
+//! Several moidvk processes (editor integrations, CI steps, watchers) can
+//! share one keyword index, benchmark baseline or audit log. Writers take an
+//! exclusive lock and readers a shared one, so a reader never sees a file
+//! half written by another process.
+//!
+//! The lock is held on a `<path>.lock` file next to the target rather than
+//! the target itself, so it keeps working when the target is replaced by a
+//! rename. Lock files are left in place after release; removing them would
+//! let two processes lock different files for the same path. Locks are
+//! advisory: they exclude only code that takes them too.
+
+use crate::errors;
+use napi_derive::napi;
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Suffix of the lock file next to each locked path
+const LOCK_SUFFIX: &str = ".lock";
+
+/// How long internal writers wait for a lock
+pub(crate) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// First and longest wait between attempts to take a contended lock
+const MIN_BACKOFF: Duration = Duration::from_millis(1);
+const MAX_BACKOFF: Duration = Duration::from_millis(50);
+
+/// A held lock, released when dropped
+#[derive(Debug)]
+pub(crate) struct LockGuard {
+    file: File,
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        // Closing the file releases the lock as well; unlock explicitly so
+        // it is released even while another handle to the file is open
+        let _ = self.file.unlock();
+    }
+}
+
+/// Lock `path` for reading (`shared`) or writing, waiting up to `timeout`
+pub(crate) fn lock(path: &Path, shared: bool, timeout: Duration) -> napi::Result<LockGuard> {
+    try_lock_for(path, shared, timeout).map_err(|e| {
+        errors::io(&e, format!("Failed to lock {}: {}", path.display(), e))
+    })
+}
+
+/// Read a file under a shared lock, so a concurrent locked write is never seen half done
+///
+/// A missing file is reported without creating its lock file.
+pub(crate) fn read_to_string(path: &Path) -> io::Result<String> {
+    fs::metadata(path)?;
+    let _lock = try_lock_for(path, true, DEFAULT_TIMEOUT)?;
+    fs::read_to_string(path)
+}
+
+/// Read a file's bytes under a shared lock, like `read_to_string`
+pub(crate) fn read(path: &Path) -> io::Result<Vec<u8>> {
+    fs::metadata(path)?;
+    let _lock = try_lock_for(path, true, DEFAULT_TIMEOUT)?;
+    fs::read(path)
+}
+
+fn try_lock_for(path: &Path, shared: bool, timeout: Duration) -> io::Result<LockGuard> {
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(lock_path(path))?;
+
+    let deadline = Instant::now() + timeout;
+    let mut backoff = MIN_BACKOFF;
+    loop {
+        let attempt = if shared { file.try_lock_shared() } else { file.try_lock() };
+        match attempt {
+            Ok(()) => return Ok(LockGuard { file }),
+            Err(TryLockError::WouldBlock) => {}
+            Err(TryLockError::Error(e)) => return Err(e),
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(io::Error::new(
+                ErrorKind::TimedOut,
+                format!("timed out after {} ms waiting for the lock", timeout.as_millis()),
+            ));
+        }
+        thread::sleep(backoff.min(deadline - now));
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+fn lock_path(path: &Path) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(LOCK_SUFFIX);
+    PathBuf::from(name)
+}
+
+/// Lock on a file, held until `release` is called or the handle is collected
+#[napi]
+pub struct FileLock {
+    guard: Option<LockGuard>,
+    path: String,
+    shared: bool,
+}
+
+#[napi]
+impl FileLock {
+    /// Path the lock was taken for
+    #[napi(getter)]
+    pub fn path(&self) -> String {
+        self.path.clone()
+    }
+
+    /// Whether this is a shared (read) lock
+    #[napi(getter)]
+    pub fn shared(&self) -> bool {
+        self.shared
+    }
+
+    /// Whether the lock is still held
+    #[napi(getter)]
+    pub fn held(&self) -> bool {
+        self.guard.is_some()
+    }
+
+    /// Release the lock; returns false if it was already released
+    #[napi]
+    pub fn release(&mut self) -> bool {
+        self.guard.take().is_some()
+    }
+}
+
+/// Take an advisory lock on a file
+///
+/// Waits for other holders to release it, polling with backoff, and fails
+/// with `TIMEOUT` once `timeout_ms` has passed. A timeout of 0 tries once.
+/// Release the returned lock with `release()`; it is also released if the
+/// handle is garbage collected or the process exits.
+///
+/// # Arguments
+/// * `path` - File to lock (`<path>.lock` is created next to it)
+/// * `timeout_ms` - Maximum wait in milliseconds (default 10000)
+/// * `shared` - Take a shared lock for reading instead of an exclusive one
+#[napi]
+pub fn acquire_lock(path: String, timeout_ms: Option<u32>, shared: Option<bool>) -> napi::Result<FileLock> {
+    errors::guard("acquireLock", || {
+        let shared = shared.unwrap_or(false);
+        let timeout = timeout_ms.map_or(DEFAULT_TIMEOUT, |ms| Duration::from_millis(ms as u64));
+        let guard = lock(Path::new(&path), shared, timeout)?;
+        Ok(FileLock {
+            guard: Some(guard),
+            path,
+            shared,
+        })
+    })
+}
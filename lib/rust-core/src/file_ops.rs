@@ -0,0 +1,524 @@
+//! Bulk file operations with a dry-run mode
+//!
+//! Replace-in-files, normalization and bulk copy, move and delete all plan
+//! their changes before touching disk. Every source is read and every
+//! destination checked first, so a bad path fails the call before anything
+//! is changed. With `ExecutionMode.DryRun` the plan is returned as is: the
+//! exact files, destinations, byte counts and text edits that `Execute`
+//! would apply. Agents can show it to the user and then run the same call
+//! again with `Execute`.
+//!
+//! Given a `SandboxPolicy`, every source and destination is checked against
+//! it while planning, so a path outside the sandbox fails the call before
+//! anything is changed.
+//!
+//! Executed changes are charged to the file quota and recorded in the audit
+//! log like other native mutations. Changes are applied in order, and a
+//! quota or I/O error while applying stops at that change: the earlier ones
+//! stay applied, and the error lists them. Given a `Journal` transaction id,
+//! each change is journaled before it is made, so a codemod that fails
+//! halfway can be rolled back.
+
+use crate::atomic_write::{self, AtomicWriteOptions};
+use crate::audit::{self, AuditKind};
+use crate::concurrency;
+use crate::errors::{self, ErrorCode};
+use crate::journal::{self, Transaction};
+use crate::kernels;
+use crate::metrics;
+use crate::security_utils::quarantine::move_file;
+use crate::security_utils::quota::{self, Mutation};
+use crate::security_utils::sandbox::{Sandbox, SandboxPolicy};
+use crate::shared;
+use crate::text_format::{apply_edits, FormatConfig, TextEdit, TextFormatter};
+use napi_derive::napi;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// Whether a mutating operation only plans its changes or applies them
+#[napi(string_enum = "snake_case")]
+#[derive(Debug, PartialEq, Eq)]
+pub enum ExecutionMode {
+    /// Return the planned changes without touching disk
+    DryRun,
+    /// Apply the changes
+    Execute,
+}
+
+/// Options for `replaceInFiles`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplaceOptions {
+    /// Treat the pattern as a regex; the replacement may then use `$1` or `${name}`
+    pub regex: bool,
+    /// Match case exactly
+    pub case_sensitive: bool,
+}
+
+impl Default for ReplaceOptions {
+    fn default() -> Self {
+        Self {
+            regex: false,
+            case_sensitive: true,
+        }
+    }
+}
+
+/// A source and destination for a copy or move
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileTransfer {
+    /// File to copy or move
+    pub source: String,
+    /// Path to copy or move it to
+    pub destination: String,
+}
+
+/// A change to one file, made or planned
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChange {
+    /// "write", "copy", "move" or "delete"
+    pub kind: String,
+    /// File that is written, copied, moved or deleted
+    pub path: String,
+    /// Destination of a copy or move
+    pub destination: Option<String>,
+    /// Bytes written to the file or destination (0 for deletes)
+    pub bytes: f64,
+    /// Whether an existing file is replaced
+    pub overwrites: bool,
+    /// Text edits of a write, in byte offsets of the current contents
+    pub edits: Vec<TextEdit>,
+}
+
+/// Result of a bulk operation
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChangeSet {
+    /// Whether the changes were applied (false for a dry run)
+    pub applied: bool,
+    /// Changes in the order they are applied
+    pub changes: Vec<FileChange>,
+    /// Files left alone: unchanged by the operation, or not UTF-8 text
+    pub unchanged: Vec<String>,
+    /// Total bytes written
+    pub bytes_written: f64,
+}
+
+/// A planned change and the new contents of a write
+struct Planned {
+    change: FileChange,
+    contents: Option<String>,
+}
+
+/// Replace a literal string or regex in a set of files
+///
+/// Files that are not valid UTF-8, or in which nothing matches, are listed
+/// in `unchanged`.
+///
+/// # Arguments
+/// * `paths` - Files to edit
+/// * `pattern` - Text or regex to find
+/// * `replacement` - Replacement text
+/// * `options` - Optional regex and case settings
+/// * `mode` - `dry_run` or `execute` (default)
+/// * `transaction` - Journal transaction to record the changes in
+/// * `sandbox` - Sandbox policy every path must satisfy
+#[napi]
+pub fn replace_in_files(
+    paths: Vec<String>,
+    pattern: String,
+    replacement: String,
+    options: Option<ReplaceOptions>,
+    mode: Option<ExecutionMode>,
+    transaction: Option<String>,
+    sandbox: Option<SandboxPolicy>,
+) -> napi::Result<FileChangeSet> {
+    errors::guard("replaceInFiles", || {
+        let _permit = concurrency::acquire("replaceInFiles")?;
+        let options = options.unwrap_or_default();
+        if pattern.is_empty() {
+            return Err(errors::new(ErrorCode::InvalidArgument, "pattern must not be empty".to_string()));
+        }
+        let source = if options.regex { pattern } else { regex::escape(&pattern) };
+        let re = shared::regex(&kernels::regex_source(&source, options.case_sensitive))
+            .map_err(|e| errors::new(ErrorCode::PatternInvalid, format!("Invalid pattern: {}", e)))?;
+
+        let sandbox = sandbox.map(Sandbox::new).transpose()?;
+        rewrite_files("replace_in_files", &paths, sandbox.as_ref(), mode, transaction, |text| {
+            replace_all(&re, text, &replacement, options.regex)
+        })
+    })
+}
+
+/// Normalize line endings and trailing whitespace in a set of files
+///
+/// Uses the same rules as `TextFormatter.normalizeLineEndings`.
+///
+/// # Arguments
+/// * `paths` - Files to normalize
+/// * `config` - Optional target line ending and whitespace settings
+/// * `mode` - `dry_run` or `execute` (default)
+/// * `transaction` - Journal transaction to record the changes in
+/// * `sandbox` - Sandbox policy every path must satisfy
+#[napi]
+pub fn normalize_files(
+    paths: Vec<String>,
+    config: Option<FormatConfig>,
+    mode: Option<ExecutionMode>,
+    transaction: Option<String>,
+    sandbox: Option<SandboxPolicy>,
+) -> napi::Result<FileChangeSet> {
+    errors::guard("normalizeFiles", || {
+        let _permit = concurrency::acquire("normalizeFiles")?;
+        let formatter = TextFormatter::new(config)?;
+        let sandbox = sandbox.map(Sandbox::new).transpose()?;
+        rewrite_files("normalize_files", &paths, sandbox.as_ref(), mode, transaction, |text| {
+            let normalized = formatter.normalize_line_endings(text.to_string())?;
+            Ok((normalized.text, normalized.edits))
+        })
+    })
+}
+
+/// Copy files to new paths
+///
+/// # Arguments
+/// * `transfers` - Sources and destinations
+/// * `overwrite` - Replace existing destinations (default false)
+/// * `mode` - `dry_run` or `execute` (default)
+/// * `transaction` - Journal transaction to record the changes in
+/// * `sandbox` - Sandbox policy every path must satisfy
+#[napi]
+pub fn copy_files(
+    transfers: Vec<FileTransfer>,
+    overwrite: Option<bool>,
+    mode: Option<ExecutionMode>,
+    transaction: Option<String>,
+    sandbox: Option<SandboxPolicy>,
+) -> napi::Result<FileChangeSet> {
+    errors::guard("copyFiles", || {
+        let _permit = concurrency::acquire("copyFiles")?;
+        let sandbox = sandbox.map(Sandbox::new).transpose()?;
+        transfer_files("copy", transfers, overwrite, sandbox.as_ref(), mode, transaction)
+    })
+}
+
+/// Move files to new paths
+///
+/// Moves across filesystems fall back to copy and delete.
+///
+/// # Arguments
+/// * `transfers` - Sources and destinations
+/// * `overwrite` - Replace existing destinations (default false)
+/// * `mode` - `dry_run` or `execute` (default)
+/// * `transaction` - Journal transaction to record the changes in
+/// * `sandbox` - Sandbox policy every path must satisfy
+#[napi]
+pub fn move_files(
+    transfers: Vec<FileTransfer>,
+    overwrite: Option<bool>,
+    mode: Option<ExecutionMode>,
+    transaction: Option<String>,
+    sandbox: Option<SandboxPolicy>,
+) -> napi::Result<FileChangeSet> {
+    errors::guard("moveFiles", || {
+        let _permit = concurrency::acquire("moveFiles")?;
+        let sandbox = sandbox.map(Sandbox::new).transpose()?;
+        transfer_files("move", transfers, overwrite, sandbox.as_ref(), mode, transaction)
+    })
+}
+
+/// Delete files
+///
+/// Symlinks are removed themselves, not their targets. Directories are
+/// rejected.
+///
+/// # Arguments
+/// * `paths` - Files to delete
+/// * `mode` - `dry_run` or `execute` (default)
+/// * `transaction` - Journal transaction to record the changes in
+/// * `sandbox` - Sandbox policy every path must satisfy
+#[napi]
+pub fn delete_files(
+    paths: Vec<String>,
+    mode: Option<ExecutionMode>,
+    transaction: Option<String>,
+    sandbox: Option<SandboxPolicy>,
+) -> napi::Result<FileChangeSet> {
+    errors::guard("deleteFiles", || {
+        let _permit = concurrency::acquire("deleteFiles")?;
+        let sandbox = sandbox.map(Sandbox::new).transpose()?;
+        let mut planned = Vec::with_capacity(paths.len());
+        for path in unique(&paths, "path")? {
+            check_sandbox("delete_files", sandbox.as_ref(), path)?;
+            let metadata = fs::symlink_metadata(path).map_err(|e| io_error("delete", path, e))?;
+            if metadata.is_dir() {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Refusing to delete directory: {}", path),
+                ));
+            }
+            planned.push(Planned {
+                change: change("delete", path, None, 0, false),
+                contents: None,
+            });
+        }
+        apply("delete_files", planned, mode, transaction)
+    })
+}
+
+/// Plan writes of the files whose text `rewrite` changes
+fn rewrite_files(
+    operation: &str,
+    paths: &[String],
+    sandbox: Option<&Sandbox>,
+    mode: Option<ExecutionMode>,
+    transaction: Option<String>,
+    rewrite: impl Fn(&str) -> napi::Result<(String, Vec<TextEdit>)>,
+) -> napi::Result<FileChangeSet> {
+    let mut planned = Vec::new();
+    let mut unchanged = Vec::new();
+    for path in unique(paths, "path")? {
+        check_sandbox(operation, sandbox, path)?;
+        let bytes = fs::read(path).map_err(|e| io_error("read", path, e))?;
+        metrics::add_bytes("file_ops", bytes.len() as u64);
+        let Ok(text) = String::from_utf8(bytes) else {
+            unchanged.push(path.clone());
+            continue;
+        };
+        let (contents, edits) = rewrite(&text)?;
+        if edits.is_empty() {
+            unchanged.push(path.clone());
+            continue;
+        }
+        let mut change = change("write", path, None, contents.len(), true);
+        change.edits = edits;
+        planned.push(Planned {
+            change,
+            contents: Some(contents),
+        });
+    }
+    let mut result = apply(operation, planned, mode, transaction)?;
+    result.unchanged = unchanged;
+    Ok(result)
+}
+
+/// Plan and apply a copy or move of every transfer
+fn transfer_files(
+    kind: &str,
+    transfers: Vec<FileTransfer>,
+    overwrite: Option<bool>,
+    sandbox: Option<&Sandbox>,
+    mode: Option<ExecutionMode>,
+    transaction: Option<String>,
+) -> napi::Result<FileChangeSet> {
+    let operation = if kind == "copy" { "copy_files" } else { "move_files" };
+    let overwrite = overwrite.unwrap_or(false);
+    let destinations: Vec<String> = transfers.iter().map(|t| t.destination.clone()).collect();
+    unique(&destinations, "destination")?;
+
+    let mut planned = Vec::with_capacity(transfers.len());
+    for FileTransfer { source, destination } in &transfers {
+        check_sandbox(operation, sandbox, source)?;
+        check_sandbox(operation, sandbox, destination)?;
+        let metadata = fs::metadata(source).map_err(|e| io_error(kind, source, e))?;
+        if !metadata.is_file() {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Not a regular file: {}", source),
+            ));
+        }
+        let exists = fs::symlink_metadata(destination).is_ok();
+        if exists && !overwrite {
+            return Err(errors::new(
+                ErrorCode::AlreadyExists,
+                format!("Refusing to overwrite existing file: {}", destination),
+            ));
+        }
+        planned.push(Planned {
+            change: change(kind, source, Some(destination), metadata.len() as usize, exists),
+            contents: None,
+        });
+    }
+    apply(operation, planned, mode, transaction)
+}
+
+/// Apply planned changes in order, or return them for a dry run
+///
+/// A failing change ends the call with an error naming the changes applied
+/// before it.
+fn apply(
+    operation: &str,
+    planned: Vec<Planned>,
+    mode: Option<ExecutionMode>,
+    transaction: Option<String>,
+) -> napi::Result<FileChangeSet> {
+    let execute = mode.unwrap_or(ExecutionMode::Execute) == ExecutionMode::Execute;
+    let transaction = transaction.as_deref().map(journal::open).transpose()?;
+    let bytes_written = planned.iter().map(|p| p.change.bytes).sum();
+    if execute {
+        for (applied, Planned { change, contents }) in planned.iter().enumerate() {
+            let result = apply_change(operation, change, contents.as_deref(), transaction.as_deref());
+            match &result {
+                Ok(()) => audit::record(
+                    AuditKind::FileMutation,
+                    operation,
+                    &change.path,
+                    "success",
+                    change.destination.as_deref(),
+                ),
+                Err(e) => audit::record(AuditKind::FileMutation, operation, &change.path, "failure", Some(&e.reason)),
+            }
+            if let Err(e) = result {
+                return Err(partially_applied(e, &planned[..applied], planned.len()));
+            }
+        }
+    }
+    Ok(FileChangeSet {
+        applied: execute,
+        changes: planned.into_iter().map(|p| p.change).collect(),
+        unchanged: Vec::new(),
+        bytes_written,
+    })
+}
+
+/// `error` of a change, extended with the changes applied before it
+fn partially_applied(error: napi::Error, applied: &[Planned], total: usize) -> napi::Error {
+    if applied.is_empty() {
+        return error;
+    }
+    let changes: Vec<String> = applied
+        .iter()
+        .map(|Planned { change, .. }| match &change.destination {
+            Some(destination) => format!("{} {} -> {}", change.kind, change.path, destination),
+            None => format!("{} {}", change.kind, change.path),
+        })
+        .collect();
+    let reason = format!(
+        "{} (applied {} of {} changes before failing: {})",
+        error.reason,
+        applied.len(),
+        total,
+        changes.join(", ")
+    );
+    napi::Error::new(error.status, reason)
+}
+
+/// Apply one change, first recording it in `transaction` if given
+fn apply_change(
+    operation: &str,
+    change: &FileChange,
+    contents: Option<&str>,
+    transaction: Option<&Transaction>,
+) -> napi::Result<()> {
+    let path = Path::new(&change.path);
+    let bytes = change.bytes as u64;
+    match (change.kind.as_str(), change.destination.as_deref()) {
+        ("write", _) => {
+            quota::charge(operation, Mutation::Write, path, bytes)?;
+            if let Some(transaction) = transaction {
+                transaction.before_write(path)?;
+            }
+            let contents = contents.unwrap_or_default().as_bytes();
+            atomic_write::write(path, contents, &AtomicWriteOptions::default())
+                .map_err(|e| io_error("write", &change.path, e))
+        }
+        ("copy", Some(destination)) => {
+            quota::charge(operation, Mutation::Write, Path::new(destination), bytes)?;
+            if let Some(transaction) = transaction {
+                transaction.before_write(Path::new(destination))?;
+            }
+            fs::copy(path, destination)
+                .map(|_| ())
+                .map_err(|e| io_error("copy", &change.path, e))
+        }
+        ("move", Some(destination)) => {
+            quota::charge_all(
+                operation,
+                &[(Mutation::Delete, path, 0), (Mutation::Write, Path::new(destination), bytes)],
+            )?;
+            if let Some(transaction) = transaction {
+                transaction.before_move(path, Path::new(destination))?;
+            }
+            move_file(path, Path::new(destination)).map_err(|e| io_error("move", &change.path, e))
+        }
+        ("delete", _) => {
+            quota::charge(operation, Mutation::Delete, path, 0)?;
+            match transaction {
+                Some(transaction) => transaction.delete(path),
+                None => fs::remove_file(path).map_err(|e| io_error("delete", &change.path, e)),
+            }
+        }
+        (kind, _) => Err(errors::new(ErrorCode::Internal, format!("Unknown change kind: {}", kind))),
+    }
+}
+
+/// Replace every match of `re`, returning the new text and one edit per match
+fn replace_all(re: &Regex, text: &str, replacement: &str, expand: bool) -> napi::Result<(String, Vec<TextEdit>)> {
+    let mut edits = Vec::new();
+    let mut line = 1u32;
+    let mut counted = 0usize;
+    for captures in re.captures_iter(text) {
+        let found = captures.get(0).expect("capture group 0 is the whole match");
+        if found.is_empty() {
+            continue;
+        }
+        line += text[counted..found.start()].matches('\n').count() as u32;
+        counted = found.start();
+        let replaced = if expand {
+            let mut expanded = String::new();
+            captures.expand(replacement, &mut expanded);
+            expanded
+        } else {
+            replacement.to_string()
+        };
+        edits.push(TextEdit {
+            start: found.start() as u32,
+            end: found.end() as u32,
+            replacement: replaced,
+            line,
+        });
+    }
+    Ok((apply_edits(text, &edits), edits))
+}
+
+/// Fail if `sandbox` denies access to `path`, recording the denial
+fn check_sandbox(operation: &str, sandbox: Option<&Sandbox>, path: &str) -> napi::Result<()> {
+    let Some(sandbox) = sandbox else {
+        return Ok(());
+    };
+    sandbox.check_access(Path::new(path)).inspect_err(|e| {
+        audit::record(AuditKind::AccessDenied, operation, path, "denied", Some(&e.reason));
+    })
+}
+
+/// Reject repeated paths, which would make later changes see stale plans
+fn unique<'a>(paths: &'a [String], what: &str) -> napi::Result<&'a [String]> {
+    let mut seen = HashSet::new();
+    match paths.iter().find(|path| !seen.insert(path.as_str())) {
+        Some(path) => Err(errors::new(
+            ErrorCode::InvalidArgument,
+            format!("Duplicate {}: {}", what, path),
+        )),
+        None => Ok(paths),
+    }
+}
+
+fn change(kind: &str, path: &str, destination: Option<&str>, bytes: usize, overwrites: bool) -> FileChange {
+    FileChange {
+        kind: kind.to_string(),
+        path: path.to_string(),
+        destination: destination.map(str::to_string),
+        bytes: bytes as f64,
+        overwrites,
+        edits: Vec::new(),
+    }
+}
+
+fn io_error(action: &str, path: &str, e: std::io::Error) -> napi::Error {
+    errors::io(&e, format!("Failed to {} {}: {}", action, path, e))
+}
@@ -3,15 +3,74 @@
 //! This module provides fast file search, content scanning, and duplicate detection
 //! that outperforms traditional JavaScript implementations by 5-20x.
 
+use crate::audit::{self, AuditKind};
+use crate::compact::{self, CompactFileList, CompactTextResults};
+use crate::concurrency;
+use crate::errors::{self, ErrorCode};
+use crate::languages;
+use crate::metrics;
+use crate::priority;
+use crate::result_cache::{self, Stamp, Validation};
+use crate::search_cursor::{self, Cursor};
+use crate::security_utils::sandbox::{Sandbox, SandboxPolicy};
+use crate::security_utils::secure_delete::filesystem_type;
+use crate::shared;
+use crate::skip_report::{SkipLog, SkipReason, SkipReport};
+use crate::tokens::{self, TokenClass};
+use crate::walk::{Truncation, Walk, WalkError};
 use napi_derive::napi;
-use globset::{Glob, GlobSet, GlobSetBuilder};
+use globset::{Glob, GlobMatcher, GlobSet};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
-use std::time::UNIX_EPOCH;
+use std::sync::Arc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use unicode_normalization::{is_nfc, UnicodeNormalization};
 use walkdir::{DirEntry, WalkDir};
+use xxhash_rust::xxh3;
+
+/// Filesystem type prefixes of network and FUSE mounts (nfs4, fuse.sshfs,
+/// macOS smbfs and macfuse, ...)
+const NETWORK_FILESYSTEMS: [&str; 18] = [
+    "nfs", "cifs", "smb", "9p", "afs", "afpfs", "ceph", "glusterfs", "lustre", "gpfs", "beegfs", "davfs",
+    "webdav", "fuse", "macfuse", "osxfuse", "sshfs", "ncpfs",
+];
+
+/// FUSE types that are local block-device filesystems (ntfs-3g, exfat-fuse)
+const LOCAL_FUSE_FILESYSTEMS: [&str; 1] = ["fuseblk"];
+
+/// Symlinks a walk may follow to reach an entry unless configured
+const DEFAULT_MAX_SYMLINK_DEPTH: u32 = 16;
+
+/// Narrowest snippet, leaving room for the ellipses and some context
+const MIN_SNIPPET_WIDTH: u32 = 8;
+
+/// Smallest file, and the average line length it must exceed, for a text
+/// search to treat it as minified
+const MINIFIED_MIN_BYTES: usize = 1024;
+const MINIFIED_LINE_AVERAGE: usize = 200;
+
+/// Files read per batch in a paged text search
+const PAGED_SEARCH_BATCH: usize = 32;
+
+/// Default and allowed read sizes when hashing files for duplicate detection
+const DEFAULT_HASH_BUFFER: u32 = 64 * 1024;
+const MIN_HASH_BUFFER: u32 = 4 * 1024;
+const MAX_HASH_BUFFER: u32 = 64 * 1024 * 1024;
+
+/// Modification-time ranges of the `getDirectoryStats` timeline, with their
+/// upper bound in seconds of age
+const TIMELINE_RANGES: [(&str, Option<u64>); 5] = [
+    ("day", Some(24 * 60 * 60)),
+    ("week", Some(7 * 24 * 60 * 60)),
+    ("month", Some(30 * 24 * 60 * 60)),
+    ("year", Some(365 * 24 * 60 * 60)),
+    ("older", None),
+];
 
 /// Configuration for file search operations
 #[napi(object)]
@@ -29,6 +88,59 @@ pub struct FileSearchConfig {
     pub exclude_patterns: Vec<String>,
     /// File size limit in bytes (0 for no limit)
     pub max_file_size: i32,
+    /// Sandbox every traversal root and visited entry must satisfy
+    pub sandbox: Option<SandboxPolicy>,
+    /// Cache directory stats, extension stats and duplicate scans: "mtime"
+    /// revalidates by modification times, "events" keeps results until
+    /// `invalidateCachedResults` (omit or "off" to disable)
+    pub result_cache: Option<String>,
+    /// Return files, matches and duplicate groups sorted by path, compared
+    /// component by component (`a/b` before `a.txt`); matches within a file
+    /// stay in line order
+    pub deterministic: Option<bool>,
+    /// Only include files detected as one of these languages (e.g. "Rust",
+    /// "Python"; case-insensitive). Files the name doesn't classify are
+    /// read for a `#!` line or modeline.
+    pub languages: Option<Vec<String>>,
+    /// "background" runs scans on worker threads with lowered CPU and I/O
+    /// priority; "normal" (default) runs them at full speed
+    pub priority: Option<String>,
+    /// Scan roots on network and FUSE filesystems (NFS, SMB, sshfs, ...)
+    /// sequentially, without mmap, and with mtime-revalidated result caching
+    /// (default true)
+    pub adapt_to_network_fs: Option<bool>,
+    /// Match patterns and excludes against NFC-normalized paths and
+    /// patterns, so names in composed and decomposed form (`é` as one code
+    /// point or `e` plus an accent, as HFS+ stores it) match alike (default
+    /// true on macOS, false elsewhere)
+    pub normalize_unicode: Option<bool>,
+    /// List the entries scans leave out, with the reason for each, in
+    /// paged results, directory stats and duplicate scans (default false)
+    pub report_skipped: Option<bool>,
+    /// With `follow_symlinks`, symlinks that may be followed to reach an
+    /// entry, counting each link of a chain; deeper entries are skipped and
+    /// not descended into (default 16)
+    pub max_symlink_depth: Option<u32>,
+    /// Entries one walk visits before it stops, excluded ones included
+    /// (omit or 0 for unlimited)
+    pub max_entries: Option<u32>,
+    /// Give text search results a `snippet` of at most this many characters
+    /// (at least 8) centered on the match, so minified lines stay printable
+    /// (omit for none)
+    pub snippet_width: Option<u32>,
+    /// Don't match text on lines longer than this many bytes (omit or 0 for
+    /// no limit)
+    pub max_line_length: Option<u32>,
+    /// How text searches treat files that look minified (at least 1 KiB,
+    /// with lines averaging over 200 bytes): "search" (default) searches
+    /// them like other files, "skip" leaves them out, and "last" returns
+    /// their matches after all others
+    pub minified_files: Option<String>,
+    /// Only match text in one class of token, found by a lightweight lexer
+    /// for the file's language: "identifier" (whole identifiers),
+    /// "string" (string literals), "comment", or "code" (outside strings
+    /// and comments). Files in languages without a lexer are skipped
+    pub token_class: Option<String>,
 }
 
 impl Default for FileSearchConfig {
@@ -46,6 +158,129 @@ impl Default for FileSearchConfig {
                 ".vscode".to_string(),
             ],
             max_file_size: 0,
+            sandbox: None,
+            result_cache: None,
+            deterministic: None,
+            languages: None,
+            priority: None,
+            adapt_to_network_fs: None,
+            normalize_unicode: None,
+            report_skipped: None,
+            max_symlink_depth: None,
+            max_entries: None,
+            snippet_width: None,
+            max_line_length: None,
+            minified_files: None,
+            token_class: None,
+        }
+    }
+}
+
+/// Per-call changes to a `FileSearchConfig`
+///
+/// Every `FileSearch` method takes these as its last argument. Set fields
+/// replace the instance's value for that call only; the sandbox and result
+/// cache mode can't be changed per call.
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FileSearchOverrides {
+    /// Maximum depth for directory traversal (-1 for unlimited)
+    pub max_depth: Option<i32>,
+    /// Follow symbolic links
+    pub follow_symlinks: Option<bool>,
+    /// Include hidden files (.dot files)
+    pub include_hidden: Option<bool>,
+    /// Use parallel processing
+    pub use_parallel: Option<bool>,
+    /// Patterns to exclude, replacing the instance's list
+    pub exclude_patterns: Option<Vec<String>>,
+    /// Patterns to exclude in addition to the instance's (or replaced) list
+    pub add_exclude_patterns: Option<Vec<String>>,
+    /// File size limit in bytes (0 for no limit)
+    pub max_file_size: Option<i32>,
+    /// Return results sorted by path
+    pub deterministic: Option<bool>,
+    /// Only include files detected as one of these languages
+    pub languages: Option<Vec<String>>,
+    /// "background" or "normal" scan priority
+    pub priority: Option<String>,
+    /// Adapt scans of network and FUSE filesystems
+    pub adapt_to_network_fs: Option<bool>,
+    /// Match paths regardless of Unicode normalization form
+    pub normalize_unicode: Option<bool>,
+    /// List skipped entries in results
+    pub report_skipped: Option<bool>,
+    /// Symlinks that may be followed to reach an entry
+    pub max_symlink_depth: Option<u32>,
+    /// Entries one walk visits before it stops (0 for unlimited)
+    pub max_entries: Option<u32>,
+    /// Characters of each matching line returned as a snippet
+    pub snippet_width: Option<u32>,
+    /// Longest line searched, in bytes (0 for no limit)
+    pub max_line_length: Option<u32>,
+    /// "search", "skip" or "last" for minified files
+    pub minified_files: Option<String>,
+    /// "identifier", "string", "comment" or "code" to match in
+    pub token_class: Option<String>,
+}
+
+impl FileSearchOverrides {
+    /// Merge these overrides over `config`
+    fn apply(self, config: &mut FileSearchConfig) {
+        if let Some(max_depth) = self.max_depth {
+            config.max_depth = max_depth;
+        }
+        if let Some(follow_symlinks) = self.follow_symlinks {
+            config.follow_symlinks = follow_symlinks;
+        }
+        if let Some(include_hidden) = self.include_hidden {
+            config.include_hidden = include_hidden;
+        }
+        if let Some(use_parallel) = self.use_parallel {
+            config.use_parallel = use_parallel;
+        }
+        if let Some(exclude_patterns) = self.exclude_patterns {
+            config.exclude_patterns = exclude_patterns;
+        }
+        config.exclude_patterns.extend(self.add_exclude_patterns.unwrap_or_default());
+        if let Some(max_file_size) = self.max_file_size {
+            config.max_file_size = max_file_size;
+        }
+        if self.deterministic.is_some() {
+            config.deterministic = self.deterministic;
+        }
+        if self.languages.is_some() {
+            config.languages = self.languages;
+        }
+        if self.priority.is_some() {
+            config.priority = self.priority;
+        }
+        if self.adapt_to_network_fs.is_some() {
+            config.adapt_to_network_fs = self.adapt_to_network_fs;
+        }
+        if self.normalize_unicode.is_some() {
+            config.normalize_unicode = self.normalize_unicode;
+        }
+        if self.report_skipped.is_some() {
+            config.report_skipped = self.report_skipped;
+        }
+        if self.max_symlink_depth.is_some() {
+            config.max_symlink_depth = self.max_symlink_depth;
+        }
+        if self.max_entries.is_some() {
+            config.max_entries = self.max_entries;
+        }
+        if self.snippet_width.is_some() {
+            config.snippet_width = self.snippet_width;
+        }
+        if self.max_line_length.is_some() {
+            config.max_line_length = self.max_line_length;
+        }
+        if self.minified_files.is_some() {
+            config.minified_files = self.minified_files;
+        }
+        if self.token_class.is_some() {
+            config.token_class = self.token_class;
         }
     }
 }
@@ -84,6 +319,60 @@ pub struct TextSearchResult {
     pub line_content: String,
     /// Match text
     pub match_text: String,
+    /// The line cut down around the match (only with `snippet_width`)
+    pub snippet: Option<SearchSnippet>,
+}
+
+/// Part of a matching line around the match, for display
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchSnippet {
+    /// At most `snippet_width` characters of the line, with "…" where it was cut
+    pub text: String,
+    /// Occurrences of the search text within `text`
+    pub highlights: Vec<SnippetHighlight>,
+}
+
+/// An occurrence of the search text within a snippet
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnippetHighlight {
+    /// Start offset in the snippet's text, in UTF-16 code units (JavaScript string indices)
+    pub start: u32,
+    /// End offset in the snippet's text, in UTF-16 code units
+    pub end: u32,
+}
+
+/// A page of files from `findFilesPaged`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilePage {
+    /// Matching files in walk order
+    pub files: Vec<FileInfo>,
+    /// Token for the next page; absent when the walk is complete
+    pub cursor: Option<String>,
+    /// How the filesystem shaped the scan
+    pub strategy: ScanStrategy,
+    /// Entries left out of the scan (only with `report_skipped`)
+    pub skipped: Option<SkipReport>,
+    /// Whether the walk stopped at `max_entries` (set on the last page)
+    pub truncated: bool,
+}
+
+/// A page of matches from `searchTextPaged`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextSearchPage {
+    /// Matches in walk order, in line order within each file
+    pub results: Vec<TextSearchResult>,
+    /// Token for the next page; absent when the walk is complete
+    pub cursor: Option<String>,
+    /// How the filesystem shaped the scan
+    pub strategy: ScanStrategy,
+    /// Entries left out of the scan (only with `report_skipped`)
+    pub skipped: Option<SkipReport>,
+    /// Whether the walk stopped at `max_entries` (set on the last page)
+    pub truncated: bool,
 }
 
 /// Directory statistics
@@ -100,12 +389,178 @@ pub struct DirectoryStats {
     pub largest_file_size: f64,
     /// Average file size
     pub average_file_size: f64,
+    /// Logical size of files and directories, counting hard-linked files once (`du -sb`)
+    pub apparent_size: f64,
+    /// Bytes allocated on disk, counting hard-linked files once (`du -sB1`)
+    pub disk_usage: f64,
+    /// Files with fewer bytes allocated than their length (sparse or compressed)
+    pub sparse_file_count: i32,
+    /// Extra links to files already counted, left out of `apparent_size` and `disk_usage`
+    pub hard_link_count: i32,
+    /// Files by age of last modification (only when requested)
+    pub timeline: Option<Vec<ModifiedTimeBucket>>,
+    /// How the filesystem shaped the scan
+    pub strategy: ScanStrategy,
+    /// Entries left out of the scan (only with `report_skipped`)
+    pub skipped: Option<SkipReport>,
+    /// Whether the walk stopped at `max_entries`, leaving the totals partial
+    pub truncated: bool,
+}
+
+/// Files last modified within one age range
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModifiedTimeBucket {
+    /// "day", "week", "month", "year" or "older"
+    pub label: String,
+    /// Upper bound of the range in seconds before the scan (absent for "older")
+    pub max_age_seconds: Option<f64>,
+    /// Number of files modified in the range
+    pub file_count: i32,
+    /// Total size in bytes of those files
+    pub total_size: f64,
+}
+
+/// Files, bytes and lines of code sharing an extension or language
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FileGroupStats {
+    /// Number of files
+    pub file_count: i32,
+    /// Total size in bytes
+    pub total_size: f64,
+    /// Non-blank lines (binary files count none)
+    pub lines_of_code: f64,
+}
+
+/// How `findDuplicateFilesWithStats` hashes files that share a size
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateScanOptions {
+    /// "blake3" (default) or "xxh3" (128-bit xxHash3: faster, not cryptographic)
+    pub algorithm: String,
+    /// Read size in bytes, from 4 KiB to 64 MiB (ignored with `use_mmap`)
+    pub buffer_size: u32,
+    /// Memory-map each file instead of reading it in chunks
+    pub use_mmap: bool,
+}
+
+impl Default for DuplicateScanOptions {
+    fn default() -> Self {
+        Self {
+            algorithm: "blake3".to_string(),
+            buffer_size: DEFAULT_HASH_BUFFER,
+            use_mmap: false,
+        }
+    }
+}
+
+/// Bytes hashed by a duplicate scan and how fast
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HashThroughput {
+    /// Algorithm used
+    pub algorithm: String,
+    /// Files hashed (only files sharing a size with another are hashed)
+    pub files_hashed: u32,
+    /// Total bytes hashed
+    pub bytes_hashed: f64,
+    /// Wall-clock time spent hashing, in milliseconds
+    pub elapsed_ms: f64,
+    /// Bytes hashed per second of wall-clock time
+    pub bytes_per_second: f64,
+}
+
+/// Duplicate groups and the hashing work behind them
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateScan {
+    /// Paths of identical files, keyed by content hash
+    pub groups: BTreeMap<String, Vec<String>>,
+    /// Hashing throughput
+    pub stats: HashThroughput,
+    /// How the filesystem shaped the scan
+    pub strategy: ScanStrategy,
+    /// Entries left out of the scan (only with `report_skipped`)
+    pub skipped: Option<SkipReport>,
+    /// Whether the walk stopped at `max_entries`, so duplicates may be missing
+    pub truncated: bool,
+}
+
+/// How a scan was adapted to the filesystem holding its root
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanStrategy {
+    /// Filesystem type of the root's mount (e.g. "ext4", "nfs4", "apfs"; Linux and macOS)
+    pub filesystem: Option<String>,
+    /// Whether the root is on a network or FUSE filesystem
+    pub network: bool,
+    /// Whether files are processed in parallel
+    pub parallel: bool,
+    /// Whether files may be memory-mapped
+    pub mmap: bool,
+    /// Result cache mode in effect: "off", "mtime" or "events"
+    pub result_cache: String,
+    /// Changes made because of the filesystem
+    pub adjustments: Vec<String>,
+}
+
+/// Glob pattern validity and its compiled regex
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobPatternInfo {
+    /// The glob pattern
+    pub pattern: String,
+    /// Whether the pattern compiles
+    pub is_valid: bool,
+    /// Anchored regex equivalent (if valid)
+    pub regex: Option<String>,
+    /// Compilation error (if invalid)
+    pub error: Option<String>,
+}
+
+/// Explanation of why a path is or isn't returned by a search
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathMatchExplanation {
+    /// The path that was checked
+    pub path: String,
+    /// Whether the path would be returned
+    pub matched: bool,
+    /// Whether the file name is hidden (starts with '.')
+    pub is_hidden: bool,
+    /// Exclude patterns that match the path
+    pub excluded_by: Vec<String>,
+    /// Whether the file exceeds `max_file_size`
+    pub exceeds_size_limit: bool,
+    /// Whether the search pattern matches (if a pattern was given)
+    pub matches_pattern: Option<bool>,
+    /// Why the sandbox policy denies the path (if it does)
+    pub sandbox_denial: Option<String>,
+    /// Detected language (when a `languages` filter is set)
+    pub language: Option<String>,
+    /// Whether the language filter rejects the path
+    pub excluded_by_language: bool,
+    /// Human-readable reasons for the outcome
+    pub reasons: Vec<String>,
 }
 
 /// File search operations implementation
 #[napi]
+#[derive(Clone)]
 pub struct FileSearch {
     config: FileSearchConfig,
+    sandbox: Option<Sandbox>,
+    /// How cached results are revalidated; `None` when caching is off
+    cache: Option<Validation>,
+    /// Hash of the config, part of every cache key
+    cache_key: String,
+    /// Whether scans run on the low-priority background pool
+    background: bool,
+    /// How text searches treat minified files
+    minified: MinifiedFiles,
+    /// Token class text search matches must fall in
+    token_class: Option<TokenClass>,
 }
 
 #[napi]
@@ -116,8 +571,24 @@ impl FileSearch {
     /// * `config` - Optional configuration for file search operations
     #[napi(constructor)]
     pub fn new(config: Option<FileSearchConfig>) -> napi::Result<Self> {
-        Ok(Self {
-            config: config.unwrap_or_default(),
+        errors::guard("new FileSearch", || {
+            let config = config.unwrap_or_default();
+            let sandbox = config.sandbox.clone().map(Sandbox::new).transpose()?;
+            let cache = Validation::parse(config.result_cache.as_deref())
+                .map_err(|m| errors::new(ErrorCode::InvalidArgument, m))?;
+            let cache_key = config_key(&config);
+            let background = priority::parse(config.priority.as_deref())?;
+            let minified = MinifiedFiles::parse(config.minified_files.as_deref())?;
+            let token_class = TokenClass::parse(config.token_class.as_deref())?;
+            Ok(Self {
+                config,
+                sandbox,
+                cache,
+                cache_key,
+                background,
+                minified,
+                token_class,
+            })
         })
     }
 
@@ -128,74 +599,72 @@ impl FileSearch {
         &self,
         root_path: String,
         pattern: String,
+        overrides: Option<FileSearchOverrides>,
     ) -> napi::Result<Vec<FileInfo>> {
-        let root = Path::new(&root_path);
-        if !root.exists() {
-            return Err(napi::Error::new(
-                napi::Status::InvalidArg,
-                format!("Path does not exist: {}", root_path),
-            ));
-        }
+        errors::guard("FileSearch.findFilesByPattern", || {
+            let search = self.scoped(overrides)?;
+            let _permit = concurrency::acquire("FileSearch.findFilesByPattern")?;
+            search.prioritized(|| {
+                let (search, _) = search.adapt(Path::new(&root_path));
+                let root = Path::new(&root_path);
+                if !root.exists() {
+                    return Err(errors::new(
+                        ErrorCode::PathNotFound,
+                        format!("Path does not exist: {}", root_path),
+                    ));
+                }
+                search.check_sandbox_root(root)?;
 
-        // Build glob matcher
-        let glob = Glob::new(&pattern).map_err(|e| {
-            napi::Error::new(napi::Status::InvalidArg, format!("Invalid pattern: {}", e))
-        })?;
-        let matcher = glob.compile_matcher();
+                // Build glob matcher
+                let matcher = search.build_matcher(&pattern)?;
 
-        // Build exclude patterns
-        let exclude_set = self.build_exclude_set()?;
+                // Build exclude patterns
+                let exclude_set = search.build_exclude_set()?;
 
-        // Configure walker
-        let mut walker = WalkDir::new(root).follow_links(self.config.follow_symlinks);
+                // Configure walker
+                let mut walker = search.walk(root);
+                let truncation = walker.truncation();
         
-        if self.config.max_depth >= 0 {
-            walker = walker.max_depth(self.config.max_depth as usize);
-        }
+                if search.config.max_depth >= 0 {
+                    walker = walker.max_depth(search.config.max_depth as usize);
+                }
 
-        // Collect matching files
-        let entries: Vec<DirEntry> = walker
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| self.should_include_entry(e, &exclude_set))
-            .collect();
+                // Collect matching files
+                let entries: Vec<DirEntry> = walker
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                    .filter(|e| search.should_include_entry(e, &exclude_set))
+                    .collect();
+                search.complete(root, &truncation)?;
 
-        // Process entries in parallel if enabled
-        let results = if self.config.use_parallel && entries.len() > 100 {
-            entries
-                .par_iter()
-                .filter_map(|entry| {
-                    let path = entry.path();
-                    if let Some(path_str) = path.to_str() {
-                        if matcher.is_match(path_str) {
-                            self.create_file_info(entry).ok()
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                })
-                .collect()
-        } else {
-            entries
-                .iter()
-                .filter_map(|entry| {
-                    let path = entry.path();
-                    if let Some(path_str) = path.to_str() {
-                        if matcher.is_match(path_str) {
-                            self.create_file_info(entry).ok()
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                })
-                .collect()
-        };
+                // Process entries in parallel if enabled
+                let results = if search.config.use_parallel && entries.len() > 100 {
+                    entries
+                        .par_iter()
+                        .filter_map(|entry| {
+                            if search.path_matches(&matcher, entry.path()) {
+                                search.create_file_info(entry).ok()
+                            } else {
+                                None
+                            }
+                        })
+                        .collect()
+                } else {
+                    entries
+                        .iter()
+                        .filter_map(|entry| {
+                            if search.path_matches(&matcher, entry.path()) {
+                                search.create_file_info(entry).ok()
+                            } else {
+                                None
+                            }
+                        })
+                        .collect()
+                };
 
-        Ok(results)
+                Ok(search.limit_results(results))
+            })
+        })
     }
 
     /// Search for text content within files
@@ -207,250 +676,1001 @@ impl FileSearch {
         search_text: String,
         file_pattern: Option<String>,
         case_sensitive: Option<bool>,
+        overrides: Option<FileSearchOverrides>,
     ) -> napi::Result<Vec<TextSearchResult>> {
-        let root = Path::new(&root_path);
-        let case_sensitive = case_sensitive.unwrap_or(true);
-        
-        // Build file pattern matcher
-        let file_matcher = if let Some(pattern) = file_pattern {
-            let glob = Glob::new(&pattern).map_err(|e| {
-                napi::Error::new(napi::Status::InvalidArg, format!("Invalid pattern: {}", e))
-            })?;
-            Some(glob.compile_matcher())
-        } else {
-            None
-        };
+        errors::guard("FileSearch.searchTextInFiles", || {
+            let search = self.scoped(overrides)?;
+            let _permit = concurrency::acquire("FileSearch.searchTextInFiles")?;
+            search.prioritized(|| {
+                let (search, _) = search.adapt(Path::new(&root_path));
+                let root = Path::new(&root_path);
+                let case_sensitive = case_sensitive.unwrap_or(true);
+                search.check_sandbox_root(root)?;
 
-        // Build exclude patterns
-        let exclude_set = self.build_exclude_set()?;
+                // Build file pattern matcher
+                let file_matcher = if let Some(pattern) = file_pattern {
+                    Some(search.build_matcher(&pattern)?)
+                } else {
+                    None
+                };
 
-        // Configure walker
-        let walker = WalkDir::new(root)
-            .follow_links(self.config.follow_symlinks)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| self.should_include_entry(e, &exclude_set))
-            .filter(|e| !e.file_type().is_dir());
+                // Build exclude patterns
+                let exclude_set = search.build_exclude_set()?;
 
-        // Collect files to search
-        let files: Vec<_> = walker
-            .filter(|entry| {
-                if let Some(ref matcher) = file_matcher {
-                    entry.path().to_str()
-                        .map(|s| matcher.is_match(s))
-                        .unwrap_or(false)
+                // Configure walker
+                let walker = search.walk(root);
+                let truncation = walker.truncation();
+                let walker = walker
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                    .filter(|e| search.should_include_entry(e, &exclude_set))
+                    .filter(|e| !e.file_type().is_dir());
+
+                // Collect files to search
+                let files: Vec<_> = walker
+                    .filter(|entry| {
+                        if let Some(ref matcher) = file_matcher {
+                            search.path_matches(matcher, entry.path())
+                        } else {
+                            true
+                        }
+                    })
+                    .collect();
+                search.complete(root, &truncation)?;
+
+                // Search files in parallel if enabled
+                let matches: Vec<(Vec<TextSearchResult>, bool)> = if search.config.use_parallel && files.len() > 10 {
+                    files
+                        .par_iter()
+                        .map(|entry| {
+                            search.search_in_file(entry.path(), &search_text, case_sensitive, None)
+                        })
+                        .collect()
                 } else {
-                    true
-                }
-            })
-            .collect();
+                    files
+                        .iter()
+                        .map(|entry| {
+                            search.search_in_file(entry.path(), &search_text, case_sensitive, None)
+                        })
+                        .collect()
+                };
 
-        // Search files in parallel if enabled
-        let results = if self.config.use_parallel && files.len() > 10 {
-            files
-                .par_iter()
-                .flat_map(|entry| {
-                    self.search_in_file(entry.path(), &search_text, case_sensitive)
-                        .unwrap_or_default()
-                })
-                .collect()
-        } else {
-            files
-                .iter()
-                .flat_map(|entry| {
-                    self.search_in_file(entry.path(), &search_text, case_sensitive)
-                        .unwrap_or_default()
-                })
-                .collect()
-        };
+                // Matches in minified files go last, each group keeping its order
+                let (minified, other): (Vec<_>, Vec<_>) = matches.into_iter().partition(|(_, minified)| *minified);
+                let results = other.into_iter().chain(minified).flat_map(|(results, _)| results).collect();
 
-        Ok(results)
+                Ok(search.limit_results(results))
+            })
+        })
     }
 
-    /// Get directory statistics (size, file count, etc.)
+    /// Find files by glob pattern, storing each directory path once
+    ///
+    /// Returns the same files as `findFilesByPattern`, with paths split into
+    /// a table of root-relative directories and per-file names, which cuts
+    /// transfer size and memory for very large results.
     #[napi]
-    pub fn get_directory_stats(&self, path: String) -> napi::Result<DirectoryStats> {
-        let root = Path::new(&path);
-        if !root.exists() {
-            return Err(napi::Error::new(
-                napi::Status::InvalidArg,
-                format!("Path does not exist: {}", path),
-            ));
-        }
+    pub fn find_files_compact(
+        &self,
+        root_path: String,
+        pattern: String,
+        overrides: Option<FileSearchOverrides>,
+    ) -> napi::Result<CompactFileList> {
+        errors::guard("FileSearch.findFilesCompact", || {
+            let files = self.find_files_by_pattern(root_path.clone(), pattern, overrides)?;
+            Ok(compact::files(root_path, files))
+        })
+    }
 
-        let exclude_set = self.build_exclude_set()?;
-        
-        let walker = WalkDir::new(root)
-            .follow_links(self.config.follow_symlinks)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| self.should_include_entry(e, &exclude_set));
+    /// Search for text content within files, storing each file path once
+    ///
+    /// Returns the same matches as `searchTextInFiles`, referring to a table
+    /// of root-relative file paths by index.
+    #[napi]
+    pub fn search_text_compact(
+        &self,
+        root_path: String,
+        search_text: String,
+        file_pattern: Option<String>,
+        case_sensitive: Option<bool>,
+        overrides: Option<FileSearchOverrides>,
+    ) -> napi::Result<CompactTextResults> {
+        errors::guard("FileSearch.searchTextCompact", || {
+            let results = self.search_text_in_files(root_path.clone(), search_text, file_pattern, case_sensitive, overrides)?;
+            Ok(compact::matches(root_path, results))
+        })
+    }
 
-        let mut total_size = 0u64;
-        let mut file_count = 0u32;
-        let mut directory_count = 0u32;
-        let mut largest_file_size = 0u64;
+    /// Find files by glob pattern one page at a time
+    ///
+    /// The first call starts a walk and returns up to `page_size` files and
+    /// a cursor. Passing the cursor back, with the same root and pattern,
+    /// continues the walk where the previous page stopped.
+    ///
+    /// # Arguments
+    /// * `root_path` - Directory to search
+    /// * `pattern` - Glob pattern matched against full paths
+    /// * `page_size` - Maximum files per page
+    /// * `cursor` - Cursor from the previous page (omit to start)
+    /// * `overrides` - Optional per-call config changes (must match across pages)
+    #[napi]
+    pub fn find_files_paged(
+        &self,
+        root_path: String,
+        pattern: String,
+        page_size: u32,
+        cursor: Option<String>,
+        overrides: Option<FileSearchOverrides>,
+    ) -> napi::Result<FilePage> {
+        errors::guard("FileSearch.findFilesPaged", || {
+            let search = self.scoped(overrides)?;
+            let _permit = concurrency::acquire("FileSearch.findFilesPaged")?;
+            search.prioritized(|| {
+                let (search, strategy) = search.adapt(Path::new(&root_path));
+                let matcher = search.build_matcher(&pattern)?;
+                let exclude_set = search.build_exclude_set()?;
+                let fingerprint = format!("files\0{}\0{}\0{}", search.cache_key, root_path, pattern);
+                let mut state = search.resume(&root_path, page_size, cursor.as_deref(), fingerprint)?;
+                let skips = search.skip_log();
 
-        for entry in walker {
-            if entry.file_type().is_dir() {
-                directory_count += 1;
-            } else {
-                file_count += 1;
-                if let Ok(metadata) = entry.metadata() {
-                    let size = metadata.len();
-                    total_size += size;
-                    if size > largest_file_size {
-                        largest_file_size = size;
+                let mut files = Vec::new();
+                let mut limit = page_size as usize;
+                if let Some(remaining) = state.remaining {
+                    limit = limit.min(remaining);
+                }
+                let mut done = false;
+                while files.len() < limit {
+                    let Some(entry) = state.walk.next() else {
+                        done = true;
+                        break;
+                    };
+                    let Some(entry) = search.admit(entry, &exclude_set, skips.as_ref()) else { continue };
+                    if !search.path_matches(&matcher, entry.path()) {
+                        continue;
+                    }
+                    if let Ok(info) = search.create_file_info(&entry) {
+                        files.push(info);
                     }
                 }
-            }
-        }
-
-        let average_file_size = if file_count > 0 {
-            total_size as f64 / file_count as f64
-        } else {
-            0.0
-        };
 
-        Ok(DirectoryStats {
-            total_size: total_size as f64,
-            file_count: file_count as i32,
-            directory_count: directory_count as i32,
-            largest_file_size: largest_file_size as f64,
-            average_file_size,
+                let truncated = done && state.walk.truncated();
+                let cursor = search.suspend(state, files.len(), done)?;
+                Ok(FilePage {
+                    files,
+                    cursor,
+                    strategy,
+                    skipped: skips.map(SkipLog::finish),
+                    truncated,
+                })
+            })
         })
     }
 
-    /// Create a map of file extensions to their counts
+    /// Search for text content within files one page at a time
+    ///
+    /// Works like `searchTextInFiles`, returning up to `page_size` matches
+    /// and a cursor that continues the walk, including the remaining matches
+    /// of a file whose matches span two pages.
+    ///
+    /// # Arguments
+    /// * `root_path` - Directory to search
+    /// * `search_text` - Text to find
+    /// * `page_size` - Maximum matches per page
+    /// * `cursor` - Cursor from the previous page (omit to start)
+    /// * `file_pattern` - Optional glob restricting the files searched
+    /// * `case_sensitive` - Match case exactly (default true)
+    /// * `overrides` - Optional per-call config changes (must match across pages)
     #[napi]
-    pub fn get_file_extension_stats(&self, path: String) -> napi::Result<HashMap<String, i32>> {
-        let root = Path::new(&path);
-        if !root.exists() {
-            return Err(napi::Error::new(
-                napi::Status::InvalidArg,
-                format!("Path does not exist: {}", path),
-            ));
-        }
-
-        let exclude_set = self.build_exclude_set()?;
-        
-        let walker = WalkDir::new(root)
-            .follow_links(self.config.follow_symlinks)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| self.should_include_entry(e, &exclude_set))
-            .filter(|e| !e.file_type().is_dir());
-
-        let mut stats: HashMap<String, i32> = HashMap::new();
+    #[allow(clippy::too_many_arguments)]
+    pub fn search_text_paged(
+        &self,
+        root_path: String,
+        search_text: String,
+        page_size: u32,
+        cursor: Option<String>,
+        file_pattern: Option<String>,
+        case_sensitive: Option<bool>,
+        overrides: Option<FileSearchOverrides>,
+    ) -> napi::Result<TextSearchPage> {
+        errors::guard("FileSearch.searchTextPaged", || {
+            let search = self.scoped(overrides)?;
+            let _permit = concurrency::acquire("FileSearch.searchTextPaged")?;
+            search.prioritized(|| {
+                let (search, strategy) = search.adapt(Path::new(&root_path));
+                let case_sensitive = case_sensitive.unwrap_or(true);
+                let file_matcher = file_pattern
+                    .as_deref()
+                    .map(|pattern| search.build_matcher(pattern))
+                    .transpose()?;
+                let exclude_set = search.build_exclude_set()?;
+                let fingerprint = format!(
+                    "text\0{}\0{}\0{}\0{:?}\0{}",
+                    search.cache_key, root_path, search_text, file_pattern, case_sensitive
+                );
+                let mut state = search.resume(&root_path, page_size, cursor.as_deref(), fingerprint)?;
+                let skips = search.skip_log();
 
-        for entry in walker {
-            if let Some(ext) = entry.path().extension().and_then(|s| s.to_str()) {
-                *stats.entry(ext.to_string()).or_insert(0) += 1;
-            } else {
-                *stats.entry("<no_extension>".to_string()).or_insert(0) += 1;
-            }
-        }
+                let mut limit = page_size as usize;
+                if let Some(remaining) = state.remaining {
+                    limit = limit.min(remaining);
+                }
+                let mut done = false;
+                while state.pending.len() < limit {
+                    // Read files in batches so a parallel search keeps walk order
+                    let batch_size = if search.config.use_parallel { PAGED_SEARCH_BATCH } else { 1 };
+                    let mut batch = Vec::with_capacity(batch_size);
+                    while batch.len() < batch_size {
+                        let Some(entry) = state.walk.next() else {
+                            done = true;
+                            break;
+                        };
+                        let Some(entry) = search.admit(entry, &exclude_set, skips.as_ref()) else { continue };
+                        let wanted = !entry.file_type().is_dir()
+                            && file_matcher.as_ref().is_none_or(|matcher| search.path_matches(matcher, entry.path()));
+                        if wanted {
+                            batch.push(entry.into_path());
+                        }
+                    }
+                    let matches: Vec<(Vec<TextSearchResult>, bool)> = batch
+                        .par_iter()
+                        .map(|path| search.search_in_file(path, &search_text, case_sensitive, skips.as_ref()))
+                        .collect();
+                    for (results, minified) in matches {
+                        if minified {
+                            state.deferred.extend(results);
+                        } else {
+                            state.pending.extend(results);
+                        }
+                    }
+                    if done {
+                        let deferred = std::mem::take(&mut state.deferred);
+                        state.pending.extend(deferred);
+                        break;
+                    }
+                }
 
-        Ok(stats)
+                let take = state.pending.len().min(limit);
+                let results: Vec<TextSearchResult> = state.pending.drain(..take).collect();
+                let done = done && state.pending.is_empty();
+                let truncated = done && state.walk.truncated();
+                let cursor = search.suspend(state, results.len(), done)?;
+                Ok(TextSearchPage {
+                    results,
+                    cursor,
+                    strategy,
+                    skipped: skips.map(SkipLog::finish),
+                    truncated,
+                })
+            })
+        })
     }
 
-    /// Fast duplicate file finder using content hashing
+    /// Get directory statistics (size, file count, etc.)
+    ///
+    /// With `timeline`, files are also bucketed by the age of their last
+    /// modification, in disjoint ranges: the last day, week, 30 days and 365
+    /// days, then older. Files modified in the future count as the last day;
+    /// files without a readable mtime are left out of the buckets. Timeline
+    /// results are not cached, since the ranges move with the clock.
+    ///
+    /// `total_size` sums file lengths as listed. `apparent_size` and
+    /// `disk_usage` count each hard-linked file once and include directories,
+    /// as `du -sb` and `du -sB1` do over the same entries; on-disk usage
+    /// below a file's length marks it sparse (or compressed by the filesystem).
+    ///
+    /// # Arguments
+    /// * `path` - Directory to scan
+    /// * `timeline` - Also bucket files by modification time
+    /// * `overrides` - Optional per-call config changes
     #[napi]
-    pub fn find_duplicate_files(&self, path: String) -> napi::Result<HashMap<String, Vec<String>>> {
-        let root = Path::new(&path);
+    pub fn get_directory_stats(
+        &self,
+        path: String,
+        timeline: Option<bool>,
+        overrides: Option<FileSearchOverrides>,
+    ) -> napi::Result<DirectoryStats> {
+        errors::guard("FileSearch.getDirectoryStats", || {
+            let search = self.scoped(overrides)?;
+            let _permit = concurrency::acquire("FileSearch.getDirectoryStats")?;
+            search.prioritized(|| {
+                let (search, strategy) = search.adapt(Path::new(&path));
+                let root = Path::new(&path);
+                if !root.exists() {
+                    return Err(errors::new(
+                        ErrorCode::PathNotFound,
+                        format!("Path does not exist: {}", path),
+                    ));
+                }
+                search.check_sandbox_root(root)?;
+
+                if timeline.unwrap_or(false) {
+                    search.directory_stats(root, Some(SystemTime::now()), strategy)
+                } else {
+                    search.cached_where(
+                        "getDirectoryStats",
+                        root,
+                        || search.directory_stats(root, None, strategy),
+                        |stats| !stats.truncated,
+                    )
+                }
+            })
+        })
+    }
+
+    /// Create a map of file extensions to their counts
+    ///
+    /// @deprecated Use `getFileTypeStats`, whose `fileCount` per extension
+    /// counts the same readable files alongside their bytes and lines of
+    /// code, and which can also group by language.
+    #[napi]
+    pub fn get_file_extension_stats(&self, path: String, overrides: Option<FileSearchOverrides>) -> napi::Result<BTreeMap<String, i32>> {
+        errors::guard("FileSearch.getFileExtensionStats", || {
+            let search = self.scoped(overrides)?;
+            let _permit = concurrency::acquire("FileSearch.getFileExtensionStats")?;
+            search.prioritized(|| {
+                let (search, _) = search.adapt(Path::new(&path));
+                let root = Path::new(&path);
+                if !root.exists() {
+                    return Err(errors::new(
+                        ErrorCode::PathNotFound,
+                        format!("Path does not exist: {}", path),
+                    ));
+                }
+                search.check_sandbox_root(root)?;
+
+                search.cached("getFileExtensionStats", root, || {
+                    let exclude_set = search.build_exclude_set()?;
+
+                    let walker = search.walk(root);
+                    let truncation = walker.truncation();
+                    let walker = walker
+                        .into_iter()
+                        .filter_map(|e| e.ok())
+                        .filter(|e| search.should_include_entry(e, &exclude_set))
+                        .filter(|e| !e.file_type().is_dir());
+
+                    let mut stats: BTreeMap<String, i32> = BTreeMap::new();
+
+                    for entry in walker {
+                        if let Some(ext) = entry.path().extension().and_then(|s| s.to_str()) {
+                            *stats.entry(ext.to_string()).or_insert(0) += 1;
+                        } else {
+                            *stats.entry("<no_extension>".to_string()).or_insert(0) += 1;
+                        }
+                    }
+                    search.complete(root, &truncation)?;
+
+                    Ok(stats)
+                })
+            })
+        })
+    }
+
+    /// Count files, bytes and lines of code per extension or detected language
+    ///
+    /// Supersedes `getFileExtensionStats`, whose keys are used by extension.
+    /// By language, files are classified by name, extension, then `#!`
+    /// interpreter line, and unrecognised files are grouped under
+    /// "<unknown>". Every file is read once to count its lines.
+    ///
+    /// # Arguments
+    /// * `path` - Directory to scan
+    /// * `by_language` - Group by detected language instead of extension
+    /// * `overrides` - Optional per-call config changes
+    #[napi]
+    pub fn get_file_type_stats(
+        &self,
+        path: String,
+        by_language: Option<bool>,
+        overrides: Option<FileSearchOverrides>,
+    ) -> napi::Result<BTreeMap<String, FileGroupStats>> {
+        errors::guard("FileSearch.getFileTypeStats", || {
+            let search = self.scoped(overrides)?;
+            let _permit = concurrency::acquire("FileSearch.getFileTypeStats")?;
+            search.prioritized(|| {
+                let (search, _) = search.adapt(Path::new(&path));
+                let root = Path::new(&path);
+                if !root.exists() {
+                    return Err(errors::new(
+                        ErrorCode::PathNotFound,
+                        format!("Path does not exist: {}", path),
+                    ));
+                }
+                search.check_sandbox_root(root)?;
+
+                let by_language = by_language.unwrap_or(false);
+                let operation = if by_language { "getFileTypeStats:language" } else { "getFileTypeStats:extension" };
+                search.cached(operation, root, || {
+                    let exclude_set = search.build_exclude_set()?;
+
+                    let walker = search.walk(root);
+                    let truncation = walker.truncation();
+                    let files: Vec<PathBuf> = walker
+                        .into_iter()
+                        .filter_map(|e| e.ok())
+                        .filter(|e| search.should_include_entry(e, &exclude_set))
+                        .filter(|e| !e.file_type().is_dir())
+                        .map(|e| e.into_path())
+                        .collect();
+                    search.complete(root, &truncation)?;
+
+                    let group = |path: &PathBuf| {
+                        let profile = languages::profile(path).ok()?;
+                        let key = if by_language {
+                            profile.language.unwrap_or("<unknown>").to_string()
+                        } else {
+                            path.extension()
+                                .and_then(|s| s.to_str())
+                                .unwrap_or("<no_extension>")
+                                .to_string()
+                        };
+                        Some((key, profile))
+                    };
+                    let profiles: Vec<_> = if search.config.use_parallel {
+                        files.par_iter().filter_map(group).collect()
+                    } else {
+                        files.iter().filter_map(group).collect()
+                    };
+
+                    let mut stats: BTreeMap<String, FileGroupStats> = BTreeMap::new();
+                    for (key, profile) in profiles {
+                        let group = stats.entry(key).or_default();
+                        group.file_count += 1;
+                        group.total_size += profile.bytes as f64;
+                        group.lines_of_code += profile.lines_of_code as f64;
+                    }
+
+                    Ok(stats)
+                })
+            })
+        })
+    }
+
+    /// Fast duplicate file finder using content hashing
+    #[napi]
+    pub fn find_duplicate_files(&self, path: String, overrides: Option<FileSearchOverrides>) -> napi::Result<BTreeMap<String, Vec<String>>> {
+        errors::guard("FileSearch.findDuplicateFiles", || {
+            let search = self.scoped(overrides)?;
+            let _permit = concurrency::acquire("FileSearch.findDuplicateFiles")?;
+            search.prioritized(|| {
+                let (search, strategy) = search.adapt(Path::new(&path));
+                let root = search.duplicate_root(&path)?;
+                let hasher = DuplicateHasher::new(&DuplicateScanOptions::default())?;
+                search.cached("findDuplicateFiles", root, || {
+                    let scan = search.scan_duplicates(root, &hasher, strategy)?;
+                    if scan.truncated {
+                        return Err(search.budget_error(root));
+                    }
+                    Ok(scan.groups)
+                })
+            })
+        })
+    }
+
+    /// Duplicate finder with a choice of hash, read size and mmap, reporting throughput
+    ///
+    /// Groups are the same as `findDuplicateFiles` returns, keyed by the
+    /// chosen algorithm's digest. The result cache is not used, so the
+    /// stats always describe files actually read.
+    ///
+    /// # Arguments
+    /// * `path` - Directory to scan
+    /// * `options` - Optional algorithm, buffer size and mmap setting (blake3, 64 KiB reads by default)
+    /// * `overrides` - Optional per-call config changes
+    #[napi]
+    pub fn find_duplicate_files_with_stats(
+        &self,
+        path: String,
+        options: Option<DuplicateScanOptions>,
+        overrides: Option<FileSearchOverrides>,
+    ) -> napi::Result<DuplicateScan> {
+        errors::guard("FileSearch.findDuplicateFilesWithStats", || {
+            let search = self.scoped(overrides)?;
+            let _permit = concurrency::acquire("FileSearch.findDuplicateFilesWithStats")?;
+            search.prioritized(|| {
+                let (search, strategy) = search.adapt(Path::new(&path));
+                let root = search.duplicate_root(&path)?;
+                let mut hasher = DuplicateHasher::new(&options.unwrap_or_default())?;
+                hasher.use_mmap &= strategy.mmap;
+                search.scan_duplicates(root, &hasher, strategy)
+            })
+        })
+    }
+
+    /// Report how scans of `path` would be adapted to its filesystem
+    ///
+    /// # Arguments
+    /// * `path` - Root a scan would start from
+    /// * `overrides` - Optional per-call config changes
+    #[napi]
+    pub fn scan_strategy(&self, path: String, overrides: Option<FileSearchOverrides>) -> napi::Result<ScanStrategy> {
+        errors::guard("FileSearch.scanStrategy", || {
+            let search = self.scoped(overrides)?;
+            Ok(search.adapt(Path::new(&path)).1)
+        })
+    }
+
+    /// Explain why a path would or would not be returned by a search
+    ///
+    /// Applies exactly the hidden-file, exclude-pattern, size-limit, sandbox
+    /// and glob checks used during traversal, reporting each one that affects the path.
+    ///
+    /// # Arguments
+    /// * `path` - Path as it would be produced by traversal (root joined with the relative path)
+    /// * `pattern` - Optional glob pattern passed to the search
+    /// * `overrides` - Optional per-call config changes
+    #[napi]
+    pub fn explain_path_match(&self, path: String, pattern: Option<String>, overrides: Option<FileSearchOverrides>) -> napi::Result<PathMatchExplanation> {
+        errors::guard("FileSearch.explainPathMatch", || {
+            let search = self.scoped(overrides)?;
+            let mut reasons = Vec::new();
+            let target = Path::new(&path);
+
+            let is_hidden = target
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|name| name.starts_with('.') && name != "." && name != "..");
+            let hidden_excluded = is_hidden && !search.config.include_hidden;
+            if hidden_excluded {
+                reasons.push("file name is hidden and include_hidden is false".to_string());
+            }
+
+            let exclude_set = search.build_exclude_set()?;
+            let excluded_by: Vec<String> = exclude_set
+                .matches(search.match_form(&path).as_ref())
+                .into_iter()
+                .map(|i| search.config.exclude_patterns[i].clone())
+                .collect();
+            for exclude in &excluded_by {
+                reasons.push(format!("excluded by pattern '{}'", exclude));
+            }
+
+            let mut exceeds_size_limit = false;
+            if search.config.max_file_size > 0 {
+                if let Ok(metadata) = fs::metadata(target) {
+                    if !metadata.is_dir() && metadata.len() > search.config.max_file_size as u64 {
+                        exceeds_size_limit = true;
+                        reasons.push(format!(
+                            "size {} exceeds max_file_size {}",
+                            metadata.len(),
+                            search.config.max_file_size
+                        ));
+                    }
+                }
+            }
+
+            let sandbox_denial = search.sandbox.as_ref().and_then(|sandbox| {
+                let is_symlink = fs::symlink_metadata(target)
+                    .map(|m| m.file_type().is_symlink())
+                    .unwrap_or(false);
+                sandbox.denial(target, is_symlink, search.follow_links())
+            });
+            if let Some(denial) = &sandbox_denial {
+                reasons.push(format!("sandbox: {}", denial));
+            }
+
+            let mut language = None;
+            let mut excluded_by_language = false;
+            if search.config.languages.is_some() && !target.is_dir() {
+                language = languages::detect(target).ok().flatten();
+                excluded_by_language = !search.language_allowed(language);
+                if excluded_by_language {
+                    reasons.push(format!(
+                        "language {} is not in languages filter",
+                        language.unwrap_or("<unknown>")
+                    ));
+                }
+            }
+
+            let matches_pattern = match &pattern {
+                Some(pattern) => {
+                    let glob = Glob::new(&search.match_form(pattern)).map_err(|e| {
+                        errors::new(ErrorCode::PatternInvalid, format!("Invalid pattern: {}", e))
+                    })?;
+                    let is_match = glob.compile_matcher().is_match(search.match_form(&path).as_ref());
+                    reasons.push(if is_match {
+                        format!("matches pattern '{}' (regex {})", pattern, glob.regex())
+                    } else {
+                        format!("does not match pattern '{}' (regex {})", pattern, glob.regex())
+                    });
+                    Some(is_match)
+                }
+                None => None,
+            };
+
+            let matched = !hidden_excluded
+                && excluded_by.is_empty()
+                && !exceeds_size_limit
+                && sandbox_denial.is_none()
+                && !excluded_by_language
+                && matches_pattern.unwrap_or(true);
+
+            Ok(PathMatchExplanation {
+                path,
+                matched,
+                is_hidden,
+                excluded_by,
+                exceeds_size_limit,
+                matches_pattern,
+                sandbox_denial,
+                language: language.map(str::to_string),
+                excluded_by_language,
+                reasons,
+            })
+        })
+    }
+
+    /// Collect the files a search over `root` would visit
+    ///
+    /// Applies the same depth, symlink, hidden-file, exclude-pattern and size
+    /// rules as the search methods, so other scanners share their semantics.
+    /// Fails with `LIMIT_REACHED` when the walk stops at `max_entries`.
+    pub(crate) fn collect_files(&self, root: &Path) -> napi::Result<Vec<PathBuf>> {
+        let (files, truncation) = self.included_files(root)?;
+        self.complete(root, &truncation)?;
+        Ok(self.limit_results(files))
+    }
+
+    /// `collect_files`, keeping what a walk cut short found
+    ///
+    /// Also returns whether some files may be missing: the walk stopped at
+    /// `max_entries` or the sandbox's `max_results` dropped the rest.
+    pub(crate) fn collect_files_partial(&self, root: &Path) -> napi::Result<(Vec<PathBuf>, bool)> {
+        let (files, truncation) = self.included_files(root)?;
+        let found = files.len();
+        let files = self.limit_results(files);
+        let truncated = truncation.is_set() || files.len() < found;
+        Ok((files, truncated))
+    }
+
+    /// Every file under `root` the filters admit, and whether the walk stopped early
+    fn included_files(&self, root: &Path) -> napi::Result<(Vec<PathBuf>, Truncation)> {
         if !root.exists() {
-            return Err(napi::Error::new(
-                napi::Status::InvalidArg,
-                format!("Path does not exist: {}", path),
+            return Err(errors::new(
+                ErrorCode::PathNotFound,
+                format!("Path does not exist: {}", root.display()),
             ));
         }
+        self.check_sandbox_root(root)?;
 
         let exclude_set = self.build_exclude_set()?;
-        
-        // First, group files by size
-        let mut size_groups: HashMap<u64, Vec<PathBuf>> = HashMap::new();
-        
-        let walker = WalkDir::new(root)
-            .follow_links(self.config.follow_symlinks)
+        let mut walker = self.walk(root);
+        let truncation = walker.truncation();
+        if self.config.max_depth >= 0 {
+            walker = walker.max_depth(self.config.max_depth as usize);
+        }
+
+        let files = walker
             .into_iter()
             .filter_map(|e| e.ok())
             .filter(|e| self.should_include_entry(e, &exclude_set))
-            .filter(|e| !e.file_type().is_dir());
+            .filter(|e| !e.file_type().is_dir())
+            .map(|e| e.into_path())
+            .collect();
+        Ok((files, truncation))
+    }
 
-        for entry in walker {
-            if let Ok(metadata) = entry.metadata() {
-                let size = metadata.len();
-                if size > 0 {  // Skip empty files
-                    size_groups.entry(size).or_insert_with(Vec::new).push(entry.path().to_path_buf());
-                }
-            }
+    /// This search, or a copy with per-call overrides merged over its config
+    fn scoped(&self, overrides: Option<FileSearchOverrides>) -> napi::Result<Cow<'_, Self>> {
+        let Some(overrides) = overrides else { return Ok(Cow::Borrowed(self)) };
+        let mut config = self.config.clone();
+        overrides.apply(&mut config);
+        if config.exclude_patterns != self.config.exclude_patterns {
+            shared::glob_set(&config.exclude_patterns).map_err(|e| {
+                errors::new(ErrorCode::PatternInvalid, format!("Invalid exclude pattern: {}", e))
+            })?;
         }
+        Ok(Cow::Owned(Self {
+            cache_key: config_key(&config),
+            background: priority::parse(config.priority.as_deref())?,
+            minified: MinifiedFiles::parse(config.minified_files.as_deref())?,
+            token_class: TokenClass::parse(config.token_class.as_deref())?,
+            config,
+            sandbox: self.sandbox.clone(),
+            cache: self.cache,
+        }))
+    }
 
-        // Only hash files that have the same size
-        let mut hash_groups: HashMap<String, Vec<String>> = HashMap::new();
+    /// Run a scan on the background pool when `priority` is "background"
+    fn prioritized<T: Send>(&self, scan: impl FnOnce() -> napi::Result<T> + Send) -> napi::Result<T> {
+        priority::run(self.background, scan)
+    }
 
-        for (_, paths) in size_groups.iter().filter(|(_, paths)| paths.len() > 1) {
-            let hashes: Vec<_> = if self.config.use_parallel {
-                paths.par_iter()
-                    .filter_map(|path| {
-                        self.hash_file(path).ok().map(|hash| (hash, path.to_string_lossy().to_string()))
-                    })
-                    .collect()
-            } else {
-                paths.iter()
-                    .filter_map(|path| {
-                        self.hash_file(path).ok().map(|hash| (hash, path.to_string_lossy().to_string()))
-                    })
-                    .collect()
-            };
+    /// Adjust a scan to the filesystem holding `root`
+    ///
+    /// Per-file round trips dominate on network and FUSE filesystems:
+    /// parallel reads queue on the same server, a mapped page fault can
+    /// stall (or kill the process if the share drops), and every rescan
+    /// costs far more than revalidating by mtimes. Such roots are scanned
+    /// sequentially, without mmap, and cached under "mtime" when the result
+    /// cache is off, unless `adapt_to_network_fs` is false.
+    fn adapt(&self, root: &Path) -> (Cow<'_, Self>, ScanStrategy) {
+        let filesystem = fs::canonicalize(root).ok().and_then(|real| filesystem_type(&real));
+        let network = filesystem
+            .as_deref()
+            .is_some_and(|fs_type| {
+                NETWORK_FILESYSTEMS.iter().any(|n| fs_type.starts_with(n))
+                    && !LOCAL_FUSE_FILESYSTEMS.contains(&fs_type)
+            });
+        let mut strategy = ScanStrategy {
+            filesystem,
+            network,
+            parallel: self.config.use_parallel,
+            mmap: true,
+            result_cache: cache_mode(self.cache).to_string(),
+            adjustments: Vec::new(),
+        };
+        if !network || self.config.adapt_to_network_fs == Some(false) {
+            return (Cow::Borrowed(self), strategy);
+        }
+
+        let mut adapted = self.clone();
+        if adapted.config.use_parallel {
+            adapted.config.use_parallel = false;
+            strategy.parallel = false;
+            strategy.adjustments.push("parallel file processing disabled".to_string());
+        }
+        strategy.mmap = false;
+        strategy.adjustments.push("memory-mapped reads disabled".to_string());
+        if adapted.cache.is_none() {
+            adapted.cache = Some(Validation::Mtime);
+            strategy.result_cache = cache_mode(adapted.cache).to_string();
+            strategy.adjustments.push("results cached and revalidated by modification times".to_string());
+        }
+        (Cow::Owned(adapted), strategy)
+    }
 
-            for (hash, path) in hashes {
-                hash_groups.entry(hash).or_insert_with(Vec::new).push(path);
+    /// Resume a paged search from `cursor`, or start a new walk over `root_path`
+    fn resume(
+        &self,
+        root_path: &str,
+        page_size: u32,
+        cursor: Option<&str>,
+        fingerprint: String,
+    ) -> napi::Result<Cursor> {
+        if page_size == 0 {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                "page_size must be greater than zero".to_string(),
+            ));
+        }
+        if let Some(token) = cursor {
+            return search_cursor::take(token, &fingerprint);
+        }
+
+        let root = Path::new(root_path);
+        if !root.exists() {
+            return Err(errors::new(
+                ErrorCode::PathNotFound,
+                format!("Path does not exist: {}", root_path),
+            ));
+        }
+        self.check_sandbox_root(root)?;
+        let mut walker = self.walk(root);
+        if self.config.max_depth >= 0 {
+            walker = walker.max_depth(self.config.max_depth as usize);
+        }
+        let remaining = self.sandbox.as_ref().and_then(|s| s.max_results());
+        Ok(Cursor::new(walker.into_iter(), fingerprint, remaining))
+    }
+
+    /// Park a paged search after a page of `returned` results
+    ///
+    /// Returns the cursor for the next page, or `None` when the walk is
+    /// complete or the sandbox's `max_results` has been reached.
+    fn suspend(&self, mut state: Cursor, returned: usize, done: bool) -> napi::Result<Option<String>> {
+        if let Some(remaining) = &mut state.remaining {
+            *remaining = remaining.saturating_sub(returned);
+            if *remaining == 0 {
+                return Ok(None);
             }
         }
+        if done {
+            return Ok(None);
+        }
+        search_cursor::park(state).map(Some)
+    }
 
-        // Filter out unique files
-        let duplicates: HashMap<String, Vec<String>> = hash_groups
-            .into_iter()
-            .filter(|(_, paths)| paths.len() > 1)
-            .collect();
+    /// Run `compute`, or return its cached result when `result_cache` is on
+    fn cached<T: Clone + Send + Sync + 'static>(
+        &self,
+        operation: &str,
+        root: &Path,
+        compute: impl FnOnce() -> napi::Result<T>,
+    ) -> napi::Result<T> {
+        self.cached_where(operation, root, compute, |_| true)
+    }
 
-        Ok(duplicates)
+    /// `cached`, storing only results `keep` accepts
+    fn cached_where<T: Clone + Send + Sync + 'static>(
+        &self,
+        operation: &str,
+        root: &Path,
+        compute: impl FnOnce() -> napi::Result<T>,
+        keep: impl FnOnce(&T) -> bool,
+    ) -> napi::Result<T> {
+        let Some(validation) = self.cache else { return compute() };
+        result_cache::get_or_compute(operation, root, &self.cache_key, validation, || self.tree_stamps(root), compute, keep)
     }
 
-    /// Build exclude pattern set
-    fn build_exclude_set(&self) -> napi::Result<GlobSet> {
-        let mut builder = GlobSetBuilder::new();
-        
-        for pattern in &self.config.exclude_patterns {
-            let glob = Glob::new(pattern).map_err(|e| {
-                napi::Error::new(napi::Status::InvalidArg, format!("Invalid exclude pattern: {}", e))
-            })?;
-            builder.add(glob);
+    /// Fail when a walk stopped at `max_entries`, so partial totals aren't returned
+    fn complete(&self, root: &Path, truncation: &Truncation) -> napi::Result<()> {
+        if truncation.is_set() {
+            return Err(self.budget_error(root));
         }
+        Ok(())
+    }
 
-        builder.build().map_err(|e| {
-            napi::Error::new(napi::Status::GenericFailure, format!("Failed to build glob set: {}", e))
+    /// Error for a walk of `root` that stopped at `max_entries`
+    fn budget_error(&self, root: &Path) -> napi::Error {
+        errors::new(
+            ErrorCode::LimitReached,
+            format!(
+                "Walk of {} stopped after max_entries {} before visiting every entry",
+                root.display(),
+                self.config.max_entries.unwrap_or(0)
+            ),
+        )
+    }
+
+    /// Stamps of every directory and included file a statistics walk visits
+    ///
+    /// Excluded directories are still descended into, so their mtimes are
+    /// needed to notice files added below them.
+    fn tree_stamps(&self, root: &Path) -> Vec<Stamp> {
+        let Ok(exclude_set) = self.build_exclude_set() else { return Vec::new() };
+        self.walk(root)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_dir() || self.should_include_entry(e, &exclude_set))
+            .filter_map(|e| Stamp::of(e.path()))
+            .collect()
+    }
+
+    /// Walker over `root` with the symlink, budget and ordering settings applied
+    ///
+    /// With `deterministic`, each directory is read in file-name order; the
+    /// depth-first walk then visits paths in sorted order, and the parallel
+    /// per-file work that follows keeps that order.
+    fn walk(&self, root: &Path) -> Walk {
+        let mut walker = WalkDir::new(root);
+        if self.config.deterministic.unwrap_or(false) {
+            walker = walker.sort_by_file_name();
+        }
+        Walk::new(
+            walker,
+            self.follow_links(),
+            self.config.max_symlink_depth.unwrap_or(DEFAULT_MAX_SYMLINK_DEPTH),
+            self.config.max_entries.filter(|&max| max > 0),
+        )
+    }
+
+    /// Whether traversal follows symlinks under the config and sandbox
+    fn follow_links(&self) -> bool {
+        self.config.follow_symlinks && self.sandbox.as_ref().is_none_or(|s| s.follows_symlinks())
+    }
+
+    /// Reject traversal roots the sandbox does not allow
+    fn check_sandbox_root(&self, root: &Path) -> napi::Result<()> {
+        let Some(sandbox) = &self.sandbox else { return Ok(()) };
+        sandbox.check_root(root).inspect_err(|e| {
+            audit::record(
+                AuditKind::AccessDenied,
+                "file_search",
+                &root.to_string_lossy(),
+                "denied",
+                Some(&e.reason),
+            );
         })
     }
 
+    /// Truncate results to the sandbox's `max_results`
+    fn limit_results<T>(&self, mut results: Vec<T>) -> Vec<T> {
+        if let Some(max) = self.sandbox.as_ref().and_then(|s| s.max_results()) {
+            results.truncate(max);
+        }
+        results
+    }
+
+    /// Build exclude pattern set
+    fn build_exclude_set(&self) -> napi::Result<Arc<GlobSet>> {
+        let set = if self.normalizes_unicode() {
+            let patterns: Vec<String> = self
+                .config
+                .exclude_patterns
+                .iter()
+                .map(|pattern| self.match_form(pattern).into_owned())
+                .collect();
+            shared::glob_set(&patterns)
+        } else {
+            shared::glob_set(&self.config.exclude_patterns)
+        };
+        set.map_err(|e| errors::new(ErrorCode::PatternInvalid, format!("Invalid exclude pattern: {}", e)))
+    }
+
+    /// Compile a search pattern, normalized like the paths it is matched against
+    fn build_matcher(&self, pattern: &str) -> napi::Result<Arc<GlobMatcher>> {
+        shared::glob_matcher(&self.match_form(pattern))
+            .map_err(|e| errors::new(ErrorCode::PatternInvalid, format!("Invalid pattern: {}", e)))
+    }
+
+    /// Whether `path` is valid UTF-8 and matches `matcher`
+    fn path_matches(&self, matcher: &GlobMatcher, path: &Path) -> bool {
+        path.to_str().is_some_and(|path| matcher.is_match(self.match_form(path).as_ref()))
+    }
+
+    /// Whether paths and patterns are compared in NFC
+    fn normalizes_unicode(&self) -> bool {
+        self.config.normalize_unicode.unwrap_or(cfg!(target_os = "macos"))
+    }
+
+    /// `text` as compared against patterns: NFC if normalization is on
+    fn match_form<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.normalizes_unicode() && !is_nfc(text) {
+            Cow::Owned(text.nfc().collect())
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+
     /// Check if directory entry should be included
     fn should_include_entry(&self, entry: &DirEntry, exclude_set: &GlobSet) -> bool {
+        self.skip_reason(entry, exclude_set).is_none()
+    }
+
+    /// Pass a walked entry through the filters, recording why it was left out in `skips`
+    fn admit(
+        &self,
+        entry: Result<DirEntry, WalkError>,
+        exclude_set: &GlobSet,
+        skips: Option<&SkipLog>,
+    ) -> Option<DirEntry> {
+        let entry = match (entry, skips) {
+            (Ok(entry), _) => entry,
+            (Err(WalkError::Io(e)), Some(skips)) => {
+                skips.walk_error(&e);
+                return None;
+            }
+            (Err(WalkError::SymlinkDepth { path, links }), Some(skips)) => {
+                let detail = format!(
+                    "reached through {} symlinks, over max_symlink_depth {}",
+                    links,
+                    self.config.max_symlink_depth.unwrap_or(DEFAULT_MAX_SYMLINK_DEPTH)
+                );
+                skips.record(&path, SkipReason::SymlinkDepth, Some(detail));
+                return None;
+            }
+            (Err(WalkError::EntryBudget { path, limit }), Some(skips)) => {
+                let detail = format!("walk stopped after max_entries {}", limit);
+                skips.record(&path, SkipReason::EntryBudget, Some(detail));
+                return None;
+            }
+            (Err(_), None) => return None,
+        };
+        let Some(reason) = self.skip_reason(&entry, exclude_set) else { return Some(entry) };
+        if let Some(skips) = skips {
+            skips.record(entry.path(), reason, self.skip_detail(&entry, reason, exclude_set));
+        }
+        None
+    }
+
+    /// The filter that leaves a directory entry out, if any
+    fn skip_reason(&self, entry: &DirEntry, exclude_set: &GlobSet) -> Option<SkipReason> {
         let path = entry.path();
         
         // Check hidden files
         if !self.config.include_hidden {
             if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
                 if name.starts_with('.') && name != "." && name != ".." {
-                    return false;
+                    return Some(SkipReason::Hidden);
                 }
             }
         }
 
         // Check exclude patterns
         if let Some(path_str) = path.to_str() {
-            if exclude_set.is_match(path_str) {
-                return false;
+            if exclude_set.is_match(self.match_form(path_str).as_ref()) {
+                return Some(SkipReason::Excluded);
             }
         }
 
@@ -458,19 +1678,74 @@ impl FileSearch {
         if self.config.max_file_size > 0 && !entry.file_type().is_dir() {
             if let Ok(metadata) = entry.metadata() {
                 if metadata.len() > self.config.max_file_size as u64 {
-                    return false;
+                    return Some(SkipReason::TooLarge);
                 }
             }
         }
 
-        true
+        // Check the sandbox policy
+        if let Some(sandbox) = &self.sandbox {
+            if let Some(denial) = sandbox.denial(path, entry.path_is_symlink(), self.follow_links()) {
+                audit::record(
+                    AuditKind::AccessDenied,
+                    "file_search",
+                    &path.to_string_lossy(),
+                    "denied",
+                    Some(&denial),
+                );
+                return Some(SkipReason::Sandbox);
+            }
+        }
+
+        // Check the language filter, reading the file only if its name doesn't decide
+        if self.config.languages.is_some()
+            && !entry.file_type().is_dir()
+            && !self.language_allowed(languages::detect(path).ok().flatten())
+        {
+            return Some(SkipReason::Language);
+        }
+
+        None
+    }
+
+    /// Specifics of a skip for the report: the pattern, size, denial or language
+    fn skip_detail(&self, entry: &DirEntry, reason: SkipReason, exclude_set: &GlobSet) -> Option<String> {
+        let path = entry.path();
+        match reason {
+            SkipReason::Excluded => {
+                let index = *exclude_set.matches(self.match_form(path.to_str()?).as_ref()).first()?;
+                Some(format!("excluded by pattern '{}'", self.config.exclude_patterns[index]))
+            }
+            SkipReason::TooLarge => entry.metadata().ok().map(|metadata| {
+                format!("size {} exceeds max_file_size {}", metadata.len(), self.config.max_file_size)
+            }),
+            SkipReason::Sandbox => self.sandbox.as_ref()?.denial(path, entry.path_is_symlink(), self.follow_links()),
+            SkipReason::Language => Some(format!(
+                "detected as {}",
+                languages::detect(path).ok().flatten().unwrap_or("<unknown>")
+            )),
+            _ => None,
+        }
+    }
+
+    /// A log for the skips of one scan, if `report_skipped` is on
+    fn skip_log(&self) -> Option<SkipLog> {
+        self.config.report_skipped.unwrap_or(false).then(SkipLog::new)
+    }
+
+    /// Whether a detected language passes the `languages` filter
+    fn language_allowed(&self, language: Option<&str>) -> bool {
+        self.config.languages.as_ref().is_none_or(|allowed| {
+            language.is_some_and(|language| allowed.iter().any(|a| a.eq_ignore_ascii_case(language)))
+        })
     }
 
     /// Create FileInfo from directory entry
     fn create_file_info(&self, entry: &DirEntry) -> napi::Result<FileInfo> {
         let path = entry.path();
         let metadata = entry.metadata().map_err(|e| {
-            napi::Error::new(napi::Status::GenericFailure, format!("Failed to get metadata: {}", e))
+            let code = e.io_error().map_or(ErrorCode::IoError, ErrorCode::from_io);
+            errors::new(code, format!("Failed to get metadata: {}", e))
         })?;
 
         let name = path.file_name()
@@ -501,8 +1776,70 @@ impl FileSearch {
     }
 
     /// Search for text in a single file
-    fn search_in_file(&self, path: &Path, search_text: &str, case_sensitive: bool) -> napi::Result<Vec<TextSearchResult>> {
-        let content = fs::read_to_string(path)?;
+    ///
+    /// Also returns whether the matches should be ranked last because the
+    /// file looks minified and `minified_files` is "last".
+    fn search_in_file(
+        &self,
+        path: &Path,
+        search_text: &str,
+        case_sensitive: bool,
+        skips: Option<&SkipLog>,
+    ) -> (Vec<TextSearchResult>, bool) {
+        let bytes = match concurrency::read_file(path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                if let Some(skips) = skips {
+                    skips.read_error(path, &e);
+                }
+                return (Vec::new(), false);
+            }
+        };
+        let content = match std::str::from_utf8(&bytes) {
+            Ok(content) => content,
+            Err(e) => {
+                if let Some(skips) = skips {
+                    skips.undecodable(path, &bytes, &e);
+                }
+                return (Vec::new(), false);
+            }
+        };
+        metrics::add_bytes("file_search", content.len() as u64);
+        let minified = match self.minified {
+            MinifiedFiles::Search => false,
+            MinifiedFiles::Skip | MinifiedFiles::Last => match minified_line_average(content) {
+                Some(average) if self.minified == MinifiedFiles::Skip => {
+                    if let Some(skips) = skips {
+                        let detail = format!("lines average {} bytes", average);
+                        skips.record(path, SkipReason::Minified, Some(detail));
+                    }
+                    return (Vec::new(), false);
+                }
+                average => average.is_some(),
+            },
+        };
+        let tokens = match self.token_class {
+            Some(_) => {
+                let language = languages::detect_in(path, &bytes);
+                match language.and_then(|language| tokens::lex(language, content)) {
+                    Some(tokens) => Some(tokens),
+                    None => {
+                        if let Some(skips) = skips {
+                            let detail = format!("no lexer for {}", language.unwrap_or("unknown language"));
+                            skips.record(path, SkipReason::Language, Some(detail));
+                        }
+                        return (Vec::new(), false);
+                    }
+                }
+            }
+            None => None,
+        };
+        // Whether the match at `start..end` of `content` is in the wanted token class
+        let admits = |start: usize, end: usize| match (self.token_class, &tokens) {
+            (Some(class), Some(tokens)) => tokens.admits(class, start, end),
+            _ => true,
+        };
+        let max_line_length = self.config.max_line_length.filter(|&max| max > 0).map(|max| max as usize);
         let mut results = Vec::new();
 
         let search_pattern = if case_sensitive {
@@ -512,67 +1849,562 @@ impl FileSearch {
         };
 
         for (line_num, line) in content.lines().enumerate() {
+            if max_line_length.is_some_and(|max| line.len() > max) {
+                continue;
+            }
             let search_line = if case_sensitive {
                 line.to_string()
             } else {
                 line.to_lowercase()
             };
 
-            if let Some(pos) = search_line.find(&search_pattern) {
+            let line_start = line.as_ptr() as usize - content.as_ptr() as usize;
+            // Matches as byte ranges of `line`, not of its lowercased copy
+            let lowered = if case_sensitive { None } else { LoweredOffsets::new(line) };
+            let occurrences = || {
+                search_line.match_indices(&search_pattern).map(|(start, text)| match &lowered {
+                    Some(lowered) => lowered.original(start, start + text.len()),
+                    None => (start, start + text.len()),
+                })
+            };
+            let found = occurrences().find(|&(start, end)| admits(line_start + start, line_start + end));
+            if let Some((pos, end)) = found {
+                let snippet = self.config.snippet_width.map(|width| {
+                    let occurrences: Vec<(usize, usize)> = occurrences()
+                        .filter(|&(start, end)| admits(line_start + start, line_start + end))
+                        .collect();
+                    snippet(line, pos, &occurrences, width.max(MIN_SNIPPET_WIDTH) as usize)
+                });
                 results.push(TextSearchResult {
                     path: path.to_string_lossy().to_string(),
                     line_number: (line_num + 1) as u32,
                     column_start: pos as u32,
-                    column_end: (pos + search_text.len()) as u32,
+                    column_end: end as u32,
                     line_content: line.to_string(),
                     match_text: search_text.to_string(),
+                    snippet,
                 });
             }
         }
 
-        Ok(results)
+        (results, minified)
     }
 
-    /// Hash file content using Blake3
-    fn hash_file(&self, path: &Path) -> napi::Result<String> {
-        use blake3::Hasher;
-        use std::io::Read;
+    /// Totals for `getDirectoryStats`, bucketing modification times relative to `now` if given
+    fn directory_stats(&self, root: &Path, now: Option<SystemTime>, strategy: ScanStrategy) -> napi::Result<DirectoryStats> {
+        let exclude_set = self.build_exclude_set()?;
+        let skips = self.skip_log();
 
-        let mut file = fs::File::open(path)?;
-        let mut hasher = Hasher::new();
-        let mut buffer = [0; 8192];
+        let walker = self.walk(root);
+        let truncation = walker.truncation();
+        let walker = walker
+            .into_iter()
+            .filter_map(|e| self.admit(e, &exclude_set, skips.as_ref()));
 
-        loop {
-            let n = file.read(&mut buffer)?;
-            if n == 0 {
-                break;
+        let mut total_size = 0u64;
+        let mut file_count = 0u32;
+        let mut directory_count = 0u32;
+        let mut largest_file_size = 0u64;
+        let mut apparent_size = 0u64;
+        let mut disk_usage = 0u64;
+        let mut sparse_file_count = 0u32;
+        let mut hard_link_count = 0u32;
+        let mut linked_files: HashSet<(u64, u64)> = HashSet::new();
+        let mut timeline: Option<Vec<ModifiedTimeBucket>> = now.map(|_| {
+            TIMELINE_RANGES
+                .iter()
+                .map(|(label, max_age)| ModifiedTimeBucket {
+                    label: label.to_string(),
+                    max_age_seconds: max_age.map(|age| age as f64),
+                    file_count: 0,
+                    total_size: 0.0,
+                })
+                .collect()
+        });
+
+        for entry in walker {
+            let is_dir = entry.file_type().is_dir();
+            let metadata = entry.metadata();
+            if let (Err(e), Some(skips)) = (&metadata, skips.as_ref()) {
+                skips.walk_error(e);
+            }
+            if let Ok(metadata) = &metadata {
+                match hard_link_id(metadata).filter(|_| !is_dir) {
+                    Some(id) if !linked_files.insert(id) => hard_link_count += 1,
+                    _ => {
+                        let allocated = allocated_size(metadata);
+                        apparent_size += metadata.len();
+                        disk_usage += allocated;
+                        if metadata.is_file() && allocated < metadata.len() {
+                            sparse_file_count += 1;
+                        }
+                    }
+                }
+            }
+
+            if is_dir {
+                directory_count += 1;
+            } else {
+                file_count += 1;
+                if let Ok(metadata) = &metadata {
+                    let size = metadata.len();
+                    total_size += size;
+                    if size > largest_file_size {
+                        largest_file_size = size;
+                    }
+                    if let (Some(buckets), Some(now), Ok(modified)) = (timeline.as_mut(), now, metadata.modified()) {
+                        let age = now.duration_since(modified).map(|d| d.as_secs()).unwrap_or(0);
+                        let index = TIMELINE_RANGES
+                            .iter()
+                            .position(|(_, max_age)| max_age.is_none_or(|max_age| age < max_age))
+                            .unwrap_or(TIMELINE_RANGES.len() - 1);
+                        buckets[index].file_count += 1;
+                        buckets[index].total_size += size as f64;
+                    }
+                }
             }
-            hasher.update(&buffer[..n]);
         }
 
-        Ok(hasher.finalize().to_hex().to_string())
+        let average_file_size = if file_count > 0 {
+            total_size as f64 / file_count as f64
+        } else {
+            0.0
+        };
+
+        Ok(DirectoryStats {
+            total_size: total_size as f64,
+            file_count: file_count as i32,
+            directory_count: directory_count as i32,
+            largest_file_size: largest_file_size as f64,
+            average_file_size,
+            apparent_size: apparent_size as f64,
+            disk_usage: disk_usage as f64,
+            sparse_file_count: sparse_file_count as i32,
+            hard_link_count: hard_link_count as i32,
+            timeline,
+            strategy,
+            skipped: skips.map(SkipLog::finish),
+            truncated: truncation.is_set(),
+        })
+    }
+
+    /// Check a duplicate scan's root exists and is inside the sandbox
+    fn duplicate_root<'a>(&self, path: &'a str) -> napi::Result<&'a Path> {
+        let root = Path::new(path);
+        if !root.exists() {
+            return Err(errors::new(
+                ErrorCode::PathNotFound,
+                format!("Path does not exist: {}", path),
+            ));
+        }
+        self.check_sandbox_root(root)?;
+        Ok(root)
+    }
+
+    /// Group files under `root` by content hash, hashing only files that share a size
+    fn scan_duplicates(&self, root: &Path, hasher: &DuplicateHasher, strategy: ScanStrategy) -> napi::Result<DuplicateScan> {
+        let exclude_set = self.build_exclude_set()?;
+
+        // First, group files by size
+        let mut size_groups: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+        let skips = self.skip_log();
+
+        let walker = self.walk(root);
+        let truncation = walker.truncation();
+        let walker = walker
+            .into_iter()
+            .filter_map(|e| self.admit(e, &exclude_set, skips.as_ref()))
+            .filter(|e| !e.file_type().is_dir());
+
+        for entry in walker {
+            match entry.metadata() {
+                Ok(metadata) => {
+                    let size = metadata.len();
+                    if size > 0 {  // Skip empty files
+                        size_groups.entry(size).or_default().push(entry.path().to_path_buf());
+                    }
+                }
+                Err(e) => {
+                    if let Some(skips) = &skips {
+                        skips.walk_error(&e);
+                    }
+                }
+            }
+        }
+
+        // Only hash files that have the same size
+        let mut hash_groups: HashMap<String, Vec<String>> = HashMap::new();
+        let mut files_hashed = 0u32;
+        let mut bytes_hashed = 0u64;
+        let started = Instant::now();
+        let hash = |path: &PathBuf| match hasher.hash(path) {
+            Ok((hash, bytes)) => Some((hash, path.to_string_lossy().to_string(), bytes)),
+            Err(e) => {
+                if let Some(skips) = &skips {
+                    skips.read_error(path, &e);
+                }
+                None
+            }
+        };
+
+        for (_, paths) in size_groups.iter().filter(|(_, paths)| paths.len() > 1) {
+            let hashes: Vec<_> = if self.config.use_parallel {
+                paths.par_iter().filter_map(hash).collect()
+            } else {
+                paths.iter().filter_map(hash).collect()
+            };
+
+            for (hash, path, bytes) in hashes {
+                files_hashed += 1;
+                bytes_hashed += bytes;
+                hash_groups.entry(hash).or_default().push(path);
+            }
+        }
+        let elapsed = started.elapsed().as_secs_f64();
+
+        // Filter out unique files
+        let groups: BTreeMap<String, Vec<String>> = hash_groups
+            .into_iter()
+            .filter(|(_, paths)| paths.len() > 1)
+            .collect();
+
+        Ok(DuplicateScan {
+            groups,
+            stats: HashThroughput {
+                algorithm: hasher.algorithm.name().to_string(),
+                files_hashed,
+                bytes_hashed: bytes_hashed as f64,
+                elapsed_ms: elapsed * 1000.0,
+                bytes_per_second: if elapsed > 0.0 { bytes_hashed as f64 / elapsed } else { 0.0 },
+            },
+            strategy,
+            skipped: skips.map(SkipLog::finish),
+            truncated: truncation.is_set(),
+        })
+    }
+}
+
+/// How a text search treats files that look minified
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MinifiedFiles {
+    Search,
+    Skip,
+    Last,
+}
+
+impl MinifiedFiles {
+    fn parse(mode: Option<&str>) -> napi::Result<Self> {
+        match mode {
+            None | Some("search") => Ok(Self::Search),
+            Some("skip") => Ok(Self::Skip),
+            Some("last") => Ok(Self::Last),
+            Some(other) => Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Unknown minified_files mode: {} (expected search, skip or last)", other),
+            )),
+        }
+    }
+}
+
+/// Average line length of `content` if it looks minified
+fn minified_line_average(content: &str) -> Option<usize> {
+    if content.len() < MINIFIED_MIN_BYTES {
+        return None;
+    }
+    let average = content.len() / content.lines().count().max(1);
+    (average > MINIFIED_LINE_AVERAGE).then_some(average)
+}
+
+/// Content hash used to group duplicate files
+#[derive(Debug, Clone, Copy)]
+enum DuplicateAlgorithm {
+    Blake3,
+    Xxh3,
+}
+
+impl DuplicateAlgorithm {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Blake3 => "blake3",
+            Self::Xxh3 => "xxh3",
+        }
+    }
+}
+
+/// Validated `DuplicateScanOptions`
+struct DuplicateHasher {
+    algorithm: DuplicateAlgorithm,
+    buffer_size: usize,
+    use_mmap: bool,
+}
+
+impl DuplicateHasher {
+    fn new(options: &DuplicateScanOptions) -> napi::Result<Self> {
+        let algorithm = match options.algorithm.to_ascii_lowercase().as_str() {
+            "blake3" => DuplicateAlgorithm::Blake3,
+            "xxh3" | "xxhash3" => DuplicateAlgorithm::Xxh3,
+            other => {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Unknown duplicate hash algorithm: {} (expected blake3 or xxh3)", other),
+                ))
+            }
+        };
+        if !(MIN_HASH_BUFFER..=MAX_HASH_BUFFER).contains(&options.buffer_size) {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!(
+                    "Buffer size must be between {} and {} bytes, got {}",
+                    MIN_HASH_BUFFER, MAX_HASH_BUFFER, options.buffer_size
+                ),
+            ));
+        }
+        Ok(Self {
+            algorithm,
+            buffer_size: options.buffer_size as usize,
+            use_mmap: options.use_mmap,
+        })
+    }
+
+    /// Hex digest of a file and the number of bytes hashed
+    fn hash(&self, path: &Path) -> std::io::Result<(String, u64)> {
+        let mut blake3 = blake3::Hasher::new();
+        let mut xxh3 = xxh3::Xxh3::new();
+        let mut update = |data: &[u8]| match self.algorithm {
+            DuplicateAlgorithm::Blake3 => {
+                blake3.update(data);
+            }
+            DuplicateAlgorithm::Xxh3 => xxh3.update(data),
+        };
+
+        let mut file = concurrency::open_file(path)?;
+        let mut bytes = 0u64;
+        if self.use_mmap {
+            // SAFETY: the map is read-only and dropped before returning; a file
+            // truncated meanwhile by another process can fault, as with any mmap reader
+            let mmap = unsafe { memmap2::Mmap::map(&*file)? };
+            update(&mmap);
+            bytes = mmap.len() as u64;
+        } else {
+            let mut buffer = vec![0u8; self.buffer_size];
+            loop {
+                let n = file.read(&mut buffer)?;
+                if n == 0 {
+                    break;
+                }
+                update(&buffer[..n]);
+                bytes += n as u64;
+            }
+        }
+        metrics::add_bytes("hashing", bytes);
+
+        let digest = match self.algorithm {
+            DuplicateAlgorithm::Blake3 => blake3.finalize().to_hex().to_string(),
+            DuplicateAlgorithm::Xxh3 => format!("{:032x}", xxh3.digest128()),
+        };
+        Ok((digest, bytes))
     }
 }
 
+/// Bytes a file occupies on disk, in 512-byte blocks as `du` counts them
+#[cfg(unix)]
+fn allocated_size(metadata: &std::fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.blocks() * 512
+}
+
+#[cfg(not(unix))]
+fn allocated_size(metadata: &std::fs::Metadata) -> u64 {
+    metadata.len()
+}
+
+/// Device and inode of a file with more than one hard link
+#[cfg(unix)]
+fn hard_link_id(metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    (metadata.nlink() > 1).then(|| (metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn hard_link_id(_metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Maps byte offsets in a lowercased line back to the original line
+///
+/// Lowercasing can change a character's UTF-8 length ('İ' becomes "i̇"),
+/// so offsets found in the lowercased copy don't index the original.
+struct LoweredOffsets {
+    /// Offset in the lowercased and in the original line of each character
+    /// start, then of the line end
+    starts: Vec<(usize, usize)>,
+}
+
+impl LoweredOffsets {
+    /// `None` for ASCII lines, whose offsets lowercasing keeps
+    fn new(line: &str) -> Option<Self> {
+        if line.is_ascii() {
+            return None;
+        }
+        let mut lowered = 0;
+        let mut starts = Vec::with_capacity(line.len() + 1);
+        for (original, c) in line.char_indices() {
+            starts.push((lowered, original));
+            lowered += c.to_lowercase().map(char::len_utf8).sum::<usize>();
+        }
+        starts.push((lowered, line.len()));
+        Some(Self { starts })
+    }
+
+    /// The original characters covering lowercased bytes `start..end`
+    fn original(&self, start: usize, end: usize) -> (usize, usize) {
+        let first = self.starts.partition_point(|&(lowered, _)| lowered <= start) - 1;
+        let last = self.starts.partition_point(|&(lowered, _)| lowered < end).min(self.starts.len() - 1);
+        (self.starts[first].1, self.starts[last].1)
+    }
+}
+
+/// At most `width` characters of `line` around the match at byte `at`
+///
+/// The window is centered on the first occurrence (or on `at` if there is
+/// none) and keeps as much context as fits; "…" marks each cut end and
+/// counts towards the width. `occurrences` are byte ranges in `line`;
+/// highlights are converted to UTF-16 code units so they index the
+/// snippet as a JavaScript string.
+fn snippet(line: &str, at: usize, occurrences: &[(usize, usize)], width: usize) -> SearchSnippet {
+    let to_highlight = |text: &str, (start, end): (usize, usize)| SnippetHighlight {
+        start: text[..start].encode_utf16().count() as u32,
+        end: text[..end].encode_utf16().count() as u32,
+    };
+    let total = line.chars().count();
+    if total <= width {
+        return SearchSnippet {
+            text: line.to_string(),
+            highlights: occurrences.iter().map(|&range| to_highlight(line, range)).collect(),
+        };
+    }
+
+    // Character positions of the match, then of a window with both ellipses
+    let boundaries: Vec<usize> = line.char_indices().map(|(i, _)| i).chain([line.len()]).collect();
+    let char_at = |byte: usize| boundaries.partition_point(|&b| b < byte);
+    let (match_start, match_end) = match occurrences.first() {
+        Some(&(start, end)) => (char_at(start), char_at(end)),
+        None => (char_at(at), char_at(at)),
+    };
+    let content = width - 2;
+    let slack = content.saturating_sub(match_end - match_start);
+    let mut start = match_start.saturating_sub(slack / 2);
+    let mut end = start + content;
+    if start <= 1 {
+        // Only the end is cut
+        start = 0;
+        end = width - 1;
+    } else if end + 1 >= total {
+        // Only the start is cut
+        end = total;
+        start = total - (width - 1);
+    }
+
+    let (from, to) = (boundaries[start], boundaries[end]);
+    let prefix = if start > 0 { "…" } else { "" };
+    let mut text = format!("{}{}", prefix, &line[from..to]);
+    if end < total {
+        text.push('…');
+    }
+    let highlights = occurrences
+        .iter()
+        .filter(|&&(s, e)| s < to && e > from)
+        .map(|&(s, e)| to_highlight(&text, (s.max(from) - from + prefix.len(), e.min(to) - from + prefix.len())))
+        .collect();
+    SearchSnippet { text, highlights }
+}
+
+/// Name of a result cache mode, as accepted by `result_cache`
+fn cache_mode(cache: Option<Validation>) -> &'static str {
+    match cache {
+        None => "off",
+        Some(Validation::Mtime) => "mtime",
+        Some(Validation::Events) => "events",
+    }
+}
+
+/// Hash of a config, part of every cache key and cursor fingerprint
+fn config_key(config: &FileSearchConfig) -> String {
+    serde_json::to_string(config)
+        .map(|json| blake3::hash(json.as_bytes()).to_hex().to_string())
+        .unwrap_or_default()
+}
+
+/// Convert a glob pattern to the anchored regex used for matching paths
+#[napi]
+pub fn glob_to_regex(pattern: String) -> napi::Result<String> {
+    errors::guard("globToRegex", || {
+        Glob::new(&pattern)
+            .map(|glob| glob.regex().to_string())
+            .map_err(|e| errors::new(ErrorCode::PatternInvalid, format!("Invalid pattern: {}", e)))
+    })
+}
+
+/// Check glob patterns for validity without running a search
+#[napi]
+pub fn validate_glob_patterns(patterns: Vec<String>) -> napi::Result<Vec<GlobPatternInfo>> {
+    errors::catch_panic("validateGlobPatterns", || {
+        patterns
+            .into_iter()
+            .map(|pattern| match Glob::new(&pattern) {
+                Ok(glob) => GlobPatternInfo {
+                    regex: Some(glob.regex().to_string()),
+                    pattern,
+                    is_valid: true,
+                    error: None,
+                },
+                Err(e) => GlobPatternInfo {
+                    pattern,
+                    is_valid: false,
+                    regex: None,
+                    error: Some(e.kind().to_string()),
+                },
+            })
+            .collect()
+    })
+}
+
 /// Standalone function for quick file search
+///
+/// # Arguments
+/// * `root_path` - Directory to search
+/// * `pattern` - Glob pattern matched against full paths
+/// * `config` - Optional search configuration (defaults exclude `node_modules`, `.git` and hidden files)
 #[napi]
 pub fn quick_find_files(
     root_path: String,
     pattern: String,
+    config: Option<FileSearchConfig>,
 ) -> napi::Result<Vec<FileInfo>> {
-    let searcher = FileSearch::new(None)?;
-    searcher.find_files_by_pattern(root_path, pattern)
+    errors::guard("quickFindFiles", || {
+        let searcher = FileSearch::new(config)?;
+        searcher.find_files_by_pattern(root_path, pattern, None)
+    })
 }
 
 /// Standalone function for quick text search
+///
+/// # Arguments
+/// * `root_path` - Directory to search
+/// * `search_text` - Text to find (case-sensitive)
+/// * `file_pattern` - Optional glob restricting the files searched
+/// * `config` - Optional search configuration (defaults exclude `node_modules`, `.git` and hidden files)
 #[napi]
 pub fn quick_search_text(
     root_path: String,
     search_text: String,
     file_pattern: Option<String>,
+    config: Option<FileSearchConfig>,
 ) -> napi::Result<Vec<TextSearchResult>> {
-    let searcher = FileSearch::new(None)?;
-    searcher.search_text_in_files(root_path, search_text, file_pattern, None)
+    errors::guard("quickSearchText", || {
+        let searcher = FileSearch::new(config)?;
+        searcher.search_text_in_files(root_path, search_text, file_pattern, None, None)
+    })
 }
 
 /// Benchmark file search performance
@@ -582,39 +2414,39 @@ pub fn benchmark_file_search(
     pattern: String,
     iterations: u32,
 ) -> napi::Result<HashMap<String, f64>> {
-    use std::time::Instant;
-    
-    let mut results = HashMap::new();
+    errors::guard("benchmarkFileSearch", || {
+        let mut results = HashMap::new();
     
-    // Benchmark parallel search
-    let searcher_parallel = FileSearch::new(Some(FileSearchConfig {
-        use_parallel: true,
-        ..Default::default()
-    }))?;
+        // Benchmark parallel search
+        let searcher_parallel = FileSearch::new(Some(FileSearchConfig {
+            use_parallel: true,
+            ..Default::default()
+        }))?;
     
-    let start = Instant::now();
-    for _ in 0..iterations {
-        let _ = searcher_parallel.find_files_by_pattern(root_path.clone(), pattern.clone())?;
-    }
-    let parallel_time = start.elapsed().as_secs_f64() * 1000.0 / iterations as f64;
-    results.insert("parallel_avg_ms".to_string(), parallel_time);
+        let start = Instant::now();
+        for _ in 0..iterations {
+            let _ = searcher_parallel.find_files_by_pattern(root_path.clone(), pattern.clone(), None)?;
+        }
+        let parallel_time = start.elapsed().as_secs_f64() * 1000.0 / iterations as f64;
+        results.insert("parallel_avg_ms".to_string(), parallel_time);
     
-    // Benchmark sequential search
-    let searcher_sequential = FileSearch::new(Some(FileSearchConfig {
-        use_parallel: false,
-        ..Default::default()
-    }))?;
+        // Benchmark sequential search
+        let searcher_sequential = FileSearch::new(Some(FileSearchConfig {
+            use_parallel: false,
+            ..Default::default()
+        }))?;
     
-    let start = Instant::now();
-    for _ in 0..iterations {
-        let _ = searcher_sequential.find_files_by_pattern(root_path.clone(), pattern.clone())?;
-    }
-    let sequential_time = start.elapsed().as_secs_f64() * 1000.0 / iterations as f64;
-    results.insert("sequential_avg_ms".to_string(), sequential_time);
+        let start = Instant::now();
+        for _ in 0..iterations {
+            let _ = searcher_sequential.find_files_by_pattern(root_path.clone(), pattern.clone(), None)?;
+        }
+        let sequential_time = start.elapsed().as_secs_f64() * 1000.0 / iterations as f64;
+        results.insert("sequential_avg_ms".to_string(), sequential_time);
     
-    // Calculate speedup
-    let speedup = sequential_time / parallel_time;
-    results.insert("speedup_ratio".to_string(), speedup);
+        // Calculate speedup
+        let speedup = sequential_time / parallel_time;
+        results.insert("speedup_ratio".to_string(), speedup);
     
-    Ok(results)
+        Ok(results)
+    })
 }
\ No newline at end of file
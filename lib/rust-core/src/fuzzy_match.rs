@@ -0,0 +1,883 @@
+//! Phonetic encoding and typo-tolerant matching
+//!
+//! This module provides Soundex and Double Metaphone encodings plus a natively
+//! built BK-tree for finding terms within a bounded edit distance, so
+//! user-typed identifiers can be matched against large symbol tables.
+
+use crate::batch::{map_batch, PARALLEL_THRESHOLD};
+use crate::errors;
+use napi_derive::napi;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Maximum length of a Double Metaphone code
+const METAPHONE_LENGTH: usize = 4;
+
+/// Configuration for typo-tolerant lookups
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuzzyMatchConfig {
+    /// Compare terms case-insensitively
+    pub ignore_case: bool,
+    /// Maximum number of results per query (0 for unlimited)
+    pub max_results: u32,
+    /// Use parallel processing for batch queries
+    pub use_parallel: bool,
+}
+
+impl Default for FuzzyMatchConfig {
+    fn default() -> Self {
+        Self {
+            ignore_case: true,
+            max_results: 20,
+            use_parallel: true,
+        }
+    }
+}
+
+/// Term found within the requested edit distance
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuzzyMatch {
+    /// Matched term as it was added
+    pub term: String,
+    /// Levenshtein distance from the query
+    pub distance: u32,
+}
+
+/// Double Metaphone encoding
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhoneticCode {
+    /// Primary encoding
+    pub primary: String,
+    /// Alternate encoding (equal to primary when there is no alternative)
+    pub alternate: String,
+}
+
+/// BK-tree node; terms sharing a normalized key are stored together
+struct BkNode {
+    key: Vec<char>,
+    terms: Vec<String>,
+    children: Vec<(u32, usize)>,
+}
+
+/// BK-tree index for edit-distance lookups
+#[napi]
+pub struct BkTree {
+    config: FuzzyMatchConfig,
+    nodes: Vec<BkNode>,
+    term_count: u32,
+}
+
+#[napi]
+impl BkTree {
+    /// Create an empty BK-tree with optional configuration
+    ///
+    /// # Arguments
+    /// * `config` - Optional configuration for lookups
+    #[napi(constructor)]
+    pub fn new(config: Option<FuzzyMatchConfig>) -> napi::Result<Self> {
+        errors::guard("new BkTree", || {
+            Ok(Self {
+                config: config.unwrap_or_default(),
+                nodes: Vec::new(),
+                term_count: 0,
+            })
+        })
+    }
+
+    /// Add terms to the tree, returning how many were new
+    #[napi]
+    pub fn add(&mut self, terms: Vec<String>) -> napi::Result<u32> {
+        errors::catch_panic("BkTree.add", || {
+            let mut added = 0;
+            let mut scratch = Vec::new();
+
+            for term in terms {
+                let key = self.normalize(&term);
+
+                if self.nodes.is_empty() {
+                    self.nodes.push(BkNode {
+                        key,
+                        terms: vec![term],
+                        children: Vec::new(),
+                    });
+                    added += 1;
+                    continue;
+                }
+
+                let mut current = 0;
+                loop {
+                    let distance = levenshtein(&self.nodes[current].key, &key, &mut scratch);
+                    if distance == 0 {
+                        if !self.nodes[current].terms.contains(&term) {
+                            self.nodes[current].terms.push(term);
+                            added += 1;
+                        }
+                        break;
+                    }
+
+                    match self.nodes[current].children.iter().find(|(d, _)| *d == distance) {
+                        Some(&(_, child)) => current = child,
+                        None => {
+                            let index = self.nodes.len();
+                            self.nodes.push(BkNode {
+                                key,
+                                terms: vec![term],
+                                children: Vec::new(),
+                            });
+                            self.nodes[current].children.push((distance, index));
+                            added += 1;
+                            break;
+                        }
+                    }
+                }
+            }
+
+            self.term_count += added;
+            added
+        })
+    }
+
+    /// Find terms within `max_distance` edits of the query, closest first
+    #[napi]
+    pub fn search(&self, query: String, max_distance: u32) -> napi::Result<Vec<FuzzyMatch>> {
+        errors::catch_panic("BkTree.search", || {
+            self.search_key(&self.normalize(&query), max_distance)
+        })
+    }
+
+    /// Run several queries in one call
+    #[napi]
+    pub fn search_batch(&self, queries: Vec<String>, max_distance: u32) -> napi::Result<Vec<Vec<FuzzyMatch>>> {
+        errors::catch_panic("BkTree.searchBatch", || {
+            if self.config.use_parallel && queries.len() > PARALLEL_THRESHOLD {
+                queries
+                    .par_iter()
+                    .map(|q| self.search_key(&self.normalize(q), max_distance))
+                    .collect()
+            } else {
+                queries
+                    .iter()
+                    .map(|q| self.search_key(&self.normalize(q), max_distance))
+                    .collect()
+            }
+        })
+    }
+
+    /// Number of distinct terms in the tree
+    #[napi]
+    pub fn size(&self) -> u32 {
+        self.term_count
+    }
+
+    /// Normalize a term into its comparison key
+    fn normalize(&self, term: &str) -> Vec<char> {
+        if self.config.ignore_case {
+            term.chars().flat_map(char::to_lowercase).collect()
+        } else {
+            term.chars().collect()
+        }
+    }
+
+    /// Internal search using the triangle inequality to prune subtrees
+    fn search_key(&self, key: &[char], max_distance: u32) -> Vec<FuzzyMatch> {
+        let mut results = Vec::new();
+        if self.nodes.is_empty() {
+            return results;
+        }
+
+        let mut scratch = Vec::new();
+        let mut stack = vec![0];
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            let distance = levenshtein(&node.key, key, &mut scratch);
+
+            if distance <= max_distance {
+                results.extend(node.terms.iter().map(|term| FuzzyMatch {
+                    term: term.clone(),
+                    distance,
+                }));
+            }
+
+            let low = distance.saturating_sub(max_distance);
+            let high = distance + max_distance;
+            stack.extend(
+                node.children
+                    .iter()
+                    .filter(|(d, _)| *d >= low && *d <= high)
+                    .map(|&(_, child)| child),
+            );
+        }
+
+        results.sort_by(|a, b| a.distance.cmp(&b.distance).then_with(|| a.term.cmp(&b.term)));
+        if self.config.max_results > 0 {
+            results.truncate(self.config.max_results as usize);
+        }
+        results
+    }
+}
+
+/// Levenshtein distance over characters, reusing a scratch row
+fn levenshtein(a: &[char], b: &[char], row: &mut Vec<u32>) -> u32 {
+    if a.is_empty() {
+        return b.len() as u32;
+    }
+    if b.is_empty() {
+        return a.len() as u32;
+    }
+
+    row.clear();
+    row.extend(0..=b.len() as u32);
+
+    for (i, &ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i as u32 + 1;
+
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + u32::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// American Soundex code of a word ("" when it has no letters)
+fn soundex_code(word: &str) -> String {
+    fn digit(c: char) -> Option<char> {
+        match c {
+            'B' | 'F' | 'P' | 'V' => Some('1'),
+            'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => Some('2'),
+            'D' | 'T' => Some('3'),
+            'L' => Some('4'),
+            'M' | 'N' => Some('5'),
+            'R' => Some('6'),
+            _ => None,
+        }
+    }
+
+    let mut letters = word
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_uppercase());
+
+    let Some(first) = letters.next() else {
+        return String::new();
+    };
+
+    let mut code = String::with_capacity(4);
+    code.push(first);
+    let mut previous = digit(first);
+
+    for c in letters {
+        let current = digit(c);
+        if let Some(d) = current {
+            if current != previous {
+                code.push(d);
+                if code.len() == 4 {
+                    break;
+                }
+            }
+        }
+        // H and W do not separate letters with the same code; vowels do
+        if c != 'H' && c != 'W' {
+            previous = current;
+        }
+    }
+
+    while code.len() < 4 {
+        code.push('0');
+    }
+    code
+}
+
+/// Double Metaphone encoder state
+struct DoubleMetaphone {
+    word: Vec<char>,
+    length: isize,
+    slavo_germanic: bool,
+    primary: String,
+    alternate: String,
+}
+
+impl DoubleMetaphone {
+    fn new(word: &str) -> Self {
+        let upper = word.to_uppercase();
+        let mut chars: Vec<char> = upper.chars().collect();
+        let length = chars.len() as isize;
+        // Padding lets lookahead past the end see spaces, as in the reference algorithm
+        chars.extend([' '; 5]);
+
+        Self {
+            slavo_germanic: upper.contains('W') || upper.contains('K') || upper.contains("CZ"),
+            word: chars,
+            length,
+            primary: String::new(),
+            alternate: String::new(),
+        }
+    }
+
+    fn at(&self, i: isize) -> char {
+        if i < 0 {
+            '\0'
+        } else {
+            self.word.get(i as usize).copied().unwrap_or(' ')
+        }
+    }
+
+    /// Whether any option occurs at position `start`
+    fn matches(&self, start: isize, options: &[&str]) -> bool {
+        start >= 0
+            && options.iter().any(|option| {
+                let mut i = start;
+                option.chars().all(|c| {
+                    let ok = self.at(i) == c;
+                    i += 1;
+                    ok
+                })
+            })
+    }
+
+    fn is_vowel(&self, i: isize) -> bool {
+        matches!(self.at(i), 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+    }
+
+    fn add(&mut self, code: &str) {
+        self.primary.push_str(code);
+        self.alternate.push_str(code);
+    }
+
+    fn add_both(&mut self, primary: &str, alternate: &str) {
+        self.primary.push_str(primary);
+        self.alternate.push_str(alternate);
+    }
+
+    /// Advance past a letter, skipping a doubled occurrence
+    fn skip_double(&self, current: isize, c: char) -> isize {
+        if self.at(current + 1) == c {
+            current + 2
+        } else {
+            current + 1
+        }
+    }
+
+    fn encode(mut self) -> PhoneticCode {
+        let last = self.length - 1;
+        let germanic_prefix = self.matches(0, &["VAN ", "VON ", "SCH"]);
+        let mut current: isize = 0;
+
+        if self.matches(0, &["GN", "KN", "PN", "WR", "PS"]) {
+            current += 1;
+        }
+        if self.at(0) == 'X' {
+            self.add("S");
+            current += 1;
+        }
+
+        while (self.primary.len() < METAPHONE_LENGTH || self.alternate.len() < METAPHONE_LENGTH)
+            && current < self.length
+        {
+            current = match self.at(current) {
+                'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                    if current == 0 {
+                        self.add("A");
+                    }
+                    current + 1
+                }
+                'B' => {
+                    self.add("P");
+                    self.skip_double(current, 'B')
+                }
+                'Ç' => {
+                    self.add("S");
+                    current + 1
+                }
+                'C' => self.encode_c(current, germanic_prefix),
+                'D' => {
+                    if self.matches(current, &["DG"]) {
+                        if self.matches(current + 2, &["I", "E", "Y"]) {
+                            self.add("J");
+                            current + 3
+                        } else {
+                            self.add("TK");
+                            current + 2
+                        }
+                    } else {
+                        self.add("T");
+                        if self.matches(current, &["DT", "DD"]) {
+                            current + 2
+                        } else {
+                            current + 1
+                        }
+                    }
+                }
+                'F' => {
+                    self.add("F");
+                    self.skip_double(current, 'F')
+                }
+                'G' => self.encode_g(current, germanic_prefix),
+                'H' => {
+                    if (current == 0 || self.is_vowel(current - 1)) && self.is_vowel(current + 1) {
+                        self.add("H");
+                        current + 2
+                    } else {
+                        current + 1
+                    }
+                }
+                'J' => self.encode_j(current, last),
+                'K' => {
+                    self.add("K");
+                    self.skip_double(current, 'K')
+                }
+                'L' => {
+                    if self.at(current + 1) == 'L' {
+                        let spanish = (current == self.length - 3 && self.matches(current - 1, &["ILLO", "ILLA", "ALLE"]))
+                            || ((self.matches(last - 1, &["AS", "OS"]) || self.matches(last, &["A", "O"]))
+                                && self.matches(current - 1, &["ALLE"]));
+                        if spanish {
+                            self.add_both("L", "");
+                        } else {
+                            self.add("L");
+                        }
+                        current + 2
+                    } else {
+                        self.add("L");
+                        current + 1
+                    }
+                }
+                'M' => {
+                    self.add("M");
+                    let silent_b = self.matches(current - 1, &["UMB"])
+                        && (current + 1 == last || self.matches(current + 2, &["ER"]));
+                    if silent_b || self.at(current + 1) == 'M' {
+                        current + 2
+                    } else {
+                        current + 1
+                    }
+                }
+                'N' => {
+                    self.add("N");
+                    self.skip_double(current, 'N')
+                }
+                'Ñ' => {
+                    self.add("N");
+                    current + 1
+                }
+                'P' => {
+                    if self.at(current + 1) == 'H' {
+                        self.add("F");
+                        current + 2
+                    } else {
+                        self.add("P");
+                        if self.matches(current + 1, &["P", "B"]) {
+                            current + 2
+                        } else {
+                            current + 1
+                        }
+                    }
+                }
+                'Q' => {
+                    self.add("K");
+                    self.skip_double(current, 'Q')
+                }
+                'R' => {
+                    if current == last
+                        && !self.slavo_germanic
+                        && self.matches(current - 2, &["IE"])
+                        && !self.matches(current - 4, &["ME", "MA"])
+                    {
+                        self.add_both("", "R");
+                    } else {
+                        self.add("R");
+                    }
+                    self.skip_double(current, 'R')
+                }
+                'S' => self.encode_s(current, last),
+                'T' => {
+                    if self.matches(current, &["TION", "TIA", "TCH"]) {
+                        self.add("X");
+                        current + 3
+                    } else if self.matches(current, &["TH", "TTH"]) {
+                        if self.matches(current + 2, &["OM", "AM"]) || germanic_prefix {
+                            self.add("T");
+                        } else {
+                            self.add_both("0", "T");
+                        }
+                        current + 2
+                    } else {
+                        self.add("T");
+                        if self.matches(current + 1, &["T", "D"]) {
+                            current + 2
+                        } else {
+                            current + 1
+                        }
+                    }
+                }
+                'V' => {
+                    self.add("F");
+                    self.skip_double(current, 'V')
+                }
+                'W' => self.encode_w(current, last),
+                'X' => {
+                    let silent = current == last
+                        && (self.matches(current - 3, &["IAU", "EAU"]) || self.matches(current - 2, &["AU", "OU"]));
+                    if !silent {
+                        self.add("KS");
+                    }
+                    if self.matches(current + 1, &["C", "X"]) {
+                        current + 2
+                    } else {
+                        current + 1
+                    }
+                }
+                'Z' => {
+                    if self.at(current + 1) == 'H' {
+                        self.add("J");
+                        current + 2
+                    } else {
+                        if self.matches(current + 1, &["ZO", "ZI", "ZA"])
+                            || (self.slavo_germanic && current > 0 && self.at(current - 1) != 'T')
+                        {
+                            self.add_both("S", "TS");
+                        } else {
+                            self.add("S");
+                        }
+                        self.skip_double(current, 'Z')
+                    }
+                }
+                _ => current + 1,
+            };
+        }
+
+        self.primary.truncate(METAPHONE_LENGTH);
+        self.alternate.truncate(METAPHONE_LENGTH);
+        PhoneticCode {
+            primary: self.primary,
+            alternate: self.alternate,
+        }
+    }
+
+    fn encode_c(&mut self, current: isize, germanic_prefix: bool) -> isize {
+        // Germanic "ACH" as in "bacher", "macher"
+        if current > 1
+            && !self.is_vowel(current - 2)
+            && self.matches(current - 1, &["ACH"])
+            && self.at(current + 2) != 'I'
+            && (self.at(current + 2) != 'E' || self.matches(current - 2, &["BACHER", "MACHER"]))
+        {
+            self.add("K");
+            return current + 2;
+        }
+
+        if current == 0 && self.matches(current, &["CAESAR"]) {
+            self.add("S");
+            return current + 2;
+        }
+
+        if self.matches(current, &["CHIA"]) {
+            self.add("K");
+            return current + 2;
+        }
+
+        if self.matches(current, &["CH"]) {
+            if current > 0 && self.matches(current, &["CHAE"]) {
+                self.add_both("K", "X");
+            } else if (current == 0
+                && self.matches(current + 1, &["HARAC", "HARIS", "HOR", "HYM", "HIA", "HEM"])
+                && !self.matches(0, &["CHORE"]))
+                || germanic_prefix
+                || self.matches(current - 2, &["ORCHES", "ARCHIT", "ORCHID"])
+                || self.matches(current + 2, &["T", "S"])
+                || ((current == 0 || self.matches(current - 1, &["A", "O", "U", "E"]))
+                    && self.matches(current + 2, &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "]))
+            {
+                self.add("K");
+            } else if current > 0 {
+                if self.matches(0, &["MC"]) {
+                    self.add("K");
+                } else {
+                    self.add_both("X", "K");
+                }
+            } else {
+                self.add("X");
+            }
+            return current + 2;
+        }
+
+        if self.matches(current, &["CZ"]) && !self.matches(current - 2, &["WICZ"]) {
+            self.add_both("S", "X");
+            return current + 2;
+        }
+
+        if self.matches(current + 1, &["CIA"]) {
+            self.add("X");
+            return current + 3;
+        }
+
+        if self.matches(current, &["CC"]) && !(current == 1 && self.at(0) == 'M') {
+            if self.matches(current + 2, &["I", "E", "H"]) && !self.matches(current + 2, &["HU"]) {
+                if (current == 1 && self.at(0) == 'A') || self.matches(current - 1, &["UCCEE", "UCCES"]) {
+                    self.add("KS");
+                } else {
+                    self.add("X");
+                }
+                return current + 3;
+            }
+            self.add("K");
+            return current + 2;
+        }
+
+        if self.matches(current, &["CK", "CG", "CQ"]) {
+            self.add("K");
+            return current + 2;
+        }
+
+        if self.matches(current, &["CI", "CE", "CY"]) {
+            if self.matches(current, &["CIO", "CIE", "CIA"]) {
+                self.add_both("S", "X");
+            } else {
+                self.add("S");
+            }
+            return current + 2;
+        }
+
+        self.add("K");
+        if self.matches(current + 1, &[" C", " Q", " G"]) {
+            current + 3
+        } else if self.matches(current + 1, &["C", "K", "Q"]) && !self.matches(current + 1, &["CE", "CI"]) {
+            current + 2
+        } else {
+            current + 1
+        }
+    }
+
+    fn encode_g(&mut self, current: isize, germanic_prefix: bool) -> isize {
+        if self.at(current + 1) == 'H' {
+            if current > 0 && !self.is_vowel(current - 1) {
+                self.add("K");
+            } else if current == 0 {
+                if self.at(current + 2) == 'I' {
+                    self.add("J");
+                } else {
+                    self.add("K");
+                }
+            } else if (current > 1 && self.matches(current - 2, &["B", "H", "D"]))
+                || (current > 2 && self.matches(current - 3, &["B", "H", "D"]))
+                || (current > 3 && self.matches(current - 4, &["B", "H"]))
+            {
+                // Silent as in "hugh", "bough", "broughton"
+            } else if current > 2
+                && self.at(current - 1) == 'U'
+                && self.matches(current - 3, &["C", "G", "L", "R", "T"])
+            {
+                self.add("F");
+            } else if self.at(current - 1) != 'I' {
+                self.add("K");
+            }
+            return current + 2;
+        }
+
+        if self.at(current + 1) == 'N' {
+            if current == 1 && self.is_vowel(0) && !self.slavo_germanic {
+                self.add_both("KN", "N");
+            } else if !self.matches(current + 2, &["EY"]) && !self.slavo_germanic {
+                self.add_both("N", "KN");
+            } else {
+                self.add("KN");
+            }
+            return current + 2;
+        }
+
+        if self.matches(current + 1, &["LI"]) && !self.slavo_germanic {
+            self.add_both("KL", "L");
+            return current + 2;
+        }
+
+        if current == 0
+            && (self.at(current + 1) == 'Y'
+                || self.matches(current + 1, &["ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER"]))
+        {
+            self.add_both("K", "J");
+            return current + 2;
+        }
+
+        if (self.matches(current + 1, &["ER"]) || self.at(current + 1) == 'Y')
+            && !self.matches(0, &["DANGER", "RANGER", "MANGER"])
+            && !self.matches(current - 1, &["E", "I", "RGY", "OGY"])
+        {
+            self.add_both("K", "J");
+            return current + 2;
+        }
+
+        if self.matches(current + 1, &["E", "I", "Y"]) || self.matches(current - 1, &["AGGI", "OGGI"]) {
+            if germanic_prefix || self.matches(current + 1, &["ET"]) {
+                self.add("K");
+            } else if self.matches(current + 1, &["IER "]) {
+                self.add("J");
+            } else {
+                self.add_both("J", "K");
+            }
+            return current + 2;
+        }
+
+        self.add("K");
+        self.skip_double(current, 'G')
+    }
+
+    fn encode_j(&mut self, current: isize, last: isize) -> isize {
+        if self.matches(current, &["JOSE"]) || self.matches(0, &["SAN "]) {
+            if (current == 0 && self.at(current + 4) == ' ') || self.matches(0, &["SAN "]) {
+                self.add("H");
+            } else {
+                self.add_both("J", "H");
+            }
+            return current + 1;
+        }
+
+        if current == 0 {
+            self.add_both("J", "A");
+        } else if self.is_vowel(current - 1)
+            && !self.slavo_germanic
+            && matches!(self.at(current + 1), 'A' | 'O')
+        {
+            self.add_both("J", "H");
+        } else if current == last {
+            self.add_both("J", "");
+        } else if !self.matches(current + 1, &["L", "T", "K", "S", "N", "M", "B", "Z"])
+            && !self.matches(current - 1, &["S", "K", "L"])
+        {
+            self.add("J");
+        }
+        self.skip_double(current, 'J')
+    }
+
+    fn encode_s(&mut self, current: isize, last: isize) -> isize {
+        if self.matches(current - 1, &["ISL", "YSL"]) {
+            return current + 1;
+        }
+
+        if current == 0 && self.matches(current, &["SUGAR"]) {
+            self.add_both("X", "S");
+            return current + 1;
+        }
+
+        if self.matches(current, &["SH"]) {
+            if self.matches(current + 1, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                self.add("S");
+            } else {
+                self.add("X");
+            }
+            return current + 2;
+        }
+
+        if self.matches(current, &["SIO", "SIA"]) {
+            if self.slavo_germanic {
+                self.add("S");
+            } else {
+                self.add_both("S", "X");
+            }
+            return current + 3;
+        }
+
+        if (current == 0 && self.matches(current + 1, &["M", "N", "L", "W"])) || self.matches(current + 1, &["Z"]) {
+            self.add_both("S", "X");
+            return self.skip_double(current, 'Z');
+        }
+
+        if self.matches(current, &["SC"]) {
+            if self.at(current + 2) == 'H' {
+                if self.matches(current + 3, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                    if self.matches(current + 3, &["ER", "EN"]) {
+                        self.add_both("X", "SK");
+                    } else {
+                        self.add("SK");
+                    }
+                } else if current == 0 && !self.is_vowel(3) && self.at(3) != 'W' {
+                    self.add_both("X", "S");
+                } else {
+                    self.add("X");
+                }
+            } else if self.matches(current + 2, &["I", "E", "Y"]) {
+                self.add("S");
+            } else {
+                self.add("SK");
+            }
+            return current + 3;
+        }
+
+        if current == last && self.matches(current - 2, &["AI", "OI"]) {
+            self.add_both("", "S");
+        } else {
+            self.add("S");
+        }
+        if self.matches(current + 1, &["S", "Z"]) {
+            current + 2
+        } else {
+            current + 1
+        }
+    }
+
+    fn encode_w(&mut self, current: isize, last: isize) -> isize {
+        if self.matches(current, &["WR"]) {
+            self.add("R");
+            return current + 2;
+        }
+
+        if current == 0 && (self.is_vowel(current + 1) || self.matches(current, &["WH"])) {
+            if self.is_vowel(current + 1) {
+                self.add_both("A", "F");
+            } else {
+                self.add("A");
+            }
+        }
+
+        if (current == last && self.is_vowel(current - 1))
+            || self.matches(current - 1, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.matches(0, &["SCH"])
+        {
+            self.add_both("", "F");
+            return current + 1;
+        }
+
+        if self.matches(current, &["WICZ", "WITZ"]) {
+            self.add_both("TS", "FX");
+            return current + 4;
+        }
+
+        current + 1
+    }
+}
+
+/// Encode words with American Soundex
+#[napi]
+pub fn soundex(words: Vec<String>) -> napi::Result<Vec<String>> {
+    errors::catch_panic("soundex", || {
+        map_batch(&words, soundex_code)
+    })
+}
+
+/// Encode words with Double Metaphone (primary and alternate codes)
+#[napi]
+pub fn double_metaphone(words: Vec<String>) -> napi::Result<Vec<PhoneticCode>> {
+    errors::catch_panic("doubleMetaphone", || {
+        map_batch(&words, |word| DoubleMetaphone::new(word).encode())
+    })
+}
+
+/// Quick typo-tolerant lookup against a candidate list
+#[napi]
+pub fn quick_fuzzy_lookup(query: String, candidates: Vec<String>, max_distance: Option<u32>) -> napi::Result<Vec<FuzzyMatch>> {
+    errors::guard("quickFuzzyLookup", || {
+        let mut tree = BkTree {
+            config: FuzzyMatchConfig::default(),
+            nodes: Vec::new(),
+            term_count: 0,
+        };
+        tree.add(candidates)?;
+        tree.search(query, max_distance.unwrap_or(2))
+    })
+}
@@ -0,0 +1,311 @@
+//! Streaming Blake3 and SHA-256 hashing and keyed MACs
+//!
+//! Incremental hashers are exposed as classes so JavaScript can feed data in
+//! chunks and get digests identical to the ones the native modules compute
+//! internally (e.g. for duplicate detection), without a second hashing
+//! library. HMAC-SHA256 and keyed Blake3 authenticate manifests and
+//! downloaded artifacts. Digests are lowercase hex strings.
+
+use crate::concurrency;
+use crate::errors::{self, ErrorCode};
+use crate::metrics;
+use crate::security_utils::secure_memory::constant_time_eq;
+use hmac::{Hmac, Mac};
+use napi::bindgen_prelude::Buffer;
+use napi_derive::napi;
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::Path;
+
+/// Read size used when hashing files
+const HASH_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Supported hash algorithms
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HashAlgorithm {
+    Blake3,
+    Sha256,
+}
+
+impl HashAlgorithm {
+    /// Parse an algorithm name, defaulting to Blake3
+    pub(crate) fn parse(algorithm: Option<&str>) -> napi::Result<Self> {
+        match algorithm.map(str::to_ascii_lowercase).as_deref() {
+            None | Some("blake3") => Ok(Self::Blake3),
+            Some("sha256") | Some("sha-256") => Ok(Self::Sha256),
+            Some(other) => Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Unknown hash algorithm: {} (expected blake3 or sha256)", other),
+            )),
+        }
+    }
+}
+
+/// Hasher state for either algorithm, plain or keyed
+#[derive(Clone)]
+pub(crate) enum StreamingHasher {
+    Blake3(Box<blake3::Hasher>),
+    Sha256(Sha256),
+    HmacSha256(Box<Hmac<Sha256>>),
+}
+
+impl StreamingHasher {
+    pub(crate) fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Blake3 => Self::Blake3(Box::new(blake3::Hasher::new())),
+            HashAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+        }
+    }
+
+    /// Keyed hasher: HMAC-SHA256 (any key length) or Blake3 keyed mode (32-byte key)
+    pub(crate) fn new_keyed(algorithm: HashAlgorithm, key: &[u8]) -> napi::Result<Self> {
+        match algorithm {
+            HashAlgorithm::Blake3 => {
+                let key: [u8; 32] = key.try_into().map_err(|_| {
+                    errors::new(
+                        ErrorCode::InvalidArgument,
+                        format!("Blake3 keyed hashing needs a 32-byte key, got {} bytes", key.len()),
+                    )
+                })?;
+                Ok(Self::Blake3(Box::new(blake3::Hasher::new_keyed(&key))))
+            }
+            HashAlgorithm::Sha256 => {
+                let mac = Hmac::<Sha256>::new_from_slice(key).map_err(|e| {
+                    errors::new(ErrorCode::InvalidArgument, format!("Invalid HMAC key: {}", e))
+                })?;
+                Ok(Self::HmacSha256(Box::new(mac)))
+            }
+        }
+    }
+
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        metrics::add_bytes("hashing", data.len() as u64);
+        match self {
+            Self::Blake3(hasher) => {
+                hasher.update(data);
+            }
+            Self::Sha256(hasher) => hasher.update(data),
+            Self::HmacSha256(mac) => mac.update(data),
+        }
+    }
+
+    /// Digest of everything written so far; the state is left untouched
+    pub(crate) fn digest(&self) -> Vec<u8> {
+        match self {
+            Self::Blake3(hasher) => hasher.finalize().as_bytes().to_vec(),
+            Self::Sha256(hasher) => hasher.clone().finalize().to_vec(),
+            Self::HmacSha256(mac) => mac.clone().finalize().into_bytes().to_vec(),
+        }
+    }
+}
+
+/// Lowercase hex encoding
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decode a hex string (either case), returning `None` if it is malformed
+pub(crate) fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| hex.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect()
+}
+
+/// Hash a file in fixed-size chunks without loading it into memory
+pub(crate) fn hash_file_with(path: &Path, algorithm: HashAlgorithm) -> std::io::Result<Vec<u8>> {
+    stream_file(path, StreamingHasher::new(algorithm))
+}
+
+/// Feed a file through a hasher in fixed-size chunks
+fn stream_file(path: &Path, mut hasher: StreamingHasher) -> std::io::Result<Vec<u8>> {
+    let mut file = concurrency::open_file(path)?;
+    let mut buffer = vec![0u8; HASH_CHUNK_SIZE];
+
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+
+    Ok(hasher.digest())
+}
+
+/// Incremental Blake3 hasher
+#[napi]
+pub struct Blake3Hasher {
+    inner: StreamingHasher,
+}
+
+#[napi]
+impl Blake3Hasher {
+    /// Create a new Blake3 hasher
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        Self {
+            inner: StreamingHasher::new(HashAlgorithm::Blake3),
+        }
+    }
+
+    /// Add a chunk of data
+    #[napi]
+    pub fn update(&mut self, data: Buffer) {
+        self.inner.update(&data);
+    }
+
+    /// Hex digest of all data added so far
+    ///
+    /// The hasher stays usable: further updates extend the same stream.
+    #[napi]
+    pub fn finalize(&self) -> String {
+        to_hex(&self.inner.digest())
+    }
+
+    /// Discard all data added so far
+    #[napi]
+    pub fn reset(&mut self) {
+        self.inner = StreamingHasher::new(HashAlgorithm::Blake3);
+    }
+}
+
+impl Default for Blake3Hasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Incremental SHA-256 hasher
+#[napi]
+pub struct Sha256Hasher {
+    inner: StreamingHasher,
+}
+
+#[napi]
+impl Sha256Hasher {
+    /// Create a new SHA-256 hasher
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        Self {
+            inner: StreamingHasher::new(HashAlgorithm::Sha256),
+        }
+    }
+
+    /// Add a chunk of data
+    #[napi]
+    pub fn update(&mut self, data: Buffer) {
+        self.inner.update(&data);
+    }
+
+    /// Hex digest of all data added so far
+    ///
+    /// The hasher stays usable: further updates extend the same stream.
+    #[napi]
+    pub fn finalize(&self) -> String {
+        to_hex(&self.inner.digest())
+    }
+
+    /// Discard all data added so far
+    #[napi]
+    pub fn reset(&mut self) {
+        self.inner = StreamingHasher::new(HashAlgorithm::Sha256);
+    }
+}
+
+impl Default for Sha256Hasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Hash a file with streaming reads
+///
+/// # Arguments
+/// * `path` - File to hash
+/// * `algorithm` - "blake3" (default) or "sha256"
+#[napi]
+pub fn hash_file(path: String, algorithm: Option<String>) -> napi::Result<String> {
+    errors::guard("hashFile", || {
+        let algorithm = HashAlgorithm::parse(algorithm.as_deref())?;
+        let digest = hash_file_with(Path::new(&path), algorithm).map_err(|e| {
+            errors::io(&e, format!("Failed to hash {}: {}", path, e))
+        })?;
+        Ok(to_hex(&digest))
+    })
+}
+
+/// Hash an in-memory buffer in one call
+///
+/// # Arguments
+/// * `data` - Bytes to hash
+/// * `algorithm` - "blake3" (default) or "sha256"
+#[napi]
+pub fn hash_buffer(data: Buffer, algorithm: Option<String>) -> napi::Result<String> {
+    errors::guard("hashBuffer", || {
+        let mut hasher = StreamingHasher::new(HashAlgorithm::parse(algorithm.as_deref())?);
+        hasher.update(&data);
+        Ok(to_hex(&hasher.digest()))
+    })
+}
+
+/// Compute a keyed MAC over a buffer
+///
+/// # Arguments
+/// * `key` - Secret key (exactly 32 bytes for Blake3)
+/// * `data` - Bytes to authenticate
+/// * `algorithm` - "sha256" for HMAC-SHA256 (default) or "blake3" for keyed Blake3
+#[napi]
+pub fn hmac(key: Buffer, data: Buffer, algorithm: Option<String>) -> napi::Result<String> {
+    errors::guard("hmac", || {
+        let mut mac = StreamingHasher::new_keyed(parse_mac_algorithm(algorithm)?, &key)?;
+        mac.update(&data);
+        Ok(to_hex(&mac.digest()))
+    })
+}
+
+/// Compute a keyed MAC over a file with streaming reads
+///
+/// # Arguments
+/// * `key` - Secret key (exactly 32 bytes for Blake3)
+/// * `path` - File to authenticate
+/// * `algorithm` - "sha256" for HMAC-SHA256 (default) or "blake3" for keyed Blake3
+#[napi]
+pub fn hmac_file(key: Buffer, path: String, algorithm: Option<String>) -> napi::Result<String> {
+    errors::guard("hmacFile", || {
+        let mac = StreamingHasher::new_keyed(parse_mac_algorithm(algorithm)?, &key)?;
+        let digest = stream_file(Path::new(&path), mac).map_err(|e| {
+            errors::io(&e, format!("Failed to hash {}: {}", path, e))
+        })?;
+        Ok(to_hex(&digest))
+    })
+}
+
+/// Verify a keyed MAC in constant time
+///
+/// Returns false for a wrong or malformed `expected` value.
+///
+/// # Arguments
+/// * `key` - Secret key (exactly 32 bytes for Blake3)
+/// * `data` - Bytes that were authenticated
+/// * `expected` - Hex MAC to check
+/// * `algorithm` - "sha256" for HMAC-SHA256 (default) or "blake3" for keyed Blake3
+#[napi]
+pub fn verify_hmac(key: Buffer, data: Buffer, expected: String, algorithm: Option<String>) -> napi::Result<bool> {
+    errors::guard("verifyHmac", || {
+        let mut mac = StreamingHasher::new_keyed(parse_mac_algorithm(algorithm)?, &key)?;
+        mac.update(&data);
+        Ok(match from_hex(&expected) {
+            Some(expected) => constant_time_eq(&mac.digest(), &expected),
+            None => false,
+        })
+    })
+}
+
+/// MACs default to HMAC-SHA256, the interoperable choice
+fn parse_mac_algorithm(algorithm: Option<String>) -> napi::Result<HashAlgorithm> {
+    HashAlgorithm::parse(Some(algorithm.as_deref().unwrap_or("sha256")))
+}
@@ -0,0 +1,194 @@
+//! Syntax highlighting token streams
+//!
+//! This module tokenizes source code with syntect's bundled Sublime Text
+//! grammars and returns spans with their TextMate scope and a coarse
+//! classification, so terminal and web previews can be colorized without a
+//! JavaScript highlighter.
+
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use syntect::parsing::{ParseState, ScopeStack, SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
+use crate::errors::{self, ErrorCode};
+
+/// Highlighted span of source text
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HighlightToken {
+    /// Start byte offset in the source
+    pub start: u32,
+    /// End byte offset in the source
+    pub end: u32,
+    /// Line number (1-based)
+    pub line: u32,
+    /// Innermost TextMate scope (e.g. "keyword.control.rust")
+    pub scope: String,
+    /// Coarse classification: "keyword", "string", "comment", "number",
+    /// "constant", "function", "type", "variable", "operator",
+    /// "punctuation", or "plain"
+    pub classification: String,
+}
+
+/// Language supported by the highlighter
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HighlightLanguage {
+    /// Display name
+    pub name: String,
+    /// File extensions and tokens accepted as the language argument
+    pub extensions: Vec<String>,
+}
+
+/// Lazily loaded bundled syntax definitions
+fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+/// Resolve a language by name or extension, falling back to first-line detection
+fn resolve_syntax<'a>(syntaxes: &'a SyntaxSet, source: &str, language: Option<&str>) -> napi::Result<&'a SyntaxReference> {
+    match language {
+        Some(language) => syntaxes
+            .find_syntax_by_token(language)
+            .or_else(|| syntaxes.find_syntax_by_name(language))
+            .ok_or_else(|| {
+                errors::new(ErrorCode::InvalidArgument, format!("Unsupported language: {}", language))
+            }),
+        None => Ok(source
+            .lines()
+            .next()
+            .and_then(|first| syntaxes.find_syntax_by_first_line(first))
+            .unwrap_or_else(|| syntaxes.find_syntax_plain_text())),
+    }
+}
+
+/// Classifications that apply to everything nested inside them (delimiters included)
+const ENCLOSING_CLASSES: [(&str, &str); 2] = [("comment", "comment"), ("string", "string")];
+
+/// Classifications decided by the innermost matching scope
+const SCOPE_CLASSES: [(&str, &str); 12] = [
+    ("constant.numeric", "number"),
+    ("constant", "constant"),
+    ("keyword.operator", "operator"),
+    ("keyword", "keyword"),
+    ("storage", "keyword"),
+    ("entity.name.function", "function"),
+    ("support.function", "function"),
+    ("entity.name", "type"),
+    ("support.type", "type"),
+    ("support.class", "type"),
+    ("variable", "variable"),
+    ("punctuation", "punctuation"),
+];
+
+/// Whether a scope name equals a prefix or extends it by dotted atoms
+fn scope_matches(name: &str, prefix: &str) -> bool {
+    name.strip_prefix(prefix).is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+/// Map a scope stack to a coarse classification
+fn classify(stack: &ScopeStack) -> &'static str {
+    let names: Vec<String> = stack.as_slice().iter().map(|s| s.build_string()).collect();
+
+    for (prefix, class) in ENCLOSING_CLASSES {
+        if names.iter().any(|name| scope_matches(name, prefix)) {
+            return class;
+        }
+    }
+
+    for name in names.iter().rev() {
+        for (prefix, class) in SCOPE_CLASSES {
+            if scope_matches(name, prefix) {
+                return class;
+            }
+        }
+    }
+    "plain"
+}
+
+/// Append a token trimmed of surrounding whitespace, merging with the
+/// previous one when the scope is unchanged
+fn push_token(tokens: &mut Vec<HighlightToken>, source: &str, start: usize, end: usize, line: u32, stack: &ScopeStack) {
+    if start >= end {
+        return;
+    }
+
+    let span = &source[start..end];
+    let trimmed = span.trim_start();
+    let start = start + (span.len() - trimmed.len());
+    let end = start + trimmed.trim_end().len();
+    if start == end {
+        return;
+    }
+
+    let scope = stack.as_slice().last().map(|s| s.build_string()).unwrap_or_default();
+    if let Some(previous) = tokens.last_mut() {
+        let contiguous = source[previous.end as usize..start].trim().is_empty();
+        if contiguous && previous.scope == scope && previous.line == line {
+            previous.end = end as u32;
+            return;
+        }
+    }
+
+    tokens.push(HighlightToken {
+        start: start as u32,
+        end: end as u32,
+        line,
+        classification: classify(stack).to_string(),
+        scope,
+    });
+}
+
+/// Tokenize source code into highlight spans
+///
+/// # Arguments
+/// * `source` - Source text to highlight
+/// * `language` - Language name or file extension (e.g. "rs", "TypeScript");
+///   detected from a shebang or modeline when omitted
+#[napi]
+pub fn highlight_tokens(source: String, language: Option<String>) -> napi::Result<Vec<HighlightToken>> {
+    errors::guard("highlightTokens", || {
+        let syntaxes = syntax_set();
+        let syntax = resolve_syntax(syntaxes, &source, language.as_deref())?;
+
+        let mut state = ParseState::new(syntax);
+        let mut stack = ScopeStack::new();
+        let mut tokens = Vec::new();
+        let mut line_start = 0;
+
+        for (index, line) in LinesWithEndings::from(&source).enumerate() {
+            let line_number = index as u32 + 1;
+            let ops = state.parse_line(line, syntaxes).map_err(|e| {
+                errors::new(ErrorCode::Internal, format!("Highlighting failed on line {}: {}", line_number, e))
+            })?;
+
+            let mut cursor = 0;
+            for (offset, op) in ops {
+                push_token(&mut tokens, &source, line_start + cursor, line_start + offset, line_number, &stack);
+                cursor = offset;
+                stack.apply(&op).map_err(|e| {
+                    errors::new(ErrorCode::Internal, format!("Highlighting failed on line {}: {}", line_number, e))
+                })?;
+            }
+            push_token(&mut tokens, &source, line_start + cursor, line_start + line.len(), line_number, &stack);
+
+            line_start += line.len();
+        }
+
+        Ok(tokens)
+    })
+}
+
+/// List languages supported by the highlighter
+#[napi]
+pub fn list_highlight_languages() -> Vec<HighlightLanguage> {
+    syntax_set()
+        .syntaxes()
+        .iter()
+        .map(|syntax| HighlightLanguage {
+            name: syntax.name.clone(),
+            extensions: syntax.file_extensions.clone(),
+        })
+        .collect()
+}
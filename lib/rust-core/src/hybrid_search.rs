@@ -0,0 +1,185 @@
+//! Hybrid retrieval fusing keyword and vector rankings
+//!
+//! BM25 scores from a [`KeywordIndex`] and cosine similarities from the
+//! vector kernels are combined into one ranking. Document `i` of the index
+//! pairs with vector `i`. Two fusion methods are available:
+//!
+//! - `rrf` (reciprocal rank fusion) sums `1 / (rrf_k + rank)` over both
+//!   rankings. Only ranks matter, so the two score scales need no calibration.
+//! - `weighted` sums `keyword_weight * bm25 / max_bm25` and
+//!   `(1 - keyword_weight) * similarity`, with negative similarities counted
+//!   as 0.
+
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use crate::errors::{self, ErrorCode};
+use crate::keyword_index::KeywordIndex;
+use crate::vector_ops::VectorOperations;
+
+/// Options for `hybridSearch`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HybridSearchOptions {
+    /// Fusion method: "rrf" or "weighted"
+    pub fusion: String,
+    /// Weight of the keyword score in weighted fusion (0-1)
+    pub keyword_weight: f64,
+    /// Rank offset in reciprocal rank fusion
+    pub rrf_k: f64,
+    /// Results taken from each ranking before fusion
+    pub candidates: u32,
+    /// Number of fused results to return
+    pub top_k: u32,
+    /// Keyword boost per field (default 1 for every field)
+    pub field_boosts: Option<HashMap<String, f64>>,
+}
+
+impl Default for HybridSearchOptions {
+    fn default() -> Self {
+        Self {
+            fusion: "rrf".to_string(),
+            keyword_weight: 0.5,
+            rrf_k: 60.0,
+            candidates: 100,
+            top_k: 10,
+            field_boosts: None,
+        }
+    }
+}
+
+/// A document in the fused ranking
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HybridResult {
+    /// Index of the document and its vector
+    pub index: u32,
+    /// Identifier the document was added with
+    pub id: String,
+    /// Fused score
+    pub score: f64,
+    /// BM25 score (0 if no query term occurs in the document)
+    pub keyword_score: f64,
+    /// Cosine similarity to the query vector
+    pub vector_similarity: f64,
+    /// Position in the keyword ranking (1-based), if among the candidates
+    pub keyword_rank: Option<u32>,
+    /// Position in the vector ranking (1-based), if among the candidates
+    pub vector_rank: Option<u32>,
+}
+
+/// Fusion method parsed from `HybridSearchOptions.fusion`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Fusion {
+    ReciprocalRank,
+    Weighted,
+}
+
+/// Rank documents by keyword relevance and vector similarity together
+///
+/// # Arguments
+/// * `index` - Keyword index over the documents
+/// * `query` - Query text for keyword scoring
+/// * `query_vector` - Embedding of the query
+/// * `vectors_flat` - Flattened document vectors, one per indexed document
+/// * `vector_size` - Size of each vector
+/// * `options` - Optional fusion method, weights and result counts
+#[napi]
+pub fn hybrid_search(
+    index: &KeywordIndex,
+    query: String,
+    query_vector: Vec<f64>,
+    vectors_flat: Vec<f64>,
+    vector_size: u32,
+    options: Option<HybridSearchOptions>,
+) -> napi::Result<Vec<HybridResult>> {
+    errors::guard("hybridSearch", || {
+        let options = options.unwrap_or_default();
+        let fusion = match options.fusion.as_str() {
+            "rrf" => Fusion::ReciprocalRank,
+            "weighted" => Fusion::Weighted,
+            other => {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Unknown fusion method: {} (expected rrf or weighted)", other),
+                ))
+            }
+        };
+        if !(0.0..=1.0).contains(&options.keyword_weight) {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                "keyword_weight must be between 0 and 1".to_string(),
+            ));
+        }
+        if !options.rrf_k.is_finite() || options.rrf_k <= 0.0 {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                "rrf_k must be a positive number".to_string(),
+            ));
+        }
+        let document_count = index.document_count() as usize;
+        if vector_size == 0 || vectors_flat.len() != document_count * vector_size as usize {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Expected {} vectors of size {}, one per indexed document", document_count, vector_size),
+            ));
+        }
+
+        let candidates = options.candidates as usize;
+        let mut keyword = index.ranked(&query, options.field_boosts.as_ref())?;
+        let max_keyword = keyword.first().map_or(0.0, |&(_, score)| score);
+        keyword.truncate(candidates);
+
+        let similarities = VectorOperations::new(None)?.batch_cosine_similarity(query_vector, vectors_flat, vector_size)?;
+        let mut vector: Vec<(usize, f64)> = similarities.iter().copied().enumerate().collect();
+        vector.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        vector.truncate(candidates);
+
+        let mut fused: BTreeMap<usize, HybridResult> = BTreeMap::new();
+        for (rank, &(document, score)) in keyword.iter().enumerate() {
+            let result = fused
+                .entry(document)
+                .or_insert_with(|| unranked(index, document, similarities[document]));
+            result.keyword_score = score;
+            result.keyword_rank = Some(rank as u32 + 1);
+        }
+        for (rank, &(document, _)) in vector.iter().enumerate() {
+            fused
+                .entry(document)
+                .or_insert_with(|| unranked(index, document, similarities[document]))
+                .vector_rank = Some(rank as u32 + 1);
+        }
+
+        for result in fused.values_mut() {
+            result.score = match fusion {
+                Fusion::ReciprocalRank => [result.keyword_rank, result.vector_rank]
+                    .into_iter()
+                    .flatten()
+                    .map(|rank| 1.0 / (options.rrf_k + rank as f64))
+                    .sum(),
+                Fusion::Weighted => {
+                    let keyword = if max_keyword > 0.0 { result.keyword_score / max_keyword } else { 0.0 };
+                    options.keyword_weight * keyword + (1.0 - options.keyword_weight) * result.vector_similarity.max(0.0)
+                }
+            };
+        }
+
+        let mut results: Vec<HybridResult> = fused.into_values().collect();
+        results.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.index.cmp(&b.index)));
+        results.truncate(options.top_k as usize);
+        Ok(results)
+    })
+}
+
+/// A result with no ranks or score yet
+fn unranked(index: &KeywordIndex, document: usize, similarity: f64) -> HybridResult {
+    HybridResult {
+        index: document as u32,
+        id: index.id(document).to_string(),
+        score: 0.0,
+        keyword_score: 0.0,
+        vector_similarity: similarity,
+        keyword_rank: None,
+        vector_rank: None,
+    }
+}
@@ -0,0 +1,167 @@
+//! Identifier splitting and case-style conversion
+//!
+//! This module converts batches of identifiers between camelCase, PascalCase,
+//! snake_case, kebab-case and CONSTANT_CASE in a single call, with word
+//! splitting that understands acronyms and embedded digits.
+
+use napi_derive::napi;
+use crate::batch::map_batch;
+use crate::errors::{self, ErrorCode};
+
+/// Supported target case styles
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CaseStyle {
+    Camel,
+    Pascal,
+    Snake,
+    Kebab,
+    Constant,
+    Title,
+}
+
+impl CaseStyle {
+    fn parse(name: &str) -> napi::Result<Self> {
+        match name {
+            "camel" => Ok(Self::Camel),
+            "pascal" => Ok(Self::Pascal),
+            "snake" => Ok(Self::Snake),
+            "kebab" => Ok(Self::Kebab),
+            "constant" => Ok(Self::Constant),
+            "title" => Ok(Self::Title),
+            _ => Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Unsupported case style: {}", name),
+            )),
+        }
+    }
+}
+
+/// Split an identifier into words
+///
+/// Separators (`_`, `-`, `.`, whitespace) always split; lower-to-upper
+/// transitions split camelCase; an uppercase run followed by a lowercase
+/// letter ends an acronym (`HTTPServer` -> `HTTP`, `Server`); digits stay
+/// attached to the preceding word (`utf8Decoder` -> `utf8`, `Decoder`).
+fn split_words(identifier: &str) -> Vec<String> {
+    let chars: Vec<char> = identifier.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        if let Some(prev) = current.chars().last() {
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            let boundary = (prev.is_lowercase() && c.is_uppercase())
+                || (prev.is_uppercase() && c.is_uppercase() && next_is_lower)
+                || (prev.is_ascii_digit() && c.is_uppercase());
+
+            if boundary {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(c);
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Uppercase the first character and lowercase the rest
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.flat_map(|c| c.to_lowercase())).collect(),
+        None => String::new(),
+    }
+}
+
+/// Join words in the requested style
+fn join_words(words: &[String], style: CaseStyle) -> String {
+    match style {
+        CaseStyle::Camel => words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| if i == 0 { w.to_lowercase() } else { capitalize(w) })
+            .collect(),
+        CaseStyle::Pascal => words.iter().map(|w| capitalize(w)).collect(),
+        CaseStyle::Snake => words.iter().map(|w| w.to_lowercase()).collect::<Vec<_>>().join("_"),
+        CaseStyle::Kebab => words.iter().map(|w| w.to_lowercase()).collect::<Vec<_>>().join("-"),
+        CaseStyle::Constant => words.iter().map(|w| w.to_uppercase()).collect::<Vec<_>>().join("_"),
+        CaseStyle::Title => words.iter().map(|w| capitalize(w)).collect::<Vec<_>>().join(" "),
+    }
+}
+
+/// Detect the case style of a single identifier
+fn detect_style(identifier: &str) -> &'static str {
+    let has_lower = identifier.chars().any(|c| c.is_lowercase());
+    let has_upper = identifier.chars().any(|c| c.is_uppercase());
+    let starts_upper = identifier.chars().next().is_some_and(|c| c.is_uppercase());
+
+    if identifier.contains('_') {
+        if has_upper && !has_lower {
+            "constant"
+        } else if !has_upper {
+            "snake"
+        } else {
+            "mixed"
+        }
+    } else if identifier.contains('-') {
+        if !has_upper {
+            "kebab"
+        } else {
+            "mixed"
+        }
+    } else if identifier.contains(' ') {
+        "title"
+    } else if has_upper && has_lower {
+        if starts_upper {
+            "pascal"
+        } else {
+            "camel"
+        }
+    } else if has_upper {
+        "constant"
+    } else if has_lower {
+        "snake"
+    } else {
+        "unknown"
+    }
+}
+
+/// Convert identifiers to a target case style in one call
+///
+/// # Arguments
+/// * `identifiers` - Identifiers to convert
+/// * `target_style` - "camel", "pascal", "snake", "kebab", "constant", or "title"
+#[napi]
+pub fn convert_case(identifiers: Vec<String>, target_style: String) -> napi::Result<Vec<String>> {
+    errors::guard("convertCase", || {
+        let style = CaseStyle::parse(&target_style)?;
+        Ok(map_batch(&identifiers, |id| join_words(&split_words(id), style)))
+    })
+}
+
+/// Split identifiers into words, handling acronyms and digits
+#[napi]
+pub fn split_identifiers(identifiers: Vec<String>) -> napi::Result<Vec<Vec<String>>> {
+    errors::catch_panic("splitIdentifiers", || {
+        map_batch(&identifiers, split_words)
+    })
+}
+
+/// Detect the case style of each identifier
+/// Returns "camel", "pascal", "snake", "kebab", "constant", "title", "mixed", or "unknown"
+#[napi]
+pub fn detect_case_styles(identifiers: Vec<String>) -> napi::Result<Vec<String>> {
+    errors::catch_panic("detectCaseStyles", || {
+        map_batch(&identifiers, |id| detect_style(id).to_string())
+    })
+}
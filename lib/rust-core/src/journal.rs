@@ -0,0 +1,383 @@
+//! Undo journal for native file mutations
+//!
+//! A `Journal` owns a directory of open transactions. Bulk file operations
+//! given a transaction id record what each change is about to do before
+//! doing it, appending one line per change to the transaction's
+//! `journal.jsonl` and syncing it to disk:
+//!
+//! - a file about to be overwritten is first copied into the transaction
+//!   directory (a before-image)
+//! - a file being deleted is renamed into the transaction directory instead
+//! - created files and moves are recorded so they can be reverted
+//! - a symlink about to be replaced or deleted is recorded by its target and
+//!   recreated as a link; a write through a symlink backs up the file it
+//!   points to and leaves the link alone
+//!
+//! `rollback` undoes the recorded changes newest first; `commit` discards
+//! the backups. Because the journal is on disk, a transaction left open by a
+//! crashed process can still be rolled back by a new `Journal` over the same
+//! directory.
+
+use crate::audit::{self, AuditKind};
+use crate::errors::{self, ErrorCode};
+use crate::security_utils::quarantine::{move_file, new_id};
+use napi_derive::napi;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// File in each transaction directory listing its entries
+const JOURNAL_FILE: &str = "journal.jsonl";
+
+/// Transactions begun by this process and not yet committed or rolled back
+static OPEN: Mutex<BTreeMap<String, Arc<Transaction>>> = Mutex::new(BTreeMap::new());
+
+/// One recorded change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct JournalEntry {
+    /// "created", "modified", "deleted" or "moved"
+    action: String,
+    /// Path the change was made to (the destination of a move)
+    path: PathBuf,
+    /// Before-image of a modified or deleted file
+    backup: Option<PathBuf>,
+    /// Original path of a moved file
+    source: Option<PathBuf>,
+    /// Target of a replaced or deleted symlink, recreated on rollback
+    #[serde(default)]
+    link: Option<PathBuf>,
+}
+
+/// Outcome of a rollback
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollbackResult {
+    /// Transaction id
+    pub transaction: String,
+    /// Changes undone
+    pub reverted: u32,
+    /// Changes that could not be undone, with the reason
+    pub failures: Vec<String>,
+}
+
+/// An open transaction that bulk operations record into
+pub(crate) struct Transaction {
+    directory: PathBuf,
+    /// Entries recorded so far; held while recording so backups get unique names
+    entries: Mutex<u32>,
+}
+
+impl Transaction {
+    /// Record a write to `path`, backing up its current contents if it exists
+    ///
+    /// Writes go through symlinks, so for a link the file it points to is
+    /// recorded instead.
+    pub(crate) fn before_write(&self, path: &Path) -> napi::Result<()> {
+        let target = write_target(path);
+        let mut entries = self.entries.lock();
+        if fs::symlink_metadata(&target).is_ok() {
+            let backup = self.backup_path(*entries);
+            fs::copy(&target, &backup).map_err(|e| io_error("back up", &target, e))?;
+            self.append(&mut entries, "modified", &target, Some(backup), None, None)
+        } else {
+            self.append(&mut entries, "created", &target, None, None, None)
+        }
+    }
+
+    /// Record a move, backing up the destination if it will be replaced
+    pub(crate) fn before_move(&self, source: &Path, destination: &Path) -> napi::Result<()> {
+        if let Ok(link) = fs::read_link(destination) {
+            // A move replaces the link itself, not the file it points to
+            let mut entries = self.entries.lock();
+            self.append(&mut entries, "modified", destination, None, None, Some(link))?;
+        } else if fs::symlink_metadata(destination).is_ok() {
+            self.before_write(destination)?;
+        }
+        let mut entries = self.entries.lock();
+        self.append(&mut entries, "moved", destination, None, Some(source.to_path_buf()), None)
+    }
+
+    /// Delete `path` by moving it into the transaction directory
+    ///
+    /// A symlink is removed and recorded by its target instead.
+    pub(crate) fn delete(&self, path: &Path) -> napi::Result<()> {
+        let mut entries = self.entries.lock();
+        if let Ok(link) = fs::read_link(path) {
+            self.append(&mut entries, "deleted", path, None, None, Some(link))?;
+            return fs::remove_file(path).map_err(|e| io_error("delete", path, e));
+        }
+        let backup = self.backup_path(*entries);
+        self.append(&mut entries, "deleted", path, Some(backup.clone()), None, None)?;
+        move_file(path, &backup).map_err(|e| io_error("delete", path, e))
+    }
+
+    fn backup_path(&self, sequence: u32) -> PathBuf {
+        self.directory.join(format!("{}.bak", sequence))
+    }
+
+    fn append(
+        &self,
+        entries: &mut u32,
+        action: &str,
+        path: &Path,
+        backup: Option<PathBuf>,
+        source: Option<PathBuf>,
+        link: Option<PathBuf>,
+    ) -> napi::Result<()> {
+        let entry = JournalEntry {
+            action: action.to_string(),
+            path: path.to_path_buf(),
+            backup,
+            source,
+            link,
+        };
+        let mut line = serde_json::to_string(&entry).map_err(|e| {
+            errors::new(ErrorCode::Internal, format!("Failed to serialize journal entry: {}", e))
+        })?;
+        line.push('\n');
+        let journal = self.directory.join(JOURNAL_FILE);
+        OpenOptions::new()
+            .append(true)
+            .open(&journal)
+            .and_then(|mut file| {
+                file.write_all(line.as_bytes())?;
+                file.sync_data()
+            })
+            .map_err(|e| io_error("append to", &journal, e))?;
+        *entries += 1;
+        Ok(())
+    }
+}
+
+/// Look up an open transaction by id
+pub(crate) fn open(id: &str) -> napi::Result<Arc<Transaction>> {
+    OPEN.lock().get(id).cloned().ok_or_else(|| {
+        errors::new(
+            ErrorCode::InvalidArgument,
+            format!("Unknown or closed transaction: {}", id),
+        )
+    })
+}
+
+/// Directory of transactions that record before-images of file changes
+#[napi]
+pub struct Journal {
+    directory: PathBuf,
+}
+
+#[napi]
+impl Journal {
+    /// Open a journal directory, creating it if missing
+    #[napi(constructor)]
+    pub fn new(directory: String) -> napi::Result<Self> {
+        errors::guard("new Journal", || {
+            fs::create_dir_all(&directory).map_err(|e| io_error("create", Path::new(&directory), e))?;
+            let directory = fs::canonicalize(&directory).map_err(|e| io_error("open", Path::new(&directory), e))?;
+            Ok(Self { directory })
+        })
+    }
+
+    /// Start a transaction and return its id
+    ///
+    /// Pass the id to bulk file operations to record their changes.
+    #[napi]
+    pub fn begin(&self) -> napi::Result<String> {
+        errors::guard("Journal.begin", || {
+            let id = new_id()?;
+            let directory = self.directory.join(&id);
+            fs::create_dir(&directory)
+                .and_then(|_| fs::File::create(directory.join(JOURNAL_FILE)))
+                .map_err(|e| io_error("create", &directory, e))?;
+            let transaction = Transaction {
+                directory,
+                entries: Mutex::new(0),
+            };
+            OPEN.lock().insert(id.clone(), Arc::new(transaction));
+            Ok(id)
+        })
+    }
+
+    /// Keep a transaction's changes and discard its backups
+    ///
+    /// Returns the number of changes it recorded.
+    #[napi]
+    pub fn commit(&self, transaction: String) -> napi::Result<u32> {
+        errors::guard("Journal.commit", || {
+            let directory = self.transaction_directory(&transaction)?;
+            let entries = read_entries(&directory)?;
+            OPEN.lock().remove(&transaction);
+            fs::remove_dir_all(&directory).map_err(|e| io_error("remove", &directory, e))?;
+            Ok(entries.len() as u32)
+        })
+    }
+
+    /// Undo a transaction's changes, newest first
+    ///
+    /// Entries that cannot be undone are reported and the rest are still
+    /// attempted. The transaction directory is kept if anything failed, so
+    /// its backups can be recovered by hand.
+    #[napi]
+    pub fn rollback(&self, transaction: String) -> napi::Result<RollbackResult> {
+        errors::guard("Journal.rollback", || {
+            let directory = self.transaction_directory(&transaction)?;
+            let entries = read_entries(&directory)?;
+            OPEN.lock().remove(&transaction);
+
+            let mut reverted = 0;
+            let mut failures = Vec::new();
+            for entry in entries.iter().rev() {
+                let path = entry.path.to_string_lossy();
+                match revert(entry) {
+                    Ok(()) => {
+                        reverted += 1;
+                        audit::record(AuditKind::FileMutation, "rollback", &path, "success", Some(&transaction));
+                    }
+                    Err(e) => {
+                        audit::record(AuditKind::FileMutation, "rollback", &path, "failure", Some(&e.to_string()));
+                        failures.push(format!("{} {}: {}", entry.action, path, e));
+                    }
+                }
+            }
+            if failures.is_empty() {
+                fs::remove_dir_all(&directory).map_err(|e| io_error("remove", &directory, e))?;
+            }
+            Ok(RollbackResult {
+                transaction,
+                reverted,
+                failures,
+            })
+        })
+    }
+
+    /// Ids of transactions in the directory that were neither committed nor rolled back
+    ///
+    /// Includes transactions left behind by other or crashed processes.
+    #[napi]
+    pub fn pending(&self) -> napi::Result<Vec<String>> {
+        errors::guard("Journal.pending", || {
+            let entries = fs::read_dir(&self.directory).map_err(|e| io_error("list", &self.directory, e))?;
+            let mut ids: Vec<String> = entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.join(JOURNAL_FILE).is_file())
+                .filter_map(|path| path.file_name().map(|name| name.to_string_lossy().to_string()))
+                .collect();
+            ids.sort();
+            Ok(ids)
+        })
+    }
+}
+
+impl Journal {
+    fn transaction_directory(&self, id: &str) -> napi::Result<PathBuf> {
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Invalid transaction id: {}", id),
+            ));
+        }
+        let directory = self.directory.join(id);
+        if !directory.join(JOURNAL_FILE).is_file() {
+            return Err(errors::new(
+                ErrorCode::PathNotFound,
+                format!("No pending transaction {} in {}", id, self.directory.display()),
+            ));
+        }
+        Ok(directory)
+    }
+}
+
+fn read_entries(directory: &Path) -> napi::Result<Vec<JournalEntry>> {
+    let journal = directory.join(JOURNAL_FILE);
+    let content = fs::read_to_string(&journal).map_err(|e| io_error("read", &journal, e))?;
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line).map_err(|e| {
+                errors::new(
+                    ErrorCode::ParseError,
+                    format!("Invalid journal entry in {}: {}", journal.display(), e),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Undo one entry
+///
+/// Entries are written before their change is made, so a change that never
+/// happened (e.g. the process died first) is treated as already undone.
+fn revert(entry: &JournalEntry) -> std::io::Result<()> {
+    let exists = |path: &Path| fs::symlink_metadata(path).is_ok();
+    if let (Some(link), "modified" | "deleted") = (&entry.link, entry.action.as_str()) {
+        return restore_link(&entry.path, link);
+    }
+    match (entry.action.as_str(), &entry.backup, &entry.source) {
+        ("created", _, _) if exists(&entry.path) => fs::remove_file(&entry.path),
+        ("modified" | "deleted", Some(backup), _) if exists(backup) => {
+            if let Some(parent) = entry.path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            move_file(backup, &entry.path)
+        }
+        ("moved", _, Some(source)) if exists(&entry.path) && !exists(source) => {
+            if let Some(parent) = source.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            move_file(&entry.path, source)
+        }
+        ("created" | "modified" | "deleted" | "moved", _, _) => Ok(()),
+        (action, _, _) => Err(std::io::Error::other(format!("unknown journal action {}", action))),
+    }
+}
+
+/// The file a write to `path` lands in: what a symlink points to, or `path` itself
+fn write_target(path: &Path) -> PathBuf {
+    match fs::read_link(path) {
+        Ok(link) => fs::canonicalize(path).unwrap_or_else(|_| {
+            // Dangling: the write creates the file the link names
+            path.parent().unwrap_or(Path::new("")).join(link)
+        }),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Put back a symlink to `link` at `path`, replacing a file now there
+fn restore_link(path: &Path, link: &Path) -> std::io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_symlink() && fs::read_link(path)? == link => return Ok(()),
+        Ok(metadata) if metadata.is_dir() => {
+            return Err(std::io::Error::other(format!("{} is now a directory", path.display())));
+        }
+        Ok(_) => fs::remove_file(path)?,
+        Err(_) => {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+    }
+    symlink(link, path)
+}
+
+#[cfg(unix)]
+fn symlink(link: &Path, path: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(link, path)
+}
+
+#[cfg(windows)]
+fn symlink(link: &Path, path: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(link, path)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn symlink(_link: &Path, _path: &Path) -> std::io::Result<()> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "symlinks are not supported on this platform"))
+}
+
+fn io_error(action: &str, path: &Path, e: std::io::Error) -> napi::Error {
+    errors::io(&e, format!("Failed to {} {}: {}", action, path.display(), e))
+}
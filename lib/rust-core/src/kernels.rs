@@ -0,0 +1,168 @@
+//! Platform-independent kernels shared by the Node addon and the wasm32 build
+//!
+//! Nothing here depends on napi, the filesystem or threads, so the same code
+//! backs `VectorOperations`/`TextProcessor` in the native addon and the
+//! exports of [`crate::wasm`] where the `.node` binary cannot be loaded.
+
+#[cfg(any(feature = "text", feature = "wasm"))]
+use aho_corasick::{AhoCorasick, AhoCorasickKind, Anchored, BuildError, Input, MatchKind, StartKind};
+#[cfg(any(feature = "text", feature = "wasm"))]
+use regex::Regex;
+
+/// A match span in a text
+#[cfg(any(feature = "text", feature = "wasm"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Span {
+    /// Byte offset of the match start
+    pub(crate) start: usize,
+    /// Byte offset just past the match
+    pub(crate) end: usize,
+    /// Index of the pattern that matched (0 for a single regex)
+    pub(crate) pattern_index: u32,
+}
+
+/// Byte offsets of line starts for offset-to-line lookups
+#[cfg(any(feature = "text", feature = "wasm"))]
+pub(crate) struct LineIndex {
+    starts: Vec<usize>,
+}
+
+#[cfg(any(feature = "text", feature = "wasm"))]
+impl LineIndex {
+    pub(crate) fn new(text: &str) -> Self {
+        let mut starts = vec![0];
+        starts.extend(text.match_indices('\n').map(|(i, _)| i + 1));
+        Self { starts }
+    }
+
+    /// Line number (1-based) containing a byte offset
+    pub(crate) fn line_of(&self, offset: usize) -> u32 {
+        self.starts.partition_point(|&start| start <= offset) as u32
+    }
+
+    /// Line number (1-based) and byte column (0-based) of a byte offset
+    pub(crate) fn position(&self, offset: usize) -> (u32, u32) {
+        let line = self.line_of(offset);
+        (line, (offset - self.starts[line as usize - 1]) as u32)
+    }
+}
+
+/// Cosine similarity of two equal-length vectors (0 if either is all zeros)
+#[cfg(any(feature = "vector", feature = "wasm"))]
+pub(crate) fn cosine_similarity(vec_a: &[f32], vec_b: &[f32]) -> f32 {
+    let dot_product = dot_product(vec_a, vec_b);
+    let norm_a = vector_norm(vec_a);
+    let norm_b = vector_norm(vec_b);
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot_product / (norm_a * norm_b)
+    }
+}
+
+/// Dot product of two equal-length vectors
+#[cfg(any(feature = "vector", feature = "wasm"))]
+pub(crate) fn dot_product(vec_a: &[f32], vec_b: &[f32]) -> f32 {
+    vec_a.iter().zip(vec_b.iter()).map(|(a, b)| a * b).sum()
+}
+
+/// L2 norm of a vector
+#[cfg(any(feature = "vector", feature = "wasm"))]
+pub(crate) fn vector_norm(vector: &[f32]) -> f32 {
+    vector.iter().map(|x| x * x).sum::<f32>().sqrt()
+}
+
+/// Match semantics and automaton choice for substring search
+#[cfg(any(feature = "text", feature = "wasm"))]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SubstringOptions {
+    /// Which pattern wins when several match at one position
+    pub(crate) match_kind: MatchKind,
+    /// Matches must start at the beginning of the text or where the previous one ended
+    pub(crate) anchored: bool,
+    /// Report overlapping matches (honored with `MatchKind::Standard` only)
+    pub(crate) overlapping: bool,
+    /// Automaton to build; `None` picks one by pattern count and size
+    pub(crate) automaton: Option<AhoCorasickKind>,
+}
+
+#[cfg(any(feature = "text", feature = "wasm"))]
+impl Default for SubstringOptions {
+    fn default() -> Self {
+        Self {
+            match_kind: MatchKind::LeftmostFirst,
+            anchored: false,
+            overlapping: false,
+            automaton: None,
+        }
+    }
+}
+
+/// Build the automaton used for substring search
+#[cfg(any(feature = "text", feature = "wasm"))]
+pub(crate) fn substring_automaton(
+    patterns: &[String],
+    case_sensitive: bool,
+    options: &SubstringOptions,
+) -> Result<AhoCorasick, BuildError> {
+    AhoCorasick::builder()
+        .match_kind(options.match_kind)
+        .ascii_case_insensitive(!case_sensitive)
+        .start_kind(if options.anchored { StartKind::Anchored } else { StartKind::Unanchored })
+        .kind(options.automaton)
+        .build(patterns)
+}
+
+/// Substring matches in a text, stopping after `max_matches` (0 for unlimited)
+///
+/// `options` must be those the automaton was built with.
+#[cfg(any(feature = "text", feature = "wasm"))]
+pub(crate) fn substring_matches(
+    automaton: &AhoCorasick,
+    text: &str,
+    max_matches: usize,
+    options: &SubstringOptions,
+) -> Vec<Span> {
+    let input = Input::new(text).anchored(if options.anchored { Anchored::Yes } else { Anchored::No });
+    let span = |mat: aho_corasick::Match| Span {
+        start: mat.start(),
+        end: mat.end(),
+        pattern_index: mat.pattern().as_u32(),
+    };
+    if options.overlapping && options.match_kind == MatchKind::Standard {
+        limit(automaton.find_overlapping_iter(input).map(span), max_matches)
+    } else {
+        limit(automaton.find_iter(input).map(span), max_matches)
+    }
+}
+
+/// The regex source for a pattern, made case-insensitive when requested
+#[cfg(any(feature = "text", feature = "wasm"))]
+pub(crate) fn regex_source(pattern: &str, case_sensitive: bool) -> String {
+    if case_sensitive {
+        pattern.to_string()
+    } else {
+        format!("(?i){}", pattern)
+    }
+}
+
+/// Regex matches in a text, stopping after `max_matches` (0 for unlimited)
+#[cfg(any(feature = "text", feature = "wasm"))]
+pub(crate) fn regex_matches(regex: &Regex, text: &str, max_matches: usize) -> Vec<Span> {
+    let matches = regex.find_iter(text).map(|mat| Span {
+        start: mat.start(),
+        end: mat.end(),
+        pattern_index: 0,
+    });
+    limit(matches, max_matches)
+}
+
+#[cfg(any(feature = "text", feature = "wasm"))]
+fn limit(matches: impl Iterator<Item = Span>, max_matches: usize) -> Vec<Span> {
+    if max_matches > 0 {
+        matches.take(max_matches).collect()
+    } else {
+        matches.collect()
+    }
+}
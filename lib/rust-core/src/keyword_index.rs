@@ -0,0 +1,323 @@
+//! In-memory keyword index with BM25 scoring
+//!
+//! Documents are tokenized into lowercased runs of letters and digits, so
+//! `config.rs` and `load_config` both match `config`, and kept in an
+//! inverted index of per-document term frequencies. Queries are scored with
+//! Okapi BM25, which complements vector similarity for exact-term lookups
+//! such as identifiers and error messages.
+//!
+//! A document can have several named fields (e.g. `path`, `title`, `body`).
+//! Each field keeps its own postings and length statistics. A query's score
+//! is the sum of its per-field BM25 scores, each multiplied by that field's
+//! boost. Indexes are saved as versioned JSON, optionally zstd- or
+//! gzip-compressed, and loaded back with `KeywordIndex.load`.
+
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use crate::atomic_write::{self, AtomicWriteOptions};
+use crate::compression::{self, CompressionOptions};
+use crate::errors::{self, ErrorCode};
+use crate::file_lock;
+#[cfg(feature = "security")]
+use crate::security_utils::quota::{self, Mutation};
+
+/// Field that `addDocument` and `addDocuments` index text under
+const DEFAULT_FIELD: &str = "body";
+
+/// Version written by `save`; `load` rejects other versions
+const INDEX_VERSION: u32 = 1;
+
+/// BM25 scoring parameters
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bm25Config {
+    /// Term frequency saturation (typically 1.2-2.0)
+    pub k1: f64,
+    /// Document length normalization (0 for none, 1 for full)
+    pub b: f64,
+}
+
+impl Default for Bm25Config {
+    fn default() -> Self {
+        Self { k1: 1.2, b: 0.75 }
+    }
+}
+
+/// A document matching a keyword query
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeywordHit {
+    /// Index of the document in insertion order
+    pub index: u32,
+    /// Identifier the document was added with
+    pub id: String,
+    /// BM25 score
+    pub score: f64,
+}
+
+/// Postings and length statistics of one field
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FieldIndex {
+    /// Documents that have this field
+    documents: u32,
+    /// Tokens in this field across all documents
+    total_length: u64,
+    /// Token count of the field in each document (0 where absent)
+    lengths: Vec<u32>,
+    /// Term to (document, term frequency), in document order
+    postings: HashMap<String, Vec<(u32, u32)>>,
+}
+
+/// Inverted index over a growing set of documents
+#[napi]
+#[derive(Serialize, Deserialize)]
+pub struct KeywordIndex {
+    config: Bm25Config,
+    ids: Vec<String>,
+    fields: BTreeMap<String, FieldIndex>,
+}
+
+/// Index as written by `save`
+#[derive(Serialize)]
+struct SavedIndex<'a> {
+    version: u32,
+    index: &'a KeywordIndex,
+}
+
+/// Index as read by `load`
+#[derive(Deserialize)]
+struct LoadedIndex {
+    version: u32,
+    index: KeywordIndex,
+}
+
+#[napi]
+impl KeywordIndex {
+    /// Create an empty index
+    ///
+    /// # Arguments
+    /// * `config` - Optional BM25 parameters
+    #[napi(constructor)]
+    pub fn new(config: Option<Bm25Config>) -> napi::Result<Self> {
+        errors::guard("new KeywordIndex", || {
+            let config = config.unwrap_or_default();
+            if config.k1 < 0.0 || !(0.0..=1.0).contains(&config.b) {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "k1 must be non-negative and b between 0 and 1".to_string(),
+                ));
+            }
+            Ok(Self {
+                config,
+                ids: Vec::new(),
+                fields: BTreeMap::new(),
+            })
+        })
+    }
+
+    /// Load an index written by `save`, compressed or not
+    #[napi(factory)]
+    pub fn load(path: String) -> napi::Result<Self> {
+        errors::guard("KeywordIndex.load", || {
+            let content = file_lock::read(Path::new(&path))
+                .and_then(compression::decompress_if_compressed)
+                .map_err(|e| errors::io(&e, format!("Failed to read keyword index {}: {}", path, e)))?;
+            let loaded: LoadedIndex = serde_json::from_slice(&content).map_err(|e| {
+                errors::new(ErrorCode::ParseError, format!("Invalid keyword index {}: {}", path, e))
+            })?;
+            if loaded.version != INDEX_VERSION {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Unsupported keyword index version {} in {}", loaded.version, path),
+                ));
+            }
+            Ok(loaded.index)
+        })
+    }
+
+    /// Write the index to a file as JSON
+    ///
+    /// # Arguments
+    /// * `path` - File to create or replace
+    /// * `compression` - Compress the JSON with this format and level
+    ///   (uncompressed if omitted)
+    #[napi]
+    pub fn save(&self, path: String, compression: Option<CompressionOptions>) -> napi::Result<()> {
+        errors::guard("KeywordIndex.save", || {
+            let saved = SavedIndex {
+                version: INDEX_VERSION,
+                index: self,
+            };
+            let mut contents = serde_json::to_vec(&saved).map_err(|e| {
+                errors::new(ErrorCode::Internal, format!("Failed to serialize keyword index: {}", e))
+            })?;
+            if let Some(options) = compression {
+                let format = compression::Format::parse(options.format.as_deref())?;
+                contents = compression::compress_bytes(format, format.level(options.level)?, &contents)
+                    .map_err(|e| errors::io(&e, format!("Failed to compress keyword index: {}", e)))?;
+            }
+            #[cfg(feature = "security")]
+            quota::charge("keyword_index_save", Mutation::Write, Path::new(&path), contents.len() as u64)?;
+            let _lock = file_lock::lock(Path::new(&path), false, file_lock::DEFAULT_TIMEOUT)?;
+            atomic_write::write(Path::new(&path), &contents, &AtomicWriteOptions::default()).map_err(|e| {
+                errors::io(&e, format!("Failed to write keyword index {}: {}", path, e))
+            })
+        })
+    }
+
+    /// Add a document with a single `body` field and return its index
+    #[napi]
+    pub fn add_document(&mut self, id: String, text: String) -> u32 {
+        self.insert(id, [(DEFAULT_FIELD, text.as_str())])
+    }
+
+    /// Add a document with named fields and return its index
+    ///
+    /// # Arguments
+    /// * `id` - Identifier returned in search results
+    /// * `fields` - Field name to text, e.g. `{ path, title, body }`
+    #[napi]
+    pub fn add_fields(&mut self, id: String, fields: HashMap<String, String>) -> u32 {
+        self.insert(id, fields.iter().map(|(name, text)| (name.as_str(), text.as_str())))
+    }
+
+    /// Add several documents; `ids` and `texts` are paired by position
+    #[napi]
+    pub fn add_documents(&mut self, ids: Vec<String>, texts: Vec<String>) -> napi::Result<()> {
+        errors::guard("KeywordIndex.addDocuments", || {
+            if ids.len() != texts.len() {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "Number of ids and texts must match".to_string(),
+                ));
+            }
+            for (id, text) in ids.into_iter().zip(texts) {
+                self.add_document(id, text);
+            }
+            Ok(())
+        })
+    }
+
+    /// Number of documents in the index
+    #[napi(getter)]
+    pub fn document_count(&self) -> u32 {
+        self.ids.len() as u32
+    }
+
+    /// Names of the indexed fields, sorted
+    #[napi(getter)]
+    pub fn field_names(&self) -> Vec<String> {
+        self.fields.keys().cloned().collect()
+    }
+
+    /// Find the `top_k` documents with the highest BM25 score for a query
+    ///
+    /// # Arguments
+    /// * `query` - Query text
+    /// * `top_k` - Maximum number of hits
+    /// * `boosts` - Multiplier per field (default 1; 0 ignores the field)
+    #[napi]
+    pub fn search(&self, query: String, top_k: u32, boosts: Option<HashMap<String, f64>>) -> napi::Result<Vec<KeywordHit>> {
+        errors::guard("KeywordIndex.search", || {
+            Ok(self
+                .ranked(&query, boosts.as_ref())?
+                .into_iter()
+                .take(top_k as usize)
+                .map(|(index, score)| KeywordHit {
+                    index: index as u32,
+                    id: self.ids[index].clone(),
+                    score,
+                })
+                .collect())
+        })
+    }
+}
+
+impl KeywordIndex {
+    /// Identifier of a document
+    pub(crate) fn id(&self, index: usize) -> &str {
+        &self.ids[index]
+    }
+
+    /// Documents with a positive score for `query`, best first
+    ///
+    /// Ties are broken by insertion order so rankings are deterministic.
+    /// Boosts for fields the index does not have are ignored.
+    pub(crate) fn ranked(&self, query: &str, boosts: Option<&HashMap<String, f64>>) -> napi::Result<Vec<(usize, f64)>> {
+        if let Some((field, _)) = boosts.and_then(|b| b.iter().find(|(_, &boost)| !(boost.is_finite() && boost >= 0.0))) {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Boost for field '{}' must be a non-negative number", field),
+            ));
+        }
+
+        let terms: HashSet<String> = tokenize(query).collect();
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+        for (name, field) in &self.fields {
+            let boost = boosts.and_then(|b| b.get(name)).copied().unwrap_or(1.0);
+            if boost > 0.0 {
+                field.score(&terms, &self.config, boost, &mut scores);
+            }
+        }
+
+        let mut ranked: Vec<(usize, f64)> = scores.into_iter().filter(|&(_, score)| score > 0.0).collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        Ok(ranked)
+    }
+
+    /// Index a document's fields and return its index
+    fn insert<'a>(&mut self, id: String, fields: impl IntoIterator<Item = (&'a str, &'a str)>) -> u32 {
+        let index = self.ids.len() as u32;
+        for (name, text) in fields {
+            self.fields.entry(name.to_string()).or_default().insert(index, text);
+        }
+        self.ids.push(id);
+        index
+    }
+}
+
+impl FieldIndex {
+    fn insert(&mut self, document: u32, text: &str) {
+        let mut frequencies: HashMap<String, u32> = HashMap::new();
+        let mut length = 0u32;
+        for token in tokenize(text) {
+            *frequencies.entry(token).or_default() += 1;
+            length += 1;
+        }
+        for (term, frequency) in frequencies {
+            self.postings.entry(term).or_default().push((document, frequency));
+        }
+        self.lengths.resize(document as usize, 0);
+        self.lengths.push(length);
+        self.documents += 1;
+        self.total_length += length as u64;
+    }
+
+    /// Add this field's boosted BM25 score for `terms` to each document
+    fn score(&self, terms: &HashSet<String>, config: &Bm25Config, boost: f64, scores: &mut HashMap<usize, f64>) {
+        let count = self.documents as f64;
+        let average_length = (self.total_length as f64 / count.max(1.0)).max(1.0);
+        let Bm25Config { k1, b } = *config;
+
+        for term in terms {
+            let Some(postings) = self.postings.get(term) else { continue };
+            let frequency = postings.len() as f64;
+            let idf = (1.0 + (count - frequency + 0.5) / (frequency + 0.5)).ln();
+            for &(document, tf) in postings {
+                let tf = tf as f64;
+                let length = self.lengths[document as usize] as f64;
+                let norm = k1 * (1.0 - b + b * length / average_length);
+                *scores.entry(document as usize).or_default() += boost * idf * tf * (k1 + 1.0) / (tf + norm);
+            }
+        }
+    }
+}
+
+/// Lowercased runs of letters and digits in a text
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_lowercase)
+}
@@ -0,0 +1,398 @@
+//! Language detection and line counting for project overviews
+//!
+//! Files are classified by well-known file names (`Makefile`, `Dockerfile`),
+//! then by extension. Anything else is classified by its content: a `#!`
+//! interpreter line (`#!/usr/bin/env python3`), or a Vim (`vim: ft=sh`) or
+//! Emacs (`-*- mode: ruby -*-`) modeline in its first or last five lines, so
+//! extensionless scripts are still recognised. Names are display names
+//! ("Rust", "TypeScript", "Shell") shared by every language-aware report and
+//! the `languages` search filter.
+//!
+//! Lines of code are counted while the file is streamed, without holding
+//! it in memory: a line counts if it has any non-whitespace byte. Files
+//! with a NUL byte near the start are treated as binary and count none.
+
+use crate::concurrency;
+use crate::errors;
+use crate::metrics;
+use napi_derive::napi;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Read size when counting lines
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Leading bytes checked for NUL to detect binary files
+const BINARY_SNIFF_LEN: usize = 8 * 1024;
+
+/// Leading and trailing bytes kept for shebang and modeline detection
+const HEAD_LEN: usize = 4 * 1024;
+const TAIL_LEN: usize = 1024;
+
+/// Lines at each end of a file searched for modelines, as Vim does
+const MODELINE_LINES: usize = 5;
+
+/// Exact file names, compared case-insensitively
+const FILE_NAMES: &[(&str, &str)] = &[
+    ("makefile", "Makefile"),
+    ("gnumakefile", "Makefile"),
+    ("dockerfile", "Dockerfile"),
+    ("containerfile", "Dockerfile"),
+    ("cmakelists.txt", "CMake"),
+    ("rakefile", "Ruby"),
+    ("gemfile", "Ruby"),
+    ("jenkinsfile", "Groovy"),
+    ("build", "Starlark"),
+    ("build.bazel", "Starlark"),
+    ("workspace", "Starlark"),
+    ("justfile", "Just"),
+];
+
+/// Extensions, compared case-insensitively
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("rs", "Rust"),
+    ("js", "JavaScript"),
+    ("mjs", "JavaScript"),
+    ("cjs", "JavaScript"),
+    ("jsx", "JavaScript"),
+    ("ts", "TypeScript"),
+    ("mts", "TypeScript"),
+    ("cts", "TypeScript"),
+    ("tsx", "TypeScript"),
+    ("py", "Python"),
+    ("pyi", "Python"),
+    ("rb", "Ruby"),
+    ("go", "Go"),
+    ("java", "Java"),
+    ("kt", "Kotlin"),
+    ("kts", "Kotlin"),
+    ("scala", "Scala"),
+    ("swift", "Swift"),
+    ("c", "C"),
+    ("h", "C"),
+    ("cc", "C++"),
+    ("cpp", "C++"),
+    ("cxx", "C++"),
+    ("hh", "C++"),
+    ("hpp", "C++"),
+    ("hxx", "C++"),
+    ("cs", "C#"),
+    ("fs", "F#"),
+    ("php", "PHP"),
+    ("sh", "Shell"),
+    ("bash", "Shell"),
+    ("zsh", "Shell"),
+    ("ksh", "Shell"),
+    ("fish", "Fish"),
+    ("ps1", "PowerShell"),
+    ("psm1", "PowerShell"),
+    ("bat", "Batch"),
+    ("cmd", "Batch"),
+    ("lua", "Lua"),
+    ("pl", "Perl"),
+    ("pm", "Perl"),
+    ("r", "R"),
+    ("dart", "Dart"),
+    ("ex", "Elixir"),
+    ("exs", "Elixir"),
+    ("erl", "Erlang"),
+    ("hrl", "Erlang"),
+    ("hs", "Haskell"),
+    ("ml", "OCaml"),
+    ("mli", "OCaml"),
+    ("clj", "Clojure"),
+    ("cljs", "Clojure"),
+    ("zig", "Zig"),
+    ("nim", "Nim"),
+    ("jl", "Julia"),
+    ("groovy", "Groovy"),
+    ("gradle", "Groovy"),
+    ("tcl", "Tcl"),
+    ("awk", "Awk"),
+    ("sql", "SQL"),
+    ("html", "HTML"),
+    ("htm", "HTML"),
+    ("css", "CSS"),
+    ("scss", "SCSS"),
+    ("sass", "Sass"),
+    ("less", "Less"),
+    ("vue", "Vue"),
+    ("svelte", "Svelte"),
+    ("json", "JSON"),
+    ("jsonc", "JSON"),
+    ("yaml", "YAML"),
+    ("yml", "YAML"),
+    ("toml", "TOML"),
+    ("xml", "XML"),
+    ("ini", "INI"),
+    ("md", "Markdown"),
+    ("markdown", "Markdown"),
+    ("rst", "reStructuredText"),
+    ("tex", "TeX"),
+    ("txt", "Text"),
+    ("proto", "Protocol Buffers"),
+    ("graphql", "GraphQL"),
+    ("gql", "GraphQL"),
+    ("tf", "HCL"),
+    ("hcl", "HCL"),
+    ("cmake", "CMake"),
+    ("mk", "Makefile"),
+    ("bzl", "Starlark"),
+];
+
+/// Interpreters named on `#!` lines, after version suffixes are stripped
+const INTERPRETERS: &[(&str, &str)] = &[
+    ("sh", "Shell"),
+    ("bash", "Shell"),
+    ("dash", "Shell"),
+    ("zsh", "Shell"),
+    ("ksh", "Shell"),
+    ("ash", "Shell"),
+    ("fish", "Fish"),
+    ("python", "Python"),
+    ("pypy", "Python"),
+    ("node", "JavaScript"),
+    ("nodejs", "JavaScript"),
+    ("bun", "JavaScript"),
+    ("deno", "TypeScript"),
+    ("ts-node", "TypeScript"),
+    ("tsx", "TypeScript"),
+    ("ruby", "Ruby"),
+    ("perl", "Perl"),
+    ("php", "PHP"),
+    ("lua", "Lua"),
+    ("luajit", "Lua"),
+    ("rscript", "R"),
+    ("pwsh", "PowerShell"),
+    ("tclsh", "Tcl"),
+    ("wish", "Tcl"),
+    ("awk", "Awk"),
+    ("gawk", "Awk"),
+    ("julia", "Julia"),
+    ("elixir", "Elixir"),
+    ("escript", "Erlang"),
+    ("runhaskell", "Haskell"),
+    ("groovy", "Groovy"),
+    ("scala", "Scala"),
+    ("make", "Makefile"),
+];
+
+/// Modeline file types that are neither interpreters, extensions nor
+/// lowercased language names
+const ALIASES: &[(&str, &str)] = &[
+    ("shell-script", "Shell"),
+    ("c++", "C++"),
+    ("javascriptreact", "JavaScript"),
+    ("typescriptreact", "TypeScript"),
+    ("js2", "JavaScript"),
+    ("cperl", "Perl"),
+    ("conf-unix", "INI"),
+    ("dosini", "INI"),
+    ("docker", "Dockerfile"),
+    ("gfm", "Markdown"),
+];
+
+/// What a single read of a file tells about it
+pub(crate) struct FileProfile {
+    /// Detected language
+    pub(crate) language: Option<&'static str>,
+    /// Bytes read
+    pub(crate) bytes: u64,
+    /// Non-blank lines (0 for binary files)
+    pub(crate) lines_of_code: u64,
+}
+
+/// Language of a file from its name and extension alone
+pub(crate) fn from_path(path: &Path) -> Option<&'static str> {
+    let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+    if let Some(language) = lookup(FILE_NAMES, &name) {
+        return Some(language);
+    }
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    lookup(EXTENSIONS, &extension)
+}
+
+/// Language named by a `#!` line, e.g. `#!/usr/bin/env python3`
+pub(crate) fn from_shebang(line: &str) -> Option<&'static str> {
+    let mut words = line.strip_prefix("#!")?.split_whitespace();
+    let mut program = basename(words.next()?);
+    if program == "env" {
+        // Skip env's options and variable assignments
+        program = words
+            .find(|word| !word.starts_with('-') && !word.contains('='))
+            .map(basename)?;
+    }
+    let program = program
+        .trim_end_matches(|c: char| c.is_ascii_digit() || c == '.')
+        .to_ascii_lowercase();
+    lookup(INTERPRETERS, &program)
+}
+
+/// Language set by a Vim or Emacs modeline on one line
+///
+/// Vim: `vim: set ft=python:`, `vi: filetype=sh`, `ex: syntax=perl`.
+/// Emacs: `-*- mode: ruby; coding: utf-8 -*-` or `-*- python -*-`.
+pub(crate) fn from_modeline(line: &str) -> Option<&'static str> {
+    vim_modeline(line).or_else(|| emacs_modeline(line)).and_then(from_name)
+}
+
+fn vim_modeline(line: &str) -> Option<&str> {
+    let start = ["vim:", "vi:", "ex:"].iter().find_map(|marker| {
+        line.match_indices(marker)
+            .find(|(i, _)| *i == 0 || line[..*i].ends_with(char::is_whitespace))
+            .map(|(i, _)| i + marker.len())
+    })?;
+    line[start..]
+        .split(|c: char| c.is_whitespace() || c == ':')
+        .find_map(|option| {
+            let (key, value) = option.split_once('=')?;
+            matches!(key, "ft" | "filetype" | "syn" | "syntax").then_some(value)
+        })
+}
+
+fn emacs_modeline(line: &str) -> Option<&str> {
+    let (_, rest) = line.split_once("-*-")?;
+    let (variables, _) = rest.split_once("-*-")?;
+    if !variables.contains(':') {
+        return Some(variables.trim());
+    }
+    variables.split(';').find_map(|variable| {
+        let (key, value) = variable.split_once(':')?;
+        key.trim().eq_ignore_ascii_case("mode").then(|| value.trim())
+    })
+}
+
+/// Language for a name used by modelines: an interpreter, extension,
+/// language or alias, with Emacs' `-mode` suffix allowed
+fn from_name(name: &str) -> Option<&'static str> {
+    let name = name.to_ascii_lowercase();
+    let name = name.strip_suffix("-mode").unwrap_or(&name);
+    lookup(INTERPRETERS, name)
+        .or_else(|| lookup(EXTENSIONS, name))
+        .or_else(|| lookup(ALIASES, name))
+        .or_else(|| {
+            EXTENSIONS
+                .iter()
+                .chain(FILE_NAMES)
+                .map(|(_, language)| *language)
+                .find(|language| language.eq_ignore_ascii_case(name))
+        })
+}
+
+/// Language from a file's leading and trailing bytes
+fn from_content(head: &[u8], tail: &[u8]) -> Option<&'static str> {
+    let head = String::from_utf8_lossy(head);
+    let tail = String::from_utf8_lossy(tail);
+    let first = head.lines().next().unwrap_or("");
+    from_shebang(first)
+        .or_else(|| head.lines().take(MODELINE_LINES).find_map(from_modeline))
+        .or_else(|| tail.lines().rev().take(MODELINE_LINES).find_map(from_modeline))
+}
+
+fn lookup(table: &[(&str, &'static str)], key: &str) -> Option<&'static str> {
+    table.iter().find(|(known, _)| *known == key).map(|(_, language)| *language)
+}
+
+fn basename(program: &str) -> &str {
+    program.rsplit('/').next().unwrap_or(program)
+}
+
+/// Language of a file, reading its first and last bytes only if its name is not enough
+pub(crate) fn detect(path: &Path) -> io::Result<Option<&'static str>> {
+    if let Some(language) = from_path(path) {
+        return Ok(Some(language));
+    }
+    let mut file = concurrency::open_file(path)?;
+    let mut head = Vec::with_capacity(HEAD_LEN);
+    (&mut *file).take(HEAD_LEN as u64).read_to_end(&mut head)?;
+    let mut tail = Vec::new();
+    let length = file.metadata()?.len();
+    if length > HEAD_LEN as u64 {
+        file.seek(SeekFrom::Start(length.saturating_sub(TAIL_LEN as u64).max(HEAD_LEN as u64)))?;
+        (&mut *file).take(TAIL_LEN as u64).read_to_end(&mut tail)?;
+    }
+    Ok(from_content(&head, &tail))
+}
+
+/// Language of a file whose contents are already in memory
+pub(crate) fn detect_in(path: &Path, content: &[u8]) -> Option<&'static str> {
+    from_path(path).or_else(|| {
+        let head = &content[..content.len().min(HEAD_LEN)];
+        let tail = if content.len() > HEAD_LEN {
+            &content[content.len().saturating_sub(TAIL_LEN).max(HEAD_LEN)..]
+        } else {
+            &[]
+        };
+        from_content(head, tail)
+    })
+}
+
+/// Detect a file's language and count its lines of code in one pass
+pub(crate) fn profile(path: &Path) -> io::Result<FileProfile> {
+    profile_with(path, |_| {})
+}
+
+/// `profile`, also passing every chunk read to `inspect` (e.g. a hasher)
+pub(crate) fn profile_with(path: &Path, mut inspect: impl FnMut(&[u8])) -> io::Result<FileProfile> {
+    let mut file = concurrency::open_file(path)?;
+    let mut buffer = vec![0u8; READ_CHUNK_SIZE];
+    let mut head = Vec::new();
+    let mut tail = Vec::new();
+    let mut bytes = 0u64;
+    let mut lines_of_code = 0u64;
+    let mut line_has_content = false;
+    let mut binary = false;
+
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        let chunk = &buffer[..n];
+        inspect(chunk);
+        if (bytes as usize) < BINARY_SNIFF_LEN {
+            let sniff = (BINARY_SNIFF_LEN - bytes as usize).min(n);
+            binary |= chunk[..sniff].contains(&0);
+        }
+        if head.len() < HEAD_LEN {
+            head.extend_from_slice(&chunk[..(HEAD_LEN - head.len()).min(n)]);
+        }
+        // The tail may overlap the head when the file is short; modelines
+        // are then found in the head first anyway
+        tail.extend_from_slice(&chunk[n.saturating_sub(TAIL_LEN)..]);
+        tail.drain(..tail.len().saturating_sub(TAIL_LEN));
+        bytes += n as u64;
+        if binary {
+            continue;
+        }
+        for &byte in chunk {
+            if byte == b'\n' {
+                lines_of_code += line_has_content as u64;
+                line_has_content = false;
+            } else if !byte.is_ascii_whitespace() {
+                line_has_content = true;
+            }
+        }
+    }
+    lines_of_code += line_has_content as u64;
+    metrics::add_bytes("languages", bytes);
+
+    let language = from_path(path).or_else(|| from_content(&head, &tail));
+    Ok(FileProfile {
+        language,
+        bytes,
+        lines_of_code: if binary { 0 } else { lines_of_code },
+    })
+}
+
+/// Detect a file's language from its name, extension, `#!` line or modeline
+///
+/// Returns a display name such as "Python", or null if unrecognised.
+#[napi]
+pub fn detect_language(path: String) -> napi::Result<Option<String>> {
+    errors::guard("detectLanguage", || {
+        detect(Path::new(&path))
+            .map(|language| language.map(str::to_string))
+            .map_err(|e| errors::io(&e, format!("Failed to read {}: {}", path, e)))
+    })
+}
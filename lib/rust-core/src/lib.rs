@@ -4,23 +4,183 @@
 //! - Vector operations and similarity calculations
 //! - Fast file system operations and search
 //! - Text processing and pattern matching
+//! - Line ending and whitespace formatting checks
+//! - Markdown and HTML structure extraction
+//! - CSV/TSV parsing
+//! - Base64, hex and URL encoding of buffers
+//! - Identifier splitting and case conversion
+//! - External-memory line sorting
+//! - Phonetic encoding and typo-tolerant matching
+//! - Syntax highlighting token streams
+//! - Streaming Blake3 and SHA-256 hashing, HMAC and Ed25519 signatures
 //! - Security utilities and path validation
+//! - Opt-in audit log of validations, denials and file mutations
+//! - Replace-in-files, normalization and bulk copy/move/delete with dry runs
+//! - Transaction journal with before-images for commit and rollback
+//! - Advisory file locks shared between processes, taken by writers of shared files
+//! - Atomic file writes (temp file, fsync, rename) used by every native writer
+//! - gzip and zstd compression of buffers, files and streams
+//! - Quotas on files written, deleted and bytes written per run
+//! - Static ReDoS analysis of user-supplied regexes
+//! - Constant-time comparison, zeroizing secret buffers and secure deletion
+//! - Quarantine of flagged files with signed metadata records
+//! - Secret scanning with built-in rules and entropy detection
+//! - Credential leak detection in .env, YAML and JSON config files
+//! - PII detection with checksum validation
+//! - Trojan-source and Unicode spoofing detection
+//! - Near-duplicate detection with MinHash signatures
+//! - License detection from SPDX tags and license texts
+//! - Dependency inventory from lockfiles and CycloneDX SBOM generation
+//! - Known-vulnerability matching against offline OSV advisories
+//! - Allocation counters and process memory statistics
+//! - Deterministic synthetic file trees and embedding corpora
+//! - Machine-readable error codes on every native error
+//! - Runtime metrics: operation counts, durations, bytes processed and cache hit rates
+//! - Project configuration from moidvk.toml
+//! - Language detection from file names, extensions, `#!` lines and
+//!   modelines, with per-language totals and a search filter
+//! - Opt-in cache of directory, extension and duplicate scans with invalidation
+//! - Paged file and text searches with resumable cursors
+//! - Token-aware text search matching only identifiers, strings, comments or code
+//! - Compact file and match results sharing root-relative path tables
+//! - Reports of the entries a scan skipped, with the reason for each
+//! - Symlink-depth and entry budgets bounding directory walks
+//! - Persistent file metadata database (size, mtime, hash, language, lines of
+//!   code) refreshed incrementally, for stats and duplicate queries without I/O
+//! - Persistent embedding cache keyed by content hash
+//! - Process-wide matcher caches and vector sets shared across worker threads,
+//!   built at once or from batches merged in the background
+//! - Limit on concurrent heavy operations with queue statistics, and a budget
+//!   of open files for parallel reads
+//! - Background scans on worker threads with lowered CPU and I/O priority
+//! - CPU capability detection: ISA extensions, cache sizes and dispatched kernels
+//! - BM25 keyword index with field boosts, saved to and loaded from disk
+//! - Hybrid keyword/vector search with rank fusion
+//! - Search, read and chunk pipeline producing embedding-ready records, with
+//!   incremental re-chunking from snapshots
+//! - wasm32 fallback build of the vector and text kernels (`wasm` feature)
+//!
+//! Module groups are behind Cargo features, all enabled by default: `vector`,
+//! `text`, `security`, `fs` (implies `security`) and `bench` (implies the
+//! rest). Embedders can build e.g. `--no-default-features --features text`
+//! without directory walking or hashing code.
 
 #![deny(clippy::all)]
 #![warn(missing_docs)]
+// The shared runtime (metrics, caches, limits, audit log) serves whichever
+// module groups are enabled, so partial feature sets leave parts of it unused
+#![cfg_attr(
+    not(all(feature = "vector", feature = "fs", feature = "text")),
+    allow(dead_code, unused_imports)
+)]
 
+#[cfg(feature = "node")]
 use napi_derive::napi;
 
-// Re-export all modules for Node.js
+// Re-export all modules for Node.js; see the features in Cargo.toml
+#[cfg(feature = "vector")]
 pub mod vector_ops;
+#[cfg(feature = "fs")]
 pub mod file_search;
+#[cfg(feature = "text")]
 pub mod text_processing;
+#[cfg(feature = "security")]
 pub mod security_utils;
+#[cfg(feature = "bench")]
 pub mod benchmarks;
+#[cfg(feature = "text")]
+pub mod near_duplicates;
+#[cfg(feature = "text")]
+pub mod text_format;
+#[cfg(feature = "text")]
+pub mod markup;
+#[cfg(feature = "text")]
+pub mod csv_parser;
+#[cfg(feature = "text")]
+pub mod encoding;
+#[cfg(feature = "text")]
+pub mod identifiers;
+#[cfg(feature = "fs")]
+pub mod line_sort;
+#[cfg(feature = "text")]
+pub mod fuzzy_match;
+#[cfg(feature = "text")]
+pub mod batch;
+#[cfg(feature = "text")]
+pub mod highlight;
+#[cfg(feature = "security")]
+pub mod hashing;
+#[cfg(all(feature = "fs", feature = "text"))]
+pub mod licenses;
+#[cfg(feature = "fs")]
+pub mod dependencies;
+#[cfg(all(feature = "fs", feature = "text"))]
+pub mod sbom;
+#[cfg(feature = "fs")]
+pub mod advisories;
+#[cfg(feature = "node")]
+pub mod audit;
+#[cfg(feature = "node")]
+pub mod file_lock;
+#[cfg(feature = "node")]
+pub mod atomic_write;
+#[cfg(feature = "node")]
+pub mod compression;
+#[cfg(feature = "node")]
+pub mod memory;
+#[cfg(feature = "bench")]
+pub mod synthetic;
+#[cfg(feature = "node")]
+pub mod errors;
+#[cfg(feature = "node")]
+pub mod metrics;
+#[cfg(feature = "fs")]
+pub mod config;
+#[cfg(feature = "fs")]
+pub mod languages;
+#[cfg(feature = "fs")]
+pub mod result_cache;
+#[cfg(feature = "fs")]
+pub mod search_cursor;
+#[cfg(feature = "fs")]
+pub mod skip_report;
+#[cfg(feature = "fs")]
+pub mod walk;
+#[cfg(feature = "fs")]
+pub mod metadata_db;
+#[cfg(feature = "fs")]
+pub mod tokens;
+#[cfg(feature = "fs")]
+pub mod compact;
+#[cfg(feature = "node")]
+pub mod shared;
+#[cfg(feature = "node")]
+pub mod concurrency;
+#[cfg(feature = "node")]
+pub mod cpu;
+#[cfg(feature = "node")]
+pub mod priority;
+#[cfg(all(feature = "fs", feature = "text"))]
+pub mod pipeline;
+#[cfg(all(feature = "fs", feature = "text"))]
+pub mod file_ops;
+#[cfg(feature = "fs")]
+pub mod journal;
+#[cfg(feature = "text")]
+pub mod keyword_index;
+#[cfg(all(feature = "vector", feature = "text"))]
+pub mod hybrid_search;
+#[cfg(all(feature = "vector", feature = "fs"))]
+pub mod embedding_cache;
+#[cfg(any(feature = "vector", feature = "text", feature = "wasm"))]
+pub mod kernels;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 /// Initialize the MOIDVK Rust core module
 /// 
 /// Returns a success message indicating the core has been initialized
+#[cfg(feature = "node")]
 #[napi]
 pub fn initialize_rust_core() -> napi::Result<String> {
     Ok("MOIDVK Rust core initialized successfully".to_string())
@@ -29,6 +189,7 @@ pub fn initialize_rust_core() -> napi::Result<String> {
 /// Get the version of the MOIDVK core crate
 /// 
 /// Returns the version string from Cargo.toml
+#[cfg(feature = "node")]
 #[napi]
 pub fn get_version() -> napi::Result<String> {
     Ok(env!("CARGO_PKG_VERSION").to_string())
@@ -36,25 +197,36 @@ pub fn get_version() -> napi::Result<String> {
 
 /// Get performance information about the Rust runtime
 /// 
-/// Returns JSON string with SIMD support, thread count, allocator info, etc.
+/// Returns JSON string with SIMD support, per-ISA capability flags
+/// (`avx2`, `avx512f`, `neon`, `sve`, ...), cache sizes in bytes (null where
+/// unknown), the kernels vector operations dispatch to, thread count,
+/// allocator info, etc.
+#[cfg(feature = "node")]
 #[napi]
 pub fn get_performance_info() -> napi::Result<String> {
-    let simd_support = {
-        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-        {
-            is_x86_feature_detected!("avx2")
-        }
-        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
-        {
-            false
-        }
-    };
-    
+    let isa = cpu::isa_extensions();
+    let simd_support = isa
+        .iter()
+        .any(|&(name, on)| on && matches!(name, "avx2" | "avx512f" | "neon" | "sve"));
+
+    #[allow(unused_mut)]
+    let mut kernels = serde_json::Map::new();
+    #[cfg(feature = "vector")]
+    for operation in ["cosine_similarity", "vector_norm"] {
+        kernels.insert(operation.to_string(), vector_ops::simd_kernel().into());
+    }
+
     let info = serde_json::json!({
         "simd_support": simd_support,
+        "isa": isa
+            .into_iter()
+            .map(|(name, on)| (name.to_string(), on.into()))
+            .collect::<serde_json::Map<_, _>>(),
+        "cache": cpu::cache_sizes(),
+        "kernels": kernels,
         "parallel_threads": rayon::current_num_threads(),
         "allocator": "mimalloc",
         "optimization_level": if cfg!(debug_assertions) { "debug" } else { "release" }
     });
     Ok(info.to_string())
-}
\ No newline at end of file
+}
@@ -0,0 +1,605 @@
+//! License detection across a repository
+//!
+//! Files are checked two ways. `SPDX-License-Identifier` tags are parsed in
+//! every file. License texts are identified in license-named files (or in
+//! every file when `match_all_files` is set). Before matching, the text is
+//! normalized: it is lowercased, punctuation is dropped, and copyright
+//! lines are removed. Short permissive licenses are then recognized
+//! by an exact hash of that form, or fuzzily by how many of the
+//! reference's word bigrams appear. The long copyleft licenses are
+//! recognized by distinctive phrases. Directory scans return per-file
+//! results and an aggregate count per license for compliance summaries.
+
+use crate::concurrency;
+use crate::errors::{self, ErrorCode};
+use crate::file_search::{FileSearch, FileSearchConfig};
+use crate::kernels::LineIndex;
+use crate::security_utils::read_text_file;
+use napi_derive::napi;
+use rayon::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// Reference texts for template matching, copyright lines omitted
+const TEMPLATES: &[(&str, &str)] = &[
+    (
+        "MIT",
+        "Permission is hereby granted, free of charge, to any person obtaining a copy of this \
+         software and associated documentation files (the \"Software\"), to deal in the Software \
+         without restriction, including without limitation the rights to use, copy, modify, \
+         merge, publish, distribute, sublicense, and/or sell copies of the Software, and to \
+         permit persons to whom the Software is furnished to do so, subject to the following \
+         conditions: The above copyright notice and this permission notice shall be included in \
+         all copies or substantial portions of the Software. THE SOFTWARE IS PROVIDED \"AS IS\", \
+         WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE \
+         WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN \
+         NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR \
+         OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT \
+         OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.",
+    ),
+    (
+        "ISC",
+        "Permission to use, copy, modify, and/or distribute this software for any purpose with \
+         or without fee is hereby granted, provided that the above copyright notice and this \
+         permission notice appear in all copies. THE SOFTWARE IS PROVIDED \"AS IS\" AND THE \
+         AUTHOR DISCLAIMS ALL WARRANTIES WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED \
+         WARRANTIES OF MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR \
+         ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER \
+         RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, \
+         NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR \
+         PERFORMANCE OF THIS SOFTWARE.",
+    ),
+    (
+        "0BSD",
+        "Permission to use, copy, modify, and/or distribute this software for any purpose with \
+         or without fee is hereby granted. THE SOFTWARE IS PROVIDED \"AS IS\" AND THE AUTHOR \
+         DISCLAIMS ALL WARRANTIES WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES \
+         OF MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, \
+         DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM \
+         LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR OTHER \
+         TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS \
+         SOFTWARE.",
+    ),
+    (
+        "BSD-2-Clause",
+        "Redistribution and use in source and binary forms, with or without modification, are \
+         permitted provided that the following conditions are met: 1. Redistributions of source \
+         code must retain the above copyright notice, this list of conditions and the following \
+         disclaimer. 2. Redistributions in binary form must reproduce the above copyright \
+         notice, this list of conditions and the following disclaimer in the documentation \
+         and/or other materials provided with the distribution. THIS SOFTWARE IS PROVIDED BY THE \
+         COPYRIGHT HOLDERS AND CONTRIBUTORS \"AS IS\" AND ANY EXPRESS OR IMPLIED WARRANTIES, \
+         INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR \
+         A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR \
+         CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR \
+         CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR \
+         SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON \
+         ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING \
+         NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF \
+         ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.",
+    ),
+    (
+        "BSD-3-Clause",
+        "Redistribution and use in source and binary forms, with or without modification, are \
+         permitted provided that the following conditions are met: 1. Redistributions of source \
+         code must retain the above copyright notice, this list of conditions and the following \
+         disclaimer. 2. Redistributions in binary form must reproduce the above copyright \
+         notice, this list of conditions and the following disclaimer in the documentation \
+         and/or other materials provided with the distribution. 3. Neither the name of the \
+         copyright holder nor the names of its contributors may be used to endorse or promote \
+         products derived from this software without specific prior written permission. THIS \
+         SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS \"AS IS\" AND ANY EXPRESS \
+         OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF \
+         MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL \
+         THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, \
+         SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT \
+         OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS \
+         INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT \
+         LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE \
+         OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.",
+    ),
+    (
+        "Unlicense",
+        "This is free and unencumbered software released into the public domain. Anyone is free \
+         to copy, modify, publish, use, compile, sell, or distribute this software, either in \
+         source code form or as a compiled binary, for any purpose, commercial or \
+         non-commercial, and by any means. In jurisdictions that recognize copyright laws, the \
+         author or authors of this software dedicate any and all copyright interest in the \
+         software to the public domain. We make this dedication for the benefit of the public at \
+         large and to the detriment of our heirs and successors. We intend this dedication to be \
+         an overt act of relinquishment in perpetuity of all present and future rights to this \
+         software under copyright law. THE SOFTWARE IS PROVIDED \"AS IS\", WITHOUT WARRANTY OF \
+         ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF \
+         MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL \
+         THE AUTHORS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF \
+         CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR \
+         THE USE OR OTHER DEALINGS IN THE SOFTWARE. For more information, please refer to \
+         <http://unlicense.org/>",
+    ),
+];
+
+/// Normalized phrases identifying long license texts
+///
+/// GPL-family texts don't state whether later versions apply, so they are
+/// reported without an `-only`/`-or-later` suffix.
+const MARKERS: &[(&str, &[&str])] = &[
+    (
+        "Apache-2.0",
+        &[
+            "apache license version 2 0 january 2004",
+            "terms and conditions for use reproduction and distribution",
+            "grant of patent license",
+        ],
+    ),
+    (
+        "GPL-2.0",
+        &[
+            "gnu general public license version 2 june 1991",
+            "terms and conditions for copying distribution and modification",
+        ],
+    ),
+    (
+        "GPL-3.0",
+        &[
+            "gnu general public license version 3 29 june 2007",
+            "the gnu general public license is a free copyleft license for software",
+        ],
+    ),
+    (
+        "LGPL-2.1",
+        &[
+            "gnu lesser general public license version 2 1 february 1999",
+            "terms and conditions for copying distribution and modification",
+        ],
+    ),
+    (
+        "LGPL-3.0",
+        &[
+            "gnu lesser general public license version 3 29 june 2007",
+            "incorporates the terms and conditions of version 3 of the gnu general public license",
+        ],
+    ),
+    (
+        "AGPL-3.0",
+        &[
+            "gnu affero general public license version 3 19 november 2007",
+            "remote network interaction",
+        ],
+    ),
+    (
+        "MPL-2.0",
+        &["mozilla public license version 2 0", "covered software", "larger work"],
+    ),
+    (
+        "EPL-2.0",
+        &[
+            "eclipse public license v 2 0",
+            "the accompanying program is provided under the terms of this eclipse public license",
+        ],
+    ),
+];
+
+/// File name stems treated as license files
+const LICENSE_FILE_STEMS: [&str; 5] = ["license", "licence", "copying", "unlicense", "notice"];
+
+/// Templates whose containment is within this of the best are considered
+/// ties, and the longer (more specific) template wins
+const TEMPLATE_TIE_MARGIN: f64 = 0.02;
+
+/// Configuration for license detection
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseConfig {
+    /// Minimum confidence (0-1) for a license text match to be reported
+    pub min_confidence: f64,
+    /// Match license texts in every file, not only license-named files
+    pub match_all_files: bool,
+    /// Traversal and exclusion settings for directory scans
+    pub search: Option<FileSearchConfig>,
+}
+
+impl Default for LicenseConfig {
+    fn default() -> Self {
+        Self {
+            min_confidence: 0.8,
+            match_all_files: false,
+            search: None,
+        }
+    }
+}
+
+/// A license identified in a file
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseMatch {
+    /// SPDX identifier of the license
+    pub spdx_id: String,
+    /// How it was found: "spdx_tag", "exact_text", "fuzzy_text" or "phrase"
+    pub kind: String,
+    /// Full SPDX expression the identifier came from (tags only)
+    pub expression: Option<String>,
+    /// Line number of the tag (1-based, tags only)
+    pub line_number: Option<u32>,
+    /// Confidence of the identification (0-1)
+    pub confidence: f64,
+}
+
+/// Licenses found in one file
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileLicenses {
+    /// File path
+    pub path: String,
+    /// Whether the file name marks it as a license file
+    pub is_license_file: bool,
+    /// Licenses identified in the file
+    pub licenses: Vec<LicenseMatch>,
+}
+
+/// Number of files declaring one license
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseCount {
+    /// SPDX identifier
+    pub spdx_id: String,
+    /// Files in which it was found
+    pub file_count: u32,
+}
+
+/// License report for a directory tree
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseReport {
+    /// Files in which at least one license was found
+    pub files: Vec<FileLicenses>,
+    /// Per-license file counts, most common first
+    pub licenses: Vec<LicenseCount>,
+    /// License-named files whose text was not recognized
+    pub unidentified_license_files: Vec<String>,
+    /// Number of files scanned
+    pub files_scanned: u32,
+}
+
+/// Compiled reference text
+struct Template {
+    spdx_id: &'static str,
+    normalized: String,
+    hash: blake3::Hash,
+    bigrams: Vec<(String, String)>,
+}
+
+/// License scanner
+#[napi]
+pub struct LicenseScanner {
+    config: LicenseConfig,
+    search: FileSearch,
+    spdx_tag: Regex,
+    templates: Vec<Template>,
+}
+
+#[napi]
+impl LicenseScanner {
+    /// Create a new license scanner with optional configuration
+    ///
+    /// # Arguments
+    /// * `config` - Optional configuration for license detection
+    #[napi(constructor)]
+    pub fn new(config: Option<LicenseConfig>) -> napi::Result<Self> {
+        errors::guard("new LicenseScanner", || {
+            let config = config.unwrap_or_default();
+            let spdx_tag = Regex::new(r"SPDX-License-Identifier:[ \t]*([^\r\n]*)").map_err(|e| {
+                errors::new(ErrorCode::PatternInvalid, format!("Invalid SPDX pattern: {}", e))
+            })?;
+
+            let templates = TEMPLATES
+                .iter()
+                .map(|(spdx_id, text)| {
+                    let normalized = normalize_license_text(text, true);
+                    Template {
+                        spdx_id,
+                        hash: blake3::hash(normalized.as_bytes()),
+                        bigrams: bigrams(&normalized)
+                            .into_iter()
+                            .map(|(a, b)| (a.to_string(), b.to_string()))
+                            .collect(),
+                        normalized,
+                    }
+                })
+                .collect();
+
+            Ok(Self {
+                search: FileSearch::new(config.search.clone())?,
+                config,
+                spdx_tag,
+                templates,
+            })
+        })
+    }
+
+    /// Identify licenses in text
+    ///
+    /// SPDX tags and license texts are both checked.
+    ///
+    /// # Arguments
+    /// * `text` - Content to scan
+    #[napi]
+    pub fn detect_text(&self, text: String) -> napi::Result<Vec<LicenseMatch>> {
+        errors::catch_panic("LicenseScanner.detectText", || {
+            self.detect(&text, true)
+        })
+    }
+
+    /// Identify licenses in a single file (binary files yield no matches)
+    #[napi]
+    pub fn scan_file(&self, path: String) -> napi::Result<FileLicenses> {
+        errors::guard("LicenseScanner.scanFile", || {
+            self.scan_path(Path::new(&path)).map_err(|e| {
+                errors::io(&e, format!("Failed to read {}: {}", path, e))
+            })
+        })
+    }
+
+    /// Identify licenses across a directory tree and aggregate the results
+    #[napi]
+    pub fn scan_directory(&self, root_path: String) -> napi::Result<LicenseReport> {
+        errors::guard("LicenseScanner.scanDirectory", || {
+            let _permit = concurrency::acquire("LicenseScanner.scanDirectory")?;
+            let files = self.search.collect_files(Path::new(&root_path))?;
+            let use_parallel = self.config.search.as_ref().is_none_or(|search| search.use_parallel);
+
+            let mut results: Vec<FileLicenses> = if use_parallel && files.len() > 100 {
+                files.par_iter().filter_map(|path| self.scan_path(path).ok()).collect()
+            } else {
+                files.iter().filter_map(|path| self.scan_path(path).ok()).collect()
+            };
+            results.sort_by(|a, b| a.path.cmp(&b.path));
+
+            let mut counts: BTreeMap<&str, u32> = BTreeMap::new();
+            for result in &results {
+                let ids: HashSet<&str> = result.licenses.iter().map(|m| m.spdx_id.as_str()).collect();
+                for id in ids {
+                    *counts.entry(id).or_default() += 1;
+                }
+            }
+            let mut licenses: Vec<LicenseCount> = counts
+                .into_iter()
+                .map(|(spdx_id, file_count)| LicenseCount {
+                    spdx_id: spdx_id.to_string(),
+                    file_count,
+                })
+                .collect();
+            licenses.sort_by(|a, b| b.file_count.cmp(&a.file_count).then(a.spdx_id.cmp(&b.spdx_id)));
+
+            let unidentified_license_files = results
+                .iter()
+                .filter(|r| r.is_license_file && r.licenses.is_empty())
+                .map(|r| r.path.clone())
+                .collect();
+
+            Ok(LicenseReport {
+                licenses,
+                unidentified_license_files,
+                files_scanned: files.len() as u32,
+                files: results.into_iter().filter(|r| !r.licenses.is_empty()).collect(),
+            })
+        })
+    }
+
+    /// Read and scan one file
+    fn scan_path(&self, path: &Path) -> std::io::Result<FileLicenses> {
+        let is_license_file = is_license_file_name(path);
+        let licenses = match read_text_file(path)? {
+            Some(text) => self.detect(&text, is_license_file || self.config.match_all_files),
+            None => Vec::new(),
+        };
+        Ok(FileLicenses {
+            path: path.to_string_lossy().to_string(),
+            is_license_file,
+            licenses,
+        })
+    }
+
+    /// Collect SPDX tags and, if requested, license text matches
+    fn detect(&self, text: &str, match_texts: bool) -> Vec<LicenseMatch> {
+        let mut matches = self.spdx_tags(text);
+        if match_texts {
+            matches.extend(self.match_template(&normalize_license_text(text, true)));
+            matches.extend(self.match_markers(&normalize_license_text(text, false)));
+        }
+        matches
+    }
+
+    /// Parse `SPDX-License-Identifier` tags into one match per identifier
+    fn spdx_tags(&self, text: &str) -> Vec<LicenseMatch> {
+        let index = LineIndex::new(text);
+        let mut matches = Vec::new();
+
+        for caps in self.spdx_tag.captures_iter(text) {
+            let Some(group) = caps.get(1) else { continue };
+            let expression = clean_spdx_expression(group.as_str());
+            if expression.is_empty() {
+                continue;
+            }
+            let line_number = index.line_of(group.start());
+            for id in spdx_identifiers(&expression) {
+                matches.push(LicenseMatch {
+                    spdx_id: id,
+                    kind: "spdx_tag".to_string(),
+                    expression: Some(expression.clone()),
+                    line_number: Some(line_number),
+                    confidence: 1.0,
+                });
+            }
+        }
+        matches
+    }
+
+    /// Best template match by exact hash or bigram containment
+    fn match_template(&self, normalized: &str) -> Option<LicenseMatch> {
+        if normalized.is_empty() {
+            return None;
+        }
+        let hash = blake3::hash(normalized.as_bytes());
+        if let Some(template) = self.templates.iter().find(|t| t.hash == hash) {
+            return Some(text_match(template.spdx_id, "exact_text", 1.0));
+        }
+
+        let candidate = bigrams(normalized);
+        let scored: Vec<(&Template, f64)> = self
+            .templates
+            .iter()
+            .map(|template| {
+                let found = template
+                    .bigrams
+                    .iter()
+                    .filter(|(a, b)| candidate.contains(&(a.as_str(), b.as_str())))
+                    .count();
+                (template, found as f64 / template.bigrams.len().max(1) as f64)
+            })
+            .collect();
+
+        let best = scored.iter().map(|(_, score)| *score).fold(0.0, f64::max);
+        if best < self.config.min_confidence {
+            return None;
+        }
+        scored
+            .into_iter()
+            .filter(|(_, score)| best - score <= TEMPLATE_TIE_MARGIN)
+            .max_by_key(|(template, _)| template.normalized.len())
+            .map(|(template, score)| text_match(template.spdx_id, "fuzzy_text", score))
+    }
+
+    /// Licenses whose distinctive phrases appear in the text
+    fn match_markers(&self, normalized: &str) -> Vec<LicenseMatch> {
+        MARKERS
+            .iter()
+            .filter_map(|(spdx_id, phrases)| {
+                let found = phrases.iter().filter(|p| contains_phrase(normalized, p)).count();
+                let confidence = found as f64 / phrases.len() as f64;
+                (confidence >= self.config.min_confidence)
+                    .then(|| text_match(spdx_id, "phrase", confidence))
+            })
+            .collect()
+    }
+}
+
+fn text_match(spdx_id: &str, kind: &str, confidence: f64) -> LicenseMatch {
+    LicenseMatch {
+        spdx_id: spdx_id.to_string(),
+        kind: kind.to_string(),
+        expression: None,
+        line_number: None,
+        confidence,
+    }
+}
+
+/// Whether a file name looks like a license file (LICENSE, COPYING.md, ...)
+pub(crate) fn is_license_file_name(path: &Path) -> bool {
+    let Some(name) = path.file_name().map(|n| n.to_string_lossy().to_ascii_lowercase()) else {
+        return false;
+    };
+    LICENSE_FILE_STEMS.iter().any(|stem| {
+        name.strip_prefix(stem)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '-', '_']))
+    })
+}
+
+/// Lowercase words only, with copyright lines (and optionally title lines) removed
+///
+/// Titles are kept for phrase matching, where they carry the version.
+fn normalize_license_text(text: &str, drop_titles: bool) -> String {
+    let mut words = Vec::new();
+    for line in text.lines() {
+        let lowered = line.to_lowercase();
+        let content = lowered.trim_start_matches(|c: char| !c.is_alphanumeric() && c != '©' && c != '(');
+        if is_copyright_line(content) || (drop_titles && is_title_line(content)) {
+            continue;
+        }
+        words.extend(
+            lowered
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|w| !w.is_empty())
+                .map(str::to_string),
+        );
+    }
+    words.join(" ")
+}
+
+/// "Copyright (c) 2020 ...", "(c) 2020 ...", "© ..."
+fn is_copyright_line(line: &str) -> bool {
+    line.starts_with('©')
+        || line.starts_with("(c)")
+        || line.strip_prefix("copyright").is_some_and(|rest| {
+            let rest = rest.trim_start();
+            rest.starts_with("(c)")
+                || rest.starts_with('©')
+                || rest.starts_with(|c: char| c.is_ascii_digit())
+        })
+}
+
+/// Short headings such as "MIT License" or "The BSD 3-Clause License"
+fn is_title_line(line: &str) -> bool {
+    (line.contains("license") || line.contains("licence"))
+        && line.split_whitespace().count() <= 6
+        && !line.trim_end().ends_with('.')
+        && !line.contains(':')
+}
+
+/// Set of adjacent word pairs
+fn bigrams(normalized: &str) -> HashSet<(&str, &str)> {
+    let words: Vec<&str> = normalized.split(' ').collect();
+    words.windows(2).map(|pair| (pair[0], pair[1])).collect()
+}
+
+/// Phrase match on word boundaries
+fn contains_phrase(normalized: &str, phrase: &str) -> bool {
+    normalized.match_indices(phrase).any(|(start, _)| {
+        let end = start + phrase.len();
+        (start == 0 || normalized.as_bytes()[start - 1] == b' ')
+            && (end == normalized.len() || normalized.as_bytes()[end] == b' ')
+    })
+}
+
+/// Strip comment terminators and quotes trailing a tag value
+fn clean_spdx_expression(raw: &str) -> String {
+    let mut value = raw;
+    for terminator in ["*/", "-->", "#}", "--%>"] {
+        if let Some(pos) = value.find(terminator) {
+            value = &value[..pos];
+        }
+    }
+    value.trim().trim_matches(|c| c == '"' || c == '\'' || c == ',').trim().to_string()
+}
+
+/// License identifiers in an SPDX expression, without operators or exceptions
+fn spdx_identifiers(expression: &str) -> Vec<String> {
+    let mut ids = Vec::new();
+    let mut after_with = false;
+    for token in expression
+        .split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+        .filter(|t| !t.is_empty())
+    {
+        match token.to_ascii_uppercase().as_str() {
+            "AND" | "OR" => after_with = false,
+            "WITH" => after_with = true,
+            _ if after_with => after_with = false,
+            _ => {
+                if !ids.iter().any(|id| id == token) {
+                    ids.push(token.to_string());
+                }
+            }
+        }
+    }
+    ids
+}
+
+/// Quick license detection over text with default settings
+#[napi]
+pub fn quick_detect_licenses(text: String) -> napi::Result<Vec<LicenseMatch>> {
+    errors::guard("quickDetectLicenses", || {
+        let scanner = LicenseScanner::new(None)?;
+        scanner.detect_text(text)
+    })
+}
@@ -0,0 +1,432 @@
+//! External-memory line sorting and deduplication
+//!
+//! This module sorts line sets of any size: inputs that fit the memory budget
+//! are sorted in parallel in memory, larger inputs are split into sorted runs
+//! spilled to disk and combined with a k-way merge.
+
+use crate::atomic_write::{AtomicFile, AtomicWriteOptions};
+use crate::concurrency;
+use crate::errors::{self, ErrorCode};
+use crate::security_utils::quota::{self, Mutation};
+use napi_derive::napi;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::fs;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Options for line sorting
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SortOptions {
+    /// Treat the input as a file path rather than text
+    pub from_file: bool,
+    /// Compare embedded numbers by value ("file2" < "file10")
+    pub natural: bool,
+    /// Drop lines that compare equal to the previous line
+    pub unique: bool,
+    /// Compare lines case-insensitively
+    pub ignore_case: bool,
+    /// Sort in descending order
+    pub reverse: bool,
+    /// Memory budget in bytes before sorted runs are spilled to disk
+    pub max_memory_bytes: f64,
+    /// Write the result to this file instead of returning it
+    pub output_path: Option<String>,
+    /// Directory for temporary run files (system temp dir if omitted)
+    pub temp_dir: Option<String>,
+}
+
+impl Default for SortOptions {
+    fn default() -> Self {
+        Self {
+            from_file: false,
+            natural: false,
+            unique: false,
+            ignore_case: false,
+            reverse: false,
+            max_memory_bytes: 256.0 * 1024.0 * 1024.0,
+            output_path: None,
+            temp_dir: None,
+        }
+    }
+}
+
+/// Result of a sort operation
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SortResult {
+    /// Sorted text (when no output path was given)
+    pub text: Option<String>,
+    /// Output file path (when one was given)
+    pub output_path: Option<String>,
+    /// Number of lines written
+    pub line_count: f64,
+    /// Number of duplicate lines removed
+    pub duplicates_removed: f64,
+    /// Number of sorted runs spilled to disk (0 for in-memory sorts)
+    pub spilled_runs: u32,
+}
+
+/// Line comparator built from sort options
+struct LineComparator {
+    natural: bool,
+    ignore_case: bool,
+    reverse: bool,
+}
+
+impl LineComparator {
+    fn new(options: &SortOptions) -> Self {
+        Self {
+            natural: options.natural,
+            ignore_case: options.ignore_case,
+            reverse: options.reverse,
+        }
+    }
+
+    /// Compare two lines under the configured key (equality defines duplicates)
+    fn key_cmp(&self, a: &str, b: &str) -> Ordering {
+        let ordering = if self.natural {
+            natural_cmp(a, b, self.ignore_case)
+        } else if self.ignore_case {
+            a.chars()
+                .flat_map(char::to_lowercase)
+                .cmp(b.chars().flat_map(char::to_lowercase))
+        } else {
+            a.cmp(b)
+        };
+
+        if self.reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+
+    /// Total order: key comparison with a byte-wise tie-break for determinism
+    fn full_cmp(&self, a: &str, b: &str) -> Ordering {
+        self.key_cmp(a, b).then_with(|| a.cmp(b))
+    }
+}
+
+/// Compare strings treating runs of ASCII digits as numbers
+fn natural_cmp(a: &str, b: &str, ignore_case: bool) -> Ordering {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        if a[i].is_ascii_digit() && b[j].is_ascii_digit() {
+            let (start_a, start_b) = (i, j);
+            while i < a.len() && a[i].is_ascii_digit() {
+                i += 1;
+            }
+            while j < b.len() && b[j].is_ascii_digit() {
+                j += 1;
+            }
+
+            let num_a = trim_leading_zeros(&a[start_a..i]);
+            let num_b = trim_leading_zeros(&b[start_b..j]);
+            let ordering = num_a.len().cmp(&num_b.len()).then_with(|| num_a.cmp(num_b));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        } else {
+            let (x, y) = if ignore_case {
+                (a[i].to_ascii_lowercase(), b[j].to_ascii_lowercase())
+            } else {
+                (a[i], b[j])
+            };
+            if x != y {
+                return x.cmp(&y);
+            }
+            i += 1;
+            j += 1;
+        }
+    }
+
+    (a.len() - i).cmp(&(b.len() - j))
+}
+
+/// Strip leading zeros from a digit run, keeping at least one digit
+fn trim_leading_zeros(digits: &[u8]) -> &[u8] {
+    let first_non_zero = digits
+        .iter()
+        .position(|&d| d != b'0')
+        .unwrap_or(digits.len().saturating_sub(1));
+    &digits[first_non_zero..]
+}
+
+/// Sorted run files that are removed when dropped
+struct RunFiles {
+    paths: Vec<PathBuf>,
+}
+
+impl Drop for RunFiles {
+    fn drop(&mut self) {
+        for path in &self.paths {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Head line of a run during the k-way merge
+struct MergeEntry<'a> {
+    line: String,
+    run: usize,
+    comparator: &'a LineComparator,
+}
+
+impl PartialEq for MergeEntry<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MergeEntry<'_> {}
+
+impl PartialOrd for MergeEntry<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MergeEntry<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // BinaryHeap is a max-heap, so invert to pop the smallest line first
+        self.comparator
+            .full_cmp(&self.line, &other.line)
+            .then(self.run.cmp(&other.run))
+            .reverse()
+    }
+}
+
+/// Destination for sorted lines with optional deduplication
+struct LineSink<'a> {
+    writer: Box<dyn Write + 'a>,
+    comparator: &'a LineComparator,
+    unique: bool,
+    last: Option<String>,
+    written: u64,
+    duplicates: u64,
+}
+
+impl<'a> LineSink<'a> {
+    fn push(&mut self, line: String) -> std::io::Result<()> {
+        if self.unique {
+            if let Some(last) = &self.last {
+                if self.comparator.key_cmp(last, &line) == Ordering::Equal {
+                    self.duplicates += 1;
+                    return Ok(());
+                }
+            }
+        }
+
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.write_all(b"\n")?;
+        self.written += 1;
+        if self.unique {
+            self.last = Some(line);
+        }
+        Ok(())
+    }
+}
+
+/// Sort lines of text or a file, spilling to disk when the memory budget is exceeded
+///
+/// # Arguments
+/// * `input` - Text to sort, or a file path when `from_file` is set
+/// * `options` - Optional sort options
+#[napi]
+pub fn sort_lines(input: String, options: Option<SortOptions>) -> napi::Result<SortResult> {
+    errors::guard("sortLines", || {
+        let _permit = concurrency::acquire("sortLines")?;
+        let options = options.unwrap_or_default();
+
+        if let Some(output_path) = &options.output_path {
+            // Output is at most the input plus a trailing newline
+            let estimate = if options.from_file {
+                fs::metadata(&input).map(|m| m.len() + 1).unwrap_or(0)
+            } else {
+                input.len() as u64 + 1
+            };
+            quota::charge("sort_lines", Mutation::Write, Path::new(output_path), estimate)?;
+        }
+
+        if options.from_file {
+            let file = fs::File::open(&input).map_err(|e| {
+                errors::io(&e, format!("Failed to open {}: {}", input, e))
+            })?;
+            sort_reader(BufReader::new(file), &options)
+        } else {
+            sort_reader(BufReader::new(input.as_bytes()), &options)
+        }
+    })
+}
+
+/// Core external sort over any buffered reader
+fn sort_reader<R: BufRead>(reader: R, options: &SortOptions) -> napi::Result<SortResult> {
+    if options.max_memory_bytes <= 0.0 {
+        return Err(errors::new(
+            ErrorCode::InvalidArgument,
+            "max_memory_bytes must be positive".to_string(),
+        ));
+    }
+
+    let comparator = LineComparator::new(options);
+    let budget = options.max_memory_bytes as usize;
+    let temp_dir = options
+        .temp_dir
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir);
+
+    let mut runs = RunFiles { paths: Vec::new() };
+    let mut chunk: Vec<String> = Vec::new();
+    let mut chunk_bytes = 0usize;
+
+    for line in reader.lines() {
+        let line = line.map_err(|e| {
+            errors::io(&e, format!("Failed to read input: {}", e))
+        })?;
+        chunk_bytes += line.len() + std::mem::size_of::<String>();
+        chunk.push(line);
+
+        if chunk_bytes >= budget {
+            runs.paths.push(spill_run(&mut chunk, &comparator, &temp_dir, runs.paths.len())?);
+            chunk_bytes = 0;
+        }
+    }
+
+    let mut output = Vec::new();
+    // The output replaces its path only once complete, so a failed sort
+    // leaves any previous file (possibly the input) intact
+    let mut output_file = match &options.output_path {
+        Some(path) => Some(AtomicFile::create(Path::new(path), &AtomicWriteOptions::default()).map_err(|e| {
+            errors::io(&e, format!("Failed to create {}: {}", path, e))
+        })?),
+        None => None,
+    };
+    let mut sink = LineSink {
+        writer: match &mut output_file {
+            Some(file) => Box::new(BufWriter::new(file)),
+            None => Box::new(&mut output),
+        },
+        comparator: &comparator,
+        unique: options.unique,
+        last: None,
+        written: 0,
+        duplicates: 0,
+    };
+
+    let spilled_runs = if runs.paths.is_empty() {
+        chunk.par_sort_by(|a, b| comparator.full_cmp(a, b));
+        for line in chunk {
+            sink.push(line).map_err(write_error)?;
+        }
+        0
+    } else {
+        if !chunk.is_empty() {
+            runs.paths.push(spill_run(&mut chunk, &comparator, &temp_dir, runs.paths.len())?);
+        }
+        merge_runs(&runs.paths, &comparator, &mut sink)?;
+        runs.paths.len() as u32
+    };
+
+    sink.writer.flush().map_err(write_error)?;
+    let (line_count, duplicates_removed) = (sink.written as f64, sink.duplicates as f64);
+    drop(sink);
+    if let Some(file) = output_file {
+        file.commit().map_err(write_error)?;
+    }
+
+    Ok(SortResult {
+        text: match options.output_path {
+            Some(_) => None,
+            None => Some(String::from_utf8(output).unwrap_or_default()),
+        },
+        output_path: options.output_path.clone(),
+        line_count,
+        duplicates_removed,
+        spilled_runs,
+    })
+}
+
+/// Sort a chunk in parallel and write it to a temporary run file
+fn spill_run(
+    chunk: &mut Vec<String>,
+    comparator: &LineComparator,
+    temp_dir: &Path,
+    index: usize,
+) -> napi::Result<PathBuf> {
+    chunk.par_sort_by(|a, b| comparator.full_cmp(a, b));
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let path = temp_dir.join(format!("moidvk-sort-{}-{}-{}.run", std::process::id(), nanos, index));
+
+    let mut writer = BufWriter::new(create_file(&path)?);
+    for line in chunk.drain(..) {
+        writer.write_all(line.as_bytes()).map_err(write_error)?;
+        writer.write_all(b"\n").map_err(write_error)?;
+    }
+    writer.flush().map_err(write_error)?;
+
+    Ok(path)
+}
+
+/// K-way merge of sorted run files into the sink
+fn merge_runs(paths: &[PathBuf], comparator: &LineComparator, sink: &mut LineSink) -> napi::Result<()> {
+    let mut readers = Vec::with_capacity(paths.len());
+    for path in paths {
+        readers.push(BufReader::new(fs::File::open(path).map_err(read_error)?).lines());
+    }
+
+    let mut heap = BinaryHeap::with_capacity(readers.len());
+    for (run, reader) in readers.iter_mut().enumerate() {
+        if let Some(line) = reader.next() {
+            heap.push(MergeEntry {
+                line: line.map_err(read_error)?,
+                run,
+                comparator,
+            });
+        }
+    }
+
+    while let Some(MergeEntry { line, run, .. }) = heap.pop() {
+        sink.push(line).map_err(write_error)?;
+        if let Some(next) = readers[run].next() {
+            heap.push(MergeEntry {
+                line: next.map_err(read_error)?,
+                run,
+                comparator,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Create a file, reporting the path on failure
+fn create_file(path: &Path) -> napi::Result<fs::File> {
+    fs::File::create(path).map_err(|e| {
+        errors::io(
+            &e,
+            format!("Failed to create {}: {}", path.display(), e),
+        )
+    })
+}
+
+/// Report a failed write of sorted output or a spilled run
+fn write_error(e: std::io::Error) -> napi::Error {
+    errors::io(&e, format!("Failed to write sorted lines: {}", e))
+}
+
+/// Report a failed read of a spilled run
+fn read_error(e: std::io::Error) -> napi::Error {
+    errors::io(&e, format!("Failed to read sorted run: {}", e))
+}
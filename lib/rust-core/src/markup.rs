@@ -0,0 +1,289 @@
+//! Markdown and HTML structure extraction
+//!
+//! This module turns Markdown documents into a structured outline (headings,
+//! links, code fences and plain text) with byte spans and line numbers, and
+//! provides basic HTML tag stripping for documentation indexing.
+
+use crate::errors;
+use crate::kernels::LineIndex;
+use napi_derive::napi;
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+
+/// Markdown heading
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkdownHeading {
+    /// Heading level (1-6)
+    pub level: u32,
+    /// Heading text without markup
+    pub text: String,
+    /// Start byte offset in the source
+    pub start: u32,
+    /// End byte offset in the source
+    pub end: u32,
+    /// Line number (1-based)
+    pub line: u32,
+}
+
+/// Markdown link or image reference
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkdownLink {
+    /// Link destination
+    pub url: String,
+    /// Link title (if any)
+    pub title: Option<String>,
+    /// Link text or image alt text
+    pub text: String,
+    /// Whether this is an image
+    pub is_image: bool,
+    /// Start byte offset in the source
+    pub start: u32,
+    /// End byte offset in the source
+    pub end: u32,
+    /// Line number (1-based)
+    pub line: u32,
+}
+
+/// Fenced or indented code block
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkdownCodeBlock {
+    /// Info-string language (if any)
+    pub language: Option<String>,
+    /// Code block content
+    pub content: String,
+    /// Start byte offset in the source
+    pub start: u32,
+    /// End byte offset in the source
+    pub end: u32,
+    /// Line number (1-based)
+    pub line: u32,
+}
+
+/// Structured outline of a Markdown document
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentOutline {
+    /// Headings in document order
+    pub headings: Vec<MarkdownHeading>,
+    /// Links and images in document order
+    pub links: Vec<MarkdownLink>,
+    /// Code blocks in document order
+    pub code_blocks: Vec<MarkdownCodeBlock>,
+    /// Plain text content with markup removed
+    pub plain_text: String,
+}
+
+/// Extract headings, links, code blocks and plain text from Markdown
+#[napi]
+pub fn extract_markdown_structure(text: String) -> napi::Result<DocumentOutline> {
+    errors::catch_panic("extractMarkdownStructure", || {
+        let lines = LineIndex::new(&text);
+        let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+
+        let mut headings = Vec::new();
+        let mut links = Vec::new();
+        let mut code_blocks = Vec::new();
+        let mut plain_text = String::new();
+
+        // Text captured for the innermost open heading, link or code block
+        let mut heading: Option<(u32, usize, usize, String)> = None;
+        let mut link_stack: Vec<(String, Option<String>, bool, usize, usize, String)> = Vec::new();
+        let mut code: Option<(Option<String>, usize, usize, String)> = None;
+
+        for (event, range) in Parser::new_ext(&text, options).into_offset_iter() {
+            match event {
+                Event::Start(Tag::Heading { level, .. }) => {
+                    heading = Some((level as u32, range.start, range.end, String::new()));
+                }
+                Event::End(TagEnd::Heading(_)) => {
+                    if let Some((level, start, end, text)) = heading.take() {
+                        headings.push(MarkdownHeading {
+                            level,
+                            text: text.trim().to_string(),
+                            start: start as u32,
+                            end: end as u32,
+                            line: lines.line_of(start),
+                        });
+                    }
+                    plain_text.push('\n');
+                }
+                Event::Start(Tag::Link { dest_url, title, .. }) => {
+                    link_stack.push((dest_url.to_string(), non_empty(&title), false, range.start, range.end, String::new()));
+                }
+                Event::Start(Tag::Image { dest_url, title, .. }) => {
+                    link_stack.push((dest_url.to_string(), non_empty(&title), true, range.start, range.end, String::new()));
+                }
+                Event::End(TagEnd::Link) | Event::End(TagEnd::Image) => {
+                    if let Some((url, title, is_image, start, end, text)) = link_stack.pop() {
+                        links.push(MarkdownLink {
+                            url,
+                            title,
+                            text,
+                            is_image,
+                            start: start as u32,
+                            end: end as u32,
+                            line: lines.line_of(start),
+                        });
+                    }
+                }
+                Event::Start(Tag::CodeBlock(kind)) => {
+                    let language = match kind {
+                        CodeBlockKind::Fenced(info) => info.split_whitespace().next().map(|s| s.to_string()),
+                        CodeBlockKind::Indented => None,
+                    };
+                    code = Some((language, range.start, range.end, String::new()));
+                }
+                Event::End(TagEnd::CodeBlock) => {
+                    if let Some((language, start, end, content)) = code.take() {
+                        plain_text.push_str(&content);
+                        code_blocks.push(MarkdownCodeBlock {
+                            language,
+                            content,
+                            start: start as u32,
+                            end: end as u32,
+                            line: lines.line_of(start),
+                        });
+                    }
+                }
+                Event::End(TagEnd::Paragraph) | Event::End(TagEnd::Item) | Event::End(TagEnd::TableRow) => {
+                    plain_text.push('\n');
+                }
+                Event::Text(value) | Event::Code(value) => {
+                    if let Some((_, _, _, content)) = code.as_mut() {
+                        content.push_str(&value);
+                        continue;
+                    }
+                    if let Some((_, _, _, text)) = heading.as_mut() {
+                        text.push_str(&value);
+                    }
+                    if let Some((_, _, _, _, _, text)) = link_stack.last_mut() {
+                        text.push_str(&value);
+                    }
+                    plain_text.push_str(&value);
+                }
+                Event::Html(value) | Event::InlineHtml(value) => {
+                    plain_text.push_str(&strip_html(&value));
+                }
+                Event::SoftBreak | Event::HardBreak => {
+                    plain_text.push(if code.is_some() { '\n' } else { ' ' });
+                }
+                _ => {}
+            }
+        }
+
+        DocumentOutline {
+            headings,
+            links,
+            code_blocks,
+            plain_text: plain_text.trim().to_string(),
+        }
+    })
+}
+
+/// Strip HTML tags, drop script/style content and decode common entities
+#[napi]
+pub fn strip_html_tags(html: String) -> napi::Result<String> {
+    errors::catch_panic("stripHtmlTags", || {
+        strip_html(&html).trim().to_string()
+    })
+}
+
+/// Convert an empty title to `None`
+fn non_empty(value: &str) -> Option<String> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// Internal HTML stripping implementation
+fn strip_html(html: &str) -> String {
+    const BLOCK_TAGS: [&str; 12] = ["p", "div", "br", "li", "tr", "h1", "h2", "h3", "h4", "h5", "h6", "pre"];
+
+    let mut output = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(open) = rest.find('<') {
+        output.push_str(&decode_entities(&rest[..open]));
+        let after = &rest[open + 1..];
+
+        let Some(close) = after.find('>') else {
+            output.push_str(&decode_entities(&rest[open..]));
+            rest = "";
+            break;
+        };
+
+        let tag = &after[..close];
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        rest = &after[close + 1..];
+
+        if !tag.starts_with('/') && (name == "script" || name == "style") {
+            // Skip everything up to the matching closing tag
+            let closing = format!("</{}", name);
+            rest = match rest.to_ascii_lowercase().find(&closing) {
+                Some(pos) => rest[pos..].find('>').map(|i| &rest[pos + i + 1..]).unwrap_or(""),
+                None => "",
+            };
+        } else if BLOCK_TAGS.contains(&name.as_str()) && !output.ends_with('\n') && !output.is_empty() {
+            output.push('\n');
+        }
+    }
+
+    output.push_str(&decode_entities(rest));
+    output
+}
+
+/// Decode named and numeric HTML entities that commonly appear in docs
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(amp) = rest.find('&') {
+        output.push_str(&rest[..amp]);
+        let after = &rest[amp + 1..];
+        let decoded = after.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &after[..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ if entity.starts_with("#x") || entity.starts_with("#X") => {
+                    u32::from_str_radix(&entity[2..], 16).ok().and_then(char::from_u32)
+                }
+                _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(char::from_u32),
+                _ => None,
+            };
+            c.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                output.push(c);
+                rest = &after[end + 1..];
+            }
+            None => {
+                output.push('&');
+                rest = after;
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
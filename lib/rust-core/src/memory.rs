@@ -0,0 +1,169 @@
+//! Global allocator and memory statistics
+//!
+//! All native allocations go through mimalloc, wrapped in a thin layer that
+//! counts allocations and live bytes. Together with mimalloc's process
+//! information (resident set and committed memory) this lets the long-running
+//! server watch its memory behavior, and benchmarks report allocations per
+//! iteration and peak heap growth.
+//!
+//! The counters only see native allocations made by this module's Rust code,
+//! not the JavaScript heap.
+
+use mimalloc::MiMalloc;
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::alloc::{GlobalAlloc, Layout};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Bytes currently allocated
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+/// Highest value of `ALLOCATED` since start-up
+static PEAK: AtomicU64 = AtomicU64::new(0);
+/// Highest value of `ALLOCATED` since the last `reset_window_peak`
+static WINDOW_PEAK: AtomicU64 = AtomicU64::new(0);
+/// Allocations since start-up
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+/// Deallocations since start-up
+static DEALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+/// Bytes allocated since start-up, including freed ones
+static TOTAL_ALLOCATED: AtomicU64 = AtomicU64::new(0);
+
+/// mimalloc with allocation counters
+struct CountingAllocator;
+
+impl CountingAllocator {
+    fn on_alloc(size: usize) {
+        let size = size as u64;
+        let live = ALLOCATED.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+        WINDOW_PEAK.fetch_max(live, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        TOTAL_ALLOCATED.fetch_add(size, Ordering::Relaxed);
+    }
+
+    fn on_dealloc(size: usize) {
+        ALLOCATED.fetch_sub(size as u64, Ordering::Relaxed);
+        DEALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = MiMalloc.alloc(layout);
+        if !ptr.is_null() {
+            Self::on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = MiMalloc.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        MiMalloc.dealloc(ptr, layout);
+        Self::on_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = MiMalloc.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::on_dealloc(layout.size());
+            Self::on_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Native memory usage of the process
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryStats {
+    /// Bytes currently allocated by native code
+    pub allocated_bytes: f64,
+    /// Most bytes allocated at once since start-up
+    pub peak_allocated_bytes: f64,
+    /// Allocations since start-up
+    pub allocation_count: f64,
+    /// Deallocations since start-up
+    pub deallocation_count: f64,
+    /// Bytes allocated since start-up, including freed ones
+    pub total_allocated_bytes: f64,
+    /// Resident set size of the whole process (estimated on Linux)
+    pub current_rss_bytes: f64,
+    /// Peak resident set size of the whole process
+    pub peak_rss_bytes: f64,
+    /// Memory committed by mimalloc
+    pub committed_bytes: f64,
+    /// Peak memory committed by mimalloc
+    pub peak_committed_bytes: f64,
+    /// Hard page faults of the process
+    pub page_faults: f64,
+}
+
+/// Get native allocator and process memory statistics
+#[napi]
+pub fn get_memory_stats() -> MemoryStats {
+    let counters = counters();
+    let (mut current_rss, mut peak_rss, mut current_commit, mut peak_commit, mut page_faults) = (0, 0, 0, 0, 0);
+    // SAFETY: every out-parameter is either null or points to a live usize
+    unsafe {
+        libmimalloc_sys::mi_process_info(
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            &mut current_rss,
+            &mut peak_rss,
+            &mut current_commit,
+            &mut peak_commit,
+            &mut page_faults,
+        );
+    }
+
+    MemoryStats {
+        allocated_bytes: counters.allocated as f64,
+        peak_allocated_bytes: PEAK.load(Ordering::Relaxed) as f64,
+        allocation_count: counters.allocations as f64,
+        deallocation_count: DEALLOCATIONS.load(Ordering::Relaxed) as f64,
+        total_allocated_bytes: counters.total_allocated as f64,
+        current_rss_bytes: current_rss as f64,
+        peak_rss_bytes: peak_rss as f64,
+        committed_bytes: current_commit as f64,
+        peak_committed_bytes: peak_commit as f64,
+        page_faults: page_faults as f64,
+    }
+}
+
+/// Allocation counters at one point in time
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Counters {
+    pub(crate) allocated: u64,
+    pub(crate) allocations: u64,
+    pub(crate) total_allocated: u64,
+}
+
+/// Read the allocation counters
+pub(crate) fn counters() -> Counters {
+    Counters {
+        allocated: ALLOCATED.load(Ordering::Relaxed),
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        total_allocated: TOTAL_ALLOCATED.load(Ordering::Relaxed),
+    }
+}
+
+/// Start a new window for `window_peak`
+pub(crate) fn reset_window_peak() {
+    WINDOW_PEAK.store(ALLOCATED.load(Ordering::Relaxed), Ordering::Relaxed);
+}
+
+/// Most bytes allocated at once since the last `reset_window_peak`
+pub(crate) fn window_peak() -> u64 {
+    WINDOW_PEAK.load(Ordering::Relaxed)
+}
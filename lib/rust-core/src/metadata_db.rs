@@ -0,0 +1,428 @@
+//! Persistent store of file metadata
+//!
+//! A `FileMetadataDb` keeps the size, modification time, Blake3 content
+//! hash, detected language and lines of code of every file it has seen in
+//! an embedded sled database on disk. `refresh` brings a tree up to date
+//! like a snapshot diff: files whose size and mtime match their record are
+//! not read, new and changed files are read once to hash and profile them,
+//! and records of files that are gone are dropped. Between refreshes, a
+//! file watcher keeps the store current by passing changed paths to
+//! `updatePaths`.
+//!
+//! Queries (`get`, `query`, `findDuplicates`, `languageStats`) answer from
+//! the store alone, without stat-ing or reading files, so repeated
+//! statistics and duplicate scans over a large tree only cost the files
+//! that changed. Hashes are those `findDuplicateFiles` groups by.
+//!
+//! sled allows one open handle per database directory, so databases are
+//! shared by path within the process: opening a directory that is already
+//! open, e.g. from a worker thread or as an `EmbeddingCache`, reuses its
+//! handle.
+
+use crate::concurrency;
+use crate::errors::{self, ErrorCode};
+use crate::file_search::{FileGroupStats, FileSearch, FileSearchConfig};
+use crate::languages;
+use napi_derive::napi;
+use parking_lot::Mutex;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Version of stored records; records of other versions are re-read
+const RECORD_VERSION: u32 = 1;
+
+/// Databases opened by this process, by directory
+static OPEN: Mutex<BTreeMap<PathBuf, sled::Db>> = Mutex::new(BTreeMap::new());
+
+/// Stored metadata of one file
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileRecord {
+    /// Path as walked or passed to `updatePaths`
+    pub path: String,
+    /// File size in bytes
+    pub size: f64,
+    /// Last modified timestamp (milliseconds since Unix epoch)
+    pub last_modified: f64,
+    /// Blake3 hash of the contents, hex-encoded
+    pub hash: String,
+    /// Detected language
+    pub language: Option<String>,
+    /// Non-blank lines (0 for binary files)
+    pub lines_of_code: f64,
+}
+
+/// Filters for `FileMetadataDb.query`
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetadataQuery {
+    /// Only files under this directory
+    pub under: Option<String>,
+    /// Only files detected as this language (case-insensitive)
+    pub language: Option<String>,
+    /// Only files of at least this many bytes
+    pub min_size: Option<f64>,
+    /// Only files of at most this many bytes
+    pub max_size: Option<f64>,
+    /// Only files modified at or after this time (milliseconds since Unix epoch)
+    pub modified_since: Option<f64>,
+    /// Maximum records returned, in path order
+    pub limit: Option<u32>,
+}
+
+/// Changes made to the store by `refresh` or `updatePaths`
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetadataRefresh {
+    /// Files recorded for the first time
+    pub added: Vec<String>,
+    /// Files re-read because their size or modification time changed
+    pub modified: Vec<String>,
+    /// Files whose records were dropped because they are gone
+    pub removed: Vec<String>,
+    /// Files that could not be read; their records were dropped
+    pub failed: Vec<String>,
+    /// Files whose records were still current
+    pub unchanged: u32,
+    /// Whether the walk was cut short by `max_entries` or `max_results`;
+    /// records of files it didn't reach were kept rather than dropped
+    pub truncated: bool,
+}
+
+impl MetadataRefresh {
+    fn sorted(mut self) -> Self {
+        self.added.sort();
+        self.modified.sort();
+        self.removed.sort();
+        self.failed.sort();
+        self
+    }
+}
+
+/// Record as stored, keyed by path
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredRecord {
+    version: u32,
+    size: u64,
+    /// Modification time since the Unix epoch, to the nanosecond
+    modified_secs: u64,
+    modified_nanos: u32,
+    hash: String,
+    language: Option<String>,
+    lines_of_code: u64,
+}
+
+impl StoredRecord {
+    fn record(&self, path: String) -> FileRecord {
+        FileRecord {
+            path,
+            size: self.size as f64,
+            last_modified: self.modified_secs as f64 * 1000.0 + (self.modified_nanos / 1_000_000) as f64,
+            hash: self.hash.clone(),
+            language: self.language.clone(),
+            lines_of_code: self.lines_of_code as f64,
+        }
+    }
+}
+
+/// What updating one file did
+enum Update {
+    Added,
+    Modified,
+    Unchanged,
+}
+
+/// File metadata kept on disk and updated incrementally
+#[napi]
+pub struct FileMetadataDb {
+    db: sled::Db,
+    search: FileSearch,
+}
+
+#[napi]
+impl FileMetadataDb {
+    /// Open or create a metadata database
+    ///
+    /// # Arguments
+    /// * `path` - Directory holding the database (created if missing)
+    /// * `search` - Optional config for `refresh` walks (excludes, hidden files, sandbox...)
+    #[napi(constructor)]
+    pub fn new(path: String, search: Option<FileSearchConfig>) -> napi::Result<Self> {
+        errors::guard("new FileMetadataDb", || {
+            let search = FileSearch::new(search)?;
+            let db = open_db(&path)?;
+            Ok(Self { db, search })
+        })
+    }
+
+    /// Bring the records of files under `root` up to date
+    ///
+    /// Walks `root` with the search config, reads files that are new or
+    /// whose size or modification time changed, and drops records of files
+    /// under `root` that the walk no longer finds (unless it was cut short).
+    #[napi]
+    pub fn refresh(&self, root: String) -> napi::Result<MetadataRefresh> {
+        errors::guard("FileMetadataDb.refresh", || {
+            let _permit = concurrency::acquire("FileMetadataDb.refresh")?;
+            let mut result = MetadataRefresh::default();
+            self.sync(Path::new(&root), &mut result)?;
+            Ok(result.sorted())
+        })
+    }
+
+    /// Update the records of paths reported changed, e.g. by a file watcher
+    ///
+    /// Existing files are re-read if their size or modification time
+    /// changed; existing directories are refreshed like `refresh`; records
+    /// of missing paths, and of files under them, are dropped. Paths are
+    /// not checked against the search config's filters.
+    #[napi]
+    pub fn update_paths(&self, paths: Vec<String>) -> napi::Result<MetadataRefresh> {
+        errors::guard("FileMetadataDb.updatePaths", || {
+            let mut result = MetadataRefresh::default();
+            for path in &paths {
+                let target = Path::new(path);
+                match fs::metadata(target) {
+                    Ok(metadata) if metadata.is_dir() => self.sync(target, &mut result)?,
+                    Ok(_) => self.apply(target, self.update(target), &mut result)?,
+                    Err(_) => result.removed.extend(self.remove_under(target)?),
+                }
+            }
+            Ok(result.sorted())
+        })
+    }
+
+    /// Get the stored record of a file
+    #[napi]
+    pub fn get(&self, path: String) -> napi::Result<Option<FileRecord>> {
+        errors::guard("FileMetadataDb.get", || {
+            self.stored(&path)
+                .map(|stored| stored.map(|stored| stored.record(path.clone())))
+                .map_err(|e| errors::io(&e, format!("Failed to read record of {}: {}", path, e)))
+        })
+    }
+
+    /// Find stored records matching all given filters, in path order
+    #[napi]
+    pub fn query(&self, query: Option<MetadataQuery>) -> napi::Result<Vec<FileRecord>> {
+        errors::guard("FileMetadataDb.query", || {
+            let query = query.unwrap_or_default();
+            let mut records = Vec::new();
+            for record in self.records(query.under.as_deref()) {
+                if query.limit.is_some_and(|limit| records.len() >= limit as usize) {
+                    break;
+                }
+                let matches = query.language.as_ref().is_none_or(|language| {
+                    record.language.as_ref().is_some_and(|l| l.eq_ignore_ascii_case(language))
+                }) && query.min_size.is_none_or(|min| record.size >= min)
+                    && query.max_size.is_none_or(|max| record.size <= max)
+                    && query.modified_since.is_none_or(|since| record.last_modified >= since);
+                if matches {
+                    records.push(record);
+                }
+            }
+            Ok(records)
+        })
+    }
+
+    /// Group stored non-empty files by content hash, keeping groups of two or more
+    ///
+    /// # Arguments
+    /// * `under` - Only files under this directory (omit for all)
+    #[napi]
+    pub fn find_duplicates(&self, under: Option<String>) -> napi::Result<BTreeMap<String, Vec<String>>> {
+        errors::guard("FileMetadataDb.findDuplicates", || {
+            let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+            for record in self.records(under.as_deref()) {
+                if record.size > 0.0 {
+                    groups.entry(record.hash).or_default().push(record.path);
+                }
+            }
+            groups.retain(|_, paths| paths.len() > 1);
+            Ok(groups)
+        })
+    }
+
+    /// Count stored files, bytes and lines of code per language
+    ///
+    /// Keys match `getFileTypeStats` by language, with unrecognised files
+    /// under "<unknown>".
+    ///
+    /// # Arguments
+    /// * `under` - Only files under this directory (omit for all)
+    #[napi]
+    pub fn language_stats(&self, under: Option<String>) -> napi::Result<BTreeMap<String, FileGroupStats>> {
+        errors::guard("FileMetadataDb.languageStats", || {
+            let mut stats: BTreeMap<String, FileGroupStats> = BTreeMap::new();
+            for record in self.records(under.as_deref()) {
+                let group = stats
+                    .entry(record.language.unwrap_or_else(|| "<unknown>".to_string()))
+                    .or_default();
+                group.file_count += 1;
+                group.total_size += record.size;
+                group.lines_of_code += record.lines_of_code;
+            }
+            Ok(stats)
+        })
+    }
+
+    /// Drop the records of a file or of every file under a directory
+    ///
+    /// Returns the number of records dropped.
+    #[napi]
+    pub fn remove(&self, path: String) -> napi::Result<u32> {
+        errors::guard("FileMetadataDb.remove", || Ok(self.remove_under(Path::new(&path))?.len() as u32))
+    }
+
+    /// Write pending changes to disk (also done periodically in the background)
+    #[napi]
+    pub fn flush(&self) -> napi::Result<()> {
+        errors::guard("FileMetadataDb.flush", || {
+            self.db.flush().map(|_| ()).map_err(|e| db_error("flush", e))
+        })
+    }
+
+    /// Number of files with a stored record
+    #[napi(getter)]
+    pub fn file_count(&self) -> u32 {
+        self.db.len() as u32
+    }
+}
+
+impl FileMetadataDb {
+    /// Update every file found under `root` and drop records of the rest
+    ///
+    /// When the walk is cut short, unvisited files may still exist, so no
+    /// record is dropped for being missing.
+    fn sync(&self, root: &Path, result: &mut MetadataRefresh) -> napi::Result<()> {
+        let (files, truncated) = self.search.collect_files_partial(root)?;
+        result.truncated |= truncated;
+        let updates: Vec<(&PathBuf, io::Result<Update>)> = files.par_iter().map(|path| (path, self.update(path))).collect();
+
+        let found: HashSet<String> = files.iter().map(|path| path.to_string_lossy().to_string()).collect();
+        for (path, update) in updates {
+            self.apply(path, update, result)?;
+        }
+        if truncated {
+            return Ok(());
+        }
+
+        let stale: Vec<sled::IVec> = self
+            .entries_under(root)
+            .filter(|(key, _, _)| !found.contains(key))
+            .map(|(_, raw, _)| raw)
+            .collect();
+        for raw in stale {
+            self.db.remove(&raw).map_err(|e| db_error("remove", e))?;
+            result.removed.push(String::from_utf8_lossy(&raw).to_string());
+        }
+        Ok(())
+    }
+
+    /// Count one file's update in `result`, dropping its record if it failed
+    fn apply(&self, path: &Path, update: io::Result<Update>, result: &mut MetadataRefresh) -> napi::Result<()> {
+        let key = path.to_string_lossy().to_string();
+        match update {
+            Ok(Update::Added) => result.added.push(key),
+            Ok(Update::Modified) => result.modified.push(key),
+            Ok(Update::Unchanged) => result.unchanged += 1,
+            Err(_) => {
+                self.db.remove(key.as_bytes()).map_err(|e| db_error("remove", e))?;
+                result.failed.push(key);
+            }
+        }
+        Ok(())
+    }
+
+    /// Re-read a file unless its record matches its size and modification time
+    fn update(&self, path: &Path) -> io::Result<Update> {
+        let key = path.to_string_lossy();
+        let metadata = fs::metadata(path)?;
+        let modified = metadata.modified()?.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let previous = self.stored(&key)?;
+        if let Some(previous) = &previous {
+            if previous.version == RECORD_VERSION
+                && previous.size == metadata.len()
+                && previous.modified_secs == modified.as_secs()
+                && previous.modified_nanos == modified.subsec_nanos()
+            {
+                return Ok(Update::Unchanged);
+            }
+        }
+
+        let mut hasher = blake3::Hasher::new();
+        let profile = languages::profile_with(path, |chunk| {
+            hasher.update(chunk);
+        })?;
+        let stored = StoredRecord {
+            version: RECORD_VERSION,
+            size: profile.bytes,
+            modified_secs: modified.as_secs(),
+            modified_nanos: modified.subsec_nanos(),
+            hash: hasher.finalize().to_hex().to_string(),
+            language: profile.language.map(str::to_string),
+            lines_of_code: profile.lines_of_code,
+        };
+        let value = serde_json::to_vec(&stored).map_err(io::Error::other)?;
+        self.db.insert(key.as_bytes(), value)?;
+        Ok(if previous.is_some() { Update::Modified } else { Update::Added })
+    }
+
+    /// The stored record of a path, if any
+    fn stored(&self, key: &str) -> io::Result<Option<StoredRecord>> {
+        let Some(value) = self.db.get(key.as_bytes())? else { return Ok(None) };
+        Ok(serde_json::from_slice(&value).ok())
+    }
+
+    /// Records of files at or under `under` (all if `None`), in path order
+    fn records<'a>(&'a self, under: Option<&'a str>) -> impl Iterator<Item = FileRecord> + 'a {
+        self.entries_under(Path::new(under.unwrap_or(""))).filter_map(|(key, _, value)| {
+            let stored: StoredRecord = serde_json::from_slice(&value).ok()?;
+            Some(stored.record(key))
+        })
+    }
+
+    /// Stored entries at or under `root` as (path, raw key, value)
+    fn entries_under<'a>(&'a self, root: &'a Path) -> impl Iterator<Item = (String, sled::IVec, sled::IVec)> + 'a {
+        self.db
+            .scan_prefix(root.to_string_lossy().as_bytes())
+            .filter_map(|entry| entry.ok())
+            .map(|(raw, value)| (String::from_utf8_lossy(&raw).to_string(), raw, value))
+            .filter(move |(key, _, _)| Path::new(key).starts_with(root))
+    }
+
+    /// Drop the records at or under `root`, returning their paths
+    fn remove_under(&self, root: &Path) -> napi::Result<Vec<String>> {
+        let keys: Vec<(String, sled::IVec)> = self.entries_under(root).map(|(key, raw, _)| (key, raw)).collect();
+        let mut removed = Vec::with_capacity(keys.len());
+        for (key, raw) in keys {
+            self.db.remove(&raw).map_err(|e| db_error("remove", e))?;
+            removed.push(key);
+        }
+        Ok(removed)
+    }
+}
+
+/// Open or create the sled database in `path`, reusing this process's handle if it is open
+pub(crate) fn open_db(path: &str) -> napi::Result<sled::Db> {
+    fs::create_dir_all(path).map_err(|e| errors::io(&e, format!("Failed to create {}: {}", path, e)))?;
+    let directory = fs::canonicalize(path)
+        .map_err(|e| errors::io(&e, format!("Failed to resolve {}: {}", path, e)))?;
+
+    let mut open = OPEN.lock();
+    if let Some(db) = open.get(&directory) {
+        return Ok(db.clone());
+    }
+    let db = sled::open(&directory).map_err(|e| db_error(&format!("open {}", path), e))?;
+    open.insert(directory, db.clone());
+    Ok(db)
+}
+
+fn db_error(context: &str, error: sled::Error) -> napi::Error {
+    errors::new(ErrorCode::IoError, format!("Metadata database error ({}): {}", context, error))
+}
@@ -0,0 +1,181 @@
+//! Runtime metrics for the native layer
+//!
+//! A process-wide registry of per-operation call counts, failures and
+//! cumulative durations, bytes processed per module, and cache hits and
+//! misses. Operations are recorded by [`crate::errors::guard`] around every
+//! export that does real work; modules add byte counts where they read or
+//! scan data, and [`crate::shared`] reports matcher cache lookups.
+
+use crate::errors::{self, ErrorCode};
+use napi_derive::napi;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+static OPERATIONS: Mutex<BTreeMap<&'static str, OperationCounters>> = Mutex::new(BTreeMap::new());
+static BYTES: Mutex<BTreeMap<&'static str, u64>> = Mutex::new(BTreeMap::new());
+static CACHES: Mutex<BTreeMap<&'static str, CacheCounters>> = Mutex::new(BTreeMap::new());
+static LIMITS: Mutex<BTreeMap<&'static str, u64>> = Mutex::new(BTreeMap::new());
+/// Start of the current collection window; `None` until first use
+static WINDOW_START: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// How an operation finished
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    Ok,
+    Error,
+    Panic,
+}
+
+#[derive(Debug, Default)]
+struct OperationCounters {
+    calls: u64,
+    errors: u64,
+    panics: u64,
+    total: Duration,
+    max: Duration,
+}
+
+#[derive(Debug, Default)]
+struct CacheCounters {
+    hits: u64,
+    misses: u64,
+}
+
+/// Snapshot returned by `get_metrics`
+#[derive(Debug, Serialize)]
+struct MetricsSnapshot {
+    /// Milliseconds since the first recorded event or the last `reset_metrics`
+    window_ms: f64,
+    operations: BTreeMap<&'static str, OperationSnapshot>,
+    bytes_processed: BTreeMap<&'static str, u64>,
+    caches: BTreeMap<&'static str, CacheSnapshot>,
+    /// Times each resource limit was reached and work waited for it
+    limits_reached: BTreeMap<&'static str, u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct OperationSnapshot {
+    calls: u64,
+    errors: u64,
+    panics: u64,
+    total_ms: f64,
+    mean_ms: f64,
+    max_ms: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct CacheSnapshot {
+    hits: u64,
+    misses: u64,
+    /// Hits divided by lookups, or 0 before the first lookup
+    hit_rate: f64,
+}
+
+/// Record one finished call of an exported operation
+pub(crate) fn record_operation(operation: &'static str, elapsed: Duration, outcome: Outcome) {
+    window_start();
+    let mut operations = OPERATIONS.lock();
+    let counters = operations.entry(operation).or_default();
+    counters.calls += 1;
+    match outcome {
+        Outcome::Ok => {}
+        Outcome::Error => counters.errors += 1,
+        Outcome::Panic => counters.panics += 1,
+    }
+    counters.total += elapsed;
+    counters.max = counters.max.max(elapsed);
+}
+
+/// Add to the bytes processed by a module (e.g. `hashing`, `file_search`)
+pub(crate) fn add_bytes(category: &'static str, bytes: u64) {
+    window_start();
+    *BYTES.lock().entry(category).or_default() += bytes;
+}
+
+/// Record a lookup in a named cache
+pub(crate) fn record_cache(cache: &'static str, hit: bool) {
+    window_start();
+    let mut caches = CACHES.lock();
+    let counters = caches.entry(cache).or_default();
+    if hit {
+        counters.hits += 1;
+    } else {
+        counters.misses += 1;
+    }
+}
+
+/// Record that a resource limit (e.g. `file_descriptors`) was reached, so work waited or was cut short
+pub(crate) fn record_limit(limit: &'static str) {
+    window_start();
+    *LIMITS.lock().entry(limit).or_default() += 1;
+}
+
+fn window_start() -> Instant {
+    *WINDOW_START.lock().get_or_insert_with(Instant::now)
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Get native-layer metrics as a JSON string
+///
+/// The object has `window_ms`, per-operation `operations` (calls, errors,
+/// panics and total/mean/max milliseconds), `bytes_processed` per module,
+/// `caches` with hits, misses and hit rate, and `limits_reached` with how
+/// often each resource or walk limit was reached.
+#[napi]
+pub fn get_metrics() -> napi::Result<String> {
+    let window_ms = millis(window_start().elapsed());
+    let operations = OPERATIONS
+        .lock()
+        .iter()
+        .map(|(&name, c)| {
+            let total_ms = millis(c.total);
+            let snapshot = OperationSnapshot {
+                calls: c.calls,
+                errors: c.errors,
+                panics: c.panics,
+                total_ms,
+                mean_ms: if c.calls > 0 { total_ms / c.calls as f64 } else { 0.0 },
+                max_ms: millis(c.max),
+            };
+            (name, snapshot)
+        })
+        .collect();
+    let caches = CACHES
+        .lock()
+        .iter()
+        .map(|(&name, c)| {
+            let lookups = c.hits + c.misses;
+            let snapshot = CacheSnapshot {
+                hits: c.hits,
+                misses: c.misses,
+                hit_rate: if lookups > 0 { c.hits as f64 / lookups as f64 } else { 0.0 },
+            };
+            (name, snapshot)
+        })
+        .collect();
+
+    let snapshot = MetricsSnapshot {
+        window_ms,
+        operations,
+        bytes_processed: BYTES.lock().clone(),
+        caches,
+        limits_reached: LIMITS.lock().clone(),
+    };
+    serde_json::to_string(&snapshot)
+        .map_err(|e| errors::new(ErrorCode::Internal, format!("Failed to serialize metrics: {}", e)))
+}
+
+/// Clear all metrics and start a new collection window
+#[napi]
+pub fn reset_metrics() {
+    OPERATIONS.lock().clear();
+    BYTES.lock().clear();
+    CACHES.lock().clear();
+    LIMITS.lock().clear();
+    *WINDOW_START.lock() = Some(Instant::now());
+}
@@ -0,0 +1,308 @@
+//! Near-duplicate detection using MinHash signatures
+//!
+//! This module provides shingling, MinHash signatures and LSH banding so that
+//! near-duplicate documents and copy-pasted code blocks can be found across
+//! thousands of files without comparing every pair.
+
+use napi_derive::napi;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use crate::concurrency;
+use crate::errors::{self, ErrorCode};
+
+/// Configuration for near-duplicate detection
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NearDuplicateConfig {
+    /// Number of whitespace-separated tokens per shingle
+    pub shingle_size: u32,
+    /// Number of hash functions in each MinHash signature
+    pub num_hashes: u32,
+    /// Number of LSH bands (must divide `num_hashes`)
+    pub bands: u32,
+    /// Minimum estimated Jaccard similarity for a reported pair
+    pub similarity_threshold: f64,
+    /// Lowercase text before shingling
+    pub ignore_case: bool,
+    /// Use parallel processing for signature generation
+    pub use_parallel: bool,
+}
+
+impl Default for NearDuplicateConfig {
+    fn default() -> Self {
+        Self {
+            shingle_size: 5,
+            num_hashes: 128,
+            bands: 32,
+            similarity_threshold: 0.8,
+            ignore_case: true,
+            use_parallel: true,
+        }
+    }
+}
+
+/// A pair of near-duplicate documents
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicatePair {
+    /// Index of the first document
+    pub index_a: u32,
+    /// Index of the second document
+    pub index_b: u32,
+    /// Estimated Jaccard similarity (0.0 to 1.0)
+    pub similarity: f64,
+}
+
+/// Near-duplicate detector based on MinHash and LSH banding
+#[napi]
+pub struct NearDuplicateDetector {
+    config: NearDuplicateConfig,
+    coefficients: Vec<(u64, u64)>,
+}
+
+#[napi]
+impl NearDuplicateDetector {
+    /// Create a new near-duplicate detector with optional configuration
+    ///
+    /// # Arguments
+    /// * `config` - Optional configuration for shingling and banding
+    #[napi(constructor)]
+    pub fn new(config: Option<NearDuplicateConfig>) -> napi::Result<Self> {
+        errors::guard("new NearDuplicateDetector", || {
+            let config = config.unwrap_or_default();
+
+            if config.shingle_size == 0 {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "shingle_size must be greater than zero".to_string(),
+                ));
+            }
+
+            if config.num_hashes == 0 || config.bands == 0 || !config.num_hashes.is_multiple_of(config.bands) {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "num_hashes must be a non-zero multiple of bands".to_string(),
+                ));
+            }
+
+            let coefficients = hash_coefficients(config.num_hashes as usize);
+            Ok(Self {
+                config,
+                coefficients,
+            })
+        })
+    }
+
+    /// Compute the MinHash signature of a single text
+    #[napi]
+    pub fn compute_signature(&self, text: String) -> napi::Result<Vec<u32>> {
+        errors::catch_panic("NearDuplicateDetector.computeSignature", || {
+            self.signature_internal(&text)
+        })
+    }
+
+    /// Estimate Jaccard similarity from two MinHash signatures
+    #[napi]
+    pub fn estimate_similarity(&self, signature_a: Vec<u32>, signature_b: Vec<u32>) -> napi::Result<f64> {
+        errors::guard("NearDuplicateDetector.estimateSimilarity", || {
+            if signature_a.len() != signature_b.len() {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "Signatures must have the same length".to_string(),
+                ));
+            }
+
+            Ok(signature_similarity(&signature_a, &signature_b))
+        })
+    }
+
+    /// Find near-duplicate pairs among a collection of texts
+    /// Uses LSH banding so only candidate pairs are compared
+    #[napi]
+    pub fn find_near_duplicates(&self, texts: Vec<String>) -> napi::Result<Vec<DuplicatePair>> {
+        errors::guard("NearDuplicateDetector.findNearDuplicates", || {
+            let _permit = concurrency::acquire("NearDuplicateDetector.findNearDuplicates")?;
+            let signatures: Vec<Option<Vec<u32>>> = if self.config.use_parallel && texts.len() > 100 {
+                texts
+                    .par_iter()
+                    .map(|text| self.signature_if_non_empty(text))
+                    .collect()
+            } else {
+                texts
+                    .iter()
+                    .map(|text| self.signature_if_non_empty(text))
+                    .collect()
+            };
+
+            Ok(self.pairs_from_signatures(&signatures))
+        })
+    }
+
+    /// Find near-duplicate pairs from precomputed signatures
+    #[napi]
+    pub fn find_near_duplicates_from_signatures(
+        &self,
+        signatures: Vec<Vec<u32>>,
+    ) -> napi::Result<Vec<DuplicatePair>> {
+        errors::guard("NearDuplicateDetector.findNearDuplicatesFromSignatures", || {
+            let expected = self.config.num_hashes as usize;
+            if signatures.iter().any(|sig| sig.len() != expected) {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Every signature must have {} values", expected),
+                ));
+            }
+
+            let signatures: Vec<Option<Vec<u32>>> = signatures.into_iter().map(Some).collect();
+            Ok(self.pairs_from_signatures(&signatures))
+        })
+    }
+
+    /// Compute a signature, skipping texts that produce no shingles
+    fn signature_if_non_empty(&self, text: &str) -> Option<Vec<u32>> {
+        if text.split_whitespace().next().is_none() {
+            None
+        } else {
+            Some(self.signature_internal(text))
+        }
+    }
+
+    /// Internal MinHash signature computation
+    fn signature_internal(&self, text: &str) -> Vec<u32> {
+        let mut signature = vec![u32::MAX; self.coefficients.len()];
+
+        for shingle in self.shingle_hashes(text) {
+            for (slot, &(a, b)) in signature.iter_mut().zip(self.coefficients.iter()) {
+                // Multiply-shift universal hashing derives each permutation from one base hash
+                let value = (a.wrapping_mul(shingle).wrapping_add(b) >> 32) as u32;
+                if value < *slot {
+                    *slot = value;
+                }
+            }
+        }
+
+        signature
+    }
+
+    /// Hash every token shingle of a text
+    fn shingle_hashes(&self, text: &str) -> HashSet<u64> {
+        let normalized = if self.config.ignore_case {
+            text.to_lowercase()
+        } else {
+            text.to_string()
+        };
+
+        let tokens: Vec<&str> = normalized.split_whitespace().collect();
+        let size = self.config.shingle_size as usize;
+        let mut hashes = HashSet::new();
+
+        if tokens.len() <= size {
+            if !tokens.is_empty() {
+                hashes.insert(seahash::hash(tokens.join(" ").as_bytes()));
+            }
+            return hashes;
+        }
+
+        for window in tokens.windows(size) {
+            hashes.insert(seahash::hash(window.join(" ").as_bytes()));
+        }
+
+        hashes
+    }
+
+    /// Bucket signatures by band and verify candidate pairs
+    fn pairs_from_signatures(&self, signatures: &[Option<Vec<u32>>]) -> Vec<DuplicatePair> {
+        let rows = (self.config.num_hashes / self.config.bands) as usize;
+        let mut candidates: HashSet<(usize, usize)> = HashSet::new();
+
+        for band in 0..self.config.bands as usize {
+            let mut buckets: HashMap<u64, Vec<usize>> = HashMap::new();
+
+            for (index, signature) in signatures.iter().enumerate() {
+                if let Some(signature) = signature {
+                    let slice = &signature[band * rows..(band + 1) * rows];
+                    let bytes: Vec<u8> = slice.iter().flat_map(|v| v.to_le_bytes()).collect();
+                    buckets.entry(seahash::hash(&bytes)).or_default().push(index);
+                }
+            }
+
+            for members in buckets.values().filter(|members| members.len() > 1) {
+                for (i, &a) in members.iter().enumerate() {
+                    for &b in &members[i + 1..] {
+                        candidates.insert((a, b));
+                    }
+                }
+            }
+        }
+
+        let mut pairs: Vec<DuplicatePair> = candidates
+            .into_iter()
+            .filter_map(|(a, b)| {
+                let (sig_a, sig_b) = (signatures[a].as_ref()?, signatures[b].as_ref()?);
+                let similarity = signature_similarity(sig_a, sig_b);
+                if similarity >= self.config.similarity_threshold {
+                    Some(DuplicatePair {
+                        index_a: a as u32,
+                        index_b: b as u32,
+                        similarity,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        // Sort by similarity (highest first), then by indices for stable output
+        pairs.sort_by(|a, b| {
+            b.similarity
+                .total_cmp(&a.similarity)
+                .then(a.index_a.cmp(&b.index_a))
+                .then(a.index_b.cmp(&b.index_b))
+        });
+        pairs
+    }
+}
+
+/// Fraction of matching positions between two signatures
+fn signature_similarity(signature_a: &[u32], signature_b: &[u32]) -> f64 {
+    if signature_a.is_empty() {
+        return 0.0;
+    }
+
+    let matching = signature_a
+        .iter()
+        .zip(signature_b.iter())
+        .filter(|(a, b)| a == b)
+        .count();
+    matching as f64 / signature_a.len() as f64
+}
+
+/// Deterministic hash coefficients generated with SplitMix64
+fn hash_coefficients(count: usize) -> Vec<(u64, u64)> {
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut next = || {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    };
+
+    (0..count).map(|_| (next() | 1, next())).collect()
+}
+
+/// Quick near-duplicate search with default configuration
+#[napi]
+pub fn quick_find_near_duplicates(
+    texts: Vec<String>,
+    similarity_threshold: Option<f64>,
+) -> napi::Result<Vec<DuplicatePair>> {
+    errors::guard("quickFindNearDuplicates", || {
+        let detector = NearDuplicateDetector::new(Some(NearDuplicateConfig {
+            similarity_threshold: similarity_threshold.unwrap_or(0.8),
+            ..Default::default()
+        }))?;
+        detector.find_near_duplicates(texts)
+    })
+}
@@ -0,0 +1,491 @@
+//! Search → read → chunk pipeline for embedding
+//!
+//! Indexing a repository for semantic search used to take three round-trips
+//! through N-API: find the files, read each one, then chunk the text in
+//! JavaScript. Every step materialized its results as V8 strings and arrays
+//! only to hand them straight back. `chunkFiles` does the whole walk in one
+//! call and returns only the chunk records to embed.
+//!
+//! Chunks are made of whole lines, up to `max_chunk_bytes` each. A line longer
+//! than that is split at character boundaries. Consecutive chunks can repeat
+//! the last `overlap_lines` lines so that context is not lost at a boundary.
+//! Each record carries its byte span, its line range and a Blake3 hash of its
+//! text, so callers can skip re-embedding chunks they have already seen.
+//!
+//! `chunkFilesIncremental` also returns a [`ChunkSnapshot`] of the files it
+//! read. Given that snapshot on the next run, it re-reads only files whose
+//! size or modification time changed. It returns only the chunks that are
+//! new, plus the IDs of chunks that no longer exist, so the index can be
+//! updated in place.
+
+use crate::concurrency;
+use crate::errors::{self, ErrorCode};
+use crate::file_search::{FileSearch, FileSearchConfig};
+use crate::metrics;
+use crate::security_utils::read_text_file;
+use crate::shared;
+use napi_derive::napi;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Configuration for `chunkFiles` and `chunkFilesIncremental`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkConfig {
+    /// Maximum size of a chunk in bytes
+    pub max_chunk_bytes: u32,
+    /// Lines repeated at the start of the next chunk
+    pub overlap_lines: u32,
+    /// Drop chunks that contain only whitespace
+    pub skip_blank: bool,
+    /// Traversal and exclusion settings for the file search
+    pub search: Option<FileSearchConfig>,
+}
+
+impl Default for ChunkConfig {
+    fn default() -> Self {
+        Self {
+            max_chunk_bytes: 2048,
+            overlap_lines: 0,
+            skip_blank: true,
+            search: None,
+        }
+    }
+}
+
+/// A chunk of a file, ready to embed
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkRecord {
+    /// Stable identifier built from the path and hash, with a counter for
+    /// repeated identical chunks; unchanged chunks keep their id when other
+    /// chunks are inserted or removed before them
+    pub id: String,
+    /// File path
+    pub path: String,
+    /// Position of the chunk within its file (0-based)
+    pub chunk_index: u32,
+    /// Byte offset of the chunk start
+    pub start: u32,
+    /// Byte offset just past the chunk
+    pub end: u32,
+    /// First line of the chunk (1-based)
+    pub start_line: u32,
+    /// Last line of the chunk (1-based, inclusive)
+    pub end_line: u32,
+    /// Chunk text
+    pub text: String,
+    /// Blake3 hash of the chunk text (hex)
+    pub hash: String,
+}
+
+/// Chunks produced by `chunkFiles`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkResult {
+    /// Chunks of every matched file, ordered by path and position
+    pub chunks: Vec<ChunkRecord>,
+    /// Files matching the pattern
+    pub files_matched: u32,
+    /// Matched files skipped as binary or unreadable
+    pub files_skipped: u32,
+    /// Bytes of text read from the matched files
+    pub bytes_read: f64,
+}
+
+/// Byte and line range of one chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ChunkSpan {
+    start: usize,
+    end: usize,
+    /// 0-based line of the chunk start
+    first_line: usize,
+    /// 0-based line of the chunk end
+    last_line: usize,
+}
+
+/// State of one chunked file, as recorded in a snapshot
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    /// File path
+    pub path: String,
+    /// File size in bytes
+    pub size: f64,
+    /// Last modified timestamp (milliseconds since Unix epoch)
+    pub modified_ms: f64,
+    /// Blake3 hash of the file content (hex)
+    pub hash: String,
+    /// IDs of the file's chunks, in order
+    pub chunk_ids: Vec<String>,
+}
+
+/// Files chunked by a run of `chunkFilesIncremental`, to pass to the next run
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkSnapshot {
+    /// `max_chunk_bytes` the chunks were made with
+    pub max_chunk_bytes: u32,
+    /// `overlap_lines` the chunks were made with
+    pub overlap_lines: u32,
+    /// `skip_blank` the chunks were made with
+    pub skip_blank: bool,
+    /// Chunked files, ordered by path
+    pub files: Vec<SnapshotFile>,
+}
+
+/// Changes since a previous snapshot
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncrementalChunkResult {
+    /// Chunks not present in the previous snapshot, ordered by path and position
+    pub chunks: Vec<ChunkRecord>,
+    /// IDs of chunks in the previous snapshot that no longer exist
+    pub deleted_ids: Vec<String>,
+    /// Files not in the previous snapshot
+    pub added_files: Vec<String>,
+    /// Files whose content changed
+    pub changed_files: Vec<String>,
+    /// Files in the previous snapshot that are gone, now binary or unreadable
+    pub removed_files: Vec<String>,
+    /// Files left as they were
+    pub unchanged_files: u32,
+    /// Bytes of text read (unchanged files with the same size and
+    /// modification time are not read)
+    pub bytes_read: f64,
+    /// Snapshot to pass to the next run
+    pub snapshot: ChunkSnapshot,
+}
+
+/// What happened to one file in an incremental run
+enum FileOutcome {
+    /// Content is as in the previous snapshot
+    Unchanged(SnapshotFile),
+    /// Content is new or changed
+    Chunked {
+        entry: SnapshotFile,
+        records: Vec<ChunkRecord>,
+        bytes: usize,
+    },
+    /// Binary or unreadable
+    Skipped,
+}
+
+/// Find files matching a glob, read them and split their text into chunks
+///
+/// The pattern is matched against the full path, as in
+/// `FileSearch.findFilesByPattern`. Binary files are skipped.
+///
+/// # Arguments
+/// * `root_path` - Directory to search
+/// * `pattern` - Glob the file paths must match
+/// * `config` - Optional chunk sizes and search settings
+#[napi]
+pub fn chunk_files(root_path: String, pattern: String, config: Option<ChunkConfig>) -> napi::Result<ChunkResult> {
+    errors::guard("chunkFiles", || {
+        let _permit = concurrency::acquire("chunkFiles")?;
+        let config = checked_config(config)?;
+        let files = matching_files(&root_path, &pattern, &config)?;
+
+        let chunk_one = |path: &PathBuf| {
+            let text = read_text_file(path).ok()??;
+            Some((text.len(), chunk_text(&path.to_string_lossy(), &text, &config)))
+        };
+        let per_file: Vec<Option<(usize, Vec<ChunkRecord>)>> = if use_parallel(&config, files.len()) {
+            files.par_iter().map(chunk_one).collect()
+        } else {
+            files.iter().map(chunk_one).collect()
+        };
+
+        let mut chunks = Vec::new();
+        let mut files_skipped = 0u32;
+        let mut bytes_read = 0usize;
+        for result in per_file {
+            match result {
+                Some((len, records)) => {
+                    bytes_read += len;
+                    chunks.extend(records);
+                }
+                None => files_skipped += 1,
+            }
+        }
+        metrics::add_bytes("chunking", bytes_read as u64);
+
+        Ok(ChunkResult {
+            chunks,
+            files_matched: files.len() as u32,
+            files_skipped,
+            bytes_read: bytes_read as f64,
+        })
+    })
+}
+
+/// Chunk only what changed since a previous snapshot
+///
+/// Files whose size and modification time match the snapshot are not read;
+/// files whose content hash matches are not re-chunked. When the chunk
+/// settings differ from the snapshot's, every file is re-chunked.
+///
+/// # Arguments
+/// * `root_path` - Directory to search
+/// * `pattern` - Glob the file paths must match
+/// * `previous` - Snapshot from the previous run (omit for a full run)
+/// * `config` - Optional chunk sizes and search settings
+#[napi]
+pub fn chunk_files_incremental(
+    root_path: String,
+    pattern: String,
+    previous: Option<ChunkSnapshot>,
+    config: Option<ChunkConfig>,
+) -> napi::Result<IncrementalChunkResult> {
+    errors::guard("chunkFilesIncremental", || {
+        let _permit = concurrency::acquire("chunkFilesIncremental")?;
+        let config = checked_config(config)?;
+        let files = matching_files(&root_path, &pattern, &config)?;
+
+        let previous = previous.filter(|snapshot| {
+            snapshot.max_chunk_bytes == config.max_chunk_bytes
+                && snapshot.overlap_lines == config.overlap_lines
+                && snapshot.skip_blank == config.skip_blank
+        });
+        let stale_ids: Vec<String> = previous
+            .iter()
+            .flat_map(|snapshot| snapshot.files.iter().flat_map(|file| file.chunk_ids.iter().cloned()))
+            .collect();
+        let previous_files: HashMap<&str, &SnapshotFile> = previous
+            .iter()
+            .flat_map(|snapshot| snapshot.files.iter().map(|file| (file.path.as_str(), file)))
+            .collect();
+
+        let update_one = |path: &PathBuf| update_file(path, previous_files.get(path.to_string_lossy().as_ref()).copied(), &config);
+        let outcomes: Vec<FileOutcome> = if use_parallel(&config, files.len()) {
+            files.par_iter().map(update_one).collect()
+        } else {
+            files.iter().map(update_one).collect()
+        };
+
+        let mut result = IncrementalChunkResult {
+            chunks: Vec::new(),
+            deleted_ids: Vec::new(),
+            added_files: Vec::new(),
+            changed_files: Vec::new(),
+            removed_files: Vec::new(),
+            unchanged_files: 0,
+            bytes_read: 0.0,
+            snapshot: ChunkSnapshot {
+                max_chunk_bytes: config.max_chunk_bytes,
+                overlap_lines: config.overlap_lines,
+                skip_blank: config.skip_blank,
+                files: Vec::new(),
+            },
+        };
+        let mut bytes_read = 0usize;
+        for outcome in outcomes {
+            match outcome {
+                FileOutcome::Unchanged(entry) => {
+                    result.unchanged_files += 1;
+                    result.snapshot.files.push(entry);
+                }
+                FileOutcome::Chunked { entry, records, bytes } => {
+                    bytes_read += bytes;
+                    let old_ids: HashSet<&str> = previous_files
+                        .get(entry.path.as_str())
+                        .map(|old| old.chunk_ids.iter().map(String::as_str).collect())
+                        .unwrap_or_default();
+                    if previous_files.contains_key(entry.path.as_str()) {
+                        result.changed_files.push(entry.path.clone());
+                    } else {
+                        result.added_files.push(entry.path.clone());
+                    }
+                    result.chunks.extend(records.into_iter().filter(|r| !old_ids.contains(r.id.as_str())));
+                    result.snapshot.files.push(entry);
+                }
+                FileOutcome::Skipped => {}
+            }
+        }
+
+        let current: HashSet<&str> = result.snapshot.files.iter().map(|f| f.path.as_str()).collect();
+        result.removed_files = previous_files
+            .keys()
+            .filter(|path| !current.contains(*path))
+            .map(|path| path.to_string())
+            .collect();
+        result.removed_files.sort();
+        let live_ids: HashSet<&str> = result
+            .snapshot
+            .files
+            .iter()
+            .flat_map(|file| file.chunk_ids.iter().map(String::as_str))
+            .collect();
+        result.deleted_ids = stale_ids.into_iter().filter(|id| !live_ids.contains(id.as_str())).collect();
+
+        metrics::add_bytes("chunking", bytes_read as u64);
+        result.bytes_read = bytes_read as f64;
+        Ok(result)
+    })
+}
+
+/// Default the config and reject a zero chunk size
+fn checked_config(config: Option<ChunkConfig>) -> napi::Result<ChunkConfig> {
+    let config = config.unwrap_or_default();
+    if config.max_chunk_bytes == 0 {
+        return Err(errors::new(
+            ErrorCode::InvalidArgument,
+            "max_chunk_bytes must be greater than 0".to_string(),
+        ));
+    }
+    Ok(config)
+}
+
+/// Files under `root_path` whose path matches `pattern`, sorted
+fn matching_files(root_path: &str, pattern: &str, config: &ChunkConfig) -> napi::Result<Vec<PathBuf>> {
+    let matcher = shared::glob_matcher(pattern).map_err(|e| {
+        errors::new(ErrorCode::PatternInvalid, format!("Invalid pattern: {}", e))
+    })?;
+    let search = FileSearch::new(config.search.clone())?;
+    let mut files: Vec<PathBuf> = search
+        .collect_files(Path::new(root_path))?
+        .into_iter()
+        .filter(|path| path.to_str().is_some_and(|p| matcher.is_match(p)))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn use_parallel(config: &ChunkConfig, file_count: usize) -> bool {
+    file_count > 1 && config.search.as_ref().is_none_or(|search| search.use_parallel)
+}
+
+/// Compare a file against its previous snapshot entry, re-chunking if it changed
+fn update_file(path: &Path, previous: Option<&SnapshotFile>, config: &ChunkConfig) -> FileOutcome {
+    let Ok(metadata) = fs::metadata(path) else { return FileOutcome::Skipped };
+    let size = metadata.len() as f64;
+    let modified_ms = metadata
+        .modified()
+        .unwrap_or(UNIX_EPOCH)
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as f64;
+    if let Some(previous) = previous.filter(|p| p.size == size && p.modified_ms == modified_ms) {
+        return FileOutcome::Unchanged(previous.clone());
+    }
+
+    let Ok(Some(text)) = read_text_file(path) else { return FileOutcome::Skipped };
+    let hash = blake3::hash(text.as_bytes()).to_hex().to_string();
+    if let Some(previous) = previous.filter(|p| p.hash == hash) {
+        return FileOutcome::Unchanged(SnapshotFile {
+            size,
+            modified_ms,
+            ..previous.clone()
+        });
+    }
+
+    let path = path.to_string_lossy().to_string();
+    let records = chunk_text(&path, &text, config);
+    FileOutcome::Chunked {
+        entry: SnapshotFile {
+            path,
+            size,
+            modified_ms,
+            hash,
+            chunk_ids: records.iter().map(|r| r.id.clone()).collect(),
+        },
+        records,
+        bytes: text.len(),
+    }
+}
+
+/// Chunk a file's text into records
+fn chunk_text(path: &str, text: &str, config: &ChunkConfig) -> Vec<ChunkRecord> {
+    let mut occurrences: HashMap<String, u32> = HashMap::new();
+    chunk_spans(text, config.max_chunk_bytes as usize, config.overlap_lines as usize)
+        .into_iter()
+        .map(|span| (&text[span.start..span.end], span))
+        .filter(|(chunk, _)| !(config.skip_blank && chunk.trim().is_empty()))
+        .enumerate()
+        .map(|(index, (chunk, span))| {
+            let hash = blake3::hash(chunk.as_bytes()).to_hex().to_string();
+            let occurrence = occurrences.entry(hash.clone()).or_insert(0);
+            let id = match *occurrence {
+                0 => format!("{}#{}", path, &hash[..16]),
+                n => format!("{}#{}.{}", path, &hash[..16], n),
+            };
+            *occurrence += 1;
+            ChunkRecord {
+                id,
+                path: path.to_string(),
+                chunk_index: index as u32,
+                start: span.start as u32,
+                end: span.end as u32,
+                start_line: span.first_line as u32 + 1,
+                end_line: span.last_line as u32 + 1,
+                text: chunk.to_string(),
+                hash,
+            }
+        })
+        .collect()
+}
+
+/// Split text into chunks of whole lines of at most `max_bytes` each
+///
+/// A line longer than `max_bytes` becomes several chunks, split at character
+/// boundaries. After a multi-line chunk, the next one starts `overlap_lines`
+/// lines back, but always after the previous chunk's first line.
+fn chunk_spans(text: &str, max_bytes: usize, overlap_lines: usize) -> Vec<ChunkSpan> {
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        lines.push((offset, offset + line.len()));
+        offset += line.len();
+    }
+
+    let mut spans = Vec::new();
+    let mut first = 0;
+    while first < lines.len() {
+        let start = lines[first].0;
+        let mut next = first;
+        while next < lines.len() && lines[next].1 - start <= max_bytes {
+            next += 1;
+        }
+
+        if next == first {
+            let end = lines[first].1;
+            let mut piece_start = start;
+            while piece_start < end {
+                let mut piece_end = (piece_start + max_bytes).min(end);
+                while !text.is_char_boundary(piece_end) {
+                    piece_end -= 1;
+                }
+                if piece_end == piece_start {
+                    piece_end = text[piece_start..].chars().next().map_or(end, |c| piece_start + c.len_utf8());
+                }
+                spans.push(ChunkSpan {
+                    start: piece_start,
+                    end: piece_end,
+                    first_line: first,
+                    last_line: first,
+                });
+                piece_start = piece_end;
+            }
+            first += 1;
+            continue;
+        }
+
+        spans.push(ChunkSpan {
+            start,
+            end: lines[next - 1].1,
+            first_line: first,
+            last_line: next - 1,
+        });
+        first = if next == lines.len() {
+            next
+        } else {
+            next.saturating_sub(overlap_lines).max(first + 1)
+        };
+    }
+    spans
+}
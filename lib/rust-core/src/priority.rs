@@ -0,0 +1,143 @@
+//! Background priority for heavy scans
+//!
+//! Indexers and watchers run scans while the developer keeps working, and a
+//! full-speed walk over a large tree competes with the editor and compiler
+//! for disk and CPU. A `FileSearch` configured with `priority: "background"`
+//! runs its scans on a separate thread pool whose workers have lowered
+//! priority:
+//!
+//! - Linux: nice 10 and the idle I/O class (as `nice -n 10 ionice -c 3`)
+//! - macOS: the background QoS class, which also throttles disk I/O
+//! - elsewhere: normal priority
+//!
+//! A separate pool is needed because an unprivileged thread can't raise its
+//! niceness again; the global pool and the calling thread keep normal
+//! priority, so other operations are unaffected.
+
+use crate::errors::{self, ErrorCode};
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::OnceLock;
+
+/// Niceness of background workers
+#[cfg(target_os = "linux")]
+const BACKGROUND_NICE: i32 = 10;
+
+static POOL: OnceLock<Result<rayon::ThreadPool, String>> = OnceLock::new();
+
+/// Background workers whose CPU and I/O priority were lowered
+static CPU_LOWERED: AtomicU32 = AtomicU32::new(0);
+static IO_LOWERED: AtomicU32 = AtomicU32::new(0);
+
+/// State of the background pool
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackgroundPoolInfo {
+    /// Whether the pool has been started by a background scan
+    pub started: bool,
+    /// Worker threads in the pool
+    pub threads: u32,
+    /// Workers running with lowered CPU priority
+    pub cpu_lowered: u32,
+    /// Workers running with lowered I/O priority
+    pub io_lowered: u32,
+}
+
+/// Whether a `priority` setting asks for background scans
+///
+/// Accepts "normal" or "background"; absent means normal.
+pub(crate) fn parse(priority: Option<&str>) -> napi::Result<bool> {
+    match priority {
+        None | Some("normal") => Ok(false),
+        Some("background") => Ok(true),
+        Some(other) => Err(errors::new(
+            ErrorCode::InvalidArgument,
+            format!("Unknown priority: {} (expected normal or background)", other),
+        )),
+    }
+}
+
+/// Run `work` on the background pool if `background`, else on the calling thread
+///
+/// Parallel iterators inside `work` then use the pool's workers as well.
+pub(crate) fn run<T: Send>(background: bool, work: impl FnOnce() -> napi::Result<T> + Send) -> napi::Result<T> {
+    if !background {
+        return work();
+    }
+    match pool() {
+        Ok(pool) => pool.install(work),
+        Err(e) => Err(errors::new(
+            ErrorCode::Internal,
+            format!("Failed to start the background thread pool: {}", e),
+        )),
+    }
+}
+
+fn pool() -> &'static Result<rayon::ThreadPool, String> {
+    POOL.get_or_init(|| {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(rayon::current_num_threads())
+            .thread_name(|i| format!("moidvk-background-{}", i))
+            .start_handler(|_| lower_current_thread())
+            .build()
+            .map_err(|e| e.to_string())
+    })
+}
+
+/// Lower the calling thread's CPU and I/O priority, counting what succeeded
+fn lower_current_thread() {
+    let (cpu, io) = lower();
+    if cpu {
+        CPU_LOWERED.fetch_add(1, Ordering::Relaxed);
+    }
+    if io {
+        IO_LOWERED.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn lower() -> (bool, bool) {
+    const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+    const IOPRIO_CLASS_IDLE: libc::c_int = 3;
+    const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+    // SAFETY: plain syscalls on the calling thread (who = 0); Linux applies
+    // both per thread, so other threads keep their priority
+    unsafe {
+        let cpu = libc::setpriority(libc::PRIO_PROCESS, 0, BACKGROUND_NICE) == 0;
+        let io = libc::syscall(
+            libc::SYS_ioprio_set,
+            IOPRIO_WHO_PROCESS,
+            0,
+            IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT,
+        ) == 0;
+        (cpu, io)
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn lower() -> (bool, bool) {
+    // SAFETY: sets the QoS class of the calling thread only
+    let lowered = unsafe { libc::pthread_set_qos_class_self_np(libc::qos_class_t::QOS_CLASS_BACKGROUND, 0) == 0 };
+    (lowered, lowered)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn lower() -> (bool, bool) {
+    (false, false)
+}
+
+/// Get the size of the background pool and how many workers were deprioritized
+#[napi]
+pub fn get_background_pool_info() -> BackgroundPoolInfo {
+    let threads = match POOL.get() {
+        Some(Ok(pool)) => pool.current_num_threads() as u32,
+        _ => 0,
+    };
+    BackgroundPoolInfo {
+        started: threads > 0,
+        threads,
+        cpu_lowered: CPU_LOWERED.load(Ordering::Relaxed),
+        io_lowered: IO_LOWERED.load(Ordering::Relaxed),
+    }
+}
@@ -0,0 +1,207 @@
+//! Opt-in cache of expensive `FileSearch` results
+//!
+//! Directory stats, extension stats and duplicate scans walk (and for
+//! duplicates, hash) a whole tree, yet tools call them again and again on
+//! trees that have not changed. With `result_cache` set in the
+//! `FileSearchConfig`, results are kept per operation, root and config.
+//!
+//! - `"mtime"` records the size and modification time of every visited
+//!   file and directory. A cached result is returned only while all of them
+//!   are unchanged. Directory mtimes catch added, removed and renamed
+//!   entries, and checking them needs only `stat` calls, without reading or
+//!   hashing anything.
+//! - `"events"` keeps results until `invalidateCachedResults` is called for
+//!   a path inside the root, e.g. from a file watcher, or `clearCache`.
+//!
+//! Lookups are reported to the `file_results` cache in `getMetrics()`.
+
+use crate::metrics;
+use napi_derive::napi;
+use parking_lot::Mutex;
+use rayon::prelude::*;
+use std::any::Any;
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Results kept before the oldest is evicted
+const CAPACITY: usize = 64;
+
+/// Stamps checked in parallel above this count
+const PARALLEL_CHECK_THRESHOLD: usize = 1000;
+
+static CACHE: Mutex<Entries> = Mutex::new(Entries {
+    map: BTreeMap::new(),
+    order: VecDeque::new(),
+});
+
+/// How cached results are revalidated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Validation {
+    /// Compare sizes and modification times of the visited tree
+    Mtime,
+    /// Trust results until invalidated explicitly
+    Events,
+}
+
+impl Validation {
+    /// Parse `FileSearchConfig.result_cache`; `None` disables caching
+    pub(crate) fn parse(mode: Option<&str>) -> Result<Option<Self>, String> {
+        match mode {
+            None | Some("off") => Ok(None),
+            Some("mtime") => Ok(Some(Self::Mtime)),
+            Some("events") => Ok(Some(Self::Events)),
+            Some(other) => Err(format!(
+                "Unknown result_cache mode: {} (expected off, mtime or events)",
+                other
+            )),
+        }
+    }
+}
+
+/// Size and modification time of a path when a result was computed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Stamp {
+    path: PathBuf,
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl Stamp {
+    /// Stamp a path; `None` if it cannot be read
+    pub(crate) fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(Self {
+            path: path.to_path_buf(),
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        })
+    }
+
+    fn is_current(&self) -> bool {
+        Self::of(&self.path).as_ref() == Some(self)
+    }
+}
+
+#[derive(Clone)]
+struct Entry {
+    /// Canonical root, matched against invalidated paths
+    root: PathBuf,
+    value: Arc<dyn Any + Send + Sync>,
+    /// Stamps to revalidate against (empty for `Validation::Events`)
+    stamps: Arc<[Stamp]>,
+}
+
+struct Entries {
+    map: BTreeMap<String, Entry>,
+    order: VecDeque<String>,
+}
+
+impl Entry {
+    /// Whether every stamp still matches the filesystem
+    fn is_current(&self) -> bool {
+        if self.stamps.len() > PARALLEL_CHECK_THRESHOLD {
+            self.stamps.par_iter().all(Stamp::is_current)
+        } else {
+            self.stamps.iter().all(Stamp::is_current)
+        }
+    }
+}
+
+/// Return the cached result of `operation` on `root`, computing it on a miss
+///
+/// `key` identifies the configuration the result depends on. With
+/// `Validation::Mtime`, `stamps` is taken before `compute` runs, so a change
+/// made during the computation invalidates the result on the next lookup.
+/// Results `keep` rejects are returned without being stored.
+pub(crate) fn get_or_compute<T: Clone + Send + Sync + 'static>(
+    operation: &str,
+    root: &Path,
+    key: &str,
+    validation: Validation,
+    stamps: impl FnOnce() -> Vec<Stamp>,
+    compute: impl FnOnce() -> napi::Result<T>,
+    keep: impl FnOnce(&T) -> bool,
+) -> napi::Result<T> {
+    let key = format!("{}\0{}\0{}", operation, root.display(), key);
+    // Revalidate outside the lock; stat calls on a large tree take a while
+    let cached = CACHE.lock().map.get(&key).cloned();
+    if let Some(entry) = cached.filter(Entry::is_current) {
+        if let Some(value) = entry.value.downcast_ref::<T>() {
+            metrics::record_cache("file_results", true);
+            return Ok(value.clone());
+        }
+    }
+    metrics::record_cache("file_results", false);
+
+    let stamps: Arc<[Stamp]> = match validation {
+        Validation::Mtime => stamps().into(),
+        Validation::Events => Arc::new([]),
+    };
+    let value = compute()?;
+    if !keep(&value) {
+        return Ok(value);
+    }
+
+    let mut cache = CACHE.lock();
+    let Entries { map, order } = &mut *cache;
+    if map.contains_key(&key) {
+        order.retain(|k| k != &key);
+    } else if order.len() >= CAPACITY {
+        if let Some(oldest) = order.pop_front() {
+            map.remove(&oldest);
+        }
+    }
+    order.push_back(key.clone());
+    map.insert(
+        key,
+        Entry {
+            root: canonical(root),
+            value: Arc::new(value.clone()),
+            stamps,
+        },
+    );
+    Ok(value)
+}
+
+/// Remove entries matching `predicate`, returning how many were removed
+fn remove_where(predicate: impl Fn(&Entry) -> bool) -> u32 {
+    let mut cache = CACHE.lock();
+    let Entries { map, order } = &mut *cache;
+    let before = map.len();
+    map.retain(|_, entry| !predicate(entry));
+    order.retain(|key| map.contains_key(key));
+    (before - map.len()) as u32
+}
+
+/// Drop every cached `FileSearch` result
+///
+/// Returns the number of results dropped.
+#[napi]
+pub fn clear_cache() -> u32 {
+    remove_where(|_| true)
+}
+
+/// Drop cached results affected by a change to `path`
+///
+/// Call this from file watcher events when using `result_cache: "events"`.
+/// Results for any root containing `path`, or inside it, are dropped.
+/// Returns the number of results dropped.
+#[napi]
+pub fn invalidate_cached_results(path: String) -> u32 {
+    let path = canonical(Path::new(&path));
+    remove_where(|entry| path.starts_with(&entry.root) || entry.root.starts_with(&path))
+}
+
+/// Canonical form of a path that may no longer exist (e.g. a deleted file)
+fn canonical(path: &Path) -> PathBuf {
+    if let Ok(resolved) = fs::canonicalize(path) {
+        return resolved;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) if !parent.as_os_str().is_empty() => canonical(parent).join(name),
+        _ => path.to_path_buf(),
+    }
+}
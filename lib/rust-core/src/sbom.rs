@@ -0,0 +1,205 @@
+//! CycloneDX SBOM generation
+//!
+//! Combines the lockfile inventory from `dependencies` with license
+//! detection on the project's own license files, and renders a CycloneDX
+//! 1.5 JSON document. Licenses come from the lockfile where it records one
+//! (npm), so other ecosystems list components without license data rather
+//! than guessing.
+
+use crate::audit::rfc3339;
+use crate::dependencies::{collect_dependencies, Dependency};
+use crate::errors::{self, ErrorCode};
+use crate::file_search::FileSearchConfig;
+use crate::licenses::{is_license_file_name, LicenseScanner};
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// CycloneDX specification version emitted
+const SPEC_VERSION: &str = "1.5";
+
+/// Options for SBOM generation
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SbomOptions {
+    /// Include development-only dependencies (marked with scope "excluded")
+    pub include_dev_dependencies: bool,
+    /// Project name (defaults to package.json, Cargo.toml or the directory name)
+    pub project_name: Option<String>,
+    /// Project version (defaults to package.json or Cargo.toml)
+    pub project_version: Option<String>,
+    /// Traversal settings used to find lockfiles
+    pub search: Option<FileSearchConfig>,
+}
+
+/// Generate a CycloneDX JSON SBOM for a project directory
+///
+/// # Arguments
+/// * `root_path` - Project directory
+/// * `options` - Optional generation settings
+#[napi]
+pub fn generate_sbom(root_path: String, options: Option<SbomOptions>) -> napi::Result<String> {
+    errors::guard("generateSbom", || {
+        let options = options.unwrap_or_default();
+        let root = Path::new(&root_path);
+
+        let dependencies: Vec<Dependency> = collect_dependencies(root, options.search.clone())?
+            .into_iter()
+            .filter(|dependency| options.include_dev_dependencies || !dependency.dev)
+            .collect();
+
+        let (manifest_name, manifest_version) = project_manifest(root);
+        let name = options
+            .project_name
+            .or(manifest_name)
+            .or_else(|| {
+                root.canonicalize()
+                    .ok()
+                    .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+            })
+            .unwrap_or_else(|| "project".to_string());
+        let version = options.project_version.or(manifest_version);
+
+        let mut project = json!({
+            "type": "application",
+            "bom-ref": format!("project:{}", name),
+            "name": name,
+        });
+        if let Some(version) = version {
+            project["version"] = json!(version);
+        }
+        let project_licenses = project_licenses(root)?;
+        if !project_licenses.is_empty() {
+            project["licenses"] = Value::Array(
+                project_licenses.iter().map(|id| json!({ "license": { "id": id } })).collect(),
+            );
+        }
+
+        let components: Vec<Value> = dependencies.iter().map(component).collect();
+        let bom = json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": SPEC_VERSION,
+            "serialNumber": format!("urn:uuid:{}", uuid_v4()?),
+            "version": 1,
+            "metadata": {
+                "timestamp": rfc3339(SystemTime::now()),
+                "tools": {
+                    "components": [{
+                        "type": "application",
+                        "name": "moidvk",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    }],
+                },
+                "component": project,
+            },
+            "components": components,
+        });
+
+        serde_json::to_string_pretty(&bom).map_err(|e| {
+            errors::new(ErrorCode::Internal, format!("Failed to serialize SBOM: {}", e))
+        })
+    })
+}
+
+/// CycloneDX component for one dependency
+fn component(dependency: &Dependency) -> Value {
+    let mut component = json!({
+        "type": "library",
+        "bom-ref": dependency.purl,
+        "name": dependency.name,
+        "version": dependency.version,
+        "purl": dependency.purl,
+    });
+    if dependency.dev {
+        component["scope"] = json!("excluded");
+    }
+    if let Some(license) = &dependency.license {
+        component["licenses"] = json!([license_choice(license)]);
+    }
+    component
+}
+
+/// SPDX ids and expressions map to `id`/`expression`; anything else is a free-form name
+fn license_choice(license: &str) -> Value {
+    let is_id = |s: &str| {
+        !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'))
+    };
+    if is_id(license) {
+        json!({ "license": { "id": license } })
+    } else if license
+        .split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+        .filter(|t| !t.is_empty())
+        .all(|t| is_id(t) || matches!(t, "AND" | "OR" | "WITH"))
+    {
+        json!({ "expression": license })
+    } else {
+        json!({ "license": { "name": license } })
+    }
+}
+
+/// Licenses identified in license files at the project root
+fn project_licenses(root: &Path) -> napi::Result<Vec<String>> {
+    let scanner = LicenseScanner::new(None)?;
+    let mut ids = Vec::new();
+    let Ok(entries) = fs::read_dir(root) else {
+        return Ok(ids);
+    };
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && is_license_file_name(path))
+        .collect();
+    paths.sort();
+    for path in paths {
+        if let Ok(found) = scanner.scan_file(path.to_string_lossy().to_string()) {
+            for license in found.licenses {
+                if !ids.contains(&license.spdx_id) {
+                    ids.push(license.spdx_id);
+                }
+            }
+        }
+    }
+    Ok(ids)
+}
+
+/// Name and version from package.json or Cargo.toml at the root
+fn project_manifest(root: &Path) -> (Option<String>, Option<String>) {
+    if let Some(manifest) = fs::read_to_string(root.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+    {
+        let field = |key: &str| manifest.get(key).and_then(Value::as_str).map(str::to_string);
+        return (field("name"), field("version"));
+    }
+    if let Some(package) = fs::read_to_string(root.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| toml::from_str::<toml::Table>(&content).ok())
+        .and_then(|manifest| manifest.get("package").and_then(|p| p.as_table()).cloned())
+    {
+        let field = |key: &str| package.get(key).and_then(|v| v.as_str()).map(str::to_string);
+        return (field("name"), field("version"));
+    }
+    (None, None)
+}
+
+/// Random (version 4) UUID for the BOM serial number
+fn uuid_v4() -> napi::Result<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).map_err(|e| {
+        errors::new(ErrorCode::Internal, format!("Failed to gather randomness: {}", e))
+    })?;
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = crate::hashing::to_hex(&bytes);
+    Ok(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    ))
+}
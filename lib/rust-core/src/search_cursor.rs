@@ -0,0 +1,110 @@
+//! Resumable cursors for paged `FileSearch` walks
+//!
+//! `findFilesPaged` and `searchTextPaged` return one page at a time. Between
+//! pages the directory walk is parked here under an opaque token, so the next
+//! call continues from the entry after the last one returned instead of
+//! walking the tree again from the root. Each token is single use; every
+//! page that is not the last comes with a new one.
+//!
+//! Parked walks hold open directory handles, so they are bounded: cursors
+//! unused for `CURSOR_TTL` expire, the oldest is dropped beyond
+//! `MAX_CURSORS`, and `closeSearchCursor` releases one early.
+
+use crate::errors::{self, ErrorCode};
+use crate::file_search::TextSearchResult;
+use crate::security_utils::quarantine::new_id;
+use crate::walk::Entries;
+use napi_derive::napi;
+use parking_lot::Mutex;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Parked walks kept before the least recently used is dropped
+const MAX_CURSORS: usize = 128;
+
+/// Time after which an unused cursor expires
+const CURSOR_TTL: Duration = Duration::from_secs(10 * 60);
+
+static CURSORS: Mutex<BTreeMap<String, Cursor>> = Mutex::new(BTreeMap::new());
+
+/// A paused search
+pub(crate) struct Cursor {
+    /// The walk, positioned after the last entry consumed
+    pub(crate) walk: Entries,
+    /// Matches found but not yet returned
+    pub(crate) pending: VecDeque<TextSearchResult>,
+    /// Matches in minified files, returned once the walk is done
+    pub(crate) deferred: Vec<TextSearchResult>,
+    /// Results still allowed by the sandbox's `max_results`
+    pub(crate) remaining: Option<usize>,
+    /// Search the cursor belongs to (operation, config, root and query)
+    fingerprint: String,
+    touched: Instant,
+}
+
+impl Cursor {
+    pub(crate) fn new(walk: Entries, fingerprint: String, remaining: Option<usize>) -> Self {
+        Self {
+            walk,
+            pending: VecDeque::new(),
+            deferred: Vec::new(),
+            remaining,
+            fingerprint,
+            touched: Instant::now(),
+        }
+    }
+}
+
+/// Resume the search parked under `token`
+///
+/// Fails if the token is unknown or expired, or was issued for a different
+/// search; in the latter case the cursor stays parked.
+pub(crate) fn take(token: &str, fingerprint: &str) -> napi::Result<Cursor> {
+    let mut cursors = CURSORS.lock();
+    expire(&mut cursors);
+    let cursor = cursors.remove(token).ok_or_else(|| {
+        errors::new(
+            ErrorCode::InvalidArgument,
+            format!("Unknown or expired search cursor: {}", token),
+        )
+    })?;
+    if cursor.fingerprint != fingerprint {
+        cursors.insert(token.to_string(), cursor);
+        return Err(errors::new(
+            ErrorCode::InvalidArgument,
+            "Search cursor was issued for a different search or configuration".to_string(),
+        ));
+    }
+    Ok(cursor)
+}
+
+/// Park a search until its next page is requested, returning its new token
+pub(crate) fn park(mut cursor: Cursor) -> napi::Result<String> {
+    let token = new_id()?;
+    cursor.touched = Instant::now();
+    let mut cursors = CURSORS.lock();
+    expire(&mut cursors);
+    if cursors.len() >= MAX_CURSORS {
+        let oldest = cursors
+            .iter()
+            .min_by_key(|(_, cursor)| cursor.touched)
+            .map(|(token, _)| token.clone());
+        if let Some(oldest) = oldest {
+            cursors.remove(&oldest);
+        }
+    }
+    cursors.insert(token.clone(), cursor);
+    Ok(token)
+}
+
+fn expire(cursors: &mut BTreeMap<String, Cursor>) {
+    cursors.retain(|_, cursor| cursor.touched.elapsed() < CURSOR_TTL);
+}
+
+/// Release a paged search before reaching its last page
+///
+/// Returns whether the cursor was still open.
+#[napi]
+pub fn close_search_cursor(cursor: String) -> bool {
+    CURSORS.lock().remove(&cursor).is_some()
+}
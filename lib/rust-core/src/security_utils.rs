@@ -3,8 +3,95 @@
 //! This module provides safe path handling to prevent directory traversal
 //! and other file system security issues.
 
+pub mod archive;
+#[cfg(all(feature = "fs", feature = "text"))]
+pub mod config_leaks;
+pub mod filename;
+#[cfg(all(feature = "fs", feature = "text"))]
+pub mod pii;
+pub mod quarantine;
+pub mod quota;
+pub mod redos;
+pub mod sandbox;
+#[cfg(all(feature = "fs", feature = "text"))]
+pub mod secrets;
+pub mod secure_delete;
+pub mod secure_memory;
+pub mod shell;
+pub mod signatures;
+pub mod unicode_spoofing;
+pub mod windows_path;
+
+use crate::audit::{self, AuditKind};
+use crate::errors;
+#[cfg(all(feature = "fs", feature = "text"))]
+use crate::concurrency;
+#[cfg(all(feature = "fs", feature = "text"))]
+use crate::metrics;
+use filename::SanitizeFilenameOptions;
 use napi_derive::napi;
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Bytes inspected when deciding whether a file is binary
+const BINARY_SNIFF_LEN: usize = 8192;
+
+/// Symlinks followed before a path is reported as a loop (Linux's ELOOP limit)
+const MAX_SYMLINK_HOPS: u32 = 40;
+
+/// Read a file as text for scanning, returning `None` for binary content
+#[cfg(all(feature = "fs", feature = "text"))]
+pub(crate) fn read_text_file(path: &Path) -> std::io::Result<Option<String>> {
+    let bytes = concurrency::read_file(path)?;
+    metrics::add_bytes("content_scan", bytes.len() as u64);
+    if bytes[..bytes.len().min(BINARY_SNIFF_LEN)].contains(&0) {
+        return Ok(None);
+    }
+    Ok(Some(String::from_utf8_lossy(&bytes).into_owned()))
+}
+
+/// Policy controlling which paths `validate_path` accepts
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathPolicy {
+    /// Accept absolute input paths (they must still resolve inside the base)
+    pub allow_absolute: bool,
+    /// Accept `..` components as long as the result stays inside the base
+    pub allow_parent_components: bool,
+    /// Accept hidden components (names starting with '.') below the base
+    pub allow_hidden: bool,
+    /// Accept ASCII control characters in component names (NUL is always rejected)
+    pub allow_control_chars: bool,
+    /// Resolve symlinks of the existing part of the path before the containment check
+    pub resolve_symlinks: bool,
+    /// Maximum total path length in bytes (0 for no limit)
+    pub max_path_length: u32,
+    /// Maximum length of a single component in bytes (0 for no limit)
+    pub max_component_length: u32,
+    /// Component names that are always rejected (e.g. ".git")
+    pub forbidden_components: Vec<String>,
+    /// Interpret paths with Windows semantics: drive letters, UNC and `\\?\`
+    /// prefixes, reserved device names, alternate data streams, and
+    /// case-insensitive containment (defaults to the host platform)
+    pub windows_paths: bool,
+}
+
+impl Default for PathPolicy {
+    fn default() -> Self {
+        Self {
+            allow_absolute: true,
+            allow_parent_components: true,
+            allow_hidden: true,
+            allow_control_chars: false,
+            resolve_symlinks: true,
+            max_path_length: 4096,
+            max_component_length: 255,
+            forbidden_components: Vec::new(),
+            windows_paths: cfg!(windows),
+        }
+    }
+}
 
 /// Path validation result
 #[napi(object)]
@@ -16,132 +103,489 @@ pub struct PathValidationResult {
     pub sanitized_path: Option<String>,
     /// Error message (if invalid)
     pub error: Option<String>,
+    /// Violated policy rule (if invalid): "null_byte", "control_char",
+    /// "path_too_long", "component_too_long", "absolute_path",
+    /// "parent_component", "outside_base", "symlink_escape",
+    /// "hidden_component", or "forbidden_component"; with Windows
+    /// semantics also "device_path", "drive_relative", "reserved_name", or
+    /// "alternate_stream"
+    pub violation: Option<String>,
+}
+
+/// Result of resolving a path one component at a time
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SymlinkCheckResult {
+    /// Whether the path, and every link followed on the way, resolves inside the base
+    pub is_valid: bool,
+    /// Fully resolved path (if valid)
+    pub resolved_path: Option<String>,
+    /// Input prefix whose resolution left the base (e.g. "assets/link")
+    pub escaping_component: Option<String>,
+    /// Target of the symlink that led outside the base, if a symlink did
+    pub link_target: Option<String>,
+    /// Number of symlinks followed
+    pub symlinks_followed: u32,
+    /// Error message (if invalid)
+    pub error: Option<String>,
+}
+
+/// One step of `check_symlink_escape`'s walk
+enum Step {
+    /// Path component to resolve, with the input prefix it came from
+    Component(PathBuf, PathBuf),
+    /// A link's target has been consumed: (input prefix, link target)
+    LinkEnd(PathBuf, PathBuf),
+}
+
+impl SymlinkCheckResult {
+    fn escape(origin: &Path, link_target: Option<&Path>, symlinks_followed: u32, error: String) -> Self {
+        Self {
+            is_valid: false,
+            resolved_path: None,
+            escaping_component: Some(origin.to_string_lossy().to_string()),
+            link_target: link_target.map(|t| t.to_string_lossy().to_string()),
+            symlinks_followed,
+            error: Some(error),
+        }
+    }
+}
+
+impl PathValidationResult {
+    fn valid(sanitized_path: String) -> Self {
+        Self {
+            is_valid: true,
+            sanitized_path: Some(sanitized_path),
+            error: None,
+            violation: None,
+        }
+    }
+
+    fn invalid(violation: &str, error: String) -> Self {
+        Self {
+            is_valid: false,
+            sanitized_path: None,
+            error: Some(error),
+            violation: Some(violation.to_string()),
+        }
+    }
 }
 
 /// Security utilities
 #[napi]
-pub struct SecurityUtils;
+pub struct SecurityUtils {
+    policy: PathPolicy,
+}
 
 #[napi]
 impl SecurityUtils {
-    #[napi(constructor)]
     /// Create a new security utilities instance
-    pub fn new() -> Self {
-        Self
+    ///
+    /// # Arguments
+    /// * `policy` - Optional path policy (permissive defaults when omitted)
+    #[napi(constructor)]
+    pub fn new(policy: Option<PathPolicy>) -> Self {
+        Self {
+            policy: policy.unwrap_or_default(),
+        }
     }
 
     /// Validate and sanitize file path
+    ///
+    /// The path is normalized component by component (relative paths are
+    /// joined onto the base), checked against the policy, and must stay
+    /// inside the base. With `resolve_symlinks`, the existing part of both
+    /// paths is canonicalized so symlinks cannot be used to leave the base.
+    /// Legal filename characters such as `~`, `$` or spaces are accepted.
+    /// With `windows_paths`, Windows syntax is parsed on any host.
     #[napi]
     pub fn validate_path(
         &self,
         path: String,
         base_path: String,
     ) -> napi::Result<PathValidationResult> {
-        let path_obj = Path::new(&path);
-        let base = Path::new(&base_path);
+        errors::guard("SecurityUtils.validatePath", || {
+            let result = self.check_path(&path, &base_path)?;
+            audit::record(
+                AuditKind::PathValidation,
+                "validate_path",
+                &path,
+                if result.is_valid { "allowed" } else { "denied" },
+                result.violation.as_deref(),
+            );
+            Ok(result)
+        })
+    }
 
-        // Check for null bytes
+    /// `validate_path` without the audit record
+    fn check_path(&self, path: &str, base_path: &str) -> napi::Result<PathValidationResult> {
+        let policy = &self.policy;
+
+        // Check for null bytes and control characters
         if path.contains('\0') {
-            return Ok(PathValidationResult {
-                is_valid: false,
-                sanitized_path: None,
-                error: Some("Path contains null bytes".to_string()),
-            });
-        }
-
-        // Normalize the path
-        let normalized = match path_obj.canonicalize() {
-            Ok(p) => p,
-            Err(_) => {
-                // If the path doesn't exist, try to normalize it manually
-                let mut components = Vec::new();
-                for component in path_obj.components() {
-                    match component {
-                        std::path::Component::ParentDir => {
-                            components.pop();
-                        }
-                        std::path::Component::Normal(c) => {
-                            components.push(c.to_string_lossy().to_string());
-                        }
-                        std::path::Component::RootDir => {
-                            components.clear();
+            return Ok(PathValidationResult::invalid("null_byte", "Path contains null bytes".to_string()));
+        }
+        if !policy.allow_control_chars && path.chars().any(|c| c.is_control()) {
+            return Ok(PathValidationResult::invalid(
+                "control_char",
+                "Path contains control characters".to_string(),
+            ));
+        }
+        if policy.max_path_length > 0 && path.len() > policy.max_path_length as usize {
+            return Ok(PathValidationResult::invalid(
+                "path_too_long",
+                format!("Path exceeds {} bytes", policy.max_path_length),
+            ));
+        }
+
+        if policy.windows_paths {
+            return windows_path::validate(policy, path, base_path);
+        }
+
+        let input = Path::new(path);
+        if input.is_absolute() && !policy.allow_absolute {
+            return Ok(PathValidationResult::invalid(
+                "absolute_path",
+                "Absolute paths are not allowed".to_string(),
+            ));
+        }
+        if !policy.allow_parent_components && input.components().any(|c| c == Component::ParentDir) {
+            return Ok(PathValidationResult::invalid(
+                "parent_component",
+                "Parent directory components are not allowed".to_string(),
+            ));
+        }
+
+        // Normalize the base and the joined path lexically. The base is
+        // canonicalized when it exists, so an absolute input may spell it
+        // either way, or through other symlinks to it.
+        let lexical_base = lexical_absolute(Path::new(base_path))?;
+        let base = lexical_base.canonicalize().unwrap_or_else(|_| lexical_base.clone());
+        let spelled = lexical_normalize(&lexical_base.join(input));
+
+        // Ensure the path is within the base path
+        let relative = match spelled
+            .strip_prefix(&lexical_base)
+            .or_else(|_| spelled.strip_prefix(&base))
+        {
+            Ok(relative) => relative.to_path_buf(),
+            Err(_) => match resolve_existing(&spelled).strip_prefix(&base) {
+                Ok(relative) => relative.to_path_buf(),
+                Err(_) => {
+                    return Ok(PathValidationResult::invalid(
+                        "outside_base",
+                        "Path traversal detected".to_string(),
+                    ))
+                }
+            },
+        };
+        let normalized = base.join(&relative);
+
+        // Check the components below the base
+        for component in relative.components() {
+            let name = component.as_os_str().to_string_lossy();
+            if policy.max_component_length > 0 && name.len() > policy.max_component_length as usize {
+                return Ok(PathValidationResult::invalid(
+                    "component_too_long",
+                    format!("Component '{}' exceeds {} bytes", name, policy.max_component_length),
+                ));
+            }
+            if !policy.allow_hidden && name.starts_with('.') {
+                return Ok(PathValidationResult::invalid(
+                    "hidden_component",
+                    format!("Hidden component '{}' is not allowed", name),
+                ));
+            }
+            if policy.forbidden_components.iter().any(|f| f == name.as_ref()) {
+                return Ok(PathValidationResult::invalid(
+                    "forbidden_component",
+                    format!("Component '{}' is not allowed", name),
+                ));
+            }
+        }
+
+        if !policy.resolve_symlinks {
+            return Ok(PathValidationResult::valid(normalized.to_string_lossy().to_string()));
+        }
+
+        // Compare real locations so symlinks cannot leave the base
+        let real_base = resolve_existing(&base);
+        let resolved = resolve_existing(&normalized);
+        if !resolved.starts_with(&real_base) {
+            return Ok(PathValidationResult::invalid(
+                "symlink_escape",
+                format!("Path resolves outside the base: {}", resolved.display()),
+            ));
+        }
+
+        Ok(PathValidationResult::valid(resolved.to_string_lossy().to_string()))
+    }
+
+    /// Check that resolving a path never leaves the base through a symlink
+    ///
+    /// Unlike canonicalizing the whole path, each component is resolved in
+    /// turn: symlinks are read and their targets walked the same way, and
+    /// each link inside the base must resolve inside it once its target is
+    /// consumed (a target may pass through `..` outside on the way). This
+    /// catches links whose target is outside the base even when the rest of
+    /// the path does not exist yet, and links that come back into the base
+    /// through a symlink outside it. Components past the first missing one
+    /// are resolved lexically.
+    ///
+    /// # Arguments
+    /// * `path` - Path to check, relative to the base or absolute below it
+    /// * `base_path` - Existing directory the path must stay inside
+    #[napi]
+    pub fn validate_path_no_symlink_escape(
+        &self,
+        path: String,
+        base_path: String,
+    ) -> napi::Result<SymlinkCheckResult> {
+        errors::guard("SecurityUtils.validatePathNoSymlinkEscape", || {
+            let result = self.check_symlink_escape(&path, &base_path)?;
+            audit::record(
+                AuditKind::PathValidation,
+                "validate_path_no_symlink_escape",
+                &path,
+                if result.is_valid { "allowed" } else { "denied" },
+                result.error.as_deref(),
+            );
+            Ok(result)
+        })
+    }
+
+    /// `validate_path_no_symlink_escape` without the audit record
+    fn check_symlink_escape(&self, path: &str, base_path: &str) -> napi::Result<SymlinkCheckResult> {
+        let real_base = Path::new(base_path).canonicalize().map_err(|e| {
+            errors::io(
+                &e,
+                format!("Cannot resolve base path '{}': {}", base_path, e),
+            )
+        })?;
+
+        let input = Path::new(&path);
+        if path.contains('\0') {
+            return Ok(SymlinkCheckResult::escape(input, None, 0, "Path contains null bytes".to_string()));
+        }
+
+        // Absolute inputs must name the base (as given or resolved) lexically
+        let relative = if input.is_absolute() {
+            let given_base = lexical_normalize(Path::new(&base_path));
+            match input.strip_prefix(&given_base).or_else(|_| input.strip_prefix(&real_base)) {
+                Ok(relative) => relative,
+                Err(_) => {
+                    return Ok(SymlinkCheckResult::escape(
+                        input,
+                        None,
+                        0,
+                        "Path is not below the base".to_string(),
+                    ))
+                }
+            }
+        } else {
+            input
+        };
+
+        // Stack of steps, next one last
+        let mut pending: Vec<Step> = Vec::new();
+        let mut origin = PathBuf::new();
+        for component in relative.components() {
+            origin.push(component);
+            pending.push(Step::Component(PathBuf::from(component.as_os_str()), origin.clone()));
+        }
+        pending.reverse();
+
+        let mut current = real_base.clone();
+        let mut exists = true;
+        let mut symlinks_followed = 0;
+        // Links whose targets are being walked, and where the input first left the base
+        let mut open_links = 0;
+        let mut left_at: Option<PathBuf> = None;
+
+        while let Some(step) = pending.pop() {
+            let (name, origin) = match step {
+                Step::Component(name, origin) => (name, origin),
+                Step::LinkEnd(origin, target) => {
+                    open_links -= 1;
+                    if open_links == 0 && !current.starts_with(&real_base) {
+                        return Ok(SymlinkCheckResult::escape(
+                            &origin,
+                            Some(&target),
+                            symlinks_followed,
+                            format!("'{}' resolves outside the base: {}", origin.display(), current.display()),
+                        ));
+                    }
+                    continue;
+                }
+            };
+            let Some(component) = name.components().next() else {
+                continue;
+            };
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    current.pop();
+                }
+                Component::Prefix(_) => current = name.clone(),
+                Component::RootDir => current.push(component),
+                Component::Normal(part) => {
+                    let next = current.join(part);
+                    if !exists {
+                        current = next;
+                    } else {
+                        match fs::symlink_metadata(&next) {
+                            Ok(meta) if meta.file_type().is_symlink() => {
+                                if !current.starts_with(&real_base) {
+                                    // Its target can change without touching the base
+                                    return Ok(SymlinkCheckResult::escape(
+                                        &origin,
+                                        None,
+                                        symlinks_followed,
+                                        format!("'{}' passes through a symlink outside the base: {}", origin.display(), next.display()),
+                                    ));
+                                }
+                                symlinks_followed += 1;
+                                if symlinks_followed > MAX_SYMLINK_HOPS {
+                                    return Ok(SymlinkCheckResult::escape(
+                                        &origin,
+                                        None,
+                                        symlinks_followed,
+                                        format!("Too many levels of symbolic links at '{}'", origin.display()),
+                                    ));
+                                }
+                                // Walk the target from the link's directory
+                                let target = fs::read_link(&next).map_err(|e| {
+                                    errors::io(&e, format!("Failed to read link {}: {}", next.display(), e))
+                                })?;
+                                pending.push(Step::LinkEnd(origin.clone(), target.clone()));
+                                let mut expanded: Vec<Step> = target
+                                    .components()
+                                    .map(|c| Step::Component(PathBuf::from(c.as_os_str()), origin.clone()))
+                                    .collect();
+                                expanded.reverse();
+                                pending.extend(expanded);
+                                open_links += 1;
+                                continue;
+                            }
+                            Ok(_) => current = next,
+                            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                                exists = false;
+                                current = next;
+                            }
+                            Err(e) => return Err(e.into()),
                         }
-                        _ => {}
                     }
                 }
-                
-                let joined = components.join("/");
-                Path::new(&base_path).join(&joined)
             }
-        };
 
-        // Ensure the path is within the base path
-        if !normalized.starts_with(&base) {
-            return Ok(PathValidationResult {
-                is_valid: false,
-                sanitized_path: None,
-                error: Some("Path traversal detected".to_string()),
-            });
-        }
-
-        // Check for dangerous patterns
-        let path_str = normalized.to_string_lossy();
-        let dangerous_patterns = [
-            "..",
-            "~",
-            "$",
-            "|",
-            ";",
-            "&",
-            ">",
-            "<",
-            "`",
-            "\\",
-        ];
-
-        for pattern in &dangerous_patterns {
-            if path_str.contains(pattern) {
-                return Ok(PathValidationResult {
-                    is_valid: false,
-                    sanitized_path: None,
-                    error: Some(format!("Dangerous pattern '{}' detected", pattern)),
-                });
+            if open_links == 0 {
+                if current.starts_with(&real_base) {
+                    left_at = None;
+                } else if left_at.is_none() {
+                    left_at = Some(origin);
+                }
             }
         }
 
-        Ok(PathValidationResult {
+        if let Some(origin) = left_at {
+            return Ok(SymlinkCheckResult::escape(
+                &origin,
+                None,
+                symlinks_followed,
+                format!("'{}' resolves outside the base: {}", origin.display(), current.display()),
+            ));
+        }
+
+        Ok(SymlinkCheckResult {
             is_valid: true,
-            sanitized_path: Some(normalized.to_string_lossy().to_string()),
+            resolved_path: Some(current.to_string_lossy().to_string()),
+            escaping_component: None,
+            link_target: None,
+            symlinks_followed,
             error: None,
         })
     }
 
-    /// Sanitize filename by removing dangerous characters
+    /// Sanitize a file name into a single portable path component
+    ///
+    /// Separators and Windows-reserved punctuation become '_', control and
+    /// invisible characters are dropped, reserved device names are renamed,
+    /// and the name is truncated to the file system's length limit while
+    /// keeping its extension.
+    ///
+    /// # Arguments
+    /// * `filename` - Name to sanitize
+    /// * `options` - Optional length limit, transliteration and uniqueness settings
     #[napi]
-    pub fn sanitize_filename(&self, filename: String) -> napi::Result<String> {
-        let dangerous_chars = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
-        let mut sanitized = filename;
+    pub fn sanitize_filename(
+        &self,
+        filename: String,
+        options: Option<SanitizeFilenameOptions>,
+    ) -> napi::Result<String> {
+        errors::guard("SecurityUtils.sanitizeFilename", || {
+            filename::sanitize(&filename, &options.unwrap_or_default())
+        })
+    }
+}
+
+/// Make the base absolute and normalized without resolving symlinks
+fn lexical_absolute(base: &Path) -> napi::Result<PathBuf> {
+    let absolute = if base.is_absolute() {
+        base.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(base)
+    };
+    Ok(lexical_normalize(&absolute))
+}
 
-        for ch in &dangerous_chars {
-            sanitized = sanitized.replace(*ch, "_");
+/// Normalize `.` and `..` components without touching the file system
+///
+/// `..` at the root stays at the root, matching how the OS resolves it.
+fn lexical_normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir) | Some(Component::Prefix(_)) => {}
+                _ => normalized.push(component),
+            },
+            _ => normalized.push(component),
         }
+    }
+    normalized
+}
 
-        // Remove leading/trailing dots and spaces
-        sanitized = sanitized.trim_matches('.').trim_matches(' ').to_string();
+/// Canonicalize the longest existing ancestor and append the remainder
+fn resolve_existing(path: &Path) -> PathBuf {
+    let mut existing = path;
+    let mut remainder = Vec::new();
 
-        // Ensure the filename is not empty
-        if sanitized.is_empty() {
-            sanitized = "unnamed".to_string();
+    loop {
+        if let Ok(canonical) = existing.canonicalize() {
+            let mut resolved = canonical;
+            resolved.extend(remainder.iter().rev());
+            return resolved;
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                remainder.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => return path.to_path_buf(),
         }
-
-        Ok(sanitized)
     }
 }
 
 /// Quick path validation function
 #[napi]
 pub fn quick_validate_path(path: String, base_path: String) -> napi::Result<bool> {
-    let utils = SecurityUtils::new();
-    let result = utils.validate_path(path, base_path)?;
-    Ok(result.is_valid)
+    errors::guard("quickValidatePath", || {
+        let utils = SecurityUtils::new(None);
+        let result = utils.validate_path(path, base_path)?;
+        Ok(result.is_valid)
+    })
 }
\ No newline at end of file
@@ -0,0 +1,282 @@
+//! Archive entry validation against zip-slip attacks
+//!
+//! Entry names are checked before anything is written: absolute paths,
+//! drive letters, `..` components, names that are invalid on Windows, and
+//! symlink entries whose target (or whose use as a directory by a later
+//! entry) would place files outside the destination. Names are split on both
+//! `/` and `\`, since archives built on Windows use either separator.
+
+use super::windows_path::is_reserved_name;
+use super::{SecurityUtils, MAX_SYMLINK_HOPS};
+use crate::audit::{self, AuditKind};
+use crate::errors;
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Archive entry as listed by the archive reader
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    /// Entry name as stored in the archive
+    pub name: String,
+    /// Whether the entry is a symbolic link
+    pub is_symlink: Option<bool>,
+    /// Link target for symlink entries
+    pub link_target: Option<String>,
+}
+
+/// Options for archive entry validation
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveValidationOptions {
+    /// Accept symlink entries whose target stays inside the destination
+    pub allow_symlinks: bool,
+    /// Reject names that cannot be created on Windows (reserved devices, ':')
+    pub portable_names: bool,
+    /// Check existing symlinks inside the destination directory on disk
+    pub check_destination: bool,
+}
+
+impl Default for ArchiveValidationOptions {
+    fn default() -> Self {
+        Self {
+            allow_symlinks: true,
+            portable_names: true,
+            check_destination: true,
+        }
+    }
+}
+
+/// Verdict for a single archive entry
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveEntryVerdict {
+    /// Entry name as stored in the archive
+    pub name: String,
+    /// Whether the entry can be extracted safely
+    pub is_safe: bool,
+    /// Where the entry would be written (if safe)
+    pub destination_path: Option<String>,
+    /// Violated rule (if unsafe): "empty_name", "control_char", "absolute_path",
+    /// "drive_letter", "parent_component", "reserved_name", "invalid_character",
+    /// "symlink_not_allowed", "symlink_escape", or "through_symlink"
+    pub violation: Option<String>,
+    /// Human-readable explanation (if unsafe)
+    pub reason: Option<String>,
+}
+
+impl ArchiveEntryVerdict {
+    fn unsafe_entry(name: &str, violation: &str, reason: String) -> Self {
+        Self {
+            name: name.to_string(),
+            is_safe: false,
+            destination_path: None,
+            violation: Some(violation.to_string()),
+            reason: Some(reason),
+        }
+    }
+}
+
+/// Split an entry name into components, dropping empty and `.` segments
+fn entry_components(name: &str) -> Vec<&str> {
+    name.split(['/', '\\']).filter(|c| !c.is_empty() && *c != ".").collect()
+}
+
+/// Whether a name starts with a root, drive letter or UNC prefix
+fn rooted_kind(name: &str) -> Option<&'static str> {
+    let bytes = name.as_bytes();
+    if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        Some("drive_letter")
+    } else if name.starts_with(['/', '\\']) {
+        Some("absolute_path")
+    } else {
+        None
+    }
+}
+
+/// Check a single entry name, without regard to other entries
+fn check_name(entry: &ArchiveEntry, options: &ArchiveValidationOptions) -> Option<(&'static str, String)> {
+    let name = &entry.name;
+    if entry_components(name).is_empty() {
+        return Some(("empty_name", "Entry name is empty".to_string()));
+    }
+    if name.chars().any(|c| c.is_control()) {
+        return Some(("control_char", "Entry name contains control characters".to_string()));
+    }
+    if let Some(kind) = rooted_kind(name) {
+        return Some((kind, format!("Entry '{}' is not a relative path", name)));
+    }
+
+    for component in entry_components(name) {
+        if component == ".." {
+            return Some(("parent_component", format!("Entry '{}' contains '..'", name)));
+        }
+        if options.portable_names {
+            if is_reserved_name(component) {
+                return Some(("reserved_name", format!("'{}' names a reserved Windows device", component)));
+            }
+            if component.contains(':') {
+                return Some((
+                    "invalid_character",
+                    format!("'{}' contains ':' (an alternate data stream on Windows)", component),
+                ));
+            }
+        }
+    }
+    None
+}
+
+/// Check that a symlink entry's target resolves inside the destination
+///
+/// Components naming earlier symlink entries are expanded to their targets,
+/// so a chain of individually harmless links cannot escape.
+///
+/// # Arguments
+/// * `name` - Name of the symlink entry
+/// * `target` - Its link target
+/// * `symlinks` - Earlier symlink entries, by components, with their targets
+fn check_link_target(
+    name: &str,
+    target: &str,
+    symlinks: &HashMap<Vec<String>, String>,
+) -> Option<(&'static str, String)> {
+    if rooted_kind(target).is_some() {
+        return Some(("symlink_escape", format!("Link target '{}' is absolute", target)));
+    }
+
+    // Resolve the target from the directory holding the link
+    let mut resolved: Vec<String> = entry_components(name).iter().map(|c| c.to_string()).collect();
+    resolved.pop();
+    let mut pending: Vec<&str> = entry_components(target);
+    pending.reverse();
+    let mut hops = 0;
+    while let Some(component) = pending.pop() {
+        if component == ".." {
+            if resolved.pop().is_none() {
+                return Some((
+                    "symlink_escape",
+                    format!("Link target '{}' points outside the destination", target),
+                ));
+            }
+            continue;
+        }
+        resolved.push(component.to_string());
+        if let Some(link) = symlinks.get(&resolved) {
+            hops += 1;
+            if hops > MAX_SYMLINK_HOPS {
+                return Some((
+                    "symlink_escape",
+                    format!("Link target '{}' has too many levels of symbolic links", target),
+                ));
+            }
+            resolved.pop();
+            let mut expanded = entry_components(link);
+            expanded.reverse();
+            pending.extend(expanded);
+        }
+    }
+    None
+}
+
+/// Validate archive entry names against a destination directory
+///
+/// Entries are checked in archive order, so a file placed beneath an earlier
+/// symlink entry (the two-step zip-slip variant) is rejected even when the
+/// link itself looked harmless. Call this before extracting anything.
+///
+/// # Arguments
+/// * `entries` - Entries in archive order
+/// * `destination` - Directory the archive will be extracted into
+/// * `options` - Optional validation options
+#[napi]
+pub fn validate_archive_entries(
+    entries: Vec<ArchiveEntry>,
+    destination: String,
+    options: Option<ArchiveValidationOptions>,
+) -> napi::Result<Vec<ArchiveEntryVerdict>> {
+    errors::guard("validateArchiveEntries", || {
+        let options = options.unwrap_or_default();
+        let destination_root = Path::new(&destination);
+        let check_disk = options.check_destination && destination_root.is_dir();
+        let utils = SecurityUtils::new(None);
+
+        let mut symlinks: HashMap<Vec<String>, String> = HashMap::new();
+        let mut verdicts = Vec::with_capacity(entries.len());
+
+        for entry in &entries {
+            if let Some((violation, reason)) = check_name(entry, &options) {
+                verdicts.push(ArchiveEntryVerdict::unsafe_entry(&entry.name, violation, reason));
+                continue;
+            }
+
+            let components = entry_components(&entry.name);
+
+            // A later entry must not be written through an earlier symlink entry
+            let through_link = (1..components.len()).find(|&len| {
+                let prefix: Vec<String> = components[..len].iter().map(|c| c.to_string()).collect();
+                symlinks.contains_key(&prefix)
+            });
+            if let Some(len) = through_link {
+                verdicts.push(ArchiveEntryVerdict::unsafe_entry(
+                    &entry.name,
+                    "through_symlink",
+                    format!("Entry is written through symlink entry '{}'", components[..len].join("/")),
+                ));
+                continue;
+            }
+
+            if entry.is_symlink.unwrap_or(false) {
+                if !options.allow_symlinks {
+                    verdicts.push(ArchiveEntryVerdict::unsafe_entry(
+                        &entry.name,
+                        "symlink_not_allowed",
+                        "Symlink entries are not allowed".to_string(),
+                    ));
+                    continue;
+                }
+                let target = entry.link_target.as_deref().unwrap_or("");
+                if let Some((violation, reason)) = check_link_target(&entry.name, target, &symlinks) {
+                    verdicts.push(ArchiveEntryVerdict::unsafe_entry(&entry.name, violation, reason));
+                    continue;
+                }
+                symlinks.insert(components.iter().map(|c| c.to_string()).collect(), target.to_string());
+            }
+
+            let relative: PathBuf = components.iter().collect();
+
+            // Existing links inside the destination can redirect the write as well
+            if check_disk {
+                let check = utils.check_symlink_escape(&relative.to_string_lossy(), &destination)?;
+                if !check.is_valid {
+                    verdicts.push(ArchiveEntryVerdict::unsafe_entry(
+                        &entry.name,
+                        "symlink_escape",
+                        check.error.unwrap_or_default(),
+                    ));
+                    continue;
+                }
+            }
+
+            verdicts.push(ArchiveEntryVerdict {
+                name: entry.name.clone(),
+                is_safe: true,
+                destination_path: Some(destination_root.join(&relative).to_string_lossy().to_string()),
+                violation: None,
+                reason: None,
+            });
+        }
+
+        for verdict in &verdicts {
+            audit::record(
+                AuditKind::PathValidation,
+                "validate_archive_entries",
+                &verdict.name,
+                if verdict.is_safe { "allowed" } else { "denied" },
+                verdict.violation.as_deref(),
+            );
+        }
+        Ok(verdicts)
+    })
+}
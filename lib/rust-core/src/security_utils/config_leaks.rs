@@ -0,0 +1,535 @@
+//! Credential leak detection for `.env`, YAML and JSON configuration files
+//!
+//! Config files are read as key/value entries rather than free text, so a
+//! value can be judged by the key it is assigned to: literal values for
+//! credential-like keys, URLs with embedded passwords, keys that duplicate
+//! well-known secret environment variables, and values matching the secret
+//! scanner's built-in formats. Placeholders and environment references
+//! (`${VAR}`, `<token>`, `changeme`) are not reported.
+
+use super::read_text_file;
+use super::secrets::{redact, shannon_entropy, SecretScanConfig, SecretScanner};
+use crate::concurrency;
+use crate::errors::{self, ErrorCode};
+use crate::file_search::{FileSearch, FileSearchConfig};
+use crate::kernels::LineIndex;
+use napi_derive::napi;
+use rayon::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Environment variables that conventionally hold secrets
+const KNOWN_SECRET_NAMES: &[&str] = &[
+    "AWS_SECRET_ACCESS_KEY", "AWS_SESSION_TOKEN", "AZURE_CLIENT_SECRET", "GITHUB_TOKEN", "GH_TOKEN",
+    "GITLAB_TOKEN", "NPM_TOKEN", "NODE_AUTH_TOKEN", "PYPI_TOKEN", "CARGO_REGISTRY_TOKEN",
+    "OPENAI_API_KEY", "ANTHROPIC_API_KEY", "STRIPE_SECRET_KEY", "SLACK_BOT_TOKEN", "SLACK_WEBHOOK_URL",
+    "TWILIO_AUTH_TOKEN", "SENDGRID_API_KEY", "SENTRY_AUTH_TOKEN", "HEROKU_API_KEY", "DIGITALOCEAN_TOKEN",
+    "DOCKER_PASSWORD", "JWT_SECRET", "SESSION_SECRET",
+];
+
+/// Key fragments that mark a value as a credential
+const CREDENTIAL_MARKERS: &[&str] = &[
+    "password", "passwd", "pwd", "secret", "token", "apikey", "api_key", "access_key", "private_key",
+    "client_secret", "credential", "auth_key",
+];
+
+/// Key suffixes that name something about a credential rather than the credential itself
+const NON_CREDENTIAL_SUFFIXES: &[&str] = &[
+    "file", "path", "env", "name", "url", "uri", "endpoint", "length", "type", "header", "expiry", "ttl",
+];
+
+/// Values that stand in for a real credential
+const PLACEHOLDER_VALUES: &[&str] = &[
+    "changeme", "change_me", "password", "secret", "example", "placeholder", "null", "none", "nil",
+    "true", "false", "todo", "tbd", "redacted",
+];
+
+/// Configuration for config leak scanning
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigLeakConfig {
+    /// Environment variable names whose values are secrets; config keys
+    /// with these names are reported when assigned a literal value
+    pub known_secret_names: Vec<String>,
+    /// Minimum length of a literal value assigned to a credential-like key
+    pub min_value_length: u32,
+    /// Maximum number of findings to return (0 for unlimited)
+    pub max_findings: u32,
+    /// Traversal and exclusion settings for directory scans
+    pub search: Option<FileSearchConfig>,
+}
+
+impl Default for ConfigLeakConfig {
+    fn default() -> Self {
+        Self {
+            known_secret_names: KNOWN_SECRET_NAMES.iter().map(|n| n.to_string()).collect(),
+            min_value_length: 6,
+            max_findings: 0,
+            search: None,
+        }
+    }
+}
+
+/// Leaked credential found in a config file
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigLeakFinding {
+    /// File path (or the label passed for text scans)
+    pub path: String,
+    /// Finding kind: "known_secret_format", "url_credentials",
+    /// "known_secret_name", or "hardcoded_credential"
+    pub kind: String,
+    /// Dotted key path of the value (e.g. "database.password")
+    pub key: String,
+    /// Line number (1-based)
+    pub line_number: u32,
+    /// Start byte column of the value within the line
+    pub column_start: u32,
+    /// End byte column of the value within the line
+    pub column_end: u32,
+    /// Severity: "critical", "high", "medium", or "low"
+    pub severity: String,
+    /// Value with all but a short prefix masked
+    pub redacted_value: String,
+    /// Explanation of the finding
+    pub message: String,
+}
+
+/// Supported config file formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigFormat {
+    Env,
+    Yaml,
+    Json,
+}
+
+impl ConfigFormat {
+    fn parse(format: &str) -> napi::Result<Self> {
+        match format.to_ascii_lowercase().as_str() {
+            "env" | "dotenv" => Ok(Self::Env),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            "json" => Ok(Self::Json),
+            other => Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Unsupported config format: {} (expected env, yaml, or json)", other),
+            )),
+        }
+    }
+
+    /// Format implied by a file name, if it is a supported config file
+    fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name == ".env" || name.starts_with(".env.") || name.ends_with(".env") {
+            return Some(Self::Env);
+        }
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "yml" | "yaml" => Some(Self::Yaml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Key/value pair with the byte span of the value in the content
+struct ConfigEntry {
+    key: String,
+    value: String,
+    start: usize,
+    end: usize,
+}
+
+/// Config file credential leak scanner
+#[napi]
+pub struct ConfigLeakScanner {
+    config: ConfigLeakConfig,
+    search: FileSearch,
+    secrets: SecretScanner,
+    url_credentials: Regex,
+    yaml_entry: Regex,
+}
+
+#[napi]
+impl ConfigLeakScanner {
+    /// Create a new config leak scanner with optional configuration
+    ///
+    /// # Arguments
+    /// * `config` - Optional configuration for the scanner
+    #[napi(constructor)]
+    pub fn new(config: Option<ConfigLeakConfig>) -> napi::Result<Self> {
+        errors::guard("new ConfigLeakScanner", || {
+            let config = config.unwrap_or_default();
+            let compile = |pattern: &str| {
+                Regex::new(pattern).map_err(|e| {
+                    errors::new(ErrorCode::PatternInvalid, format!("Invalid config pattern: {}", e))
+                })
+            };
+
+            Ok(Self {
+                search: FileSearch::new(config.search.clone())?,
+                secrets: SecretScanner::new(Some(SecretScanConfig {
+                    enable_entropy: false,
+                    ..Default::default()
+                }))?,
+                url_credentials: compile(r"(?i)\b[a-z][a-z0-9+.\-]*://[^/\s:@]*:(?P<password>[^/\s@]+)@")?,
+                yaml_entry: compile(
+                    r#"^(?P<indent>\s*(?:-\s+)?)(?P<key>"[^"]*"|'[^']*'|[^\s:#'"\-][^:#]*?)\s*:(?:\s+(?P<value>.*?))?\s*$"#,
+                )?,
+                config,
+            })
+        })
+    }
+
+    /// Scan config text in the given format
+    ///
+    /// # Arguments
+    /// * `text` - Config file content
+    /// * `format` - "env", "yaml", or "json"
+    /// * `label` - Optional name reported as the finding path
+    #[napi]
+    pub fn scan_text(&self, text: String, format: String, label: Option<String>) -> napi::Result<Vec<ConfigLeakFinding>> {
+        errors::guard("ConfigLeakScanner.scanText", || {
+            let format = ConfigFormat::parse(&format)?;
+            let mut findings = self.scan_content(&text, format, &label.unwrap_or_default());
+            self.truncate(&mut findings);
+            Ok(findings)
+        })
+    }
+
+    /// Scan a single config file, inferring the format from its name
+    #[napi]
+    pub fn scan_file(&self, path: String) -> napi::Result<Vec<ConfigLeakFinding>> {
+        errors::guard("ConfigLeakScanner.scanFile", || {
+            let file = Path::new(&path);
+            let format = ConfigFormat::from_path(file).ok_or_else(|| {
+                errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Not a .env, YAML, or JSON file: {}", path),
+                )
+            })?;
+            let mut findings = self.scan_path(file, format).map_err(|e| {
+                errors::io(&e, format!("Failed to read {}: {}", path, e))
+            })?;
+            self.truncate(&mut findings);
+            Ok(findings)
+        })
+    }
+
+    /// Scan every `.env`, YAML and JSON file in a directory tree in parallel
+    #[napi]
+    pub fn scan_directory(&self, root_path: String) -> napi::Result<Vec<ConfigLeakFinding>> {
+        errors::guard("ConfigLeakScanner.scanDirectory", || {
+            let _permit = concurrency::acquire("ConfigLeakScanner.scanDirectory")?;
+            let files: Vec<_> = self
+                .search
+                .collect_files(Path::new(&root_path))?
+                .into_iter()
+                .filter_map(|path| ConfigFormat::from_path(&path).map(|format| (path, format)))
+                .collect();
+            let use_parallel = self.config.search.as_ref().is_none_or(|search| search.use_parallel);
+
+            let mut findings: Vec<ConfigLeakFinding> = if use_parallel && files.len() > 100 {
+                files
+                    .par_iter()
+                    .flat_map(|(path, format)| self.scan_path(path, *format).unwrap_or_default())
+                    .collect()
+            } else {
+                files
+                    .iter()
+                    .flat_map(|(path, format)| self.scan_path(path, *format).unwrap_or_default())
+                    .collect()
+            };
+
+            findings.sort_by(|a, b| a.path.cmp(&b.path).then(a.line_number.cmp(&b.line_number)));
+            self.truncate(&mut findings);
+            Ok(findings)
+        })
+    }
+
+    /// Apply the `max_findings` limit
+    fn truncate(&self, findings: &mut Vec<ConfigLeakFinding>) {
+        if self.config.max_findings > 0 {
+            findings.truncate(self.config.max_findings as usize);
+        }
+    }
+
+    /// Read and scan one file, skipping binary content
+    fn scan_path(&self, path: &Path, format: ConfigFormat) -> std::io::Result<Vec<ConfigLeakFinding>> {
+        Ok(match read_text_file(path)? {
+            Some(text) => self.scan_content(&text, format, &path.to_string_lossy()),
+            None => Vec::new(),
+        })
+    }
+
+    /// Extract entries and classify each value
+    fn scan_content(&self, text: &str, format: ConfigFormat, path: &str) -> Vec<ConfigLeakFinding> {
+        let entries = match format {
+            ConfigFormat::Env => env_entries(text),
+            ConfigFormat::Yaml => self.yaml_entries(text),
+            ConfigFormat::Json => json_entries(text),
+        };
+
+        let lines = LineIndex::new(text);
+        entries
+            .into_iter()
+            .filter_map(|entry| {
+                let (kind, severity, message) = self.classify(&entry)?;
+                let (line_number, column_start) = lines.position(entry.start);
+                Some(ConfigLeakFinding {
+                    path: path.to_string(),
+                    kind: kind.to_string(),
+                    key: entry.key,
+                    line_number,
+                    column_start,
+                    column_end: column_start + (entry.end - entry.start) as u32,
+                    severity,
+                    redacted_value: redact(&entry.value),
+                    message,
+                })
+            })
+            .collect()
+    }
+
+    /// Decide whether a value is a leaked credential: (kind, severity, message)
+    fn classify(&self, entry: &ConfigEntry) -> Option<(&'static str, String, String)> {
+        let name = entry.key.rsplit('.').next().unwrap_or(&entry.key);
+
+        // Give secret rules the key as context (e.g. "aws_secret_access_key=...")
+        let context = format!("{}={}", name, entry.value);
+        let known_format = self
+            .secrets
+            .scan_content(&context, "")
+            .into_iter()
+            .find(|f| f.column_start as usize > name.len());
+        if let Some(finding) = known_format {
+            return Some((
+                "known_secret_format",
+                finding.severity,
+                format!("Value matches {}", finding.description),
+            ));
+        }
+
+        if let Some(captures) = self.url_credentials.captures(&entry.value) {
+            if !is_placeholder(&captures["password"]) {
+                return Some((
+                    "url_credentials",
+                    "high".to_string(),
+                    "URL embeds a password".to_string(),
+                ));
+            }
+        }
+
+        if is_placeholder(&entry.value) {
+            return None;
+        }
+
+        let normalized = name.to_ascii_uppercase().replace(['-', '.'], "_");
+        if self.config.known_secret_names.iter().any(|known| known.eq_ignore_ascii_case(&normalized)) {
+            return Some((
+                "known_secret_name",
+                "high".to_string(),
+                format!("'{}' duplicates a well-known secret environment variable", name),
+            ));
+        }
+
+        let lowercase = name.to_ascii_lowercase().replace('-', "_");
+        let credential_key = CREDENTIAL_MARKERS.iter().any(|marker| lowercase.contains(marker))
+            && !NON_CREDENTIAL_SUFFIXES.iter().any(|suffix| lowercase.ends_with(suffix));
+        if credential_key && entry.value.chars().count() >= self.config.min_value_length as usize {
+            let severity = if shannon_entropy(&entry.value) >= 3.0 { "high" } else { "medium" };
+            return Some((
+                "hardcoded_credential",
+                severity.to_string(),
+                format!("Credential '{}' is assigned a literal value", name),
+            ));
+        }
+
+        None
+    }
+
+    /// Extract `key: value` entries, tracking indentation for dotted key paths
+    fn yaml_entries(&self, text: &str) -> Vec<ConfigEntry> {
+        let mut entries = Vec::new();
+        let mut parents: Vec<(usize, String)> = Vec::new();
+        let mut offset = 0;
+
+        for line in text.split_inclusive('\n') {
+            let line_start = offset;
+            offset += line.len();
+            let content = line.trim_end_matches(['\n', '\r']);
+            if content.trim_start().starts_with('#') {
+                continue;
+            }
+            let Some(captures) = self.yaml_entry.captures(content) else {
+                continue;
+            };
+
+            let indent = captures["indent"].len();
+            let key = unquote(&captures["key"]).to_string();
+            while parents.last().is_some_and(|(level, _)| *level >= indent) {
+                parents.pop();
+            }
+
+            let Some(value) = captures.name("value").filter(|v| !v.as_str().is_empty()) else {
+                parents.push((indent, key));
+                continue;
+            };
+            // Block scalars, anchors, aliases and flow collections are not plain values
+            if value.as_str().starts_with(['|', '>', '&', '*', '{', '[', '#']) {
+                continue;
+            }
+
+            let (start, end) = scalar_span(value.as_str());
+            let path: Vec<&str> = parents.iter().map(|(_, k)| k.as_str()).chain([key.as_str()]).collect();
+            entries.push(ConfigEntry {
+                key: path.join("."),
+                value: value.as_str()[start..end].to_string(),
+                start: line_start + value.start() + start,
+                end: line_start + value.start() + end,
+            });
+        }
+        entries
+    }
+}
+
+/// Extract `KEY=value` entries from dotenv content
+fn env_entries(text: &str) -> Vec<ConfigEntry> {
+    let mut entries = Vec::new();
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let content = line.trim_end_matches(['\n', '\r']);
+        let trimmed = content.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let statement = trimmed.strip_prefix("export ").unwrap_or(trimmed);
+        let Some((key, raw)) = statement.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || "_.-".contains(c)) {
+            continue;
+        }
+
+        let raw_start = content.len() - raw.len();
+        let leading = raw.len() - raw.trim_start().len();
+        let (start, end) = scalar_span(raw.trim_start());
+        entries.push(ConfigEntry {
+            key: key.to_string(),
+            value: raw.trim_start()[start..end].to_string(),
+            start: line_start + raw_start + leading + start,
+            end: line_start + raw_start + leading + end,
+        });
+    }
+    entries
+}
+
+/// Extract string values from JSON with their dotted key paths
+///
+/// A small tokenizer is used instead of a parser so that byte positions are
+/// kept and slightly malformed files (comments, trailing commas) still scan.
+fn json_entries(text: &str) -> Vec<ConfigEntry> {
+    let bytes = text.as_bytes();
+    let mut entries = Vec::new();
+    // Key each open container was assigned to
+    let mut containers: Vec<Option<String>> = Vec::new();
+    let mut pending_key: Option<String> = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                let start = i + 1;
+                let mut end = start;
+                while end < bytes.len() && bytes[end] != b'"' {
+                    end += if bytes[end] == b'\\' { 2 } else { 1 };
+                }
+                let end = end.min(bytes.len());
+                let raw = &text[start..end];
+                i = end + 1;
+
+                let next = bytes[i.min(bytes.len())..].iter().find(|b| !b.is_ascii_whitespace());
+                if next == Some(&b':') {
+                    pending_key = Some(unescape_json(raw));
+                    continue;
+                }
+
+                // Array elements are reported under the array's key
+                let key = pending_key.take();
+                let mut path: Vec<&str> = containers.iter().flatten().map(String::as_str).collect();
+                path.extend(key.as_deref());
+                if !path.is_empty() {
+                    entries.push(ConfigEntry {
+                        key: path.join("."),
+                        value: unescape_json(raw),
+                        start,
+                        end,
+                    });
+                }
+                continue;
+            }
+            b'{' | b'[' => containers.push(pending_key.take()),
+            b'}' | b']' => {
+                containers.pop();
+                pending_key = None;
+            }
+            b',' => pending_key = None,
+            _ => {}
+        }
+        i += 1;
+    }
+    entries
+}
+
+/// Resolve the common JSON escapes
+fn unescape_json(raw: &str) -> String {
+    raw.replace("\\\"", "\"").replace("\\/", "/").replace("\\\\", "\\")
+}
+
+/// Strip matching quotes from a key
+fn unquote(key: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = key.strip_prefix(quote).and_then(|k| k.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    key
+}
+
+/// Byte span of a scalar value: inside quotes, or up to an inline comment
+fn scalar_span(raw: &str) -> (usize, usize) {
+    if let Some(quote) = raw.chars().next().filter(|c| *c == '"' || *c == '\'') {
+        let end = raw[1..].find(quote).map_or(raw.len(), |i| i + 1);
+        return (1, end);
+    }
+    let end = raw.find(" #").unwrap_or(raw.len());
+    (0, raw[..end].trim_end().len())
+}
+
+/// Whether a value is a placeholder or a reference to another variable
+fn is_placeholder(value: &str) -> bool {
+    let value = value.trim();
+    let lowercase = value.to_ascii_lowercase();
+    value.is_empty()
+        || value.starts_with('$')
+        || value.starts_with("{{")
+        || value.starts_with("%(")
+        || (value.starts_with('<') && value.ends_with('>'))
+        || PLACEHOLDER_VALUES.contains(&lowercase.as_str())
+        || lowercase.starts_with("your")
+        || lowercase.contains("example")
+        || value.chars().all(|c| c == value.chars().next().unwrap_or('x'))
+}
+
+/// Quick config leak scan of text in the given format with default settings
+#[napi]
+pub fn quick_scan_config_leaks(text: String, format: String) -> napi::Result<Vec<ConfigLeakFinding>> {
+    errors::guard("quickScanConfigLeaks", || {
+        let scanner = ConfigLeakScanner::new(None)?;
+        scanner.scan_text(text, format, None)
+    })
+}
@@ -0,0 +1,212 @@
+//! Portable file name sanitization
+//!
+//! Turns arbitrary text into a single file name component that every
+//! mainstream file system accepts: separators and reserved punctuation are
+//! replaced, control and invisible characters are dropped, Windows device
+//! names are defused, and the result is truncated to the target file
+//! system's length limit without splitting a character or losing the
+//! extension. Optionally the name is transliterated to ASCII and suffixed
+//! until it doesn't collide with existing names.
+
+use crate::errors::{self, ErrorCode};
+use super::unicode_spoofing::is_invisible_char;
+use super::windows_path::is_reserved_name;
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Characters replaced with '_': path separators and Windows-reserved punctuation
+const DANGEROUS_CHARS: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Name used when nothing survives sanitization
+const FALLBACK_NAME: &str = "unnamed";
+
+/// Longest suffix after the last '.' that is kept as an extension when truncating
+const MAX_EXTENSION_CHARS: usize = 16;
+
+/// Smallest accepted length limit (room for a short name, a suffix and an extension)
+const MIN_LENGTH_LIMIT: u32 = 8;
+
+/// File systems whose name limit counts UTF-16 code units rather than UTF-8 bytes
+const UTF16_FILESYSTEMS: [&str; 6] = ["ntfs", "exfat", "fat32", "vfat", "hfs+", "refs"];
+
+/// File systems whose name limit counts UTF-8 bytes
+const UTF8_FILESYSTEMS: [&str; 6] = ["posix", "ext4", "xfs", "btrfs", "zfs", "apfs"];
+
+/// Options for `sanitize_filename`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SanitizeFilenameOptions {
+    /// Target file system deciding how length is counted: "posix", "ext4",
+    /// "xfs", "btrfs", "zfs" or "apfs" (UTF-8 bytes) or "ntfs", "exfat",
+    /// "fat32", "vfat", "hfs+" or "refs" (UTF-16 code units)
+    pub filesystem: String,
+    /// Maximum name length in the file system's unit (default 255)
+    pub max_length: u32,
+    /// Rename Windows reserved device names such as "CON" or "com1.txt"
+    pub windows_safe: bool,
+    /// Transliterate non-ASCII text to ASCII (e.g. "Crème brûlée" to "Creme brulee")
+    pub transliterate: bool,
+    /// Names the result must not collide with
+    pub existing_names: Option<Vec<String>>,
+    /// Directory whose entries the result must not collide with
+    pub unique_in_directory: Option<String>,
+}
+
+impl Default for SanitizeFilenameOptions {
+    fn default() -> Self {
+        Self {
+            filesystem: "posix".to_string(),
+            max_length: 255,
+            windows_safe: true,
+            transliterate: false,
+            existing_names: None,
+            unique_in_directory: None,
+        }
+    }
+}
+
+/// Sanitize one file name component according to the options
+pub(crate) fn sanitize(filename: &str, options: &SanitizeFilenameOptions) -> napi::Result<String> {
+    let utf16 = match options.filesystem.to_ascii_lowercase().as_str() {
+        fs if UTF16_FILESYSTEMS.contains(&fs) => true,
+        fs if UTF8_FILESYSTEMS.contains(&fs) => false,
+        other => {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!(
+                    "Unknown filesystem: {} (expected one of {}, {})",
+                    other,
+                    UTF8_FILESYSTEMS.join(", "),
+                    UTF16_FILESYSTEMS.join(", ")
+                ),
+            ))
+        }
+    };
+    if options.max_length < MIN_LENGTH_LIMIT {
+        return Err(errors::new(
+            ErrorCode::InvalidArgument,
+            format!("max_length must be at least {}", MIN_LENGTH_LIMIT),
+        ));
+    }
+    let limit = options.max_length as usize;
+    let measure = |s: &str| if utf16 { s.encode_utf16().count() } else { s.len() };
+
+    let mut name: String = filename.nfc().collect();
+    if options.transliterate {
+        name = deunicode::deunicode(&name);
+    }
+    let name: String = name
+        .chars()
+        .filter(|&c| !c.is_control() && !is_invisible_char(c))
+        .map(|c| if DANGEROUS_CHARS.contains(&c) { '_' } else { c })
+        .collect();
+    let name = trim_name(&name);
+    let name = if name.is_empty() { FALLBACK_NAME } else { name };
+
+    let (stem, extension) = split_extension(name);
+    let mut stem = stem.to_string();
+    if options.windows_safe && is_reserved_name(&stem) {
+        // "CON.tar" becomes "CON_.tar": the device part must not stand alone
+        let device = stem.split(['.', ':']).next().unwrap_or("").trim_end().len();
+        stem.insert(device, '_');
+    }
+
+    let mut taken: HashSet<String> = HashSet::new();
+    let fold = |s: &str| if options.windows_safe { s.to_lowercase() } else { s.to_string() };
+    for existing in options.existing_names.iter().flatten() {
+        taken.insert(fold(existing));
+    }
+    if let Some(directory) = &options.unique_in_directory {
+        let entries = fs::read_dir(directory).map_err(|e| {
+            errors::io(
+                &e,
+                format!("Failed to read directory {}: {}", directory, e),
+            )
+        })?;
+        for entry in entries.flatten() {
+            taken.insert(fold(&entry.file_name().to_string_lossy()));
+        }
+    }
+
+    let mut counter = 0u32;
+    loop {
+        let suffix = if counter == 0 { String::new() } else { format!("-{}", counter) };
+        let candidate = fit(&stem, extension, &suffix, limit, options.windows_safe, &measure);
+        if !taken.contains(&fold(&candidate)) {
+            return Ok(candidate);
+        }
+        counter += 1;
+    }
+}
+
+/// Strip the leading and trailing dots and spaces Windows drops or hides
+fn trim_name(name: &str) -> &str {
+    name.trim_matches(|c: char| c == '.' || c.is_whitespace())
+}
+
+/// Split off a short extension after the last '.', keeping the dot
+fn split_extension(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(dot) if dot > 0 => {
+            let extension = &name[dot..];
+            let chars = extension.chars().count() - 1;
+            if (1..=MAX_EXTENSION_CHARS).contains(&chars) && !extension.contains(char::is_whitespace) {
+                (&name[..dot], extension)
+            } else {
+                (name, "")
+            }
+        }
+        _ => (name, ""),
+    }
+}
+
+/// Join stem, suffix and extension, dropping trailing graphemes of the stem until it fits
+fn fit(
+    stem: &str,
+    extension: &str,
+    suffix: &str,
+    limit: usize,
+    windows_safe: bool,
+    measure: &dyn Fn(&str) -> usize,
+) -> String {
+    // An extension that leaves no room for the stem is truncated with it
+    let (stem, extension) = if measure(extension) + measure(suffix) >= limit {
+        (format!("{}{}", stem, extension), "")
+    } else {
+        (stem.to_string(), extension)
+    };
+
+    let budget = limit.saturating_sub(measure(suffix) + measure(extension));
+    let mut kept = String::new();
+    let mut used = 0;
+    for grapheme in stem.graphemes(true) {
+        used += measure(grapheme);
+        if used > budget {
+            break;
+        }
+        kept.push_str(grapheme);
+    }
+
+    let mut kept = trim_name(&kept).to_string();
+    if extension.is_empty() {
+        // Truncation can expose a new extension ("a..bc" cut to "a..b"), so
+        // split again and trim the stem the way a second pass would
+        let (kept_stem, kept_extension) = split_extension(&kept);
+        kept = format!("{}{}", trim_name(kept_stem), kept_extension);
+    }
+    if kept.is_empty() {
+        kept = FALLBACK_NAME[..budget.min(FALLBACK_NAME.len())].to_string();
+    }
+    // Truncation can also cut a defused name back to a device name ("CON_x" to "CON")
+    if windows_safe && is_reserved_name(&kept) {
+        let device = kept.split(['.', ':']).next().unwrap_or("").trim_end();
+        if let Some((last, ch)) = device.char_indices().next_back() {
+            kept.replace_range(last..last + ch.len_utf8(), "_");
+        }
+    }
+    format!("{}{}{}", kept, suffix, extension)
+}
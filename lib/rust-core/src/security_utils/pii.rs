@@ -0,0 +1,422 @@
+//! Personally identifiable information detection
+//!
+//! Detectors for email addresses, phone numbers, IBANs, US Social Security
+//! numbers and payment card numbers. Structured identifiers are checked
+//! (IBAN mod-97, card Luhn and issuer prefixes, SSN reserved ranges) so each
+//! finding carries a confidence score rather than a bare regex hit.
+
+use super::read_text_file;
+use crate::concurrency;
+use crate::errors::{self, ErrorCode};
+use crate::file_search::{FileSearch, FileSearchConfig};
+use crate::kernels::LineIndex;
+use napi_derive::napi;
+use rayon::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// All supported detector names
+const DETECTORS: [&str; 5] = ["email", "phone", "iban", "ssn", "credit_card"];
+
+/// Domains reserved for documentation and tests
+const EXAMPLE_DOMAINS: [&str; 6] = ["example.com", "example.org", "example.net", "test.com", "localhost", "invalid"];
+
+/// Configuration for PII detection
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PiiConfig {
+    /// Detectors to run: "email", "phone", "iban", "ssn", "credit_card"
+    pub detectors: Vec<String>,
+    /// Minimum confidence (0-1) for a finding to be reported
+    pub min_confidence: f64,
+    /// Maximum number of findings to return (0 for unlimited)
+    pub max_findings: u32,
+    /// Traversal and exclusion settings for directory scans
+    pub search: Option<FileSearchConfig>,
+}
+
+impl Default for PiiConfig {
+    fn default() -> Self {
+        Self {
+            detectors: DETECTORS.iter().map(|d| d.to_string()).collect(),
+            min_confidence: 0.5,
+            max_findings: 0,
+            search: None,
+        }
+    }
+}
+
+/// PII found in scanned content
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PiiFinding {
+    /// File path (or the label passed for text scans)
+    pub path: String,
+    /// Detector that produced the finding
+    pub kind: String,
+    /// Start byte offset in the content
+    pub start: u32,
+    /// End byte offset in the content
+    pub end: u32,
+    /// Line number (1-based)
+    pub line_number: u32,
+    /// Start byte column within the line
+    pub column_start: u32,
+    /// Matched value with identifying parts masked
+    pub redacted_match: String,
+    /// Confidence that the match is real PII (0-1)
+    pub confidence: f64,
+}
+
+/// Candidate match before overlap resolution
+struct Candidate {
+    kind: &'static str,
+    start: usize,
+    end: usize,
+    confidence: f64,
+}
+
+/// PII detector
+#[napi]
+pub struct PiiDetector {
+    config: PiiConfig,
+    search: FileSearch,
+    email: Regex,
+    phone: Regex,
+    iban: Regex,
+    ssn: Regex,
+    card: Regex,
+}
+
+#[napi]
+impl PiiDetector {
+    /// Create a new PII detector with optional configuration
+    ///
+    /// # Arguments
+    /// * `config` - Optional configuration for PII detection
+    #[napi(constructor)]
+    pub fn new(config: Option<PiiConfig>) -> napi::Result<Self> {
+        errors::guard("new PiiDetector", || {
+            let config = config.unwrap_or_default();
+            if let Some(unknown) = config.detectors.iter().find(|d| !DETECTORS.contains(&d.as_str())) {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Unknown PII detector: {}", unknown),
+                ));
+            }
+
+            let compile = |pattern: &str| {
+                Regex::new(pattern).map_err(|e| {
+                    errors::new(ErrorCode::PatternInvalid, format!("Invalid PII pattern: {}", e))
+                })
+            };
+
+            Ok(Self {
+                search: FileSearch::new(config.search.clone())?,
+                email: compile(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}")?,
+                phone: compile(
+                    r"\+[1-9]\d{0,3}[\s.-]?(?:\d[\s.-]?){6,13}\d|(?:\+?1[\s.-]?)?(?:\(\d{3}\)\s?|\d{3}[\s.-])\d{3}[\s.-]\d{4}",
+                )?,
+                iban: compile(r"[A-Z]{2}\d{2}(?: ?[A-Z0-9]{4}){2,7}(?: ?[A-Z0-9]{1,3})?")?,
+                ssn: compile(r"\d{3}-\d{2}-\d{4}")?,
+                card: compile(r"\d(?:[ -]?\d){12,18}")?,
+                config,
+            })
+        })
+    }
+
+    /// Detect PII in text
+    ///
+    /// # Arguments
+    /// * `text` - Content to scan
+    /// * `label` - Optional name reported as the finding path
+    #[napi]
+    pub fn scan_text(&self, text: String, label: Option<String>) -> napi::Result<Vec<PiiFinding>> {
+        errors::catch_panic("PiiDetector.scanText", || {
+            let mut findings = self.scan_content(&text, &label.unwrap_or_default());
+            self.truncate(&mut findings);
+            findings
+        })
+    }
+
+    /// Detect PII in a single file (binary files are skipped)
+    #[napi]
+    pub fn scan_file(&self, path: String) -> napi::Result<Vec<PiiFinding>> {
+        errors::guard("PiiDetector.scanFile", || {
+            let mut findings = self.scan_path(Path::new(&path)).map_err(|e| {
+                errors::io(&e, format!("Failed to read {}: {}", path, e))
+            })?;
+            self.truncate(&mut findings);
+            Ok(findings)
+        })
+    }
+
+    /// Detect PII across a directory tree in parallel
+    #[napi]
+    pub fn scan_directory(&self, root_path: String) -> napi::Result<Vec<PiiFinding>> {
+        errors::guard("PiiDetector.scanDirectory", || {
+            let _permit = concurrency::acquire("PiiDetector.scanDirectory")?;
+            let files = self.search.collect_files(Path::new(&root_path))?;
+            let use_parallel = self.config.search.as_ref().is_none_or(|search| search.use_parallel);
+
+            let mut findings: Vec<PiiFinding> = if use_parallel && files.len() > 100 {
+                files
+                    .par_iter()
+                    .flat_map(|path| self.scan_path(path).unwrap_or_default())
+                    .collect()
+            } else {
+                files
+                    .iter()
+                    .flat_map(|path| self.scan_path(path).unwrap_or_default())
+                    .collect()
+            };
+
+            findings.sort_by(|a, b| a.path.cmp(&b.path).then(a.start.cmp(&b.start)));
+            self.truncate(&mut findings);
+            Ok(findings)
+        })
+    }
+
+    /// Apply the `max_findings` limit
+    fn truncate(&self, findings: &mut Vec<PiiFinding>) {
+        if self.config.max_findings > 0 {
+            findings.truncate(self.config.max_findings as usize);
+        }
+    }
+
+    /// Whether a detector is enabled
+    fn enabled(&self, kind: &str) -> bool {
+        self.config.detectors.iter().any(|d| d == kind)
+    }
+
+    /// Read and scan one file, skipping binary content
+    fn scan_path(&self, path: &Path) -> std::io::Result<Vec<PiiFinding>> {
+        Ok(match read_text_file(path)? {
+            Some(text) => self.scan_content(&text, &path.to_string_lossy()),
+            None => Vec::new(),
+        })
+    }
+
+    /// Run enabled detectors and resolve overlapping candidates
+    fn scan_content(&self, text: &str, path: &str) -> Vec<PiiFinding> {
+        let mut candidates = Vec::new();
+
+        if self.enabled("email") {
+            for m in self.email.find_iter(text) {
+                let domain = m.as_str().rsplit('@').next().unwrap_or("").to_ascii_lowercase();
+                let example = EXAMPLE_DOMAINS
+                    .iter()
+                    .any(|d| domain == *d || domain.ends_with(&format!(".{}", d)));
+                candidates.push(Candidate {
+                    kind: "email",
+                    start: m.start(),
+                    end: m.end(),
+                    confidence: if example { 0.5 } else { 0.95 },
+                });
+            }
+        }
+
+        if self.enabled("phone") {
+            for m in self.phone.find_iter(text).filter(|m| isolated(text, m.start(), m.end())) {
+                let digits = m.as_str().bytes().filter(u8::is_ascii_digit).count();
+                if !(10..=15).contains(&digits) {
+                    continue;
+                }
+                candidates.push(Candidate {
+                    kind: "phone",
+                    start: m.start(),
+                    end: m.end(),
+                    confidence: if m.as_str().starts_with('+') { 0.75 } else { 0.7 },
+                });
+            }
+        }
+
+        if self.enabled("iban") {
+            for m in self.iban.find_iter(text).filter(|m| isolated(text, m.start(), m.end())) {
+                if valid_iban(m.as_str()) {
+                    candidates.push(Candidate {
+                        kind: "iban",
+                        start: m.start(),
+                        end: m.end(),
+                        confidence: 0.95,
+                    });
+                }
+            }
+        }
+
+        if self.enabled("ssn") {
+            for m in self.ssn.find_iter(text).filter(|m| isolated(text, m.start(), m.end())) {
+                if valid_ssn(m.as_str()) {
+                    candidates.push(Candidate {
+                        kind: "ssn",
+                        start: m.start(),
+                        end: m.end(),
+                        confidence: 0.85,
+                    });
+                }
+            }
+        }
+
+        if self.enabled("credit_card") {
+            for m in self.card.find_iter(text).filter(|m| isolated(text, m.start(), m.end())) {
+                let digits: Vec<u8> = m.as_str().bytes().filter(u8::is_ascii_digit).map(|b| b - b'0').collect();
+                if digits.windows(2).all(|w| w[0] == w[1]) || !luhn_valid(&digits) {
+                    continue;
+                }
+                candidates.push(Candidate {
+                    kind: "credit_card",
+                    start: m.start(),
+                    end: m.end(),
+                    confidence: if known_card_prefix(&digits) { 0.95 } else { 0.5 },
+                });
+            }
+        }
+
+        // Keep the most confident candidate wherever detectors overlap
+        candidates.retain(|c| c.confidence >= self.config.min_confidence);
+        candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence).then(a.start.cmp(&b.start)));
+        let mut kept: Vec<Candidate> = Vec::new();
+        for candidate in candidates {
+            if !kept.iter().any(|k| candidate.start < k.end && k.start < candidate.end) {
+                kept.push(candidate);
+            }
+        }
+        kept.sort_by_key(|c| c.start);
+
+        let lines = LineIndex::new(text);
+        kept.into_iter()
+            .map(|c| {
+                let (line_number, column_start) = lines.position(c.start);
+                PiiFinding {
+                    path: path.to_string(),
+                    kind: c.kind.to_string(),
+                    start: c.start as u32,
+                    end: c.end as u32,
+                    line_number,
+                    column_start,
+                    redacted_match: redact(c.kind, &text[c.start..c.end]),
+                    confidence: c.confidence,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Whether a match is not embedded in a longer alphanumeric run
+fn isolated(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().next_back();
+    let after = text[end..].chars().next();
+    !before.is_some_and(|c| c.is_alphanumeric() || c == '+')
+        && !after.is_some_and(|c| c.is_alphanumeric())
+}
+
+/// Luhn checksum over card digits
+fn luhn_valid(digits: &[u8]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            let d = d as u32;
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+/// Whether card digits start with a known issuer prefix of a valid length
+fn known_card_prefix(digits: &[u8]) -> bool {
+    let prefix = |n: usize| digits.iter().take(n).fold(0u32, |acc, &d| acc * 10 + d as u32);
+    let length = digits.len();
+
+    match digits.first() {
+        Some(4) => matches!(length, 13 | 16 | 19),
+        Some(5) => (51..=55).contains(&prefix(2)) && length == 16,
+        Some(2) => (2221..=2720).contains(&prefix(4)) && length == 16,
+        Some(3) => {
+            (matches!(prefix(2), 34 | 37) && length == 15)
+                || (prefix(2) == 35 && (16..=19).contains(&length))
+                || (matches!(prefix(2), 30 | 36 | 38) && (14..=19).contains(&length))
+        }
+        Some(6) => {
+            (prefix(4) == 6011 || prefix(2) == 65 || (644..=649).contains(&prefix(3))) && (16..=19).contains(&length)
+        }
+        _ => false,
+    }
+}
+
+/// Validate an IBAN with the ISO 13616 mod-97 check
+fn valid_iban(candidate: &str) -> bool {
+    let compact: Vec<u8> = candidate.bytes().filter(|b| *b != b' ').collect();
+    if !(15..=34).contains(&compact.len()) {
+        return false;
+    }
+
+    // Move the country code and check digits to the end, letters become 10..35
+    let mut remainder = 0u32;
+    for &b in compact[4..].iter().chain(&compact[..4]) {
+        let value = match b {
+            b'0'..=b'9' => (b - b'0') as u32,
+            b'A'..=b'Z' => (b - b'A') as u32 + 10,
+            _ => return false,
+        };
+        remainder = if value >= 10 {
+            (remainder * 100 + value) % 97
+        } else {
+            (remainder * 10 + value) % 97
+        };
+    }
+    remainder == 1
+}
+
+/// Reject SSNs in ranges the SSA never issues
+fn valid_ssn(candidate: &str) -> bool {
+    let mut parts = candidate.split('-');
+    let (Some(area), Some(group), Some(serial)) = (parts.next(), parts.next(), parts.next()) else {
+        return false;
+    };
+    area != "000" && area != "666" && !area.starts_with('9') && group != "00" && serial != "0000"
+}
+
+/// Mask a match, keeping the domain of emails and the last four characters otherwise
+fn redact(kind: &str, value: &str) -> String {
+    if kind == "email" {
+        if let Some((local, domain)) = value.split_once('@') {
+            let first: String = local.chars().take(1).collect();
+            return format!("{}***@{}", first, domain);
+        }
+    }
+
+    let visible = value.chars().filter(|c| c.is_ascii_alphanumeric()).count().saturating_sub(4);
+    let mut hidden = 0;
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() && hidden < visible {
+                hidden += 1;
+                '*'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Quick PII detection over text with all detectors enabled
+#[napi]
+pub fn quick_detect_pii(text: String) -> napi::Result<Vec<PiiFinding>> {
+    errors::guard("quickDetectPii", || {
+        let detector = PiiDetector::new(None)?;
+        detector.scan_text(text, None)
+    })
+}
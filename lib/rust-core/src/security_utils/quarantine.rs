@@ -0,0 +1,432 @@
+//! Quarantine for files flagged by the scanners
+//!
+//! Flagged files are moved into a private quarantine directory (owner-only
+//! on Unix, with read-only payloads) next to a JSON metadata record holding
+//! the original location, reason, SHA-256 digest and permissions. Records
+//! are signed with an Ed25519 key, so a restore refuses metadata or payloads
+//! that were altered while in quarantine, e.g. to redirect the restore to
+//! another path.
+
+use super::quota::{self, Mutation};
+use super::signatures::parse_signing_key;
+use crate::atomic_write::{self, AtomicWriteOptions};
+use crate::audit::{self, rfc3339, AuditKind};
+use crate::errors::{self, ErrorCode};
+use crate::hashing::{from_hex, hash_file_with, to_hex, HashAlgorithm};
+use ed25519_dalek::{Signature, Signer, SigningKey};
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Extension of quarantined file contents
+const PAYLOAD_EXTENSION: &str = "quarantined";
+
+/// Extension of metadata records
+const RECORD_EXTENSION: &str = "json";
+
+/// Quarantine settings
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantineConfig {
+    /// Directory holding quarantined files (created if missing)
+    pub directory: String,
+    /// Hex-encoded 32-byte Ed25519 secret seed used to sign records
+    pub signing_key: String,
+}
+
+/// Signed metadata for a quarantined file
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantineRecord {
+    /// Quarantine id
+    pub id: String,
+    /// Absolute path the file was taken from
+    pub original_path: String,
+    /// Why the file was quarantined (e.g. the scanner finding)
+    pub reason: String,
+    /// RFC 3339 UTC time of quarantine
+    pub quarantined_at: String,
+    /// File size in bytes
+    pub size: f64,
+    /// Hex SHA-256 digest of the contents
+    pub sha256: String,
+    /// Unix permission bits of the original file
+    pub mode: Option<u32>,
+    /// Hex public key of the signer
+    pub public_key: String,
+    /// Hex Ed25519 signature over the other fields
+    pub signature: String,
+}
+
+/// A record as listed, with its verification status
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantineEntry {
+    /// Stored metadata
+    pub record: QuarantineRecord,
+    /// Whether the record is signed by this quarantine's key
+    pub signature_valid: bool,
+    /// Whether the stored contents still match the recorded digest
+    pub content_intact: bool,
+}
+
+/// Fields covered by the signature, in a fixed order
+#[derive(Serialize)]
+struct SignedFields<'a> {
+    id: &'a str,
+    original_path: &'a str,
+    reason: &'a str,
+    quarantined_at: &'a str,
+    size: f64,
+    sha256: &'a str,
+    mode: Option<u32>,
+    public_key: &'a str,
+}
+
+impl<'a> From<&'a QuarantineRecord> for SignedFields<'a> {
+    fn from(record: &'a QuarantineRecord) -> Self {
+        Self {
+            id: &record.id,
+            original_path: &record.original_path,
+            reason: &record.reason,
+            quarantined_at: &record.quarantined_at,
+            size: record.size,
+            sha256: &record.sha256,
+            mode: record.mode,
+            public_key: &record.public_key,
+        }
+    }
+}
+
+/// Quarantine directory with signed records
+#[napi]
+pub struct Quarantine {
+    directory: PathBuf,
+    signing_key: SigningKey,
+}
+
+#[napi]
+impl Quarantine {
+    /// Open (and create, if needed) a quarantine directory
+    ///
+    /// # Arguments
+    /// * `config` - Directory and signing key
+    #[napi(constructor)]
+    pub fn new(config: QuarantineConfig) -> napi::Result<Self> {
+        errors::guard("new Quarantine", || {
+            let signing_key = parse_signing_key(&config.signing_key)?;
+            let directory = PathBuf::from(&config.directory);
+            fs::create_dir_all(&directory)
+                .and_then(|_| restrict_directory(&directory))
+                .and_then(|_| directory.canonicalize())
+                .map(|directory| Self { directory, signing_key })
+                .map_err(|e| io_error("open quarantine", &config.directory, e))
+        })
+    }
+
+    /// Hex public key that verifies this quarantine's records
+    #[napi(getter)]
+    pub fn public_key(&self) -> String {
+        to_hex(self.signing_key.verifying_key().as_bytes())
+    }
+
+    /// Move a file into quarantine
+    ///
+    /// # Arguments
+    /// * `path` - Regular file to quarantine (symlinks are rejected)
+    /// * `reason` - Why the file is being quarantined
+    #[napi]
+    pub fn quarantine_file(&self, path: String, reason: String) -> napi::Result<QuarantineRecord> {
+        errors::guard("Quarantine.quarantineFile", || {
+            let result = self.isolate(&path, reason);
+            match &result {
+                Ok(record) => audit::record(
+                    AuditKind::FileMutation,
+                    "quarantine_file",
+                    &path,
+                    "success",
+                    Some(&record.id),
+                ),
+                Err(e) => audit::record(AuditKind::FileMutation, "quarantine_file", &path, "failure", Some(&e.reason)),
+            }
+            result
+        })
+    }
+
+    /// Records in quarantine, oldest first
+    ///
+    /// Metadata files that can't be parsed are skipped.
+    #[napi]
+    pub fn list(&self) -> napi::Result<Vec<QuarantineEntry>> {
+        errors::guard("Quarantine.list", || {
+            let entries = fs::read_dir(&self.directory)
+                .map_err(|e| io_error("list quarantine", &self.directory.to_string_lossy(), e))?;
+
+            let mut listed: Vec<QuarantineEntry> = entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == RECORD_EXTENSION))
+                .filter_map(|path| fs::read_to_string(path).ok())
+                .filter_map(|content| serde_json::from_str::<QuarantineRecord>(&content).ok())
+                .map(|record| QuarantineEntry {
+                    signature_valid: self.verify(&record),
+                    content_intact: self.content_intact(&record),
+                    record,
+                })
+                .collect();
+            listed.sort_by(|a, b| a.record.id.cmp(&b.record.id));
+            Ok(listed)
+        })
+    }
+
+    /// Move a quarantined file back, after checking its record and contents
+    ///
+    /// Returns the restored path.
+    ///
+    /// # Arguments
+    /// * `id` - Quarantine id
+    /// * `destination` - Where to restore to (defaults to the original path; must not exist)
+    #[napi]
+    pub fn restore(&self, id: String, destination: Option<String>) -> napi::Result<String> {
+        errors::guard("Quarantine.restore", || {
+            let result = self.release(&id, destination);
+            match &result {
+                Ok(path) => audit::record(AuditKind::FileMutation, "restore_quarantined", path, "success", Some(&id)),
+                Err(e) => audit::record(AuditKind::FileMutation, "restore_quarantined", &id, "failure", Some(&e.reason)),
+            }
+            result
+        })
+    }
+
+    fn isolate(&self, path: &str, reason: String) -> napi::Result<QuarantineRecord> {
+        let source = Path::new(path);
+        let metadata = fs::symlink_metadata(source).map_err(|e| io_error("quarantine", path, e))?;
+        if metadata.file_type().is_symlink() || !metadata.is_file() {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Only regular files can be quarantined: {}", path),
+            ));
+        }
+        let original = source.canonicalize().map_err(|e| io_error("quarantine", path, e))?;
+        if original.starts_with(&self.directory) {
+            return Err(errors::new(
+                ErrorCode::AlreadyExists,
+                format!("File is already in quarantine: {}", path),
+            ));
+        }
+
+        let sha256 = hash_file_with(&original, HashAlgorithm::Sha256).map_err(|e| io_error("quarantine", path, e))?;
+        let id = new_id()?;
+        let mut record = QuarantineRecord {
+            id,
+            original_path: original.to_string_lossy().to_string(),
+            reason,
+            quarantined_at: rfc3339(SystemTime::now()),
+            size: metadata.len() as f64,
+            sha256: to_hex(&sha256),
+            mode: permission_bits(&metadata),
+            public_key: self.public_key(),
+            signature: String::new(),
+        };
+        record.signature = to_hex(&self.signing_key.sign(&signed_bytes(&record)?).to_bytes());
+
+        quota::charge("quarantine_file", Mutation::Delete, &original, 0)?;
+        let payload = self.payload_path(&record.id);
+        move_file(&original, &payload).map_err(|e| io_error("quarantine", path, e))?;
+
+        let stored = make_read_only(&payload).and_then(|_| {
+            let json = serde_json::to_string_pretty(&record).map_err(std::io::Error::other)?;
+            let record_path = self.record_path(&record.id);
+            atomic_write::write(&record_path, json.as_bytes(), &AtomicWriteOptions::default())?;
+            make_read_only(&record_path)
+        });
+        if let Err(e) = stored {
+            // Put the file back rather than leave it without a record
+            let _ = move_file(&payload, &original);
+            return Err(io_error("quarantine", path, e));
+        }
+        Ok(record)
+    }
+
+    fn release(&self, id: &str, destination: Option<String>) -> napi::Result<String> {
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Invalid quarantine id: {}", id),
+            ));
+        }
+        let record_path = self.record_path(id);
+        let record: QuarantineRecord = fs::read_to_string(&record_path)
+            .map_err(|e| io_error("read quarantine record", id, e))
+            .and_then(|content| {
+                serde_json::from_str(&content).map_err(|e| {
+                    errors::new(
+                        ErrorCode::ParseError,
+                        format!("Invalid quarantine record {}: {}", id, e),
+                    )
+                })
+            })?;
+
+        if record.id != id || !self.verify(&record) {
+            return Err(errors::new(
+                ErrorCode::IntegrityError,
+                format!("Quarantine record {} is not signed by this quarantine's key", id),
+            ));
+        }
+        if !self.content_intact(&record) {
+            return Err(errors::new(
+                ErrorCode::IntegrityError,
+                format!("Quarantined contents of {} no longer match the recorded digest", id),
+            ));
+        }
+
+        let target = PathBuf::from(destination.unwrap_or_else(|| record.original_path.clone()));
+        let target_str = target.to_string_lossy().to_string();
+        if fs::symlink_metadata(&target).is_ok() {
+            return Err(errors::new(
+                ErrorCode::AlreadyExists,
+                format!("Refusing to overwrite existing file: {}", target_str),
+            ));
+        }
+        quota::charge("restore_quarantined", Mutation::Write, &target, record.size as u64)?;
+
+        let payload = self.payload_path(id);
+        target
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| move_file(&payload, &target))
+            .and_then(|_| restore_permissions(&target, record.mode))
+            .map_err(|e| io_error("restore", &target_str, e))?;
+
+        let _ = make_writable(&record_path).and_then(|_| fs::remove_file(&record_path));
+        Ok(target_str)
+    }
+
+    /// Whether the record's signature verifies with this quarantine's key
+    fn verify(&self, record: &QuarantineRecord) -> bool {
+        let Some(signature) = from_hex(&record.signature).and_then(|bytes| <[u8; 64]>::try_from(bytes).ok()) else {
+            return false;
+        };
+        signed_bytes(record).is_ok_and(|message| {
+            self.signing_key
+                .verifying_key()
+                .verify_strict(&message, &Signature::from_bytes(&signature))
+                .is_ok()
+        })
+    }
+
+    fn content_intact(&self, record: &QuarantineRecord) -> bool {
+        hash_file_with(&self.payload_path(&record.id), HashAlgorithm::Sha256)
+            .is_ok_and(|digest| to_hex(&digest) == record.sha256)
+    }
+
+    fn payload_path(&self, id: &str) -> PathBuf {
+        self.directory.join(format!("{}.{}", id, PAYLOAD_EXTENSION))
+    }
+
+    fn record_path(&self, id: &str) -> PathBuf {
+        self.directory.join(format!("{}.{}", id, RECORD_EXTENSION))
+    }
+}
+
+/// Canonical bytes the signature covers
+fn signed_bytes(record: &QuarantineRecord) -> napi::Result<Vec<u8>> {
+    serde_json::to_vec(&SignedFields::from(record)).map_err(|e| {
+        errors::new(
+            ErrorCode::Internal,
+            format!("Failed to serialize quarantine record: {}", e),
+        )
+    })
+}
+
+/// Time-ordered unique id: milliseconds since the epoch and 8 random bytes, in hex
+pub(crate) fn new_id() -> napi::Result<String> {
+    let millis = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis()).unwrap_or(0);
+    let mut bytes = [0u8; 8];
+    getrandom::getrandom(&mut bytes).map_err(|e| {
+        errors::new(ErrorCode::Internal, format!("Failed to gather randomness: {}", e))
+    })?;
+    Ok(format!("{:012x}-{}", millis, to_hex(&bytes)))
+}
+
+/// Rename, falling back to copy and delete across file systems
+pub(crate) fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    match fs::rename(from, to) {
+        Err(e) if e.kind() == ErrorKind::CrossesDevices => {
+            fs::copy(from, to)?;
+            fs::File::open(to)?.sync_all()?;
+            fs::remove_file(from)
+        }
+        result => result,
+    }
+}
+
+#[cfg(unix)]
+fn permission_bits(metadata: &fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode() & 0o777)
+}
+
+#[cfg(not(unix))]
+fn permission_bits(_metadata: &fs::Metadata) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+fn restrict_directory(directory: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(directory, fs::Permissions::from_mode(0o700))
+}
+
+#[cfg(not(unix))]
+fn restrict_directory(_directory: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(unix)]
+fn make_read_only(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o400))
+}
+
+#[cfg(not(unix))]
+fn make_read_only(path: &Path) -> std::io::Result<()> {
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_readonly(true);
+    fs::set_permissions(path, permissions)
+}
+
+#[cfg(unix)]
+fn make_writable(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+}
+
+#[cfg(not(unix))]
+#[allow(clippy::permissions_set_readonly_false)]
+fn make_writable(path: &Path) -> std::io::Result<()> {
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_readonly(false);
+    fs::set_permissions(path, permissions)
+}
+
+/// Reapply the original permission bits, or make the file writable again
+#[cfg(unix)]
+fn restore_permissions(path: &Path, mode: Option<u32>) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode.unwrap_or(0o644)))
+}
+
+#[cfg(not(unix))]
+fn restore_permissions(path: &Path, _mode: Option<u32>) -> std::io::Result<()> {
+    make_writable(path)
+}
+
+fn io_error(action: &str, path: &str, e: std::io::Error) -> napi::Error {
+    errors::io(
+        &e,
+        format!("Failed to {} {}: {}", action, path, e),
+    )
+}
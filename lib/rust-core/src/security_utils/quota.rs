@@ -0,0 +1,332 @@
+//! Quotas on native file mutations
+//!
+//! A process-wide `QuotaPolicy` caps how many files an automated run may
+//! write or delete and how many bytes it may write, overall and per path
+//! prefix. Native operations that modify the file system charge the active
+//! quota before touching disk and fail with a quota-exceeded error once a
+//! budget would be overrun. JavaScript callers performing their own writes
+//! can charge the same quota through `consume_file_quota`.
+
+use super::{lexical_normalize, resolve_existing};
+use crate::audit::{self, AuditKind};
+use crate::errors::{self, ErrorCode};
+use napi_derive::napi;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+static ACTIVE: Mutex<Option<QuotaState>> = Mutex::new(None);
+
+/// Budget for paths below one prefix
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefixBudget {
+    /// Directory the budget applies to
+    pub prefix: String,
+    /// Maximum files written below the prefix (0 for no limit)
+    pub max_files_written: u32,
+    /// Maximum files deleted below the prefix (0 for no limit)
+    pub max_files_deleted: u32,
+    /// Maximum bytes written below the prefix (0 for no limit)
+    pub max_bytes_written: f64,
+}
+
+/// Limits on file mutations for one run
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaPolicy {
+    /// Maximum files written (0 for no limit)
+    pub max_files_written: u32,
+    /// Maximum files deleted (0 for no limit)
+    pub max_files_deleted: u32,
+    /// Maximum bytes written (0 for no limit)
+    pub max_bytes_written: f64,
+    /// Additional budgets for paths below specific directories
+    pub prefix_budgets: Vec<PrefixBudget>,
+}
+
+impl Default for QuotaPolicy {
+    fn default() -> Self {
+        Self {
+            max_files_written: 0,
+            max_files_deleted: 0,
+            max_bytes_written: 0.0,
+            prefix_budgets: Vec::new(),
+        }
+    }
+}
+
+/// Mutations counted so far under one budget
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QuotaUsage {
+    /// Prefix of the budget (absent for the overall budget)
+    pub prefix: Option<String>,
+    /// Files written
+    pub files_written: u32,
+    /// Files deleted
+    pub files_deleted: u32,
+    /// Bytes written
+    pub bytes_written: f64,
+}
+
+/// Outcome of charging the quota
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaVerdict {
+    /// Whether the operation fits in every applicable budget
+    pub allowed: bool,
+    /// Limit that would be exceeded: "max_files_written", "max_files_deleted" or "max_bytes_written"
+    pub limit: Option<String>,
+    /// Prefix of the exceeded budget (absent for the overall budget)
+    pub prefix: Option<String>,
+    /// Amount already used under the exceeded limit
+    pub used: f64,
+    /// Amount the operation asked for
+    pub requested: f64,
+    /// Value of the exceeded limit
+    pub maximum: f64,
+}
+
+/// Kind of mutation being charged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Mutation {
+    Write,
+    Delete,
+}
+
+/// One budget with its running usage
+struct Budget {
+    root: Option<PathBuf>,
+    max_files_written: u32,
+    max_files_deleted: u32,
+    max_bytes_written: f64,
+    usage: QuotaUsage,
+}
+
+impl Budget {
+    /// First limit the mutation would exceed, if any
+    fn exceeded(&self, mutation: Mutation, bytes: f64) -> Option<QuotaVerdict> {
+        let over = |limit: &str, used: f64, requested: f64, maximum: f64| {
+            (maximum > 0.0 && used + requested > maximum).then(|| QuotaVerdict {
+                allowed: false,
+                limit: Some(limit.to_string()),
+                prefix: self.usage.prefix.clone(),
+                used,
+                requested,
+                maximum,
+            })
+        };
+        match mutation {
+            Mutation::Write => over(
+                "max_files_written",
+                self.usage.files_written as f64,
+                1.0,
+                self.max_files_written as f64,
+            )
+            .or_else(|| over("max_bytes_written", self.usage.bytes_written, bytes, self.max_bytes_written)),
+            Mutation::Delete => over(
+                "max_files_deleted",
+                self.usage.files_deleted as f64,
+                1.0,
+                self.max_files_deleted as f64,
+            ),
+        }
+    }
+
+    fn charge(&mut self, mutation: Mutation, bytes: f64) {
+        match mutation {
+            Mutation::Write => {
+                self.usage.files_written += 1;
+                self.usage.bytes_written += bytes;
+            }
+            Mutation::Delete => self.usage.files_deleted += 1,
+        }
+    }
+}
+
+struct QuotaState {
+    budgets: Vec<Budget>,
+}
+
+impl QuotaState {
+    /// Charge several mutations together, or none of them
+    ///
+    /// Returns the index of the first mutation that does not fit, with its
+    /// verdict. Mutations charge separate counters, so each is checked
+    /// against the usage before the call.
+    fn consume_all(&mut self, mutations: &[(Mutation, &Path, f64)]) -> Option<(usize, QuotaVerdict)> {
+        let targets: Vec<PathBuf> = mutations.iter().map(|(_, path, _)| absolute_path(path)).collect();
+        let applicable =
+            |budget: &Budget, target: &Path| budget.root.as_ref().is_none_or(|root| target.starts_with(root));
+
+        for (i, ((mutation, _, bytes), target)) in mutations.iter().zip(&targets).enumerate() {
+            if let Some(verdict) = self
+                .budgets
+                .iter()
+                .filter(|b| applicable(b, target))
+                .find_map(|b| b.exceeded(*mutation, *bytes))
+            {
+                return Some((i, verdict));
+            }
+        }
+        for ((mutation, _, bytes), target) in mutations.iter().zip(&targets) {
+            for budget in self.budgets.iter_mut().filter(|b| applicable(b, target)) {
+                budget.charge(*mutation, *bytes);
+            }
+        }
+        None
+    }
+
+    /// Check every applicable budget and charge them all only if each has room
+    fn consume(&mut self, mutation: Mutation, path: &Path, bytes: f64) -> QuotaVerdict {
+        if let Some((_, verdict)) = self.consume_all(&[(mutation, path, bytes)]) {
+            return verdict;
+        }
+        QuotaVerdict {
+            allowed: true,
+            limit: None,
+            prefix: None,
+            used: 0.0,
+            requested: bytes,
+            maximum: 0.0,
+        }
+    }
+}
+
+/// Charge the active quota for a mutation native code is about to perform
+///
+/// Succeeds without counting anything when no quota is set.
+pub(crate) fn charge(operation: &str, mutation: Mutation, path: &Path, bytes: u64) -> napi::Result<()> {
+    charge_all(operation, &[(mutation, path, bytes)])
+}
+
+/// Charge several mutations of one change together
+///
+/// Nothing is counted unless every mutation fits (e.g. both halves of a
+/// move), and nothing is counted when no quota is set.
+pub(crate) fn charge_all(operation: &str, mutations: &[(Mutation, &Path, u64)]) -> napi::Result<()> {
+    let charges: Vec<(Mutation, &Path, f64)> = mutations
+        .iter()
+        .map(|&(mutation, path, bytes)| (mutation, path, bytes as f64))
+        .collect();
+    let (index, verdict) = match ACTIVE.lock().as_mut().and_then(|state| state.consume_all(&charges)) {
+        Some(refused) => refused,
+        None => return Ok(()),
+    };
+
+    let reason = format!(
+        "Quota exceeded: {}{} (used {}, requested {}, limit {})",
+        verdict.limit.unwrap_or_default(),
+        verdict.prefix.map(|p| format!(" under '{}'", p)).unwrap_or_default(),
+        verdict.used,
+        verdict.requested,
+        verdict.maximum
+    );
+    let path = mutations[index].1.to_string_lossy();
+    audit::record(AuditKind::AccessDenied, operation, &path, "denied", Some(&reason));
+    Err(errors::new(ErrorCode::QuotaExceeded, format!("{}: {}", reason, path)))
+}
+
+/// Install a quota for subsequent native file mutations, resetting usage
+///
+/// # Arguments
+/// * `policy` - Limits to enforce
+#[napi]
+pub fn set_file_quota(policy: QuotaPolicy) -> napi::Result<()> {
+    errors::guard("setFileQuota", || {
+        if policy.max_bytes_written < 0.0 || policy.prefix_budgets.iter().any(|b| b.max_bytes_written < 0.0) {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                "max_bytes_written must not be negative".to_string(),
+            ));
+        }
+
+        let mut budgets = vec![Budget {
+            root: None,
+            max_files_written: policy.max_files_written,
+            max_files_deleted: policy.max_files_deleted,
+            max_bytes_written: policy.max_bytes_written,
+            usage: QuotaUsage::default(),
+        }];
+        for budget in policy.prefix_budgets {
+            budgets.push(Budget {
+                root: Some(absolute_path(Path::new(&budget.prefix))),
+                max_files_written: budget.max_files_written,
+                max_files_deleted: budget.max_files_deleted,
+                max_bytes_written: budget.max_bytes_written,
+                usage: QuotaUsage {
+                    prefix: Some(budget.prefix),
+                    ..QuotaUsage::default()
+                },
+            });
+        }
+
+        *ACTIVE.lock() = Some(QuotaState { budgets });
+        Ok(())
+    })
+}
+
+/// Remove the active quota
+#[napi]
+pub fn clear_file_quota() {
+    *ACTIVE.lock() = None;
+}
+
+/// Usage under the overall budget followed by each prefix budget
+#[napi]
+pub fn get_file_quota_usage() -> Vec<QuotaUsage> {
+    ACTIVE
+        .lock()
+        .as_ref()
+        .map(|state| state.budgets.iter().map(|b| b.usage.clone()).collect())
+        .unwrap_or_default()
+}
+
+/// Charge the active quota for a mutation performed outside the native layer
+///
+/// Nothing is counted when the operation would exceed a budget, so the
+/// caller can skip it and continue with smaller operations.
+///
+/// # Arguments
+/// * `operation` - "write" or "delete"
+/// * `path` - File being modified
+/// * `bytes` - Bytes to be written (ignored for deletes)
+#[napi]
+pub fn consume_file_quota(operation: String, path: String, bytes: Option<f64>) -> napi::Result<QuotaVerdict> {
+    errors::guard("consumeFileQuota", || {
+        let mutation = match operation.as_str() {
+            "write" => Mutation::Write,
+            "delete" => Mutation::Delete,
+            other => {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Unknown quota operation: {} (expected write or delete)", other),
+                ))
+            }
+        };
+        let bytes = if mutation == Mutation::Write { bytes.unwrap_or(0.0).max(0.0) } else { 0.0 };
+
+        Ok(match ACTIVE.lock().as_mut() {
+            Some(state) => state.consume(mutation, Path::new(&path), bytes),
+            None => QuotaVerdict {
+                allowed: true,
+                limit: None,
+                prefix: None,
+                used: 0.0,
+                requested: bytes,
+                maximum: 0.0,
+            },
+        })
+    })
+}
+
+/// Absolute, normalized path with existing symlinked ancestors resolved
+fn absolute_path(path: &Path) -> PathBuf {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().unwrap_or_default().join(path)
+    };
+    resolve_existing(&lexical_normalize(&absolute))
+}
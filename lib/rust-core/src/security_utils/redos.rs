@@ -0,0 +1,966 @@
+//! Static ReDoS analysis of user-supplied regular expressions
+//!
+//! Patterns are parsed with JavaScript-compatible syntax (lookarounds and
+//! backreferences included) and checked for the shapes that make
+//! backtracking engines take exponential or polynomial time: nested
+//! unbounded quantifiers, overlapping alternatives under repetition and
+//! adjacent quantifiers over overlapping character sets. The pattern is also
+//! compiled with a size limit to catch counted repetitions that blow up the
+//! automaton. The checks are conservative heuristics: a reported pattern is
+//! not necessarily exploitable, so callers decide whether to reject or warn.
+//!
+//! `validate_regexes` runs the same checks over a batch of patterns, so tools
+//! loading hundreds of user rules can report every broken or dangerous one
+//! up front instead of failing on the first mid-scan.
+
+use napi_derive::napi;
+use rayon::prelude::*;
+use regex_automata::nfa::thompson;
+use serde::{Deserialize, Serialize};
+use crate::errors::{self, ErrorCode};
+
+/// Highest Unicode scalar value, for negated classes and `.`
+const MAX_CHAR: u32 = 0x10FFFF;
+
+/// Deepest nesting of groups and stacked quantifiers the parser accepts;
+/// the parser and the checks recurse once per level
+const MAX_NESTING: usize = 256;
+
+/// Limits applied by the analyzer
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegexAnalysisConfig {
+    /// Maximum compiled program size in bytes
+    pub max_compiled_size: u32,
+    /// Maximum number of atoms after expanding counted repetitions
+    pub max_expanded_size: u32,
+}
+
+impl Default for RegexAnalysisConfig {
+    fn default() -> Self {
+        Self {
+            max_compiled_size: 1024 * 1024,
+            max_expanded_size: 10_000,
+        }
+    }
+}
+
+/// A dangerous construct found in a pattern
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegexIssue {
+    /// Issue kind: "nested_quantifier", "overlapping_alternation",
+    /// "adjacent_quantifiers", "backreference", "compiled_size" or "excessive_repetition"
+    pub kind: String,
+    /// Severity: "high" (exponential), "medium" (polynomial or oversized) or "low"
+    pub severity: String,
+    /// Character offset of the construct in the pattern
+    pub position: u32,
+    /// Source text of the construct
+    pub fragment: String,
+    /// Description of the problem
+    pub message: String,
+    /// Suggested safe rewrite
+    pub suggestion: String,
+}
+
+/// Result of analyzing a pattern
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegexAnalysis {
+    /// Pattern that was analyzed
+    pub pattern: String,
+    /// Whether no high or medium severity issue was found
+    pub safe: bool,
+    /// Issues found, in pattern order
+    pub issues: Vec<RegexIssue>,
+    /// Deepest nesting of unbounded quantifiers
+    pub star_height: u32,
+    /// Atom count after expanding counted repetitions
+    pub expanded_size: f64,
+    /// Why the pattern doesn't compile with the linear-time engine, if it doesn't
+    pub compile_error: Option<String>,
+    /// Estimated compiled size in bytes (absent if the pattern doesn't
+    /// compile or exceeds `max_compiled_size`)
+    pub compiled_size: Option<f64>,
+}
+
+/// Validation of one pattern of a batch
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegexValidation {
+    /// Pattern that was validated
+    pub pattern: String,
+    /// Whether the pattern compiles with the linear-time engine
+    pub valid: bool,
+    /// Why the pattern doesn't parse or compile, if it doesn't
+    pub error: Option<String>,
+    /// Character offset of a syntax error, when known
+    pub error_position: Option<u32>,
+    /// Estimated compiled size in bytes (absent if the pattern doesn't
+    /// compile or exceeds `max_compiled_size`)
+    pub compiled_size: Option<f64>,
+    /// ReDoS issues, in pattern order (empty if the pattern doesn't parse)
+    pub issues: Vec<RegexIssue>,
+    /// Whether the pattern is valid and has no high or medium severity issue
+    pub safe: bool,
+}
+
+/// Analyze a regular expression for catastrophic backtracking
+///
+/// # Arguments
+/// * `pattern` - Regular expression source, without delimiters or flags
+/// * `config` - Optional size limits
+#[napi]
+pub fn analyze_regex(pattern: String, config: Option<RegexAnalysisConfig>) -> napi::Result<RegexAnalysis> {
+    errors::guard("analyzeRegex", || {
+        analyze(pattern, &config.unwrap_or_default()).map_err(|(_, pos, message)| {
+            errors::new(
+                ErrorCode::PatternInvalid,
+                format!("Invalid regex at offset {}: {}", pos, message),
+            )
+        })
+    })
+}
+
+/// Validate many regular expressions in one call
+///
+/// Every pattern is parsed, compiled and checked for catastrophic
+/// backtracking; a broken pattern is reported in its entry rather than
+/// failing the batch. Results are in input order.
+///
+/// # Arguments
+/// * `patterns` - Regular expression sources, without delimiters or flags
+/// * `config` - Optional size limits
+#[napi]
+pub fn validate_regexes(patterns: Vec<String>, config: Option<RegexAnalysisConfig>) -> napi::Result<Vec<RegexValidation>> {
+    errors::guard("validateRegexes", || {
+        let config = config.unwrap_or_default();
+        Ok(patterns
+            .into_par_iter()
+            .map(|pattern| match analyze(pattern, &config) {
+                Ok(analysis) => RegexValidation {
+                    valid: analysis.compile_error.is_none(),
+                    safe: analysis.safe && analysis.compile_error.is_none(),
+                    error: analysis.compile_error,
+                    error_position: None,
+                    compiled_size: analysis.compiled_size,
+                    issues: analysis.issues,
+                    pattern: analysis.pattern,
+                },
+                Err((pattern, pos, message)) => RegexValidation {
+                    pattern,
+                    valid: false,
+                    error: Some(message),
+                    error_position: Some(pos as u32),
+                    compiled_size: None,
+                    issues: Vec::new(),
+                    safe: false,
+                },
+            })
+            .collect())
+    })
+}
+
+/// Analyze `pattern`, or give it back with the offset and reason it doesn't parse
+fn analyze(pattern: String, config: &RegexAnalysisConfig) -> Result<RegexAnalysis, (String, usize, String)> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let ast = match (Parser { chars: &chars, pos: 0, depth: 0 }.parse()) {
+        Ok(ast) => ast,
+        Err((pos, message)) => return Err((pattern, pos, message)),
+    };
+
+    let mut analyzer = Analyzer {
+        chars: &chars,
+        issues: Vec::new(),
+    };
+    analyzer.walk(&ast);
+
+    let expanded_size = expanded_size(&ast);
+    let compile_error = match regex::RegexBuilder::new(&pattern)
+        .size_limit(config.max_compiled_size as usize)
+        .build()
+    {
+        Ok(_) => None,
+        Err(regex::Error::CompiledTooBig(limit)) => {
+            analyzer.push(
+                &ast,
+                "compiled_size",
+                "medium",
+                format!("Compiled pattern exceeds {} bytes", limit),
+                "Reduce counted repetition bounds (e.g. `{1000}`) or split the pattern into smaller ones".to_string(),
+            );
+            None
+        }
+        Err(e) => Some(e.to_string()),
+    };
+    if expanded_size > config.max_expanded_size as f64
+        && !analyzer.issues.iter().any(|i| i.kind == "compiled_size")
+    {
+        analyzer.push(
+            &ast,
+            "excessive_repetition",
+            "medium",
+            format!(
+                "Counted repetitions expand to about {} atoms (limit {})",
+                expanded_size, config.max_expanded_size
+            ),
+            "Lower the repetition bounds or validate length separately before matching".to_string(),
+        );
+    }
+    // The engine's size limit bounds the same NFA, so this builds whenever the pattern compiled
+    let compiled_size = if compile_error.is_none() {
+        thompson::Compiler::new()
+            .configure(thompson::Config::new().nfa_size_limit(Some(config.max_compiled_size as usize)))
+            .build(&pattern)
+            .ok()
+            .map(|nfa| nfa.memory_usage() as f64)
+    } else {
+        None
+    };
+
+    let mut issues = analyzer.issues;
+    issues.sort_by_key(|issue| issue.position);
+    Ok(RegexAnalysis {
+        pattern,
+        safe: issues.iter().all(|issue| issue.severity == "low"),
+        issues,
+        star_height: star_height(&ast),
+        expanded_size,
+        compile_error,
+        compiled_size,
+    })
+}
+
+/// Sorted, non-overlapping inclusive ranges of code points
+#[derive(Debug, Clone, Default)]
+struct CharSet(Vec<(u32, u32)>);
+
+impl CharSet {
+    fn single(c: char) -> Self {
+        Self(vec![(c as u32, c as u32)])
+    }
+
+    fn full() -> Self {
+        Self(vec![(0, MAX_CHAR)])
+    }
+
+    fn from_ranges(mut ranges: Vec<(u32, u32)>) -> Self {
+        ranges.sort_unstable();
+        let mut merged: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+        for (lo, hi) in ranges {
+            match merged.last_mut() {
+                Some(last) if lo <= last.1.saturating_add(1) => last.1 = last.1.max(hi),
+                _ => merged.push((lo, hi)),
+            }
+        }
+        Self(merged)
+    }
+
+    fn union(&self, other: &CharSet) -> Self {
+        Self::from_ranges(self.0.iter().chain(&other.0).copied().collect())
+    }
+
+    fn negate(&self) -> Self {
+        let mut ranges = Vec::new();
+        let mut next = 0u32;
+        for &(lo, hi) in &self.0 {
+            if lo > next {
+                ranges.push((next, lo - 1));
+            }
+            next = hi.saturating_add(1);
+        }
+        if next <= MAX_CHAR {
+            ranges.push((next, MAX_CHAR));
+        }
+        Self(ranges)
+    }
+
+    fn overlaps(&self, other: &CharSet) -> bool {
+        let (mut i, mut j) = (0, 0);
+        while i < self.0.len() && j < other.0.len() {
+            let (a, b) = (self.0[i], other.0[j]);
+            if a.0 <= b.1 && b.0 <= a.1 {
+                return true;
+            }
+            if a.1 < b.1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        false
+    }
+
+    /// Whether the set is a single code point (a literal)
+    fn is_single(&self) -> bool {
+        self.0.len() == 1 && self.0[0].0 == self.0[0].1
+    }
+}
+
+/// Parsed pattern node with its source span (character offsets)
+#[derive(Debug)]
+struct Node {
+    kind: NodeKind,
+    start: usize,
+    end: usize,
+}
+
+#[derive(Debug)]
+enum NodeKind {
+    Empty,
+    Set(CharSet),
+    /// Anchors and word boundaries
+    Assertion,
+    Lookaround(Box<Node>),
+    Backreference,
+    Group(Box<Node>),
+    Concat(Vec<Node>),
+    Alternation(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: u32,
+        max: Option<u32>,
+    },
+}
+
+type ParseResult<T> = Result<T, (usize, String)>;
+
+/// Recursive-descent parser for JavaScript-style regex syntax
+struct Parser<'a> {
+    chars: &'a [char],
+    pos: usize,
+    /// Groups open at the current position
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn parse(mut self) -> ParseResult<Node> {
+        let node = self.alternation()?;
+        match self.peek() {
+            Some(')') => Err((self.pos, "unmatched ')'".to_string())),
+            _ => Ok(node),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(c);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn alternation(&mut self) -> ParseResult<Node> {
+        let start = self.pos;
+        let mut branches = vec![self.concat()?];
+        while self.eat('|') {
+            branches.push(self.concat()?);
+        }
+        Ok(if branches.len() == 1 {
+            branches.pop().unwrap_or(Node {
+                kind: NodeKind::Empty,
+                start,
+                end: start,
+            })
+        } else {
+            Node {
+                kind: NodeKind::Alternation(branches),
+                start,
+                end: self.pos,
+            }
+        })
+    }
+
+    fn concat(&mut self) -> ParseResult<Node> {
+        let start = self.pos;
+        let mut items = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            items.push(self.quantified(atom)?);
+        }
+        Ok(match items.len() {
+            0 => Node {
+                kind: NodeKind::Empty,
+                start,
+                end: start,
+            },
+            1 => items.pop().unwrap_or(Node {
+                kind: NodeKind::Empty,
+                start,
+                end: start,
+            }),
+            _ => Node {
+                kind: NodeKind::Concat(items),
+                start,
+                end: self.pos,
+            },
+        })
+    }
+
+    /// Apply any quantifiers following an atom
+    fn quantified(&mut self, mut atom: Node) -> ParseResult<Node> {
+        let mut stacked = 0;
+        loop {
+            let start = self.pos;
+            let (min, max) = match self.peek() {
+                Some('*') => (0, None),
+                Some('+') => (1, None),
+                Some('?') => (0, Some(1)),
+                Some('{') => match self.counted() {
+                    Some(bounds) => bounds,
+                    None => return Ok(atom),
+                },
+                _ => return Ok(atom),
+            };
+            if self.pos == start {
+                self.pos += 1;
+            }
+            if matches!(atom.kind, NodeKind::Assertion) {
+                return Err((start, "quantifier follows nothing repeatable".to_string()));
+            }
+            stacked += 1;
+            if self.depth + stacked > MAX_NESTING {
+                return Err((start, "pattern nested too deeply".to_string()));
+            }
+            // Lazy and possessive suffixes don't change what can be matched
+            if !self.eat('?') {
+                self.eat('+');
+            }
+            let atom_start = atom.start;
+            atom = Node {
+                kind: NodeKind::Repeat {
+                    node: Box::new(atom),
+                    min,
+                    max,
+                },
+                start: atom_start,
+                end: self.pos,
+            };
+        }
+    }
+
+    /// `{n}`, `{n,}` or `{n,m}`; anything else leaves `{` as a literal
+    fn counted(&mut self) -> Option<(u32, Option<u32>)> {
+        let rest: String = self.chars[self.pos..].iter().take_while(|&&c| c != '}').collect();
+        if self.pos + rest.chars().count() >= self.chars.len() {
+            return None;
+        }
+        let body = rest.strip_prefix('{')?;
+        let number = |s: &str| s.trim().parse::<u32>().ok();
+        let bounds = match body.split_once(',') {
+            None => {
+                let n = number(body)?;
+                (n, Some(n))
+            }
+            Some((lo, "")) => (number(lo)?, None),
+            Some((lo, hi)) => (number(lo)?, Some(number(hi)?)),
+        };
+        if bounds.1.is_some_and(|hi| hi < bounds.0) {
+            return None;
+        }
+        self.pos += rest.chars().count() + 1;
+        Some(bounds)
+    }
+
+    fn atom(&mut self) -> ParseResult<Node> {
+        let start = self.pos;
+        let c = self.peek().ok_or((start, "unexpected end of pattern".to_string()))?;
+        self.pos += 1;
+        let kind = match c {
+            '(' => self.group(start)?,
+            '[' => NodeKind::Set(self.class(start)?),
+            '.' => NodeKind::Set(CharSet::single('\n').negate()),
+            '^' | '$' => NodeKind::Assertion,
+            '\\' => self.escape(start)?,
+            '*' | '+' | '?' => return Err((start, "quantifier follows nothing".to_string())),
+            c => NodeKind::Set(CharSet::single(c)),
+        };
+        Ok(Node {
+            kind,
+            start,
+            end: self.pos,
+        })
+    }
+
+    fn group(&mut self, start: usize) -> ParseResult<NodeKind> {
+        let mut lookaround = false;
+        if self.eat('?') {
+            match self.peek() {
+                Some('=') | Some('!') => {
+                    self.pos += 1;
+                    lookaround = true;
+                }
+                Some('<') if matches!(self.chars.get(self.pos + 1), Some('=') | Some('!')) => {
+                    self.pos += 2;
+                    lookaround = true;
+                }
+                Some('<') | Some('P') => {
+                    // Named group: (?<name>...) or (?P<name>...)
+                    self.eat('P');
+                    while let Some(c) = self.peek() {
+                        self.pos += 1;
+                        if c == '>' {
+                            break;
+                        }
+                    }
+                }
+                _ => {
+                    // Non-capturing group or inline flags: (?:...), (?i), (?i:...)
+                    while let Some(c) = self.peek() {
+                        if c == ':' {
+                            self.pos += 1;
+                            break;
+                        }
+                        if c == ')' {
+                            self.pos += 1;
+                            return Ok(NodeKind::Empty);
+                        }
+                        self.pos += 1;
+                    }
+                }
+            }
+        }
+        if self.depth >= MAX_NESTING {
+            return Err((start, "pattern nested too deeply".to_string()));
+        }
+        self.depth += 1;
+        let inner = self.alternation()?;
+        self.depth -= 1;
+        if !self.eat(')') {
+            return Err((start, "unclosed group".to_string()));
+        }
+        Ok(if lookaround {
+            NodeKind::Lookaround(Box::new(inner))
+        } else {
+            NodeKind::Group(Box::new(inner))
+        })
+    }
+
+    fn escape(&mut self, start: usize) -> ParseResult<NodeKind> {
+        let c = self.peek().ok_or((start, "trailing backslash".to_string()))?;
+        self.pos += 1;
+        Ok(match c {
+            'b' | 'B' | 'A' | 'z' | 'Z' | 'G' => NodeKind::Assertion,
+            '1'..='9' => {
+                while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    self.pos += 1;
+                }
+                NodeKind::Backreference
+            }
+            'k' if self.peek() == Some('<') => {
+                while let Some(c) = self.peek() {
+                    self.pos += 1;
+                    if c == '>' {
+                        break;
+                    }
+                }
+                NodeKind::Backreference
+            }
+            _ => NodeKind::Set(self.escaped_set(c)),
+        })
+    }
+
+    /// Character set of an escape other than assertions and backreferences
+    fn escaped_set(&mut self, c: char) -> CharSet {
+        match c {
+            'd' => digit(),
+            'D' => digit().negate(),
+            'w' => word(),
+            'W' => word().negate(),
+            's' => space(),
+            'S' => space().negate(),
+            'n' => CharSet::single('\n'),
+            'r' => CharSet::single('\r'),
+            't' => CharSet::single('\t'),
+            'f' => CharSet::single('\x0C'),
+            'v' => CharSet::single('\x0B'),
+            '0' => CharSet::single('\0'),
+            'x' => self.hex_escape(2),
+            'u' => self.hex_escape(4),
+            // Unicode properties: treat as any character
+            'p' | 'P' => {
+                if self.eat('{') {
+                    while self.peek().is_some_and(|c| c != '}') {
+                        self.pos += 1;
+                    }
+                    self.eat('}');
+                } else {
+                    self.pos = (self.pos + 1).min(self.chars.len());
+                }
+                CharSet::full()
+            }
+            other => CharSet::single(other),
+        }
+    }
+
+    /// `\xHH`, `\uHHHH` or `\u{H...}`; falls back to the letter itself
+    fn hex_escape(&mut self, digits: usize) -> CharSet {
+        let letter = if digits == 2 { 'x' } else { 'u' };
+        let braced = digits == 4 && self.peek() == Some('{');
+        let (from, len) = if braced {
+            let len = self.chars[self.pos + 1..].iter().take_while(|&&c| c != '}').count();
+            (self.pos + 1, len)
+        } else {
+            (self.pos, digits)
+        };
+        let text: String = self.chars.iter().skip(from).take(len).collect();
+        match u32::from_str_radix(&text, 16).ok().and_then(char::from_u32) {
+            Some(value) if text.chars().count() == len && len > 0 => {
+                self.pos = from + len + usize::from(braced);
+                CharSet::single(value)
+            }
+            _ => CharSet::single(letter),
+        }
+    }
+
+    /// Bracketed class, after the opening `[`
+    fn class(&mut self, start: usize) -> ParseResult<CharSet> {
+        let negated = self.eat('^');
+        let mut set = CharSet::default();
+        let mut first = true;
+        loop {
+            let c = self.peek().ok_or((start, "unclosed character class".to_string()))?;
+            self.pos += 1;
+            if c == ']' && !first {
+                break;
+            }
+            first = false;
+
+            let item = if c == '\\' {
+                let e = self.peek().ok_or((start, "unclosed character class".to_string()))?;
+                self.pos += 1;
+                if e == 'b' {
+                    CharSet::single('\x08')
+                } else {
+                    self.escaped_set(e)
+                }
+            } else if c == '[' && self.peek() == Some(':') {
+                // POSIX class such as [:alpha:]: treat as any character
+                while let Some(c) = self.peek() {
+                    self.pos += 1;
+                    if c == ']' {
+                        break;
+                    }
+                }
+                CharSet::full()
+            } else {
+                CharSet::single(c)
+            };
+
+            let is_range = item.is_single()
+                && self.peek() == Some('-')
+                && self.chars.get(self.pos + 1).is_some_and(|&c| c != ']');
+            if is_range {
+                self.pos += 1;
+                let hi = self.peek().unwrap_or('-');
+                self.pos += 1;
+                let hi = if hi == '\\' {
+                    let e = self.peek().unwrap_or('\\');
+                    self.pos += 1;
+                    self.escaped_set(e)
+                } else {
+                    CharSet::single(hi)
+                };
+                let lo = item.0[0].0;
+                match hi.is_single().then(|| hi.0[0].0) {
+                    Some(hi) if hi >= lo => set = set.union(&CharSet(vec![(lo, hi)])),
+                    Some(_) => return Err((start, "character class range out of order".to_string())),
+                    None => set = set.union(&item).union(&hi).union(&CharSet::single('-')),
+                }
+            } else {
+                set = set.union(&item);
+            }
+        }
+        Ok(if negated { set.negate() } else { set })
+    }
+}
+
+fn digit() -> CharSet {
+    CharSet(vec![('0' as u32, '9' as u32)])
+}
+
+fn word() -> CharSet {
+    CharSet::from_ranges(vec![
+        ('0' as u32, '9' as u32),
+        ('A' as u32, 'Z' as u32),
+        ('_' as u32, '_' as u32),
+        ('a' as u32, 'z' as u32),
+    ])
+}
+
+fn space() -> CharSet {
+    CharSet::from_ranges(vec![
+        (0x09, 0x0D),
+        (0x20, 0x20),
+        (0xA0, 0xA0),
+        (0x1680, 0x1680),
+        (0x2000, 0x200A),
+        (0x2028, 0x2029),
+        (0x202F, 0x202F),
+        (0x205F, 0x205F),
+        (0x3000, 0x3000),
+        (0xFEFF, 0xFEFF),
+    ])
+}
+
+/// Whether a node can match the empty string
+fn nullable(node: &Node) -> bool {
+    match &node.kind {
+        NodeKind::Empty | NodeKind::Assertion | NodeKind::Lookaround(_) | NodeKind::Backreference => true,
+        NodeKind::Set(_) => false,
+        NodeKind::Group(inner) => nullable(inner),
+        NodeKind::Concat(items) => items.iter().all(nullable),
+        NodeKind::Alternation(branches) => branches.iter().any(nullable),
+        NodeKind::Repeat { node, min, .. } => *min == 0 || nullable(node),
+    }
+}
+
+/// Characters a match of the node can start with
+fn first_chars(node: &Node) -> CharSet {
+    match &node.kind {
+        NodeKind::Empty | NodeKind::Assertion | NodeKind::Lookaround(_) => CharSet::default(),
+        NodeKind::Backreference => CharSet::full(),
+        NodeKind::Set(set) => set.clone(),
+        NodeKind::Group(inner) | NodeKind::Repeat { node: inner, .. } => first_chars(inner),
+        NodeKind::Concat(items) => {
+            let mut set = CharSet::default();
+            for item in items {
+                set = set.union(&first_chars(item));
+                if !nullable(item) {
+                    break;
+                }
+            }
+            set
+        }
+        NodeKind::Alternation(branches) => branches
+            .iter()
+            .fold(CharSet::default(), |set, branch| set.union(&first_chars(branch))),
+    }
+}
+
+/// Every character a match of the node can consume
+fn all_chars(node: &Node) -> CharSet {
+    match &node.kind {
+        NodeKind::Empty | NodeKind::Assertion | NodeKind::Lookaround(_) => CharSet::default(),
+        NodeKind::Backreference => CharSet::full(),
+        NodeKind::Set(set) => set.clone(),
+        NodeKind::Group(inner) | NodeKind::Repeat { node: inner, .. } => all_chars(inner),
+        NodeKind::Concat(nodes) | NodeKind::Alternation(nodes) => nodes
+            .iter()
+            .fold(CharSet::default(), |set, node| set.union(&all_chars(node))),
+    }
+}
+
+/// Strip redundant groups
+fn unwrap_groups(mut node: &Node) -> &Node {
+    while let NodeKind::Group(inner) = &node.kind {
+        node = inner;
+    }
+    node
+}
+
+fn is_unbounded(node: &Node) -> bool {
+    matches!(node.kind, NodeKind::Repeat { max: None, .. })
+}
+
+/// Shallowest unbounded repetition inside a node, outside lookarounds
+fn find_unbounded(node: &Node) -> Option<&Node> {
+    if is_unbounded(node) {
+        return Some(node);
+    }
+    match &node.kind {
+        NodeKind::Group(inner) | NodeKind::Repeat { node: inner, .. } => find_unbounded(inner),
+        NodeKind::Concat(nodes) | NodeKind::Alternation(nodes) => nodes.iter().find_map(find_unbounded),
+        _ => None,
+    }
+}
+
+fn contains(outer: &Node, inner: &Node) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+/// Deepest nesting of unbounded repetitions
+fn star_height(node: &Node) -> u32 {
+    let children = match &node.kind {
+        NodeKind::Group(inner) | NodeKind::Lookaround(inner) | NodeKind::Repeat { node: inner, .. } => {
+            star_height(inner)
+        }
+        NodeKind::Concat(nodes) | NodeKind::Alternation(nodes) => nodes.iter().map(star_height).max().unwrap_or(0),
+        _ => 0,
+    };
+    children + u32::from(is_unbounded(node))
+}
+
+/// Atom count once counted repetitions are written out
+fn expanded_size(node: &Node) -> f64 {
+    match &node.kind {
+        NodeKind::Empty | NodeKind::Assertion => 0.0,
+        NodeKind::Set(_) | NodeKind::Backreference => 1.0,
+        NodeKind::Group(inner) | NodeKind::Lookaround(inner) => expanded_size(inner),
+        NodeKind::Concat(nodes) | NodeKind::Alternation(nodes) => nodes.iter().map(expanded_size).sum(),
+        NodeKind::Repeat { node, min, max } => {
+            let copies = max.unwrap_or(min.saturating_add(1)).max(1) as f64;
+            expanded_size(node) * copies
+        }
+    }
+}
+
+struct Analyzer<'a> {
+    chars: &'a [char],
+    issues: Vec<RegexIssue>,
+}
+
+impl Analyzer<'_> {
+    fn fragment(&self, node: &Node) -> String {
+        self.chars[node.start..node.end].iter().collect()
+    }
+
+    fn push(&mut self, node: &Node, kind: &str, severity: &str, message: String, suggestion: String) {
+        self.issues.push(RegexIssue {
+            kind: kind.to_string(),
+            severity: severity.to_string(),
+            position: node.start as u32,
+            fragment: self.fragment(node),
+            message,
+            suggestion,
+        });
+    }
+
+    fn walk(&mut self, node: &Node) {
+        match &node.kind {
+            NodeKind::Backreference => self.push(
+                node,
+                "backreference",
+                "low",
+                "Backreferences can only be matched by backtracking".to_string(),
+                "Avoid backreferences in patterns applied to untrusted input".to_string(),
+            ),
+            NodeKind::Group(inner) | NodeKind::Lookaround(inner) => self.walk(inner),
+            NodeKind::Concat(items) => {
+                self.check_adjacent(items);
+                items.iter().for_each(|item| self.walk(item));
+            }
+            NodeKind::Alternation(branches) => branches.iter().for_each(|branch| self.walk(branch)),
+            NodeKind::Repeat { node: body, max, .. } => {
+                if max.is_none() {
+                    self.check_nested(node, body);
+                    self.check_alternation(node, body);
+                }
+                self.walk(body);
+            }
+            NodeKind::Empty | NodeKind::Set(_) | NodeKind::Assertion => {}
+        }
+    }
+
+    /// `(a+)+`: an unbounded repetition whose body contains another one,
+    /// unless every iteration must consume a character the inner one can't
+    fn check_nested(&mut self, outer: &Node, body: &Node) {
+        let Some(inner) = find_unbounded(body) else { return };
+        let inner_chars = all_chars(inner);
+
+        let body = unwrap_groups(body);
+        let elements: Vec<&Node> = match &body.kind {
+            NodeKind::Concat(items) => items.iter().collect(),
+            _ => vec![body],
+        };
+        let separated = elements
+            .iter()
+            .any(|e| !contains(e, inner) && !nullable(e) && !all_chars(e).overlaps(&inner_chars));
+        if separated {
+            return;
+        }
+
+        let suggestion = if std::ptr::eq(body, inner) {
+            format!("Replace `{}` with `{}`", self.fragment(outer), self.fragment(inner))
+        } else {
+            format!(
+                "Remove the quantifier on `{}` or make each repetition of `{}` end with a delimiter `{}` cannot match",
+                self.fragment(inner),
+                self.fragment(outer),
+                self.fragment(inner)
+            )
+        };
+        self.push(
+            outer,
+            "nested_quantifier",
+            "high",
+            format!(
+                "Nested quantifiers: `{}` can split the same input between repetitions in exponentially many ways",
+                self.fragment(inner)
+            ),
+            suggestion,
+        );
+    }
+
+    /// `(a|ab)*`: alternatives under repetition that can start with the same character
+    fn check_alternation(&mut self, outer: &Node, body: &Node) {
+        let NodeKind::Alternation(branches) = &unwrap_groups(body).kind else { return };
+        let firsts: Vec<CharSet> = branches.iter().map(first_chars).collect();
+        for i in 0..branches.len() {
+            for j in i + 1..branches.len() {
+                if !firsts[i].overlaps(&firsts[j]) {
+                    continue;
+                }
+                let single = |n: &Node| matches!(unwrap_groups(n).kind, NodeKind::Set(_));
+                let severity = if single(&branches[i]) && single(&branches[j]) || nullable(&branches[i]) || nullable(&branches[j]) {
+                    "high"
+                } else {
+                    "medium"
+                };
+                self.push(
+                    outer,
+                    "overlapping_alternation",
+                    severity,
+                    format!(
+                        "Alternatives `{}` and `{}` overlap inside a repetition, so the engine retries each split",
+                        self.fragment(&branches[i]),
+                        self.fragment(&branches[j])
+                    ),
+                    "Make the alternatives mutually exclusive, e.g. merge them into one character class or factor out the common prefix".to_string(),
+                );
+                return;
+            }
+        }
+    }
+
+    /// `\d+\d+`: unbounded repetitions over overlapping sets separated only
+    /// by optional items
+    fn check_adjacent(&mut self, items: &[Node]) {
+        let mut open: Vec<(&Node, CharSet)> = Vec::new();
+        for item in items {
+            if is_unbounded(item) {
+                let chars = all_chars(item);
+                if let Some((previous, _)) = open.iter().find(|(_, set)| set.overlaps(&chars)) {
+                    let span = Node {
+                        kind: NodeKind::Empty,
+                        start: previous.start,
+                        end: item.end,
+                    };
+                    self.push(
+                        &span,
+                        "adjacent_quantifiers",
+                        "medium",
+                        format!(
+                            "`{}` and `{}` match overlapping characters, giving polynomial backtracking",
+                            self.fragment(previous),
+                            self.fragment(item)
+                        ),
+                        "Merge the repetitions into one or separate them with a required delimiter neither can match".to_string(),
+                    );
+                    return;
+                }
+                open.push((item, chars));
+            } else if !nullable(item) {
+                open.clear();
+            }
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! Sandbox policy for native file access
+//!
+//! A `SandboxPolicy` confines every file the native modules touch to a set of
+//! allowed roots, rejects denied globs and oversized files, caps result
+//! counts, and decides whether symlinks may be followed. It is attached to
+//! `FileSearch` through `FileSearchConfig::sandbox`, so the scanners built on
+//! top of it inherit the same checks. The bulk file operations in `file_ops`
+//! take it as an argument, and `check_sandbox_access` exposes the verdict to
+//! JavaScript callers that perform file operations themselves.
+
+use super::{lexical_normalize, SecurityUtils};
+use crate::audit::{self, AuditKind};
+use crate::errors::{self, ErrorCode};
+use crate::shared;
+use globset::GlobSet;
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Limits applied to every native file access
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SandboxPolicy {
+    /// Directories file access is confined to (empty allows any location)
+    pub allowed_roots: Vec<String>,
+    /// Glob patterns for paths that may never be accessed (e.g. "**/.ssh/**"),
+    /// matched against the absolute, normalized path
+    pub denied_globs: Vec<String>,
+    /// Maximum size in bytes of a file that may be read (0 for no limit)
+    pub max_file_size: f64,
+    /// Maximum number of results a single operation may return (0 for no limit)
+    pub max_results: u32,
+    /// Follow symlinks whose real location stays inside an allowed root
+    pub follow_symlinks: bool,
+}
+
+impl Default for SandboxPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_roots: Vec::new(),
+            denied_globs: Vec::new(),
+            max_file_size: 0.0,
+            max_results: 0,
+            follow_symlinks: false,
+        }
+    }
+}
+
+/// Verdict for a single path under a sandbox policy
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SandboxVerdict {
+    /// Whether the path may be accessed
+    pub allowed: bool,
+    /// Why access is denied (if denied)
+    pub reason: Option<String>,
+}
+
+/// Compiled sandbox policy
+#[derive(Debug, Clone)]
+pub(crate) struct Sandbox {
+    policy: SandboxPolicy,
+    /// Allowed roots as given (made absolute), for paths spelled through them
+    given_roots: Vec<PathBuf>,
+    /// Allowed roots with every symlink resolved
+    roots: Vec<PathBuf>,
+    denied: Arc<GlobSet>,
+}
+
+impl Sandbox {
+    /// Compile the policy, resolving every allowed root
+    pub(crate) fn new(policy: SandboxPolicy) -> napi::Result<Self> {
+        let roots = policy
+            .allowed_roots
+            .iter()
+            .map(|root| {
+                Path::new(root).canonicalize().map_err(|e| {
+                    errors::io(
+                        &e,
+                        format!("Cannot resolve sandbox root '{}': {}", root, e),
+                    )
+                })
+            })
+            .collect::<napi::Result<Vec<_>>>()?;
+        let cwd = std::env::current_dir()?;
+        let given_roots = policy
+            .allowed_roots
+            .iter()
+            .map(|root| lexical_normalize(&cwd.join(root)))
+            .collect();
+
+        let denied = shared::glob_set(&policy.denied_globs).map_err(|e| {
+            errors::new(ErrorCode::PatternInvalid, format!("Invalid denied glob: {}", e))
+        })?;
+
+        Ok(Self { policy, given_roots, roots, denied })
+    }
+
+    /// Whether symlinks may be followed at all
+    pub(crate) fn follows_symlinks(&self) -> bool {
+        self.policy.follow_symlinks
+    }
+
+    /// Result cap for a single operation, if any
+    pub(crate) fn max_results(&self) -> Option<usize> {
+        (self.policy.max_results > 0).then_some(self.policy.max_results as usize)
+    }
+
+    /// Validate the starting point of an operation (e.g. a traversal root)
+    ///
+    /// The path is resolved one component at a time, so a root reached
+    /// through a symlink that leaves every allowed root is rejected. Roots
+    /// are passed as given, so a path spelled through a root that is itself
+    /// a symlink is still recognised as inside it.
+    pub(crate) fn check_root(&self, path: &Path) -> napi::Result<()> {
+        let absolute = if path.is_absolute() {
+            lexical_normalize(path)
+        } else {
+            lexical_normalize(&std::env::current_dir()?.join(path))
+        };
+
+        if let Some(pattern) = self.denied_by(&absolute) {
+            return Err(errors::new(
+                ErrorCode::PermissionDenied,
+                format!("Path '{}' is denied by sandbox pattern '{}'", path.display(), pattern),
+            ));
+        }
+        if self.roots.is_empty() {
+            return Ok(());
+        }
+
+        let utils = SecurityUtils::new(None);
+        for root in &self.given_roots {
+            let check = utils.check_symlink_escape(&absolute.to_string_lossy(), &root.to_string_lossy())?;
+            if check.is_valid {
+                return Ok(());
+            }
+        }
+        Err(errors::new(
+            ErrorCode::PermissionDenied,
+            format!("Path '{}' is outside the sandbox roots", path.display()),
+        ))
+    }
+
+    /// Validate a path an operation is about to read or write
+    ///
+    /// Combines `check_root` with the entry checks of `denial`, failing with
+    /// `PERMISSION_DENIED` when either rejects the path.
+    pub(crate) fn check_access(&self, path: &Path) -> napi::Result<()> {
+        self.check_root(path)?;
+        let is_symlink = fs::symlink_metadata(path)
+            .map(|m| m.file_type().is_symlink())
+            .unwrap_or(false);
+        match self.denial(path, is_symlink, false) {
+            Some(reason) => Err(errors::new(
+                ErrorCode::PermissionDenied,
+                format!("Path '{}' {}", path.display(), reason),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Reason an entry found during traversal may not be accessed, if any
+    ///
+    /// # Arguments
+    /// * `path` - Path of the entry
+    /// * `is_symlink` - Whether the entry itself is a symlink
+    /// * `via_symlinks` - Whether the traversal follows links, so the entry may
+    ///   live below a followed directory link
+    pub(crate) fn denial(&self, path: &Path, is_symlink: bool, via_symlinks: bool) -> Option<String> {
+        if let Some(pattern) = self.denied_by(path) {
+            return Some(format!("denied by sandbox pattern '{}'", pattern));
+        }
+        if is_symlink && !self.policy.follow_symlinks {
+            return Some("symlinks are not followed in the sandbox".to_string());
+        }
+        if (is_symlink || via_symlinks) && !self.roots.is_empty() {
+            let inside = path
+                .canonicalize()
+                .map(|real| self.roots.iter().any(|root| real.starts_with(root)))
+                .unwrap_or(false);
+            if !inside {
+                return Some("resolves outside the sandbox roots".to_string());
+            }
+        }
+        if self.policy.max_file_size > 0.0 {
+            if let Ok(metadata) = fs::metadata(path) {
+                if metadata.is_file() && metadata.len() as f64 > self.policy.max_file_size {
+                    return Some(format!(
+                        "size {} exceeds sandbox max_file_size {}",
+                        metadata.len(),
+                        self.policy.max_file_size
+                    ));
+                }
+            }
+        }
+        None
+    }
+
+    /// First denied glob matching the path
+    ///
+    /// Relative paths (e.g. entries of a walk from ".") are made absolute
+    /// first, so a pattern matches however the root was spelled.
+    fn denied_by(&self, path: &Path) -> Option<&str> {
+        let absolute = if path.is_absolute() {
+            lexical_normalize(path)
+        } else {
+            match std::env::current_dir() {
+                Ok(cwd) => lexical_normalize(&cwd.join(path)),
+                Err(_) => lexical_normalize(path),
+            }
+        };
+        self.denied
+            .matches(&absolute)
+            .first()
+            .map(|&i| self.policy.denied_globs[i].as_str())
+    }
+}
+
+/// Check whether a path may be accessed under a sandbox policy
+///
+/// Intended for file operations performed outside the native modules: the
+/// path must resolve inside an allowed root without escaping through a
+/// symlink, must not match a denied glob, and must respect the size limit.
+///
+/// # Arguments
+/// * `policy` - Sandbox policy to apply
+/// * `path` - Path that is about to be accessed
+#[napi]
+pub fn check_sandbox_access(policy: SandboxPolicy, path: String) -> napi::Result<SandboxVerdict> {
+    errors::guard("checkSandboxAccess", || {
+        let sandbox = Sandbox::new(policy)?;
+        let target = Path::new(&path);
+
+        let reason = match sandbox.check_root(target) {
+            Err(e) => Some(e.reason),
+            Ok(()) => {
+                let is_symlink = fs::symlink_metadata(target)
+                    .map(|m| m.file_type().is_symlink())
+                    .unwrap_or(false);
+                sandbox.denial(target, is_symlink, false)
+            }
+        };
+        if let Some(reason) = &reason {
+            audit::record(AuditKind::AccessDenied, "check_sandbox_access", &path, "denied", Some(reason));
+        }
+        Ok(SandboxVerdict {
+            allowed: reason.is_none(),
+            reason,
+        })
+    })
+}
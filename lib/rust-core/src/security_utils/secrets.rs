@@ -0,0 +1,634 @@
+//! Secret detection for source trees
+//!
+//! Built-in rules cover AWS keys, GitHub/GitLab tokens, private key blocks
+//! and JWTs; a Shannon-entropy pass catches credentials without a known
+//! format, and is also exposed on its own as `find_high_entropy_strings`.
+//! Directory scans use the same traversal and exclusion rules as
+//! `FileSearch` and run in parallel. Additional rules can be loaded at
+//! runtime from TOML or JSON rulesets.
+
+use super::read_text_file;
+use crate::concurrency;
+use crate::errors::{self, ErrorCode};
+use crate::file_search::{FileSearch, FileSearchConfig};
+use crate::kernels::LineIndex;
+use napi_derive::napi;
+use rayon::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Configuration for secret scanning
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretScanConfig {
+    /// Report high-entropy strings that match no rule
+    pub enable_entropy: bool,
+    /// Minimum Shannon entropy (bits per char) for base64-like strings;
+    /// hex strings use two thirds of this value
+    pub entropy_threshold: f64,
+    /// Minimum length of strings considered by the entropy pass
+    pub min_entropy_length: u32,
+    /// Maximum number of findings to return (0 for unlimited)
+    pub max_findings: u32,
+    /// Traversal and exclusion settings for directory scans
+    pub search: Option<FileSearchConfig>,
+}
+
+impl Default for SecretScanConfig {
+    fn default() -> Self {
+        Self {
+            enable_entropy: true,
+            entropy_threshold: 4.5,
+            min_entropy_length: 20,
+            max_findings: 0,
+            search: None,
+        }
+    }
+}
+
+/// Secret found in scanned content
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretFinding {
+    /// File path (or the label passed for text scans)
+    pub path: String,
+    /// Line number (1-based)
+    pub line_number: u32,
+    /// Start byte column within the line
+    pub column_start: u32,
+    /// End byte column within the line
+    pub column_end: u32,
+    /// Rule identifier (e.g. "aws-access-key-id", "high-entropy-string")
+    pub rule_id: String,
+    /// Rule description
+    pub description: String,
+    /// Severity: "critical", "high", "medium", or "low"
+    pub severity: String,
+    /// Matched secret with all but a short prefix masked
+    pub redacted_match: String,
+    /// Shannon entropy of the match (bits per char)
+    pub entropy: f64,
+}
+
+/// High-entropy string found by `find_high_entropy_strings`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntropySpan {
+    /// Start byte offset in the text
+    pub start: u32,
+    /// End byte offset in the text
+    pub end: u32,
+    /// Line number (1-based)
+    pub line_number: u32,
+    /// Start byte column within the line
+    pub column_start: u32,
+    /// Alphabet of the string: "hex", "base64", "base64url" or "mixed"
+    pub charset: String,
+    /// Shannon entropy of the string (bits per char)
+    pub entropy: f64,
+    /// String with all but a short prefix masked
+    pub redacted_match: String,
+}
+
+/// Rule definition as written in a TOML or JSON ruleset
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleDefinition {
+    id: String,
+    #[serde(default)]
+    description: Option<String>,
+    regex: String,
+    #[serde(default)]
+    severity: Option<String>,
+    #[serde(default)]
+    keywords: Vec<String>,
+    #[serde(default)]
+    entropy: Option<f64>,
+}
+
+/// Top-level ruleset document: `[[rules]]` in TOML, `{"rules": [...]}` in JSON
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesetDefinition {
+    rules: Vec<RuleDefinition>,
+}
+
+/// Compiled detection rule
+struct SecretRule {
+    id: String,
+    description: String,
+    severity: String,
+    regex: Regex,
+    /// Lowercase keywords; a line is only matched when it contains one
+    keywords: Vec<String>,
+    /// Minimum entropy the secret must reach to be reported
+    min_entropy: Option<f64>,
+}
+
+/// Built-in rules: (id, description, severity, regex, keywords)
+const BUILTIN_RULES: [(&str, &str, &str, &str, &[&str]); 8] = [
+    (
+        "aws-access-key-id",
+        "AWS access key ID",
+        "high",
+        r"\b(?P<secret>(?:AKIA|ASIA|ABIA|ACCA)[0-9A-Z]{16})\b",
+        &["akia", "asia", "abia", "acca"],
+    ),
+    (
+        "aws-secret-access-key",
+        "AWS secret access key",
+        "critical",
+        r#"(?i)aws.{0,20}(?:secret|private).{0,20}?['"=:\s]\s*['"]?(?P<secret>[A-Za-z0-9/+]{40})\b"#,
+        &["aws"],
+    ),
+    (
+        "github-token",
+        "GitHub personal access, OAuth or app token",
+        "high",
+        r"\b(?P<secret>(?:ghp|gho|ghu|ghs|ghr)_[A-Za-z0-9]{36})\b",
+        &["ghp_", "gho_", "ghu_", "ghs_", "ghr_"],
+    ),
+    (
+        "github-fine-grained-token",
+        "GitHub fine-grained personal access token",
+        "high",
+        r"\b(?P<secret>github_pat_[A-Za-z0-9_]{82})\b",
+        &["github_pat_"],
+    ),
+    (
+        "gitlab-token",
+        "GitLab personal access token",
+        "high",
+        r"\b(?P<secret>glpat-[A-Za-z0-9_\-]{20})\b",
+        &["glpat-"],
+    ),
+    (
+        "private-key",
+        "Private key block",
+        "critical",
+        r"-----BEGIN (?P<secret>(?:RSA |DSA |EC |OPENSSH |PGP |ENCRYPTED )?PRIVATE KEY(?: BLOCK)?)-----",
+        &["private key"],
+    ),
+    (
+        "jwt",
+        "JSON Web Token",
+        "medium",
+        r"\b(?P<secret>eyJ[A-Za-z0-9_-]{10,}\.eyJ[A-Za-z0-9_-]{10,}\.[A-Za-z0-9_-]{10,})",
+        &["eyj"],
+    ),
+    (
+        "slack-token",
+        "Slack token",
+        "high",
+        r"\b(?P<secret>xox[baprs]-[A-Za-z0-9-]{10,})\b",
+        &["xox"],
+    ),
+];
+
+/// Secret scanner
+#[napi]
+pub struct SecretScanner {
+    config: SecretScanConfig,
+    rules: Vec<SecretRule>,
+    search: FileSearch,
+}
+
+#[napi]
+impl SecretScanner {
+    /// Create a new secret scanner with optional configuration
+    ///
+    /// # Arguments
+    /// * `config` - Optional configuration for secret scanning
+    #[napi(constructor)]
+    pub fn new(config: Option<SecretScanConfig>) -> napi::Result<Self> {
+        errors::guard("new SecretScanner", || {
+            let config = config.unwrap_or_default();
+            let search = FileSearch::new(config.search.clone())?;
+
+            let rules = BUILTIN_RULES
+                .iter()
+                .map(|(id, description, severity, pattern, keywords)| {
+                    Ok(SecretRule {
+                        id: id.to_string(),
+                        description: description.to_string(),
+                        severity: severity.to_string(),
+                        regex: compile_rule_regex(id, pattern)?,
+                        keywords: keywords.iter().map(|k| k.to_string()).collect(),
+                        min_entropy: None,
+                    })
+                })
+                .collect::<napi::Result<_>>()?;
+
+            Ok(Self {
+                config,
+                rules,
+                search,
+            })
+        })
+    }
+
+    /// Scan text for secrets
+    ///
+    /// # Arguments
+    /// * `text` - Content to scan
+    /// * `label` - Optional name reported as the finding path
+    #[napi]
+    pub fn scan_text(&self, text: String, label: Option<String>) -> napi::Result<Vec<SecretFinding>> {
+        errors::catch_panic("SecretScanner.scanText", || {
+            let mut findings = self.scan_content(&text, &label.unwrap_or_default());
+            self.truncate(&mut findings);
+            findings
+        })
+    }
+
+    /// Scan a single file for secrets (binary files are skipped)
+    #[napi]
+    pub fn scan_file(&self, path: String) -> napi::Result<Vec<SecretFinding>> {
+        errors::guard("SecretScanner.scanFile", || {
+            let mut findings = self.scan_path(Path::new(&path)).map_err(|e| {
+                errors::io(&e, format!("Failed to read {}: {}", path, e))
+            })?;
+            self.truncate(&mut findings);
+            Ok(findings)
+        })
+    }
+
+    /// Scan a directory tree for secrets in parallel
+    #[napi]
+    pub fn scan_directory(&self, root_path: String) -> napi::Result<Vec<SecretFinding>> {
+        errors::guard("SecretScanner.scanDirectory", || {
+            let _permit = concurrency::acquire("SecretScanner.scanDirectory")?;
+            let files = self.search.collect_files(Path::new(&root_path))?;
+
+            let mut findings: Vec<SecretFinding> = if self.use_parallel() && files.len() > 100 {
+                files
+                    .par_iter()
+                    .flat_map(|path| self.scan_path(path).unwrap_or_default())
+                    .collect()
+            } else {
+                files
+                    .iter()
+                    .flat_map(|path| self.scan_path(path).unwrap_or_default())
+                    .collect()
+            };
+
+            findings.sort_by(|a, b| {
+                a.path
+                    .cmp(&b.path)
+                    .then(a.line_number.cmp(&b.line_number))
+                    .then(a.column_start.cmp(&b.column_start))
+            });
+            self.truncate(&mut findings);
+            Ok(findings)
+        })
+    }
+
+    /// Load additional rules from a TOML or JSON ruleset
+    ///
+    /// Each rule has an `id`, a `regex` (a `secret` named group selects the
+    /// reported span), and optional `description`, `severity`, `keywords` and
+    /// minimum `entropy`. The whole ruleset is validated and compiled before
+    /// any rule is added; ids must not collide with active rules.
+    ///
+    /// # Arguments
+    /// * `ruleset` - Ruleset document
+    /// * `format` - "toml" or "json" (detected from the first character when omitted)
+    ///
+    /// Returns the number of rules added
+    #[napi]
+    pub fn load_rules(&mut self, ruleset: String, format: Option<String>) -> napi::Result<u32> {
+        errors::guard("SecretScanner.loadRules", || {
+            let format = format.unwrap_or_else(|| {
+                if ruleset.trim_start().starts_with('{') {
+                    "json".to_string()
+                } else {
+                    "toml".to_string()
+                }
+            });
+
+            let definition: RulesetDefinition = match format.as_str() {
+                "json" => serde_json::from_str(&ruleset).map_err(|e| {
+                    errors::new(ErrorCode::ParseError, format!("Invalid JSON ruleset: {}", e))
+                })?,
+                "toml" => toml::from_str(&ruleset).map_err(|e| {
+                    errors::new(ErrorCode::ParseError, format!("Invalid TOML ruleset: {}", e))
+                })?,
+                _ => {
+                    return Err(errors::new(
+                        ErrorCode::InvalidArgument,
+                        format!("Unsupported ruleset format: {}", format),
+                    ))
+                }
+            };
+
+            let mut compiled = Vec::with_capacity(definition.rules.len());
+            for rule in definition.rules {
+                compiled.push(self.compile_definition(rule, &compiled)?);
+            }
+
+            let added = compiled.len() as u32;
+            self.rules.extend(compiled);
+            Ok(added)
+        })
+    }
+
+    /// Identifiers of the active rules
+    #[napi]
+    pub fn rule_ids(&self) -> Vec<String> {
+        self.rules.iter().map(|rule| rule.id.clone()).collect()
+    }
+
+    /// Validate and compile one ruleset entry
+    fn compile_definition(&self, rule: RuleDefinition, pending: &[SecretRule]) -> napi::Result<SecretRule> {
+        let invalid = |message: String| errors::new(ErrorCode::InvalidArgument, message);
+
+        if rule.id.trim().is_empty() {
+            return Err(invalid("Rule id must not be empty".to_string()));
+        }
+        if self.rules.iter().chain(pending).any(|existing| existing.id == rule.id) {
+            return Err(invalid(format!("Duplicate rule id: {}", rule.id)));
+        }
+
+        let severity = rule.severity.unwrap_or_else(|| "medium".to_string());
+        if !["critical", "high", "medium", "low"].contains(&severity.as_str()) {
+            return Err(invalid(format!("Rule '{}' has invalid severity: {}", rule.id, severity)));
+        }
+
+        if let Some(entropy) = rule.entropy {
+            if !(0.0..=8.0).contains(&entropy) {
+                return Err(invalid(format!("Rule '{}' entropy must be between 0 and 8", rule.id)));
+            }
+        }
+
+        Ok(SecretRule {
+            regex: compile_rule_regex(&rule.id, &rule.regex)?,
+            description: rule.description.unwrap_or_else(|| rule.id.clone()),
+            severity,
+            keywords: rule.keywords.iter().map(|k| k.to_lowercase()).collect(),
+            min_entropy: rule.entropy,
+            id: rule.id,
+        })
+    }
+
+    /// Whether traversal should run in parallel
+    fn use_parallel(&self) -> bool {
+        self.config.search.as_ref().is_none_or(|search| search.use_parallel)
+    }
+
+    /// Apply the `max_findings` limit
+    fn truncate(&self, findings: &mut Vec<SecretFinding>) {
+        if self.config.max_findings > 0 {
+            findings.truncate(self.config.max_findings as usize);
+        }
+    }
+
+    /// Read and scan one file, skipping binary content
+    fn scan_path(&self, path: &Path) -> std::io::Result<Vec<SecretFinding>> {
+        Ok(match read_text_file(path)? {
+            Some(text) => self.scan_content(&text, &path.to_string_lossy()),
+            None => Vec::new(),
+        })
+    }
+
+    /// Run all rules and the entropy pass over content, line by line
+    pub(crate) fn scan_content(&self, text: &str, path: &str) -> Vec<SecretFinding> {
+        let mut findings = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index as u32 + 1;
+            let lowercase = line.to_lowercase();
+            let first_on_line = findings.len();
+
+            for rule in &self.rules {
+                if !rule.keywords.is_empty() && !rule.keywords.iter().any(|k| lowercase.contains(k.as_str())) {
+                    continue;
+                }
+
+                for captures in rule.regex.captures_iter(line) {
+                    let Some(secret) = captures.name("secret").or_else(|| captures.get(0)) else {
+                        continue;
+                    };
+
+                    let entropy = shannon_entropy(secret.as_str());
+                    if rule.min_entropy.is_some_and(|min| entropy < min) {
+                        continue;
+                    }
+
+                    findings.push(SecretFinding {
+                        path: path.to_string(),
+                        line_number,
+                        column_start: secret.start() as u32,
+                        column_end: secret.end() as u32,
+                        rule_id: rule.id.clone(),
+                        description: rule.description.clone(),
+                        severity: rule.severity.clone(),
+                        redacted_match: redact(secret.as_str()),
+                        entropy,
+                    });
+                }
+            }
+
+            if self.config.enable_entropy {
+                let tokens = high_entropy_tokens(
+                    line,
+                    self.config.min_entropy_length as usize,
+                    self.config.entropy_threshold,
+                );
+                for token in tokens {
+                    let overlaps_rule = findings[first_on_line..]
+                        .iter()
+                        .any(|f| (token.start as u32) < f.column_end && f.column_start < token.end as u32);
+                    if overlaps_rule {
+                        continue;
+                    }
+
+                    findings.push(SecretFinding {
+                        path: path.to_string(),
+                        line_number,
+                        column_start: token.start as u32,
+                        column_end: token.end as u32,
+                        rule_id: "high-entropy-string".to_string(),
+                        description: "High-entropy string".to_string(),
+                        severity: "medium".to_string(),
+                        redacted_match: redact(&line[token.start..token.end]),
+                        entropy: token.entropy,
+                    });
+                }
+            }
+        }
+
+        findings
+    }
+}
+
+/// Compile a rule pattern, naming the rule on failure
+fn compile_rule_regex(id: &str, pattern: &str) -> napi::Result<Regex> {
+    Regex::new(pattern).map_err(|e| {
+        errors::new(ErrorCode::PatternInvalid, format!("Invalid pattern for rule '{}': {}", id, e))
+    })
+}
+
+/// High-entropy run of token characters
+pub(crate) struct EntropyToken {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) charset: &'static str,
+    pub(crate) entropy: f64,
+}
+
+/// Whether a byte can be part of a base64, base64url or hex token
+fn is_token_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'+' | b'/' | b'=' | b'_' | b'-')
+}
+
+/// Alphabet a token is drawn from: "hex", "base64", "base64url" or "mixed"
+fn token_charset(token: &str) -> &'static str {
+    let body = token.trim_end_matches('=');
+    let standard = body.contains(['+', '/']);
+    let url_safe = body.contains(['-', '_']);
+    if body.bytes().all(|b| b.is_ascii_hexdigit()) {
+        "hex"
+    } else if standard && url_safe {
+        "mixed"
+    } else if url_safe {
+        "base64url"
+    } else {
+        "base64"
+    }
+}
+
+/// Maximal runs of token characters at least `min_length` long whose entropy
+/// reaches `threshold`
+///
+/// The threshold is given for base64 alphabets (6 bits per char at most) and
+/// scaled by 2/3 for hex, whose ceiling is 4 bits. Runs must mix letters and
+/// digits, which skips long identifiers and words.
+pub(crate) fn high_entropy_tokens(text: &str, min_length: usize, threshold: f64) -> Vec<EntropyToken> {
+    let bytes = text.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !is_token_byte(bytes[i]) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < bytes.len() && is_token_byte(bytes[i]) {
+            if bytes[i] == b'=' {
+                // '=' is only base64 padding at the end of a run; "key=value" splits
+                let padding = bytes[i..].iter().take_while(|&&b| b == b'=').count();
+                let next = bytes.get(i + padding).copied();
+                if i > start && next.is_none_or(|b| !is_token_byte(b)) {
+                    i += padding;
+                }
+                break;
+            }
+            i += 1;
+        }
+        if i == start {
+            i += 1;
+            continue;
+        }
+        let token = &text[start..i];
+        if token.len() < min_length.max(1) {
+            continue;
+        }
+
+        let mixed = token.bytes().any(|b| b.is_ascii_digit()) && token.bytes().any(|b| b.is_ascii_alphabetic());
+        if !mixed {
+            continue;
+        }
+
+        let charset = token_charset(token);
+        let entropy = shannon_entropy(token);
+        let required = if charset == "hex" { threshold * 2.0 / 3.0 } else { threshold };
+        if entropy >= required {
+            tokens.push(EntropyToken {
+                start,
+                end: i,
+                charset,
+                entropy,
+            });
+        }
+    }
+    tokens
+}
+
+/// Shannon entropy of a string in bits per character
+pub(crate) fn shannon_entropy(value: &str) -> f64 {
+    if value.is_empty() {
+        return 0.0;
+    }
+
+    let mut counts = [0u32; 256];
+    for byte in value.bytes() {
+        counts[byte as usize] += 1;
+    }
+
+    let length = value.len() as f64;
+    counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / length;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Mask a secret, keeping a short prefix for identification
+pub(crate) fn redact(secret: &str) -> String {
+    let length = secret.chars().count();
+    let visible = if length >= 12 { 4 } else { 0 };
+    secret
+        .chars()
+        .take(visible)
+        .chain(std::iter::repeat_n('*', length - visible))
+        .collect()
+}
+
+/// Find high-entropy strings without running the secret rules
+///
+/// Candidates are runs of base64, base64url or hex characters. Hex runs only
+/// need two thirds of the threshold, matching their smaller alphabet.
+///
+/// # Arguments
+/// * `text` - Content to scan
+/// * `min_length` - Minimum run length (default 20)
+/// * `threshold` - Minimum Shannon entropy in bits per char for base64 runs (default 4.5)
+#[napi]
+pub fn find_high_entropy_strings(text: String, min_length: Option<u32>, threshold: Option<f64>) -> napi::Result<Vec<EntropySpan>> {
+    errors::catch_panic("findHighEntropyStrings", || {
+        let defaults = SecretScanConfig::default();
+        let min_length = min_length.unwrap_or(defaults.min_entropy_length) as usize;
+        let threshold = threshold.unwrap_or(defaults.entropy_threshold);
+        let index = LineIndex::new(&text);
+
+        high_entropy_tokens(&text, min_length, threshold)
+            .into_iter()
+            .map(|token| {
+                let (line_number, column_start) = index.position(token.start);
+                EntropySpan {
+                    start: token.start as u32,
+                    end: token.end as u32,
+                    line_number,
+                    column_start,
+                    charset: token.charset.to_string(),
+                    entropy: token.entropy,
+                    redacted_match: redact(&text[token.start..token.end]),
+                }
+            })
+            .collect()
+    })
+}
+
+/// Quick secret scan of text with default rules
+#[napi]
+pub fn quick_scan_secrets(text: String) -> napi::Result<Vec<SecretFinding>> {
+    errors::guard("quickScanSecrets", || {
+        let scanner = SecretScanner::new(None)?;
+        scanner.scan_text(text, None)
+    })
+}
@@ -0,0 +1,348 @@
+//! Overwrite-and-delete for sensitive files
+//!
+//! `secure_delete` overwrites a file's contents in place, flushes each pass
+//! to disk, renames the file to a random name and only then unlinks it. This
+//! only destroys the data when the filesystem rewrites blocks in place:
+//! copy-on-write and log-structured filesystems, network mounts, snapshots
+//! and SSD wear levelling can all keep older copies. The result says so
+//! instead of pretending otherwise, so callers can fall back to full-disk
+//! encryption or key destruction where it matters.
+
+use super::quota::{self, Mutation};
+use crate::audit::{self, AuditKind};
+use crate::errors::{self, ErrorCode};
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Size of each overwrite write
+const WIPE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Upper bound on overwrite passes
+const MAX_PASSES: u32 = 35;
+
+/// Filesystems that never overwrite blocks in place
+const BEST_EFFORT_FILESYSTEMS: &[&str] = &[
+    "btrfs", "zfs", "bcachefs", "f2fs", "nilfs2", "overlay", "nfs", "cifs", "smb3", "fuse", "9p",
+    // macOS names
+    "apfs", "smbfs", "macfuse",
+];
+
+/// Outcome of a secure delete
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecureDeleteResult {
+    /// Path that was deleted
+    pub path: String,
+    /// Whether the file was removed
+    pub deleted: bool,
+    /// Overwrite passes completed
+    pub passes: u32,
+    /// Bytes written across all passes
+    pub bytes_overwritten: f64,
+    /// Filesystem type, where it could be determined
+    pub filesystem: Option<String>,
+    /// Whether old contents may survive on disk despite the overwrite
+    pub best_effort: bool,
+    /// Reasons the overwrite may be incomplete
+    pub warnings: Vec<String>,
+}
+
+/// Overwrite a file's contents and then delete it
+///
+/// Every pass but the last writes random bytes; the last writes zeros. Each
+/// pass is synced to disk before the next starts. Symlinks and non-regular
+/// files are rejected so a link can't redirect the overwrite elsewhere; the
+/// file is opened without following links and checked to be the one
+/// inspected, so swapping in a link meanwhile fails instead.
+///
+/// A file with other hard links is rejected unless `allow_hard_links` is
+/// set: the overwrite destroys the data under every name, while only this
+/// one is removed.
+///
+/// # Arguments
+/// * `path` - File to delete
+/// * `passes` - Number of overwrite passes (default 1, at most 35)
+/// * `allow_hard_links` - Overwrite files with other hard links (default: false)
+#[napi]
+pub fn secure_delete(
+    path: String,
+    passes: Option<u32>,
+    allow_hard_links: Option<bool>,
+) -> napi::Result<SecureDeleteResult> {
+    errors::guard("secureDelete", || {
+        let result = delete_securely(path.clone(), passes, allow_hard_links.unwrap_or(false));
+        match &result {
+            Ok(deleted) => audit::record(
+                AuditKind::FileMutation,
+                "secure_delete",
+                &path,
+                "success",
+                deleted.best_effort.then_some("best effort"),
+            ),
+            Err(e) => audit::record(AuditKind::FileMutation, "secure_delete", &path, "failure", Some(&e.reason)),
+        }
+        result
+    })
+}
+
+fn delete_securely(path: String, passes: Option<u32>, allow_hard_links: bool) -> napi::Result<SecureDeleteResult> {
+    let passes = passes.unwrap_or(1);
+    if passes == 0 || passes > MAX_PASSES {
+        return Err(errors::new(
+            ErrorCode::InvalidArgument,
+            format!("passes must be between 1 and {}", MAX_PASSES),
+        ));
+    }
+
+    let target = Path::new(&path);
+    let metadata = fs::symlink_metadata(target).map_err(|e| io_error(&path, e))?;
+    if metadata.file_type().is_symlink() {
+        return Err(errors::new(
+            ErrorCode::InvalidArgument,
+            format!("Refusing to securely delete symlink: {}", path),
+        ));
+    }
+    if !metadata.is_file() {
+        return Err(errors::new(
+            ErrorCode::InvalidArgument,
+            format!("Not a regular file: {}", path),
+        ));
+    }
+    // A link swapped in since the check fails to open or is a different file
+    let mut file = open_unfollowed(target).map_err(|e| io_error(&path, e))?;
+    let opened = file.metadata().map_err(|e| io_error(&path, e))?;
+    if !same_file(&metadata, &opened) {
+        return Err(errors::new(
+            ErrorCode::IoError,
+            format!("Secure delete of {} failed: file was replaced while being opened", path),
+        ));
+    }
+    let metadata = opened;
+    if let Some(links) = hard_link_count(&metadata).filter(|&n| n > 1) {
+        if !allow_hard_links {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!(
+                    "Refusing to securely delete {}: it has {} other hard links whose data would be overwritten (set allowHardLinks to proceed)",
+                    path,
+                    links - 1
+                ),
+            ));
+        }
+    }
+    quota::charge("secure_delete", Mutation::Delete, target, 0)?;
+
+    let mut warnings = Vec::new();
+    let filesystem = target
+        .canonicalize()
+        .ok()
+        .and_then(|real| filesystem_type(&real));
+    let mut best_effort = true;
+    match filesystem.as_deref() {
+        Some(fs_type)
+            if BEST_EFFORT_FILESYSTEMS
+                .iter()
+                .any(|b| fs_type.starts_with(b)) =>
+        {
+            warnings.push(format!(
+                "{} does not overwrite data in place; previous contents may remain on disk",
+                fs_type
+            ));
+        }
+        Some(_) => best_effort = false,
+        None => warnings.push("filesystem type could not be determined".to_string()),
+    }
+    if !best_effort {
+        warnings.push("flash storage may keep remapped copies of overwritten blocks".to_string());
+    }
+    if let Some(links) = hard_link_count(&metadata).filter(|&n| n > 1) {
+        warnings.push(format!(
+            "file has {} other hard links; they now point to overwritten data",
+            links - 1
+        ));
+    }
+
+    let len = metadata.len();
+    overwrite(&mut file, len, passes).map_err(|e| io_error(&path, e))?;
+
+    let renamed = rename_randomly(target).unwrap_or_else(|_| {
+        warnings.push("file could not be renamed before deletion; its name may remain".to_string());
+        target.to_path_buf()
+    });
+    // Truncate so the final directory entry doesn't reference the data blocks
+    let _ = file.set_len(0);
+    let _ = file.sync_all();
+    // Unlink only the entry of the file that was overwritten
+    match fs::symlink_metadata(&renamed) {
+        Ok(current) if same_file(&metadata, &current) => {}
+        Ok(_) => {
+            return Err(errors::new(
+                ErrorCode::IoError,
+                format!("Secure delete of {} failed: file was replaced during the overwrite", path),
+            ))
+        }
+        Err(e) => return Err(io_error(&path, e)),
+    }
+    fs::remove_file(&renamed).map_err(|e| io_error(&path, e))?;
+
+    Ok(SecureDeleteResult {
+        path,
+        deleted: true,
+        passes,
+        bytes_overwritten: (len as f64) * passes as f64,
+        filesystem,
+        best_effort,
+        warnings,
+    })
+}
+
+/// Open a file for writing, failing if `path` is a symlink
+#[cfg(unix)]
+fn open_unfollowed(path: &Path) -> std::io::Result<File> {
+    use std::os::unix::fs::OpenOptionsExt;
+    OpenOptions::new().write(true).custom_flags(libc::O_NOFOLLOW).open(path)
+}
+
+#[cfg(not(unix))]
+fn open_unfollowed(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().write(true).open(path)
+}
+
+/// Whether two metadata describe the same file
+#[cfg(unix)]
+fn same_file(a: &fs::Metadata, b: &fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    a.dev() == b.dev() && a.ino() == b.ino()
+}
+
+#[cfg(not(unix))]
+fn same_file(a: &fs::Metadata, b: &fs::Metadata) -> bool {
+    a.is_file() == b.is_file() && a.len() == b.len()
+}
+
+/// Overwrite `len` bytes in place, syncing after each pass
+fn overwrite(file: &mut File, len: u64, passes: u32) -> std::io::Result<()> {
+    let mut chunk = vec![0u8; WIPE_CHUNK_SIZE];
+
+    for pass in 0..passes {
+        let random = pass + 1 < passes;
+        file.seek(SeekFrom::Start(0))?;
+        let mut remaining = len;
+        while remaining > 0 {
+            let n = remaining.min(WIPE_CHUNK_SIZE as u64) as usize;
+            if random {
+                getrandom::getrandom(&mut chunk[..n])
+                    .map_err(|e| std::io::Error::other(e.to_string()))?;
+            } else {
+                chunk[..n].fill(0);
+            }
+            file.write_all(&chunk[..n])?;
+            remaining -= n as u64;
+        }
+        file.sync_all()?;
+    }
+    Ok(())
+}
+
+/// Rename the file to a random name in the same directory
+fn rename_randomly(path: &Path) -> std::io::Result<PathBuf> {
+    let mut bytes = [0u8; 8];
+    getrandom::getrandom(&mut bytes).map_err(|e| std::io::Error::other(e.to_string()))?;
+    let renamed = path.with_file_name(format!(".{}", crate::hashing::to_hex(&bytes)));
+    fs::rename(path, &renamed)?;
+    Ok(renamed)
+}
+
+/// Filesystem type of the mount containing `path` (Linux and macOS)
+#[cfg(target_os = "linux")]
+pub(crate) fn filesystem_type(path: &Path) -> Option<String> {
+    let mountinfo = fs::read_to_string("/proc/self/mountinfo").ok()?;
+    let mut best: Option<(usize, String)> = None;
+
+    for line in mountinfo.lines() {
+        let Some((mount_fields, fs_fields)) = line.split_once(" - ") else {
+            continue;
+        };
+        let (Some(raw_mount), Some(fs_type)) =
+            (mount_fields.split(' ').nth(4), fs_fields.split(' ').next())
+        else {
+            continue;
+        };
+        let mount_point = unescape_mount_path(raw_mount);
+        let depth = Path::new(&mount_point).components().count();
+        if path.starts_with(&mount_point) && best.as_ref().is_none_or(|(d, _)| depth >= *d) {
+            best = Some((depth, fs_type.to_string()));
+        }
+    }
+    best.map(|(_, fs_type)| fs_type)
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn filesystem_type(path: &Path) -> Option<String> {
+    use std::ffi::{CStr, CString};
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stats = std::mem::MaybeUninit::<libc::statfs>::uninit();
+    // SAFETY: `path` is NUL-terminated and `stats` is only read after statfs filled it
+    let stats = unsafe {
+        if libc::statfs(path.as_ptr(), stats.as_mut_ptr()) != 0 {
+            return None;
+        }
+        stats.assume_init()
+    };
+    // SAFETY: the kernel NUL-terminates f_fstypename ("apfs", "smbfs", "macfuse", ...)
+    let name = unsafe { CStr::from_ptr(stats.f_fstypename.as_ptr()) };
+    Some(name.to_string_lossy().into_owned())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub(crate) fn filesystem_type(_path: &Path) -> Option<String> {
+    None
+}
+
+/// Decode the octal escapes (`\040` for space) used in mountinfo paths
+#[cfg(target_os = "linux")]
+fn unescape_mount_path(raw: &str) -> String {
+    let bytes = raw.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\'
+            && i + 3 < bytes.len()
+            && bytes[i + 1..i + 4]
+                .iter()
+                .all(|b| (b'0'..=b'7').contains(b))
+        {
+            out.push(
+                (bytes[i + 1] - b'0') * 64 + (bytes[i + 2] - b'0') * 8 + (bytes[i + 3] - b'0'),
+            );
+            i += 4;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(unix)]
+fn hard_link_count(metadata: &fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.nlink())
+}
+
+#[cfg(not(unix))]
+fn hard_link_count(_metadata: &fs::Metadata) -> Option<u64> {
+    None
+}
+
+fn io_error(path: &str, e: std::io::Error) -> napi::Error {
+    errors::io(
+        &e,
+        format!("Secure delete of {} failed: {}", path, e),
+    )
+}
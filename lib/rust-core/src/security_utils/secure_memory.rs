@@ -0,0 +1,131 @@
+//! Constant-time comparison and zeroizing secret storage
+//!
+//! Token and digest comparisons made in the native layer go through
+//! `constant_time_eq`, whose running time depends only on the input lengths.
+//! `SecretBuffer` keeps key material out of the JavaScript heap: the bytes
+//! live in native memory, are wiped on `clear()` or when the object is
+//! garbage collected, and are only copied back out on explicit request.
+
+use napi::bindgen_prelude::Buffer;
+use napi_derive::napi;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, Zeroizing};
+
+/// Compare two byte strings in constant time
+///
+/// Only the lengths may leak; inputs of different length compare unequal.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+/// Compare two buffers without leaking where they differ
+///
+/// # Arguments
+/// * `a` - First value (e.g. the expected token)
+/// * `b` - Second value (e.g. the token supplied by a caller)
+#[napi]
+pub fn constant_time_equals(a: Buffer, b: Buffer) -> bool {
+    constant_time_eq(&a, &b)
+}
+
+/// Compare two strings (tokens, hex digests) without leaking where they differ
+///
+/// The UTF-8 bytes are compared as-is; no case folding is applied.
+///
+/// # Arguments
+/// * `a` - First string
+/// * `b` - Second string
+#[napi]
+pub fn constant_time_equals_str(a: String, b: String) -> bool {
+    constant_time_eq(a.as_bytes(), b.as_bytes())
+}
+
+/// Secret bytes held in native memory and wiped when no longer needed
+#[napi]
+pub struct SecretBuffer {
+    data: Zeroizing<Vec<u8>>,
+}
+
+#[napi]
+impl SecretBuffer {
+    /// Copy secret bytes into native memory
+    ///
+    /// # Arguments
+    /// * `data` - Secret bytes
+    /// * `wipe_source` - Overwrite the passed buffer with zeros after copying
+    #[napi(constructor)]
+    pub fn new(mut data: Buffer, wipe_source: Option<bool>) -> Self {
+        let secret = Self {
+            data: Zeroizing::new(data.to_vec()),
+        };
+        if wipe_source.unwrap_or(false) {
+            data.as_mut().zeroize();
+        }
+        secret
+    }
+
+    /// Copy a secret string (e.g. a token read from the environment)
+    ///
+    /// # Arguments
+    /// * `value` - Secret string, stored as UTF-8
+    #[napi(factory)]
+    pub fn from_string(value: String) -> Self {
+        let value = Zeroizing::new(value);
+        Self {
+            data: Zeroizing::new(value.as_bytes().to_vec()),
+        }
+    }
+
+    /// Number of bytes held (0 once cleared)
+    #[napi(getter)]
+    pub fn length(&self) -> u32 {
+        self.data.len() as u32
+    }
+
+    /// Whether the secret has been wiped
+    #[napi(getter)]
+    pub fn is_cleared(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Compare against a candidate value in constant time
+    ///
+    /// # Arguments
+    /// * `candidate` - Bytes to compare with the secret
+    #[napi]
+    pub fn equals(&self, candidate: Buffer) -> bool {
+        constant_time_eq(&self.data, &candidate)
+    }
+
+    /// Compare against a candidate string in constant time
+    ///
+    /// # Arguments
+    /// * `candidate` - String whose UTF-8 bytes are compared with the secret
+    #[napi]
+    pub fn equals_str(&self, candidate: String) -> bool {
+        constant_time_eq(&self.data, candidate.as_bytes())
+    }
+
+    /// Compare against another secret in constant time
+    ///
+    /// # Arguments
+    /// * `other` - Secret to compare with
+    #[napi]
+    pub fn equals_secret(&self, other: &SecretBuffer) -> bool {
+        constant_time_eq(&self.data, &other.data)
+    }
+
+    /// Copy the secret back into a JavaScript buffer
+    ///
+    /// The copy is owned by JavaScript and is not wiped automatically.
+    #[napi]
+    pub fn expose(&self) -> Buffer {
+        Buffer::from(self.data.to_vec())
+    }
+
+    /// Overwrite the secret with zeros and release it
+    #[napi]
+    pub fn clear(&mut self) {
+        self.data.zeroize();
+    }
+}
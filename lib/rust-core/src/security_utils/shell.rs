@@ -0,0 +1,449 @@
+//! Shell argument quoting and command injection analysis
+//!
+//! Quoting follows each shell's own rules: POSIX single quotes, PowerShell
+//! single quotes, and for `cmd.exe` the MSVCRT argv convention followed by
+//! caret-escaping of cmd metacharacters. The analyzer tokenizes a command
+//! with the shell's quoting rules and reports constructs that execute or
+//! expand text, so config-provided commands can be reviewed before running.
+
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use crate::errors::{self, ErrorCode};
+
+/// Characters that never need quoting in a POSIX word
+const POSIX_SAFE: &str = "_@%+=:,./-";
+
+/// Characters cmd.exe interprets outside double quotes
+const CMD_METACHARS: &str = "()%!^\"<>&|";
+
+/// Shell syntax a command is written for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ShellPlatform {
+    Posix,
+    Cmd,
+    PowerShell,
+}
+
+impl ShellPlatform {
+    /// Parse a platform name, defaulting to the host's shell
+    fn parse(platform: Option<String>) -> napi::Result<Self> {
+        match platform.as_deref() {
+            None if cfg!(windows) => Ok(Self::Cmd),
+            None => Ok(Self::Posix),
+            Some("posix") | Some("sh") | Some("bash") => Ok(Self::Posix),
+            Some("cmd") => Ok(Self::Cmd),
+            Some("powershell") | Some("pwsh") => Ok(Self::PowerShell),
+            Some(other) => Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Unknown shell platform: {} (expected posix, cmd, or powershell)", other),
+            )),
+        }
+    }
+}
+
+/// Potentially dangerous construct found in a shell command
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShellFinding {
+    /// Construct kind: "command_substitution", "process_substitution",
+    /// "arithmetic_expansion", "unquoted_variable", "variable_expansion",
+    /// "unquoted_glob", "command_separator", "command_chaining", "pipe",
+    /// "background", "redirection", "eval", or "unterminated_quote"
+    pub kind: String,
+    /// Severity: "high", "medium", or "low"
+    pub severity: String,
+    /// Start byte offset in the command
+    pub start: u32,
+    /// End byte offset in the command
+    pub end: u32,
+    /// The offending text
+    pub snippet: String,
+    /// Explanation of the risk
+    pub message: String,
+}
+
+/// Quote a single argument so the shell passes it through unchanged
+///
+/// # Arguments
+/// * `arg` - Argument to quote
+/// * `platform` - "posix", "cmd", or "powershell" (defaults to the host shell)
+#[napi]
+pub fn quote_shell_arg(arg: String, platform: Option<String>) -> napi::Result<String> {
+    errors::guard("quoteShellArg", || {
+        let platform = ShellPlatform::parse(platform)?;
+        quote_arg(&arg, platform)
+    })
+}
+
+/// Quote every argument and join them into a command line
+///
+/// # Arguments
+/// * `args` - Program and arguments
+/// * `platform` - "posix", "cmd", or "powershell" (defaults to the host shell)
+#[napi]
+pub fn quote_shell_command(args: Vec<String>, platform: Option<String>) -> napi::Result<String> {
+    errors::guard("quoteShellCommand", || {
+        let platform = ShellPlatform::parse(platform)?;
+        let quoted = args
+            .iter()
+            .map(|arg| quote_arg(arg, platform))
+            .collect::<napi::Result<Vec<_>>>()?;
+        Ok(quoted.join(" "))
+    })
+}
+
+fn quote_arg(arg: &str, platform: ShellPlatform) -> napi::Result<String> {
+    if arg.contains('\0') {
+        return Err(errors::new(
+            ErrorCode::InvalidArgument,
+            "Shell arguments cannot contain null bytes".to_string(),
+        ));
+    }
+
+    Ok(match platform {
+        ShellPlatform::Posix => {
+            if !arg.is_empty() && arg.chars().all(|c| c.is_ascii_alphanumeric() || POSIX_SAFE.contains(c)) {
+                arg.to_string()
+            } else {
+                format!("'{}'", arg.replace('\'', r"'\''"))
+            }
+        }
+        ShellPlatform::PowerShell => {
+            // PowerShell also treats typographic single quotes as quotes
+            let mut quoted = String::with_capacity(arg.len() + 2);
+            quoted.push('\'');
+            for c in arg.chars() {
+                if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}') {
+                    quoted.push(c);
+                }
+                quoted.push(c);
+            }
+            quoted.push('\'');
+            quoted
+        }
+        ShellPlatform::Cmd => {
+            if arg.contains(['\n', '\r']) {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "cmd.exe arguments cannot contain line breaks".to_string(),
+                ));
+            }
+            caret_escape(&msvcrt_quote(arg))
+        }
+    })
+}
+
+/// Quote for `CommandLineToArgvW`: backslashes are literal unless they precede a quote
+fn msvcrt_quote(arg: &str) -> String {
+    let mut quoted = String::with_capacity(arg.len() + 2);
+    quoted.push('"');
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                quoted.extend(std::iter::repeat_n('\\', backslashes * 2 + 1));
+                quoted.push('"');
+                backslashes = 0;
+            }
+            _ => {
+                quoted.extend(std::iter::repeat_n('\\', backslashes));
+                quoted.push(c);
+                backslashes = 0;
+            }
+        }
+    }
+    quoted.extend(std::iter::repeat_n('\\', backslashes * 2));
+    quoted.push('"');
+    quoted
+}
+
+/// Escape cmd metacharacters, including quotes so cmd never enters a quoted region
+fn caret_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() * 2);
+    for c in text.chars() {
+        if CMD_METACHARS.contains(c) {
+            escaped.push('^');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Quoting state while scanning a command
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Quote {
+    None,
+    Single,
+    Double,
+}
+
+/// Collects findings with their spans
+struct Findings<'a> {
+    command: &'a str,
+    findings: Vec<ShellFinding>,
+}
+
+impl Findings<'_> {
+    fn push(&mut self, kind: &str, severity: &str, start: usize, end: usize, message: &str) {
+        self.findings.push(ShellFinding {
+            kind: kind.to_string(),
+            severity: severity.to_string(),
+            start: start as u32,
+            end: end as u32,
+            snippet: self.command[start..end].to_string(),
+            message: message.to_string(),
+        });
+    }
+}
+
+/// Analyze a command string for injection-prone constructs
+///
+/// The command is tokenized with the shell's quoting rules, so constructs
+/// inside single quotes (or escaped) are not reported, while substitutions
+/// inside double quotes are.
+///
+/// # Arguments
+/// * `command` - Command line to analyze
+/// * `platform` - "posix" or "cmd" (defaults to the host shell)
+#[napi]
+pub fn analyze_shell_command(command: String, platform: Option<String>) -> napi::Result<Vec<ShellFinding>> {
+    errors::guard("analyzeShellCommand", || {
+        let mut findings = Findings {
+            command: &command,
+            findings: Vec::new(),
+        };
+        match ShellPlatform::parse(platform)? {
+            ShellPlatform::Posix => analyze_posix(&command, &mut findings),
+            ShellPlatform::Cmd => analyze_cmd(&command, &mut findings),
+            ShellPlatform::PowerShell => {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "Command analysis supports posix and cmd syntax".to_string(),
+                ))
+            }
+        }
+        Ok(findings.findings)
+    })
+}
+
+/// Byte offset just past the character starting at `i` (or the end of the command)
+fn skip_char(command: &str, i: usize) -> usize {
+    command.get(i..).and_then(|rest| rest.chars().next()).map_or(command.len(), |c| i + c.len_utf8())
+}
+
+/// Byte offset just past the construct closed by `close`, honoring nesting of `open`
+fn find_closing(bytes: &[u8], from: usize, open: u8, close: u8) -> Option<usize> {
+    let mut depth = 1;
+    let mut i = from;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b if b == open && open != close => depth += 1,
+            b if b == close => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+fn analyze_posix(command: &str, findings: &mut Findings) {
+    let bytes = command.as_bytes();
+    let mut quote = Quote::None;
+    let mut quote_start = 0;
+    let mut command_start = true;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        let next = bytes.get(i + 1).copied();
+
+        if quote == Quote::Single {
+            if b == b'\'' {
+                quote = Quote::None;
+            }
+            i += 1;
+            continue;
+        }
+
+        match b {
+            b'\\' => {
+                i = skip_char(command, i + 1);
+                continue;
+            }
+            b'\'' if quote == Quote::None => {
+                quote = Quote::Single;
+                quote_start = i;
+                command_start = false;
+            }
+            b'"' => {
+                command_start = false;
+                if quote == Quote::Double {
+                    quote = Quote::None;
+                } else {
+                    quote = Quote::Double;
+                    quote_start = i;
+                }
+            }
+            b'`' => {
+                let end = find_closing(bytes, i + 1, b'`', b'`').unwrap_or(bytes.len());
+                findings.push("command_substitution", "high", i, end, "Backticks execute the enclosed command");
+                i = end;
+                continue;
+            }
+            b'$' => match next {
+                Some(b'(') if bytes.get(i + 2) == Some(&b'(') => {
+                    let end = find_closing(bytes, i + 2, b'(', b')').unwrap_or(bytes.len());
+                    findings.push("arithmetic_expansion", "low", i, end, "Arithmetic expansion evaluates the enclosed expression");
+                    i = end;
+                    continue;
+                }
+                Some(b'(') => {
+                    let end = find_closing(bytes, i + 2, b'(', b')').unwrap_or(bytes.len());
+                    findings.push("command_substitution", "high", i, end, "$(...) executes the enclosed command");
+                    i = end;
+                    continue;
+                }
+                Some(b'{') => {
+                    let end = find_closing(bytes, i + 2, b'{', b'}').unwrap_or(bytes.len());
+                    if quote == Quote::None {
+                        findings.push("unquoted_variable", "medium", i, end, "Unquoted expansion is subject to word splitting and globbing");
+                    }
+                    i = end;
+                    continue;
+                }
+                Some(c) if c == b'_' || c.is_ascii_alphanumeric() || b"@*#?$!-".contains(&c) => {
+                    let mut end = i + 2;
+                    if c == b'_' || c.is_ascii_alphabetic() {
+                        while end < bytes.len() && (bytes[end] == b'_' || bytes[end].is_ascii_alphanumeric()) {
+                            end += 1;
+                        }
+                    }
+                    if quote == Quote::None {
+                        findings.push("unquoted_variable", "medium", i, end, "Unquoted expansion is subject to word splitting and globbing");
+                    }
+                    i = end;
+                    continue;
+                }
+                _ => {}
+            },
+            _ if quote == Quote::Double => {}
+            b';' | b'\n' => {
+                findings.push("command_separator", "medium", i, i + 1, "Starts another command");
+                command_start = true;
+            }
+            b'&' | b'|' if next == Some(b) => {
+                findings.push("command_chaining", "medium", i, i + 2, "Runs another command conditionally");
+                command_start = true;
+                i += 2;
+                continue;
+            }
+            b'|' => {
+                findings.push("pipe", "medium", i, i + 1, "Pipes output into another command");
+                command_start = true;
+            }
+            b'&' if next != Some(b'>') => {
+                findings.push("background", "medium", i, i + 1, "Runs the command in the background and starts another");
+                command_start = true;
+            }
+            b'<' | b'>' if next == Some(b'(') => {
+                let end = find_closing(bytes, i + 2, b'(', b')').unwrap_or(bytes.len());
+                findings.push("process_substitution", "high", i, end, "Process substitution executes the enclosed command");
+                i = end;
+                continue;
+            }
+            b'<' | b'>' | b'&' => {
+                let mut end = i + 1;
+                while end < bytes.len() && matches!(bytes[end], b'>' | b'&' | b'|') {
+                    end += 1;
+                }
+                findings.push("redirection", "low", i, end, "Redirects input or output to a file");
+                i = end;
+                continue;
+            }
+            b'*' | b'?' | b'[' => {
+                findings.push("unquoted_glob", "medium", i, i + 1, "Unquoted glob expands to matching file names");
+            }
+            b'(' => command_start = true,
+            c if command_start && !c.is_ascii_whitespace() => {
+                let end = command[i..]
+                    .find(|ch: char| ch.is_ascii_whitespace() || ";&|()".contains(ch))
+                    .map_or(bytes.len(), |len| i + len);
+                if &command[i..end] == "eval" {
+                    findings.push("eval", "high", i, end, "eval executes its arguments as shell code");
+                }
+                command_start = false;
+                i = end;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    if quote != Quote::None {
+        findings.push("unterminated_quote", "high", quote_start, bytes.len(), "Quote is never closed");
+    }
+}
+
+fn analyze_cmd(command: &str, findings: &mut Findings) {
+    let bytes = command.as_bytes();
+    let mut in_quotes = false;
+    let mut quote_start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        let next = bytes.get(i + 1).copied();
+
+        match b {
+            // %VAR% and !VAR! are expanded even inside double quotes
+            b'%' | b'!' => {
+                if let Some(len) = command[i + 1..].find(b as char) {
+                    let name = &command[i + 1..i + 1 + len];
+                    if !name.is_empty() && !name.contains(char::is_whitespace) {
+                        let end = i + len + 2;
+                        findings.push("variable_expansion", "medium", i, end, "Variable is expanded before the command runs");
+                        i = end;
+                        continue;
+                    }
+                }
+            }
+            b'"' => {
+                in_quotes = !in_quotes;
+                quote_start = i;
+            }
+            _ if in_quotes => {}
+            b'^' => {
+                i = skip_char(command, i + 1);
+                continue;
+            }
+            b'\n' => findings.push("command_separator", "medium", i, i + 1, "Starts another command"),
+            b'&' | b'|' if next == Some(b) => {
+                findings.push("command_chaining", "medium", i, i + 2, "Runs another command conditionally");
+                i += 2;
+                continue;
+            }
+            b'&' => findings.push("command_separator", "medium", i, i + 1, "Starts another command"),
+            b'|' => findings.push("pipe", "medium", i, i + 1, "Pipes output into another command"),
+            b'<' | b'>' => {
+                let end = if next == Some(b'>') { i + 2 } else { i + 1 };
+                findings.push("redirection", "low", i, end, "Redirects input or output to a file");
+                i = end;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    if in_quotes {
+        findings.push("unterminated_quote", "high", quote_start, bytes.len(), "Quote is never closed");
+    }
+}
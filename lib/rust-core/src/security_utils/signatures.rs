@@ -0,0 +1,147 @@
+//! Ed25519 signatures for manifests and release artifacts
+//!
+//! Keys and signatures cross the JavaScript boundary as lowercase hex: a
+//! 32-byte secret seed, a 32-byte public key and a 64-byte signature. Files
+//! are signed over their raw bytes (plain Ed25519, not the pre-hashed
+//! variant), so signatures interoperate with other standard tooling.
+//! Verification uses the strict rules, rejecting malleable signatures and
+//! weak public keys.
+
+use crate::errors::{self, ErrorCode};
+use crate::hashing::{from_hex, to_hex};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use napi::bindgen_prelude::Buffer;
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Freshly generated Ed25519 key pair
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ed25519KeyPair {
+    /// Hex-encoded 32-byte public key
+    pub public_key: String,
+    /// Hex-encoded 32-byte secret seed (keep private)
+    pub secret_key: String,
+}
+
+/// Generate a new Ed25519 key pair from OS randomness
+#[napi]
+pub fn generate_ed25519_keypair() -> napi::Result<Ed25519KeyPair> {
+    errors::guard("generateEd25519Keypair", || {
+        let mut seed = [0u8; 32];
+        getrandom::getrandom(&mut seed).map_err(|e| {
+            errors::new(ErrorCode::Internal, format!("Failed to gather randomness: {}", e))
+        })?;
+        let signing_key = SigningKey::from_bytes(&seed);
+        seed.fill(0);
+
+        Ok(Ed25519KeyPair {
+            public_key: to_hex(signing_key.verifying_key().as_bytes()),
+            secret_key: to_hex(signing_key.as_bytes()),
+        })
+    })
+}
+
+/// Derive the public key for a secret seed
+///
+/// # Arguments
+/// * `secret_key` - Hex-encoded 32-byte secret seed
+#[napi]
+pub fn ed25519_public_key(secret_key: String) -> napi::Result<String> {
+    errors::guard("ed25519PublicKey", || {
+        let signing_key = parse_signing_key(&secret_key)?;
+        Ok(to_hex(signing_key.verifying_key().as_bytes()))
+    })
+}
+
+/// Sign a buffer
+///
+/// # Arguments
+/// * `secret_key` - Hex-encoded 32-byte secret seed
+/// * `message` - Bytes to sign
+#[napi]
+pub fn ed25519_sign(secret_key: String, message: Buffer) -> napi::Result<String> {
+    errors::guard("ed25519Sign", || {
+        let signing_key = parse_signing_key(&secret_key)?;
+        Ok(to_hex(&signing_key.sign(&message).to_bytes()))
+    })
+}
+
+/// Verify a signature over a buffer
+///
+/// Returns false when the signature does not match; malformed keys or
+/// signatures are reported as errors.
+///
+/// # Arguments
+/// * `public_key` - Hex-encoded 32-byte public key
+/// * `message` - Bytes that were signed
+/// * `signature` - Hex-encoded 64-byte signature
+#[napi]
+pub fn ed25519_verify(public_key: String, message: Buffer, signature: String) -> napi::Result<bool> {
+    errors::guard("ed25519Verify", || {
+        verify_bytes(&public_key, &message, &signature)
+    })
+}
+
+/// Sign a file's contents
+///
+/// # Arguments
+/// * `secret_key` - Hex-encoded 32-byte secret seed
+/// * `path` - File to sign
+#[napi]
+pub fn ed25519_sign_file(secret_key: String, path: String) -> napi::Result<String> {
+    errors::guard("ed25519SignFile", || {
+        let signing_key = parse_signing_key(&secret_key)?;
+        let contents = read_artifact(&path)?;
+        Ok(to_hex(&signing_key.sign(&contents).to_bytes()))
+    })
+}
+
+/// Verify a signature over a file's contents
+///
+/// # Arguments
+/// * `public_key` - Hex-encoded 32-byte public key
+/// * `path` - File that was signed
+/// * `signature` - Hex-encoded 64-byte signature
+#[napi]
+pub fn ed25519_verify_file(public_key: String, path: String, signature: String) -> napi::Result<bool> {
+    errors::guard("ed25519VerifyFile", || {
+        let contents = read_artifact(&path)?;
+        verify_bytes(&public_key, &contents, &signature)
+    })
+}
+
+fn verify_bytes(public_key: &str, message: &[u8], signature: &str) -> napi::Result<bool> {
+    let verifying_key = parse_verifying_key(public_key)?;
+    let signature = Signature::from_bytes(&decode_fixed::<64>(signature, "signature")?);
+    Ok(verifying_key.verify_strict(message, &signature).is_ok())
+}
+
+pub(crate) fn parse_signing_key(secret_key: &str) -> napi::Result<SigningKey> {
+    Ok(SigningKey::from_bytes(&decode_fixed::<32>(secret_key, "secret key")?))
+}
+
+fn parse_verifying_key(public_key: &str) -> napi::Result<VerifyingKey> {
+    VerifyingKey::from_bytes(&decode_fixed::<32>(public_key, "public key")?).map_err(|e| {
+        errors::new(ErrorCode::InvalidArgument, format!("Invalid public key: {}", e))
+    })
+}
+
+/// Decode a hex value that must be exactly `N` bytes long
+fn decode_fixed<const N: usize>(hex: &str, what: &str) -> napi::Result<[u8; N]> {
+    from_hex(hex.trim())
+        .and_then(|bytes| <[u8; N]>::try_from(bytes).ok())
+        .ok_or_else(|| {
+            errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Invalid {}: expected {} hex-encoded bytes", what, N),
+            )
+        })
+}
+
+fn read_artifact(path: &str) -> napi::Result<Vec<u8>> {
+    fs::read(path).map_err(|e| {
+        errors::io(&e, format!("Failed to read {}: {}", path, e))
+    })
+}
@@ -0,0 +1,472 @@
+//! Trojan-source and Unicode spoofing detection
+//!
+//! Finds characters that make source render differently from how it is
+//! parsed: bidirectional controls (including embeddings and isolates left
+//! open at the end of a line, which reorder the rest of it), invisible
+//! characters that split or hide identifiers, and homoglyphs from other
+//! scripts mixed into otherwise Latin identifiers.
+
+#[cfg(all(feature = "fs", feature = "text"))]
+use super::read_text_file;
+#[cfg(all(feature = "fs", feature = "text"))]
+use crate::concurrency;
+use crate::errors::{self, ErrorCode};
+#[cfg(all(feature = "fs", feature = "text"))]
+use crate::file_search::{FileSearch, FileSearchConfig};
+#[cfg(all(feature = "fs", feature = "text"))]
+use crate::kernels::LineIndex;
+use napi_derive::napi;
+#[cfg(all(feature = "fs", feature = "text"))]
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+#[cfg(all(feature = "fs", feature = "text"))]
+use std::path::Path;
+
+/// All supported check names
+const CHECKS: [&str; 3] = ["bidi", "invisible", "confusable"];
+
+/// Bidirectional formatting characters: (char, name)
+const BIDI_CONTROLS: &[(char, &str)] = &[
+    ('\u{202A}', "LEFT-TO-RIGHT EMBEDDING"),
+    ('\u{202B}', "RIGHT-TO-LEFT EMBEDDING"),
+    ('\u{202C}', "POP DIRECTIONAL FORMATTING"),
+    ('\u{202D}', "LEFT-TO-RIGHT OVERRIDE"),
+    ('\u{202E}', "RIGHT-TO-LEFT OVERRIDE"),
+    ('\u{2066}', "LEFT-TO-RIGHT ISOLATE"),
+    ('\u{2067}', "RIGHT-TO-LEFT ISOLATE"),
+    ('\u{2068}', "FIRST STRONG ISOLATE"),
+    ('\u{2069}', "POP DIRECTIONAL ISOLATE"),
+    ('\u{200E}', "LEFT-TO-RIGHT MARK"),
+    ('\u{200F}', "RIGHT-TO-LEFT MARK"),
+    ('\u{061C}', "ARABIC LETTER MARK"),
+];
+
+/// Characters that render as nothing (or as blank space) in most editors
+const INVISIBLE_CHARS: &[(char, &str)] = &[
+    ('\u{00AD}', "SOFT HYPHEN"),
+    ('\u{115F}', "HANGUL CHOSEONG FILLER"),
+    ('\u{1160}', "HANGUL JUNGSEONG FILLER"),
+    ('\u{180E}', "MONGOLIAN VOWEL SEPARATOR"),
+    ('\u{200B}', "ZERO WIDTH SPACE"),
+    ('\u{200C}', "ZERO WIDTH NON-JOINER"),
+    ('\u{200D}', "ZERO WIDTH JOINER"),
+    ('\u{2060}', "WORD JOINER"),
+    ('\u{2061}', "FUNCTION APPLICATION"),
+    ('\u{2062}', "INVISIBLE TIMES"),
+    ('\u{2063}', "INVISIBLE SEPARATOR"),
+    ('\u{2064}', "INVISIBLE PLUS"),
+    ('\u{3164}', "HANGUL FILLER"),
+    ('\u{FEFF}', "ZERO WIDTH NO-BREAK SPACE"),
+    ('\u{FFA0}', "HALFWIDTH HANGUL FILLER"),
+];
+
+/// Non-Latin letters that render like ASCII letters: (char, lookalike)
+const CONFUSABLES: &[(char, char)] = &[
+    // Cyrillic lowercase
+    ('\u{0430}', 'a'), ('\u{0435}', 'e'), ('\u{043E}', 'o'), ('\u{0440}', 'p'),
+    ('\u{0441}', 'c'), ('\u{0443}', 'y'), ('\u{0445}', 'x'), ('\u{0455}', 's'),
+    ('\u{0456}', 'i'), ('\u{0458}', 'j'), ('\u{04BB}', 'h'), ('\u{0501}', 'd'),
+    // Cyrillic uppercase
+    ('\u{0410}', 'A'), ('\u{0412}', 'B'), ('\u{0415}', 'E'), ('\u{041A}', 'K'),
+    ('\u{041C}', 'M'), ('\u{041D}', 'H'), ('\u{041E}', 'O'), ('\u{0420}', 'P'),
+    ('\u{0421}', 'C'), ('\u{0422}', 'T'), ('\u{0425}', 'X'), ('\u{0405}', 'S'),
+    ('\u{0406}', 'I'), ('\u{0408}', 'J'),
+    // Greek
+    ('\u{03B1}', 'a'), ('\u{03BF}', 'o'), ('\u{03BD}', 'v'), ('\u{03C1}', 'p'),
+    ('\u{0391}', 'A'), ('\u{0392}', 'B'), ('\u{0395}', 'E'), ('\u{0396}', 'Z'),
+    ('\u{0397}', 'H'), ('\u{0399}', 'I'), ('\u{039A}', 'K'), ('\u{039C}', 'M'),
+    ('\u{039D}', 'N'), ('\u{039F}', 'O'), ('\u{03A1}', 'P'), ('\u{03A4}', 'T'),
+    ('\u{03A5}', 'Y'), ('\u{03A7}', 'X'),
+];
+
+/// Punctuation that renders like ASCII syntax: (char, lookalike)
+const CONFUSABLE_PUNCTUATION: &[(char, char)] = &[
+    ('\u{037E}', ';'),
+    ('\u{01C3}', '!'),
+    ('\u{2215}', '/'),
+    ('\u{2044}', '/'),
+    ('\u{FF1B}', ';'),
+];
+
+/// Configuration for Unicode spoofing detection
+#[cfg(all(feature = "fs", feature = "text"))]
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnicodeSpoofConfig {
+    /// Checks to run: "bidi", "invisible", "confusable"
+    pub checks: Vec<String>,
+    /// Maximum number of findings to return (0 for unlimited)
+    pub max_findings: u32,
+    /// Traversal and exclusion settings for directory scans
+    pub search: Option<FileSearchConfig>,
+}
+
+#[cfg(all(feature = "fs", feature = "text"))]
+impl Default for UnicodeSpoofConfig {
+    fn default() -> Self {
+        Self {
+            checks: CHECKS.iter().map(|c| c.to_string()).collect(),
+            max_findings: 0,
+            search: None,
+        }
+    }
+}
+
+/// Suspicious character found in scanned content
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnicodeFinding {
+    /// File path (or the label passed for text scans)
+    pub path: String,
+    /// Finding kind: "bidi_control", "bidi_unterminated", "invisible_char", or "confusable"
+    pub kind: String,
+    /// Code point, e.g. "U+202E"
+    pub code_point: String,
+    /// Unicode character name (or lookalike description for confusables)
+    pub name: String,
+    /// Start byte offset in the content
+    pub start: u32,
+    /// End byte offset in the content
+    pub end: u32,
+    /// Line number (1-based)
+    pub line_number: u32,
+    /// Start byte column within the line
+    pub column_start: u32,
+    /// End byte column within the line
+    pub column_end: u32,
+    /// Severity: "critical", "high", "medium", or "low"
+    pub severity: String,
+    /// Explanation of the risk
+    pub message: String,
+}
+
+/// Finding before line/column resolution
+struct Candidate {
+    kind: &'static str,
+    ch: char,
+    name: String,
+    start: usize,
+    severity: &'static str,
+    message: String,
+}
+
+/// Unicode spoofing scanner
+///
+/// Needs the `fs` and `text` features; `is_invisible_char` is always available.
+#[cfg(all(feature = "fs", feature = "text"))]
+#[napi]
+pub struct UnicodeSpoofScanner {
+    config: UnicodeSpoofConfig,
+    search: FileSearch,
+}
+
+#[cfg(all(feature = "fs", feature = "text"))]
+#[napi]
+impl UnicodeSpoofScanner {
+    /// Create a new Unicode spoofing scanner with optional configuration
+    ///
+    /// # Arguments
+    /// * `config` - Optional configuration for the scanner
+    #[napi(constructor)]
+    pub fn new(config: Option<UnicodeSpoofConfig>) -> napi::Result<Self> {
+        errors::guard("new UnicodeSpoofScanner", || {
+            let config = config.unwrap_or_default();
+            if let Some(unknown) = config.checks.iter().find(|c| !CHECKS.contains(&c.as_str())) {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Unknown Unicode check: {}", unknown),
+                ));
+            }
+
+            Ok(Self {
+                search: FileSearch::new(config.search.clone())?,
+                config,
+            })
+        })
+    }
+
+    /// Scan text for spoofing characters
+    ///
+    /// # Arguments
+    /// * `text` - Content to scan
+    /// * `label` - Optional name reported as the finding path
+    #[napi]
+    pub fn scan_text(&self, text: String, label: Option<String>) -> napi::Result<Vec<UnicodeFinding>> {
+        errors::catch_panic("UnicodeSpoofScanner.scanText", || {
+            let mut findings = self.scan_content(&text, &label.unwrap_or_default());
+            self.truncate(&mut findings);
+            findings
+        })
+    }
+
+    /// Scan a single file (binary files are skipped)
+    #[napi]
+    pub fn scan_file(&self, path: String) -> napi::Result<Vec<UnicodeFinding>> {
+        errors::guard("UnicodeSpoofScanner.scanFile", || {
+            let mut findings = self.scan_path(Path::new(&path)).map_err(|e| {
+                errors::io(&e, format!("Failed to read {}: {}", path, e))
+            })?;
+            self.truncate(&mut findings);
+            Ok(findings)
+        })
+    }
+
+    /// Scan a directory tree in parallel
+    #[napi]
+    pub fn scan_directory(&self, root_path: String) -> napi::Result<Vec<UnicodeFinding>> {
+        errors::guard("UnicodeSpoofScanner.scanDirectory", || {
+            let _permit = concurrency::acquire("UnicodeSpoofScanner.scanDirectory")?;
+            let files = self.search.collect_files(Path::new(&root_path))?;
+            let use_parallel = self.config.search.as_ref().is_none_or(|search| search.use_parallel);
+
+            let mut findings: Vec<UnicodeFinding> = if use_parallel && files.len() > 100 {
+                files
+                    .par_iter()
+                    .flat_map(|path| self.scan_path(path).unwrap_or_default())
+                    .collect()
+            } else {
+                files
+                    .iter()
+                    .flat_map(|path| self.scan_path(path).unwrap_or_default())
+                    .collect()
+            };
+
+            findings.sort_by(|a, b| a.path.cmp(&b.path).then(a.start.cmp(&b.start)));
+            self.truncate(&mut findings);
+            Ok(findings)
+        })
+    }
+
+    /// Apply the `max_findings` limit
+    fn truncate(&self, findings: &mut Vec<UnicodeFinding>) {
+        if self.config.max_findings > 0 {
+            findings.truncate(self.config.max_findings as usize);
+        }
+    }
+
+    /// Whether a check is enabled
+    fn enabled(&self, check: &str) -> bool {
+        self.config.checks.iter().any(|c| c == check)
+    }
+
+    /// Read and scan one file, skipping binary content
+    fn scan_path(&self, path: &Path) -> std::io::Result<Vec<UnicodeFinding>> {
+        Ok(match read_text_file(path)? {
+            Some(text) => self.scan_content(&text, &path.to_string_lossy()),
+            None => Vec::new(),
+        })
+    }
+
+    /// Run enabled checks and resolve positions
+    fn scan_content(&self, text: &str, path: &str) -> Vec<UnicodeFinding> {
+        // Fast path: the checks only concern non-ASCII characters
+        if text.is_ascii() {
+            return Vec::new();
+        }
+
+        let mut candidates = Vec::new();
+        if self.enabled("bidi") {
+            bidi_candidates(text, &mut candidates);
+        }
+        if self.enabled("invisible") {
+            invisible_candidates(text, &mut candidates);
+        }
+        if self.enabled("confusable") {
+            confusable_candidates(text, &mut candidates);
+        }
+        candidates.sort_by_key(|c| c.start);
+
+        let lines = LineIndex::new(text);
+        candidates
+            .into_iter()
+            .map(|c| {
+                let end = c.start + c.ch.len_utf8();
+                let (line_number, column_start) = lines.position(c.start);
+                UnicodeFinding {
+                    path: path.to_string(),
+                    kind: c.kind.to_string(),
+                    code_point: format!("U+{:04X}", c.ch as u32),
+                    name: c.name,
+                    start: c.start as u32,
+                    end: end as u32,
+                    line_number,
+                    column_start,
+                    column_end: column_start + c.ch.len_utf8() as u32,
+                    severity: c.severity.to_string(),
+                    message: c.message,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Report bidi controls, and embeddings or isolates still open at the end of a line
+fn bidi_candidates(text: &str, candidates: &mut Vec<Candidate>) {
+    // Offsets of unclosed embeddings/overrides and isolates on the current line
+    let mut embeddings: Vec<(usize, char)> = Vec::new();
+    let mut isolates: Vec<(usize, char)> = Vec::new();
+
+    for (start, ch) in text.char_indices() {
+        match ch {
+            '\n' => flush_unterminated(&mut embeddings, &mut isolates, candidates),
+            '\u{202A}' | '\u{202B}' | '\u{202D}' | '\u{202E}' => embeddings.push((start, ch)),
+            '\u{202C}' => {
+                embeddings.pop();
+            }
+            '\u{2066}' | '\u{2067}' | '\u{2068}' => isolates.push((start, ch)),
+            '\u{2069}' => {
+                isolates.pop();
+            }
+            _ => {}
+        }
+
+        if let Some((_, name)) = BIDI_CONTROLS.iter().find(|(c, _)| *c == ch) {
+            let is_mark = matches!(ch, '\u{200E}' | '\u{200F}' | '\u{061C}');
+            candidates.push(Candidate {
+                kind: "bidi_control",
+                ch,
+                name: name.to_string(),
+                start,
+                severity: if is_mark { "low" } else { "high" },
+                message: if is_mark {
+                    "Directional mark changes how neighbouring text is ordered".to_string()
+                } else {
+                    "Bidirectional control can make code render differently from how it is parsed".to_string()
+                },
+            });
+        }
+    }
+    flush_unterminated(&mut embeddings, &mut isolates, candidates);
+}
+
+/// Report the embeddings and isolates left open at the end of a line
+fn flush_unterminated(
+    embeddings: &mut Vec<(usize, char)>,
+    isolates: &mut Vec<(usize, char)>,
+    candidates: &mut Vec<Candidate>,
+) {
+    for (start, ch) in embeddings.drain(..).chain(isolates.drain(..)) {
+        candidates.push(Candidate {
+            kind: "bidi_unterminated",
+            ch,
+            name: bidi_name(ch).to_string(),
+            start,
+            severity: "critical",
+            message: "Directional formatting is not closed on this line and reorders the rest of it".to_string(),
+        });
+    }
+}
+
+fn bidi_name(ch: char) -> &'static str {
+    BIDI_CONTROLS.iter().find(|(c, _)| *c == ch).map_or("", |(_, name)| name)
+}
+
+/// Report invisible characters, except a leading BOM and joiners inside emoji sequences
+fn invisible_candidates(text: &str, candidates: &mut Vec<Candidate>) {
+    let mut previous: Option<char> = None;
+    for (start, ch) in text.char_indices() {
+        let invisible = INVISIBLE_CHARS.iter().find(|(c, _)| *c == ch).map(|(_, name)| *name);
+        let tag = ('\u{E0000}'..='\u{E007F}').contains(&ch);
+
+        let benign = (ch == '\u{FEFF}' && start == 0) || (ch == '\u{200D}' && previous.is_some_and(is_emoji));
+        if (invisible.is_some() || tag) && !benign {
+            candidates.push(Candidate {
+                kind: "invisible_char",
+                ch,
+                name: invisible.unwrap_or("TAG CHARACTER").to_string(),
+                start,
+                severity: "medium",
+                message: "Invisible character can hide text or make identifiers look identical".to_string(),
+            });
+        }
+        previous = Some(ch);
+    }
+}
+
+/// Whether a character is a bidi control, an invisible character or a tag character
+pub(crate) fn is_invisible_char(ch: char) -> bool {
+    BIDI_CONTROLS.iter().chain(INVISIBLE_CHARS).any(|(c, _)| *c == ch) || ('\u{E0000}'..='\u{E007F}').contains(&ch)
+}
+
+fn is_emoji(ch: char) -> bool {
+    ('\u{1F000}'..='\u{1FAFF}').contains(&ch) || ('\u{2600}'..='\u{27BF}').contains(&ch) || ch == '\u{FE0F}'
+}
+
+/// Report homoglyphs in identifiers that mix them with ASCII letters (or consist only of them)
+fn confusable_candidates(text: &str, candidates: &mut Vec<Candidate>) {
+    let lookalike = |ch: char| CONFUSABLES.iter().find(|(c, _)| *c == ch).map(|(_, l)| *l);
+
+    for (start, ch) in text.char_indices() {
+        let punctuation = CONFUSABLE_PUNCTUATION.iter().find(|(c, _)| *c == ch).map(|(_, l)| *l);
+        let fullwidth = ('\u{FF01}'..='\u{FF5E}').contains(&ch);
+        if let Some(similar) = punctuation.or_else(|| fullwidth.then(|| char::from_u32(ch as u32 - 0xFEE0).unwrap_or('?'))) {
+            candidates.push(Candidate {
+                kind: "confusable",
+                ch,
+                name: format!("looks like '{}'", similar),
+                start,
+                severity: "medium",
+                message: format!("Character renders like ASCII '{}' but is parsed differently", similar),
+            });
+        }
+    }
+
+    // Identifier-like runs of letters, digits and underscores
+    let mut token_start = None;
+    let mut boundaries: Vec<(usize, usize)> = Vec::new();
+    for (i, ch) in text.char_indices() {
+        let word = ch.is_alphanumeric() || ch == '_';
+        match (word, token_start) {
+            (true, None) => token_start = Some(i),
+            (false, Some(start)) => {
+                boundaries.push((start, i));
+                token_start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = token_start {
+        boundaries.push((start, text.len()));
+    }
+
+    for (start, end) in boundaries {
+        let token = &text[start..end];
+        if token.is_ascii() {
+            continue;
+        }
+        let has_ascii_letter = token.chars().any(|c| c.is_ascii_alphabetic());
+        let all_lookalikes = token
+            .chars()
+            .filter(|c| c.is_alphabetic())
+            .all(|c| c.is_ascii_alphabetic() || lookalike(c).is_some());
+        if !(has_ascii_letter || all_lookalikes) {
+            continue;
+        }
+
+        for (offset, ch) in token.char_indices() {
+            if let Some(similar) = lookalike(ch) {
+                candidates.push(Candidate {
+                    kind: "confusable",
+                    ch,
+                    name: format!("looks like '{}'", similar),
+                    start: start + offset,
+                    severity: "high",
+                    message: format!(
+                        "'{}' in identifier '{}' is not ASCII '{}' and can impersonate another name",
+                        ch, token, similar
+                    ),
+                });
+            }
+        }
+    }
+}
+
+/// Quick Unicode spoofing scan over text with all checks enabled
+#[cfg(all(feature = "fs", feature = "text"))]
+#[napi]
+pub fn quick_scan_unicode_spoofing(text: String) -> napi::Result<Vec<UnicodeFinding>> {
+    errors::guard("quickScanUnicodeSpoofing", || {
+        let scanner = UnicodeSpoofScanner::new(None)?;
+        scanner.scan_text(text, None)
+    })
+}
@@ -0,0 +1,430 @@
+//! Windows path parsing and validation independent of the host platform
+//!
+//! `std::path` only understands Windows syntax when compiled for Windows, so
+//! paths are parsed here by hand: drive letters, UNC shares, `\\?\` verbatim
+//! and `\\.\` device prefixes, reserved device names and alternate data
+//! streams. Containment checks compare prefixes and components
+//! case-insensitively, the way NTFS resolves names.
+
+use crate::errors::{self, ErrorCode};
+use super::{resolve_existing, PathPolicy, PathValidationResult};
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Device names Win32 reserves in every directory, with or without an extension
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "CONIN$", "CONOUT$",
+    "COM0", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "COM¹", "COM²", "COM³",
+    "LPT0", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+    "LPT¹", "LPT²", "LPT³",
+];
+
+/// Structure of a path interpreted with Windows semantics
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowsPathInfo {
+    /// Prefix kind: "relative", "rooted", "drive_relative", "drive", "unc", or "device"
+    pub prefix_kind: String,
+    /// Rendered prefix (e.g. "C:" or "\\server\share"), if any
+    pub prefix: Option<String>,
+    /// Whether the path uses the `\\?\` verbatim form
+    pub verbatim: bool,
+    /// Whether the path is fully qualified (does not depend on process state)
+    pub is_absolute: bool,
+    /// Path components after the prefix
+    pub components: Vec<String>,
+    /// Components that name a reserved device (e.g. "NUL.txt")
+    pub reserved_names: Vec<String>,
+    /// Alternate data stream suffixes (e.g. "secret:$DATA")
+    pub alternate_streams: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum WindowsPrefix {
+    /// `foo\bar`
+    Relative,
+    /// `\foo`, relative to the root of the current drive
+    Rooted,
+    /// `C:foo`, relative to the current directory of drive C
+    DriveRelative(char),
+    /// `C:\foo`
+    Drive(char),
+    /// `\\server\share\foo`
+    Unc { server: String, share: String },
+    /// `\\.\PhysicalDrive0` or `\\?\Volume{...}`
+    Device(String),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct WindowsPath {
+    pub prefix: WindowsPrefix,
+    pub verbatim: bool,
+    pub components: Vec<String>,
+}
+
+impl WindowsPath {
+    /// Parse a path, accepting both `\` and `/` as separators outside verbatim paths
+    pub(crate) fn parse(path: &str) -> Self {
+        if let Some(rest) = path.strip_prefix(r"\\?\").or_else(|| path.strip_prefix(r"\??\")) {
+            // Verbatim paths are passed to the kernel as-is: only '\' separates
+            let mut parts = rest.split('\\');
+            let first = parts.next().unwrap_or("");
+            let prefix = if first.eq_ignore_ascii_case("UNC") {
+                WindowsPrefix::Unc {
+                    server: parts.next().unwrap_or("").to_string(),
+                    share: parts.next().unwrap_or("").to_string(),
+                }
+            } else if let Some(letter) = drive_letter(first).filter(|_| first.len() == 2) {
+                WindowsPrefix::Drive(letter)
+            } else {
+                WindowsPrefix::Device(first.to_string())
+            };
+            return Self {
+                prefix,
+                verbatim: true,
+                components: parts.filter(|p| !p.is_empty()).map(str::to_string).collect(),
+            };
+        }
+
+        let unified = path.replace('/', "\\");
+        let (prefix, rest) = if let Some(rest) = unified.strip_prefix(r"\\.\") {
+            let (device, rest) = rest.split_once('\\').unwrap_or((rest, ""));
+            (WindowsPrefix::Device(device.to_string()), rest.to_string())
+        } else if let Some(rest) = unified.strip_prefix(r"\\") {
+            let mut parts = rest.splitn(3, '\\');
+            let server = parts.next().unwrap_or("").to_string();
+            let share = parts.next().unwrap_or("").to_string();
+            (WindowsPrefix::Unc { server, share }, parts.next().unwrap_or("").to_string())
+        } else if let Some(letter) = drive_letter(&unified) {
+            let rest = &unified[2..];
+            match rest.strip_prefix('\\') {
+                Some(rest) => (WindowsPrefix::Drive(letter), rest.to_string()),
+                None => (WindowsPrefix::DriveRelative(letter), rest.to_string()),
+            }
+        } else if let Some(rest) = unified.strip_prefix('\\') {
+            (WindowsPrefix::Rooted, rest.to_string())
+        } else {
+            (WindowsPrefix::Relative, unified.clone())
+        };
+
+        Self {
+            prefix,
+            verbatim: false,
+            components: rest.split('\\').filter(|p| !p.is_empty()).map(str::to_string).collect(),
+        }
+    }
+
+    /// Whether the path is fully qualified
+    pub(crate) fn is_absolute(&self) -> bool {
+        matches!(
+            self.prefix,
+            WindowsPrefix::Drive(_) | WindowsPrefix::Unc { .. } | WindowsPrefix::Device(_)
+        )
+    }
+
+    /// Resolve `.` and `..` and strip the trailing dots and spaces Win32 ignores
+    ///
+    /// Verbatim paths are left untouched, since the kernel does not normalize them.
+    fn normalized(&self) -> Self {
+        if self.verbatim {
+            return self.clone();
+        }
+
+        let mut components: Vec<String> = Vec::new();
+        for component in &self.components {
+            match component.as_str() {
+                "." => {}
+                ".." => {
+                    // `..` at the root stays at the root
+                    components.pop();
+                }
+                _ => {
+                    let trimmed = component.trim_end_matches(['.', ' ']);
+                    if !trimmed.is_empty() {
+                        components.push(trimmed.to_string());
+                    }
+                }
+            }
+        }
+        Self {
+            prefix: self.prefix.clone(),
+            verbatim: false,
+            components,
+        }
+    }
+
+    /// Render the path with backslash separators
+    pub(crate) fn render(&self) -> String {
+        let mut out = match &self.prefix {
+            WindowsPrefix::Relative => String::new(),
+            WindowsPrefix::Rooted => "\\".to_string(),
+            WindowsPrefix::DriveRelative(letter) => format!("{}:", letter),
+            WindowsPrefix::Drive(letter) => format!("{}:\\", letter),
+            WindowsPrefix::Unc { server, share } => format!(r"\\{}\{}\", server, share),
+            WindowsPrefix::Device(name) => format!(r"\\.\{}\", name),
+        };
+        if self.verbatim {
+            out = match &self.prefix {
+                WindowsPrefix::Unc { server, share } => format!(r"\\?\UNC\{}\{}\", server, share),
+                WindowsPrefix::Device(name) => format!(r"\\?\{}\", name),
+                _ => format!(r"\\?\{}", out),
+            };
+        }
+        out.push_str(&self.components.join("\\"));
+        out
+    }
+
+    fn prefix_kind(&self) -> &'static str {
+        match self.prefix {
+            WindowsPrefix::Relative => "relative",
+            WindowsPrefix::Rooted => "rooted",
+            WindowsPrefix::DriveRelative(_) => "drive_relative",
+            WindowsPrefix::Drive(_) => "drive",
+            WindowsPrefix::Unc { .. } => "unc",
+            WindowsPrefix::Device(_) => "device",
+        }
+    }
+}
+
+/// Drive letter of a path starting with `X:`
+fn drive_letter(path: &str) -> Option<char> {
+    let mut chars = path.chars();
+    match (chars.next(), chars.next()) {
+        (Some(letter), Some(':')) if letter.is_ascii_alphabetic() => Some(letter.to_ascii_uppercase()),
+        _ => None,
+    }
+}
+
+/// Whether a component names a reserved device, e.g. "nul", "COM1.txt" or "CON .log"
+pub(crate) fn is_reserved_name(component: &str) -> bool {
+    let stem = component.split(['.', ':']).next().unwrap_or("").trim_end_matches(' ');
+    RESERVED_NAMES.iter().any(|name| name.eq_ignore_ascii_case(stem))
+}
+
+/// Alternate data stream suffix of a component, e.g. "secret:$DATA" for "file.txt:secret:$DATA"
+fn alternate_stream(component: &str) -> Option<&str> {
+    component.split_once(':').map(|(_, stream)| stream)
+}
+
+/// Case-insensitive prefix equality (drive letters, server and share names)
+fn same_prefix(a: &WindowsPrefix, b: &WindowsPrefix) -> bool {
+    match (a, b) {
+        (WindowsPrefix::Drive(x), WindowsPrefix::Drive(y)) => x == y,
+        (
+            WindowsPrefix::Unc { server: s1, share: h1 },
+            WindowsPrefix::Unc { server: s2, share: h2 },
+        ) => s1.to_lowercase() == s2.to_lowercase() && h1.to_lowercase() == h2.to_lowercase(),
+        _ => false,
+    }
+}
+
+/// Whether `path` lies at or below `base`, comparing names case-insensitively
+fn contains(base: &WindowsPath, path: &WindowsPath) -> bool {
+    same_prefix(&base.prefix, &path.prefix)
+        && path.components.len() >= base.components.len()
+        && base
+            .components
+            .iter()
+            .zip(&path.components)
+            .all(|(b, p)| b.to_lowercase() == p.to_lowercase())
+}
+
+/// Fully qualify the base, resolving relative forms against the current directory
+fn qualify_base(base_path: &str) -> napi::Result<WindowsPath> {
+    let base = WindowsPath::parse(base_path);
+    if base.is_absolute() {
+        return Ok(base);
+    }
+
+    if !cfg!(windows) {
+        return Err(errors::new(
+            ErrorCode::InvalidArgument,
+            format!("Base path '{}' must be fully qualified", base_path),
+        ));
+    }
+
+    let current = WindowsPath::parse(&std::env::current_dir()?.to_string_lossy());
+    let components = match base.prefix {
+        WindowsPrefix::Rooted => base.components,
+        WindowsPrefix::Relative => current.components.into_iter().chain(base.components).collect(),
+        _ => {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Base path '{}' must be fully qualified", base_path),
+            ))
+        }
+    };
+    Ok(WindowsPath {
+        prefix: current.prefix,
+        verbatim: current.verbatim,
+        components,
+    })
+}
+
+/// Case-insensitive `Path::starts_with` for resolved Windows paths
+fn starts_with_ignore_case(path: &Path, base: &Path) -> bool {
+    let path = WindowsPath::parse(&path.to_string_lossy());
+    let base = WindowsPath::parse(&base.to_string_lossy());
+    contains(&base, &path)
+}
+
+/// Validate a path against the policy using Windows semantics
+pub(crate) fn validate(
+    policy: &PathPolicy,
+    path: &str,
+    base_path: &str,
+) -> napi::Result<PathValidationResult> {
+    let input = WindowsPath::parse(path);
+
+    match input.prefix {
+        WindowsPrefix::Device(_) => {
+            return Ok(PathValidationResult::invalid(
+                "device_path",
+                "Device namespace paths are not allowed".to_string(),
+            ))
+        }
+        WindowsPrefix::DriveRelative(letter) => {
+            return Ok(PathValidationResult::invalid(
+                "drive_relative",
+                format!("Drive-relative path '{}:' depends on per-drive state", letter),
+            ))
+        }
+        _ => {}
+    }
+    if input.prefix != WindowsPrefix::Relative && !policy.allow_absolute {
+        return Ok(PathValidationResult::invalid(
+            "absolute_path",
+            "Absolute paths are not allowed".to_string(),
+        ));
+    }
+
+    let has_dots = input.components.iter().any(|c| c == "." || c == "..");
+    if input.verbatim && has_dots {
+        return Ok(PathValidationResult::invalid(
+            "parent_component",
+            "Verbatim paths cannot contain '.' or '..' components".to_string(),
+        ));
+    }
+    if !policy.allow_parent_components && input.components.iter().any(|c| c == "..") {
+        return Ok(PathValidationResult::invalid(
+            "parent_component",
+            "Parent directory components are not allowed".to_string(),
+        ));
+    }
+
+    for component in &input.components {
+        if is_reserved_name(component) {
+            return Ok(PathValidationResult::invalid(
+                "reserved_name",
+                format!("Component '{}' names a reserved device", component),
+            ));
+        }
+        if alternate_stream(component).is_some() {
+            return Ok(PathValidationResult::invalid(
+                "alternate_stream",
+                format!("Component '{}' addresses an alternate data stream", component),
+            ));
+        }
+    }
+
+    // Join relative forms onto the base and normalize
+    let base = qualify_base(base_path)?.normalized();
+    let joined = match input.prefix {
+        WindowsPrefix::Relative => WindowsPath {
+            prefix: base.prefix.clone(),
+            verbatim: false,
+            components: base.components.iter().chain(&input.components).cloned().collect(),
+        },
+        WindowsPrefix::Rooted => WindowsPath {
+            prefix: base.prefix.clone(),
+            verbatim: false,
+            components: input.components.clone(),
+        },
+        _ => input,
+    };
+    let normalized = joined.normalized();
+
+    if !contains(&base, &normalized) {
+        return Ok(PathValidationResult::invalid(
+            "outside_base",
+            "Path traversal detected".to_string(),
+        ));
+    }
+
+    // Check the components below the base
+    for name in &normalized.components[base.components.len()..] {
+        if policy.max_component_length > 0 && name.len() > policy.max_component_length as usize {
+            return Ok(PathValidationResult::invalid(
+                "component_too_long",
+                format!("Component '{}' exceeds {} bytes", name, policy.max_component_length),
+            ));
+        }
+        if !policy.allow_hidden && name.starts_with('.') {
+            return Ok(PathValidationResult::invalid(
+                "hidden_component",
+                format!("Hidden component '{}' is not allowed", name),
+            ));
+        }
+        if policy.forbidden_components.iter().any(|f| f.to_lowercase() == name.to_lowercase()) {
+            return Ok(PathValidationResult::invalid(
+                "forbidden_component",
+                format!("Component '{}' is not allowed", name),
+            ));
+        }
+    }
+
+    // Symlinks and junctions can only be resolved on a Windows host
+    if !(policy.resolve_symlinks && cfg!(windows)) {
+        return Ok(PathValidationResult::valid(normalized.render()));
+    }
+
+    let real_base = resolve_existing(&PathBuf::from(base.render()));
+    let resolved = resolve_existing(&PathBuf::from(normalized.render()));
+    if !starts_with_ignore_case(&resolved, &real_base) {
+        return Ok(PathValidationResult::invalid(
+            "symlink_escape",
+            format!("Path resolves outside the base: {}", resolved.display()),
+        ));
+    }
+
+    Ok(PathValidationResult::valid(resolved.to_string_lossy().to_string()))
+}
+
+/// Describe how a path is interpreted under Windows semantics
+///
+/// Works on any host, which makes it suitable for checking paths that will
+/// be used on Windows machines (e.g. in archives or configuration files).
+#[napi]
+pub fn analyze_windows_path(path: String) -> napi::Result<WindowsPathInfo> {
+    errors::guard("analyzeWindowsPath", || {
+        let parsed = WindowsPath::parse(&path);
+
+        let prefix = match &parsed.prefix {
+            WindowsPrefix::Relative => None,
+            _ => {
+                let bare = WindowsPath {
+                    prefix: parsed.prefix.clone(),
+                    verbatim: parsed.verbatim,
+                    components: Vec::new(),
+                };
+                Some(bare.render().trim_end_matches('\\').to_string()).filter(|p| !p.is_empty())
+            }
+        };
+
+        Ok(WindowsPathInfo {
+            prefix_kind: parsed.prefix_kind().to_string(),
+            prefix,
+            verbatim: parsed.verbatim,
+            is_absolute: parsed.is_absolute(),
+            reserved_names: parsed.components.iter().filter(|c| is_reserved_name(c)).cloned().collect(),
+            alternate_streams: parsed
+                .components
+                .iter()
+                .filter_map(|c| alternate_stream(c).map(str::to_string))
+                .collect(),
+            components: parsed.components,
+        })
+    })
+}
@@ -0,0 +1,522 @@
+//! Process-wide state shared by every Node worker thread
+//!
+//! Node loads this library once per process, but each `worker_thread` gets
+//! its own copy of the exports: class instances (`FileSearch`,
+//! `VectorOperations`, ...) belong to the worker that created them and cannot
+//! be passed to another. Everything in this module lives in process-wide
+//! statics instead, so a multi-worker MCP server compiles each matcher and
+//! holds each vector set once, no matter how many workers use it.
+//!
+//! - Compiled globs, glob sets and regexes are cached by pattern. The modules
+//!   that compile patterns go through [`glob_matcher`], [`glob_set`] and
+//!   [`regex`], and lookups are reported to the `globs`, `glob_sets` and
+//!   `regexes` caches in `getMetrics()`.
+//! - Vector sets are stored under a name with `shareVectors` and searched from
+//!   any worker with `VectorOperations.findSimilarInShared`, so the vectors
+//!   are never copied into each worker's heap. A `VectorSetWriter` builds a
+//!   set in batches instead, merging them into the shared set in the
+//!   background.
+//!
+//! Values are handed out as `Arc`s: a lock is held only for the lookup, and
+//! dropping or replacing an entry never invalidates a search in progress.
+
+use crate::errors::{self, ErrorCode};
+use crate::metrics;
+use globset::{Glob, GlobMatcher, GlobSet, GlobSetBuilder};
+use napi_derive::napi;
+use parking_lot::{Mutex, RwLock};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Compiled patterns kept per cache before the oldest is evicted
+const MATCHER_CAPACITY: usize = 256;
+
+/// Pending vectors are merged once they reach 1/MERGE_RATIO of the shared
+/// set, so each merge copies the set for a proportional amount of new data
+const MERGE_RATIO: usize = 4;
+
+static GLOBS: MatcherCache<GlobMatcher> = MatcherCache::new("globs");
+static GLOB_SETS: MatcherCache<GlobSet> = MatcherCache::new("glob_sets");
+static REGEXES: MatcherCache<Regex> = MatcherCache::new("regexes");
+static VECTOR_SETS: RwLock<BTreeMap<String, Arc<VectorSet>>> = RwLock::new(BTreeMap::new());
+
+/// Bounded cache of compiled patterns, evicting in insertion order
+struct MatcherCache<V> {
+    name: &'static str,
+    entries: Mutex<CacheEntries<V>>,
+}
+
+struct CacheEntries<V> {
+    map: BTreeMap<String, Arc<V>>,
+    order: VecDeque<String>,
+}
+
+impl<V> MatcherCache<V> {
+    const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            entries: Mutex::new(CacheEntries {
+                map: BTreeMap::new(),
+                order: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Return the cached value for `key`, compiling it on a miss
+    ///
+    /// Compilation runs outside the lock; if two threads miss at once, the
+    /// first insert wins and both get the same value.
+    fn get_or_compile<E>(&self, key: &str, compile: impl FnOnce() -> Result<V, E>) -> Result<Arc<V>, E> {
+        if let Some(value) = self.entries.lock().map.get(key) {
+            metrics::record_cache(self.name, true);
+            return Ok(Arc::clone(value));
+        }
+        metrics::record_cache(self.name, false);
+
+        let value = Arc::new(compile()?);
+        let mut entries = self.entries.lock();
+        if let Some(existing) = entries.map.get(key) {
+            return Ok(Arc::clone(existing));
+        }
+        if entries.order.len() >= MATCHER_CAPACITY {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.map.remove(&oldest);
+            }
+        }
+        entries.order.push_back(key.to_string());
+        entries.map.insert(key.to_string(), Arc::clone(&value));
+        Ok(value)
+    }
+
+    fn len(&self) -> usize {
+        self.entries.lock().map.len()
+    }
+
+    fn clear(&self) {
+        let mut entries = self.entries.lock();
+        entries.map.clear();
+        entries.order.clear();
+    }
+}
+
+/// Compiled matcher for a single glob
+#[cfg(feature = "fs")]
+pub(crate) fn glob_matcher(pattern: &str) -> Result<Arc<GlobMatcher>, globset::Error> {
+    GLOBS.get_or_compile(pattern, || Ok(Glob::new(pattern)?.compile_matcher()))
+}
+
+/// Compiled set of globs, matched together
+#[cfg(feature = "security")]
+pub(crate) fn glob_set(patterns: &[String]) -> Result<Arc<GlobSet>, globset::Error> {
+    GLOB_SETS.get_or_compile(&patterns.join("\0"), || {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            builder.add(Glob::new(pattern)?);
+        }
+        builder.build()
+    })
+}
+
+/// Compiled regex
+#[cfg(feature = "text")]
+pub(crate) fn regex(pattern: &str) -> Result<Arc<Regex>, regex::Error> {
+    REGEXES.get_or_compile(pattern, || Regex::new(pattern))
+}
+
+/// Vectors stored once per process under a name
+#[derive(Debug)]
+pub(crate) struct VectorSet {
+    /// Flattened vectors, `count * vector_size` values
+    pub(crate) vectors: Vec<f32>,
+    pub(crate) vector_size: usize,
+    /// Path or identifier of each vector
+    pub(crate) paths: Vec<String>,
+}
+
+impl VectorSet {
+    fn info(&self, name: &str) -> SharedVectorSetInfo {
+        SharedVectorSetInfo {
+            name: name.to_string(),
+            count: self.paths.len() as u32,
+            vector_size: self.vector_size as u32,
+            bytes: (self.vectors.len() * std::mem::size_of::<f32>()) as f64,
+        }
+    }
+}
+
+/// Look up a shared vector set by name
+#[cfg(feature = "vector")]
+pub(crate) fn vector_set(name: &str) -> napi::Result<Arc<VectorSet>> {
+    VECTOR_SETS.read().get(name).cloned().ok_or_else(|| {
+        errors::new(ErrorCode::PathNotFound, format!("No shared vector set named '{}'", name))
+    })
+}
+
+/// A named vector set visible to every worker
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedVectorSetInfo {
+    /// Name the set was shared under
+    pub name: String,
+    /// Number of vectors
+    pub count: u32,
+    /// Size of each vector
+    pub vector_size: u32,
+    /// Memory held by the vectors in bytes
+    pub bytes: f64,
+}
+
+/// Sizes of the process-wide caches and stores
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedStateInfo {
+    /// Cached single-glob matchers
+    pub globs: u32,
+    /// Cached glob sets (exclude lists, sandbox denials)
+    pub glob_sets: u32,
+    /// Cached regexes
+    pub regexes: u32,
+    /// Shared vector sets
+    pub vector_sets: Vec<SharedVectorSetInfo>,
+}
+
+/// Store vectors under a name so every worker thread can search them
+///
+/// Vectors are kept as 32-bit floats. Sharing under an existing name replaces
+/// the previous set; searches already running keep the old one.
+/// To build a large set without one array holding every vector, use a
+/// `VectorSetWriter`.
+///
+/// # Arguments
+/// * `name` - Name to share the set under
+/// * `vectors_flat` - Flattened vectors (count * vector_size)
+/// * `vector_size` - Size of each vector
+/// * `paths` - Path or identifier of each vector
+#[napi]
+pub fn share_vectors(
+    name: String,
+    vectors_flat: Vec<f64>,
+    vector_size: u32,
+    paths: Vec<String>,
+) -> napi::Result<SharedVectorSetInfo> {
+    errors::guard("shareVectors", || {
+        let vector_size = vector_size as usize;
+        check_batch(&vectors_flat, vector_size, &paths)?;
+
+        let set = VectorSet {
+            vectors: vectors_flat.iter().map(|&x| x as f32).collect(),
+            vector_size,
+            paths,
+        };
+        let info = set.info(&name);
+        VECTOR_SETS.write().insert(name, Arc::new(set));
+        Ok(info)
+    })
+}
+
+/// Check a flattened batch has one vector of `vector_size` per path
+fn check_batch(vectors_flat: &[f64], vector_size: usize, paths: &[String]) -> napi::Result<()> {
+    if vector_size == 0 || !vectors_flat.len().is_multiple_of(vector_size) {
+        return Err(errors::new(
+            ErrorCode::InvalidArgument,
+            "Vectors array length must be a positive multiple of vector_size".to_string(),
+        ));
+    }
+    if vectors_flat.len() / vector_size != paths.len() {
+        return Err(errors::new(
+            ErrorCode::InvalidArgument,
+            "Number of vectors and paths must match".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Batches added to a `VectorSetWriter` and not merged yet
+#[derive(Default)]
+struct Pending {
+    vectors: Vec<f32>,
+    paths: Vec<String>,
+}
+
+/// State of a `VectorSetWriter`, shared with its background merges
+struct Ingest {
+    name: String,
+    vector_size: usize,
+    pending: Mutex<Pending>,
+    /// Held for a whole merge, so merges publish in the order they took batches
+    merging: Mutex<()>,
+    /// Whether batches were added that a merge has been asked for and no
+    /// merge has taken yet
+    scheduled: AtomicBool,
+    /// Error of a failed background merge, reported by the next call
+    failed: Mutex<Option<String>>,
+}
+
+impl Ingest {
+    /// Merge the pending batches, waiting for a merge in progress
+    fn merge(self: &Arc<Self>) -> Result<Arc<VectorSet>, String> {
+        let merged = {
+            let _merging = self.merging.lock();
+            self.merge_locked()
+        };
+        self.reschedule();
+        merged
+    }
+
+    /// Merge the pending batches on the thread pool
+    ///
+    /// Never blocks a pool thread: if a merge is in progress, the task
+    /// returns and that merge schedules another once it ends.
+    fn merge_in_background(self: Arc<Self>) {
+        rayon::spawn(move || {
+            let Some(merging) = self.merging.try_lock() else {
+                return;
+            };
+            let merged = self.merge_locked();
+            drop(merging);
+            if let Err(message) = merged {
+                *self.failed.lock() = Some(message);
+            }
+            self.reschedule();
+        });
+    }
+
+    /// Start a background merge for batches added during the last one
+    ///
+    /// Checked after the merge lock is released, so a task that found it
+    /// held is always followed by another.
+    fn reschedule(self: &Arc<Self>) {
+        if self.scheduled.load(Ordering::Acquire) {
+            Arc::clone(self).merge_in_background();
+        }
+    }
+
+    /// Publish the shared set with the pending batches appended; the caller
+    /// holds `merging`
+    ///
+    /// The new set is built outside the map lock. If the set is replaced
+    /// or dropped meanwhile, the batches are appended to whatever is
+    /// shared under the name now (or start a new set).
+    fn merge_locked(&self) -> Result<Arc<VectorSet>, String> {
+        let batch = {
+            let mut pending = self.pending.lock();
+            // Batches added from here on need another merge
+            self.scheduled.store(false, Ordering::Release);
+            std::mem::take(&mut *pending)
+        };
+        loop {
+            let base = VECTOR_SETS.read().get(&self.name).cloned();
+            let set = match &base {
+                Some(base) if batch.paths.is_empty() => return Ok(Arc::clone(base)),
+                Some(base) if base.vector_size != self.vector_size => {
+                    return Err(format!(
+                        "Shared vector set '{}' now holds {}-dimensional vectors; dropped {} pending vectors of {} dimensions",
+                        self.name,
+                        base.vector_size,
+                        batch.paths.len(),
+                        self.vector_size
+                    ));
+                }
+                Some(base) => {
+                    let mut vectors = Vec::with_capacity(base.vectors.len() + batch.vectors.len());
+                    vectors.extend_from_slice(&base.vectors);
+                    vectors.extend_from_slice(&batch.vectors);
+                    let mut paths = Vec::with_capacity(base.paths.len() + batch.paths.len());
+                    paths.extend_from_slice(&base.paths);
+                    paths.extend_from_slice(&batch.paths);
+                    VectorSet {
+                        vectors,
+                        vector_size: self.vector_size,
+                        paths,
+                    }
+                }
+                None => VectorSet {
+                    vectors: batch.vectors.clone(),
+                    vector_size: self.vector_size,
+                    paths: batch.paths.clone(),
+                },
+            };
+
+            let set = Arc::new(set);
+            let mut sets = VECTOR_SETS.write();
+            if sets.get(&self.name).map(Arc::as_ptr) == base.as_ref().map(Arc::as_ptr) {
+                sets.insert(self.name.clone(), Arc::clone(&set));
+                return Ok(set);
+            }
+        }
+    }
+
+    /// Error of the last failed background merge, if any
+    fn take_failure(&self) -> napi::Result<()> {
+        match self.failed.lock().take() {
+            Some(message) => Err(errors::new(ErrorCode::InvalidArgument, message)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Builds a shared vector set from batches
+///
+/// Each `addBatch` only copies its own vectors; batches are merged into the
+/// set shared under the name on the thread pool, in the order they were
+/// added, so the corpus never has to be held in one JavaScript array.
+/// Merging replaces the set like `shareVectors` does, so searches in
+/// progress keep the set they started with. Batches become searchable once
+/// merged: pending vectors are merged when they reach a quarter of the
+/// shared set, and `flush` merges the rest.
+#[napi]
+pub struct VectorSetWriter {
+    state: Arc<Ingest>,
+}
+
+#[napi]
+impl VectorSetWriter {
+    /// Start writing to a shared vector set
+    ///
+    /// An existing set with that name is appended to; otherwise an empty
+    /// set is shared.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the shared set
+    /// * `vector_size` - Size of each vector
+    #[napi(constructor)]
+    pub fn new(name: String, vector_size: u32) -> napi::Result<Self> {
+        errors::guard("new VectorSetWriter", || {
+            let vector_size = vector_size as usize;
+            if vector_size == 0 {
+                return Err(errors::new(ErrorCode::InvalidArgument, "vector_size must be positive"));
+            }
+            let mut sets = VECTOR_SETS.write();
+            match sets.get(&name) {
+                Some(set) if set.vector_size != vector_size => {
+                    return Err(errors::new(
+                        ErrorCode::InvalidArgument,
+                        format!(
+                            "Shared vector set '{}' holds {}-dimensional vectors, not {}",
+                            name, set.vector_size, vector_size
+                        ),
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    let set = VectorSet {
+                        vectors: Vec::new(),
+                        vector_size,
+                        paths: Vec::new(),
+                    };
+                    sets.insert(name.clone(), Arc::new(set));
+                }
+            }
+            Ok(Self {
+                state: Arc::new(Ingest {
+                    name,
+                    vector_size,
+                    pending: Mutex::new(Pending::default()),
+                    merging: Mutex::new(()),
+                    scheduled: AtomicBool::new(false),
+                    failed: Mutex::new(None),
+                }),
+            })
+        })
+    }
+
+    /// Add a batch of vectors
+    ///
+    /// Returns the number of vectors not merged yet. Fails if a background
+    /// merge failed since the last call (the set was replaced with one of
+    /// another vector size).
+    ///
+    /// # Arguments
+    /// * `ids` - Path or identifier of each vector
+    /// * `vectors_flat` - Flattened vectors (ids.length * vector_size)
+    #[napi]
+    pub fn add_batch(&self, ids: Vec<String>, vectors_flat: Vec<f64>) -> napi::Result<u32> {
+        errors::guard("VectorSetWriter.addBatch", || {
+            let state = &self.state;
+            state.take_failure()?;
+            check_batch(&vectors_flat, state.vector_size, &ids)?;
+
+            let shared = VECTOR_SETS.read().get(&state.name).map_or(0, |set| set.paths.len());
+            let pending = {
+                let mut pending = state.pending.lock();
+                pending.vectors.extend(vectors_flat.iter().map(|&x| x as f32));
+                pending.paths.extend(ids);
+                pending.paths.len()
+            };
+            if pending > 0 && pending >= shared / MERGE_RATIO && !state.scheduled.swap(true, Ordering::AcqRel) {
+                Arc::clone(state).merge_in_background();
+            }
+            Ok(pending as u32)
+        })
+    }
+
+    /// Merge every pending batch and return the shared set
+    ///
+    /// Waits for a background merge in progress.
+    #[napi]
+    pub fn flush(&self) -> napi::Result<SharedVectorSetInfo> {
+        errors::guard("VectorSetWriter.flush", || {
+            self.state.take_failure()?;
+            let set = self
+                .state
+                .merge()
+                .map_err(|message| errors::new(ErrorCode::InvalidArgument, message))?;
+            Ok(set.info(&self.state.name))
+        })
+    }
+
+    /// Vectors added and not merged yet
+    #[napi(getter)]
+    pub fn pending(&self) -> u32 {
+        self.state.pending.lock().paths.len() as u32
+    }
+
+    /// Name of the shared set
+    #[napi(getter)]
+    pub fn name(&self) -> String {
+        self.state.name.clone()
+    }
+
+    /// Size of each vector
+    #[napi(getter)]
+    pub fn vector_size(&self) -> u32 {
+        self.state.vector_size as u32
+    }
+}
+
+/// Remove a shared vector set
+///
+/// Returns `false` when no set had that name. Memory is released once no
+/// search is using the set.
+#[napi]
+pub fn drop_shared_vectors(name: String) -> bool {
+    VECTOR_SETS.write().remove(&name).is_some()
+}
+
+/// Describe the process-wide caches and shared vector sets
+#[napi]
+pub fn get_shared_state_info() -> SharedStateInfo {
+    SharedStateInfo {
+        globs: GLOBS.len() as u32,
+        glob_sets: GLOB_SETS.len() as u32,
+        regexes: REGEXES.len() as u32,
+        vector_sets: VECTOR_SETS
+            .read()
+            .iter()
+            .map(|(name, set)| set.info(name))
+            .collect(),
+    }
+}
+
+/// Empty the compiled pattern caches
+///
+/// Shared vector sets are kept; remove them with `dropSharedVectors`.
+#[napi]
+pub fn clear_matcher_caches() {
+    GLOBS.clear();
+    GLOB_SETS.clear();
+    REGEXES.clear();
+}
@@ -0,0 +1,160 @@
+//! Entries a `FileSearch` scan left out, and why
+//!
+//! Scans pass over entries they can't or shouldn't read: walk errors such as
+//! unreadable directories, symlink loops and exhausted walk budgets, files
+//! over the size limit, binary, non-UTF-8 or minified files in a text
+//! search, and entries the hidden-file, exclude, sandbox and language
+//! filters reject.
+//! With `report_skipped`, scan results that are objects carry a `SkipReport`
+//! listing them.
+//!
+//! Every skip is counted by reason, but only the first `MAX_SKIPPED_ENTRIES`
+//! are listed, since an excluded dependency directory alone can hold
+//! hundreds of thousands of files.
+
+use napi_derive::napi;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+/// Skipped entries listed per report; the rest are only counted
+const MAX_SKIPPED_ENTRIES: usize = 1000;
+
+/// Bytes of a file checked for NUL when telling binary from mis-encoded text
+const BINARY_PROBE_LEN: usize = 8 * 1024;
+
+/// An entry left out of a scan
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedEntry {
+    /// Path of the entry, as produced by the walk
+    pub path: String,
+    /// "permission_denied", "not_found", "symlink_loop", "symlink_depth",
+    /// "entry_budget", "io_error", "hidden", "excluded", "too_large",
+    /// "sandbox", "language", "binary", "decode_error" or "minified"
+    pub reason: String,
+    /// Specifics, such as the exclude pattern or the OS error
+    pub detail: Option<String>,
+}
+
+/// Entries a scan left out
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SkipReport {
+    /// The first skipped entries, sorted by path
+    pub entries: Vec<SkippedEntry>,
+    /// Entries skipped in total, including those not listed
+    pub total: u32,
+    /// Skipped entries per reason
+    pub by_reason: BTreeMap<String, u32>,
+}
+
+/// Why an entry was left out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SkipReason {
+    PermissionDenied,
+    NotFound,
+    SymlinkLoop,
+    SymlinkDepth,
+    EntryBudget,
+    IoError,
+    Hidden,
+    Excluded,
+    TooLarge,
+    Sandbox,
+    Language,
+    Binary,
+    DecodeError,
+    Minified,
+}
+
+impl SkipReason {
+    fn name(self) -> &'static str {
+        match self {
+            SkipReason::PermissionDenied => "permission_denied",
+            SkipReason::NotFound => "not_found",
+            SkipReason::SymlinkLoop => "symlink_loop",
+            SkipReason::SymlinkDepth => "symlink_depth",
+            SkipReason::EntryBudget => "entry_budget",
+            SkipReason::IoError => "io_error",
+            SkipReason::Hidden => "hidden",
+            SkipReason::Excluded => "excluded",
+            SkipReason::TooLarge => "too_large",
+            SkipReason::Sandbox => "sandbox",
+            SkipReason::Language => "language",
+            SkipReason::Binary => "binary",
+            SkipReason::DecodeError => "decode_error",
+            SkipReason::Minified => "minified",
+        }
+    }
+
+    /// Reason for a failed read or stat
+    fn of_io(error: &io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::PermissionDenied => SkipReason::PermissionDenied,
+            io::ErrorKind::NotFound => SkipReason::NotFound,
+            io::ErrorKind::InvalidData => SkipReason::DecodeError,
+            _ => SkipReason::IoError,
+        }
+    }
+}
+
+/// Collects the skips of one scan; shared by its worker threads
+pub(crate) struct SkipLog {
+    report: Mutex<SkipReport>,
+}
+
+impl SkipLog {
+    pub(crate) fn new() -> Self {
+        Self { report: Mutex::new(SkipReport::default()) }
+    }
+
+    /// Record that `path` was left out
+    pub(crate) fn record(&self, path: &Path, reason: SkipReason, detail: Option<String>) {
+        let mut report = self.report.lock();
+        report.total += 1;
+        *report.by_reason.entry(reason.name().to_string()).or_insert(0) += 1;
+        if report.entries.len() < MAX_SKIPPED_ENTRIES {
+            report.entries.push(SkippedEntry {
+                path: path.to_string_lossy().to_string(),
+                reason: reason.name().to_string(),
+                detail,
+            });
+        }
+    }
+
+    /// Record an entry the walk could not read
+    pub(crate) fn walk_error(&self, error: &walkdir::Error) {
+        let path = error.path().unwrap_or(Path::new(""));
+        if let Some(ancestor) = error.loop_ancestor() {
+            let detail = format!("links back to {}", ancestor.display());
+            self.record(path, SkipReason::SymlinkLoop, Some(detail));
+        } else {
+            let reason = error.io_error().map_or(SkipReason::IoError, SkipReason::of_io);
+            self.record(path, reason, Some(error.to_string()));
+        }
+    }
+
+    /// Record a file that could not be read
+    pub(crate) fn read_error(&self, path: &Path, error: &io::Error) {
+        self.record(path, SkipReason::of_io(error), Some(error.to_string()));
+    }
+
+    /// Record a file a text search could not decode as UTF-8
+    pub(crate) fn undecodable(&self, path: &Path, bytes: &[u8], error: &std::str::Utf8Error) {
+        if bytes[..bytes.len().min(BINARY_PROBE_LEN)].contains(&0) {
+            self.record(path, SkipReason::Binary, None);
+        } else {
+            self.record(path, SkipReason::DecodeError, Some(error.to_string()));
+        }
+    }
+
+    /// The report, with listed entries sorted by path
+    pub(crate) fn finish(self) -> SkipReport {
+        let mut report = self.report.into_inner();
+        report.entries.sort_by(|a, b| a.path.cmp(&b.path));
+        report
+    }
+}
@@ -0,0 +1,355 @@
+//! Deterministic synthetic workloads
+//!
+//! Generates file trees and embedding corpora from a seed, so benchmarks
+//! run on the same data on every machine instead of on whatever happens to
+//! be in the working directory. The same seed and configuration always
+//! produce byte-identical files and bit-identical vectors.
+
+use crate::atomic_write::{self, AtomicWriteOptions};
+use crate::errors::{self, ErrorCode};
+use crate::security_utils::quota::{self, Mutation};
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Languages the tree generator can write, with their file extensions
+const LANGUAGES: [(&str, &str); 6] = [
+    ("rust", "rs"),
+    ("javascript", "js"),
+    ("typescript", "ts"),
+    ("python", "py"),
+    ("go", "go"),
+    ("markdown", "md"),
+];
+
+/// Identifiers and prose words used to fill generated files
+const WORDS: [&str; 24] = [
+    "vector", "index", "search", "cache", "token", "buffer", "config", "result", "record", "stream",
+    "parser", "handle", "value", "entry", "batch", "limit", "scope", "query", "match", "score",
+    "merge", "split", "graph", "node",
+];
+
+/// Synthetic file tree settings
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyntheticTreeConfig {
+    /// Seed of the generator
+    pub seed: u32,
+    /// Number of files to write
+    pub file_count: u32,
+    /// Number of directories to spread the files over
+    pub directory_count: u32,
+    /// Deepest directory nesting below the root
+    pub max_depth: u32,
+    /// Smallest file size in bytes
+    pub min_file_size: u32,
+    /// Largest file size in bytes
+    pub max_file_size: u32,
+    /// Languages to write: "rust", "javascript", "typescript", "python", "go" and/or "markdown"
+    pub languages: Vec<String>,
+    /// Fraction of files that are exact copies of an earlier file, between 0 and 1
+    pub duplicate_ratio: f64,
+}
+
+impl Default for SyntheticTreeConfig {
+    fn default() -> Self {
+        Self {
+            seed: 42,
+            file_count: 200,
+            directory_count: 20,
+            max_depth: 3,
+            min_file_size: 256,
+            max_file_size: 16 * 1024,
+            languages: LANGUAGES.iter().map(|(language, _)| language.to_string()).collect(),
+            duplicate_ratio: 0.1,
+        }
+    }
+}
+
+/// What `generate_synthetic_tree` wrote
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyntheticTreeSummary {
+    /// Root directory of the tree
+    pub root: String,
+    /// Files written
+    pub files: u32,
+    /// Directories created below the root
+    pub directories: u32,
+    /// Bytes written
+    pub total_bytes: f64,
+    /// Files that copy an earlier file
+    pub duplicates: u32,
+    /// Files per language
+    pub files_by_language: HashMap<String, u32>,
+}
+
+/// Synthetic embedding corpus settings
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyntheticEmbeddingConfig {
+    /// Seed of the generator
+    pub seed: u32,
+    /// Number of vectors
+    pub count: u32,
+    /// Dimensions of each vector
+    pub dimensions: u32,
+    /// Number of clusters the vectors are drawn around
+    pub clusters: u32,
+    /// Standard deviation of the noise added to the cluster centers
+    pub noise: f64,
+    /// Scale every vector to unit length
+    pub normalize: bool,
+}
+
+impl Default for SyntheticEmbeddingConfig {
+    fn default() -> Self {
+        Self {
+            seed: 42,
+            count: 1000,
+            dimensions: 1536,
+            clusters: 10,
+            noise: 0.1,
+            normalize: true,
+        }
+    }
+}
+
+/// Generated embedding corpus
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyntheticEmbeddings {
+    /// Dimensions of each vector
+    pub dimensions: u32,
+    /// Number of vectors
+    pub count: u32,
+    /// Vectors, flattened row by row
+    pub vectors: Vec<f64>,
+    /// Cluster of each vector
+    pub labels: Vec<u32>,
+}
+
+/// Write a deterministic synthetic file tree
+///
+/// # Arguments
+/// * `root` - Directory to fill; must not exist or be empty
+/// * `config` - Optional tree settings
+#[napi]
+pub fn generate_synthetic_tree(root: String, config: Option<SyntheticTreeConfig>) -> napi::Result<SyntheticTreeSummary> {
+    errors::guard("generateSyntheticTree", || {
+        let config = config.unwrap_or_default();
+        let languages = validate_tree_config(&config)?;
+        let root_path = Path::new(&root);
+        let occupied = fs::read_dir(root_path).map(|mut entries| entries.next().is_some());
+        if matches!(occupied, Ok(true)) || (root_path.exists() && !root_path.is_dir()) {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Refusing to generate into a non-empty path: {}", root),
+            ));
+        }
+        let io_error = |path: &Path, e: std::io::Error| {
+            errors::io(
+                &e,
+                format!("Failed to write {}: {}", path.display(), e),
+            )
+        };
+
+        let mut rng = SeededRng::new(config.seed as u64);
+
+        // Each directory nests under a random earlier one, within max_depth
+        let mut directories = vec![(root_path.to_path_buf(), 0u32)];
+        for index in 0..config.directory_count {
+            let candidates: Vec<usize> = (0..directories.len())
+                .filter(|&i| directories[i].1 < config.max_depth)
+                .collect();
+            if candidates.is_empty() {
+                break;
+            }
+            let (parent, depth) = directories[candidates[rng.below(candidates.len())]].clone();
+            directories.push((parent.join(format!("{}_{}", WORDS[rng.below(WORDS.len())], index)), depth + 1));
+        }
+        for (directory, _) in &directories {
+            fs::create_dir_all(directory).map_err(|e| io_error(directory, e))?;
+        }
+
+        let mut summary = SyntheticTreeSummary {
+            root: root.clone(),
+            files: 0,
+            directories: directories.len() as u32 - 1,
+            total_bytes: 0.0,
+            duplicates: 0,
+            files_by_language: HashMap::new(),
+        };
+        let mut written: Vec<(usize, String)> = Vec::new();
+        for index in 0..config.file_count {
+            let duplicate = !written.is_empty() && rng.next_f64() < config.duplicate_ratio;
+            let (language, content) = if duplicate {
+                written[rng.below(written.len())].clone()
+            } else {
+                let language = languages[rng.below(languages.len())];
+                let span = (config.max_file_size - config.min_file_size) as usize;
+                let size = config.min_file_size as usize + rng.below(span + 1);
+                (language, file_content(LANGUAGES[language].0, size, &mut rng))
+            };
+
+            let (directory, _) = &directories[rng.below(directories.len())];
+            let (name, extension) = LANGUAGES[language];
+            let path = directory.join(format!("{}_{}.{}", WORDS[rng.below(WORDS.len())], index, extension));
+            quota::charge("generate_synthetic_tree", Mutation::Write, &path, content.len() as u64)?;
+            // Throwaway benchmark trees don't need to survive a power loss
+            let options = AtomicWriteOptions {
+                fsync: false,
+                ..Default::default()
+            };
+            atomic_write::write(&path, content.as_bytes(), &options).map_err(|e| io_error(&path, e))?;
+
+            summary.files += 1;
+            summary.total_bytes += content.len() as f64;
+            summary.duplicates += duplicate as u32;
+            *summary.files_by_language.entry(name.to_string()).or_default() += 1;
+            if !duplicate {
+                written.push((language, content));
+            }
+        }
+        Ok(summary)
+    })
+}
+
+/// Generate a deterministic clustered embedding corpus
+///
+/// # Arguments
+/// * `config` - Optional corpus settings
+#[napi]
+pub fn generate_synthetic_embeddings(config: Option<SyntheticEmbeddingConfig>) -> napi::Result<SyntheticEmbeddings> {
+    errors::guard("generateSyntheticEmbeddings", || {
+        let config = config.unwrap_or_default();
+        if config.count == 0 || config.dimensions == 0 || config.clusters == 0 {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                "count, dimensions and clusters must be positive".to_string(),
+            ));
+        }
+        if !config.noise.is_finite() || config.noise < 0.0 {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                "noise must be a non-negative number".to_string(),
+            ));
+        }
+
+        let dimensions = config.dimensions as usize;
+        let mut rng = SeededRng::new(config.seed as u64);
+        let centers: Vec<Vec<f64>> = (0..config.clusters)
+            .map(|_| (0..dimensions).map(|_| rng.next_gaussian()).collect())
+            .collect();
+
+        let mut vectors = Vec::with_capacity(config.count as usize * dimensions);
+        let mut labels = Vec::with_capacity(config.count as usize);
+        for _ in 0..config.count {
+            let label = rng.below(centers.len());
+            let start = vectors.len();
+            vectors.extend(centers[label].iter().map(|c| c + rng.next_gaussian() * config.noise));
+            if config.normalize {
+                let norm = vectors[start..].iter().map(|x| x * x).sum::<f64>().sqrt();
+                if norm > 0.0 {
+                    vectors[start..].iter_mut().for_each(|x| *x /= norm);
+                }
+            }
+            labels.push(label as u32);
+        }
+
+        Ok(SyntheticEmbeddings {
+            dimensions: config.dimensions,
+            count: config.count,
+            vectors,
+            labels,
+        })
+    })
+}
+
+/// Check a tree configuration, returning the indices into `LANGUAGES` to use
+fn validate_tree_config(config: &SyntheticTreeConfig) -> napi::Result<Vec<usize>> {
+    let invalid = |message: String| Err(errors::new(ErrorCode::InvalidArgument, message));
+    if config.min_file_size > config.max_file_size {
+        return invalid("min_file_size must not exceed max_file_size".to_string());
+    }
+    if !(0.0..=1.0).contains(&config.duplicate_ratio) {
+        return invalid("duplicate_ratio must be between 0 and 1".to_string());
+    }
+    let mut indices = Vec::new();
+    for language in &config.languages {
+        match LANGUAGES.iter().position(|(name, _)| name.eq_ignore_ascii_case(language)) {
+            Some(index) if !indices.contains(&index) => indices.push(index),
+            Some(_) => {}
+            None => {
+                let known: Vec<&str> = LANGUAGES.iter().map(|(name, _)| *name).collect();
+                return invalid(format!("Unknown language: {} (expected one of {})", language, known.join(", ")));
+            }
+        }
+    }
+    if indices.is_empty() {
+        return invalid("At least one language is required".to_string());
+    }
+    Ok(indices)
+}
+
+/// Source text of exactly `size` bytes in a language
+fn file_content(language: &str, size: usize, rng: &mut SeededRng) -> String {
+    let mut content = String::with_capacity(size + 128);
+    let mut index = 0;
+    while content.len() < size {
+        let name = WORDS[rng.below(WORDS.len())];
+        let other = WORDS[rng.below(WORDS.len())];
+        let constant = rng.below(10_000);
+        let chunk = match language {
+            "rust" => format!("pub fn {name}_{index}({other}: u64) -> u64 {{\n    {other}.wrapping_mul({constant})\n}}\n\n"),
+            "javascript" => format!("export function {name}{index}({other}) {{\n  return {other} * {constant};\n}}\n\n"),
+            "typescript" => {
+                format!("export function {name}{index}({other}: number): number {{\n  return {other} * {constant};\n}}\n\n")
+            }
+            "python" => format!("def {name}_{index}({other}):\n    return {other} * {constant}\n\n\n"),
+            "go" => format!("func {name}{index}({other} int) int {{\n\treturn {other} * {constant}\n}}\n\n"),
+            _ => format!("## The {name} {other}\n\nEach {name} keeps {constant} {other} entries in order.\n\n"),
+        };
+        content.push_str(&chunk);
+        index += 1;
+    }
+    // Generated text is ASCII, so any byte offset is a char boundary
+    content.truncate(size);
+    content
+}
+
+/// Small deterministic generator (SplitMix64)
+pub(crate) struct SeededRng(u64);
+
+impl SeededRng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform index in `0..n`
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Uniform value in `[0, 1)`
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal value (Box-Muller)
+    pub(crate) fn next_gaussian(&mut self) -> f64 {
+        let u1 = 1.0 - self.next_f64();
+        let u2 = self.next_f64();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+}
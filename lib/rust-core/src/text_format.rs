@@ -0,0 +1,692 @@
+//! Formatting checks for whitespace and line endings
+//!
+//! This module provides single-pass analysis of line endings, trailing
+//! whitespace, final newlines and indentation, plus normalization that
+//! returns an edit list so callers can preview or apply changes. Batches of
+//! span edits can be applied in one pass with a source map back to the
+//! original text.
+
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::errors::{self, ErrorCode};
+
+/// Configuration for text formatting checks
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormatConfig {
+    /// Target line ending when normalizing ("lf" or "crlf")
+    pub line_ending: String,
+    /// Remove trailing spaces and tabs when normalizing
+    pub trim_trailing_whitespace: bool,
+    /// Append a final newline when normalizing if one is missing
+    pub ensure_final_newline: bool,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self {
+            line_ending: "lf".to_string(),
+            trim_trailing_whitespace: true,
+            ensure_final_newline: true,
+        }
+    }
+}
+
+/// Line ending and whitespace analysis report
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineEndingReport {
+    /// Number of LF line endings
+    pub lf_count: u32,
+    /// Number of CRLF line endings
+    pub crlf_count: u32,
+    /// Number of bare CR line endings
+    pub cr_count: u32,
+    /// Most common line ending ("lf", "crlf", "cr", or "none")
+    pub dominant: String,
+    /// Whether more than one kind of line ending is present
+    pub is_mixed: bool,
+    /// Line numbers (1-based) whose ending differs from the dominant one
+    pub inconsistent_lines: Vec<u32>,
+    /// Line numbers (1-based) with trailing spaces or tabs
+    pub trailing_whitespace_lines: Vec<u32>,
+    /// Whether the text ends with a line ending
+    pub has_final_newline: bool,
+    /// Number of blank lines after the last non-blank line
+    pub trailing_blank_lines: u32,
+}
+
+/// A single text edit expressed in byte offsets of the original text
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextEdit {
+    /// Start byte offset in the original text
+    pub start: u32,
+    /// End byte offset in the original text
+    pub end: u32,
+    /// Replacement text
+    pub replacement: String,
+    /// Line number (1-based) the edit applies to
+    pub line: u32,
+}
+
+/// Result of a normalization operation
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NormalizationResult {
+    /// Normalized text
+    pub text: String,
+    /// Edits that transform the original text into the normalized text
+    pub edits: Vec<TextEdit>,
+    /// Whether any change was made
+    pub changed: bool,
+}
+
+/// Replacement of a byte span of the original text
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpanEdit {
+    /// Start byte offset in the original text
+    pub start: u32,
+    /// End byte offset in the original text (equal to start for insertions)
+    pub end: u32,
+    /// Replacement text
+    pub replacement: String,
+}
+
+/// Mapping between a span of the edited text and the original text
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceMapSegment {
+    /// Start byte offset in the edited text
+    pub generated_start: u32,
+    /// End byte offset in the edited text
+    pub generated_end: u32,
+    /// Start byte offset in the original text
+    pub original_start: u32,
+    /// End byte offset in the original text
+    pub original_end: u32,
+    /// Whether the segment comes from an edit rather than the original text
+    pub edited: bool,
+}
+
+/// Result of applying a batch of edits
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditApplicationResult {
+    /// Edited text
+    pub text: String,
+    /// Segments covering the edited text in order
+    pub source_map: Vec<SourceMapSegment>,
+    /// Number of edits applied
+    pub applied: u32,
+}
+
+/// Indentation problem found on a single line
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndentationIssue {
+    /// Line number (1-based)
+    pub line: u32,
+    /// Issue kind ("mixed", "wrong_style", or "irregular_width")
+    pub kind: String,
+    /// Human-readable description
+    pub message: String,
+}
+
+/// Indentation analysis report
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndentationReport {
+    /// Dominant indentation character ("spaces", "tabs", or "none")
+    pub style: String,
+    /// Inferred indentation width in columns (0 if unknown)
+    pub width: u32,
+    /// Number of lines indented with spaces only
+    pub space_lines: u32,
+    /// Number of lines indented with tabs only
+    pub tab_lines: u32,
+    /// Number of lines mixing tabs and spaces in their indentation
+    pub mixed_lines: u32,
+    /// Lines whose indentation is inconsistent with the dominant style
+    pub issues: Vec<IndentationIssue>,
+}
+
+/// A line split into content and its terminator
+struct Line<'a> {
+    start: usize,
+    content: &'a str,
+    ending: &'a str,
+}
+
+/// Split text into lines, preserving LF, CRLF and bare CR terminators
+fn split_lines(text: &str) -> Vec<Line<'_>> {
+    let bytes = text.as_bytes();
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let ending_len = match bytes[i] {
+            b'\n' => 1,
+            b'\r' if bytes.get(i + 1) == Some(&b'\n') => 2,
+            b'\r' => 1,
+            _ => 0,
+        };
+
+        if ending_len > 0 {
+            lines.push(Line {
+                start,
+                content: &text[start..i],
+                ending: &text[i..i + ending_len],
+            });
+            i += ending_len;
+            start = i;
+        } else {
+            i += 1;
+        }
+    }
+
+    if start < bytes.len() {
+        lines.push(Line {
+            start,
+            content: &text[start..],
+            ending: "",
+        });
+    }
+
+    lines
+}
+
+/// Name of a line terminator
+fn ending_name(ending: &str) -> &'static str {
+    match ending {
+        "\r\n" => "crlf",
+        "\r" => "cr",
+        "\n" => "lf",
+        _ => "none",
+    }
+}
+
+/// Text formatter for whitespace and line ending checks
+#[napi]
+pub struct TextFormatter {
+    config: FormatConfig,
+}
+
+#[napi]
+impl TextFormatter {
+    /// Create a new text formatter with optional configuration
+    ///
+    /// # Arguments
+    /// * `config` - Optional configuration for normalization
+    #[napi(constructor)]
+    pub fn new(config: Option<FormatConfig>) -> napi::Result<Self> {
+        errors::guard("new TextFormatter", || {
+            let config = config.unwrap_or_default();
+
+            if config.line_ending != "lf" && config.line_ending != "crlf" {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Unsupported line ending: {}", config.line_ending),
+                ));
+            }
+
+            Ok(Self { config })
+        })
+    }
+
+    /// Analyze line endings, trailing whitespace and final newline in one pass
+    #[napi]
+    pub fn analyze_line_endings(&self, text: String) -> napi::Result<LineEndingReport> {
+        errors::catch_panic("TextFormatter.analyzeLineEndings", || {
+            let lines = split_lines(&text);
+            let (mut lf_count, mut crlf_count, mut cr_count) = (0u32, 0u32, 0u32);
+            let mut trailing_whitespace_lines = Vec::new();
+
+            for (index, line) in lines.iter().enumerate() {
+                match line.ending {
+                    "\n" => lf_count += 1,
+                    "\r\n" => crlf_count += 1,
+                    "\r" => cr_count += 1,
+                    _ => {}
+                }
+
+                if line.content.ends_with([' ', '\t']) {
+                    trailing_whitespace_lines.push((index + 1) as u32);
+                }
+            }
+
+            let dominant = if lf_count == 0 && crlf_count == 0 && cr_count == 0 {
+                "none"
+            } else if lf_count >= crlf_count && lf_count >= cr_count {
+                "lf"
+            } else if crlf_count >= cr_count {
+                "crlf"
+            } else {
+                "cr"
+            };
+
+            let inconsistent_lines = lines
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| !line.ending.is_empty() && ending_name(line.ending) != dominant)
+                .map(|(index, _)| (index + 1) as u32)
+                .collect();
+
+            let trailing_blank_lines = lines
+                .iter()
+                .rev()
+                .take_while(|line| line.content.trim().is_empty())
+                .count() as u32;
+
+            LineEndingReport {
+                lf_count,
+                crlf_count,
+                cr_count,
+                dominant: dominant.to_string(),
+                is_mixed: [lf_count, crlf_count, cr_count].iter().filter(|&&c| c > 0).count() > 1,
+                inconsistent_lines,
+                trailing_whitespace_lines,
+                has_final_newline: lines.last().map(|l| !l.ending.is_empty()).unwrap_or(false),
+                trailing_blank_lines,
+            }
+        })
+    }
+
+    /// Normalize line endings and whitespace, returning the result and an edit list
+    #[napi]
+    pub fn normalize_line_endings(&self, text: String) -> napi::Result<NormalizationResult> {
+        errors::catch_panic("TextFormatter.normalizeLineEndings", || {
+            let target = if self.config.line_ending == "crlf" { "\r\n" } else { "\n" };
+            let lines = split_lines(&text);
+            let mut edits = Vec::new();
+
+            for (index, line) in lines.iter().enumerate() {
+                let line_number = (index + 1) as u32;
+                let content_end = line.start + line.content.len();
+
+                if self.config.trim_trailing_whitespace {
+                    let trimmed = line.content.trim_end_matches([' ', '\t']);
+                    if trimmed.len() != line.content.len() {
+                        edits.push(TextEdit {
+                            start: (line.start + trimmed.len()) as u32,
+                            end: content_end as u32,
+                            replacement: String::new(),
+                            line: line_number,
+                        });
+                    }
+                }
+
+                if !line.ending.is_empty() && line.ending != target {
+                    edits.push(TextEdit {
+                        start: content_end as u32,
+                        end: (content_end + line.ending.len()) as u32,
+                        replacement: target.to_string(),
+                        line: line_number,
+                    });
+                }
+            }
+
+            if self.config.ensure_final_newline {
+                if let Some(last) = lines.last() {
+                    if last.ending.is_empty() {
+                        edits.push(TextEdit {
+                            start: text.len() as u32,
+                            end: text.len() as u32,
+                            replacement: target.to_string(),
+                            line: lines.len() as u32,
+                        });
+                    }
+                }
+            }
+
+            NormalizationResult {
+                text: apply_edits(&text, &edits),
+                changed: !edits.is_empty(),
+                edits,
+            }
+        })
+    }
+
+    /// Analyze indentation character, inferred width and inconsistencies
+    #[napi]
+    pub fn analyze_indentation(&self, text: String) -> napi::Result<IndentationReport> {
+        errors::catch_panic("TextFormatter.analyzeIndentation", || {
+            let lines = split_lines(&text);
+            let (mut space_lines, mut tab_lines, mut mixed_lines) = (0u32, 0u32, 0u32);
+            let mut deltas: HashMap<usize, u32> = HashMap::new();
+            let mut previous_width = 0usize;
+            let mut in_string = None;
+            let mut indents = Vec::with_capacity(lines.len());
+
+            for line in &lines {
+                let was_in_string = in_string.is_some();
+                in_string = update_string_state(line.content, in_string);
+                let indent = leading_whitespace(line.content);
+
+                if was_in_string || indent.is_empty() || indent.len() == line.content.len() {
+                    indents.push(None);
+                    continue;
+                }
+
+                let has_tabs = indent.contains('\t');
+                let has_spaces = indent.contains(' ');
+                match (has_tabs, has_spaces) {
+                    (true, true) => mixed_lines += 1,
+                    (true, false) => tab_lines += 1,
+                    _ => {
+                        space_lines += 1;
+                        if indent.len() > previous_width {
+                            *deltas.entry(indent.len() - previous_width).or_insert(0) += 1;
+                        }
+                        previous_width = indent.len();
+                    }
+                }
+                indents.push(Some(indent));
+            }
+
+            let style = if space_lines == 0 && tab_lines == 0 && mixed_lines == 0 {
+                "none"
+            } else if tab_lines > space_lines {
+                "tabs"
+            } else {
+                "spaces"
+            };
+
+            let width = if style == "spaces" {
+                deltas
+                    .iter()
+                    .filter(|(&delta, _)| (2..=8).contains(&delta))
+                    .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+                    .map(|(&delta, _)| delta as u32)
+                    .unwrap_or(0)
+            } else {
+                0
+            };
+
+            let mut issues = Vec::new();
+            for (index, indent) in indents.iter().enumerate() {
+                let Some(indent) = indent else { continue };
+                let line = (index + 1) as u32;
+                let has_tabs = indent.contains('\t');
+                let has_spaces = indent.contains(' ');
+
+                if has_tabs && has_spaces {
+                    issues.push(IndentationIssue {
+                        line,
+                        kind: "mixed".to_string(),
+                        message: "Indentation mixes tabs and spaces".to_string(),
+                    });
+                } else if (style == "spaces" && has_tabs) || (style == "tabs" && has_spaces) {
+                    issues.push(IndentationIssue {
+                        line,
+                        kind: "wrong_style".to_string(),
+                        message: format!("Indented with {} but file uses {}", if has_tabs { "tabs" } else { "spaces" }, style),
+                    });
+                } else if style == "spaces" && width > 1 && !indent.len().is_multiple_of(width as usize) {
+                    issues.push(IndentationIssue {
+                        line,
+                        kind: "irregular_width".to_string(),
+                        message: format!("Indentation of {} columns is not a multiple of {}", indent.len(), width),
+                    });
+                }
+            }
+
+            IndentationReport {
+                style: style.to_string(),
+                width,
+                space_lines,
+                tab_lines,
+                mixed_lines,
+                issues,
+            }
+        })
+    }
+
+    /// Convert leading indentation between tabs and spaces
+    ///
+    /// Only leading whitespace is rewritten, so alignment after code is kept,
+    /// and lines continuing a multi-line string literal are left untouched.
+    ///
+    /// # Arguments
+    /// * `text` - Text to convert
+    /// * `target_style` - "spaces" or "tabs"
+    /// * `tab_width` - Columns per indentation level (inferred when omitted)
+    #[napi]
+    pub fn convert_indentation(
+        &self,
+        text: String,
+        target_style: String,
+        tab_width: Option<u32>,
+    ) -> napi::Result<NormalizationResult> {
+        errors::guard("TextFormatter.convertIndentation", || {
+            if target_style != "spaces" && target_style != "tabs" {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Unsupported indentation style: {}", target_style),
+                ));
+            }
+
+            let width = match tab_width {
+                Some(0) => {
+                    return Err(errors::new(
+                        ErrorCode::InvalidArgument,
+                        "tab_width must be greater than zero".to_string(),
+                    ))
+                }
+                Some(width) => width as usize,
+                None => match self.analyze_indentation(text.clone())?.width {
+                    0 => 4,
+                    width => width as usize,
+                },
+            };
+
+            let lines = split_lines(&text);
+            let mut edits = Vec::new();
+            let mut in_string = None;
+
+            for (index, line) in lines.iter().enumerate() {
+                let was_in_string = in_string.is_some();
+                in_string = update_string_state(line.content, in_string);
+                let indent = leading_whitespace(line.content);
+                if was_in_string || indent.is_empty() {
+                    continue;
+                }
+
+                // Expand to a column count first so mixed indentation converts cleanly
+                let columns = indent.chars().fold(0, |col, c| {
+                    if c == '\t' {
+                        (col / width + 1) * width
+                    } else {
+                        col + 1
+                    }
+                });
+
+                let replacement = if target_style == "spaces" {
+                    " ".repeat(columns)
+                } else {
+                    format!("{}{}", "\t".repeat(columns / width), " ".repeat(columns % width))
+                };
+
+                if replacement != indent {
+                    edits.push(TextEdit {
+                        start: line.start as u32,
+                        end: (line.start + indent.len()) as u32,
+                        replacement,
+                        line: (index + 1) as u32,
+                    });
+                }
+            }
+
+            Ok(NormalizationResult {
+                text: apply_edits(&text, &edits),
+                changed: !edits.is_empty(),
+                edits,
+            })
+        })
+    }
+}
+
+/// Leading whitespace of a line
+fn leading_whitespace(content: &str) -> &str {
+    let end = content
+        .find(|c: char| c != ' ' && c != '\t')
+        .unwrap_or(content.len());
+    &content[..end]
+}
+
+/// Track whether a line leaves a multi-line string literal open
+///
+/// Recognizes template literals and triple-quoted strings; this is a
+/// heuristic that ignores comments but honors backslash escapes.
+fn update_string_state(content: &str, open: Option<&'static str>) -> Option<&'static str> {
+    let mut open = open;
+    let bytes = content.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'\\' {
+            i += 2;
+            continue;
+        }
+
+        let rest = &bytes[i..];
+        match open {
+            Some(delimiter) if rest.starts_with(delimiter.as_bytes()) => {
+                open = None;
+                i += delimiter.len();
+            }
+            Some(_) => i += 1,
+            None => {
+                if let Some(delimiter) = ["\"\"\"", "'''", "`"].into_iter().find(|d| rest.starts_with(d.as_bytes())) {
+                    open = Some(delimiter);
+                    i += delimiter.len();
+                } else {
+                    i += 1;
+                }
+            }
+        }
+    }
+
+    open
+}
+
+/// Apply sorted, non-overlapping edits to a text
+pub(crate) fn apply_edits(text: &str, edits: &[TextEdit]) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut cursor = 0;
+
+    for edit in edits {
+        result.push_str(&text[cursor..edit.start as usize]);
+        result.push_str(&edit.replacement);
+        cursor = edit.end as usize;
+    }
+
+    result.push_str(&text[cursor..]);
+    result
+}
+
+/// Piece of the edited text: a span of the original or an edit's replacement
+enum Piece {
+    Original(usize, usize),
+    Inserted(usize),
+}
+
+/// Apply span edits in one pass using a piece table
+///
+/// Edits may be given in any order. Insertions at the same offset keep their
+/// input order; overlapping spans, out-of-range offsets and offsets inside a
+/// UTF-8 character are rejected before anything is applied.
+#[napi]
+pub fn apply_text_edits(text: String, edits: Vec<SpanEdit>) -> napi::Result<EditApplicationResult> {
+    errors::guard("applyTextEdits", || {
+        let mut order: Vec<usize> = (0..edits.len()).collect();
+        order.sort_by_key(|&i| (edits[i].start, edits[i].end));
+
+        let mut pieces = Vec::with_capacity(edits.len() * 2 + 1);
+        let mut cursor = 0usize;
+
+        for &i in &order {
+            let (start, end) = (edits[i].start as usize, edits[i].end as usize);
+
+            if start > end || end > text.len() {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Edit {} span {}..{} is out of range for text of {} bytes", i, start, end, text.len()),
+                ));
+            }
+            if !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Edit {} span {}..{} splits a UTF-8 character", i, start, end),
+                ));
+            }
+            if start < cursor {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Edit {} span {}..{} overlaps a previous edit ending at {}", i, start, end, cursor),
+                ));
+            }
+
+            if start > cursor {
+                pieces.push(Piece::Original(cursor, start));
+            }
+            pieces.push(Piece::Inserted(i));
+            cursor = end;
+        }
+
+        if cursor < text.len() {
+            pieces.push(Piece::Original(cursor, text.len()));
+        }
+
+        let mut result = String::with_capacity(
+            text.len() + edits.iter().map(|e| e.replacement.len()).sum::<usize>(),
+        );
+        let mut source_map = Vec::with_capacity(pieces.len());
+
+        for piece in pieces {
+            let generated_start = result.len() as u32;
+            let (original_start, original_end, edited) = match piece {
+                Piece::Original(start, end) => {
+                    result.push_str(&text[start..end]);
+                    (start as u32, end as u32, false)
+                }
+                Piece::Inserted(i) => {
+                    result.push_str(&edits[i].replacement);
+                    (edits[i].start, edits[i].end, true)
+                }
+            };
+
+            source_map.push(SourceMapSegment {
+                generated_start,
+                generated_end: result.len() as u32,
+                original_start,
+                original_end,
+                edited,
+            });
+        }
+
+        Ok(EditApplicationResult {
+            text: result,
+            source_map,
+            applied: edits.len() as u32,
+        })
+    })
+}
+
+/// Quick line ending normalization function
+#[napi]
+pub fn quick_normalize_line_endings(
+    text: String,
+    line_ending: Option<String>,
+) -> napi::Result<NormalizationResult> {
+    errors::guard("quickNormalizeLineEndings", || {
+        let formatter = TextFormatter::new(Some(FormatConfig {
+            line_ending: line_ending.unwrap_or_else(|| "lf".to_string()),
+            ..Default::default()
+        }))?;
+        formatter.normalize_line_endings(text)
+    })
+}
@@ -3,10 +3,19 @@
 //! This module provides optimized string operations and pattern matching
 //! that outperform JavaScript implementations by 10-50x for large texts.
 
+use napi::bindgen_prelude::{Buffer, Either};
 use napi_derive::napi;
-use aho_corasick::{AhoCorasick, MatchKind};
-use regex::Regex;
+use aho_corasick::{AhoCorasick, AhoCorasickKind, MatchKind};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fs;
+use std::io::Read;
+use unicode_segmentation::UnicodeSegmentation;
+use crate::errors::{self, ErrorCode};
+use crate::kernels::{self, LineIndex, Span, SubstringOptions};
+use crate::metrics;
+use crate::shared;
 
 /// Configuration for text processing
 #[napi(object)]
@@ -14,10 +23,23 @@ use serde::{Deserialize, Serialize};
 pub struct TextProcessingConfig {
     /// Case-sensitive matching
     pub case_sensitive: bool,
-    /// Use overlapping matches
+    /// Report overlapping matches (only with `match_kind` "standard")
     pub overlapping: bool,
     /// Maximum match count (0 for unlimited)
     pub max_matches: u32,
+    /// Which pattern wins when several match at one position:
+    /// "leftmost-first" (default; the one listed first), "leftmost-longest"
+    /// (the longest), or "standard" (the first to finish matching, as in
+    /// classic Aho-Corasick)
+    pub match_kind: Option<String>,
+    /// Only report matches starting at the beginning of the text or right
+    /// where the previous match ended (default false; not combinable with
+    /// overlapping matches)
+    pub anchored: Option<bool>,
+    /// Automaton to build: "auto" (default; chosen by pattern count and
+    /// size), "nfa", "contiguous-nfa" (faster, more memory) or "dfa"
+    /// (fastest search, slowest build, most memory)
+    pub automaton: Option<String>,
 }
 
 impl Default for TextProcessingConfig {
@@ -26,6 +48,9 @@ impl Default for TextProcessingConfig {
             case_sensitive: true,
             overlapping: false,
             max_matches: 0,
+            match_kind: None,
+            anchored: None,
+            automaton: None,
         }
     }
 }
@@ -34,16 +59,122 @@ impl Default for TextProcessingConfig {
 #[napi(object)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TextMatch {
-    /// Start position of the match
+    /// Start offset of the match: UTF-8 bytes for string input, or the
+    /// buffer encoding's units for the `*InBuffer` methods
     pub start: u32,
-    /// End position of the match
+    /// End offset of the match, in the same units as `start`
     pub end: u32,
+    /// Line of the match start (1-based)
+    pub line: u32,
+    /// Column of the match start within its line (0-based), in the same
+    /// units as `start`
+    pub column: u32,
     /// The matched text
     pub text: String,
     /// Pattern index (for multi-pattern search)
     pub pattern_index: u32,
 }
 
+impl TextMatch {
+    fn from_span(span: Span, text: &str, lines: &LineIndex) -> Self {
+        let (line, column) = lines.position(span.start);
+        Self {
+            start: span.start as u32,
+            end: span.end as u32,
+            line,
+            column,
+            text: text[span.start..span.end].to_string(),
+            pattern_index: span.pattern_index,
+        }
+    }
+}
+
+/// Match found by a streaming matcher
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamMatch {
+    /// Absolute byte offset of the match start within the stream
+    pub start: f64,
+    /// Absolute byte offset of the match end within the stream
+    pub end: f64,
+    /// The matched text (lossily decoded as UTF-8)
+    pub text: String,
+    /// Pattern index (for multi-pattern search)
+    pub pattern_index: u32,
+}
+
+/// Mapping of a run of output bytes back to the original text
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OffsetSegment {
+    /// Byte offset of the run in the sanitized output
+    pub output_start: u32,
+    /// Byte offset of the run in the original text
+    pub original_start: u32,
+    /// Length of the run in bytes
+    pub length: u32,
+}
+
+/// Result of ANSI or control-character sanitization
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SanitizedText {
+    /// Sanitized text
+    pub text: String,
+    /// Kept runs mapping output byte offsets to original byte offsets
+    pub segments: Vec<OffsetSegment>,
+    /// Number of escape sequences removed
+    pub escape_sequences_removed: u32,
+    /// Number of control characters removed or replaced
+    pub control_chars_removed: u32,
+}
+
+/// JSON validation result
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonValidationResult {
+    /// Whether the input is valid JSON
+    pub is_valid: bool,
+    /// Error message (if invalid)
+    pub error: Option<String>,
+    /// Line of the error (1-based, if invalid)
+    pub line: Option<u32>,
+    /// Column of the error (1-based, if invalid)
+    pub column: Option<u32>,
+}
+
+/// Value matched by a JSON Pointer or JSONPath query
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonQueryMatch {
+    /// JSON Pointer (RFC 6901) to the matched value
+    pub pointer: String,
+    /// The matched value
+    pub value: serde_json::Value,
+}
+
+/// Aggregate text statistics
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextStats {
+    /// Number of lines (a trailing newline does not start a new line)
+    pub line_count: u32,
+    /// Number of whitespace-separated words
+    pub word_count: u32,
+    /// Number of Unicode scalar values
+    pub char_count: u32,
+    /// Number of extended grapheme clusters
+    pub grapheme_count: u32,
+    /// Length of the longest line in characters
+    pub longest_line_length: u32,
+    /// Line number of the longest line (1-based, 0 for empty text)
+    pub longest_line: u32,
+    /// Average line length in characters
+    pub average_line_length: f64,
+    /// Size in UTF-8 bytes
+    pub byte_size: u32,
+}
+
 /// Text processor for high-performance pattern matching
 #[napi]
 pub struct TextProcessor {
@@ -61,72 +192,314 @@ impl TextProcessor {
     }
 
     /// Fast substring search using Aho-Corasick
+    ///
+    /// Offsets are UTF-8 byte offsets into `text`, which differ from
+    /// JavaScript string indices once the text has non-ASCII characters; use
+    /// `findSubstringsInBuffer` with "utf16le" for string indices.
     #[napi]
     pub fn find_substrings(
         &self,
         text: String,
         patterns: Vec<String>,
     ) -> napi::Result<Vec<TextMatch>> {
-        if patterns.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        let ac = AhoCorasick::builder()
-            .match_kind(MatchKind::LeftmostFirst)
-            .ascii_case_insensitive(!self.config.case_sensitive)
-            .build(&patterns)
-            .map_err(|e| napi::Error::new(napi::Status::InvalidArg, e.to_string()))?;
+        errors::guard("TextProcessor.findSubstrings", || {
+            metrics::add_bytes("text_processing", text.len() as u64);
+            if patterns.is_empty() {
+                return Ok(Vec::new());
+            }
 
-        let mut matches = Vec::new();
-        
-        for mat in ac.find_iter(&text) {
-            matches.push(TextMatch {
-                start: mat.start() as u32,
-                end: mat.end() as u32,
-                text: text[mat.start()..mat.end()].to_string(),
-                pattern_index: mat.pattern().as_u32(),
-            });
+            let search = self.build_automaton(&patterns)?;
+            Ok(self.collect_substring_matches(&search, &text))
+        })
+    }
 
-            if self.config.max_matches > 0 && matches.len() >= self.config.max_matches as usize {
-                break;
+    /// Search many documents in parallel with one shared automaton
+    /// Returns one match list per document, in input order
+    #[napi]
+    pub fn find_substrings_in_documents(
+        &self,
+        documents: Vec<String>,
+        patterns: Vec<String>,
+    ) -> napi::Result<Vec<Vec<TextMatch>>> {
+        errors::guard("TextProcessor.findSubstringsInDocuments", || {
+            metrics::add_bytes("text_processing", documents.iter().map(|d| d.len() as u64).sum());
+            if patterns.is_empty() {
+                return Ok(vec![Vec::new(); documents.len()]);
             }
-        }
 
-        Ok(matches)
+            let search = self.build_automaton(&patterns)?;
+
+            let results = if documents.len() > 10 {
+                documents
+                    .par_iter()
+                    .map(|doc| self.collect_substring_matches(&search, doc))
+                    .collect()
+            } else {
+                documents
+                    .iter()
+                    .map(|doc| self.collect_substring_matches(&search, doc))
+                    .collect()
+            };
+
+            Ok(results)
+        })
     }
 
     /// Regex pattern matching
+    ///
+    /// Offsets are UTF-8 byte offsets into `text`, as with `findSubstrings`.
     #[napi]
     pub fn find_regex_matches(
         &self,
         text: String,
         pattern: String,
     ) -> napi::Result<Vec<TextMatch>> {
-        let regex_pattern = if !self.config.case_sensitive {
-            format!("(?i){}", pattern)
-        } else {
-            pattern
+        errors::guard("TextProcessor.findRegexMatches", || {
+            metrics::add_bytes("text_processing", text.len() as u64);
+            let regex_pattern = kernels::regex_source(&pattern, self.config.case_sensitive);
+            let re = shared::regex(&regex_pattern)
+                .map_err(|e| errors::new(ErrorCode::InvalidArgument, e.to_string()))?;
+
+            Ok(text_matches(kernels::regex_matches(&re, &text, self.config.max_matches as usize), &text))
+        })
+    }
+
+    /// Substring search in a Buffer, without first copying it into a string
+    ///
+    /// `encoding` says how the buffer encodes the text, as in Node's
+    /// `Buffer.from(text, encoding)`, and sets the unit of match offsets:
+    /// - "utf8" (default): UTF-8 byte offsets; the buffer is searched in place
+    ///   and must be valid UTF-8
+    /// - "latin1": byte offsets, which equal string indices; ASCII-only
+    ///   buffers are searched in place, others are transcoded
+    /// - "utf16le": UTF-16 code units, which equal JavaScript string indices;
+    ///   the buffer is transcoded and lone surrogates match as U+FFFD
+    #[napi]
+    pub fn find_substrings_in_buffer(
+        &self,
+        buffer: Buffer,
+        patterns: Vec<String>,
+        encoding: Option<String>,
+    ) -> napi::Result<Vec<TextMatch>> {
+        errors::guard("TextProcessor.findSubstringsInBuffer", || {
+            metrics::add_bytes("text_processing", buffer.len() as u64);
+            let encoding = BufferEncoding::parse(encoding.as_deref())?;
+            if patterns.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let search = self.build_automaton(&patterns)?;
+            let text = encoding.decode(&buffer)?;
+            let spans = kernels::substring_matches(
+                &search.automaton,
+                &text,
+                self.config.max_matches as usize,
+                &search.options,
+            );
+            Ok(encoding.matches(&text, spans))
+        })
+    }
+
+    /// Regex pattern matching in a Buffer, without first copying it into a string
+    ///
+    /// `encoding` and the offset units are as for `findSubstringsInBuffer`.
+    #[napi]
+    pub fn find_regex_matches_in_buffer(
+        &self,
+        buffer: Buffer,
+        pattern: String,
+        encoding: Option<String>,
+    ) -> napi::Result<Vec<TextMatch>> {
+        errors::guard("TextProcessor.findRegexMatchesInBuffer", || {
+            metrics::add_bytes("text_processing", buffer.len() as u64);
+            let encoding = BufferEncoding::parse(encoding.as_deref())?;
+            let regex_pattern = kernels::regex_source(&pattern, self.config.case_sensitive);
+            let re = shared::regex(&regex_pattern)
+                .map_err(|e| errors::new(ErrorCode::InvalidArgument, e.to_string()))?;
+
+            let text = encoding.decode(&buffer)?;
+            let spans = kernels::regex_matches(&re, &text, self.config.max_matches as usize);
+            Ok(encoding.matches(&text, spans))
+        })
+    }
+
+    /// Build the Aho-Corasick automaton for a pattern set
+    fn build_automaton(&self, patterns: &[String]) -> napi::Result<SubstringSearch> {
+        let options = SubstringOptions {
+            match_kind: parse_match_kind(self.config.match_kind.as_deref())?,
+            anchored: self.config.anchored.unwrap_or(false),
+            overlapping: self.config.overlapping,
+            automaton: parse_automaton_kind(self.config.automaton.as_deref())?,
         };
+        if options.anchored && options.overlapping && options.match_kind == MatchKind::Standard {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                "Anchored search can't report overlapping matches".to_string(),
+            ));
+        }
+        let automaton = kernels::substring_automaton(patterns, self.config.case_sensitive, &options)
+            .map_err(|e| errors::new(ErrorCode::InvalidArgument, e.to_string()))?;
+        Ok(SubstringSearch { automaton, options })
+    }
 
-        let re = Regex::new(&regex_pattern)
-            .map_err(|e| napi::Error::new(napi::Status::InvalidArg, e.to_string()))?;
+    /// Collect substring matches in a single text, honoring `max_matches`
+    fn collect_substring_matches(&self, search: &SubstringSearch, text: &str) -> Vec<TextMatch> {
+        let spans = kernels::substring_matches(&search.automaton, text, self.config.max_matches as usize, &search.options);
+        text_matches(spans, text)
+    }
+}
 
-        let mut matches = Vec::new();
-        
-        for mat in re.find_iter(&text) {
-            matches.push(TextMatch {
-                start: mat.start() as u32,
-                end: mat.end() as u32,
-                text: mat.as_str().to_string(),
-                pattern_index: 0,
-            });
+/// Matches for spans of `text`, located by a line index built only if there are any
+fn text_matches(spans: Vec<Span>, text: &str) -> Vec<TextMatch> {
+    if spans.is_empty() {
+        return Vec::new();
+    }
+    let lines = LineIndex::new(text);
+    spans.into_iter().map(|span| TextMatch::from_span(span, text, &lines)).collect()
+}
 
-            if self.config.max_matches > 0 && matches.len() >= self.config.max_matches as usize {
-                break;
+/// An automaton with the options it was built with
+struct SubstringSearch {
+    automaton: AhoCorasick,
+    options: SubstringOptions,
+}
+
+/// Parse a `match_kind` setting
+fn parse_match_kind(kind: Option<&str>) -> napi::Result<MatchKind> {
+    match kind {
+        None | Some("leftmost-first") => Ok(MatchKind::LeftmostFirst),
+        Some("leftmost-longest") => Ok(MatchKind::LeftmostLongest),
+        Some("standard") => Ok(MatchKind::Standard),
+        Some(other) => Err(errors::new(
+            ErrorCode::InvalidArgument,
+            format!("Unknown match kind: {} (expected leftmost-first, leftmost-longest or standard)", other),
+        )),
+    }
+}
+
+/// Parse an `automaton` setting; `None` lets the builder choose
+fn parse_automaton_kind(kind: Option<&str>) -> napi::Result<Option<AhoCorasickKind>> {
+    match kind {
+        None | Some("auto") => Ok(None),
+        Some("nfa") => Ok(Some(AhoCorasickKind::NoncontiguousNFA)),
+        Some("contiguous-nfa") => Ok(Some(AhoCorasickKind::ContiguousNFA)),
+        Some("dfa") => Ok(Some(AhoCorasickKind::DFA)),
+        Some(other) => Err(errors::new(
+            ErrorCode::InvalidArgument,
+            format!("Unknown automaton: {} (expected auto, nfa, contiguous-nfa or dfa)", other),
+        )),
+    }
+}
+
+/// How a Buffer passed to a text search encodes its text
+#[derive(Debug, Clone, Copy)]
+enum BufferEncoding {
+    Utf8,
+    Latin1,
+    Utf16Le,
+}
+
+impl BufferEncoding {
+    /// Parse an encoding name, accepting Node's aliases
+    fn parse(encoding: Option<&str>) -> napi::Result<Self> {
+        match encoding.map(str::to_ascii_lowercase).as_deref() {
+            None | Some("utf8" | "utf-8") => Ok(Self::Utf8),
+            Some("latin1" | "binary") => Ok(Self::Latin1),
+            Some("utf16le" | "utf-16le" | "ucs2" | "ucs-2") => Ok(Self::Utf16Le),
+            Some(other) => Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Unsupported encoding: {} (expected utf8, latin1 or utf16le)", other),
+            )),
+        }
+    }
+
+    /// The buffer's text, borrowed when its bytes are already UTF-8
+    fn decode(self, bytes: &[u8]) -> napi::Result<Cow<'_, str>> {
+        match self {
+            Self::Utf8 => std::str::from_utf8(bytes).map(Cow::Borrowed).map_err(|e| {
+                errors::new(ErrorCode::InvalidArgument, format!("Buffer is not valid UTF-8: {}", e))
+            }),
+            Self::Latin1 => Ok(match std::str::from_utf8(bytes) {
+                Ok(text) if bytes.is_ascii() => Cow::Borrowed(text),
+                _ => Cow::Owned(bytes.iter().map(|&byte| byte as char).collect()),
+            }),
+            Self::Utf16Le => {
+                if !bytes.len().is_multiple_of(2) {
+                    return Err(errors::new(
+                        ErrorCode::InvalidArgument,
+                        "UTF-16 buffer has an odd number of bytes".to_string(),
+                    ));
+                }
+                let units = bytes.chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]]));
+                Ok(Cow::Owned(
+                    char::decode_utf16(units)
+                        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                        .collect(),
+                ))
             }
         }
+    }
+
+    /// Units a character takes in this encoding
+    fn units(self, c: char) -> usize {
+        match self {
+            Self::Utf8 => c.len_utf8(),
+            Self::Latin1 => 1,
+            Self::Utf16Le => c.len_utf16(),
+        }
+    }
+
+    /// Units the characters of `text` take in this encoding
+    fn length(self, text: &str) -> usize {
+        text.chars().map(|c| self.units(c)).sum()
+    }
 
-        Ok(matches)
+    /// Matches over the decoded `text`, with offsets converted to this encoding's units
+    ///
+    /// Offsets are converted by walking from each one to the next, so spans
+    /// in text order (overlapping or not) are converted in about one pass.
+    /// Columns are measured from the start of the line in units, converted
+    /// once per line.
+    fn matches(self, text: &str, spans: Vec<Span>) -> Vec<TextMatch> {
+        if let Self::Utf8 = self {
+            return text_matches(spans, text);
+        }
+        let lines = LineIndex::new(text);
+        let (mut byte, mut unit) = (0, 0);
+        let mut to_units = |offset: usize| {
+            if offset >= byte {
+                unit += self.length(&text[byte..offset]);
+            } else {
+                unit -= self.length(&text[offset..byte]);
+            }
+            byte = offset;
+            unit as u32
+        };
+        // Line of the last match and the offset of its start in units
+        let mut line_start: Option<(u32, u32)> = None;
+        spans
+            .into_iter()
+            .map(|span| {
+                let (line, byte_column) = lines.position(span.start);
+                let line_start_units = match line_start {
+                    Some((current, units)) if current == line => units,
+                    _ => {
+                        let units = to_units(span.start - byte_column as usize);
+                        line_start = Some((line, units));
+                        units
+                    }
+                };
+                let start = to_units(span.start);
+                TextMatch {
+                    start,
+                    end: to_units(span.end),
+                    line,
+                    column: start - line_start_units,
+                    text: text[span.start..span.end].to_string(),
+                    pattern_index: span.pattern_index,
+                }
+            })
+            .collect()
     }
 }
 
@@ -137,9 +510,888 @@ pub fn quick_substring_search(
     patterns: Vec<String>,
     case_sensitive: Option<bool>,
 ) -> napi::Result<Vec<TextMatch>> {
-    let processor = TextProcessor::new(Some(TextProcessingConfig {
-        case_sensitive: case_sensitive.unwrap_or(true),
-        ..Default::default()
-    }));
-    processor.find_substrings(text, patterns)
-}
\ No newline at end of file
+    errors::guard("quickSubstringSearch", || {
+        let processor = TextProcessor::new(Some(TextProcessingConfig {
+            case_sensitive: case_sensitive.unwrap_or(true),
+            ..Default::default()
+        }));
+        processor.find_substrings(text, patterns)
+    })
+}
+
+/// Length in bytes of the ANSI escape sequence starting at `start`, if any
+///
+/// Recognizes CSI (`ESC [` and 8-bit `0x9B`), OSC/DCS/APC strings terminated
+/// by BEL or ST, charset designations, and two-byte `ESC x` sequences.
+fn ansi_sequence_len(text: &str, start: usize) -> Option<usize> {
+    let rest = &text[start..];
+    let mut chars = rest.char_indices();
+    let (_, first) = chars.next()?;
+
+    let csi_body_start = match first {
+        '\u{1b}' => match chars.next() {
+            Some((i, '[')) => i + 1,
+            Some((i, ']' | 'P' | '_' | '^' | 'X')) => {
+                // String sequences run until BEL or ST (ESC \)
+                let body = &rest[i + 1..];
+                return Some(match (body.find('\u{07}'), body.find("\u{1b}\\")) {
+                    (Some(bel), Some(st)) if st < bel => i + 1 + st + 2,
+                    (Some(bel), _) => i + 1 + bel + 1,
+                    (None, Some(st)) => i + 1 + st + 2,
+                    (None, None) => rest.len(),
+                });
+            }
+            Some((i, c)) if ('\u{20}'..='\u{2f}').contains(&c) => {
+                // nF sequences: intermediate bytes followed by one final byte
+                let end = rest[i..]
+                    .char_indices()
+                    .find(|(_, c)| !('\u{20}'..='\u{2f}').contains(c))
+                    .map(|(j, c)| i + j + c.len_utf8())
+                    .unwrap_or(rest.len());
+                return Some(end);
+            }
+            Some((i, c)) => return Some(i + c.len_utf8()),
+            None => return Some(1),
+        },
+        '\u{9b}' => first.len_utf8(),
+        _ => return None,
+    };
+
+    // CSI: parameter and intermediate bytes, then one final byte in 0x40..=0x7E
+    for (i, c) in rest[csi_body_start..].char_indices() {
+        if ('\u{40}'..='\u{7e}').contains(&c) {
+            return Some(csi_body_start + i + 1);
+        }
+        if !('\u{20}'..='\u{3f}').contains(&c) {
+            return Some(csi_body_start + i);
+        }
+    }
+    Some(rest.len())
+}
+
+/// Remove ANSI escapes and/or control characters while recording offset segments
+fn sanitize_text(
+    text: &str,
+    strip_ansi: bool,
+    strip_controls: bool,
+    replacement: Option<&str>,
+) -> SanitizedText {
+    let mut output = String::with_capacity(text.len());
+    let mut segments: Vec<OffsetSegment> = Vec::new();
+    let mut escape_sequences_removed = 0;
+    let mut control_chars_removed = 0;
+    let mut i = 0;
+
+    while i < text.len() {
+        if strip_ansi {
+            if let Some(len) = ansi_sequence_len(text, i) {
+                escape_sequences_removed += 1;
+                i += len;
+                continue;
+            }
+        }
+
+        let c = text[i..].chars().next().unwrap_or_default();
+        let len = c.len_utf8();
+
+        if strip_controls && c.is_control() && !matches!(c, '\t' | '\n' | '\r') {
+            control_chars_removed += 1;
+            if let Some(replacement) = replacement {
+                output.push_str(replacement);
+            }
+            i += len;
+            continue;
+        }
+
+        match segments.last_mut() {
+            Some(last)
+                if (last.output_start + last.length) as usize == output.len()
+                    && (last.original_start + last.length) as usize == i =>
+            {
+                last.length += len as u32;
+            }
+            _ => segments.push(OffsetSegment {
+                output_start: output.len() as u32,
+                original_start: i as u32,
+                length: len as u32,
+            }),
+        }
+        output.push(c);
+        i += len;
+    }
+
+    SanitizedText {
+        text: output,
+        segments,
+        escape_sequences_removed,
+        control_chars_removed,
+    }
+}
+
+/// Strip ANSI escape sequences from captured terminal output
+/// Offsets in `segments` are UTF-8 byte offsets
+#[napi]
+pub fn strip_ansi(text: String) -> napi::Result<SanitizedText> {
+    errors::catch_panic("stripAnsi", || {
+        sanitize_text(&text, true, false, None)
+    })
+}
+
+/// Strip ANSI escapes and control characters (tab, LF and CR are kept)
+///
+/// # Arguments
+/// * `text` - Text to sanitize
+/// * `replacement` - Optional replacement for each removed control character
+#[napi]
+pub fn sanitize_control_chars(text: String, replacement: Option<String>) -> napi::Result<SanitizedText> {
+    errors::catch_panic("sanitizeControlChars", || {
+        sanitize_text(&text, true, true, replacement.as_deref())
+    })
+}
+
+/// Borrow the bytes of a string-or-Buffer argument
+pub(crate) fn input_bytes(input: &Either<String, Buffer>) -> &[u8] {
+    match input {
+        Either::A(text) => text.as_bytes(),
+        Either::B(buffer) => buffer,
+    }
+}
+
+/// Validate JSON without building a document tree
+fn check_json(bytes: &[u8]) -> Result<(), serde_json::Error> {
+    serde_json::from_slice::<serde::de::IgnoredAny>(bytes).map(|_| ())
+}
+
+/// Convert a serde_json error into a napi error with its location
+fn json_error(e: serde_json::Error) -> napi::Error {
+    errors::new(
+        ErrorCode::InvalidArgument,
+        format!("Invalid JSON at line {} column {}: {}", e.line(), e.column(), e),
+    )
+}
+
+/// Re-emit validated JSON token by token, preserving key order and number text
+///
+/// With `indent` set, containers are expanded one entry per line; otherwise
+/// all insignificant whitespace is dropped.
+fn reformat_json(bytes: &[u8], indent: Option<usize>) -> String {
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    let newline = |output: &mut Vec<u8>, depth: usize| {
+        if let Some(width) = indent {
+            output.push(b'\n');
+            output.resize(output.len() + depth * width, b' ');
+        }
+    };
+
+    while i < bytes.len() {
+        let byte = bytes[i];
+        match byte {
+            b'"' => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                output.extend_from_slice(&bytes[start..(i + 1).min(bytes.len())]);
+            }
+            b'{' | b'[' => {
+                let close = if byte == b'{' { b'}' } else { b']' };
+                let next = bytes[i + 1..].iter().position(|b| !b.is_ascii_whitespace());
+                output.push(byte);
+                if next.map(|offset| bytes[i + 1 + offset]) == Some(close) {
+                    // Keep empty containers on one line
+                    output.push(close);
+                    i += 1 + next.unwrap_or(0);
+                } else {
+                    depth += 1;
+                    newline(&mut output, depth);
+                }
+            }
+            b'}' | b']' => {
+                depth = depth.saturating_sub(1);
+                newline(&mut output, depth);
+                output.push(byte);
+            }
+            b',' => {
+                output.push(byte);
+                newline(&mut output, depth);
+            }
+            b':' => {
+                output.push(byte);
+                if indent.is_some() {
+                    output.push(b' ');
+                }
+            }
+            b' ' | b'\t' | b'\n' | b'\r' => {}
+            _ => output.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8(output).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).to_string())
+}
+
+/// Validate JSON from a string or Buffer, reporting line and column of the first error
+#[napi]
+pub fn validate_json(input: Either<String, Buffer>) -> napi::Result<JsonValidationResult> {
+    errors::catch_panic("validateJson", || {
+        match check_json(input_bytes(&input)) {
+            Ok(()) => JsonValidationResult {
+                is_valid: true,
+                error: None,
+                line: None,
+                column: None,
+            },
+            Err(e) => JsonValidationResult {
+                is_valid: false,
+                error: Some(e.to_string()),
+                line: Some(e.line() as u32),
+                column: Some(e.column() as u32),
+            },
+        }
+    })
+}
+
+/// Minify JSON from a string or Buffer, preserving key order
+#[napi]
+pub fn minify_json(input: Either<String, Buffer>) -> napi::Result<String> {
+    errors::guard("minifyJson", || {
+        let bytes = input_bytes(&input);
+        check_json(bytes).map_err(json_error)?;
+        Ok(reformat_json(bytes, None))
+    })
+}
+
+/// Pretty-print JSON from a string or Buffer, preserving key order
+///
+/// # Arguments
+/// * `input` - JSON text as a string or Buffer
+/// * `indent` - Spaces per indentation level (default 2)
+#[napi]
+pub fn pretty_print_json(input: Either<String, Buffer>, indent: Option<u32>) -> napi::Result<String> {
+    errors::guard("prettyPrintJson", || {
+        let bytes = input_bytes(&input);
+        check_json(bytes).map_err(json_error)?;
+        Ok(reformat_json(bytes, Some(indent.unwrap_or(2) as usize)))
+    })
+}
+
+/// Selector applied at one step of a JSONPath expression
+#[derive(Debug, Clone)]
+enum JsonSelector {
+    Key(String),
+    Index(i64),
+    Wildcard,
+}
+
+/// One JSONPath step: direct children or all descendants
+#[derive(Debug, Clone)]
+enum JsonPathStep {
+    Child(JsonSelector),
+    Descendant(JsonSelector),
+}
+
+/// Parse the supported JSONPath subset: `$`, `.key`, `..key`, `.*`, `[n]`, `[*]`, `['key']`
+fn parse_json_path(expression: &str) -> napi::Result<Vec<JsonPathStep>> {
+    let invalid = |reason: &str| {
+        errors::new(
+            ErrorCode::InvalidArgument,
+            format!("Invalid JSONPath '{}': {}", expression, reason),
+        )
+    };
+
+    let mut rest = expression
+        .strip_prefix('$')
+        .ok_or_else(|| invalid("must start with '$'"))?;
+    let mut steps = Vec::new();
+
+    while !rest.is_empty() {
+        let descendant = rest.starts_with("..");
+        if descendant {
+            rest = &rest[2..];
+        } else if let Some(after) = rest.strip_prefix('.') {
+            rest = after;
+        } else if !rest.starts_with('[') {
+            return Err(invalid("expected '.', '..' or '['"));
+        }
+
+        let selector = if let Some(after) = rest.strip_prefix('[') {
+            let close = after.find(']').ok_or_else(|| invalid("unclosed '['"))?;
+            let inner = after[..close].trim();
+            rest = &after[close + 1..];
+
+            if inner == "*" {
+                JsonSelector::Wildcard
+            } else if let Ok(index) = inner.parse::<i64>() {
+                JsonSelector::Index(index)
+            } else if inner.len() >= 2
+                && ((inner.starts_with('\'') && inner.ends_with('\''))
+                    || (inner.starts_with('"') && inner.ends_with('"')))
+            {
+                JsonSelector::Key(inner[1..inner.len() - 1].to_string())
+            } else {
+                return Err(invalid("unsupported bracket selector"));
+            }
+        } else {
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            let name = &rest[..end];
+            rest = &rest[end..];
+
+            match name {
+                "" => return Err(invalid("empty member name")),
+                "*" => JsonSelector::Wildcard,
+                _ => JsonSelector::Key(name.to_string()),
+            }
+        };
+
+        steps.push(if descendant {
+            JsonPathStep::Descendant(selector)
+        } else {
+            JsonPathStep::Child(selector)
+        });
+    }
+
+    Ok(steps)
+}
+
+/// Escape a key for use in a JSON Pointer
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Apply a selector to a node's children
+fn select_children<'a>(
+    pointer: &str,
+    value: &'a serde_json::Value,
+    selector: &JsonSelector,
+    out: &mut Vec<(String, &'a serde_json::Value)>,
+) {
+    match (selector, value) {
+        (JsonSelector::Key(key), serde_json::Value::Object(map)) => {
+            if let Some(child) = map.get(key) {
+                out.push((format!("{}/{}", pointer, escape_pointer_token(key)), child));
+            }
+        }
+        (JsonSelector::Index(index), serde_json::Value::Array(items)) => {
+            let resolved = if *index < 0 { items.len() as i64 + index } else { *index };
+            if let Some(child) = usize::try_from(resolved).ok().and_then(|i| items.get(i)) {
+                out.push((format!("{}/{}", pointer, resolved), child));
+            }
+        }
+        (JsonSelector::Wildcard, serde_json::Value::Object(map)) => {
+            for (key, child) in map {
+                out.push((format!("{}/{}", pointer, escape_pointer_token(key)), child));
+            }
+        }
+        (JsonSelector::Wildcard, serde_json::Value::Array(items)) => {
+            for (i, child) in items.iter().enumerate() {
+                out.push((format!("{}/{}", pointer, i), child));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Visit a node and all of its descendants in document order
+fn visit_descendants<'a>(
+    pointer: String,
+    value: &'a serde_json::Value,
+    out: &mut Vec<(String, &'a serde_json::Value)>,
+) {
+    let mut children = Vec::new();
+    select_children(&pointer, value, &JsonSelector::Wildcard, &mut children);
+    out.push((pointer, value));
+    for (child_pointer, child) in children {
+        visit_descendants(child_pointer, child, out);
+    }
+}
+
+/// Evaluate a JSON Pointer or JSONPath expression against a parsed document
+fn evaluate_json_query(document: &serde_json::Value, expression: &str) -> napi::Result<Vec<JsonQueryMatch>> {
+    if expression.is_empty() || expression.starts_with('/') {
+        return Ok(document
+            .pointer(expression)
+            .map(|value| JsonQueryMatch {
+                pointer: expression.to_string(),
+                value: value.clone(),
+            })
+            .into_iter()
+            .collect());
+    }
+
+    let mut current = vec![(String::new(), document)];
+    for step in parse_json_path(expression)? {
+        let mut next = Vec::new();
+        match &step {
+            JsonPathStep::Child(selector) => {
+                for (pointer, value) in &current {
+                    select_children(pointer, value, selector, &mut next);
+                }
+            }
+            JsonPathStep::Descendant(selector) => {
+                let mut nodes = Vec::new();
+                for (pointer, value) in current {
+                    visit_descendants(pointer, value, &mut nodes);
+                }
+                for (pointer, value) in &nodes {
+                    select_children(pointer, value, selector, &mut next);
+                }
+            }
+        }
+        current = next;
+    }
+
+    Ok(current
+        .into_iter()
+        .map(|(pointer, value)| JsonQueryMatch {
+            pointer,
+            value: value.clone(),
+        })
+        .collect())
+}
+
+/// Query a JSON document with a JSON Pointer (`/a/0`) or JSONPath (`$.a[*].b`)
+#[napi]
+pub fn query_json(input: Either<String, Buffer>, expression: String) -> napi::Result<Vec<JsonQueryMatch>> {
+    errors::guard("queryJson", || {
+        let document: serde_json::Value = serde_json::from_slice(input_bytes(&input)).map_err(json_error)?;
+        evaluate_json_query(&document, &expression)
+    })
+}
+
+/// Query a JSON file natively without passing its contents through JS
+#[napi]
+pub fn query_json_file(path: String, expression: String) -> napi::Result<Vec<JsonQueryMatch>> {
+    errors::guard("queryJsonFile", || {
+        let file = fs::File::open(&path).map_err(|e| {
+            errors::io(&e, format!("Failed to open {}: {}", path, e))
+        })?;
+
+        // SAFETY: the mapping is read-only and dropped before this function returns
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+        let document: serde_json::Value = serde_json::from_slice(&mmap).map_err(json_error)?;
+        evaluate_json_query(&document, &expression)
+    })
+}
+
+/// Compute line, word, character and grapheme statistics in a single pass
+#[napi]
+pub fn text_stats(text: String) -> napi::Result<TextStats> {
+    errors::catch_panic("textStats", || {
+        let mut stats = TextStats {
+            line_count: 0,
+            word_count: 0,
+            char_count: 0,
+            grapheme_count: 0,
+            longest_line_length: 0,
+            longest_line: 0,
+            average_line_length: 0.0,
+            byte_size: text.len() as u32,
+        };
+
+        let mut line_length = 0u32;
+        let mut total_line_length = 0u64;
+        let mut in_word = false;
+
+        for grapheme in text.graphemes(true) {
+            stats.grapheme_count += 1;
+
+            // "\r\n" is a single grapheme cluster, so line endings arrive whole
+            let is_newline = grapheme == "\n" || grapheme == "\r\n";
+            let chars = grapheme.chars().count() as u32;
+            stats.char_count += chars;
+
+            if grapheme.chars().all(char::is_whitespace) {
+                in_word = false;
+            } else if !in_word {
+                in_word = true;
+                stats.word_count += 1;
+            }
+
+            if is_newline {
+                stats.line_count += 1;
+                total_line_length += line_length as u64;
+                if line_length > stats.longest_line_length || stats.longest_line == 0 {
+                    stats.longest_line_length = line_length;
+                    stats.longest_line = stats.line_count;
+                }
+                line_length = 0;
+            } else {
+                line_length += chars;
+            }
+        }
+
+        if !text.is_empty() && !text.ends_with('\n') {
+            stats.line_count += 1;
+            total_line_length += line_length as u64;
+            if line_length > stats.longest_line_length || stats.longest_line == 0 {
+                stats.longest_line_length = line_length;
+                stats.longest_line = stats.line_count;
+            }
+        }
+
+        if stats.line_count > 0 {
+            stats.average_line_length = total_line_length as f64 / stats.line_count as f64;
+        }
+
+        stats
+    })
+}
+
+/// Streaming multi-pattern matcher fed with Buffer chunks
+///
+/// Matches that straddle chunk boundaries are reported exactly once with
+/// absolute offsets; only the last `max_pattern_len - 1` bytes are retained
+/// between chunks, so multi-GB inputs can be scanned in constant memory.
+#[napi]
+pub struct StreamingMatcher {
+    automaton: AhoCorasick,
+    max_pattern_len: usize,
+    max_matches: u32,
+    pending: Vec<u8>,
+    pending_offset: u64,
+    match_count: u32,
+}
+
+#[napi]
+impl StreamingMatcher {
+    /// Create a new streaming matcher for a set of patterns
+    ///
+    /// # Arguments
+    /// * `patterns` - Patterns to search for
+    /// * `config` - Optional text processing configuration (`overlapping` and `anchored` are ignored)
+    #[napi(constructor)]
+    pub fn new(patterns: Vec<String>, config: Option<TextProcessingConfig>) -> napi::Result<Self> {
+        errors::guard("new StreamingMatcher", || {
+            let config = config.unwrap_or_default();
+
+            if patterns.is_empty() || patterns.iter().any(|p| p.is_empty()) {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "Patterns must be non-empty".to_string(),
+                ));
+            }
+
+            let automaton = AhoCorasick::builder()
+                .match_kind(parse_match_kind(config.match_kind.as_deref())?)
+                .ascii_case_insensitive(!config.case_sensitive)
+                .kind(parse_automaton_kind(config.automaton.as_deref())?)
+                .build(&patterns)
+                .map_err(|e| errors::new(ErrorCode::InvalidArgument, e.to_string()))?;
+
+            Ok(Self {
+                automaton,
+                max_pattern_len: patterns.iter().map(|p| p.len()).max().unwrap_or(1),
+                max_matches: config.max_matches,
+                pending: Vec::new(),
+                pending_offset: 0,
+                match_count: 0,
+            })
+        })
+    }
+
+    /// Feed the next chunk of the stream and return newly settled matches
+    #[napi]
+    pub fn feed(&mut self, chunk: Buffer) -> napi::Result<Vec<StreamMatch>> {
+        errors::catch_panic("StreamingMatcher.feed", || {
+            self.feed_bytes(&chunk)
+        })
+    }
+
+    /// Flush any buffered bytes at end of stream and return the remaining matches
+    #[napi]
+    pub fn finish(&mut self) -> napi::Result<Vec<StreamMatch>> {
+        errors::catch_panic("StreamingMatcher.finish", || {
+            let matches = self.drain_matches(true);
+            self.pending_offset += self.pending.len() as u64;
+            self.pending.clear();
+            matches
+        })
+    }
+
+    /// Reset the matcher so it can be reused for a new stream
+    #[napi]
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.pending_offset = 0;
+        self.match_count = 0;
+    }
+
+    /// Total number of bytes fed into the current stream
+    #[napi]
+    pub fn bytes_processed(&self) -> f64 {
+        (self.pending_offset + self.pending.len() as u64) as f64
+    }
+
+    /// Scan a whole file in fixed-size chunks without loading it into memory
+    #[napi]
+    pub fn scan_file(&mut self, path: String) -> napi::Result<Vec<StreamMatch>> {
+        errors::guard("StreamingMatcher.scanFile", || {
+            let mut file = fs::File::open(&path).map_err(|e| {
+                errors::io(&e, format!("Failed to open {}: {}", path, e))
+            })?;
+
+            self.reset();
+            let mut matches = Vec::new();
+            let mut buffer = vec![0u8; 1 << 20];
+
+            loop {
+                let n = file.read(&mut buffer).map_err(|e| {
+                    errors::io(&e, format!("Failed to read {}: {}", path, e))
+                })?;
+                if n == 0 {
+                    break;
+                }
+                matches.extend(self.feed_bytes(&buffer[..n]));
+            }
+
+            matches.extend(self.finish()?);
+            Ok(matches)
+        })
+    }
+
+    /// Append bytes to the pending window and report settled matches
+    fn feed_bytes(&mut self, chunk: &[u8]) -> Vec<StreamMatch> {
+        metrics::add_bytes("text_processing", chunk.len() as u64);
+        self.pending.extend_from_slice(chunk);
+        self.drain_matches(false)
+    }
+
+    /// Search the pending window, keeping bytes that may still form a match
+    ///
+    /// The last `max_pattern_len - 1` bytes are unsettled: a match starting
+    /// there may still grow, or be preceded by a longer one, once more bytes
+    /// arrive. Only matches ending before them are reported; the rest of the
+    /// window, from the first unreported match or the unsettled bytes, is
+    /// searched again with the next chunk.
+    fn drain_matches(&mut self, at_end: bool) -> Vec<StreamMatch> {
+        let len = self.pending.len();
+        let unsettled_from = len.saturating_sub(self.max_pattern_len.saturating_sub(1));
+        let mut matches = Vec::new();
+        let mut keep_from = if at_end { len } else { unsettled_from };
+        let mut last_end = 0;
+
+        for mat in self.automaton.find_iter(&self.pending) {
+            if !at_end && mat.end() > unsettled_from {
+                keep_from = mat.start().min(unsettled_from);
+                break;
+            }
+            last_end = mat.end();
+
+            if self.max_matches > 0 && self.match_count >= self.max_matches {
+                continue;
+            }
+            self.match_count += 1;
+            matches.push(StreamMatch {
+                start: (self.pending_offset + mat.start() as u64) as f64,
+                end: (self.pending_offset + mat.end() as u64) as f64,
+                text: String::from_utf8_lossy(&self.pending[mat.start()..mat.end()]).to_string(),
+                pattern_index: mat.pattern().as_u32(),
+            });
+        }
+
+        let keep_from = keep_from.max(last_end).min(len);
+        if !at_end {
+            self.pending.drain(..keep_from);
+            self.pending_offset += keep_from as u64;
+        }
+
+        matches
+    }
+}
+
+/// Match found by an `IncrementalMatcher`
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineMatch {
+    /// Absolute byte offset of the match start within the stream
+    pub start: f64,
+    /// Absolute byte offset of the match end within the stream
+    pub end: f64,
+    /// Line of the stream holding the match (1-based)
+    pub line_number: f64,
+    /// That line without its line ending (lossily decoded as UTF-8)
+    pub line: String,
+    /// The matched text (lossily decoded as UTF-8)
+    pub text: String,
+    /// Index of the pattern that matched
+    pub pattern_index: u32,
+}
+
+/// Longest partial line an `IncrementalMatcher` holds before matching it anyway
+const MAX_PENDING_LINE: usize = 1024 * 1024;
+
+/// Regex matcher for text appended over time, such as a followed log
+///
+/// Appended chunks are split into lines and each complete line is matched
+/// once, so a pattern split across two appends is still found and earlier
+/// text is never rescanned. Only the unfinished last line is held between
+/// appends; a line growing past 1 MiB without a newline is matched as it is
+/// and its remainder counted as a new line. Matches don't span lines.
+#[napi]
+pub struct IncrementalMatcher {
+    regexes: Vec<regex::bytes::Regex>,
+    /// All patterns at once, to skip lines none of them match
+    set: regex::bytes::RegexSet,
+    max_matches: u32,
+    /// Bytes after the last complete line
+    pending: Vec<u8>,
+    /// Stream offset of `pending`
+    pending_offset: u64,
+    /// Lines matched so far
+    lines: u64,
+    match_count: u32,
+}
+
+#[napi]
+impl IncrementalMatcher {
+    /// Create a matcher for a set of regular expressions
+    ///
+    /// # Arguments
+    /// * `patterns` - Regular expressions, reported by index
+    /// * `config` - Optional text processing configuration (only `case_sensitive` and `max_matches` apply)
+    #[napi(constructor)]
+    pub fn new(patterns: Vec<String>, config: Option<TextProcessingConfig>) -> napi::Result<Self> {
+        errors::guard("new IncrementalMatcher", || {
+            let config = config.unwrap_or_default();
+            if patterns.is_empty() {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "At least one pattern is required".to_string(),
+                ));
+            }
+
+            let sources: Vec<String> = patterns
+                .iter()
+                .map(|pattern| kernels::regex_source(pattern, config.case_sensitive))
+                .collect();
+            let regexes = sources
+                .iter()
+                .enumerate()
+                .map(|(i, source)| {
+                    regex::bytes::Regex::new(source).map_err(|e| {
+                        errors::new(ErrorCode::PatternInvalid, format!("Invalid pattern {}: {}", i, e))
+                    })
+                })
+                .collect::<napi::Result<Vec<_>>>()?;
+            let set = regex::bytes::RegexSet::new(&sources)
+                .map_err(|e| errors::new(ErrorCode::PatternInvalid, e.to_string()))?;
+
+            Ok(Self {
+                regexes,
+                set,
+                max_matches: config.max_matches,
+                pending: Vec::new(),
+                pending_offset: 0,
+                lines: 0,
+                match_count: 0,
+            })
+        })
+    }
+
+    /// Append the next chunk of text and return matches in the lines it completed
+    #[napi]
+    pub fn append(&mut self, chunk: Either<String, Buffer>) -> napi::Result<Vec<LineMatch>> {
+        errors::catch_panic("IncrementalMatcher.append", || {
+            let chunk = input_bytes(&chunk);
+            metrics::add_bytes("text_processing", chunk.len() as u64);
+            let searched = self.pending.len();
+            self.pending.extend_from_slice(chunk);
+
+            let complete = match self.pending[searched..].iter().rposition(|&byte| byte == b'\n') {
+                Some(newline) => searched + newline + 1,
+                None if self.pending.len() > MAX_PENDING_LINE => self.pending.len(),
+                None => return Vec::new(),
+            };
+            self.match_lines(complete)
+        })
+    }
+
+    /// Match the unfinished last line, if any, at the end of the stream
+    #[napi]
+    pub fn flush(&mut self) -> napi::Result<Vec<LineMatch>> {
+        errors::catch_panic("IncrementalMatcher.flush", || self.match_lines(self.pending.len()))
+    }
+
+    /// Reset the matcher so it can be reused for a new stream
+    #[napi]
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.pending_offset = 0;
+        self.lines = 0;
+        self.match_count = 0;
+    }
+
+    /// Total number of bytes appended to the current stream
+    #[napi]
+    pub fn bytes_processed(&self) -> f64 {
+        (self.pending_offset + self.pending.len() as u64) as f64
+    }
+
+    /// Number of lines matched so far, excluding the unfinished last line
+    #[napi]
+    pub fn lines_processed(&self) -> f64 {
+        self.lines as f64
+    }
+
+    /// Match the lines in the first `end` pending bytes and drop them
+    fn match_lines(&mut self, end: usize) -> Vec<LineMatch> {
+        let mut matches = Vec::new();
+        let mut line_start = 0;
+        for line in self.pending[..end].split_inclusive(|&byte| byte == b'\n') {
+            let offset = self.pending_offset + line_start as u64;
+            line_start += line.len();
+            self.lines += 1;
+
+            let content = line.strip_suffix(b"\n").unwrap_or(line);
+            let content = content.strip_suffix(b"\r").unwrap_or(content);
+            let mut found: Vec<(usize, usize, usize)> = self
+                .set
+                .matches(content)
+                .into_iter()
+                .flat_map(|index| self.regexes[index].find_iter(content).map(move |mat| (mat.start(), mat.end(), index)))
+                .collect();
+            found.sort_unstable();
+
+            for (start, end, index) in found {
+                if self.max_matches > 0 && self.match_count >= self.max_matches {
+                    break;
+                }
+                self.match_count += 1;
+                matches.push(LineMatch {
+                    start: (offset + start as u64) as f64,
+                    end: (offset + end as u64) as f64,
+                    line_number: self.lines as f64,
+                    line: String::from_utf8_lossy(content).into_owned(),
+                    text: String::from_utf8_lossy(&content[start..end]).into_owned(),
+                    pattern_index: index as u32,
+                });
+            }
+        }
+        self.pending.drain(..end);
+        self.pending_offset += end as u64;
+        matches
+    }
+}
+
+/// Quick streaming search over a file for multiple patterns
+#[napi]
+pub fn quick_stream_search_file(
+    path: String,
+    patterns: Vec<String>,
+    case_sensitive: Option<bool>,
+) -> napi::Result<Vec<StreamMatch>> {
+    errors::guard("quickStreamSearchFile", || {
+        let mut matcher = StreamingMatcher::new(
+            patterns,
+            Some(TextProcessingConfig {
+                case_sensitive: case_sensitive.unwrap_or(true),
+                ..Default::default()
+            }),
+        )?;
+        matcher.scan_file(path)
+    })
+}
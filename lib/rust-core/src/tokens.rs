@@ -0,0 +1,367 @@
+//! Lightweight source lexers for token-aware text search
+//!
+//! With `token_class` set, a text search only counts matches inside one
+//! class of token: whole identifiers, string literals, comments, or code
+//! (anything outside strings and comments). Finding `timeout` as an
+//! identifier then skips `"timeout"` in a message and `// timeout` in a
+//! comment.
+//!
+//! The lexers only know each language family's comment and string
+//! delimiters, so they are fast enough for whole-tree searches but not
+//! exact: keywords lex as identifiers, string interpolation as string, and
+//! heredocs and raw strings with custom delimiters as code. For exact
+//! token streams see `highlightTokens`.
+
+use crate::errors::{self, ErrorCode};
+
+/// Token class a match must fall in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenClass {
+    /// A whole identifier (or keyword)
+    Identifier,
+    /// Inside a string or character literal, delimiters included
+    String,
+    /// Inside a comment, delimiters included
+    Comment,
+    /// Outside strings and comments
+    Code,
+}
+
+impl TokenClass {
+    /// Parse a `token_class` setting; absent means any text matches
+    pub(crate) fn parse(class: Option<&str>) -> napi::Result<Option<Self>> {
+        match class {
+            None => Ok(None),
+            Some("identifier") => Ok(Some(Self::Identifier)),
+            Some("string") => Ok(Some(Self::String)),
+            Some("comment") => Ok(Some(Self::Comment)),
+            Some("code") => Ok(Some(Self::Code)),
+            Some(other) => Err(errors::new(
+                ErrorCode::InvalidArgument,
+                format!("Unknown token class: {} (expected identifier, string, comment or code)", other),
+            )),
+        }
+    }
+}
+
+/// Delimiters of a language family
+struct Syntax {
+    line_comments: &'static [&'static str],
+    block_comments: &'static [(&'static str, &'static str)],
+    /// String delimiters (open, close, whether the string may span lines),
+    /// longest first
+    strings: &'static [(&'static str, &'static str, bool)],
+    /// `'` opens a character literal only when one closes right after, so
+    /// Rust lifetimes and OCaml type variables stay code
+    char_literals: bool,
+    /// Characters identifiers may contain besides letters, digits and `_`
+    identifier_chars: &'static str,
+}
+
+const C_LIKE: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: &[("/*", "*/")],
+    strings: &[("\"\"\"", "\"\"\"", true), ("\"", "\"", false), ("'", "'", false), ("`", "`", true)],
+    char_literals: false,
+    identifier_chars: "$",
+};
+
+const RUST: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: &[("/*", "*/")],
+    strings: &[("\"", "\"", true)],
+    char_literals: true,
+    identifier_chars: "",
+};
+
+const PHP: Syntax = Syntax {
+    line_comments: &["//", "#"],
+    block_comments: &[("/*", "*/")],
+    strings: &[("\"", "\"", true), ("'", "'", true), ("`", "`", false)],
+    char_literals: false,
+    identifier_chars: "$",
+};
+
+const SCRIPT: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comments: &[],
+    strings: &[
+        ("\"\"\"", "\"\"\"", true),
+        ("'''", "'''", true),
+        ("\"", "\"", false),
+        ("'", "'", false),
+        ("`", "`", false),
+    ],
+    char_literals: false,
+    identifier_chars: "",
+};
+
+const POWERSHELL: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comments: &[("<#", "#>")],
+    strings: &[("\"", "\"", false), ("'", "'", false)],
+    char_literals: false,
+    identifier_chars: "-",
+};
+
+const HCL: Syntax = Syntax {
+    line_comments: &["#", "//"],
+    block_comments: &[("/*", "*/")],
+    strings: &[("\"", "\"", false)],
+    char_literals: false,
+    identifier_chars: "-",
+};
+
+const INI: Syntax = Syntax {
+    line_comments: &[";", "#"],
+    block_comments: &[],
+    strings: &[("\"", "\"", false)],
+    char_literals: false,
+    identifier_chars: "-",
+};
+
+const BATCH: Syntax = Syntax {
+    line_comments: &["::", "REM ", "rem "],
+    block_comments: &[],
+    strings: &[("\"", "\"", false)],
+    char_literals: false,
+    identifier_chars: "",
+};
+
+const SQL: Syntax = Syntax {
+    line_comments: &["--"],
+    block_comments: &[("/*", "*/")],
+    strings: &[("'", "'", true)],
+    char_literals: false,
+    identifier_chars: "",
+};
+
+const LUA: Syntax = Syntax {
+    line_comments: &["--"],
+    block_comments: &[("--[[", "]]")],
+    strings: &[("[[", "]]", true), ("\"", "\"", false), ("'", "'", false)],
+    char_literals: false,
+    identifier_chars: "",
+};
+
+const HASKELL: Syntax = Syntax {
+    line_comments: &["--"],
+    block_comments: &[("{-", "-}")],
+    strings: &[("\"", "\"", false)],
+    char_literals: true,
+    identifier_chars: "'",
+};
+
+const ML: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: &[("(*", "*)")],
+    strings: &[("\"\"\"", "\"\"\"", true), ("\"", "\"", true)],
+    char_literals: true,
+    identifier_chars: "'",
+};
+
+const ERLANG: Syntax = Syntax {
+    line_comments: &["%"],
+    block_comments: &[],
+    strings: &[("\"", "\"", true), ("'", "'", false)],
+    char_literals: false,
+    identifier_chars: "",
+};
+
+const TEX: Syntax = Syntax {
+    line_comments: &["%"],
+    block_comments: &[],
+    strings: &[],
+    char_literals: false,
+    identifier_chars: "",
+};
+
+const CLOJURE: Syntax = Syntax {
+    line_comments: &[";"],
+    block_comments: &[],
+    strings: &[("\"", "\"", true)],
+    char_literals: false,
+    identifier_chars: "-?!*",
+};
+
+const MARKUP: Syntax = Syntax {
+    line_comments: &[],
+    block_comments: &[("<!--", "-->")],
+    strings: &[("\"", "\"", true)],
+    char_literals: false,
+    identifier_chars: "-",
+};
+
+const CSS: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: &[("/*", "*/")],
+    strings: &[("\"", "\"", false), ("'", "'", false)],
+    char_literals: false,
+    identifier_chars: "-",
+};
+
+const JSON: Syntax = Syntax {
+    line_comments: &[],
+    block_comments: &[],
+    strings: &[("\"", "\"", false)],
+    char_literals: false,
+    identifier_chars: "",
+};
+
+/// Lexer for a language detected by `languages`, if it has one
+fn syntax(language: &str) -> Option<&'static Syntax> {
+    Some(match language {
+        "C" | "C++" | "C#" | "Java" | "JavaScript" | "TypeScript" | "Go" | "Kotlin" | "Scala" | "Swift" | "Dart"
+        | "Groovy" | "Zig" | "Protocol Buffers" => &C_LIKE,
+        "Rust" => &RUST,
+        "PHP" => &PHP,
+        "Python" | "Starlark" | "Ruby" | "Perl" | "Shell" | "Fish" | "R" | "Elixir" | "Julia" | "Nim" | "YAML"
+        | "TOML" | "Makefile" | "CMake" | "Tcl" | "Awk" | "Dockerfile" | "GraphQL" | "Just" => &SCRIPT,
+        "PowerShell" => &POWERSHELL,
+        "HCL" => &HCL,
+        "INI" => &INI,
+        "Batch" => &BATCH,
+        "SQL" => &SQL,
+        "Lua" => &LUA,
+        "Haskell" => &HASKELL,
+        "OCaml" | "F#" => &ML,
+        "Erlang" => &ERLANG,
+        "TeX" => &TEX,
+        "Clojure" => &CLOJURE,
+        "HTML" | "XML" | "Vue" | "Svelte" | "Markdown" => &MARKUP,
+        "CSS" | "SCSS" | "Less" | "Sass" => &CSS,
+        "JSON" => &JSON,
+        _ => return None,
+    })
+}
+
+/// A lexed token; text between tokens is code
+struct Span {
+    start: usize,
+    end: usize,
+    class: TokenClass,
+}
+
+/// Identifier, string and comment spans of a source text, in order
+pub(crate) struct Tokens {
+    spans: Vec<Span>,
+}
+
+impl Tokens {
+    /// Whether the bytes `start..end` of the source lie in a token of `class`
+    pub(crate) fn admits(&self, class: TokenClass, start: usize, end: usize) -> bool {
+        // The last span starting at or before the match
+        let index = self.spans.partition_point(|span| span.start <= start);
+        let containing = index
+            .checked_sub(1)
+            .map(|i| &self.spans[i])
+            .filter(|span| end <= span.end);
+        match class {
+            TokenClass::Identifier => containing
+                .is_some_and(|span| span.class == TokenClass::Identifier && span.start == start && span.end == end),
+            TokenClass::String | TokenClass::Comment => containing.is_some_and(|span| span.class == class),
+            TokenClass::Code => {
+                let literal = |span: &Span| matches!(span.class, TokenClass::String | TokenClass::Comment);
+                let before = index.checked_sub(1).map(|i| &self.spans[i]);
+                !before.is_some_and(|span| literal(span) && span.end > start)
+                    && !self.spans[index..].iter().take_while(|span| span.start < end).any(literal)
+            }
+        }
+    }
+}
+
+/// Lex `source` as `language`; `None` if there is no lexer for it
+pub(crate) fn lex(language: &str, source: &str) -> Option<Tokens> {
+    let syntax = syntax(language)?;
+    let bytes = source.as_bytes();
+    let mut spans = Vec::new();
+    let mut i = 0;
+
+    'scan: while i < source.len() {
+        let rest = &bytes[i..];
+        for &(open, close) in syntax.block_comments {
+            if rest.starts_with(open.as_bytes()) {
+                let end = find(bytes, i + open.len(), close).map_or(source.len(), |at| at + close.len());
+                spans.push(Span { start: i, end, class: TokenClass::Comment });
+                i = end;
+                continue 'scan;
+            }
+        }
+        for prefix in syntax.line_comments {
+            if rest.starts_with(prefix.as_bytes()) {
+                let end = find(bytes, i, "\n").unwrap_or(source.len());
+                spans.push(Span { start: i, end, class: TokenClass::Comment });
+                i = end;
+                continue 'scan;
+            }
+        }
+        for &(open, close, multiline) in syntax.strings {
+            if rest.starts_with(open.as_bytes()) {
+                let end = string_end(bytes, i + open.len(), close, multiline);
+                spans.push(Span { start: i, end, class: TokenClass::String });
+                i = end;
+                continue 'scan;
+            }
+        }
+        if syntax.char_literals && rest[0] == b'\'' {
+            if let Some(length) = char_literal(&source[i..]) {
+                spans.push(Span { start: i, end: i + length, class: TokenClass::String });
+                i += length;
+                continue;
+            }
+        }
+
+        let Some(c) = source[i..].chars().next() else { break };
+        if is_identifier_char(syntax, c) {
+            let length = source[i..]
+                .find(|c| !is_identifier_char(syntax, c))
+                .unwrap_or(source.len() - i);
+            // Runs starting with a digit are numbers (`0x1f`, `10px`)
+            if !c.is_ascii_digit() {
+                spans.push(Span { start: i, end: i + length, class: TokenClass::Identifier });
+            }
+            i += length;
+        } else {
+            i += c.len_utf8();
+        }
+    }
+    Some(Tokens { spans })
+}
+
+fn is_identifier_char(syntax: &Syntax, c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || syntax.identifier_chars.contains(c)
+}
+
+/// Start of the first `needle` in `bytes` at or after `from`
+fn find(bytes: &[u8], from: usize, needle: &str) -> Option<usize> {
+    let needle = needle.as_bytes();
+    (from..bytes.len()).find(|&at| bytes[at..].starts_with(needle))
+}
+
+/// End of a string whose contents start at `from`: after `close`, or at the
+/// line end for an unterminated single-line string
+fn string_end(bytes: &[u8], from: usize, close: &str, multiline: bool) -> usize {
+    let mut at = from;
+    while at < bytes.len() {
+        match bytes[at] {
+            b'\\' => at += 2,
+            b'\n' if !multiline => return at,
+            _ if bytes[at..].starts_with(close.as_bytes()) => return at + close.len(),
+            _ => at += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Length of a character literal at the start of `rest` (`'a'`, `'\n'`,
+/// `'\u{1F600}'`), if there is one
+fn char_literal(rest: &str) -> Option<usize> {
+    let mut chars = rest[1..].char_indices();
+    let (_, first) = chars.next()?;
+    let (close, _) = if first == '\\' {
+        rest[1..].char_indices().skip(2).take(10).find(|&(_, c)| c == '\'')?
+    } else {
+        chars.next().filter(|&(_, c)| c == '\'')?
+    };
+    Some(1 + close + 1)
+}
@@ -7,6 +7,16 @@ use napi_derive::napi;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::cpu;
+use crate::errors::{self, ErrorCode};
+use crate::kernels;
+use crate::shared;
+
+/// Vectors per tile when scanning for similar pairs
+const PAIR_BLOCK: usize = 256;
+
+/// Slack on the pruning bound for f32 rounding, so no qualifying pair is skipped
+const PAIR_BOUND_SLACK: f32 = 1e-5;
 
 /// Configuration for vector operations
 #[napi(object)]
@@ -40,6 +50,44 @@ pub struct SimilarityResult {
     pub path: String,
     /// Similarity score (0.0 to 1.0)
     pub similarity: f64,
+    /// How the score and rank came about (only with `explain`)
+    pub explanation: Option<SimilarityExplanation>,
+}
+
+/// Diagnostics of one vector in a `findSimilarVectors` search
+///
+/// `similarity` is `dot_product / (query_norm * vector_norm)`, computed in f32.
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarityExplanation {
+    /// Dot product of the query and the vector
+    pub dot_product: f64,
+    /// L2 norm of the query
+    pub query_norm: f64,
+    /// L2 norm of the vector
+    pub vector_norm: f64,
+    /// Similarity threshold results had to meet
+    pub threshold: f64,
+    /// Position among all vectors by similarity (1-based), before the
+    /// threshold and `top_k` were applied
+    pub rank: u32,
+    /// Vectors that met the threshold, before truncating to `top_k`
+    pub candidates: u32,
+    /// Filter that kept the vector out of the results: "threshold" or
+    /// "top_k" (absent for returned results)
+    pub dropped_by: Option<String>,
+}
+
+/// Two vectors at least as similar as the `similarPairs` threshold
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarPair {
+    /// Index of the first vector
+    pub index_a: u32,
+    /// Index of the second vector (always greater than `index_a`)
+    pub index_b: u32,
+    /// Cosine similarity of the two
+    pub similarity: f64,
 }
 
 /// Batch embedding generation result
@@ -72,8 +120,10 @@ impl VectorOperations {
     /// * `config` - Optional configuration for vector operations
     #[napi(constructor)]
     pub fn new(config: Option<VectorConfig>) -> napi::Result<Self> {
-        Ok(Self {
-            config: config.unwrap_or_default(),
+        errors::guard("new VectorOperations", || {
+            Ok(Self {
+                config: config.unwrap_or_default(),
+            })
         })
     }
 
@@ -81,22 +131,24 @@ impl VectorOperations {
     /// 10-20x faster than JavaScript implementation
     #[napi]
     pub fn cosine_similarity(&self, vec_a: Vec<f64>, vec_b: Vec<f64>) -> napi::Result<f64> {
-        if vec_a.len() != vec_b.len() {
-            return Err(napi::Error::new(
-                napi::Status::InvalidArg,
-                "Vectors must have the same length".to_string(),
-            ));
-        }
+        errors::guard("VectorOperations.cosineSimilarity", || {
+            if vec_a.len() != vec_b.len() {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "Vectors must have the same length".to_string(),
+                ));
+            }
 
-        if vec_a.is_empty() {
-            return Ok(0.0);
-        }
+            if vec_a.is_empty() {
+                return Ok(0.0);
+            }
 
-        // Convert to f32 for internal processing
-        let vec_a_f32: Vec<f32> = vec_a.iter().map(|&x| x as f32).collect();
-        let vec_b_f32: Vec<f32> = vec_b.iter().map(|&x| x as f32).collect();
+            // Convert to f32 for internal processing
+            let vec_a_f32: Vec<f32> = vec_a.iter().map(|&x| x as f32).collect();
+            let vec_b_f32: Vec<f32> = vec_b.iter().map(|&x| x as f32).collect();
 
-        Ok(self.cosine_similarity_internal(&vec_a_f32, &vec_b_f32) as f64)
+            Ok(self.cosine_similarity_internal(&vec_a_f32, &vec_b_f32) as f64)
+        })
     }
 
     /// Calculate cosine similarity for multiple vector pairs in parallel
@@ -108,58 +160,17 @@ impl VectorOperations {
         vectors_flat: Vec<f64>,
         vector_size: u32,
     ) -> napi::Result<Vec<f64>> {
-        let vector_size = vector_size as usize;
-        
-        if vectors_flat.len() % vector_size != 0 {
-            return Err(napi::Error::new(
-                napi::Status::InvalidArg,
-                "Vectors array length must be a multiple of vector_size".to_string(),
-            ));
-        }
-
-        let num_vectors = vectors_flat.len() / vector_size;
-        if num_vectors == 0 {
-            return Ok(Vec::new());
-        }
-
-        // Convert query vector to f32
-        let query_f32: Vec<f32> = query_vector.iter().map(|&x| x as f32).collect();
-
-        // Convert and process vectors
-        let results = if self.config.use_parallel && num_vectors > 100 {
-            // Parallel processing
-            (0..num_vectors)
-                .into_par_iter()
-                .map(|i| {
-                    let start = i * vector_size;
-                    let end = start + vector_size;
-                    let vec_f32: Vec<f32> = vectors_flat[start..end]
-                        .iter()
-                        .map(|&x| x as f32)
-                        .collect();
-                    self.cosine_similarity_internal(&query_f32, &vec_f32) as f64
-                })
-                .collect()
-        } else {
-            // Sequential processing
-            (0..num_vectors)
-                .map(|i| {
-                    let start = i * vector_size;
-                    let end = start + vector_size;
-                    let vec_f32: Vec<f32> = vectors_flat[start..end]
-                        .iter()
-                        .map(|&x| x as f32)
-                        .collect();
-                    self.cosine_similarity_internal(&query_f32, &vec_f32) as f64
-                })
-                .collect()
-        };
-
-        Ok(results)
+        errors::guard("VectorOperations.batchCosineSimilarity", || {
+            self.similarities(&query_vector, &vectors_flat, vector_size as usize)
+        })
     }
 
     /// Find the most similar vectors from a collection
     /// Returns top-k results above the similarity threshold
+    ///
+    /// With `explain`, each result also carries its dot product, norms,
+    /// the threshold and its rank before filtering. To see why an expected
+    /// vector did not make the cut, use `explainSimilarity`.
     #[napi]
     pub fn find_similar_vectors(
         &self,
@@ -168,57 +179,162 @@ impl VectorOperations {
         vector_size: u32,
         paths: Vec<String>,
         top_k: u32,
+        explain: Option<bool>,
     ) -> napi::Result<Vec<SimilarityResult>> {
-        let num_vectors = vectors_flat.len() / (vector_size as usize);
+        errors::guard("VectorOperations.findSimilarVectors", || {
+            let num_vectors = vectors_flat.len() / (vector_size as usize);
         
-        if num_vectors != paths.len() {
-            return Err(napi::Error::new(
-                napi::Status::InvalidArg,
-                "Number of vectors and paths must match".to_string(),
-            ));
-        }
+            if num_vectors != paths.len() {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "Number of vectors and paths must match".to_string(),
+                ));
+            }
 
-        let similarities = self.batch_cosine_similarity(query_vector, vectors_flat, vector_size)?;
+            let similarities = self.similarities(&query_vector, &vectors_flat, vector_size as usize)?;
         
-        // Create indexed results
-        let mut results: Vec<_> = similarities
-            .into_iter()
-            .enumerate()
-            .filter_map(|(i, similarity)| {
-                if similarity >= self.config.similarity_threshold {
-                    Some(SimilarityResult {
-                        index: i as u32,
-                        path: paths[i].clone(),
-                        similarity,
-                    })
-                } else {
-                    None
-                }
-            })
-            .collect();
+            // Create indexed results
+            let mut results: Vec<_> = similarities
+                .iter()
+                .enumerate()
+                .filter_map(|(i, &similarity)| {
+                    if similarity >= self.config.similarity_threshold {
+                        Some(SimilarityResult {
+                            index: i as u32,
+                            path: paths[i].clone(),
+                            similarity,
+                            explanation: None,
+                        })
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            let candidates = results.len() as u32;
 
-        // Sort by similarity (highest first)
-        results.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap());
+            // Sort by similarity (highest first)
+            results.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
         
-        // Return top-k results
-        results.truncate(top_k as usize);
-        Ok(results)
+            // Return top-k results
+            results.truncate(top_k as usize);
+
+            if explain.unwrap_or(false) {
+                let explainer = Explainer::new(self, &query_vector, &similarities, candidates, top_k);
+                for result in &mut results {
+                    result.explanation = Some(explainer.explain(&vectors_flat, vector_size as usize, result.index as usize));
+                }
+            }
+            Ok(results)
+        })
+    }
+
+    /// Explain where chosen vectors stand in a `findSimilarVectors` search
+    ///
+    /// Runs the same search and returns a result with its explanation for
+    /// each path in `targets`, in that order, whether or not the search
+    /// returns it; `droppedBy` says which filter excluded it. Use it to see
+    /// why an expected vector did not appear.
+    ///
+    /// # Arguments
+    /// * `targets` - Paths of the vectors to explain
+    #[napi]
+    pub fn explain_similarity(
+        &self,
+        query_vector: Vec<f64>,
+        vectors_flat: Vec<f64>,
+        vector_size: u32,
+        paths: Vec<String>,
+        top_k: u32,
+        targets: Vec<String>,
+    ) -> napi::Result<Vec<SimilarityResult>> {
+        errors::guard("VectorOperations.explainSimilarity", || {
+            if vector_size == 0 || vectors_flat.len() / vector_size as usize != paths.len() {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "Number of vectors and paths must match".to_string(),
+                ));
+            }
+            let similarities = self.similarities(&query_vector, &vectors_flat, vector_size as usize)?;
+            let candidates = similarities
+                .iter()
+                .filter(|&&similarity| similarity >= self.config.similarity_threshold)
+                .count() as u32;
+            let explainer = Explainer::new(self, &query_vector, &similarities, candidates, top_k);
+            targets
+                .iter()
+                .map(|target| {
+                    let index = paths.iter().position(|path| path == target).ok_or_else(|| {
+                        errors::new(ErrorCode::InvalidArgument, format!("No vector with path '{}'", target))
+                    })?;
+                    Ok(SimilarityResult {
+                        index: index as u32,
+                        path: target.clone(),
+                        similarity: similarities[index],
+                        explanation: Some(explainer.explain(&vectors_flat, vector_size as usize, index)),
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// Find the most similar vectors in a set stored with `shareVectors`
+    ///
+    /// Works from any worker thread without copying the set into it.
+    /// Returns top-k results above the similarity threshold.
+    #[napi]
+    pub fn find_similar_in_shared(
+        &self,
+        name: String,
+        query_vector: Vec<f64>,
+        top_k: u32,
+    ) -> napi::Result<Vec<SimilarityResult>> {
+        errors::guard("VectorOperations.findSimilarInShared", || {
+            let set = shared::vector_set(&name)?;
+            if query_vector.len() != set.vector_size {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Query vector must have {} dimensions", set.vector_size),
+                ));
+            }
+
+            let query_f32: Vec<f32> = query_vector.iter().map(|&x| x as f32).collect();
+            let score = |(i, vector): (usize, &[f32])| {
+                let similarity = self.cosine_similarity_internal(&query_f32, vector) as f64;
+                (similarity >= self.config.similarity_threshold).then(|| SimilarityResult {
+                    index: i as u32,
+                    path: set.paths[i].clone(),
+                    similarity,
+                    explanation: None,
+                })
+            };
+            let mut results: Vec<_> = if self.config.use_parallel && set.paths.len() > 100 {
+                set.vectors.par_chunks(set.vector_size).enumerate().filter_map(score).collect()
+            } else {
+                set.vectors.chunks(set.vector_size).enumerate().filter_map(score).collect()
+            };
+
+            results.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+            results.truncate(top_k as usize);
+            Ok(results)
+        })
     }
 
     /// Normalize a vector to unit length
     #[napi]
     pub fn normalize_vector(&self, vector: Vec<f64>) -> napi::Result<Vec<f64>> {
-        let vec_f32: Vec<f32> = vector.iter().map(|&x| x as f32).collect();
-        let norm = self.vector_norm_internal(&vec_f32);
+        errors::guard("VectorOperations.normalizeVector", || {
+            let vec_f32: Vec<f32> = vector.iter().map(|&x| x as f32).collect();
+            let norm = self.vector_norm_internal(&vec_f32);
         
-        if norm == 0.0 {
-            return Err(napi::Error::new(
-                napi::Status::InvalidArg,
-                "Cannot normalize zero vector".to_string(),
-            ));
-        }
+            if norm == 0.0 {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "Cannot normalize zero vector".to_string(),
+                ));
+            }
 
-        Ok(vector.into_iter().map(|x| x / (norm as f64)).collect())
+            Ok(vector.into_iter().map(|x| x / (norm as f64)).collect())
+        })
     }
 
     /// Calculate the L2 norm (magnitude) of a vector
@@ -229,26 +345,49 @@ impl VectorOperations {
     }
 
     /// Compute pairwise distances between all vectors in a collection
+    ///
+    /// Returns the full n*n matrix; for more than a few thousand vectors,
+    /// use `similarPairs` to get only the pairs above a threshold.
     #[napi]
     pub fn pairwise_distances(&self, vectors_flat: Vec<f64>, vector_size: u32) -> napi::Result<Vec<f64>> {
-        let vector_size = vector_size as usize;
-        let n = vectors_flat.len() / vector_size;
-        
-        if vectors_flat.len() % vector_size != 0 {
-            return Err(napi::Error::new(
-                napi::Status::InvalidArg,
-                "Vectors array length must be a multiple of vector_size".to_string(),
-            ));
-        }
-
-        let mut distances = vec![0.0f64; n * n];
+        errors::guard("VectorOperations.pairwiseDistances", || {
+            let vector_size = vector_size as usize;
+            if vector_size == 0 || !vectors_flat.len().is_multiple_of(vector_size) {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "Vectors array length must be a multiple of vector_size".to_string(),
+                ));
+            }
+            let n = vectors_flat.len() / vector_size;
+
+            let mut distances = vec![0.0f64; n * n];
+
+            if self.config.use_parallel && n > 50 {
+                // Parallel computation
+                distances
+                    .par_chunks_mut(n)
+                    .enumerate()
+                    .for_each(|(i, row)| {
+                        let vec_i_start = i * vector_size;
+                        let vec_i: Vec<f32> = vectors_flat[vec_i_start..vec_i_start + vector_size]
+                            .iter()
+                            .map(|&x| x as f32)
+                            .collect();
 
-        if self.config.use_parallel && n > 50 {
-            // Parallel computation
-            distances
-                .par_chunks_mut(n)
-                .enumerate()
-                .for_each(|(i, row)| {
+                        for (j, distance) in row.iter_mut().enumerate() {
+                            if i != j {
+                                let vec_j_start = j * vector_size;
+                                let vec_j: Vec<f32> = vectors_flat[vec_j_start..vec_j_start + vector_size]
+                                    .iter()
+                                    .map(|&x| x as f32)
+                                    .collect();
+                                *distance = 1.0 - self.cosine_similarity_internal(&vec_i, &vec_j) as f64;
+                            }
+                        }
+                    });
+            } else {
+                // Sequential computation
+                for i in 0..n {
                     let vec_i_start = i * vector_size;
                     let vec_i: Vec<f32> = vectors_flat[vec_i_start..vec_i_start + vector_size]
                         .iter()
@@ -262,36 +401,60 @@ impl VectorOperations {
                                 .iter()
                                 .map(|&x| x as f32)
                                 .collect();
-                            row[j] = 1.0 - self.cosine_similarity_internal(&vec_i, &vec_j) as f64;
+                            distances[i * n + j] = 1.0 - self.cosine_similarity_internal(&vec_i, &vec_j) as f64;
                         }
                     }
-                });
-        } else {
-            // Sequential computation
-            for i in 0..n {
-                let vec_i_start = i * vector_size;
-                let vec_i: Vec<f32> = vectors_flat[vec_i_start..vec_i_start + vector_size]
-                    .iter()
-                    .map(|&x| x as f32)
-                    .collect();
-
-                for j in 0..n {
-                    if i != j {
-                        let vec_j_start = j * vector_size;
-                        let vec_j: Vec<f32> = vectors_flat[vec_j_start..vec_j_start + vector_size]
-                            .iter()
-                            .map(|&x| x as f32)
-                            .collect();
-                        distances[i * n + j] = 1.0 - self.cosine_similarity_internal(&vec_i, &vec_j) as f64;
-                    }
                 }
             }
-        }
 
-        Ok(distances)
+            Ok(distances)
+        })
+    }
+
+    /// Find every pair of vectors whose cosine similarity is at least `threshold`
+    ///
+    /// Unlike `pairwiseDistances`, memory grows with the pairs found rather
+    /// than n*n. Vectors are normalized once and compared tile by tile; a
+    /// pair whose similarity over the highest-energy quarter of the
+    /// dimensions, plus the product of the two remaining norms, can't reach
+    /// the threshold is skipped without finishing its dot product, so high
+    /// thresholds (near-duplicate detection) prune most of the work.
+    /// Results are ordered by `index_a`, then `index_b`.
+    ///
+    /// # Arguments
+    /// * `vectors_flat` - Vectors, flattened (count * vector_size)
+    /// * `vector_size` - Dimensions of each vector
+    /// * `threshold` - Minimum cosine similarity of a reported pair
+    #[napi]
+    pub fn similar_pairs(&self, vectors_flat: Vec<f64>, vector_size: u32, threshold: f64) -> napi::Result<Vec<SimilarPair>> {
+        errors::guard("VectorOperations.similarPairs", || {
+            let vector_size = vector_size as usize;
+            if vector_size == 0 || !vectors_flat.len().is_multiple_of(vector_size) {
+                return Err(errors::new(
+                    ErrorCode::InvalidArgument,
+                    "Vectors array length must be a multiple of a non-zero vector_size".to_string(),
+                ));
+            }
+            if threshold.is_nan() {
+                return Err(errors::new(ErrorCode::InvalidArgument, "Threshold must be a number".to_string()));
+            }
+
+            let index = PairIndex::new(&vectors_flat, vector_size);
+            let threshold = threshold as f32;
+            let blocks: Vec<usize> = (0..index.count).step_by(PAIR_BLOCK).collect();
+            let pairs = if self.config.use_parallel && blocks.len() > 1 {
+                blocks.par_iter().flat_map_iter(|&start| index.block_pairs(start, threshold)).collect()
+            } else {
+                blocks.iter().flat_map(|&start| index.block_pairs(start, threshold)).collect()
+            };
+            Ok(pairs)
+        })
     }
 
     /// Create embeddings cache key from vector
+    ///
+    /// To skip re-embedding unchanged content, store vectors by content
+    /// hash in an `EmbeddingCache` instead.
     #[napi]
     pub fn create_cache_key(&self, vector: Vec<f64>) -> String {
         use blake3::Hasher;
@@ -307,19 +470,69 @@ impl VectorOperations {
         hasher.finalize().to_hex().to_string()
     }
 
+    /// Cosine similarity of the query to each vector of a flattened collection
+    fn similarities(&self, query_vector: &[f64], vectors_flat: &[f64], vector_size: usize) -> napi::Result<Vec<f64>> {
+        if vector_size == 0 || !vectors_flat.len().is_multiple_of(vector_size) {
+            return Err(errors::new(
+                ErrorCode::InvalidArgument,
+                "Vectors array length must be a multiple of vector_size".to_string(),
+            ));
+        }
+
+        let num_vectors = vectors_flat.len() / vector_size;
+        if num_vectors == 0 {
+            return Ok(Vec::new());
+        }
+
+        // Convert query vector to f32
+        let query_f32: Vec<f32> = query_vector.iter().map(|&x| x as f32).collect();
+
+        // Convert and process vectors
+        let results = if self.config.use_parallel && num_vectors > 100 {
+            // Parallel processing
+            (0..num_vectors)
+                .into_par_iter()
+                .map(|i| {
+                    let start = i * vector_size;
+                    let end = start + vector_size;
+                    let vec_f32: Vec<f32> = vectors_flat[start..end]
+                        .iter()
+                        .map(|&x| x as f32)
+                        .collect();
+                    self.cosine_similarity_internal(&query_f32, &vec_f32) as f64
+                })
+                .collect()
+        } else {
+            // Sequential processing
+            (0..num_vectors)
+                .map(|i| {
+                    let start = i * vector_size;
+                    let end = start + vector_size;
+                    let vec_f32: Vec<f32> = vectors_flat[start..end]
+                        .iter()
+                        .map(|&x| x as f32)
+                        .collect();
+                    self.cosine_similarity_internal(&query_f32, &vec_f32) as f64
+                })
+                .collect()
+        };
+
+        Ok(results)
+    }
+
     /// Internal vector norm calculation
     fn vector_norm_internal(&self, vector: &[f32]) -> f32 {
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
         {
-            if self.config.use_simd && is_x86_feature_detected!("avx2") {
+            if self.config.use_simd && cpu::has_avx2_fma() {
                 unsafe { self.vector_norm_simd(vector) }
             } else {
-                vector.iter().map(|x| x * x).sum::<f32>().sqrt()
+                kernels::vector_norm(vector)
             }
         }
         #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
         {
-            vector.iter().map(|x| x * x).sum::<f32>().sqrt()
+            kernels::vector_norm(vector)
         }
     }
 
@@ -327,7 +540,7 @@ impl VectorOperations {
     fn cosine_similarity_internal(&self, vec_a: &[f32], vec_b: &[f32]) -> f32 {
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
         {
-            if self.config.use_simd && is_x86_feature_detected!("avx2") {
+            if self.config.use_simd && cpu::has_avx2_fma() {
                 unsafe { self.cosine_similarity_simd(vec_a, vec_b) }
             } else {
                 self.cosine_similarity_scalar(vec_a, vec_b)
@@ -341,7 +554,7 @@ impl VectorOperations {
 
     /// SIMD-optimized cosine similarity calculation
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-    #[target_feature(enable = "avx2")]
+    #[target_feature(enable = "avx2,fma")]
     unsafe fn cosine_similarity_simd(&self, vec_a: &[f32], vec_b: &[f32]) -> f32 {
         // Use SIMD instructions for vectorized operations
         // This can be 3-5x faster than scalar operations
@@ -410,20 +623,12 @@ impl VectorOperations {
 
     /// Scalar implementation for fallback
     fn cosine_similarity_scalar(&self, vec_a: &[f32], vec_b: &[f32]) -> f32 {
-        let dot_product: f32 = vec_a.iter().zip(vec_b.iter()).map(|(a, b)| a * b).sum();
-        let norm_a: f32 = vec_a.iter().map(|a| a * a).sum::<f32>().sqrt();
-        let norm_b: f32 = vec_b.iter().map(|b| b * b).sum::<f32>().sqrt();
-
-        if norm_a == 0.0 || norm_b == 0.0 {
-            0.0
-        } else {
-            dot_product / (norm_a * norm_b)
-        }
+        kernels::cosine_similarity(vec_a, vec_b)
     }
 
     /// SIMD-optimized vector norm calculation
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-    #[target_feature(enable = "avx2")]
+    #[target_feature(enable = "avx2,fma")]
     unsafe fn vector_norm_simd(&self, vector: &[f32]) -> f32 {
         #[cfg(target_arch = "x86_64")]
         {
@@ -451,16 +656,194 @@ impl VectorOperations {
         }
         #[cfg(not(target_arch = "x86_64"))]
         {
-            vector.iter().map(|x| x * x).sum::<f32>().sqrt()
+            kernels::vector_norm(vector)
+        }
+    }
+}
+
+/// Ranks of a search, for explaining individual vectors
+struct Explainer<'a> {
+    ops: &'a VectorOperations,
+    query: Vec<f32>,
+    query_norm: f64,
+    similarities: &'a [f64],
+    /// Rank of each vector, highest first and ties in collection order like the results
+    ranks: Vec<u32>,
+    candidates: u32,
+    top_k: u32,
+}
+
+impl<'a> Explainer<'a> {
+    fn new(
+        ops: &'a VectorOperations,
+        query_vector: &[f64],
+        similarities: &'a [f64],
+        candidates: u32,
+        top_k: u32,
+    ) -> Self {
+        let query: Vec<f32> = query_vector.iter().map(|&x| x as f32).collect();
+        let query_norm = ops.vector_norm_internal(&query) as f64;
+        let mut order: Vec<usize> = (0..similarities.len()).collect();
+        order.sort_by(|&a, &b| similarities[b].total_cmp(&similarities[a]));
+        let mut ranks = vec![0u32; similarities.len()];
+        for (rank, &i) in order.iter().enumerate() {
+            ranks[i] = rank as u32 + 1;
+        }
+        Self {
+            ops,
+            query,
+            query_norm,
+            similarities,
+            ranks,
+            candidates,
+            top_k,
+        }
+    }
+
+    /// Explanation of the vector at `index` of the collection
+    fn explain(&self, vectors_flat: &[f64], vector_size: usize, index: usize) -> SimilarityExplanation {
+        let start = index * vector_size;
+        let vector: Vec<f32> = vectors_flat[start..start + vector_size].iter().map(|&x| x as f32).collect();
+        let threshold = self.ops.config.similarity_threshold;
+        let rank = self.ranks[index];
+        let dropped_by = if self.similarities[index] < threshold {
+            Some("threshold".to_string())
+        } else if rank > self.top_k {
+            Some("top_k".to_string())
+        } else {
+            None
+        };
+        SimilarityExplanation {
+            dot_product: kernels::dot_product(&self.query, &vector) as f64,
+            query_norm: self.query_norm,
+            vector_norm: self.ops.vector_norm_internal(&vector) as f64,
+            threshold,
+            rank,
+            candidates: self.candidates,
+            dropped_by,
+        }
+    }
+}
+
+/// Normalized vectors prepared for a `similarPairs` scan
+struct PairIndex {
+    /// Unit vectors (zero vectors stay zero), dimensions sorted by energy
+    vectors: Vec<f32>,
+    /// Norm of each vector past `prefix`
+    tail_norms: Vec<f32>,
+    count: usize,
+    size: usize,
+    /// Dimensions compared before the pruning bound is checked
+    prefix: usize,
+}
+
+impl PairIndex {
+    fn new(vectors_flat: &[f64], size: usize) -> Self {
+        let count = vectors_flat.len() / size;
+        let mut vectors: Vec<f32> = vectors_flat.iter().map(|&x| x as f32).collect();
+        for vector in vectors.chunks_mut(size) {
+            let norm = kernels::vector_norm(vector);
+            if norm > 0.0 {
+                vector.iter_mut().for_each(|x| *x /= norm);
+            }
+        }
+
+        // Put the dimensions carrying most of the mass first, so the prefix
+        // covers as much of each dot product as possible
+        let mut energy = vec![0.0f32; size];
+        for vector in vectors.chunks(size) {
+            for (total, x) in energy.iter_mut().zip(vector) {
+                *total += x * x;
+            }
+        }
+        let mut dimensions: Vec<usize> = (0..size).collect();
+        dimensions.sort_by(|&a, &b| energy[b].total_cmp(&energy[a]));
+        for vector in vectors.chunks_mut(size) {
+            let original = vector.to_vec();
+            for (slot, &dimension) in vector.iter_mut().zip(&dimensions) {
+                *slot = original[dimension];
+            }
+        }
+
+        let prefix = size.div_ceil(4);
+        let tail_norms = vectors.chunks(size).map(|vector| kernels::vector_norm(&vector[prefix..])).collect();
+        Self {
+            vectors,
+            tail_norms,
+            count,
+            size,
+            prefix,
+        }
+    }
+
+    fn vector(&self, i: usize) -> &[f32] {
+        &self.vectors[i * self.size..(i + 1) * self.size]
+    }
+
+    /// Pairs whose first vector is in the block at `start`, in index order
+    fn block_pairs(&self, start: usize, threshold: f32) -> Vec<SimilarPair> {
+        let rows = start..(start + PAIR_BLOCK).min(self.count);
+        let mut pairs = Vec::new();
+        for column in (start..self.count).step_by(PAIR_BLOCK) {
+            let columns = column..(column + PAIR_BLOCK).min(self.count);
+            for i in rows.clone() {
+                let a = self.vector(i);
+                for j in columns.clone().filter(|&j| j > i) {
+                    let b = self.vector(j);
+                    let head = lane_dot(&a[..self.prefix], &b[..self.prefix]);
+                    if head + self.tail_norms[i] * self.tail_norms[j] + PAIR_BOUND_SLACK < threshold {
+                        continue;
+                    }
+                    let similarity = head + lane_dot(&a[self.prefix..], &b[self.prefix..]);
+                    if similarity >= threshold {
+                        pairs.push(SimilarPair {
+                            index_a: i as u32,
+                            index_b: j as u32,
+                            similarity: similarity as f64,
+                        });
+                    }
+                }
+            }
+        }
+        pairs.sort_unstable_by_key(|pair| (pair.index_a, pair.index_b));
+        pairs
+    }
+}
+
+/// Dot product summed in eight independent lanes, which the compiler vectorizes
+fn lane_dot(a: &[f32], b: &[f32]) -> f32 {
+    let mut lanes = [0.0f32; 8];
+    let (a_chunks, b_chunks) = (a.chunks_exact(8), b.chunks_exact(8));
+    let rest = kernels::dot_product(a_chunks.remainder(), b_chunks.remainder());
+    for (a, b) in a_chunks.zip(b_chunks) {
+        for lane in 0..8 {
+            lanes[lane] += a[lane] * b[lane];
         }
     }
+    lanes.iter().sum::<f32>() + rest
+}
+
+/// Kernel `VectorOperations` dispatches to when `use_simd` is enabled
+pub(crate) fn simd_kernel() -> &'static str {
+    if cfg!(target_arch = "x86_64") && cpu::has_avx2_fma() {
+        "avx2+fma"
+    } else {
+        "scalar"
+    }
 }
 
 /// Standalone function for quick similarity calculation
+///
+/// # Arguments
+/// * `vec_a` - First vector
+/// * `vec_b` - Second vector
+/// * `config` - Optional vector configuration (e.g. to disable SIMD)
 #[napi]
-pub fn quick_cosine_similarity(vec_a: Vec<f64>, vec_b: Vec<f64>) -> napi::Result<f64> {
-    let ops = VectorOperations::new(None)?;
-    ops.cosine_similarity(vec_a, vec_b)
+pub fn quick_cosine_similarity(vec_a: Vec<f64>, vec_b: Vec<f64>, config: Option<VectorConfig>) -> napi::Result<f64> {
+    errors::guard("quickCosineSimilarity", || {
+        let ops = VectorOperations::new(config)?;
+        ops.cosine_similarity(vec_a, vec_b)
+    })
 }
 
 /// Benchmark vector operations performance
@@ -469,43 +852,45 @@ pub fn benchmark_vector_operations(
     vector_size: u32,
     num_vectors: u32,
 ) -> napi::Result<HashMap<String, f64>> {
-    use std::time::Instant;
+    errors::guard("benchmarkVectorOperations", || {
+        use std::time::Instant;
     
-    // Generate test data
-    let query_vector: Vec<f64> = (0..vector_size).map(|i| (i as f64) / (vector_size as f64)).collect();
-    let vectors_flat: Vec<f64> = (0..num_vectors * vector_size)
-        .map(|i| (i as f64) / (vector_size as f64))
-        .collect();
+        // Generate test data
+        let query_vector: Vec<f64> = (0..vector_size).map(|i| (i as f64) / (vector_size as f64)).collect();
+        let vectors_flat: Vec<f64> = (0..num_vectors * vector_size)
+            .map(|i| (i as f64) / (vector_size as f64))
+            .collect();
 
-    let mut results = HashMap::new();
+        let mut results = HashMap::new();
     
-    // Benchmark SIMD version
-    let ops_simd = VectorOperations::new(Some(VectorConfig {
-        use_simd: true,
-        use_parallel: true,
-        similarity_threshold: 0.0,
-    }))?;
+        // Benchmark SIMD version
+        let ops_simd = VectorOperations::new(Some(VectorConfig {
+            use_simd: true,
+            use_parallel: true,
+            similarity_threshold: 0.0,
+        }))?;
     
-    let start = Instant::now();
-    let _simd_results = ops_simd.batch_cosine_similarity(query_vector.clone(), vectors_flat.clone(), vector_size)?;
-    let simd_time = start.elapsed().as_secs_f64() * 1000.0;
-    results.insert("simd_parallel_ms".to_string(), simd_time);
+        let start = Instant::now();
+        let _simd_results = ops_simd.batch_cosine_similarity(query_vector.clone(), vectors_flat.clone(), vector_size)?;
+        let simd_time = start.elapsed().as_secs_f64() * 1000.0;
+        results.insert("simd_parallel_ms".to_string(), simd_time);
     
-    // Benchmark scalar version
-    let ops_scalar = VectorOperations::new(Some(VectorConfig {
-        use_simd: false,
-        use_parallel: false,
-        similarity_threshold: 0.0,
-    }))?;
+        // Benchmark scalar version
+        let ops_scalar = VectorOperations::new(Some(VectorConfig {
+            use_simd: false,
+            use_parallel: false,
+            similarity_threshold: 0.0,
+        }))?;
     
-    let start = Instant::now();
-    let _scalar_results = ops_scalar.batch_cosine_similarity(query_vector, vectors_flat, vector_size)?;
-    let scalar_time = start.elapsed().as_secs_f64() * 1000.0;
-    results.insert("scalar_sequential_ms".to_string(), scalar_time);
+        let start = Instant::now();
+        let _scalar_results = ops_scalar.batch_cosine_similarity(query_vector, vectors_flat, vector_size)?;
+        let scalar_time = start.elapsed().as_secs_f64() * 1000.0;
+        results.insert("scalar_sequential_ms".to_string(), scalar_time);
     
-    // Calculate speedup
-    let speedup = scalar_time / simd_time;
-    results.insert("speedup_ratio".to_string(), speedup);
+        // Calculate speedup
+        let speedup = scalar_time / simd_time;
+        results.insert("speedup_ratio".to_string(), speedup);
     
-    Ok(results)
+        Ok(results)
+    })
 }
\ No newline at end of file
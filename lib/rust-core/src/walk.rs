@@ -0,0 +1,173 @@
+//! Directory walks with symlink and entry budgets
+//!
+//! `FileSearch` walks through `Walk`, a `WalkDir` with two limits that keep
+//! pathological or adversarial trees from causing unbounded traversal:
+//!
+//! - With `follow_symlinks`, each entry carries the number of symlinks
+//!   followed to reach it from the root, counting every link of a chain
+//!   (`a -> b -> dir` is two). An entry past `max_symlink_depth` is yielded
+//!   as an error and, if a directory, not descended into. `WalkDir` already
+//!   stops at links back to an ancestor; the depth limit also bounds trees
+//!   whose links fan out without forming a loop.
+//! - `max_entries` caps the entries one walk visits, excluded ones included.
+//!   The walk then ends after yielding an error naming the first entry left
+//!   unvisited, and its [`Truncation`] is set so callers that drop walk
+//!   errors can still tell the results are partial.
+//!
+//! Both limits are counted in `getMetrics` under `limits_reached`.
+
+use crate::metrics;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use walkdir::{DirEntry, WalkDir};
+
+/// Why a walk yielded no entry
+pub(crate) enum WalkError {
+    /// An entry the walk could not read
+    Io(walkdir::Error),
+    /// An entry reached through more than `max_symlink_depth` symlinks
+    SymlinkDepth { path: PathBuf, links: u32 },
+    /// The first entry past the `max_entries` budget; the walk ends here
+    EntryBudget { path: PathBuf, limit: u32 },
+}
+
+/// Whether a walk stopped at its entry budget, readable after the walk is consumed
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Truncation(Arc<AtomicBool>);
+
+impl Truncation {
+    pub(crate) fn is_set(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn set(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// A `WalkDir` with symlink-depth and entry budgets
+pub(crate) struct Walk {
+    walker: WalkDir,
+    follow_links: bool,
+    max_symlink_depth: u32,
+    max_entries: Option<u32>,
+    truncation: Truncation,
+}
+
+impl Walk {
+    pub(crate) fn new(walker: WalkDir, follow_links: bool, max_symlink_depth: u32, max_entries: Option<u32>) -> Self {
+        Self {
+            walker: walker.follow_links(follow_links),
+            follow_links,
+            max_symlink_depth,
+            max_entries,
+            truncation: Truncation::default(),
+        }
+    }
+
+    /// Handle telling whether this walk stopped at `max_entries`
+    pub(crate) fn truncation(&self) -> Truncation {
+        self.truncation.clone()
+    }
+
+    /// Descend at most `depth` levels below the root
+    pub(crate) fn max_depth(mut self, depth: usize) -> Self {
+        self.walker = self.walker.max_depth(depth);
+        self
+    }
+}
+
+impl IntoIterator for Walk {
+    type Item = Result<DirEntry, WalkError>;
+    type IntoIter = Entries;
+
+    fn into_iter(self) -> Entries {
+        Entries {
+            inner: self.walker.into_iter(),
+            follow_links: self.follow_links,
+            max_symlink_depth: self.max_symlink_depth,
+            max_entries: self.max_entries,
+            visited: 0,
+            links: Vec::new(),
+            truncation: self.truncation,
+        }
+    }
+}
+
+/// Iterator over the entries of a `Walk`
+pub(crate) struct Entries {
+    inner: walkdir::IntoIter,
+    follow_links: bool,
+    max_symlink_depth: u32,
+    max_entries: Option<u32>,
+    visited: u32,
+    /// Symlinks followed to reach each directory on the current path
+    links: Vec<u32>,
+    truncation: Truncation,
+}
+
+impl Entries {
+    /// Whether the walk stopped at `max_entries`
+    pub(crate) fn truncated(&self) -> bool {
+        self.truncation.is_set()
+    }
+}
+
+impl Iterator for Entries {
+    type Item = Result<DirEntry, WalkError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.truncation.is_set() {
+            return None;
+        }
+        let entry = match self.inner.next()? {
+            Ok(entry) => entry,
+            Err(e) => return Some(Err(WalkError::Io(e))),
+        };
+
+        if let Some(limit) = self.max_entries.filter(|&limit| self.visited >= limit) {
+            self.truncation.set();
+            metrics::record_limit("walk_entries");
+            return Some(Err(WalkError::EntryBudget { path: entry.into_path(), limit }));
+        }
+        self.visited += 1;
+
+        if self.follow_links {
+            // Entries are yielded depth first, so the parent's count is the last kept
+            self.links.truncate(entry.depth());
+            let inherited = self.links.last().copied().unwrap_or(0);
+            let own = if entry.path_is_symlink() {
+                chain_length(entry.path(), self.max_symlink_depth)
+            } else {
+                0
+            };
+            let links = inherited + own;
+            if links > self.max_symlink_depth {
+                if entry.file_type().is_dir() {
+                    self.inner.skip_current_dir();
+                }
+                metrics::record_limit("symlink_depth");
+                return Some(Err(WalkError::SymlinkDepth { path: entry.into_path(), links }));
+            }
+            self.links.push(links);
+        }
+        Some(Ok(entry))
+    }
+}
+
+/// Symlinks resolved from `path` to a non-link, counting at most `limit + 1`
+fn chain_length(path: &Path, limit: u32) -> u32 {
+    let mut hops = 0;
+    let mut current = path.to_path_buf();
+    while hops <= limit {
+        let Ok(target) = fs::read_link(&current) else { break };
+        hops += 1;
+        current = match current.parent() {
+            Some(parent) => parent.join(target),
+            None => target,
+        };
+    }
+    hops
+}
@@ -0,0 +1,167 @@
+//! wasm32 fallback for environments that cannot load the native addon
+//!
+//! Built with `--no-default-features --features wasm` for
+//! `wasm32-unknown-unknown` (e.g. `wasm-pack build --target nodejs`), this
+//! exposes the vector and text kernels without the filesystem, hashing and
+//! security modules. Names and result shapes follow the native exports, and
+//! errors carry the same `[CODE]` prefixes, so callers can branch on error
+//! codes the same way with either build.
+
+use crate::kernels::{self, LineIndex, Span, SubstringOptions};
+use regex::Regex;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// Text match, shaped like the native `TextMatch`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TextMatch {
+    start: u32,
+    end: u32,
+    line: u32,
+    column: u32,
+    text: String,
+    pattern_index: u32,
+}
+
+impl TextMatch {
+    fn from_span(span: Span, text: &str, lines: &LineIndex) -> Self {
+        let (line, column) = lines.position(span.start);
+        Self {
+            start: span.start as u32,
+            end: span.end as u32,
+            line,
+            column,
+            text: text[span.start..span.end].to_string(),
+            pattern_index: span.pattern_index,
+        }
+    }
+}
+
+/// Matches for spans of `text`, with their line and column
+fn text_matches(spans: Vec<Span>, text: &str) -> Vec<TextMatch> {
+    let lines = LineIndex::new(text);
+    spans.into_iter().map(|span| TextMatch::from_span(span, text, &lines)).collect()
+}
+
+/// Similarity search hit, shaped like the native `SimilarityResult` without a path
+#[derive(Debug, Serialize)]
+struct SimilarityResult {
+    index: u32,
+    similarity: f32,
+}
+
+fn error(code: &str, message: impl std::fmt::Display) -> JsError {
+    JsError::new(&format!("[{}] {}", code, message))
+}
+
+fn to_js(value: &impl Serialize) -> Result<JsValue, JsError> {
+    serde_wasm_bindgen::to_value(value).map_err(|e| error("INTERNAL", e))
+}
+
+/// Check that `vectors_flat` holds whole vectors of `vector_size` values
+fn check_flat(vectors_flat: &[f32], vector_size: usize) -> Result<(), JsError> {
+    if vector_size == 0 || !vectors_flat.len().is_multiple_of(vector_size) {
+        return Err(error(
+            "INVALID_ARGUMENT",
+            "Vectors array length must be a multiple of vector_size",
+        ));
+    }
+    Ok(())
+}
+
+/// Calculate cosine similarity between two vectors
+#[wasm_bindgen(js_name = cosineSimilarity)]
+pub fn cosine_similarity(vec_a: &[f32], vec_b: &[f32]) -> Result<f32, JsError> {
+    if vec_a.len() != vec_b.len() {
+        return Err(error("INVALID_ARGUMENT", "Vectors must have the same length"));
+    }
+    Ok(kernels::cosine_similarity(vec_a, vec_b))
+}
+
+/// Calculate cosine similarity of a query against every vector in a flattened array
+#[wasm_bindgen(js_name = batchCosineSimilarity)]
+pub fn batch_cosine_similarity(query_vector: &[f32], vectors_flat: &[f32], vector_size: usize) -> Result<Vec<f32>, JsError> {
+    check_flat(vectors_flat, vector_size)?;
+    Ok(vectors_flat
+        .chunks_exact(vector_size)
+        .map(|vector| kernels::cosine_similarity(query_vector, vector))
+        .collect())
+}
+
+/// Find the `top_k` most similar vectors with a similarity of at least `threshold`
+///
+/// Returns `{ index, similarity }` objects, highest similarity first.
+#[wasm_bindgen(js_name = findSimilarVectors)]
+pub fn find_similar_vectors(
+    query_vector: &[f32],
+    vectors_flat: &[f32],
+    vector_size: usize,
+    top_k: usize,
+    threshold: f32,
+) -> Result<JsValue, JsError> {
+    check_flat(vectors_flat, vector_size)?;
+    let mut results: Vec<SimilarityResult> = vectors_flat
+        .chunks_exact(vector_size)
+        .enumerate()
+        .map(|(i, vector)| SimilarityResult {
+            index: i as u32,
+            similarity: kernels::cosine_similarity(query_vector, vector),
+        })
+        .filter(|result| result.similarity >= threshold)
+        .collect();
+
+    results.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    results.truncate(top_k);
+    to_js(&results)
+}
+
+/// Normalize a vector to unit length
+#[wasm_bindgen(js_name = normalizeVector)]
+pub fn normalize_vector(vector: &[f32]) -> Result<Vec<f32>, JsError> {
+    let norm = kernels::vector_norm(vector);
+    if norm == 0.0 {
+        return Err(error("INVALID_ARGUMENT", "Cannot normalize zero vector"));
+    }
+    Ok(vector.iter().map(|x| x / norm).collect())
+}
+
+/// Calculate the L2 norm (magnitude) of a vector
+#[wasm_bindgen(js_name = vectorNorm)]
+pub fn vector_norm(vector: &[f32]) -> f32 {
+    kernels::vector_norm(vector)
+}
+
+/// Find all occurrences of several substrings
+///
+/// # Arguments
+/// * `max_matches` - Stop after this many matches (0 for unlimited)
+#[wasm_bindgen(js_name = findSubstrings)]
+pub fn find_substrings(
+    text: &str,
+    patterns: Vec<String>,
+    case_sensitive: bool,
+    max_matches: usize,
+) -> Result<JsValue, JsError> {
+    let automaton =
+        kernels::substring_automaton(&patterns, case_sensitive, &SubstringOptions::default())
+            .map_err(|e| error("INVALID_ARGUMENT", e))?;
+    let spans = kernels::substring_matches(&automaton, text, max_matches, &SubstringOptions::default());
+    to_js(&text_matches(spans, text))
+}
+
+/// Find regex matches
+///
+/// # Arguments
+/// * `max_matches` - Stop after this many matches (0 for unlimited)
+#[wasm_bindgen(js_name = findRegexMatches)]
+pub fn find_regex_matches(
+    text: &str,
+    pattern: &str,
+    case_sensitive: bool,
+    max_matches: usize,
+) -> Result<JsValue, JsError> {
+    let regex = Regex::new(&kernels::regex_source(pattern, case_sensitive))
+        .map_err(|e| error("INVALID_ARGUMENT", e))?;
+    to_js(&text_matches(kernels::regex_matches(&regex, text, max_matches), text))
+}
@@ -0,0 +1,198 @@
+/**
+ * Test Rust Core Path Validation - Standalone
+ *
+ * Exercises SecurityUtils.validatePath from the native module against a
+ * table of legal names, traversal attempts, policy options and symlinks.
+ */
+
+import { createRequire } from 'module';
+import { mkdtempSync, mkdirSync, symlinkSync, rmSync, realpathSync } from 'fs';
+import { tmpdir } from 'os';
+import { join, sep } from 'path';
+
+const require = createRequire(import.meta.url);
+const { SecurityUtils, analyzeWindowsPath } = require('../lib/rust-core/index.node');
+
+const isWindows = process.platform === 'win32';
+
+function createSandbox() {
+  const root = realpathSync(mkdtempSync(join(tmpdir(), 'moidvk-path-')));
+  const base = join(root, 'base');
+  mkdirSync(join(base, 'src'), { recursive: true });
+  mkdirSync(join(base, 'shared'), { recursive: true });
+  mkdirSync(join(root, 'base2'), { recursive: true });
+  mkdirSync(join(root, 'outside'), { recursive: true });
+
+  let hasSymlink = false;
+  try {
+    symlinkSync(join(root, 'outside'), join(base, 'link'), 'dir');
+    symlinkSync(join(base, 'shared'), join(base, 'abs'), 'dir');
+    hasSymlink = true;
+  } catch {
+    // Symlinks need extra privileges on Windows; those cases are skipped
+  }
+
+  return { root, base, hasSymlink };
+}
+
+function buildCases(base, hasSymlink) {
+  const cases = [
+    // Legal paths inside the base
+    { name: 'plain relative file', path: 'src/main.rs', violation: null },
+    { name: 'dot segments resolving inside', path: './src/../lib/x.js', violation: null },
+    { name: 'absolute path inside base', path: join(base, 'inside.txt'), violation: null },
+    { name: 'tilde in file name', path: '~backup.txt', violation: null },
+    { name: 'dollar in file name', path: 'price$1.txt', violation: null },
+    { name: 'spaces and parentheses', path: 'my file (1).txt', violation: null },
+    { name: 'unicode file name', path: 'données/résumé.md', violation: null },
+
+    // Traversal
+    { name: 'parent escape', path: '../etc/passwd', violation: 'outside_base' },
+    { name: 'nested parent escape', path: 'a/../../x', violation: 'outside_base' },
+    { name: 'absolute path outside base', path: isWindows ? 'C:\\Windows\\win.ini' : '/etc/passwd', violation: 'outside_base' },
+    { name: 'sibling with base as prefix', path: `${base}2${sep}x`, violation: 'outside_base' },
+
+    // Character checks
+    { name: 'null byte', path: 'a\0b', violation: 'null_byte' },
+    { name: 'control character', path: 'a\x07b', violation: 'control_char' },
+
+    // Length limits
+    { name: 'overlong component', path: 'x'.repeat(300), violation: 'component_too_long' },
+    { name: 'overlong path', path: 'a/'.repeat(3000), violation: 'path_too_long' },
+
+    // Policy options
+    { name: 'hidden component rejected', path: '.env', policy: { allowHidden: false }, violation: 'hidden_component' },
+    { name: 'forbidden component', path: 'x/.git/config', policy: { forbiddenComponents: ['.git'] }, violation: 'forbidden_component' },
+    { name: 'absolute path rejected', path: join(base, 'inside.txt'), policy: { allowAbsolute: false }, violation: 'absolute_path' },
+    { name: 'parent component rejected', path: 'a/../b', policy: { allowParentComponents: false }, violation: 'parent_component' },
+  ];
+
+  if (!isWindows) {
+    // Backslash is an ordinary file name character on POSIX systems
+    cases.push({ name: 'backslash in file name', path: 'back\\slash.txt', violation: null });
+  }
+
+  if (hasSymlink) {
+    cases.push(
+      { name: 'symlink leaving base', path: 'link/file', violation: 'symlink_escape' },
+      { name: 'symlink ignored without resolution', path: 'link/file', policy: { resolveSymlinks: false }, violation: null },
+    );
+  }
+
+  return cases;
+}
+
+const defaultPolicy = {
+  allowAbsolute: true,
+  allowParentComponents: true,
+  allowHidden: true,
+  allowControlChars: false,
+  resolveSymlinks: true,
+  maxPathLength: 4096,
+  maxComponentLength: 255,
+  forbiddenComponents: [],
+  windowsPaths: isWindows,
+};
+
+// Windows semantics are parsed on every host, so these run everywhere
+const windowsBase = 'C:\\Users\\Dev\\project';
+const windowsCases = [
+  { name: 'windows relative file', path: 'src\\main.rs', violation: null },
+  { name: 'windows case-insensitive containment', path: 'c:\\users\\DEV\\Project\\x.txt', violation: null },
+  { name: 'windows verbatim prefix', path: '\\\\?\\C:\\Users\\Dev\\project\\x', violation: null },
+  { name: 'windows other drive', path: 'D:\\Users\\Dev\\project\\x', violation: 'outside_base' },
+  { name: 'windows UNC share', path: '\\\\server\\share\\x', violation: 'outside_base' },
+  { name: 'windows drive-relative path', path: 'C:foo', violation: 'drive_relative' },
+  { name: 'windows device namespace', path: '\\\\.\\PhysicalDrive0', violation: 'device_path' },
+  { name: 'windows reserved name', path: 'sub\\com1.txt', violation: 'reserved_name' },
+  { name: 'windows alternate data stream', path: 'file.txt:secret', violation: 'alternate_stream' },
+];
+
+async function runAllTests() {
+  console.log('🔒 Testing Rust Path Validation');
+  console.log('================================');
+
+  const { root, base, hasSymlink } = createSandbox();
+  let failures = 0;
+
+  try {
+    for (const testCase of buildCases(base, hasSymlink)) {
+      const utils = new SecurityUtils(testCase.policy ? { ...defaultPolicy, ...testCase.policy } : undefined);
+      const result = utils.validatePath(testCase.path, base);
+      const violation = result.violation ?? null;
+      const staysInside = !result.isValid || result.sanitizedPath.startsWith(base);
+
+      if (violation === testCase.violation && result.isValid === (testCase.violation === null) && staysInside) {
+        console.log(`✅ ${testCase.name}`);
+      } else {
+        failures++;
+        console.error(`❌ ${testCase.name}: expected ${testCase.violation}, got ${violation} (${result.error ?? result.sanitizedPath})`);
+      }
+    }
+
+    if (hasSymlink) {
+      const escape = new SecurityUtils().validatePathNoSymlinkEscape('link/missing/file', base);
+      if (!escape.isValid && escape.escapingComponent === 'link') {
+        console.log('✅ per-component symlink escape reports the escaping link');
+      } else {
+        failures++;
+        console.error(`❌ per-component symlink escape: ${JSON.stringify(escape)}`);
+      }
+
+      const absolute = new SecurityUtils().validatePathNoSymlinkEscape('abs/x', base);
+      if (absolute.isValid && absolute.resolvedPath === join(base, 'shared', 'x')) {
+        console.log('✅ absolute symlink target inside the base is accepted');
+      } else {
+        failures++;
+        console.error(`❌ absolute symlink inside base: ${JSON.stringify(absolute)}`);
+      }
+    }
+
+    const inside = new SecurityUtils().validatePathNoSymlinkEscape('src/new/file.txt', base);
+    if (inside.isValid && inside.symlinksFollowed === 0) {
+      console.log('✅ per-component resolution accepts partially-existing paths');
+    } else {
+      failures++;
+      console.error(`❌ per-component resolution: ${JSON.stringify(inside)}`);
+    }
+
+    const windowsUtils = new SecurityUtils({ ...defaultPolicy, windowsPaths: true, resolveSymlinks: false });
+    for (const testCase of windowsCases) {
+      const result = windowsUtils.validatePath(testCase.path, windowsBase);
+      const violation = result.violation ?? null;
+
+      if (violation === testCase.violation) {
+        console.log(`✅ ${testCase.name}`);
+      } else {
+        failures++;
+        console.error(`❌ ${testCase.name}: expected ${testCase.violation}, got ${violation}`);
+      }
+    }
+
+    const info = analyzeWindowsPath('\\\\?\\UNC\\srv\\share\\a:stream');
+    if (info.prefixKind === 'unc' && info.verbatim && info.alternateStreams[0] === 'stream') {
+      console.log('✅ analyzeWindowsPath reports prefix and streams');
+    } else {
+      failures++;
+      console.error(`❌ analyzeWindowsPath: ${JSON.stringify(info)}`);
+    }
+  } finally {
+    rmSync(root, { recursive: true, force: true });
+  }
+
+  if (!hasSymlink) {
+    console.log('⚠️  Symlink cases skipped (symlinks unavailable)');
+  }
+
+  console.log(failures === 0 ? '\n🎉 All path validation tests passed' : `\n❌ ${failures} path validation test(s) failed`);
+  return failures === 0;
+}
+
+// Run tests if called directly
+if (import.meta.url === `file://${process.argv[1]}`) {
+  runAllTests().then(success => {
+    process.exit(success ? 0 : 1);
+  });
+}
+
+export { runAllTests };
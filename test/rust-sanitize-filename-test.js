@@ -0,0 +1,173 @@
+/**
+ * Test Rust Core Filename Sanitization - Standalone
+ *
+ * Property-based checks for SecurityUtils.sanitizeFilename: random names
+ * built from hostile fragments (separators, control and zero-width
+ * characters, device names, long Unicode runs) must always come out as a
+ * single portable component. The generator is seeded so failures replay.
+ */
+
+import { createRequire } from 'module';
+import { mkdtempSync, writeFileSync, rmSync } from 'fs';
+import { tmpdir } from 'os';
+import { join } from 'path';
+
+const require = createRequire(import.meta.url);
+const { SecurityUtils } = require('../lib/rust-core/index.node');
+
+const ITERATIONS = 2000;
+const SEED = Number(process.env.SANITIZE_SEED ?? 0x5eed);
+
+const defaultOptions = {
+  filesystem: 'posix',
+  maxLength: 255,
+  windowsSafe: true,
+  transliterate: false,
+};
+
+const FRAGMENTS = [
+  'a', 'report', 'Résumé', 'données', '日本語', 'Ελληνικά', 'emoji😀', '👨\u200D👩\u200D👧\u200D👦', 'é',
+  '.', '..', ' ', '  ', '/', '\\', ':', '*', '?', '"', '<', '>', '|',
+  '\0', '\x07', '\x1b', '\x7f', '\u0085', '\u200B', '\u200D', '\u202E', '\u2066', '\uFEFF', '\u{E0041}',
+  'CON', 'con', 'NUL', 'aux', 'COM1', 'lpt9', 'COM¹', 'CONIN$',
+  '.txt', '.tar.gz', '.md', '.', '~', '$', '-', '_', '(1)',
+];
+
+// Reserved device names, matched on the part before the first dot
+const RESERVED = /^(con|prn|aux|nul|conin\$|conout\$|com[0-9¹²³]|lpt[0-9¹²³])$/i;
+const FORBIDDEN = /[/\\:*?"<>|\u0000-\u001f\u007f-\u009f\u200B-\u200F\u202A-\u202E\u2060-\u2064\u2066-\u2069\uFEFF]/u;
+
+function mulberry32(seed) {
+  let state = seed >>> 0;
+  return () => {
+    state = (state + 0x6d2b79f5) >>> 0;
+    let t = state;
+    t = Math.imul(t ^ (t >>> 15), t | 1);
+    t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+    return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+  };
+}
+
+function randomName(random) {
+  const parts = [];
+  const count = Math.floor(random() * 12);
+  for (let i = 0; i < count; i++) {
+    const fragment = FRAGMENTS[Math.floor(random() * FRAGMENTS.length)];
+    // Occasionally repeat a fragment to push past length limits
+    parts.push(random() < 0.05 ? fragment.repeat(40 + Math.floor(random() * 100)) : fragment);
+  }
+  return parts.join('');
+}
+
+function lengthIn(filesystem, name) {
+  return filesystem === 'ntfs' ? name.length : Buffer.byteLength(name, 'utf8');
+}
+
+function isWellFormed(name) {
+  return !/[\ud800-\udbff](?![\udc00-\udfff])|(?<![\ud800-\udbff])[\udc00-\udfff]/.test(name);
+}
+
+/** Invariants every sanitized name must satisfy; returns the first violated one */
+function violatedProperty(input, output, options) {
+  if (output.length === 0) return 'non-empty';
+  if (FORBIDDEN.test(output)) return 'no separators, control or invisible characters';
+  if (/^[.\s]|[.\s]$/u.test(output)) return 'no leading or trailing dots and spaces';
+  if (lengthIn(options.filesystem, output) > options.maxLength) return 'fits the length limit';
+  if (RESERVED.test(output.split('.')[0].trimEnd())) return 'not a reserved device name';
+  if (!isWellFormed(output)) return 'well-formed Unicode';
+  if (options.transliterate && /[^\x20-\x7e]/.test(output)) return 'ASCII when transliterating';
+
+  const utils = new SecurityUtils();
+  if (utils.sanitizeFilename(output, options) !== output) return 'idempotent';
+  return null;
+}
+
+function checkProperty(name, options, random, failures) {
+  for (let i = 0; i < ITERATIONS; i++) {
+    const input = randomName(random);
+    const output = new SecurityUtils().sanitizeFilename(input, options);
+    const property = violatedProperty(input, output, options);
+    if (property) {
+      failures.push(`${name}: ${property} (input ${JSON.stringify(input)}, output ${JSON.stringify(output)})`);
+      return;
+    }
+  }
+  console.log(`✅ ${name} (${ITERATIONS} random names)`);
+}
+
+async function runAllTests() {
+  console.log('🔒 Testing Rust Filename Sanitization');
+  console.log('=====================================');
+  console.log(`Seed: ${SEED}`);
+
+  const random = mulberry32(SEED);
+  const failures = [];
+  const utils = new SecurityUtils();
+
+  checkProperty('default options', defaultOptions, random, failures);
+  checkProperty('ntfs length in UTF-16 units', { ...defaultOptions, filesystem: 'ntfs' }, random, failures);
+  checkProperty('short limit', { ...defaultOptions, maxLength: 12 }, random, failures);
+  checkProperty('transliteration', { ...defaultOptions, transliterate: true }, random, failures);
+
+  // Extensions survive truncation
+  for (let i = 0; i < 200; i++) {
+    const stem = randomName(random) + 'x'.repeat(300);
+    const output = utils.sanitizeFilename(`${stem}.txt`, defaultOptions);
+    if (!output.endsWith('.txt')) {
+      failures.push(`extension kept: ${JSON.stringify(output)}`);
+      break;
+    }
+  }
+
+  // Every result is distinct from the names already taken (case-insensitively)
+  const taken = [];
+  for (let i = 0; i < 300; i++) {
+    const input = random() < 0.5 ? 'Report.txt' : randomName(random);
+    const output = utils.sanitizeFilename(input, { ...defaultOptions, existingNames: taken });
+    if (taken.some(name => name.toLowerCase() === output.toLowerCase())) {
+      failures.push(`unique among existing names: ${JSON.stringify(output)}`);
+      break;
+    }
+    taken.push(output);
+  }
+
+  const dir = mkdtempSync(join(tmpdir(), 'moidvk-sanitize-'));
+  try {
+    writeFileSync(join(dir, 'notes.md'), '');
+    writeFileSync(join(dir, 'notes-1.md'), '');
+    const output = utils.sanitizeFilename('notes.md', { ...defaultOptions, uniqueInDirectory: dir });
+    if (output !== 'notes-2.md') failures.push(`unique in directory: ${output}`);
+  } finally {
+    rmSync(dir, { recursive: true, force: true });
+  }
+
+  const examples = [
+    ['../etc/passwd', '_etc_passwd'],
+    ['CON.txt', 'CON_.txt'],
+    ['a\u200Bb\u202Ec.md', 'abc.md'],
+    [' .hidden. ', 'hidden'],
+    ['', 'unnamed'],
+  ];
+  for (const [input, expected] of examples) {
+    const output = utils.sanitizeFilename(input);
+    if (output !== expected) failures.push(`${JSON.stringify(input)}: expected ${expected}, got ${output}`);
+  }
+
+  if (failures.length === 0) {
+    console.log('✅ extensions, uniqueness and examples');
+  } else {
+    failures.forEach(failure => console.error(`❌ ${failure}`));
+  }
+
+  console.log(failures.length === 0 ? '\n🎉 All sanitization tests passed' : `\n❌ ${failures.length} sanitization test(s) failed`);
+  return failures.length === 0;
+}
+
+// Run tests if called directly
+if (import.meta.url === `file://${process.argv[1]}`) {
+  runAllTests().then(success => {
+    process.exit(success ? 0 : 1);
+  });
+}
+
+export { runAllTests };